@@ -1,7 +1,7 @@
 //! Derive macros for rlm-core typed signatures.
 //!
 //! This crate provides the `#[derive(Signature)]` macro for automatically
-//! implementing the `Signature` trait on structs.
+//! implementing the `Signature` trait on structs and enums.
 //!
 //! # Example
 //!
@@ -29,7 +29,7 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, format_ident};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, Ident, Type,
+    parse_macro_input, Data, DataEnum, DeriveInput, Fields, Ident, Type,
     Error, spanned::Spanned, LitStr, LitBool,
 };
 
@@ -41,15 +41,30 @@ use syn::{
 ///
 /// - `#[signature(instructions = "...")]` - Required. Sets the task instructions.
 ///
+/// ## Enum-level
+///
+/// - `#[signature(tag = "...")]` - JSON key holding the variant name (default `"variant"`).
+/// - `#[input(name = "...", desc = "...")]` - Declare an input field (repeatable).
+/// - `#[input(name = "...", ty = "Option<u32>")]` - Input with an explicit type (default `String`).
+///
+/// Variants must be unit variants or have named fields; their fields are outputs
+/// and accept `#[output(desc = "...")]` and `#[field(...)]`.
+///
 /// ## Field-level
 ///
 /// - `#[input(desc = "...")]` - Mark field as input with description.
 /// - `#[input(desc = "...", prefix = "...")]` - Input with custom display prefix.
 /// - `#[output(desc = "...")]` - Mark field as output with description.
 /// - `#[output(desc = "...", prefix = "...")]` - Output with custom display prefix.
+/// - `#[output(desc = "...", aliases = "a,b")]` - Also accept keys `a` and `b` when
+///   parsing responses.
+/// - `#[field(desc = "...")]` - Set the description (for fields without `#[input]`/`#[output]`).
 /// - `#[field(required = false)]` - Mark field as optional (also inferred from `Option<T>`).
 /// - `#[field(default = "...")]` - Set default value (JSON).
 /// - `#[field(enum_values = "a,b,c")]` - Treat field as enum with explicit allowed values.
+/// - `#[field(range = "0..=100")]` - Restrict numeric values (`a..b`, `a..=b`, `a..`, `..=b`).
+/// - `#[field(pattern = "^[A-Z]{2,3}$")]` - Require string values to match a regex
+///   (checked at compile time).
 ///
 /// # Generated Code
 ///
@@ -57,6 +72,14 @@ use syn::{
 /// - `{Name}Inputs` struct with all `#[input]` fields
 /// - `{Name}Outputs` struct with all `#[output]` fields
 /// - `Signature` trait implementation
+///
+/// Both generated structs implement `FieldShape`, so they can be nested in
+/// other signatures. Fields whose type implements `FieldShape` are described
+/// as `FieldType::Object`; other unknown types become `FieldType::Custom`.
+///
+/// For enums, `{Name}Outputs` is an internally-tagged enum mirroring the
+/// variants, and `{Name}::variant_types()` returns the tagged object shape of
+/// each variant.
 #[proc_macro_derive(Signature, attributes(signature, input, output, field))]
 pub fn derive_signature(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -67,12 +90,79 @@ pub fn derive_signature(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Derive macro for implementing the `FieldShape` trait.
+///
+/// Lets a plain struct with named fields be nested inside a signature field:
+/// the `Signature` derive then describes it as `FieldType::Object` with these
+/// field specs instead of `FieldType::Custom`.
+///
+/// Fields accept `#[field(...)]`, including `#[field(desc = "...")]`.
+#[proc_macro_derive(FieldShape, attributes(field))]
+pub fn derive_field_shape(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive_field_shape_impl(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn derive_field_shape_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(
+                input.ident.span(),
+                "FieldShape can only be derived for structs with named fields"
+            )),
+        },
+        _ => return Err(Error::new(
+            input.ident.span(),
+            "FieldShape can only be derived for structs"
+        )),
+    };
+
+    let mut field_specs = Vec::new();
+    for field in fields {
+        let parsed = ParsedField {
+            name: field.ident.clone().unwrap(),
+            ty: field.ty.clone(),
+            attrs: parse_field_attrs(field)?,
+        };
+        field_specs.push(generate_field_spec(&parsed));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::rlm_core::signature::FieldShape for #name #ty_generics #where_clause {
+            fn field_specs() -> Vec<::rlm_core::signature::FieldSpec> {
+                vec![
+                    #(#field_specs),*
+                ]
+            }
+        }
+    })
+}
+
 fn derive_signature_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
+    match &input.data {
+        Data::Struct(_) => derive_struct_signature(&input),
+        Data::Enum(data) => derive_enum_signature(&input, data),
+        _ => Err(Error::new(
+            input.ident.span(),
+            "Signature can only be derived for structs or enums"
+        )),
+    }
+}
+
+fn derive_struct_signature(input: &DeriveInput) -> Result<TokenStream2, Error> {
     let name = &input.ident;
     let vis = &input.vis;
 
     // Parse struct-level attributes
-    let signature_attrs = parse_signature_attrs(&input)?;
+    let signature_attrs = parse_signature_attrs(input)?;
     let instructions = signature_attrs.instructions.ok_or_else(|| {
         Error::new(
             input.ident.span(),
@@ -89,10 +179,7 @@ fn derive_signature_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
                 "Signature can only be derived for structs with named fields"
             )),
         },
-        _ => return Err(Error::new(
-            input.ident.span(),
-            "Signature can only be derived for structs"
-        )),
+        _ => unreachable!("derive_struct_signature called on a non-struct"),
     };
 
     // Parse field attributes and separate inputs/outputs
@@ -187,6 +274,22 @@ fn derive_signature_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
             #(#output_struct_fields),*
         }
 
+        impl ::rlm_core::signature::FieldShape for #inputs_name {
+            fn field_specs() -> Vec<::rlm_core::signature::FieldSpec> {
+                vec![
+                    #(#input_field_specs),*
+                ]
+            }
+        }
+
+        impl ::rlm_core::signature::FieldShape for #outputs_name {
+            fn field_specs() -> Vec<::rlm_core::signature::FieldSpec> {
+                vec![
+                    #(#output_field_specs),*
+                ]
+            }
+        }
+
         impl ::rlm_core::signature::Signature for #name {
             type Inputs = #inputs_name;
             type Outputs = #outputs_name;
@@ -196,26 +299,267 @@ fn derive_signature_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
             }
 
             fn input_fields() -> Vec<::rlm_core::signature::FieldSpec> {
+                <#inputs_name as ::rlm_core::signature::FieldShape>::field_specs()
+            }
+
+            fn output_fields() -> Vec<::rlm_core::signature::FieldSpec> {
+                <#outputs_name as ::rlm_core::signature::FieldShape>::field_specs()
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+fn derive_enum_signature(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let signature_attrs = parse_signature_attrs(input)?;
+    let instructions = signature_attrs.instructions.ok_or_else(|| {
+        Error::new(
+            input.ident.span(),
+            "Missing #[signature(instructions = \"...\")] attribute"
+        )
+    })?;
+    let tag = signature_attrs.tag.unwrap_or_else(|| DEFAULT_ENUM_TAG.to_string());
+
+    // Inputs are declared on the enum itself since variants only describe outputs
+    let mut input_fields = Vec::new();
+    for attr in &input.attrs {
+        if attr.path().is_ident("input") {
+            input_fields.push(parse_enum_input_attr(attr)?);
+        } else if attr.path().is_ident("output") || attr.path().is_ident("field") {
+            return Err(Error::new(
+                attr.path().span(),
+                "Enum signatures describe outputs through their variants; only #[input(name = \"...\")] is allowed on the enum"
+            ));
+        }
+    }
+
+    if input_fields.is_empty() {
+        return Err(Error::new(
+            name.span(),
+            "Enum signature must declare at least one #[input(name = \"...\")] attribute"
+        ));
+    }
+    if data.variants.is_empty() {
+        return Err(Error::new(
+            name.span(),
+            "Enum signature must have at least one variant"
+        ));
+    }
+
+    // Parse variants: unit variants carry no data, struct variants carry output fields
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        let fields = match &variant.fields {
+            Fields::Unit => Vec::new(),
+            Fields::Named(named) => {
+                let mut parsed = Vec::new();
+                for field in &named.named {
+                    let field_name = field.ident.as_ref().unwrap();
+                    if field_name == tag.as_str() {
+                        return Err(Error::new(
+                            field_name.span(),
+                            format!(
+                                "Field '{}' collides with the enum tag; choose another #[signature(tag = \"...\")]",
+                                field_name
+                            )
+                        ));
+                    }
+                    let attrs = parse_field_attrs(field)?;
+                    if matches!(attrs.kind, Some(FieldKind::Input)) {
+                        return Err(Error::new(
+                            field_name.span(),
+                            format!(
+                                "Field '{}' in variant '{}' cannot be an #[input]; variant fields are outputs",
+                                field_name, variant.ident
+                            )
+                        ));
+                    }
+                    parsed.push(ParsedField {
+                        name: field_name.clone(),
+                        ty: field.ty.clone(),
+                        attrs,
+                    });
+                }
+                parsed
+            }
+            Fields::Unnamed(unnamed) => {
+                return Err(Error::new(
+                    unnamed.span(),
+                    format!(
+                        "Variant '{}' has unnamed fields; Signature enum variants must be unit or use named fields",
+                        variant.ident
+                    )
+                ));
+            }
+        };
+        variants.push((variant.ident.clone(), fields));
+    }
+
+    let inputs_name = format_ident!("{}Inputs", name);
+    let outputs_name = format_ident!("{}Outputs", name);
+
+    let input_struct_fields: Vec<_> = input_fields.iter().map(|f| {
+        let name = &f.name;
+        let ty = &f.ty;
+        quote! { pub #name: #ty }
+    }).collect();
+
+    let input_field_specs: Vec<_> = input_fields.iter().map(|f| {
+        generate_field_spec(f)
+    }).collect();
+
+    let output_variants: Vec<_> = variants.iter().map(|(ident, fields)| {
+        if fields.is_empty() {
+            quote! { #ident }
+        } else {
+            let variant_fields = fields.iter().map(|f| {
+                let name = &f.name;
+                let ty = &f.ty;
+                quote! { #name: #ty }
+            });
+            quote! { #ident { #(#variant_fields),* } }
+        }
+    }).collect();
+
+    let variant_names: Vec<_> = variants
+        .iter()
+        .map(|(ident, _)| LitStr::new(&ident.to_string(), ident.span()))
+        .collect();
+
+    // Internally-tagged JSON flattens variant fields next to the tag, so each
+    // variant's fields become optional top-level output fields. Fields shared
+    // by several variants are listed once.
+    let mut flattened: Vec<(&ParsedField, Vec<String>)> = Vec::new();
+    for (ident, fields) in &variants {
+        for field in fields {
+            match flattened.iter_mut().find(|(f, _)| f.name == field.name) {
+                Some((_, owners)) => owners.push(ident.to_string()),
+                None => flattened.push((field, vec![ident.to_string()])),
+            }
+        }
+    }
+    let variant_field_specs: Vec<_> = flattened.iter().map(|(field, owners)| {
+        let spec = generate_field_spec(field);
+        let note = format!("only when {} is {}", tag, owners.join(" or "));
+        quote! { (#note, #spec) }
+    }).collect();
+
+    let tag_desc = format!("Which variant applies: {}", variants
+        .iter()
+        .map(|(ident, _)| ident.to_string())
+        .collect::<Vec<_>>()
+        .join(", "));
+
+    // One tagged object per variant, for callers that need the full shape
+    let variant_schemas: Vec<_> = variants.iter().map(|(ident, fields)| {
+        let variant_name = ident.to_string();
+        let specs = fields.iter().map(generate_field_spec);
+        quote! {
+            (
+                #variant_name,
+                ::rlm_core::signature::FieldType::Object(vec![
+                    ::rlm_core::signature::FieldSpec::new(
+                        #tag,
+                        ::rlm_core::signature::FieldType::Enum(vec![
+                            ::std::string::String::from(#variant_name)
+                        ]),
+                    )
+                    #(, #specs)*
+                ]),
+            )
+        }
+    }).collect();
+
+    let expanded = quote! {
+        /// Input type for the #name signature.
+        #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
+        #vis struct #inputs_name {
+            #(#input_struct_fields),*
+        }
+
+        /// Output type for the #name signature.
+        #[derive(Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        #[serde(tag = #tag)]
+        #vis enum #outputs_name {
+            #(#output_variants),*
+        }
+
+        impl ::rlm_core::signature::FieldShape for #inputs_name {
+            fn field_specs() -> Vec<::rlm_core::signature::FieldSpec> {
                 vec![
                     #(#input_field_specs),*
                 ]
             }
+        }
 
-            fn output_fields() -> Vec<::rlm_core::signature::FieldSpec> {
+        impl ::rlm_core::signature::FieldShape for #outputs_name {
+            fn field_specs() -> Vec<::rlm_core::signature::FieldSpec> {
+                <#name as ::rlm_core::signature::Signature>::output_fields()
+            }
+        }
+
+        impl #name {
+            /// Tagged object shape of each variant, keyed by variant name.
+            #vis fn variant_types() -> Vec<(&'static str, ::rlm_core::signature::FieldType)> {
                 vec![
-                    #(#output_field_specs),*
+                    #(#variant_schemas),*
                 ]
             }
         }
+
+        impl ::rlm_core::signature::Signature for #name {
+            type Inputs = #inputs_name;
+            type Outputs = #outputs_name;
+
+            fn instructions() -> &'static str {
+                #instructions
+            }
+
+            fn input_fields() -> Vec<::rlm_core::signature::FieldSpec> {
+                <#inputs_name as ::rlm_core::signature::FieldShape>::field_specs()
+            }
+
+            fn output_fields() -> Vec<::rlm_core::signature::FieldSpec> {
+                let mut fields = vec![
+                    ::rlm_core::signature::FieldSpec::new(
+                        #tag,
+                        ::rlm_core::signature::FieldType::Enum(vec![
+                            #(::std::string::String::from(#variant_names)),*
+                        ]),
+                    )
+                    .with_description(#tag_desc),
+                ];
+                let variant_fields: Vec<(&str, ::rlm_core::signature::FieldSpec)> = vec![
+                    #(#variant_field_specs),*
+                ];
+                for (note, spec) in variant_fields {
+                    let description = if spec.description.is_empty() {
+                        note.to_string()
+                    } else {
+                        format!("{} ({})", spec.description, note)
+                    };
+                    fields.push(spec.with_description(description).optional());
+                }
+                fields
+            }
+        }
     };
 
     Ok(expanded)
 }
 
+/// Tag key used for enum signatures when `#[signature(tag = "...")]` is absent.
+const DEFAULT_ENUM_TAG: &str = "variant";
+
 /// Parsed struct-level signature attributes.
 #[derive(Default)]
 struct SignatureAttrs {
     instructions: Option<String>,
+    tag: Option<String>,
 }
 
 /// Parse #[signature(...)] attributes.
@@ -232,6 +576,10 @@ fn parse_signature_attrs(input: &DeriveInput) -> Result<SignatureAttrs, Error> {
                 let value: LitStr = meta.value()?.parse()?;
                 result.instructions = Some(value.value());
                 Ok(())
+            } else if meta.path.is_ident("tag") {
+                let value: LitStr = meta.value()?.parse()?;
+                result.tag = Some(value.value());
+                Ok(())
             } else {
                 Err(meta.error("unknown signature attribute"))
             }
@@ -257,6 +605,9 @@ struct FieldAttrs {
     required: Option<bool>,
     default: Option<String>,
     enum_values: Option<Vec<String>>,
+    range: Option<TokenStream2>,
+    pattern: Option<String>,
+    aliases: Option<Vec<String>>,
 }
 
 /// Parse field attributes (#[input], #[output], #[field]).
@@ -302,16 +653,77 @@ fn parse_io_attr(attr: &syn::Attribute, result: &mut FieldAttrs) -> Result<(), E
             let value: LitStr = meta.value()?.parse()?;
             result.prefix = Some(value.value());
             Ok(())
+        } else if meta.path.is_ident("aliases") {
+            let value: LitStr = meta.value()?.parse()?;
+            let parsed = value
+                .value()
+                .split(',')
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>();
+            if parsed.is_empty() {
+                return Err(meta.error("aliases cannot be empty"));
+            }
+            result.aliases = Some(parsed);
+            Ok(())
+        } else {
+            Err(meta.error("unknown attribute, expected 'desc', 'prefix', or 'aliases'"))
+        }
+    })
+}
+
+/// Parse an enum-level #[input(name = "...", ty = "...", ...)] attribute.
+///
+/// The type defaults to `String` when `ty` is omitted.
+fn parse_enum_input_attr(attr: &syn::Attribute) -> Result<ParsedField, Error> {
+    let mut name: Option<LitStr> = None;
+    let mut ty: Option<Type> = None;
+    let mut attrs = FieldAttrs {
+        kind: Some(FieldKind::Input),
+        ..FieldAttrs::default()
+    };
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("ty") {
+            let value: LitStr = meta.value()?.parse()?;
+            ty = Some(value.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("desc") {
+            let value: LitStr = meta.value()?.parse()?;
+            attrs.desc = Some(value.value());
+            Ok(())
+        } else if meta.path.is_ident("prefix") {
+            let value: LitStr = meta.value()?.parse()?;
+            attrs.prefix = Some(value.value());
+            Ok(())
         } else {
-            Err(meta.error("unknown attribute, expected 'desc' or 'prefix'"))
+            Err(meta.error("unknown attribute, expected 'name', 'ty', 'desc', or 'prefix'"))
         }
+    })?;
+
+    let name = name.ok_or_else(|| {
+        Error::new(attr.path().span(), "Enum-level #[input] requires name = \"...\"")
+    })?;
+
+    Ok(ParsedField {
+        name: name.parse()?,
+        ty: ty.unwrap_or_else(|| syn::parse_quote!(String)),
+        attrs,
     })
 }
 
 /// Parse #[field(...)] attribute.
 fn parse_field_attr(attr: &syn::Attribute, result: &mut FieldAttrs) -> Result<(), Error> {
     attr.parse_nested_meta(|meta| {
-        if meta.path.is_ident("required") {
+        if meta.path.is_ident("desc") {
+            let value: LitStr = meta.value()?.parse()?;
+            result.desc = Some(value.value());
+            Ok(())
+        } else if meta.path.is_ident("required") {
             let value: LitBool = meta.value()?.parse()?;
             result.required = Some(value.value());
             Ok(())
@@ -333,12 +745,70 @@ fn parse_field_attr(attr: &syn::Attribute, result: &mut FieldAttrs) -> Result<()
             }
             result.enum_values = Some(parsed);
             Ok(())
+        } else if meta.path.is_ident("range") {
+            let value: LitStr = meta.value()?.parse()?;
+            result.range = Some(parse_range(&value)?);
+            Ok(())
+        } else if meta.path.is_ident("pattern") {
+            let value: LitStr = meta.value()?.parse()?;
+            if let Err(e) = regex::Regex::new(&value.value()) {
+                return Err(Error::new(value.span(), format!("invalid pattern: {}", e)));
+            }
+            result.pattern = Some(value.value());
+            Ok(())
         } else {
-            Err(meta.error("unknown field attribute, expected 'required', 'default', or 'enum_values'"))
+            Err(meta.error("unknown field attribute, expected 'desc', 'required', 'default', 'enum_values', 'range', or 'pattern'"))
         }
     })
 }
 
+/// Parse a range literal like `"0..=100"`, `"0.5..1"` or `"0.."` into an
+/// `f64` range expression, rejecting malformed or empty ranges at compile time.
+fn parse_range(lit: &LitStr) -> Result<TokenStream2, Error> {
+    let expr: syn::Expr = lit.parse()?;
+    let range = match expr {
+        syn::Expr::Range(range) => range,
+        _ => return Err(Error::new(
+            lit.span(),
+            "range must be a range expression such as \"0..=100\" or \"0..\""
+        )),
+    };
+
+    let min = range.start.as_deref().map(|e| range_bound_value(e, lit)).transpose()?;
+    let max = range.end.as_deref().map(|e| range_bound_value(e, lit)).transpose()?;
+    if min.is_none() && max.is_none() {
+        return Err(Error::new(lit.span(), "range must have at least one bound"));
+    }
+    if let (Some(lo), Some(hi)) = (min, max) {
+        if lo > hi {
+            return Err(Error::new(
+                lit.span(),
+                format!("range lower bound {} exceeds upper bound {}", lo, hi)
+            ));
+        }
+    }
+
+    let min = min.map(proc_macro2::Literal::f64_suffixed);
+    let max = max.map(proc_macro2::Literal::f64_suffixed);
+    Ok(match range.limits {
+        syn::RangeLimits::HalfOpen(_) => quote! { #min..#max },
+        syn::RangeLimits::Closed(_) => quote! { #min..=#max },
+    })
+}
+
+/// Evaluate a numeric range bound (optionally negated integer or float literal).
+fn range_bound_value(expr: &syn::Expr, lit: &LitStr) -> Result<f64, Error> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(int), .. }) => int.base10_parse::<f64>(),
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Float(float), .. }) => float.base10_parse::<f64>(),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => {
+            range_bound_value(expr, lit).map(|v| -v)
+        }
+        _ => Err(Error::new(lit.span(), "range bounds must be numeric literals")),
+    }
+    .map_err(|_| Error::new(lit.span(), "range bounds must be numeric literals"))
+}
+
 /// A parsed field with its attributes.
 struct ParsedField {
     name: Ident,
@@ -378,10 +848,22 @@ fn generate_field_spec(field: &ParsedField) -> TokenStream2 {
         builder = quote! { #builder.with_prefix(#prefix) };
     }
 
+    if let Some(aliases) = &field.attrs.aliases {
+        builder = quote! { #builder.with_aliases([#(#aliases),*]) };
+    }
+
     if !required {
         builder = quote! { #builder.optional() };
     }
 
+    if let Some(range) = &field.attrs.range {
+        builder = quote! { #builder.with_range(#range) };
+    }
+
+    if let Some(pattern) = &field.attrs.pattern {
+        builder = quote! { #builder.with_pattern(#pattern) };
+    }
+
     if let Some(default) = &field.attrs.default {
         builder = quote! {
             #builder.with_default(::serde_json::json!(#default))
@@ -454,9 +936,21 @@ fn infer_field_type(ty: &Type) -> TokenStream2 {
                         }
                         quote! { ::rlm_core::signature::FieldType::String }
                     }
-                    // Custom type
+                    // Custom type: expand as an object if it implements FieldShape
                     _ => {
-                        quote! { ::rlm_core::signature::FieldType::Custom(#ident_str.to_string()) }
+                        quote! {
+                            {
+                                #[allow(unused_imports)]
+                                use ::rlm_core::signature::shape::{
+                                    ShapeViaFallback as _, ShapeViaTrait as _,
+                                };
+                                (&::rlm_core::signature::shape::ShapeProbe::<#ty>::new())
+                                    .probe_field_type()
+                                    .unwrap_or_else(|| {
+                                        ::rlm_core::signature::FieldType::Custom(#ident_str.to_string())
+                                    })
+                            }
+                        }
                     }
                 }
             } else {
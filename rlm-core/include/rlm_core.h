@@ -99,7 +99,13 @@ typedef enum {
     RLM_EVENT_MEMORY = 17,
     RLM_EVENT_EXTERNALIZE = 18,
     RLM_EVENT_DECOMPOSE = 19,
-    RLM_EVENT_SYNTHESIZE = 20
+    RLM_EVENT_SYNTHESIZE = 20,
+    RLM_EVENT_ADVERSARIAL_START = 21,
+    RLM_EVENT_CRITIC_INVOKED = 22,
+    RLM_EVENT_ISSUE_FOUND = 23,
+    RLM_EVENT_ADVERSARIAL_COMPLETE = 24,
+    RLM_EVENT_BUDGET_EXHAUSTED = 25,
+    RLM_EVENT_CYCLE_DETECTED = 26
 } RlmTrajectoryEventType;
 
 /* ============================================================================
@@ -777,6 +783,14 @@ typedef enum {
 
 typedef struct RlmOrchestratorConfig RlmOrchestratorConfig;
 typedef struct RlmOrchestratorBuilder RlmOrchestratorBuilder;
+typedef struct RlmOrchestrator RlmOrchestrator;
+
+/**
+ * Callback invoked for each trajectory event of a streaming run.
+ * The event is borrowed for the duration of the call only; copy anything
+ * you need and do not free it.
+ */
+typedef void (*RlmTrajectoryEventCallback)(const RlmTrajectoryEvent* event, void* user_data);
 
 /* ExecutionMode functions */
 
@@ -852,6 +866,16 @@ uint64_t rlm_orchestrator_config_total_token_budget(const RlmOrchestratorConfig*
  */
 double rlm_orchestrator_config_cost_budget_usd(const RlmOrchestratorConfig* config);
 
+/**
+ * Get the total recursive call limit (0 = unlimited).
+ */
+uint32_t rlm_orchestrator_config_max_total_calls(const RlmOrchestratorConfig* config);
+
+/**
+ * Get whether recursive-call cycle detection is enabled (1 = yes, 0 = no).
+ */
+int rlm_orchestrator_config_cycle_detection(const RlmOrchestratorConfig* config);
+
 /**
  * Serialize config to JSON.
  * @param config Config to serialize
@@ -905,6 +929,16 @@ RlmOrchestratorBuilder* rlm_orchestrator_builder_total_token_budget(RlmOrchestra
  */
 RlmOrchestratorBuilder* rlm_orchestrator_builder_cost_budget_usd(RlmOrchestratorBuilder* builder, double budget);
 
+/**
+ * Set the total recursive call limit. Consumes and returns new builder.
+ */
+RlmOrchestratorBuilder* rlm_orchestrator_builder_max_total_calls(RlmOrchestratorBuilder* builder, uint32_t max_calls);
+
+/**
+ * Set whether recursive-call cycle detection is enabled. Consumes and returns new builder.
+ */
+RlmOrchestratorBuilder* rlm_orchestrator_builder_cycle_detection(RlmOrchestratorBuilder* builder, int enabled);
+
 /**
  * Set the execution mode. Consumes and returns new builder.
  */
@@ -921,6 +955,40 @@ RlmOrchestratorConfig* rlm_orchestrator_builder_build(RlmOrchestratorBuilder* bu
  */
 RlmExecutionMode rlm_orchestrator_builder_get_mode(const RlmOrchestratorBuilder* builder);
 
+/* Orchestrator functions */
+
+/**
+ * Create an orchestrator that runs queries through the Claude Code adapter.
+ * The config's cost, token and depth limits become its budget.
+ * @param config Orchestrator config (not consumed)
+ * @return Orchestrator handle (must be freed with rlm_orchestrator_free), or NULL on error
+ */
+RlmOrchestrator* rlm_orchestrator_new(const RlmOrchestratorConfig* config);
+
+/**
+ * Free an orchestrator handle.
+ */
+void rlm_orchestrator_free(RlmOrchestrator* handle);
+
+/**
+ * Run a query and stream trajectory events to a callback.
+ *
+ * Only available when rlm-core is built with the `tokio-runtime` feature
+ * (enabled by default); without it this symbol is not exported.
+ *
+ * Blocks until the run finishes. The callback may be invoked from a worker
+ * thread rather than the calling thread, but never concurrently and always
+ * in event order. Do not call back into this function from the callback.
+ * Panics inside the run are caught and reported as errors.
+ *
+ * @param handle Orchestrator handle
+ * @param request JSON request: {"query": "...", "context": {...}} (context optional)
+ * @param callback Event callback (may be NULL to only count events)
+ * @param user_data Opaque pointer passed through to the callback
+ * @return Number of events delivered, or -1 on error (see rlm_last_error)
+ */
+int64_t rlm_orchestrator_run_streaming(const RlmOrchestrator* handle, const char* request, RlmTrajectoryEventCallback callback, void* user_data);
+
 /* Complexity signals functions */
 
 /**
@@ -1081,6 +1149,100 @@ char* rlm_model_spec_json(const char* model_name);
  */
 uint64_t rlm_effective_input_tokens(uint64_t input_tokens, uint64_t cache_read_tokens);
 
+/* ============================================================================
+ * SmartRouter - Model routing
+ * ============================================================================ */
+
+/** Model tier for routing decisions */
+typedef enum {
+    RLM_MODEL_TIER_FLAGSHIP = 0,
+    RLM_MODEL_TIER_BALANCED = 1,
+    RLM_MODEL_TIER_FAST = 2
+} RlmModelTier;
+
+typedef struct RlmSmartRouter RlmSmartRouter;
+typedef struct RlmRoutingDecision RlmRoutingDecision;
+
+/**
+ * Create a smart router with the default model catalog.
+ * @return Router pointer (must be freed with rlm_smart_router_free)
+ */
+RlmSmartRouter* rlm_smart_router_new(void);
+
+/**
+ * Free a smart router.
+ * @param router Router to free (may be NULL)
+ */
+void rlm_smart_router_free(RlmSmartRouter* router);
+
+/**
+ * Route a query to a model.
+ * @param router Smart router
+ * @param query Query text
+ * @param depth Current recursion depth
+ * @param budget Remaining budget in USD (negative for no constraint)
+ * @return Decision pointer (must be freed with rlm_routing_decision_free), or NULL on error
+ */
+RlmRoutingDecision* rlm_smart_router_route(const RlmSmartRouter* router, const char* query, uint32_t depth, double budget);
+
+/**
+ * Free a routing decision.
+ * @param decision Decision to free (may be NULL)
+ */
+void rlm_routing_decision_free(RlmRoutingDecision* decision);
+
+/**
+ * Get the selected model ID.
+ * @return Model ID (must be freed with rlm_string_free)
+ */
+char* rlm_routing_decision_model_id(const RlmRoutingDecision* decision);
+
+/**
+ * Get the recommended model tier.
+ */
+RlmModelTier rlm_routing_decision_tier(const RlmRoutingDecision* decision);
+
+/**
+ * Get the reasoning behind the selection.
+ * @return Reason (must be freed with rlm_string_free)
+ */
+char* rlm_routing_decision_reason(const RlmRoutingDecision* decision);
+
+/* ============================================================================
+ * Signature - Typed prompt generation
+ * ============================================================================ */
+
+/**
+ * Register a signature built from instructions and field specs.
+ * Field specs are JSON arrays of FieldSpec objects, e.g.
+ * [{"name": "code", "field_type": {"type": "string"}, "description": "...", "required": true}]
+ * @param instructions Task instructions
+ * @param input_fields_json JSON array of input field specs
+ * @param output_fields_json JSON array of output field specs
+ * @return Signature ID (release with rlm_signature_free), or -1 on error
+ */
+int64_t rlm_signature_register(const char* instructions, const char* input_fields_json, const char* output_fields_json);
+
+/**
+ * Release a registered signature.
+ * @return 0 on success, -1 if the ID is not registered
+ */
+int rlm_signature_free(int64_t id);
+
+/**
+ * Generate a prompt for a registered signature.
+ * @param id Signature ID
+ * @param inputs_json JSON object of input values keyed by field name
+ * @return Prompt (must be freed with rlm_string_free), or NULL on error
+ */
+char* rlm_signature_to_prompt(int64_t id, const char* inputs_json);
+
+/**
+ * Get the JSON schema for a registered signature's outputs.
+ * @return Schema JSON (must be freed with rlm_string_free), or NULL on error
+ */
+char* rlm_signature_output_schema(int64_t id);
+
 #ifdef __cplusplus
 }
 #endif
@@ -186,6 +186,7 @@ class Provider(IntEnum):
     Anthropic = 0
     OpenAI = 1
     OpenRouter = 2
+    Ollama = 4
 
 class ModelTier(IntEnum):
     Flagship = 0
@@ -224,6 +225,8 @@ class ModelSpec:
     def gpt4o() -> ModelSpec: ...
     @staticmethod
     def gpt4o_mini() -> ModelSpec: ...
+    @staticmethod
+    def ollama(id: str, tier: ModelTier) -> ModelSpec: ...
     @property
     def id(self) -> str: ...
     @property
@@ -246,6 +249,8 @@ class ModelSpec:
     def supports_vision(self) -> bool: ...
     @property
     def supports_tools(self) -> bool: ...
+    @property
+    def is_local(self) -> bool: ...
     def calculate_cost(self, input_tokens: int, output_tokens: int) -> float: ...
 
 class ChatMessage:
@@ -424,6 +429,192 @@ class TrajectoryEvent:
     @staticmethod
     def from_json(json: str) -> TrajectoryEvent: ...
 
+# Reasoning trace types
+
+class ReasoningTrace:
+    def __init__(self, goal: str, session_id: str = "") -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def root_id(self) -> str: ...
+    @property
+    def session_id(self) -> str: ...
+    @property
+    def node_count(self) -> int: ...
+    @property
+    def edge_count(self) -> int: ...
+    def log_decision(
+        self,
+        parent_id: str,
+        context: str,
+        options: List[str],
+        chosen_index: int,
+        reason: str,
+    ) -> str: ...
+    def log_action(
+        self, parent_id: str, action: str, outcome: str
+    ) -> tuple[str, str]: ...
+    def log_observation(self, parent_id: str, observation: str) -> str: ...
+    def to_networkx_json(self) -> Dict[str, Any]: ...
+    def to_dot(self) -> str: ...
+    def to_mermaid(self) -> str: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> ReasoningTrace: ...
+
+class TraceAnalyzer:
+    def __init__(self, trace: ReasoningTrace) -> None: ...
+    def overall_confidence(self) -> float: ...
+    def narrative(self) -> str: ...
+
+# Spec agent types
+
+class FormalizationLevel(IntEnum):
+    Types = 0
+    Invariants = 1
+    Contracts = 2
+    FullProofs = 3
+
+class CompletenessMode(IntEnum):
+    Baseline = 0
+    Placeholder = 1
+
+class QuestionCategory(IntEnum):
+    Scope = 0
+    DataTypes = 1
+    Invariants = 2
+    Behavior = 3
+    EdgeCases = 4
+    Performance = 5
+    Security = 6
+
+class Question:
+    def __init__(
+        self,
+        id: str,
+        text: str,
+        category: QuestionCategory,
+        rationale: str = "",
+        suggestions: Optional[List[str]] = None,
+        required: bool = True,
+    ) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def text(self) -> str: ...
+    @property
+    def category(self) -> QuestionCategory: ...
+    @property
+    def rationale(self) -> str: ...
+    @property
+    def suggestions(self) -> List[str]: ...
+    @property
+    def required(self) -> bool: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+    @staticmethod
+    def from_dict(value: Dict[str, Any]) -> Question: ...
+
+class Answer:
+    def __init__(
+        self, question_id: str, text: str, notes: Optional[str] = None
+    ) -> None: ...
+    @staticmethod
+    def for_question(
+        question: Question, text: str, notes: Optional[str] = None
+    ) -> Answer: ...
+    @property
+    def question_id(self) -> str: ...
+    @property
+    def text(self) -> str: ...
+    @property
+    def notes(self) -> Optional[str]: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+    @staticmethod
+    def from_dict(value: Dict[str, Any]) -> Answer: ...
+
+class SpecContext:
+    @property
+    def nl_input(self) -> str: ...
+    @property
+    def phase(self) -> str: ...
+    @property
+    def requirements(self) -> List[Dict[str, Any]]: ...
+    @property
+    def questions(self) -> List[Question]: ...
+    @property
+    def answers(self) -> List[Answer]: ...
+    @property
+    def ambiguities(self) -> List[Dict[str, Any]]: ...
+    @property
+    def topos_spec(self) -> Optional[str]: ...
+    @property
+    def lean_spec(self) -> Optional[str]: ...
+    def unanswered_questions(self) -> List[Question]: ...
+    def all_required_answered(self) -> bool: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+    @staticmethod
+    def from_dict(value: Dict[str, Any]) -> SpecContext: ...
+
+class FormalizationResult:
+    @property
+    def topos_content(self) -> str: ...
+    @property
+    def topos_filename(self) -> str: ...
+    @property
+    def lean_content(self) -> str: ...
+    @property
+    def lean_filename(self) -> str: ...
+    @property
+    def cross_refs(self) -> List[Dict[str, Any]]: ...
+    @property
+    def warnings(self) -> List[str]: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+
+class SpecVerificationResult:
+    @property
+    def passed(self) -> bool: ...
+    @property
+    def lean_type_check_ok(self) -> bool: ...
+    @property
+    def lean_errors(self) -> List[str]: ...
+    @property
+    def topos_valid(self) -> bool: ...
+    @property
+    def topos_errors(self) -> List[str]: ...
+    @property
+    def proof_results(self) -> List[Dict[str, Any]]: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+
+class WorkflowResult:
+    @property
+    def context(self) -> SpecContext: ...
+    @property
+    def formalization(self) -> FormalizationResult: ...
+    @property
+    def verification(self) -> SpecVerificationResult: ...
+    def success(self) -> bool: ...
+    def warnings(self) -> List[str]: ...
+    def errors(self) -> List[str]: ...
+
+class SpecAgent:
+    def __init__(
+        self,
+        level: Optional[FormalizationLevel] = None,
+        completeness_mode: Optional[CompletenessMode] = None,
+        validate_with_lean: bool = True,
+        validate_with_topos: bool = True,
+        spec_name: Optional[str] = None,
+    ) -> None: ...
+    async def intake(self, nl_input: str) -> SpecContext: ...
+    async def refine(
+        self, ctx: SpecContext, answers: Optional[List[Answer]] = None
+    ) -> List[Question]: ...
+    async def formalize(self, ctx: SpecContext) -> FormalizationResult: ...
+    async def verify(
+        self, formalization: FormalizationResult
+    ) -> SpecVerificationResult: ...
+    async def run_workflow(self, nl_input: str) -> WorkflowResult: ...
+
 # Complexity types
 
 class ActivationDecision:
@@ -151,6 +151,12 @@ class ReplServer:
 
         execution_time_ms = (time.perf_counter() - start_time) * 1000
 
+        truncated = False
+        if req.max_output_bytes is not None:
+            stdout, stdout_cut = _truncate_bytes(stdout, req.max_output_bytes)
+            stderr, stderr_cut = _truncate_bytes(stderr, req.max_output_bytes)
+            truncated = stdout_cut or stderr_cut
+
         return ExecuteResponse(
             success=success,
             result=_serialize_result(result),
@@ -161,6 +167,7 @@ class ReplServer:
             execution_time_ms=execution_time_ms,
             pending_operations=all_pending,
             submit_result=submit_result,
+            truncated=truncated,
         ).model_dump()
 
     def _get_variable(self, params: dict[str, Any]) -> Any:
@@ -343,6 +350,14 @@ def _serialize_result(value: Any) -> Any:
     return str(value)
 
 
+def _truncate_bytes(text: str, max_bytes: int) -> tuple[str, bool]:
+    """Cut text to at most max_bytes of UTF-8 without splitting a character."""
+    encoded = text.encode("utf-8")
+    if len(encoded) <= max_bytes:
+        return text, False
+    return encoded[:max_bytes].decode("utf-8", errors="ignore"), True
+
+
 def main() -> None:
     """Main entry point."""
     server = ReplServer()
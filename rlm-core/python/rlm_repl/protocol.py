@@ -109,6 +109,9 @@ class ExecuteRequest(BaseModel):
     code: str = Field(..., description="Python code to execute")
     timeout_ms: int = Field(default=30000, description="Execution timeout in milliseconds")
     capture_output: bool = Field(default=True, description="Whether to capture stdout/stderr")
+    max_output_bytes: int | None = Field(
+        default=None, description="Maximum bytes kept from each of stdout/stderr"
+    )
 
 
 class ExecuteResponse(BaseModel):
@@ -130,6 +133,9 @@ class ExecuteResponse(BaseModel):
             "Result of SUBMIT call if execution used typed-signature submission"
         ),
     )
+    truncated: bool = Field(
+        default=False, description="Whether stdout/stderr were cut at max_output_bytes"
+    )
 
 
 class GetVariableRequest(BaseModel):
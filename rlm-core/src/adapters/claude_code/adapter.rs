@@ -12,7 +12,7 @@ use super::hooks::{
 };
 use super::mcp::{
     McpToolRegistry, MemoryQueryInput, MemoryStoreInput, RlmExecuteInput, RlmStatusInput,
-    ToolHandler,
+    ToolHandler, DEFAULT_MEMORY_QUERY_LIMIT, MAX_MEMORY_QUERY_LIMIT,
 };
 use super::skills::{RlmSkill, SkillRegistry};
 use super::types::{
@@ -22,9 +22,9 @@ use super::types::{
 use crate::complexity::PatternClassifier;
 use crate::context::ExternalizedContext;
 use crate::error::{Error, Result};
-use crate::llm::TokenUsage as LlmTokenUsage;
+use crate::llm::{CostTracker, LLMClient, TokenUsage as LlmTokenUsage};
 use crate::memory::{Node, NodeId, NodeQuery, NodeType, SqliteMemoryStore, Tier};
-use crate::orchestrator::{ExecutionMode, OrchestrationRoutingRuntime};
+use crate::orchestrator::{ExecutionMode, OrchestrationRoutingRuntime, OrchestratorBuilder};
 use crate::repl::{ReplConfig, ReplHandle};
 use crate::signature::{FieldSpec, FieldType, SubmitResult};
 use crate::trajectory::{
@@ -63,6 +63,8 @@ pub struct ClaudeCodeAdapter {
     status: Arc<RwLock<AdapterStatus>>,
     /// Whether currently executing
     executing: Arc<RwLock<bool>>,
+    /// Tiered costs of every routed call so far, carried between requests
+    routing_costs: Arc<RwLock<CostTracker>>,
 }
 
 #[derive(Clone)]
@@ -74,9 +76,11 @@ struct AdapterRuntime {
     mode: Arc<RwLock<ExecutionMode>>,
     status: Arc<RwLock<AdapterStatus>>,
     executing: Arc<RwLock<bool>>,
+    routing_costs: Arc<RwLock<CostTracker>>,
 }
 
 impl AdapterRuntime {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         config: AdapterConfig,
         memory: Arc<SqliteMemoryStore>,
@@ -85,6 +89,7 @@ impl AdapterRuntime {
         mode: Arc<RwLock<ExecutionMode>>,
         status: Arc<RwLock<AdapterStatus>>,
         executing: Arc<RwLock<bool>>,
+        routing_costs: Arc<RwLock<CostTracker>>,
     ) -> Self {
         Self {
             config,
@@ -94,10 +99,21 @@ impl AdapterRuntime {
             mode,
             status,
             executing,
+            routing_costs,
         }
     }
 
     fn execute_request(&self, request: RlmRequest) -> Result<RlmResponse> {
+        let session_ctx = build_session_context(&request.context);
+        self.execute_in_context(request, session_ctx)
+    }
+
+    /// Execute a request against an already-built session context.
+    fn execute_in_context(
+        &self,
+        request: RlmRequest,
+        session_ctx: crate::context::SessionContext,
+    ) -> Result<RlmResponse> {
         {
             let mut executing = self
                 .executing
@@ -126,7 +142,6 @@ impl AdapterRuntime {
         let mode = request
             .mode
             .unwrap_or_else(|| *self.mode.read().unwrap_or_else(|_| panic!("Lock poisoned")));
-        let session_ctx = build_session_context(&request.context);
         let decision = self
             .classifier
             .should_activate(&request.query, &session_ctx);
@@ -159,6 +174,24 @@ impl AdapterRuntime {
             }
         }
 
+        // The runtime checks the budget before each model call it routes,
+        // projecting from the costs of earlier requests as well as this one.
+        let routing_costs = self
+            .routing_costs
+            .read()
+            .map_err(|_| Error::Internal("Lock error".into()))?
+            .clone();
+        let budget_usd = request
+            .max_budget_usd
+            .unwrap_or_else(|| routing_costs.total_cost + final_mode.typical_budget_usd());
+        let orchestrator_config = OrchestratorBuilder::new()
+            .execution_mode(final_mode)
+            .cost_budget_usd(budget_usd)
+            .build_config();
+        let mut routing_runtime = OrchestrationRoutingRuntime::from_config(&orchestrator_config)
+            .with_cost_tracker(routing_costs);
+        let (routing_decision, tier) = routing_runtime.route_recursive(&request.query, 0)?;
+
         let start_time = Instant::now();
         let externalized = ExternalizedContext::from_session(&session_ctx, &request.query);
         let root_prompt = externalized.root_prompt();
@@ -172,8 +205,6 @@ impl AdapterRuntime {
             &memory_hits,
         )?;
 
-        let mut routing_runtime = OrchestrationRoutingRuntime::for_mode(final_mode);
-        let (routing_decision, tier) = routing_runtime.route_recursive(&request.query, 0);
         let usage = LlmTokenUsage {
             input_tokens: estimate_tokens(&root_prompt)
                 + estimate_tokens(&request.query)
@@ -186,6 +217,11 @@ impl AdapterRuntime {
             .model
             .calculate_cost(usage.input_tokens, usage.output_tokens);
         routing_runtime.record_usage(&routing_decision, &usage, Some(cost), tier);
+        *self
+            .routing_costs
+            .write()
+            .map_err(|_| Error::Internal("Lock error".into()))? =
+            routing_runtime.cost_tracker().clone();
         self.budget.record_cost(cost, usage.total());
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
@@ -337,12 +373,22 @@ SUBMIT({"answer": answer})
         if let Some(min_confidence) = input.min_confidence {
             query = query.min_confidence(min_confidence);
         }
-        query = query.limit(input.limit.unwrap_or(10));
-
-        let nodes = self.memory.query_nodes(&query)?;
+        let limit = input
+            .limit
+            .unwrap_or(DEFAULT_MEMORY_QUERY_LIMIT)
+            .clamp(1, MAX_MEMORY_QUERY_LIMIT);
+        // Fetch one extra node to tell whether the result was cut off
+        query = query.limit(limit + 1);
+
+        let mut nodes = self.memory.query_nodes(&query)?;
+        let truncated = nodes.len() > limit;
+        nodes.truncate(limit);
+
+        let nodes: Vec<Value> = nodes.iter().map(memory_node_json).collect();
         Ok(serde_json::json!({
+            "total_count": nodes.len(),
             "nodes": nodes,
-            "total_count": nodes.len()
+            "truncated": truncated
         }))
     }
 
@@ -395,7 +441,9 @@ impl ClaudeCodeAdapter {
         let mut hooks = HookRegistry::new();
         hooks.register(Box::new(SessionStartHandler::new()));
         hooks.register(Box::new(PromptAnalysisHandler::new()));
-        hooks.register(Box::new(PreCompactHandler::new()));
+        hooks.register(Box::new(
+            PreCompactHandler::new().with_memory(memory.clone()),
+        ));
 
         let skills = SkillRegistry::with_defaults();
         let classifier = PatternClassifier::with_threshold(config.escalation_threshold);
@@ -403,6 +451,7 @@ impl ClaudeCodeAdapter {
         let mode = Arc::new(RwLock::new(status.mode));
         let status = Arc::new(RwLock::new(status));
         let executing = Arc::new(RwLock::new(false));
+        let routing_costs = Arc::new(RwLock::new(CostTracker::new()));
 
         let runtime = AdapterRuntime::new(
             config.clone(),
@@ -412,6 +461,7 @@ impl ClaudeCodeAdapter {
             mode.clone(),
             status.clone(),
             executing.clone(),
+            routing_costs.clone(),
         );
         let mut tools = McpToolRegistry::with_defaults();
         Self::bind_live_mcp_handlers(&mut tools, runtime)?;
@@ -428,6 +478,7 @@ impl ClaudeCodeAdapter {
             mode,
             status,
             executing,
+            routing_costs,
         })
     }
 
@@ -450,6 +501,16 @@ impl ClaudeCodeAdapter {
         self.runtime().execute_request(request)
     }
 
+    /// Execute a request against a session context built by the caller,
+    /// ignoring `request.context`.
+    pub(super) fn execute_with_session(
+        &self,
+        request: RlmRequest,
+        session_ctx: crate::context::SessionContext,
+    ) -> Result<RlmResponse> {
+        self.runtime().execute_in_context(request, session_ctx)
+    }
+
     /// Get current adapter status.
     pub fn status(&self) -> AdapterStatus {
         self.runtime().status()
@@ -504,11 +565,22 @@ impl ClaudeCodeAdapter {
 
     /// Handle pre-compact event.
     pub async fn handle_pre_compact(&self, context: SessionContext) -> Result<CompactData> {
+        self.handle_pre_compact_messages(context, Vec::new()).await
+    }
+
+    /// Handle pre-compact event, externalizing the messages about to be
+    /// dropped into memory.
+    pub async fn handle_pre_compact_messages(
+        &self,
+        context: SessionContext,
+        messages: Vec<String>,
+    ) -> Result<CompactData> {
         let hook_ctx =
             HookContext::new(HookTrigger::PreCompact, context).with_data(HookData::Compact {
                 context_tokens: 100_000,
                 max_tokens: 200_000,
-                messages_to_remove: 10,
+                messages_to_remove: messages.len(),
+                messages,
             });
 
         let hooks = self
@@ -537,12 +609,30 @@ impl ClaudeCodeAdapter {
         Ok(())
     }
 
+    /// Summarize context with `client` at compaction, gating the extracted
+    /// facts, instead of storing raw message excerpts.
+    pub fn set_compaction_client(&self, client: Arc<dyn LLMClient>) -> Result<()> {
+        let handler = PreCompactHandler::new()
+            .with_memory(self.memory.clone())
+            .with_llm(client);
+        let mut hooks = self
+            .hooks
+            .write()
+            .map_err(|_| Error::Internal("Lock error".into()))?;
+        hooks.unregister(handler.name());
+        hooks.register(Box::new(handler));
+        Ok(())
+    }
+
     // =========================================================================
     // MCP Tool Operations
     // =========================================================================
 
-    /// Execute an MCP tool by name.
-    pub fn execute_tool(&self, name: &str, input: serde_json::Value) -> Result<serde_json::Value> {
+    /// Execute an MCP tool by name without firing hooks.
+    ///
+    /// Private so callers can't skip PreToolUse/PostToolUse handlers; use
+    /// [`execute_tool_with_hooks`](Self::execute_tool_with_hooks).
+    fn execute_tool(&self, name: &str, input: serde_json::Value) -> Result<serde_json::Value> {
         let tools = self
             .tools
             .read()
@@ -550,6 +640,67 @@ impl ClaudeCodeAdapter {
         tools.execute(name, input)
     }
 
+    /// Execute an MCP tool by name, firing PreToolUse and PostToolUse hooks.
+    ///
+    /// This is the only public way to run a registered tool.
+    ///
+    /// A PreToolUse handler that aborts blocks the call with
+    /// [`Error::ToolBlocked`] and the tool never runs. One that returns
+    /// [`HookResultData::ModifiedToolArgs`] replaces the input before the
+    /// tool sees it.
+    pub async fn execute_tool_with_hooks(
+        &self,
+        name: &str,
+        input: Value,
+        context: SessionContext,
+    ) -> Result<Value> {
+        let hook_ctx = HookContext::new(HookTrigger::PreToolUse, context.clone()).with_data(
+            HookData::ToolUse {
+                tool_name: name.to_string(),
+                arguments: input.clone(),
+            },
+        );
+        // Snapshot the registry so no lock is held across the awaits
+        let hooks = self
+            .hooks
+            .read()
+            .map_err(|_| Error::Internal("Lock error".into()))?
+            .clone();
+        let results = hooks.execute(hook_ctx).await?;
+
+        let mut input = input;
+        for result in results {
+            if result.abort {
+                return Err(Error::ToolBlocked {
+                    tool: name.to_string(),
+                    reason: result
+                        .abort_reason
+                        .unwrap_or_else(|| "no reason given".to_string()),
+                });
+            }
+            if let HookResultData::ModifiedToolArgs(arguments) = result.data {
+                input = arguments;
+            }
+        }
+
+        let output = self.execute_tool(name, input);
+
+        let (output_text, success) = match &output {
+            Ok(value) => (value.to_string(), true),
+            Err(e) => (e.to_string(), false),
+        };
+        let hook_ctx =
+            HookContext::new(HookTrigger::PostToolUse, context).with_data(HookData::ToolResult {
+                tool_name: name.to_string(),
+                output: output_text,
+                exit_code: None,
+                success,
+            });
+        hooks.execute(hook_ctx).await?;
+
+        output
+    }
+
     /// Get all available tools.
     pub fn available_tools(&self) -> Vec<String> {
         let tools = self
@@ -677,6 +828,7 @@ impl ClaudeCodeAdapter {
             self.mode.clone(),
             self.status.clone(),
             self.executing.clone(),
+            self.routing_costs.clone(),
         )
     }
 
@@ -874,6 +1026,17 @@ fn extract_answer(exec: crate::repl::ExecuteResult) -> Result<String> {
     }
 }
 
+/// A node as it appears in memory_query results.
+fn memory_node_json(node: &Node) -> Value {
+    serde_json::json!({
+        "id": node.id.to_string(),
+        "node_type": node.node_type.to_string(),
+        "content": node.content,
+        "confidence": node.confidence,
+        "tier": node.tier.to_string()
+    })
+}
+
 fn estimate_tokens(text: &str) -> u64 {
     ((text.chars().count() as u64).saturating_add(3) / 4).max(1)
 }
@@ -1168,6 +1331,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_memory_query_structured_result() {
+        let adapter = ClaudeCodeAdapter::testing().unwrap();
+        let result_type = super::super::mcp::memory_query_result_type();
+        let schema = adapter.export_tools_schema()["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|tool| tool["name"] == "memory_query")
+            .map(|tool| tool["outputSchema"].clone())
+            .unwrap();
+        assert_eq!(schema, result_type.to_json_schema());
+
+        let empty = adapter
+            .execute_tool("memory_query", serde_json::json!({"text": "nothing here"}))
+            .unwrap();
+        crate::signature::validate_value(&empty, &result_type, "result").unwrap();
+        assert_eq!(empty["nodes"], serde_json::json!([]));
+        assert_eq!(empty["total_count"], 0);
+        assert_eq!(empty["truncated"], false);
+
+        for i in 0..3 {
+            adapter
+                .store_fact(&format!("Cache layer {} uses Redis", i), 0.8)
+                .unwrap();
+        }
+        let result = adapter
+            .execute_tool(
+                "memory_query",
+                serde_json::json!({"text": "Redis", "limit": 2}),
+            )
+            .unwrap();
+        crate::signature::validate_value(&result, &result_type, "result").unwrap();
+        assert_eq!(result["total_count"], 2);
+        assert_eq!(result["truncated"], true);
+
+        let properties = schema["properties"]["nodes"]["items"]["properties"]
+            .as_object()
+            .unwrap();
+        for node in result["nodes"].as_array().unwrap() {
+            let node = node.as_object().unwrap();
+            assert_eq!(
+                node.keys().collect::<Vec<_>>(),
+                properties.keys().collect::<Vec<_>>()
+            );
+            assert_eq!(node["node_type"], "fact");
+            assert!(node["content"].as_str().unwrap().contains("Redis"));
+        }
+    }
+
     #[test]
     fn test_execute_tool_rlm_status_live() {
         let adapter = ClaudeCodeAdapter::testing().unwrap();
@@ -1220,6 +1433,98 @@ mod tests {
         assert!(data.work_summary.is_some());
     }
 
+    struct ToolGuard {
+        trigger: HookTrigger,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl HookHandler for ToolGuard {
+        fn name(&self) -> &str {
+            "tool_guard"
+        }
+
+        fn trigger(&self) -> HookTrigger {
+            self.trigger
+        }
+
+        async fn execute(&self, context: HookContext) -> Result<HookResult> {
+            match context.data {
+                HookData::ToolUse {
+                    tool_name,
+                    arguments,
+                } => {
+                    self.calls.lock().unwrap().push(tool_name.clone());
+                    if tool_name == "memory_store" {
+                        Ok(HookResult::abort("writes are disabled"))
+                    } else {
+                        let mut arguments = arguments;
+                        arguments["limit"] = serde_json::json!(1);
+                        Ok(HookResult::modify_tool_args(arguments))
+                    }
+                }
+                HookData::ToolResult { tool_name, .. } => {
+                    self.calls
+                        .lock()
+                        .unwrap()
+                        .push(format!("post:{}", tool_name));
+                    Ok(HookResult::ok())
+                }
+                _ => Ok(HookResult::ok()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_hooks_block_and_rewrite() {
+        let adapter = ClaudeCodeAdapter::testing().unwrap();
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for trigger in [HookTrigger::PreToolUse, HookTrigger::PostToolUse] {
+            adapter
+                .register_hook(Box::new(ToolGuard {
+                    trigger,
+                    calls: calls.clone(),
+                }))
+                .unwrap();
+        }
+        adapter.store_fact("Hooks guard tool calls", 0.9).unwrap();
+        adapter.store_fact("Hooks rewrite tool calls", 0.9).unwrap();
+        let session = SessionContext::new("tool-hooks");
+
+        let err = adapter
+            .execute_tool_with_hooks(
+                "memory_store",
+                serde_json::json!({"content": "should not be stored"}),
+                session.clone(),
+            )
+            .await
+            .expect_err("blocking hook should prevent the call");
+        assert!(matches!(
+            err,
+            Error::ToolBlocked { ref tool, ref reason }
+                if tool == "memory_store" && reason == "writes are disabled"
+        ));
+        assert!(adapter
+            .search_memory("should not be stored", 10)
+            .unwrap()
+            .is_empty());
+
+        let query = adapter
+            .execute_tool_with_hooks(
+                "memory_query",
+                serde_json::json!({"text": "Hooks", "limit": 10}),
+                session,
+            )
+            .await
+            .unwrap();
+        assert_eq!(query.get("total_count").and_then(Value::as_u64), Some(1));
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["memory_store", "memory_query", "post:memory_query"]
+        );
+    }
+
     #[test]
     fn test_export_tools_schema() {
         let adapter = ClaudeCodeAdapter::testing().unwrap();
@@ -9,11 +9,16 @@
 //! - **PostToolUse**: Process tool results
 
 use super::types::{CompactData, PromptEnhancement, SessionContext};
+use crate::epistemic::{EpistemicVerifier, HaikuVerifier, MemoryGate, MemoryGateConfig};
 use crate::error::Result;
+use crate::llm::{ChatMessage, CompletionRequest, LLMClient};
+use crate::memory::{Node, NodeType, Provenance, ProvenanceSource, SqliteMemoryStore, Tier};
 use async_trait::async_trait;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// When a hook should be triggered.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -122,6 +127,9 @@ pub enum HookData {
         max_tokens: u64,
         /// Messages that will be removed
         messages_to_remove: usize,
+        /// Content of the messages that will be removed, when available
+        #[serde(default)]
+        messages: Vec<String>,
     },
 }
 
@@ -191,6 +199,11 @@ impl HookResult {
         }
     }
 
+    /// Create a result that rewrites the tool arguments (for PreToolUse hooks).
+    pub fn modify_tool_args(arguments: Value) -> Self {
+        Self::ok().with_data(HookResultData::ModifiedToolArgs(arguments))
+    }
+
     /// Set additional context.
     pub fn with_context(mut self, context: impl Into<String>) -> Self {
         self.additional_context = Some(context.into());
@@ -237,8 +250,11 @@ pub trait HookHandler: Send + Sync {
 }
 
 /// Registry of hook handlers.
+///
+/// Cloning is cheap and clones share the registered handlers.
+#[derive(Clone)]
 pub struct HookRegistry {
-    handlers: Vec<Box<dyn HookHandler>>,
+    handlers: Vec<Arc<dyn HookHandler>>,
 }
 
 impl Default for HookRegistry {
@@ -257,11 +273,18 @@ impl HookRegistry {
 
     /// Register a hook handler.
     pub fn register(&mut self, handler: Box<dyn HookHandler>) {
-        self.handlers.push(handler);
+        self.handlers.push(Arc::from(handler));
         // Sort by priority (lower priority number = higher priority)
         self.handlers.sort_by_key(|h| h.priority());
     }
 
+    /// Remove all handlers with the given name, returning how many were removed.
+    pub fn unregister(&mut self, name: &str) -> usize {
+        let before = self.handlers.len();
+        self.handlers.retain(|h| h.name() != name);
+        before - self.handlers.len()
+    }
+
     /// Get all handlers for a specific trigger.
     pub fn handlers_for(&self, trigger: HookTrigger) -> Vec<&dyn HookHandler> {
         self.handlers
@@ -272,13 +295,24 @@ impl HookRegistry {
     }
 
     /// Execute all handlers for a trigger.
-    pub async fn execute(&self, context: HookContext) -> Result<Vec<HookResult>> {
+    ///
+    /// For tool-use hooks, arguments rewritten by one handler are passed on
+    /// to the next.
+    pub async fn execute(&self, mut context: HookContext) -> Result<Vec<HookResult>> {
         let handlers = self.handlers_for(context.trigger);
         let mut results = Vec::with_capacity(handlers.len());
 
         for handler in handlers {
             let result = handler.execute(context.clone()).await?;
 
+            if let (
+                HookData::ToolUse { arguments, .. },
+                HookResultData::ModifiedToolArgs(modified),
+            ) = (&mut context.data, &result.data)
+            {
+                *arguments = modified.clone();
+            }
+
             // If any handler requests abort, stop processing
             if result.abort {
                 results.push(result);
@@ -409,16 +443,137 @@ impl HookHandler for PromptAnalysisHandler {
     }
 }
 
-/// Handler for pre-compact - extracts important data before compaction.
+/// Maximum facts kept from one compaction summary.
+const MAX_COMPACT_FACTS: usize = 20;
+/// Maximum characters kept from each message when storing raw excerpts.
+const MAX_EXCERPT_CHARS: usize = 500;
+
+const COMPACT_SUMMARY_PROMPT: &str = "The following conversation messages are about to be \
+     dropped from context. Extract the durable facts worth remembering later: decisions made, \
+     constraints discovered, and concrete details about the code or environment. Write one \
+     self-contained fact per line with no numbering. Reply with NONE if there is nothing \
+     worth keeping.";
+
+/// Handler for pre-compact - externalizes context into memory before compaction.
+///
+/// With a memory store attached, the messages about to be dropped become
+/// durable nodes. If an LLM client is configured it summarizes them into
+/// facts, and each fact must pass a [`MemoryGate`] before it is stored.
+/// Without a client, raw message excerpts are stored as snippets.
 pub struct PreCompactHandler {
     name: String,
+    memory: Option<Arc<SqliteMemoryStore>>,
+    llm: Option<Arc<dyn LLMClient>>,
+    verifier: Option<Arc<dyn EpistemicVerifier>>,
+    gate_config: MemoryGateConfig,
 }
 
 impl PreCompactHandler {
     pub fn new() -> Self {
         Self {
             name: "pre_compact_handler".to_string(),
+            memory: None,
+            llm: None,
+            verifier: None,
+            gate_config: MemoryGateConfig::default(),
+        }
+    }
+
+    /// Store externalized context in this memory store.
+    pub fn with_memory(mut self, memory: Arc<SqliteMemoryStore>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Summarize dropped messages with this client.
+    ///
+    /// Facts are verified with a [`HaikuVerifier`] on the same client unless
+    /// [`with_verifier`](Self::with_verifier) supplies another.
+    pub fn with_llm(mut self, client: Arc<dyn LLMClient>) -> Self {
+        self.llm = Some(client);
+        self
+    }
+
+    /// Verify summarized facts with this verifier.
+    pub fn with_verifier(mut self, verifier: Arc<dyn EpistemicVerifier>) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Set the gate configuration for summarized facts.
+    pub fn with_gate_config(mut self, config: MemoryGateConfig) -> Self {
+        self.gate_config = config;
+        self
+    }
+
+    /// Ask the LLM for the durable facts in `messages`.
+    async fn summarize(&self, client: &dyn LLMClient, messages: &[String]) -> Result<Vec<String>> {
+        let request = CompletionRequest::new()
+            .with_system(COMPACT_SUMMARY_PROMPT)
+            .with_message(ChatMessage::user(messages.join("\n\n")))
+            .with_temperature(0.0);
+        let response = client.complete(request).await?;
+
+        Ok(response
+            .content
+            .lines()
+            .map(|line| line.trim().trim_start_matches(['-', '*', '•']).trim())
+            .filter(|line| !line.is_empty() && !line.eq_ignore_ascii_case("none"))
+            .take(MAX_COMPACT_FACTS)
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Gate and store summarized facts. Returns the stored nodes and the
+    /// number of facts the gate rejected.
+    async fn externalize_facts(
+        &self,
+        memory: &SqliteMemoryStore,
+        client: Arc<dyn LLMClient>,
+        facts: Vec<String>,
+        messages: &[String],
+        session_id: &str,
+    ) -> Result<(Vec<Node>, usize)> {
+        let verifier = self
+            .verifier
+            .clone()
+            .unwrap_or_else(|| Arc::new(HaikuVerifier::new(client)));
+        let gate = MemoryGate::new(verifier, self.gate_config.clone());
+        let context = messages.join("\n\n");
+
+        let mut stored = Vec::new();
+        let mut rejected = 0;
+        for fact in facts {
+            let mut node = compact_node(NodeType::Fact, fact, session_id);
+            let admitted = match gate.evaluate(&node, &context).await {
+                Ok(decision) => decision.apply(&mut node),
+                Err(_) => false,
+            };
+            if admitted {
+                memory.add_node(&node)?;
+                stored.push(node);
+            } else {
+                rejected += 1;
+            }
+        }
+        Ok((stored, rejected))
+    }
+
+    /// Store each message, truncated, as a snippet.
+    fn externalize_excerpts(
+        &self,
+        memory: &SqliteMemoryStore,
+        messages: &[String],
+        session_id: &str,
+    ) -> Result<Vec<Node>> {
+        let mut stored = Vec::new();
+        for message in messages.iter().map(|m| m.trim()).filter(|m| !m.is_empty()) {
+            let excerpt: String = message.chars().take(MAX_EXCERPT_CHARS).collect();
+            let node = compact_node(NodeType::Snippet, excerpt, session_id);
+            memory.add_node(&node)?;
+            stored.push(node);
         }
+        Ok(stored)
     }
 }
 
@@ -428,6 +583,19 @@ impl Default for PreCompactHandler {
     }
 }
 
+/// A session-tier node recording that it was externalized at compaction.
+fn compact_node(node_type: NodeType, content: String, session_id: &str) -> Node {
+    Node::new(node_type, content)
+        .with_tier(Tier::Session)
+        .with_provenance(Provenance {
+            source_type: ProvenanceSource::Consolidation,
+            source_ref: Some(session_id.to_string()),
+            observed_at: Utc::now(),
+            context: None,
+        })
+        .with_metadata("source", "pre_compact")
+}
+
 #[async_trait]
 impl HookHandler for PreCompactHandler {
     fn name(&self) -> &str {
@@ -439,10 +607,40 @@ impl HookHandler for PreCompactHandler {
     }
 
     async fn execute(&self, context: HookContext) -> Result<HookResult> {
-        let compact_data = CompactData::new().with_summary(format!(
-            "Session {} context compacted",
-            context.session.session_id
-        ));
+        let session_id = &context.session.session_id;
+        let mut compact_data =
+            CompactData::new().with_summary(format!("Session {} context compacted", session_id));
+
+        let messages = match &context.data {
+            HookData::Compact { messages, .. } => messages.as_slice(),
+            _ => &[],
+        };
+        let Some(memory) = self.memory.as_deref().filter(|_| !messages.is_empty()) else {
+            return Ok(HookResult::ok().with_data(HookResultData::CompactData(compact_data)));
+        };
+
+        let summary = match &self.llm {
+            Some(client) => self.summarize(client.as_ref(), messages).await.ok(),
+            None => None,
+        };
+        let (stored, rejected) = match (summary, &self.llm) {
+            (Some(facts), Some(client)) => {
+                self.externalize_facts(memory, client.clone(), facts, messages, session_id)
+                    .await?
+            }
+            // No LLM, or summarization failed: keep the raw text instead
+            _ => (self.externalize_excerpts(memory, messages, session_id)?, 0),
+        };
+
+        for node in &stored {
+            if node.node_type == NodeType::Fact {
+                compact_data = compact_data.with_fact(node.content.clone());
+            }
+        }
+        let ids: Vec<String> = stored.iter().map(|n| n.id.to_string()).collect();
+        compact_data = compact_data
+            .with_state("externalized_nodes", ids)
+            .with_state("rejected_facts", rejected);
 
         Ok(HookResult::ok().with_data(HookResultData::CompactData(compact_data)))
     }
@@ -548,4 +746,166 @@ mod tests {
             panic!("Expected PromptEnhancement data");
         }
     }
+
+    /// Summarizes every request into a fixed list of facts.
+    struct SummaryClient;
+
+    #[async_trait]
+    impl LLMClient for SummaryClient {
+        async fn complete(
+            &self,
+            request: CompletionRequest,
+        ) -> Result<crate::llm::CompletionResponse> {
+            assert!(request.messages[0].content.contains("switched to Postgres"));
+            Ok(crate::llm::CompletionResponse {
+                id: "mock".to_string(),
+                model: "mock".to_string(),
+                content: "- The service stores sessions in Postgres 16\n\
+                          - The moon is made of cheese\n"
+                    .to_string(),
+                stop_reason: None,
+                usage: crate::llm::TokenUsage::default(),
+                timestamp: Utc::now(),
+                cost: None,
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        }
+
+        async fn embed(
+            &self,
+            _request: crate::llm::EmbeddingRequest,
+        ) -> Result<crate::llm::EmbeddingResponse> {
+            Err(crate::error::Error::LLM("not implemented".into()))
+        }
+
+        fn provider(&self) -> crate::llm::Provider {
+            crate::llm::Provider::Anthropic
+        }
+
+        fn available_models(&self) -> Vec<crate::llm::ModelSpec> {
+            Vec::new()
+        }
+    }
+
+    /// Grounds claims mentioned in the context and rejects the rest.
+    struct ContextVerifier {
+        config: crate::epistemic::VerificationConfig,
+    }
+
+    #[async_trait]
+    impl EpistemicVerifier for ContextVerifier {
+        async fn verify_claim(
+            &self,
+            claim: &crate::epistemic::Claim,
+            context: &str,
+            _evidence: &[String],
+        ) -> Result<crate::epistemic::BudgetResult> {
+            use crate::epistemic::{BudgetResult, Probability};
+            let p0 = Probability::point(0.5);
+            let p1 = Probability::point(0.95);
+            let observed = p0.kl_divergence(&p1);
+            let required = if context.contains("Postgres 16") && claim.text.contains("Postgres") {
+                observed * 0.5
+            } else {
+                observed + 5.0
+            };
+            Ok(BudgetResult::new(claim.id.clone(), p0, p1, required))
+        }
+
+        async fn verify_response(
+            &self,
+            _response: &str,
+            _context: &str,
+        ) -> Result<crate::epistemic::VerificationResult> {
+            Err(crate::error::Error::LLM("not implemented".into()))
+        }
+
+        fn config(&self) -> &crate::epistemic::VerificationConfig {
+            &self.config
+        }
+
+        async fn get_events(&self) -> Vec<crate::trajectory::TrajectoryEvent> {
+            Vec::new()
+        }
+    }
+
+    fn compact_context(messages: &[&str]) -> HookContext {
+        HookContext::new(HookTrigger::PreCompact, SessionContext::new("compact")).with_data(
+            HookData::Compact {
+                context_tokens: 150_000,
+                max_tokens: 200_000,
+                messages_to_remove: messages.len(),
+                messages: messages.iter().map(|m| m.to_string()).collect(),
+            },
+        )
+    }
+
+    fn compact_data(result: HookResult) -> CompactData {
+        match result.data {
+            HookResultData::CompactData(data) => data,
+            other => panic!("Expected CompactData, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pre_compact_stores_gated_facts() {
+        let memory = Arc::new(SqliteMemoryStore::in_memory().unwrap());
+        let handler = PreCompactHandler::new()
+            .with_memory(memory.clone())
+            .with_llm(Arc::new(SummaryClient))
+            .with_verifier(Arc::new(ContextVerifier {
+                config: Default::default(),
+            }));
+
+        let context = compact_context(&[
+            "user: we switched to Postgres for sessions",
+            "assistant: confirmed, docker-compose pins Postgres 16",
+        ]);
+        let data = compact_data(handler.execute(context).await.unwrap());
+
+        assert_eq!(
+            data.critical_facts,
+            vec!["The service stores sessions in Postgres 16"]
+        );
+        assert_eq!(data.restore_state["rejected_facts"], 1);
+
+        let stored = memory.search_content("Postgres", 10).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].node_type, NodeType::Fact);
+        assert_eq!(
+            stored[0].provenance.as_ref().unwrap().source_type,
+            ProvenanceSource::Verification
+        );
+        assert!(memory.search_content("cheese", 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pre_compact_without_llm_stores_excerpts() {
+        let memory = Arc::new(SqliteMemoryStore::in_memory().unwrap());
+        let handler = PreCompactHandler::new().with_memory(memory.clone());
+
+        let long = format!("assistant: {}", "x".repeat(2 * MAX_EXCERPT_CHARS));
+        let context = compact_context(&["user: the build uses nextest", "  ", &long]);
+        let data = compact_data(handler.execute(context).await.unwrap());
+
+        assert!(data.critical_facts.is_empty());
+        assert_eq!(
+            data.restore_state["externalized_nodes"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let snippets = memory
+            .query_nodes(&crate::memory::NodeQuery::new().node_types(vec![NodeType::Snippet]))
+            .unwrap();
+        assert_eq!(snippets.len(), 2);
+        assert!(snippets
+            .iter()
+            .all(|n| n.content.chars().count() <= MAX_EXCERPT_CHARS));
+        assert!(snippets.iter().any(|n| n.content.contains("nextest")));
+    }
 }
@@ -10,6 +10,7 @@
 
 use crate::error::{Error, Result};
 use crate::reasoning::{HtmlConfig, HtmlTheme, ReasoningTrace};
+use crate::signature::{FieldSpec, FieldType};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -24,6 +25,9 @@ pub struct McpTool {
     pub description: String,
     /// JSON Schema for input parameters
     pub input_schema: Value,
+    /// JSON Schema for structured results, if the tool declares one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<Value>,
     /// Whether this tool requires confirmation before execution
     pub requires_confirmation: bool,
     /// Category for organization
@@ -39,6 +43,7 @@ impl McpTool {
             name: name.into(),
             description: description.into(),
             input_schema: Value::Object(Default::default()),
+            output_schema: None,
             requires_confirmation: false,
             category: None,
             examples: Vec::new(),
@@ -51,6 +56,12 @@ impl McpTool {
         self
     }
 
+    /// Set the output schema.
+    pub fn with_output_schema(mut self, schema: Value) -> Self {
+        self.output_schema = Some(schema);
+        self
+    }
+
     /// Mark as requiring confirmation.
     pub fn requires_confirmation(mut self) -> Self {
         self.requires_confirmation = true;
@@ -184,11 +195,15 @@ impl McpToolRegistry {
             .tools()
             .iter()
             .map(|tool| {
-                serde_json::json!({
+                let mut entry = serde_json::json!({
                     "name": tool.name,
                     "description": tool.description,
                     "inputSchema": tool.input_schema,
-                })
+                });
+                if let Some(output_schema) = &tool.output_schema {
+                    entry["outputSchema"] = output_schema.clone();
+                }
+                entry
             })
             .collect();
 
@@ -332,10 +347,13 @@ impl McpToolRegistry {
                 "limit": {
                     "type": "integer",
                     "description": "Maximum results to return",
-                    "default": 10
+                    "default": DEFAULT_MEMORY_QUERY_LIMIT,
+                    "minimum": 1,
+                    "maximum": MAX_MEMORY_QUERY_LIMIT
                 }
             }
         }))
+        .with_output_schema(memory_query_result_type().to_json_schema())
         .with_category("memory")
         .with_example(ToolExample::new(
             "Search for auth-related facts",
@@ -598,6 +616,36 @@ pub struct RlmStatusInput {
     pub include_budget_details: Option<bool>,
 }
 
+/// Default number of nodes returned by memory_query.
+pub const DEFAULT_MEMORY_QUERY_LIMIT: usize = 10;
+/// Upper bound on memory_query's `limit`.
+pub const MAX_MEMORY_QUERY_LIMIT: usize = 100;
+
+/// Declared result type of the memory_query tool.
+///
+/// Each node carries `id`, `node_type`, `content`, `confidence`, and `tier`;
+/// `truncated` is set when more nodes matched than `limit` allowed.
+pub fn memory_query_result_type() -> FieldType {
+    let node = FieldType::object(vec![
+        FieldSpec::new("id", FieldType::String),
+        FieldSpec::new(
+            "node_type",
+            FieldType::enum_of(["entity", "fact", "experience", "decision", "snippet"]),
+        ),
+        FieldSpec::new("content", FieldType::String),
+        FieldSpec::new("confidence", FieldType::Float),
+        FieldSpec::new(
+            "tier",
+            FieldType::enum_of(["task", "session", "longterm", "archive"]),
+        ),
+    ]);
+    FieldType::object(vec![
+        FieldSpec::new("nodes", FieldType::list(node)),
+        FieldSpec::new("total_count", FieldType::Integer),
+        FieldSpec::new("truncated", FieldType::Boolean),
+    ])
+}
+
 /// Input for memory_query tool.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryQueryInput {
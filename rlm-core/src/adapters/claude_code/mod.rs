@@ -9,6 +9,7 @@
 //! - **MCP Tools**: Tool definitions for rlm_execute, rlm_status, memory_query, memory_store
 //! - **Hooks**: Session lifecycle handlers (SessionStart, UserPromptSubmit, PreCompact)
 //! - **Skills**: RLM exposed as discoverable skills
+//! - **Orchestrator**: The adapter behind the [`Orchestrator`](crate::orchestrator::Orchestrator) trait
 //!
 //! ## Example
 //!
@@ -28,12 +29,16 @@
 mod adapter;
 mod hooks;
 mod mcp;
+mod orchestrator;
 mod skills;
 mod types;
 
 pub use adapter::ClaudeCodeAdapter;
-pub use hooks::{HookContext, HookHandler, HookResult, HookTrigger};
+pub use hooks::{
+    HookContext, HookData, HookHandler, HookResult, HookResultData, HookTrigger, PreCompactHandler,
+};
 pub use mcp::{McpTool, McpToolRegistry};
+pub use orchestrator::ClaudeCodeOrchestrator;
 pub use skills::RlmSkill;
 pub use types::{
     AdapterConfig, AdapterStatus, CompactData, PromptEnhancement, RlmRequest, RlmResponse,
@@ -0,0 +1,230 @@
+//! [`Orchestrator`] implementation backed by the Claude Code adapter.
+//!
+//! Lets callers that only know the [`Orchestrator`] trait (such as the C FFI)
+//! run the adapter's orchestration loop and observe it as trajectory events.
+
+use super::adapter::ClaudeCodeAdapter;
+use super::types::{AdapterConfig, RlmRequest, RlmResponse};
+use crate::complexity::{ActivationDecision, PatternClassifier};
+use crate::context::SessionContext;
+use crate::error::{Error, Result};
+use crate::orchestrator::{
+    ExecutionMode, Orchestrator, OrchestratorConfig, RecursiveResult, TrajectoryStream,
+};
+use crate::trajectory::{BudgetConfig, TrajectoryEvent};
+use async_trait::async_trait;
+
+/// Orchestrator that runs each query through a [`ClaudeCodeAdapter`].
+///
+/// The cost, token and depth limits of the [`OrchestratorConfig`] become the
+/// adapter's budget, so they hold across every run of this orchestrator.
+pub struct ClaudeCodeOrchestrator {
+    adapter: ClaudeCodeAdapter,
+    classifier: PatternClassifier,
+    config: OrchestratorConfig,
+}
+
+impl ClaudeCodeOrchestrator {
+    /// Create an orchestrator with an in-memory adapter limited by `config`.
+    pub fn new(config: OrchestratorConfig) -> Result<Self> {
+        let adapter_config = AdapterConfig {
+            budget: BudgetConfig {
+                max_cost_usd: Some(config.cost_budget_usd),
+                max_tokens: Some(config.total_token_budget),
+                max_depth: Some(config.max_depth),
+                ..BudgetConfig::default()
+            },
+            ..AdapterConfig::default()
+        };
+        Self::with_adapter_config(config, adapter_config)
+    }
+
+    /// Create an orchestrator whose adapter uses `adapter_config` as given.
+    pub fn with_adapter_config(
+        config: OrchestratorConfig,
+        adapter_config: AdapterConfig,
+    ) -> Result<Self> {
+        let classifier = PatternClassifier::with_threshold(adapter_config.escalation_threshold);
+        Ok(Self {
+            adapter: ClaudeCodeAdapter::new(adapter_config)?,
+            classifier,
+            config,
+        })
+    }
+
+    /// The adapter runs are delegated to.
+    pub fn adapter(&self) -> &ClaudeCodeAdapter {
+        &self.adapter
+    }
+
+    fn request(&self, query: &str) -> RlmRequest {
+        let mut request = RlmRequest::new(query);
+        request.max_budget_usd = Some(self.config.cost_budget_usd);
+        request
+    }
+}
+
+/// Trajectory events describing a finished adapter response.
+fn response_events(query: &str, response: &RlmResponse) -> Vec<TrajectoryEvent> {
+    let mut events = vec![
+        TrajectoryEvent::rlm_start(query),
+        TrajectoryEvent::analyze(0, response.activation_reason.clone())
+            .with_metadata("activated", response.activated)
+            .with_metadata("mode", response.mode.to_string()),
+    ];
+    if !response.activated {
+        return events;
+    }
+    events.push(TrajectoryEvent::cost_report(&response.cost));
+    match (&response.answer, &response.error) {
+        (_, Some(error)) => events.push(TrajectoryEvent::error(0, error.clone())),
+        (Some(answer), None) => events.push(TrajectoryEvent::final_answer(0, answer.clone())),
+        (None, None) => events.push(TrajectoryEvent::error(0, "run produced no answer")),
+    }
+    events
+}
+
+#[async_trait]
+impl Orchestrator for ClaudeCodeOrchestrator {
+    fn should_activate(&self, query: &str, context: &SessionContext) -> ActivationDecision {
+        self.classifier.should_activate(query, context)
+    }
+
+    /// Runs the query to completion, then replays the run as events.
+    ///
+    /// A query the classifier declines ends after the `Analyze` event.
+    async fn run(&self, query: &str, context: &SessionContext) -> Result<TrajectoryStream> {
+        let response = self
+            .adapter
+            .execute_with_session(self.request(query), context.clone())?;
+        Ok(Box::pin(futures::stream::iter(response_events(
+            query, &response,
+        ))))
+    }
+
+    /// Runs a forced sub-call with `context` as the `context` memory entry.
+    ///
+    /// The adapter always executes in a REPL, so `spawn_repl` is ignored.
+    async fn recursive_call(
+        &self,
+        query: &str,
+        context: &str,
+        depth: u32,
+        _spawn_repl: bool,
+    ) -> Result<RecursiveResult> {
+        if depth > self.config.max_depth {
+            return Err(Error::MaxDepthExceeded {
+                max_depth: self.config.max_depth,
+            });
+        }
+
+        let mut session = SessionContext::new();
+        if !context.is_empty() {
+            session.set_memory("context", serde_json::json!(context));
+        }
+        let mut request = self.request(query);
+        request.force_activation = true;
+
+        let response = self.adapter.execute_with_session(request, session)?;
+        if let Some(error) = response.error {
+            return Err(Error::Internal(error));
+        }
+        Ok(RecursiveResult {
+            content: response.answer.unwrap_or_default(),
+            depth,
+            used_repl: response.metadata.used_repl,
+            tokens_used: response.cost.total_tokens(),
+            cost_usd: response.cost.total_cost_usd,
+        })
+    }
+
+    fn execution_mode(&self) -> ExecutionMode {
+        self.adapter.current_mode()
+    }
+
+    fn set_execution_mode(&mut self, mode: ExecutionMode) {
+        self.adapter.set_mode(mode);
+    }
+
+    fn config(&self) -> &OrchestratorConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trajectory::{CostComponent, CostSummary, TokenUsage, TrajectoryEventType};
+
+    #[test]
+    fn test_new_carries_limits_into_adapter_budget() {
+        let config = OrchestratorConfig {
+            cost_budget_usd: 0.25,
+            total_token_budget: 1_000,
+            max_depth: 2,
+            ..OrchestratorConfig::default()
+        };
+        let orchestrator = ClaudeCodeOrchestrator::new(config).unwrap();
+
+        let budget = &orchestrator.adapter().config().budget;
+        assert_eq!(budget.max_cost_usd, Some(0.25));
+        assert_eq!(budget.max_tokens, Some(1_000));
+        assert_eq!(budget.max_depth, Some(2));
+        assert_eq!(orchestrator.request("q").max_budget_usd, Some(0.25));
+    }
+
+    #[test]
+    fn test_skipped_run_ends_after_analysis() {
+        let orchestrator = ClaudeCodeOrchestrator::new(OrchestratorConfig::default()).unwrap();
+        let mut stream =
+            futures::executor::block_on(orchestrator.run("What is 2 + 2?", &SessionContext::new()))
+                .unwrap();
+
+        let events: Vec<_> =
+            futures::executor::block_on(futures::StreamExt::collect::<Vec<_>>(&mut stream));
+        let types: Vec<_> = events.iter().map(|e| e.event_type).collect();
+        assert_eq!(
+            types,
+            vec![TrajectoryEventType::RlmStart, TrajectoryEventType::Analyze]
+        );
+        assert_eq!(
+            events[1].get_metadata("activated"),
+            Some(&serde_json::json!(false))
+        );
+    }
+
+    #[test]
+    fn test_response_events_report_answer_and_cost() {
+        let mut cost = CostSummary::new();
+        cost.add(CostComponent::Orchestration, TokenUsage::new(10, 5), 0.01);
+        let response = RlmResponse::success("42", ExecutionMode::Fast, cost);
+
+        let types: Vec<_> = response_events("q", &response)
+            .iter()
+            .map(|e| e.event_type)
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                TrajectoryEventType::RlmStart,
+                TrajectoryEventType::Analyze,
+                TrajectoryEventType::CostReport,
+                TrajectoryEventType::Final,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_call_respects_max_depth() {
+        let orchestrator = ClaudeCodeOrchestrator::new(OrchestratorConfig {
+            max_depth: 1,
+            ..OrchestratorConfig::default()
+        })
+        .unwrap();
+        let result = futures::executor::block_on(orchestrator.recursive_call("q", "", 2, false));
+        assert!(matches!(
+            result,
+            Err(Error::MaxDepthExceeded { max_depth: 1 })
+        ));
+    }
+}
@@ -4,17 +4,21 @@
 //! wrapper can call to export `ReasoningTrace` artifacts.
 
 use crate::error::{Error, Result};
-use crate::reasoning::{HtmlConfig, HtmlTheme, ReasoningTrace};
+use crate::reasoning::{DotConfig, HtmlConfig, HtmlTheme, ReasoningTrace};
+use serde::Serialize;
 use std::fs;
 use std::path::PathBuf;
 
 /// Supported trace visualization output formats.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TraceVisualizeFormat {
     Html,
     Dot,
     NetworkXJson,
     Mermaid,
+    /// Standalone SVG rendered without Graphviz.
+    Svg,
 }
 
 impl TraceVisualizeFormat {
@@ -24,6 +28,7 @@ impl TraceVisualizeFormat {
             Self::Dot => "dot",
             Self::NetworkXJson => "json",
             Self::Mermaid => "mmd",
+            Self::Svg => "svg",
         }
     }
 }
@@ -44,6 +49,9 @@ pub struct TraceVisualizeOptions {
     pub output: Option<PathBuf>,
     pub html_preset: HtmlPreset,
     pub title: Option<String>,
+    /// `--json`: always write the artifact to disk (at the suggested path if
+    /// `output` is unset) and report a JSON summary on stdout.
+    pub json: bool,
 }
 
 impl Default for TraceVisualizeOptions {
@@ -53,16 +61,47 @@ impl Default for TraceVisualizeOptions {
             output: None,
             html_preset: HtmlPreset::Default,
             title: None,
+            json: false,
         }
     }
 }
 
 /// Result from trace visualization export.
-#[derive(Debug, Clone)]
+///
+/// Serializes to the machine-readable summary printed in `--json` mode; the
+/// artifact itself is left out.
+#[derive(Debug, Clone, Serialize)]
 pub struct TraceVisualizeResult {
     pub format: TraceVisualizeFormat,
+    #[serde(skip)]
     pub artifact: String,
     pub output_path: Option<PathBuf>,
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub warnings: Vec<String>,
+    #[serde(skip)]
+    pub json: bool,
+}
+
+impl TraceVisualizeResult {
+    /// Machine-readable summary of the export.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// What a CLI wrapper should print to stdout.
+    ///
+    /// Only the JSON summary in `--json` mode; otherwise the artifact when it
+    /// was not written to disk, or the path it was written to.
+    pub fn stdout(&self) -> String {
+        if self.json {
+            self.to_json()
+        } else if let Some(path) = &self.output_path {
+            format!("Wrote {}", path.display())
+        } else {
+            self.artifact.clone()
+        }
+    }
 }
 
 /// Export a trace visualization artifact for CLI consumers.
@@ -75,9 +114,30 @@ pub fn trace_visualize(
         TraceVisualizeFormat::Dot => trace.to_dot(),
         TraceVisualizeFormat::NetworkXJson => trace.to_networkx_json(),
         TraceVisualizeFormat::Mermaid => trace.to_mermaid_enhanced(),
+        TraceVisualizeFormat::Svg => trace.to_svg(&DotConfig::default()),
     };
 
-    let output_path = if let Some(path) = &options.output {
+    let mut warnings = Vec::new();
+    if options.format != TraceVisualizeFormat::Html
+        && (options.title.is_some() || options.html_preset != HtmlPreset::Default)
+    {
+        warnings.push("html_preset and title only apply to html output".to_string());
+    }
+    if trace.edges.is_empty() {
+        warnings.push("trace has no edges".to_string());
+    }
+
+    let output = match &options.output {
+        Some(path) => Some(path.clone()),
+        None if options.json => {
+            let path = suggested_output_path(trace, options.format);
+            warnings.push(format!("no output path given; wrote to {}", path.display()));
+            Some(path)
+        }
+        None => None,
+    };
+
+    let output_path = if let Some(path) = &output {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).map_err(|error| {
                 Error::Config(format!(
@@ -104,6 +164,10 @@ pub fn trace_visualize(
         format: options.format,
         artifact,
         output_path,
+        node_count: trace.nodes.len(),
+        edge_count: trace.edges.len(),
+        warnings,
+        json: options.json,
     })
 }
 
@@ -159,6 +223,7 @@ mod tests {
             output: Some(output.clone()),
             html_preset: HtmlPreset::Default,
             title: Some("CLI Trace".to_string()),
+            json: false,
         };
 
         let result = trace_visualize(&trace, &options).expect("export should succeed");
@@ -186,6 +251,67 @@ mod tests {
         assert!(result.artifact.contains("%% ReasoningTrace (enhanced)"));
     }
 
+    #[test]
+    fn test_trace_visualize_svg() {
+        let mut trace = ReasoningTrace::new("CLI svg", "cli-svg");
+        let root = trace.root_goal.clone();
+        trace.log_decision(
+            &root,
+            "Pick renderer",
+            &["Graphviz", "Built-in"],
+            1,
+            "No deps",
+        );
+
+        let options = TraceVisualizeOptions {
+            format: TraceVisualizeFormat::Svg,
+            ..Default::default()
+        };
+        let result = trace_visualize(&trace, &options).expect("svg export should work");
+        assert!(result
+            .artifact
+            .contains("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert_eq!(
+            suggested_output_path(&trace, TraceVisualizeFormat::Svg)
+                .extension()
+                .and_then(|ext| ext.to_str()),
+            Some("svg")
+        );
+    }
+
+    #[test]
+    fn test_trace_visualize_json_mode_reports_summary() {
+        let mut trace = ReasoningTrace::new("CLI json mode", "cli-json-mode");
+        let root = trace.root_goal.clone();
+        trace.log_decision(&root, "Pick format", &["DOT", "SVG"], 0, "Tooling");
+
+        let dir = tempdir().expect("tempdir should be created");
+        let suggested = dir
+            .path()
+            .join(suggested_output_path(&trace, TraceVisualizeFormat::Dot));
+        let options = TraceVisualizeOptions {
+            format: TraceVisualizeFormat::Dot,
+            output: Some(suggested.clone()),
+            json: true,
+            ..Default::default()
+        };
+
+        let result = trace_visualize(&trace, &options).expect("export should succeed");
+        let stdout = result.stdout();
+        assert!(!stdout.contains("digraph"));
+
+        let summary: serde_json::Value =
+            serde_json::from_str(&stdout).expect("stdout should be JSON only");
+        assert_eq!(summary["format"], "dot");
+        assert_eq!(summary["output_path"], suggested.to_string_lossy().as_ref());
+        assert_eq!(summary["node_count"], 4);
+        assert_eq!(summary["edge_count"], 3);
+        assert_eq!(summary["warnings"], serde_json::json!([]));
+        assert!(fs::read_to_string(&suggested)
+            .expect("artifact should be written")
+            .contains("digraph"));
+    }
+
     #[test]
     fn test_trace_visualize_from_json_rejects_invalid_payload() {
         let options = TraceVisualizeOptions::default();
@@ -20,13 +20,14 @@ pub use cli::{
 };
 
 pub use claude_code::{
-    AdapterConfig, AdapterStatus, ClaudeCodeAdapter, CompactData, HookContext, HookHandler,
-    HookResult, HookTrigger, McpTool, McpToolRegistry, PromptEnhancement, RlmRequest, RlmResponse,
-    RlmSkill, SessionContext as AdapterSessionContext,
+    AdapterConfig, AdapterStatus, ClaudeCodeAdapter, ClaudeCodeOrchestrator, CompactData,
+    HookContext, HookData, HookHandler, HookResult, HookResultData, HookTrigger, McpTool,
+    McpToolRegistry, PromptEnhancement, RlmRequest, RlmResponse, RlmSkill,
+    SessionContext as AdapterSessionContext,
 };
 
 pub use tui::{
     BudgetPanelData, BudgetUpdate, EventBridge, EventStyle, ExecutionStatus, MemoryNodeView,
-    MemoryPanelData, ReplEntry, ReplPanelData, ReplStatus, StatusUpdate, TUIAdapter, TUIConfig,
-    TUIEvent, TierCounts, TraceEventView, TracePanelData,
+    MemoryPanelData, MemoryPanelDelta, ReplEntry, ReplPanelData, ReplStatus, StatusUpdate,
+    TUIAdapter, TUIConfig, TUIEvent, TierCounts, TraceEventView, TracePanelData,
 };
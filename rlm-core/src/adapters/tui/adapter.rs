@@ -9,6 +9,7 @@ use std::time::Instant;
 use tokio::sync::broadcast;
 use tokio::sync::RwLock;
 
+use crate::error::{Error, Result};
 use crate::memory::SqliteMemoryStore;
 use crate::reasoning::ReasoningTrace;
 use crate::trajectory::{
@@ -16,10 +17,10 @@ use crate::trajectory::{
     Verbosity,
 };
 
-use super::events::{EventBridge, ExecutionStatus, TUIEvent};
+use super::events::{EventBridge, ExecutionStatus, StatusUpdate, TUIEvent};
 use super::panels::{
-    BudgetPanelData, MemoryNodeView, MemoryPanelData, ReplEntry, ReplPanelData, TierCounts,
-    TraceEventView, TracePanelData,
+    BudgetPanelData, MemoryNodeView, MemoryPanelData, MemoryPanelDelta, ReplEntry, ReplPanelData,
+    TierCounts, TraceEventView, TracePanelData,
 };
 
 // =============================================================================
@@ -142,6 +143,27 @@ struct AdapterState {
     status: ExecutionStatus,
 }
 
+impl AdapterState {
+    /// Move to `next` if the status machine allows it.
+    fn transition(&mut self, next: ExecutionStatus) -> Result<()> {
+        if !self.status.can_transition_to(next) {
+            return Err(self.illegal(next));
+        }
+        self.status = next;
+        self.trace.status = next;
+        Ok(())
+    }
+
+    /// Log and build the error for an illegal move to `next`.
+    fn illegal(&self, next: ExecutionStatus) -> Error {
+        tracing::warn!(from = %self.status, to = %next, "rejected execution status transition");
+        Error::InvalidTransition {
+            from: self.status.to_string(),
+            to: next.to_string(),
+        }
+    }
+}
+
 impl TUIAdapter {
     /// Create a new TUI adapter with the given configuration.
     pub fn new(config: TUIConfig) -> Self {
@@ -158,6 +180,7 @@ impl TUIAdapter {
         );
 
         let state = AdapterState {
+            memory: MemoryPanelData::new().with_max_recent_nodes(config.max_memory_nodes),
             budget: budget_panel,
             ..Default::default()
         };
@@ -276,7 +299,6 @@ impl TUIAdapter {
         let budget_state = self.budget.state();
         panel.cost_usd = budget_state.current_cost_usd;
         panel.tokens_used = budget_state.current_tokens;
-        panel.burn_rate = budget_state.burn_rate_per_minute();
 
         if let Some(limit) = self.config.budget_config.max_cost_usd {
             panel.utilization_percent = (budget_state.current_cost_usd / limit) * 100.0;
@@ -304,21 +326,16 @@ impl TUIAdapter {
         state.trace.push_event(view.clone());
         state.trace.truncate_to(self.config.max_trace_events);
 
-        // Update status based on event type
-        match event.event_type {
-            crate::trajectory::TrajectoryEventType::RlmStart => {
-                state.status = ExecutionStatus::Running;
-                state.trace.status = ExecutionStatus::Running;
-            }
-            crate::trajectory::TrajectoryEventType::Final => {
-                state.status = ExecutionStatus::Complete;
-                state.trace.status = ExecutionStatus::Complete;
-            }
-            crate::trajectory::TrajectoryEventType::Error => {
-                state.status = ExecutionStatus::Error;
-                state.trace.status = ExecutionStatus::Error;
-            }
-            _ => {}
+        // Update status based on event type; an illegal transition is
+        // logged and dropped, but the event still shows in the trace
+        let next = match event.event_type {
+            crate::trajectory::TrajectoryEventType::RlmStart => Some(ExecutionStatus::Running),
+            crate::trajectory::TrajectoryEventType::Final => Some(ExecutionStatus::Complete),
+            crate::trajectory::TrajectoryEventType::Error => Some(ExecutionStatus::Error),
+            _ => None,
+        };
+        if let Some(next) = next {
+            let _ = state.transition(next);
         }
 
         // Forward to event bridge
@@ -337,17 +354,54 @@ impl TUIAdapter {
         self.event_bridge.forward_repl(entry);
     }
 
-    /// Record a memory node addition.
+    /// Record a memory node that was added, changed, or moved between tiers.
+    ///
+    /// Emits a single [`MemoryPanelDelta`]; a promotion is one `Moved` delta.
     pub async fn record_memory_node(&self, node: &crate::memory::Node) {
         let view =
             MemoryNodeView::from_node_with_preview_length(node, self.config.memory_preview_length);
 
         let mut state = self.state.write().await;
-        state.memory.add_node(view.clone());
-        state.memory.truncate_to(self.config.max_memory_nodes);
+        let delta = state.memory.delta_for(view);
+        state.memory.apply(&delta);
 
         // Forward to event bridge
-        self.event_bridge.forward_memory(view);
+        self.event_bridge.forward_memory_delta(delta);
+    }
+
+    /// Record a memory node deletion.
+    pub async fn record_memory_removed(&self, node: &crate::memory::Node) {
+        let delta = MemoryPanelDelta::Removed {
+            id: node.id.to_string(),
+            tier: node.tier.to_string(),
+        };
+
+        let mut state = self.state.write().await;
+        state.memory.apply(&delta);
+
+        self.event_bridge.forward_memory_delta(delta);
+    }
+
+    /// Emit a full memory panel snapshot, for the initial load or a resync.
+    ///
+    /// Counts are refreshed from the memory store and become the base that
+    /// later deltas patch.
+    pub async fn emit_memory_snapshot(&self) -> MemoryPanelData {
+        let mut panel = self.get_memory_panel().await;
+        // Every counted node's tier, so a later move of an unlisted node
+        // is not mistaken for an addition.
+        if let Some(ref memory) = self.memory {
+            if let Ok(nodes) = memory.query_nodes(&crate::memory::NodeQuery::new()) {
+                panel.track_tiers(nodes.iter().map(|n| (n.id.to_string(), n.tier.to_string())));
+            }
+        }
+        let delta = MemoryPanelDelta::Snapshot {
+            panel: Box::new(panel.clone()),
+        };
+        self.state.write().await.memory.apply(&delta);
+
+        self.event_bridge.forward_memory_delta(delta);
+        panel
     }
 
     /// Record cost and update budget panel.
@@ -361,7 +415,6 @@ impl TUIAdapter {
             input_tokens,
             output_tokens,
         );
-        state.budget.set_burn_rate(self.budget.burn_rate());
 
         // Forward alerts
         let budget_state = self.budget.state();
@@ -383,68 +436,104 @@ impl TUIAdapter {
     // Execution Control
     // =========================================================================
 
+    /// Apply a status update and forward it.
+    ///
+    /// An update that [`STATUS_TRANSITIONS`](super::events::STATUS_TRANSITIONS)
+    /// doesn't allow is rejected without being forwarded, and the last good
+    /// status is kept.
+    pub async fn handle_status(&self, update: StatusUpdate) -> Result<()> {
+        self.state.write().await.transition(update.status)?;
+        self.event_bridge.forward_status_update(update);
+        Ok(())
+    }
+
     /// Mark execution as started.
-    pub async fn start_execution(&self) {
+    pub async fn start_execution(&self) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.transition(ExecutionStatus::Running)?;
         *self.execution_start.write().await = Some(Instant::now());
 
+        self.event_bridge.forward_status(
+            ExecutionStatus::Running,
+            Some("Execution started".to_string()),
+        );
+        Ok(())
+    }
+
+    /// Pause a running execution.
+    pub async fn pause_execution(&self) -> Result<()> {
+        self.state
+            .write()
+            .await
+            .transition(ExecutionStatus::Paused)?;
+
+        self.event_bridge.forward_status(
+            ExecutionStatus::Paused,
+            Some("Execution paused".to_string()),
+        );
+        Ok(())
+    }
+
+    /// Resume a paused execution.
+    pub async fn resume_execution(&self) -> Result<()> {
         let mut state = self.state.write().await;
-        state.status = ExecutionStatus::Running;
-        state.trace.status = ExecutionStatus::Running;
+        if state.status != ExecutionStatus::Paused {
+            return Err(state.illegal(ExecutionStatus::Running));
+        }
+        state.transition(ExecutionStatus::Running)?;
 
         self.event_bridge.forward_status(
             ExecutionStatus::Running,
-            Some("Execution started".to_string()),
+            Some("Execution resumed".to_string()),
         );
+        Ok(())
     }
 
     /// Mark execution as complete.
-    pub async fn complete_execution(&self) {
+    pub async fn complete_execution(&self) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.transition(ExecutionStatus::Complete)?;
+
         let elapsed = self
             .execution_start
-            .read()
+            .write()
             .await
+            .take()
             .map(|s| s.elapsed().as_millis() as u64)
             .unwrap_or(0);
-
-        *self.execution_start.write().await = None;
-
-        let mut state = self.state.write().await;
-        state.status = ExecutionStatus::Complete;
-        state.trace.status = ExecutionStatus::Complete;
         state.trace.elapsed_ms = elapsed;
 
         self.event_bridge.forward_status(
             ExecutionStatus::Complete,
             Some(format!("Execution completed in {}ms", elapsed)),
         );
+        Ok(())
     }
 
     /// Mark execution as failed.
-    pub async fn fail_execution(&self, error: impl Into<String>) {
+    pub async fn fail_execution(&self, error: impl Into<String>) -> Result<()> {
         let error_msg = error.into();
-        *self.execution_start.write().await = None;
-
         let mut state = self.state.write().await;
-        state.status = ExecutionStatus::Error;
-        state.trace.status = ExecutionStatus::Error;
+        state.transition(ExecutionStatus::Error)?;
+        *self.execution_start.write().await = None;
 
         self.event_bridge
             .forward_status(ExecutionStatus::Error, Some(error_msg.clone()));
         self.event_bridge.forward_error(error_msg);
+        Ok(())
     }
 
     /// Cancel execution.
-    pub async fn cancel_execution(&self) {
-        *self.execution_start.write().await = None;
-
+    pub async fn cancel_execution(&self) -> Result<()> {
         let mut state = self.state.write().await;
-        state.status = ExecutionStatus::Cancelled;
-        state.trace.status = ExecutionStatus::Cancelled;
+        state.transition(ExecutionStatus::Cancelled)?;
+        *self.execution_start.write().await = None;
 
         self.event_bridge.forward_status(
             ExecutionStatus::Cancelled,
             Some("Execution cancelled".to_string()),
         );
+        Ok(())
     }
 
     /// Reset all panel state.
@@ -454,7 +543,7 @@ impl TUIAdapter {
         let mut state = self.state.write().await;
         state.trace = TracePanelData::new();
         state.repl = ReplPanelData::new();
-        state.memory = MemoryPanelData::new();
+        state.memory = MemoryPanelData::new().with_max_recent_nodes(self.config.max_memory_nodes);
         state.budget = BudgetPanelData::with_limits(
             self.config.budget_config.max_cost_usd,
             self.config.budget_config.max_tokens,
@@ -551,19 +640,54 @@ mod tests {
     async fn test_execution_lifecycle() {
         let adapter = TUIAdapter::with_defaults();
 
-        adapter.start_execution().await;
+        adapter.start_execution().await.unwrap();
         assert_eq!(adapter.get_status().await, ExecutionStatus::Running);
 
-        adapter.complete_execution().await;
+        adapter.complete_execution().await.unwrap();
         assert_eq!(adapter.get_status().await, ExecutionStatus::Complete);
     }
 
+    #[tokio::test]
+    async fn test_illegal_status_transition_rejected() {
+        let adapter = TUIAdapter::with_defaults();
+        let mut rx = adapter.subscribe_events();
+
+        adapter.start_execution().await.unwrap();
+        adapter
+            .handle_status(StatusUpdate::new(ExecutionStatus::Error).with_message("boom"))
+            .await
+            .unwrap();
+        while rx.try_recv().is_ok() {}
+
+        let err = adapter
+            .handle_status(StatusUpdate::new(ExecutionStatus::Complete))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidTransition { .. }));
+        assert!(adapter.complete_execution().await.is_err());
+        assert!(adapter.resume_execution().await.is_err());
+
+        assert_eq!(adapter.get_status().await, ExecutionStatus::Error);
+        assert_eq!(
+            adapter.get_trace_panel().await.status,
+            ExecutionStatus::Error
+        );
+        assert!(rx.try_recv().is_err());
+
+        // A new run may start after a failure
+        adapter.start_execution().await.unwrap();
+        adapter.pause_execution().await.unwrap();
+        adapter.resume_execution().await.unwrap();
+        adapter.cancel_execution().await.unwrap();
+        assert_eq!(adapter.get_status().await, ExecutionStatus::Cancelled);
+    }
+
     #[tokio::test]
     async fn test_event_subscription() {
         let adapter = TUIAdapter::with_defaults();
         let mut rx = adapter.subscribe_events();
 
-        adapter.start_execution().await;
+        adapter.start_execution().await.unwrap();
 
         // Should receive status update
         let event = rx.recv().await.unwrap();
@@ -575,6 +699,83 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_memory_promotion_emits_single_move() {
+        use crate::memory::{Node, NodeType, SqliteMemoryStore, Tier};
+
+        let store = Arc::new(SqliteMemoryStore::in_memory().unwrap());
+        let adapter = TUIAdapter::with_defaults().with_memory(store.clone());
+        let mut rx = adapter.subscribe_events();
+
+        let node = Node::new(NodeType::Fact, "API uses JWT");
+        store.add_node(&node).unwrap();
+        adapter.record_memory_node(&node).await;
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            TUIEvent::MemoryDelta(MemoryPanelDelta::Added { .. })
+        ));
+
+        let snapshot = adapter.emit_memory_snapshot().await;
+        assert_eq!(snapshot.tier_counts.task, 1);
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            TUIEvent::MemoryDelta(MemoryPanelDelta::Snapshot { .. })
+        ));
+
+        store
+            .promote(std::slice::from_ref(&node.id), "confirmed")
+            .unwrap();
+        let promoted = store.get_node(&node.id).unwrap().unwrap();
+        adapter.record_memory_node(&promoted).await;
+
+        match rx.recv().await.unwrap() {
+            TUIEvent::MemoryDelta(MemoryPanelDelta::Moved { from_tier, node }) => {
+                assert_eq!(from_tier, "task");
+                assert_eq!(node.tier, Tier::Session.to_string());
+            }
+            other => panic!("Expected move delta, got {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+
+        let panel = adapter.state.read().await.memory.clone();
+        assert_eq!((panel.tier_counts.task, panel.tier_counts.session), (0, 1));
+        assert_eq!(panel.recent_nodes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_memory_snapshot_tracks_unlisted_nodes() {
+        use crate::memory::{Node, NodeType, SqliteMemoryStore};
+
+        let store = Arc::new(SqliteMemoryStore::in_memory().unwrap());
+        let adapter =
+            TUIAdapter::new(TUIConfig::default().max_memory_nodes(1)).with_memory(store.clone());
+
+        let first = Node::new(NodeType::Fact, "API uses JWT");
+        let second = Node::new(NodeType::Fact, "Tokens expire hourly");
+        for node in [&first, &second] {
+            store.add_node(node).unwrap();
+            adapter.record_memory_node(node).await;
+        }
+        adapter.emit_memory_snapshot().await;
+        let mut rx = adapter.subscribe_events();
+
+        // `first` was trimmed from the listed nodes before the snapshot
+        store
+            .promote(std::slice::from_ref(&first.id), "confirmed")
+            .unwrap();
+        let promoted = store.get_node(&first.id).unwrap().unwrap();
+        adapter.record_memory_node(&promoted).await;
+
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            TUIEvent::MemoryDelta(MemoryPanelDelta::Moved { .. })
+        ));
+        let panel = adapter.state.read().await.memory.clone();
+        assert_eq!(panel.node_count, 2);
+        assert_eq!((panel.tier_counts.task, panel.tier_counts.session), (1, 1));
+        assert_eq!(panel.recent_nodes.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_trajectory_processing() {
         let adapter = TUIAdapter::with_defaults();
@@ -616,7 +817,7 @@ mod tests {
     async fn test_reset() {
         let adapter = TUIAdapter::with_defaults();
 
-        adapter.start_execution().await;
+        adapter.start_execution().await.unwrap();
         let event = TrajectoryEvent::rlm_start("Test");
         adapter.process_trajectory_event(&event).await;
 
@@ -4,11 +4,14 @@
 //! events to TUI-friendly events suitable for Go channel consumption.
 
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
 use tokio::sync::broadcast;
 
-use crate::trajectory::{BudgetAlert, BudgetState, TrajectoryEvent, TrajectoryEventType};
+use crate::trajectory::{
+    BudgetAlert, BudgetState, TrajectoryEvent, TrajectoryEventType, TrajectoryFilter,
+};
 
-use super::panels::{MemoryNodeView, ReplEntry, TraceEventView};
+use super::panels::{MemoryNodeView, MemoryPanelDelta, ReplEntry, TraceEventView};
 
 // =============================================================================
 // TUI Events
@@ -28,6 +31,8 @@ pub enum TUIEvent {
     Repl(ReplEntry),
     /// Memory node added/updated
     Memory(MemoryNodeView),
+    /// Incremental memory panel update
+    MemoryDelta(MemoryPanelDelta),
     /// Budget update
     Budget(BudgetUpdate),
     /// Execution status change
@@ -54,6 +59,11 @@ impl TUIEvent {
         Self::Memory(node)
     }
 
+    /// Create a memory delta event.
+    pub fn memory_delta(delta: MemoryPanelDelta) -> Self {
+        Self::MemoryDelta(delta)
+    }
+
     /// Create a budget event.
     pub fn budget(update: BudgetUpdate) -> Self {
         Self::Budget(update)
@@ -90,6 +100,7 @@ impl TUIEvent {
             Self::Trace(_) => "trace",
             Self::Repl(_) => "repl",
             Self::Memory(_) => "memory",
+            Self::MemoryDelta(_) => "memory_delta",
             Self::Budget(_) => "budget",
             Self::Status(_) => "status",
             Self::Error(_) => "error",
@@ -294,6 +305,34 @@ impl std::fmt::Display for ExecutionStatus {
     }
 }
 
+/// Legal execution status transitions.
+///
+/// A run goes Idle → Running and ends in Complete, Error or Cancelled,
+/// possibly pausing and resuming (Paused → Running) on the way. A finished
+/// run may be followed by a new one. Resetting the adapter returns to Idle
+/// from any status and is not governed by this table.
+pub const STATUS_TRANSITIONS: &[(ExecutionStatus, ExecutionStatus)] = &[
+    (ExecutionStatus::Idle, ExecutionStatus::Running),
+    (ExecutionStatus::Running, ExecutionStatus::Paused),
+    (ExecutionStatus::Running, ExecutionStatus::Complete),
+    (ExecutionStatus::Running, ExecutionStatus::Error),
+    (ExecutionStatus::Running, ExecutionStatus::Cancelled),
+    (ExecutionStatus::Paused, ExecutionStatus::Running),
+    (ExecutionStatus::Paused, ExecutionStatus::Error),
+    (ExecutionStatus::Paused, ExecutionStatus::Cancelled),
+    (ExecutionStatus::Complete, ExecutionStatus::Running),
+    (ExecutionStatus::Error, ExecutionStatus::Running),
+    (ExecutionStatus::Cancelled, ExecutionStatus::Running),
+];
+
+impl ExecutionStatus {
+    /// Whether [`STATUS_TRANSITIONS`] allows moving to `next`. Staying in
+    /// the same status is always allowed.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        self == next || STATUS_TRANSITIONS.contains(&(self, next))
+    }
+}
+
 // =============================================================================
 // Event Bridge
 // =============================================================================
@@ -307,13 +346,33 @@ pub struct EventBridge {
     sender: broadcast::Sender<TUIEvent>,
     /// Channel capacity
     capacity: usize,
+    /// Trajectory events to forward (None = all); shared by clones
+    trajectory_filter: Arc<RwLock<Option<TrajectoryFilter>>>,
 }
 
 impl EventBridge {
     /// Create a new event bridge with the specified channel capacity.
     pub fn new(capacity: usize) -> Self {
         let (sender, _) = broadcast::channel(capacity);
-        Self { sender, capacity }
+        Self {
+            sender,
+            capacity,
+            trajectory_filter: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Forward only the trajectory events that match `filter`.
+    pub fn with_trajectory_filter(self, filter: TrajectoryFilter) -> Self {
+        self.set_trajectory_filter(Some(filter));
+        self
+    }
+
+    /// Install or clear (`None`) the trajectory filter.
+    ///
+    /// The filter applies to [`forward_trajectory`](Self::forward_trajectory)
+    /// on this bridge and all its clones.
+    pub fn set_trajectory_filter(&self, filter: Option<TrajectoryFilter>) {
+        *self.trajectory_filter.write().unwrap() = filter;
     }
 
     /// Subscribe to TUI events.
@@ -335,6 +394,11 @@ impl EventBridge {
 
     /// Forward a trajectory event as a TUI trace event.
     pub fn forward_trajectory(&self, event: &TrajectoryEvent) {
+        if let Some(filter) = self.trajectory_filter.read().unwrap().as_ref() {
+            if !filter.matches(event) {
+                return;
+            }
+        }
         let view = TraceEventView::from_trajectory_event(event);
         let tui_event = TUIEvent::Trace(view);
         let _ = self.sender.send(tui_event);
@@ -387,6 +451,11 @@ impl EventBridge {
         let _ = self.sender.send(TUIEvent::Status(update));
     }
 
+    /// Forward a prepared status update.
+    pub fn forward_status_update(&self, update: StatusUpdate) {
+        let _ = self.sender.send(TUIEvent::Status(update));
+    }
+
     /// Forward a REPL entry.
     pub fn forward_repl(&self, entry: ReplEntry) {
         let _ = self.sender.send(TUIEvent::Repl(entry));
@@ -397,6 +466,11 @@ impl EventBridge {
         let _ = self.sender.send(TUIEvent::Memory(node));
     }
 
+    /// Forward a memory panel delta.
+    pub fn forward_memory_delta(&self, delta: MemoryPanelDelta) {
+        let _ = self.sender.send(TUIEvent::MemoryDelta(delta));
+    }
+
     /// Forward an error.
     pub fn forward_error(&self, error: impl Into<String>) {
         let _ = self.sender.send(TUIEvent::Error(error.into()));
@@ -421,6 +495,7 @@ impl Clone for EventBridge {
         Self {
             sender: self.sender.clone(),
             capacity: self.capacity,
+            trajectory_filter: Arc::clone(&self.trajectory_filter),
         }
     }
 }
@@ -551,6 +626,28 @@ mod tests {
         assert_eq!(ExecutionStatus::Complete.to_string(), "complete");
     }
 
+    #[tokio::test]
+    async fn test_bridge_trajectory_filter() {
+        let bridge = EventBridge::new(16).with_trajectory_filter(
+            TrajectoryFilter::new().event_types([TrajectoryEventType::Final]),
+        );
+        let mut rx = bridge.subscribe();
+
+        bridge
+            .clone()
+            .forward_trajectory(&TrajectoryEvent::rlm_start("q"));
+        bridge.forward_trajectory(&TrajectoryEvent::final_answer(0, "done"));
+        match rx.recv().await.unwrap() {
+            TUIEvent::Trace(view) => assert_eq!(view.event_type, "FINAL"),
+            other => panic!("Expected trace event, got {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+
+        bridge.set_trajectory_filter(None);
+        bridge.forward_trajectory(&TrajectoryEvent::rlm_start("q"));
+        assert!(rx.try_recv().is_ok());
+    }
+
     #[test]
     fn test_tui_event_type_name() {
         assert_eq!(TUIEvent::error("test").type_name(), "error");
@@ -37,8 +37,10 @@ mod events;
 mod panels;
 
 pub use adapter::{TUIAdapter, TUIConfig};
-pub use events::{BudgetUpdate, EventBridge, ExecutionStatus, StatusUpdate, TUIEvent};
+pub use events::{
+    BudgetUpdate, EventBridge, ExecutionStatus, StatusUpdate, TUIEvent, STATUS_TRANSITIONS,
+};
 pub use panels::{
-    BudgetPanelData, EventStyle, MemoryNodeView, MemoryPanelData, ReplEntry, ReplPanelData,
-    ReplStatus, TierCounts, TraceEventView, TracePanelData,
+    BudgetPanelData, BurnRateWindow, EventStyle, MemoryNodeView, MemoryPanelData, MemoryPanelDelta,
+    ReplEntry, ReplPanelData, ReplStatus, TierCounts, TraceEventView, TracePanelData,
 };
@@ -3,10 +3,12 @@
 //! These structures represent the data needed to render each panel
 //! in the Bubble Tea TUI. All types are serializable for FFI transport.
 
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 
 use crate::memory::{Node, Tier};
+use crate::repl::floor_char_boundary;
 use crate::repl::ExecuteResult;
 use crate::trajectory::{TrajectoryEvent, TrajectoryEventType};
 
@@ -119,7 +121,10 @@ impl TraceEventView {
     /// Truncate content to a maximum length.
     pub fn with_max_content_length(mut self, max_len: usize) -> Self {
         if self.content.len() > max_len {
-            self.content = format!("{}...", &self.content[..max_len - 3]);
+            self.content = format!(
+                "{}...",
+                &self.content[..floor_char_boundary(&self.content, max_len - 3)]
+            );
         }
         self
     }
@@ -336,7 +341,8 @@ impl Default for ReplStatus {
 
 /// Data for the memory inspector panel.
 ///
-/// Displays hypergraph memory statistics and recent nodes.
+/// Displays hypergraph memory statistics and recent nodes. After an initial
+/// snapshot, the panel is kept current with [`MemoryPanelDelta`]s.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryPanelData {
     /// Total number of nodes in memory
@@ -349,6 +355,19 @@ pub struct MemoryPanelData {
     pub edge_count: usize,
     /// Last update timestamp
     pub updated_at: String,
+    /// Tier of every node seen since the last snapshot, by ID
+    #[serde(skip)]
+    node_tiers: HashMap<String, String>,
+    /// Most nodes kept in `recent_nodes` as deltas are applied
+    #[serde(skip, default = "default_max_recent_nodes")]
+    max_recent_nodes: usize,
+}
+
+/// Default cap on [`MemoryPanelData::recent_nodes`].
+pub const DEFAULT_MAX_RECENT_NODES: usize = 20;
+
+fn default_max_recent_nodes() -> usize {
+    DEFAULT_MAX_RECENT_NODES
 }
 
 impl MemoryPanelData {
@@ -360,24 +379,111 @@ impl MemoryPanelData {
             tier_counts: TierCounts::default(),
             edge_count: 0,
             updated_at: Utc::now().to_rfc3339(),
+            node_tiers: HashMap::new(),
+            max_recent_nodes: DEFAULT_MAX_RECENT_NODES,
         }
     }
 
+    /// Keep at most `max` nodes in `recent_nodes` as deltas are applied.
+    pub fn with_max_recent_nodes(mut self, max: usize) -> Self {
+        self.max_recent_nodes = max;
+        self
+    }
+
+    /// Record the tiers of nodes not shown in `recent_nodes`.
+    ///
+    /// A producer seeds this from the memory store before emitting a
+    /// snapshot, so [`delta_for`](Self::delta_for) recognises a later
+    /// promotion of any node the snapshot counted, not only a listed one.
+    pub fn track_tiers(&mut self, tiers: impl IntoIterator<Item = (String, String)>) {
+        self.node_tiers.extend(tiers);
+    }
+
     /// Add a node view.
     pub fn add_node(&mut self, node: MemoryNodeView) {
-        // Update tier counts
-        match node.tier.as_str() {
-            "task" => self.tier_counts.task += 1,
-            "session" => self.tier_counts.session += 1,
-            "longterm" => self.tier_counts.long_term += 1,
-            "archive" => self.tier_counts.archive += 1,
-            _ => {}
+        if let Some(count) = self.tier_counts.slot_mut(&node.tier) {
+            *count += 1;
         }
         self.node_count += 1;
+        self.node_tiers.insert(node.id.clone(), node.tier.clone());
         self.recent_nodes.push(node);
         self.updated_at = Utc::now().to_rfc3339();
     }
 
+    /// The delta that brings this panel up to date with `node`.
+    ///
+    /// A node already seen in another tier yields a single
+    /// [`MemoryPanelDelta::Moved`] rather than a removal and an addition.
+    pub fn delta_for(&self, node: MemoryNodeView) -> MemoryPanelDelta {
+        match self.node_tiers.get(&node.id) {
+            None => MemoryPanelDelta::Added { node },
+            Some(tier) if *tier == node.tier => MemoryPanelDelta::Updated { node },
+            Some(tier) => MemoryPanelDelta::Moved {
+                from_tier: tier.clone(),
+                node,
+            },
+        }
+    }
+
+    /// Patch the panel in place with a delta.
+    ///
+    /// A snapshot keeps the tiers its panel tracked (see
+    /// [`track_tiers`](Self::track_tiers)) plus those of `recent_nodes`.
+    /// Tracked tiers are not serialized, so a snapshot received as JSON
+    /// only knows its listed nodes; consumers need no more, since
+    /// [`MemoryPanelDelta::Moved`] carries the old tier.
+    pub fn apply(&mut self, delta: &MemoryPanelDelta) {
+        match delta {
+            MemoryPanelDelta::Snapshot { panel } => {
+                let max_recent_nodes = self.max_recent_nodes;
+                *self = (**panel).clone();
+                self.max_recent_nodes = max_recent_nodes;
+                let listed: Vec<_> = self
+                    .recent_nodes
+                    .iter()
+                    .map(|n| (n.id.clone(), n.tier.clone()))
+                    .collect();
+                self.node_tiers.extend(listed);
+                self.truncate_to(max_recent_nodes);
+            }
+            MemoryPanelDelta::Added { node } => {
+                self.add_node(node.clone());
+                self.truncate_to(self.max_recent_nodes);
+            }
+            MemoryPanelDelta::Updated { node } => self.replace_view(node),
+            MemoryPanelDelta::Moved { from_tier, node } => {
+                if let Some(count) = self.tier_counts.slot_mut(from_tier) {
+                    *count = count.saturating_sub(1);
+                }
+                if let Some(count) = self.tier_counts.slot_mut(&node.tier) {
+                    *count += 1;
+                }
+                self.node_tiers.insert(node.id.clone(), node.tier.clone());
+                self.replace_view(node);
+            }
+            MemoryPanelDelta::Removed { id, tier } => {
+                if let Some(count) = self.tier_counts.slot_mut(tier) {
+                    *count = count.saturating_sub(1);
+                }
+                self.node_count = self.node_count.saturating_sub(1);
+                self.node_tiers.remove(id);
+                self.recent_nodes.retain(|n| n.id != *id);
+            }
+        }
+        self.updated_at = Utc::now().to_rfc3339();
+    }
+
+    /// Replace the displayed view of a node, or show it if it isn't listed.
+    fn replace_view(&mut self, node: &MemoryNodeView) {
+        match self.recent_nodes.iter_mut().find(|n| n.id == node.id) {
+            Some(existing) => *existing = node.clone(),
+            None => {
+                self.recent_nodes.push(node.clone());
+                self.truncate_to(self.max_recent_nodes);
+            }
+        }
+    }
+
     /// Keep only the most recent N nodes.
     pub fn truncate_to(&mut self, max_nodes: usize) {
         if self.recent_nodes.len() > max_nodes {
@@ -393,6 +499,28 @@ impl Default for MemoryPanelData {
     }
 }
 
+/// Incremental change to the memory panel.
+///
+/// A consumer loads a [`Snapshot`](Self::Snapshot) once and patches its copy
+/// with the deltas that follow, as [`MemoryPanelData::apply`] does.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum MemoryPanelDelta {
+    /// Full panel state, for the initial load or a resync
+    Snapshot { panel: Box<MemoryPanelData> },
+    /// A node was added
+    Added { node: MemoryNodeView },
+    /// A node changed without leaving its tier
+    Updated { node: MemoryNodeView },
+    /// A node moved between tiers (e.g. promoted); `node.tier` is the new tier
+    Moved {
+        from_tier: String,
+        node: MemoryNodeView,
+    },
+    /// A node was deleted
+    Removed { id: String, tier: String },
+}
+
 /// View of a memory node for display.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryNodeView {
@@ -444,7 +572,10 @@ impl MemoryNodeView {
 fn truncate_preview(content: &str, max_len: usize) -> String {
     let first_line = content.lines().next().unwrap_or(content);
     if first_line.len() > max_len {
-        format!("{}...", &first_line[..max_len - 3])
+        format!(
+            "{}...",
+            &first_line[..floor_char_boundary(first_line, max_len - 3)]
+        )
     } else {
         first_line.to_string()
     }
@@ -483,15 +614,79 @@ impl TierCounts {
             Tier::Archive => self.archive += 1,
         }
     }
+
+    /// Count for a tier as named in [`MemoryNodeView::tier`].
+    fn slot_mut(&mut self, tier: &str) -> Option<&mut usize> {
+        match tier {
+            "task" => Some(&mut self.task),
+            "session" => Some(&mut self.session),
+            "longterm" => Some(&mut self.long_term),
+            "archive" => Some(&mut self.archive),
+            _ => None,
+        }
+    }
 }
 
 // =============================================================================
 // Budget Panel
 // =============================================================================
 
+/// Default span of the burn-rate window in seconds.
+pub const DEFAULT_BURN_WINDOW_SECS: i64 = 60;
+
+/// Sliding window of cumulative spend for burn-rate forecasting.
+///
+/// The rate is the spend between the oldest and newest samples, where the
+/// oldest is the last one at or before the start of the window. It follows a
+/// change of pace within one window instead of averaging over the whole run.
+#[derive(Debug, Clone)]
+pub struct BurnRateWindow {
+    window: Duration,
+    samples: VecDeque<(DateTime<Utc>, f64)>,
+}
+
+impl BurnRateWindow {
+    /// Create a window spanning `window`.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Record the cumulative cost at a point in time.
+    pub fn record(&mut self, at: DateTime<Utc>, cumulative_cost: f64) {
+        self.samples.push_back((at, cumulative_cost));
+        let window_start = at - self.window;
+        while self.samples.len() > 2 && self.samples[1].0 <= window_start {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Spend rate across the window in USD per minute (0 with fewer than two samples).
+    pub fn rate_per_minute(&self) -> f64 {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return 0.0;
+        };
+        let minutes = (last.0 - first.0).num_milliseconds() as f64 / 60_000.0;
+        if minutes > 0.0 {
+            ((last.1 - first.1) / minutes).max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for BurnRateWindow {
+    fn default() -> Self {
+        Self::new(Duration::seconds(DEFAULT_BURN_WINDOW_SECS))
+    }
+}
+
 /// Data for the budget status panel.
 ///
-/// Displays cost tracking, token usage, and budget alerts.
+/// Displays cost tracking, token usage, budget alerts, and a forecast of
+/// when the cost limit will be reached at the recent burn rate.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BudgetPanelData {
     /// Current accumulated cost in USD
@@ -510,12 +705,21 @@ pub struct BudgetPanelData {
     pub utilization_percent: f64,
     /// Current burn rate (USD per minute)
     pub burn_rate: f64,
+    /// Burn rate over the recent window (USD per minute); the forecast
+    /// is based on this rate
+    #[serde(default)]
+    pub burn_rate_per_min: f64,
     /// Estimated time to budget exhaustion (seconds, None = N/A)
     pub estimated_exhaustion_secs: Option<u64>,
+    /// Projected time the cost limit is reached (RFC 3339, None = N/A)
+    pub projected_exhaustion: Option<String>,
     /// Active alerts
     pub alerts: Vec<String>,
     /// Last update timestamp
     pub updated_at: String,
+    /// Recent spend samples the burn rate is computed from
+    #[serde(skip)]
+    burn_window: BurnRateWindow,
 }
 
 impl BudgetPanelData {
@@ -530,9 +734,12 @@ impl BudgetPanelData {
             token_limit: None,
             utilization_percent: 0.0,
             burn_rate: 0.0,
+            burn_rate_per_min: 0.0,
             estimated_exhaustion_secs: None,
+            projected_exhaustion: None,
             alerts: Vec::new(),
             updated_at: Utc::now().to_rfc3339(),
+            burn_window: BurnRateWindow::default(),
         }
     }
 
@@ -547,34 +754,56 @@ impl BudgetPanelData {
 
     /// Update with new cost/token data.
     pub fn update(&mut self, cost_usd: f64, input_tokens: u64, output_tokens: u64) {
+        self.update_at(cost_usd, input_tokens, output_tokens, Utc::now());
+    }
+
+    /// Update with cost/token data observed at `at`, refreshing the burn
+    /// rate and exhaustion forecast.
+    pub fn update_at(
+        &mut self,
+        cost_usd: f64,
+        input_tokens: u64,
+        output_tokens: u64,
+        at: DateTime<Utc>,
+    ) {
         self.cost_usd = cost_usd;
         self.input_tokens = input_tokens;
         self.output_tokens = output_tokens;
         self.tokens_used = input_tokens + output_tokens;
-        self.updated_at = Utc::now().to_rfc3339();
+        self.updated_at = at.to_rfc3339();
 
         // Recalculate utilization
         if let Some(limit) = self.budget_limit {
             self.utilization_percent = (cost_usd / limit) * 100.0;
         }
+
+        self.burn_window.record(at, cost_usd);
+        self.burn_rate_per_min = self.burn_window.rate_per_minute();
+        self.burn_rate = self.burn_rate_per_min;
+        self.forecast(at);
     }
 
-    /// Set burn rate and estimated exhaustion.
+    /// Override the burn rate and recompute the exhaustion forecast.
     pub fn set_burn_rate(&mut self, rate_per_minute: f64) {
         self.burn_rate = rate_per_minute;
+        self.burn_rate_per_min = rate_per_minute;
+        self.forecast(Utc::now());
+    }
 
-        // Calculate estimated exhaustion
-        if rate_per_minute > 0.0 {
-            if let Some(limit) = self.budget_limit {
-                let remaining = limit - self.cost_usd;
-                if remaining > 0.0 {
-                    let minutes = remaining / rate_per_minute;
-                    self.estimated_exhaustion_secs = Some((minutes * 60.0) as u64);
-                } else {
-                    self.estimated_exhaustion_secs = Some(0);
-                }
+    /// Project when the cost limit is reached: immediately if already spent,
+    /// and not at all without a limit or while nothing is being spent.
+    fn forecast(&mut self, now: DateTime<Utc>) {
+        let secs = match self.budget_limit {
+            Some(limit) if self.cost_usd >= limit => Some(0.0),
+            Some(limit) if self.burn_rate_per_min > 0.0 => {
+                Some((limit - self.cost_usd) / self.burn_rate_per_min * 60.0)
             }
-        }
+            _ => None,
+        };
+
+        self.estimated_exhaustion_secs = secs.map(|s| s as u64);
+        self.projected_exhaustion =
+            secs.map(|s| (now + Duration::milliseconds((s * 1000.0) as i64)).to_rfc3339());
     }
 
     /// Add an alert.
@@ -668,6 +897,79 @@ mod tests {
         assert_eq!(counts.total(), 3);
     }
 
+    #[test]
+    fn test_memory_panel_applies_deltas() {
+        let mut panel = MemoryPanelData::new();
+        let node = Node::new(crate::memory::NodeType::Fact, "cache is warm");
+        let view = MemoryNodeView::from_node(&node);
+
+        let added = panel.delta_for(view.clone());
+        assert!(matches!(added, MemoryPanelDelta::Added { .. }));
+        panel.apply(&added);
+
+        let mut promoted = view.clone();
+        promoted.tier = Tier::Session.to_string();
+        let moved = panel.delta_for(promoted);
+        assert!(matches!(&moved, MemoryPanelDelta::Moved { from_tier, .. } if from_tier == "task"));
+        panel.apply(&moved);
+        assert_eq!((panel.tier_counts.task, panel.tier_counts.session), (0, 1));
+        assert_eq!(panel.recent_nodes.len(), 1);
+        assert_eq!(panel.recent_nodes[0].tier, "session");
+
+        panel.apply(&MemoryPanelDelta::Removed {
+            id: view.id,
+            tier: "session".to_string(),
+        });
+        assert_eq!(panel.node_count, 0);
+        assert_eq!(panel.tier_counts.total(), 0);
+        assert!(panel.recent_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_memory_panel_moves_tracked_node_outside_recent() {
+        let nodes: Vec<MemoryNodeView> = (0..3)
+            .map(|i| {
+                MemoryNodeView::from_node(&Node::new(
+                    crate::memory::NodeType::Fact,
+                    format!("fact {i}"),
+                ))
+            })
+            .collect();
+
+        let mut producer = MemoryPanelData::new().with_max_recent_nodes(2);
+        for node in &nodes {
+            let delta = producer.delta_for(node.clone());
+            producer.apply(&delta);
+        }
+        assert_eq!(producer.recent_nodes.len(), 2);
+
+        // The snapshot lists two nodes but counted all three
+        let mut snapshot = producer.clone();
+        snapshot.track_tiers(nodes.iter().map(|n| (n.id.clone(), n.tier.clone())));
+        producer.apply(&MemoryPanelDelta::Snapshot {
+            panel: Box::new(snapshot),
+        });
+        let mut consumer = MemoryPanelData::new().with_max_recent_nodes(2);
+        let json = serde_json::to_string(&MemoryPanelDelta::Snapshot {
+            panel: Box::new(producer.clone()),
+        })
+        .unwrap();
+        consumer.apply(&serde_json::from_str(&json).unwrap());
+
+        let mut promoted = nodes[0].clone();
+        promoted.tier = Tier::Session.to_string();
+        let delta = producer.delta_for(promoted);
+        assert!(matches!(&delta, MemoryPanelDelta::Moved { from_tier, .. } if from_tier == "task"));
+
+        for panel in [&mut producer, &mut consumer] {
+            panel.apply(&delta);
+            assert_eq!(panel.node_count, 3);
+            assert_eq!((panel.tier_counts.task, panel.tier_counts.session), (2, 1));
+            assert_eq!(panel.recent_nodes.len(), 2);
+            assert_eq!(panel.recent_nodes[1].id, nodes[0].id);
+        }
+    }
+
     #[test]
     fn test_budget_panel_limits() {
         let mut panel = BudgetPanelData::with_limits(Some(1.0), Some(100_000));
@@ -681,6 +983,41 @@ mod tests {
         assert!(panel.is_warning());
     }
 
+    #[test]
+    fn test_budget_panel_burn_rate_forecast() {
+        let t0 = Utc::now();
+        let at = |secs| t0 + Duration::seconds(secs);
+        let mut panel = BudgetPanelData::with_limits(Some(1.0), None);
+
+        // No spend, no forecast
+        panel.update_at(0.0, 0, 0, at(0));
+        panel.update_at(0.0, 0, 0, at(10));
+        assert_eq!(panel.burn_rate_per_min, 0.0);
+        assert_eq!(panel.projected_exhaustion, None);
+
+        // $0.20/min leaves $0.80 for four minutes
+        panel.update_at(0.1, 1_000, 500, at(40));
+        panel.update_at(0.2, 2_000, 1_000, at(70));
+        assert!((panel.burn_rate_per_min - 0.2).abs() < 1e-9);
+        assert_eq!(panel.estimated_exhaustion_secs, Some(240));
+        assert_eq!(panel.projected_exhaustion, Some(at(70 + 240).to_rfc3339()));
+
+        // Speeding up: the window drops the slower early samples
+        panel.update_at(0.5, 5_000, 2_500, at(100));
+        assert!((panel.burn_rate_per_min - 0.4).abs() < 1e-9);
+        assert_eq!(panel.estimated_exhaustion_secs, Some(75));
+
+        // Already exhausted
+        panel.update_at(1.2, 9_000, 4_500, at(110));
+        assert_eq!(panel.estimated_exhaustion_secs, Some(0));
+        assert_eq!(panel.projected_exhaustion, Some(at(110).to_rfc3339()));
+
+        // The TUI still reads `burn_rate`; both fields are serialized.
+        let json = serde_json::to_value(&panel).unwrap();
+        assert_eq!(json["burn_rate"], json["burn_rate_per_min"]);
+        assert!(json["burn_rate"].as_f64().unwrap() > 0.0);
+    }
+
     #[test]
     fn test_event_style_from_type() {
         assert_eq!(
@@ -706,5 +1043,11 @@ mod tests {
         let truncated = truncate_preview(long, 20);
         assert!(truncated.ends_with("..."));
         assert!(truncated.len() <= 20);
+
+        // Byte 17 is inside the third emoji
+        assert_eq!(
+            truncate_preview("Result: 🎉🎉🎉 done", 20),
+            "Result: 🎉🎉..."
+        );
     }
 }
@@ -0,0 +1,236 @@
+//! Caching of adversarial validation results.
+//!
+//! Re-reviewing an unchanged diff should not pay for another adversarial
+//! call. Results are keyed by a hash of everything that shapes the review
+//! prompt: the request and response, code files, tool outputs, spec
+//! references, prior iterations, the model and the strategy set. Changing
+//! any of these produces a new key, so stale results are never returned.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::types::{
+    AdversarialConfig, CodeFile, IssueCategory, IssueSeverity, ToolOutput, ValidationContext,
+    ValidationResult,
+};
+use crate::error::{Error, Result};
+
+/// Storage for validation results keyed by [`cache_key`].
+pub trait ValidationCache: Send + Sync {
+    /// Look up a stored result.
+    fn get(&self, key: &str) -> Option<ValidationResult>;
+
+    /// Store a result.
+    fn put(&self, key: &str, result: &ValidationResult) -> Result<()>;
+
+    /// Number of stored results.
+    fn len(&self) -> usize;
+
+    /// Whether the cache is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Everything that influences a review, in a stable serialized order.
+#[derive(Serialize)]
+struct KeyMaterial<'a> {
+    model: &'a str,
+    min_confidence: f64,
+    strategies: &'a [&'a str],
+    request: &'a str,
+    response: &'a str,
+    code_context: &'a [CodeFile],
+    tool_outputs: &'a [ToolOutput],
+    relevant_specs: &'a [String],
+    /// Prior issues without their random IDs and timestamps.
+    prior_issues: Vec<Vec<(IssueSeverity, IssueCategory, &'a str, &'a str)>>,
+}
+
+/// Compute the cache key for validating `context` with `strategies`.
+pub fn cache_key(
+    context: &ValidationContext,
+    config: &AdversarialConfig,
+    strategies: &[&str],
+) -> String {
+    let material = KeyMaterial {
+        model: &config.model,
+        min_confidence: config.min_confidence,
+        strategies,
+        request: &context.request,
+        response: &context.response,
+        code_context: &context.code_context,
+        tool_outputs: &context.tool_outputs,
+        relevant_specs: &context.relevant_specs,
+        prior_issues: context
+            .prior_iterations
+            .iter()
+            .map(|iteration| {
+                iteration
+                    .issues
+                    .iter()
+                    .map(|i| {
+                        (
+                            i.severity,
+                            i.category,
+                            i.title.as_str(),
+                            i.description.as_str(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+    };
+
+    let mut hasher = Sha256::new();
+    // Serializing plain data into a Vec cannot fail.
+    hasher.update(serde_json::to_vec(&material).unwrap_or_default());
+    format!("{:x}", hasher.finalize())
+}
+
+/// In-memory validation cache (the default).
+#[derive(Debug, Default)]
+pub struct InMemoryValidationCache {
+    entries: Mutex<HashMap<String, ValidationResult>>,
+}
+
+impl InMemoryValidationCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ValidationCache for InMemoryValidationCache {
+    fn get(&self, key: &str) -> Option<ValidationResult> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .cloned()
+    }
+
+    fn put(&self, key: &str, result: &ValidationResult) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), result.clone());
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+/// Validation cache persisted to a JSON file.
+///
+/// The file is read once on open and rewritten on every insert, so results
+/// survive across `/dp:review` runs.
+#[derive(Debug)]
+pub struct FileValidationCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, ValidationResult>>,
+}
+
+impl FileValidationCache {
+    /// Open a cache file, starting empty if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = if path.exists() {
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|e| Error::Internal(format!("Failed to read validation cache: {}", e)))?;
+            serde_json::from_str(&contents).map_err(Error::Serialization)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Path of the backing file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ValidationCache for FileValidationCache {
+    fn get(&self, key: &str) -> Option<ValidationResult> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .cloned()
+    }
+
+    fn put(&self, key: &str, result: &ValidationResult) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(key.to_string(), result.clone());
+
+        let json = serde_json::to_string_pretty(&*entries).map_err(Error::Serialization)?;
+        std::fs::write(&self.path, json)
+            .map_err(|e| Error::Internal(format!("Failed to write validation cache: {}", e)))
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adversarial::types::ValidationVerdict;
+
+    fn context() -> ValidationContext {
+        ValidationContext::new("Fix the bug", "Fixed it")
+            .with_code_file(CodeFile::new("src/lib.rs", "fn a() {}"))
+            .with_spec("SPEC-01.01")
+    }
+
+    #[test]
+    fn test_key_invalidation() {
+        let config = AdversarialConfig::default();
+        let base = cache_key(&context(), &config, &["critic"]);
+
+        // Fresh context IDs do not affect the key.
+        assert_eq!(base, cache_key(&context(), &config, &["critic"]));
+
+        let mut edited = context();
+        edited.code_context[0].content = "fn a() { todo!() }".to_string();
+        assert_ne!(base, cache_key(&edited, &config, &["critic"]));
+
+        assert_ne!(
+            base,
+            cache_key(&context(), &config, &["critic", "security"])
+        );
+        assert_ne!(
+            base,
+            cache_key(&context().with_spec("SPEC-01.02"), &config, &["critic"])
+        );
+    }
+
+    #[test]
+    fn test_file_cache_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("validation_cache.json");
+        let result = ValidationResult::new(context().id).complete(ValidationVerdict::Approved);
+
+        let cache = FileValidationCache::open(&path).unwrap();
+        assert!(cache.is_empty());
+        cache.put("abc", &result).unwrap();
+
+        let reopened = FileValidationCache::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(
+            reopened.get("abc").unwrap().verdict,
+            ValidationVerdict::Approved
+        );
+    }
+}
@@ -9,9 +9,11 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, instrument};
 
+use super::cache::{InMemoryValidationCache, ValidationCache};
 use super::types::{AdversarialConfig, ValidationContext, ValidationResult};
 use super::validator::{AdversarialValidator, GeminiValidator};
 use crate::error::{Error, Result};
+use crate::llm::SampleStats;
 
 /// Trait for fresh context invocation.
 ///
@@ -36,6 +38,9 @@ pub trait FreshContextInvoker: Send + Sync {
 }
 
 /// Statistics about fresh context invocations.
+///
+/// Results served from the validation cache are counted in `cache_hits`
+/// only; the other counters cover real model invocations.
 #[derive(Debug, Clone, Default)]
 pub struct InvocationStats {
     /// Total invocations
@@ -48,20 +53,73 @@ pub struct InvocationStats {
     pub total_cost_usd: f64,
     /// Average latency in milliseconds
     pub avg_latency_ms: f64,
+    /// Validations answered from the cache
+    pub cache_hits: u64,
+    /// Per-invocation cost distribution, when enabled with [`Self::with_samples`]
+    pub cost_samples: Option<SampleStats>,
+    /// Per-invocation latency distribution in milliseconds, when enabled
+    pub latency_samples: Option<SampleStats>,
 }
 
 impl InvocationStats {
+    /// Also keep per-invocation cost and latency distributions.
+    pub fn with_samples(mut self) -> Self {
+        self.cost_samples.get_or_insert_with(SampleStats::new);
+        self.latency_samples.get_or_insert_with(SampleStats::new);
+        self
+    }
+
+    /// Estimated `p`th percentile (0–100) of invocation latency in milliseconds.
+    pub fn latency_percentile(&self, p: f64) -> Option<f64> {
+        self.latency_samples.as_ref()?.percentile(p)
+    }
+
+    /// Estimated `p`th percentile (0–100) of invocation cost.
+    pub fn cost_percentile(&self, p: f64) -> Option<f64> {
+        self.cost_samples.as_ref()?.percentile(p)
+    }
+
     /// Record a successful invocation.
     pub fn record_success(&mut self, cost: f64, latency_ms: u64) {
         self.total_invocations += 1;
         self.successful_invocations += 1;
         self.total_cost_usd += cost;
+        if let Some(samples) = &mut self.cost_samples {
+            samples.record(cost);
+        }
+        if let Some(samples) = &mut self.latency_samples {
+            samples.record(latency_ms as f64);
+        }
 
         // Update rolling average
         let n = self.successful_invocations as f64;
         self.avg_latency_ms = ((n - 1.0) * self.avg_latency_ms + latency_ms as f64) / n;
     }
 
+    /// Record a validation answered from the cache.
+    pub fn record_cache_hit(&mut self) {
+        self.cache_hits += 1;
+    }
+
+    /// Record the outcome of a validation, distinguishing cache hits.
+    pub fn record_result(&mut self, result: &ValidationResult, latency_ms: u64) {
+        if result.cached {
+            self.record_cache_hit();
+        } else {
+            self.record_success(result.cost_usd, latency_ms);
+        }
+    }
+
+    /// Fraction of validations answered from the cache.
+    pub fn cache_hit_rate(&self) -> f64 {
+        let total = self.cache_hits + self.total_invocations;
+        if total == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / total as f64
+        }
+    }
+
     /// Record a failed invocation.
     pub fn record_failure(&mut self) {
         self.total_invocations += 1;
@@ -87,6 +145,7 @@ pub struct GeminiFreshInvoker {
     api_key: String,
     config: AdversarialConfig,
     stats: Arc<RwLock<InvocationStats>>,
+    cache: Arc<dyn ValidationCache>,
 }
 
 impl GeminiFreshInvoker {
@@ -96,9 +155,16 @@ impl GeminiFreshInvoker {
             api_key: api_key.into(),
             config,
             stats: Arc::new(RwLock::new(InvocationStats::default())),
+            cache: Arc::new(InMemoryValidationCache::new()),
         }
     }
 
+    /// Share results through `cache` (e.g. a `FileValidationCache`).
+    pub fn with_cache(mut self, cache: Arc<dyn ValidationCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
     /// Create with custom stats (for testing).
     pub fn with_stats(mut self, stats: InvocationStats) -> Self {
         self.stats = Arc::new(RwLock::new(stats));
@@ -114,9 +180,9 @@ impl FreshContextInvoker for GeminiFreshInvoker {
 
         let start = std::time::Instant::now();
 
-        // Create a brand new validator instance - no shared state
+        // Create a brand new validator instance - only the result cache is shared
         let validator = match GeminiValidator::new(&self.api_key, self.config.clone()) {
-            Ok(v) => v,
+            Ok(v) => v.with_cache(Arc::clone(&self.cache)),
             Err(e) => {
                 self.stats.write().await.record_failure();
                 return Err(e);
@@ -139,7 +205,7 @@ impl FreshContextInvoker for GeminiFreshInvoker {
         // Record success
         {
             let mut stats = self.stats.write().await;
-            stats.record_success(result.cost_usd, latency_ms);
+            stats.record_result(&result, latency_ms);
         }
 
         info!(
@@ -179,6 +245,7 @@ pub struct PooledFreshInvoker {
     config: AdversarialConfig,
     pool_size: usize,
     stats: Arc<RwLock<InvocationStats>>,
+    cache: Arc<dyn ValidationCache>,
 }
 
 impl PooledFreshInvoker {
@@ -189,19 +256,30 @@ impl PooledFreshInvoker {
             config,
             pool_size: pool_size.max(1),
             stats: Arc::new(RwLock::new(InvocationStats::default())),
+            cache: Arc::new(InMemoryValidationCache::new()),
         }
     }
+
+    /// Share results through `cache` (e.g. a `FileValidationCache`).
+    pub fn with_cache(mut self, cache: Arc<dyn ValidationCache>) -> Self {
+        self.cache = cache;
+        self
+    }
 }
 
 #[async_trait]
 impl FreshContextInvoker for PooledFreshInvoker {
     async fn invoke_fresh(&self, context: &ValidationContext) -> Result<ValidationResult> {
-        // For now, same as GeminiFreshInvoker - pooling can be added later
-        // when we have benchmarks showing it's needed
+        // Validators are still created per call; the pool size bounds how
+        // many strategies each validation runs concurrently.
         let start = std::time::Instant::now();
 
-        let validator = match GeminiValidator::new(&self.api_key, self.config.clone()) {
-            Ok(v) => v,
+        let config = AdversarialConfig {
+            max_concurrent_strategies: self.pool_size,
+            ..self.config.clone()
+        };
+        let validator = match GeminiValidator::new(&self.api_key, config) {
+            Ok(v) => v.with_cache(Arc::clone(&self.cache)),
             Err(e) => {
                 self.stats.write().await.record_failure();
                 return Err(e);
@@ -217,10 +295,7 @@ impl FreshContextInvoker for PooledFreshInvoker {
         };
 
         let latency_ms = start.elapsed().as_millis() as u64;
-        self.stats
-            .write()
-            .await
-            .record_success(result.cost_usd, latency_ms);
+        self.stats.write().await.record_result(&result, latency_ms);
 
         Ok(result)
     }
@@ -243,6 +318,7 @@ pub struct FreshInvokerBuilder {
     config: AdversarialConfig,
     pooled: bool,
     pool_size: usize,
+    cache: Option<Arc<dyn ValidationCache>>,
 }
 
 impl FreshInvokerBuilder {
@@ -253,6 +329,7 @@ impl FreshInvokerBuilder {
             config: AdversarialConfig::default(),
             pooled: false,
             pool_size: 4,
+            cache: None,
         }
     }
 
@@ -275,20 +352,30 @@ impl FreshInvokerBuilder {
         self
     }
 
+    /// Share results through `cache` instead of a private in-memory cache.
+    pub fn with_cache(mut self, cache: Arc<dyn ValidationCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     /// Build the invoker.
     pub fn build(self) -> Result<Box<dyn FreshContextInvoker>> {
         let api_key = self
             .api_key
             .ok_or_else(|| Error::Config("API key required for fresh invoker".to_string()))?;
 
+        let cache = self
+            .cache
+            .unwrap_or_else(|| Arc::new(InMemoryValidationCache::new()));
+
         if self.pooled {
-            Ok(Box::new(PooledFreshInvoker::new(
-                api_key,
-                self.config,
-                self.pool_size,
-            )))
+            Ok(Box::new(
+                PooledFreshInvoker::new(api_key, self.config, self.pool_size).with_cache(cache),
+            ))
         } else {
-            Ok(Box::new(GeminiFreshInvoker::new(api_key, self.config)))
+            Ok(Box::new(
+                GeminiFreshInvoker::new(api_key, self.config).with_cache(cache),
+            ))
         }
     }
 }
@@ -317,6 +404,38 @@ mod tests {
         assert!((stats.total_cost_usd - 0.003).abs() < 0.0001);
         assert!((stats.avg_latency_ms - 150.0).abs() < 0.1);
         assert!((stats.success_rate() - 0.6667).abs() < 0.01);
+        assert!(stats.latency_samples.is_none());
+        assert_eq!(stats.latency_percentile(50.0), None);
+    }
+
+    #[test]
+    fn test_invocation_stats_samples() {
+        let mut stats = InvocationStats::default().with_samples();
+        for latency_ms in 1..=100 {
+            stats.record_success(0.001, latency_ms);
+        }
+        stats.record_failure();
+
+        let latency = stats.latency_samples.as_ref().unwrap();
+        assert_eq!(latency.count(), 100);
+        assert_eq!(latency.max(), Some(100.0));
+        assert!((stats.latency_percentile(95.0).unwrap() - 95.0).abs() <= 2.0);
+        assert!((stats.cost_percentile(50.0).unwrap() - 0.001).abs() < 0.00002);
+    }
+
+    #[test]
+    fn test_invocation_stats_cache_hits() {
+        let mut stats = InvocationStats::default();
+        let mut result = ValidationResult::new(Default::default()).with_cost(0.002);
+
+        stats.record_result(&result, 100);
+        result.cached = true;
+        stats.record_result(&result, 1);
+
+        assert_eq!(stats.total_invocations, 1);
+        assert_eq!(stats.cache_hits, 1);
+        assert!((stats.total_cost_usd - 0.002).abs() < 1e-9);
+        assert!((stats.cache_hit_rate() - 0.5).abs() < 1e-9);
     }
 
     #[test]
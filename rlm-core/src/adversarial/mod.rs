@@ -1,8 +1,8 @@
 //! Adversarial validation for LLM outputs.
 //!
 //! This module provides adversarial review capabilities using a different
-//! model provider (Google/Gemini) to validate outputs from the primary
-//! model (Anthropic/Claude). This cross-provider validation helps catch
+//! model provider (Google/Gemini, or OpenAI via `OpenAIValidator`) to
+//! validate outputs from the primary model (Anthropic/Claude). This cross-provider validation helps catch
 //! issues that might be missed due to shared biases.
 //!
 //! ## Architecture
@@ -95,15 +95,23 @@
 //! - Gemini 2.0 Flash: ~$0.075/1M input, $0.30/1M output
 //! - Typical review: ~5K input, ~2K output = ~$0.001 per review
 //! - Multi-iteration: Up to 3x cost for max_iterations=3
-//!
-//! Configure triggers carefully to balance coverage vs. cost.
+//! - Each configured strategy is a separate request; strategies run
+//!   concurrently (bounded by `max_concurrent_strategies`) and their issues
+//!   are merged
+//!
+//! Configure triggers carefully to balance coverage vs. cost. Validators
+//! cache results by a hash of the validation context, so re-reviewing an
+//! unchanged diff is free; use `FileValidationCache` to keep results
+//! across runs.
 
+pub mod cache;
 pub mod invoker;
 pub mod strategies;
 pub mod types;
 pub mod validator;
 
 // Re-exports
+pub use cache::{cache_key, FileValidationCache, InMemoryValidationCache, ValidationCache};
 pub use invoker::{
     FreshContextInvoker, FreshInvokerBuilder, GeminiFreshInvoker, InvocationStats,
     PooledFreshInvoker,
@@ -117,7 +125,7 @@ pub use types::{
     IssueSeverity, ToolOutput, ValidationContext, ValidationId, ValidationIteration,
     ValidationResult, ValidationStats, ValidationVerdict,
 };
-pub use validator::{AdversarialValidator, GeminiValidator};
+pub use validator::{AdversarialValidator, GeminiValidator, OpenAIValidator};
 
 #[cfg(test)]
 pub use validator::MockValidator;
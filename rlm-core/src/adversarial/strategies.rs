@@ -1,6 +1,11 @@
 //! Validation strategies for adversarial review.
 //!
-//! Each strategy focuses on a specific type of issue detection.
+//! Each strategy focuses on a specific type of issue detection. Custom
+//! strategies can be added with [`StrategyFactory::register`] and are then
+//! selected by name from `AdversarialConfig::strategies` like the built-ins.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
 
 use super::types::{Issue, IssueCategory, IssueSeverity, ValidationContext};
 
@@ -21,6 +26,23 @@ pub trait ValidationStrategy: Send + Sync {
     /// Build strategy-specific prompt additions.
     fn prompt_additions(&self, context: &ValidationContext) -> String;
 
+    /// Complete prompt for reviewing `context` with this strategy alone.
+    ///
+    /// Strategies returning `Some` are run as a separate request instead of
+    /// being folded into the combined review prompt.
+    fn prompt_template(&self, context: &ValidationContext) -> Option<String> {
+        let _ = context;
+        None
+    }
+
+    /// Parse issues from the response to [`Self::prompt_template`].
+    ///
+    /// Returning `None` falls back to the standard `ISSUE:` block format.
+    fn parse_issues(&self, response: &str) -> Option<Vec<Issue>> {
+        let _ = response;
+        None
+    }
+
     /// Post-process issues to add strategy-specific context.
     fn post_process(&self, issues: &mut [Issue]) {
         // Default: no post-processing
@@ -28,6 +50,42 @@ pub trait ValidationStrategy: Send + Sync {
     }
 }
 
+/// Registered strategies are shared, so each lookup hands out a new box
+/// around the same instance.
+impl<T: ValidationStrategy + ?Sized> ValidationStrategy for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    fn categories(&self) -> Vec<IssueCategory> {
+        (**self).categories()
+    }
+
+    fn prompt_additions(&self, context: &ValidationContext) -> String {
+        (**self).prompt_additions(context)
+    }
+
+    fn prompt_template(&self, context: &ValidationContext) -> Option<String> {
+        (**self).prompt_template(context)
+    }
+
+    fn parse_issues(&self, response: &str) -> Option<Vec<Issue>> {
+        (**self).parse_issues(response)
+    }
+
+    fn post_process(&self, issues: &mut [Issue]) {
+        (**self).post_process(issues)
+    }
+}
+
+/// Strategies added via [`StrategyFactory::register`].
+static REGISTRY: LazyLock<RwLock<HashMap<String, Arc<dyn ValidationStrategy>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
 /// General code critic strategy.
 ///
 /// Looks for logic errors, missing error handling, and general code quality issues.
@@ -389,23 +447,48 @@ impl ValidationStrategy for TraceabilityStrategy {
 pub struct StrategyFactory;
 
 impl StrategyFactory {
-    /// Create strategies from names.
-    pub fn from_names(names: &[String]) -> Vec<Box<dyn ValidationStrategy>> {
-        let mut strategies: Vec<Box<dyn ValidationStrategy>> = Vec::new();
-
-        for name in names {
-            match name.as_str() {
-                "critic" => strategies.push(Box::new(CriticStrategy::new())),
-                "edge_case" => strategies.push(Box::new(EdgeCaseStrategy::new())),
-                "security" => strategies.push(Box::new(SecurityStrategy::new())),
-                "performance" => strategies.push(Box::new(PerformanceStrategy::new())),
-                "testing" => strategies.push(Box::new(TestingStrategy::new())),
-                "traceability" => strategies.push(Box::new(TraceabilityStrategy::new())),
-                _ => {}
-            }
+    /// Register a custom strategy under `name`.
+    ///
+    /// Registered strategies take precedence over built-ins of the same
+    /// name. Returns `true` if an earlier registration was replaced.
+    pub fn register(name: impl Into<String>, strategy: Box<dyn ValidationStrategy>) -> bool {
+        REGISTRY
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.into(), Arc::from(strategy))
+            .is_some()
+    }
+
+    /// Remove a registered strategy. Returns `true` if it was registered.
+    pub fn unregister(name: &str) -> bool {
+        REGISTRY
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(name)
+            .is_some()
+    }
+
+    /// Create a strategy by name, checking registered strategies first.
+    pub fn create(name: &str) -> Option<Box<dyn ValidationStrategy>> {
+        if let Some(strategy) = REGISTRY.read().unwrap_or_else(|e| e.into_inner()).get(name) {
+            return Some(Box::new(Arc::clone(strategy)));
         }
 
-        strategies
+        let strategy: Box<dyn ValidationStrategy> = match name {
+            "critic" => Box::new(CriticStrategy::new()),
+            "edge_case" => Box::new(EdgeCaseStrategy::new()),
+            "security" => Box::new(SecurityStrategy::new()),
+            "performance" => Box::new(PerformanceStrategy::new()),
+            "testing" => Box::new(TestingStrategy::new()),
+            "traceability" => Box::new(TraceabilityStrategy::new()),
+            _ => return None,
+        };
+        Some(strategy)
+    }
+
+    /// Create strategies from names, skipping unknown ones.
+    pub fn from_names(names: &[String]) -> Vec<Box<dyn ValidationStrategy>> {
+        names.iter().filter_map(|name| Self::create(name)).collect()
     }
 
     /// Create a comprehensive strategy set.
@@ -476,6 +559,69 @@ mod tests {
         assert_eq!(strategies[1].name(), "security");
     }
 
+    struct AccessibilityStrategy;
+
+    impl ValidationStrategy for AccessibilityStrategy {
+        fn name(&self) -> &str {
+            "a11y"
+        }
+
+        fn description(&self) -> &str {
+            "Accessibility checklist"
+        }
+
+        fn categories(&self) -> Vec<IssueCategory> {
+            vec![IssueCategory::Other]
+        }
+
+        fn prompt_additions(&self, _context: &ValidationContext) -> String {
+            String::new()
+        }
+
+        fn prompt_template(&self, context: &ValidationContext) -> Option<String> {
+            Some(format!("Check for missing alt text:\n{}", context.response))
+        }
+
+        fn parse_issues(&self, response: &str) -> Option<Vec<Issue>> {
+            Some(
+                response
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("A11Y: "))
+                    .map(|title| Issue::new(IssueSeverity::Low, IssueCategory::Other, title, ""))
+                    .collect(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_register_custom_strategy() {
+        assert!(StrategyFactory::create("a11y_factory_test").is_none());
+        assert!(!StrategyFactory::register(
+            "a11y_factory_test",
+            Box::new(AccessibilityStrategy)
+        ));
+
+        let strategies =
+            StrategyFactory::from_names(&["critic".to_string(), "a11y_factory_test".to_string()]);
+        assert_eq!(strategies.len(), 2);
+        assert_eq!(strategies[1].name(), "a11y");
+
+        let ctx = ValidationContext::new("request", "<img src=x>");
+        assert!(strategies[1]
+            .prompt_template(&ctx)
+            .unwrap()
+            .contains("<img"));
+        let issues = strategies[1]
+            .parse_issues("A11Y: Missing alt\nnoise")
+            .unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].title, "Missing alt");
+        assert!(strategies[0].prompt_template(&ctx).is_none());
+
+        assert!(StrategyFactory::unregister("a11y_factory_test"));
+        assert!(StrategyFactory::create("a11y_factory_test").is_none());
+    }
+
     #[test]
     fn test_traceability_with_specs() {
         let strategy = TraceabilityStrategy::new();
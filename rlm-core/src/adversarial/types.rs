@@ -411,6 +411,12 @@ pub struct ValidationResult {
     pub completed_at: DateTime<Utc>,
     /// Total cost in dollars
     pub cost_usd: f64,
+    /// Whether this result was served from a [`ValidationCache`](super::cache::ValidationCache)
+    #[serde(default)]
+    pub cached: bool,
+    /// Strategies whose review request failed (their issues are missing)
+    #[serde(default)]
+    pub failed_strategies: Vec<String>,
 }
 
 impl ValidationResult {
@@ -427,6 +433,8 @@ impl ValidationResult {
             started_at: now,
             completed_at: now,
             cost_usd: 0.0,
+            cached: false,
+            failed_strategies: Vec::new(),
         }
     }
 
@@ -574,6 +582,13 @@ pub struct AdversarialConfig {
     pub include_code_context: bool,
     /// Maximum code context size in bytes
     pub max_code_context_bytes: usize,
+    /// Maximum number of strategies reviewed concurrently
+    #[serde(default = "default_max_concurrent_strategies")]
+    pub max_concurrent_strategies: usize,
+}
+
+fn default_max_concurrent_strategies() -> usize {
+    4
 }
 
 impl Default for AdversarialConfig {
@@ -588,6 +603,7 @@ impl Default for AdversarialConfig {
             min_confidence: 0.7,
             include_code_context: true,
             max_code_context_bytes: 50_000,
+            max_concurrent_strategies: default_max_concurrent_strategies(),
         }
     }
 }
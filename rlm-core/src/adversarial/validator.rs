@@ -4,15 +4,21 @@
 //! for different validation backends.
 
 use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
 use tracing::{debug, info, instrument, warn};
 
-use super::strategies::{CriticStrategy, EdgeCaseStrategy, SecurityStrategy, ValidationStrategy};
+use super::cache::{cache_key, InMemoryValidationCache, ValidationCache};
+use super::strategies::{CriticStrategy, StrategyFactory, ValidationStrategy};
 use super::types::{
-    AdversarialConfig, Issue, ValidationContext, ValidationIteration, ValidationResult,
-    ValidationStats, ValidationVerdict,
+    AdversarialConfig, Issue, IssueSeverity, ValidationContext, ValidationIteration,
+    ValidationResult, ValidationStats, ValidationVerdict,
 };
 use crate::error::Result;
-use crate::llm::{ChatMessage, ClientConfig, CompletionRequest, GoogleClient, LLMClient};
+use crate::llm::{
+    ChatMessage, ClientConfig, CompletionRequest, CompletionResponse, GoogleClient, LLMClient,
+    ModelSpec, OpenAIClient,
+};
 
 /// Trait for adversarial validation.
 ///
@@ -65,6 +71,7 @@ pub struct GeminiValidator {
     client: GoogleClient,
     config: AdversarialConfig,
     strategies: Vec<Box<dyn ValidationStrategy>>,
+    cache: Option<Arc<dyn ValidationCache>>,
 }
 
 impl GeminiValidator {
@@ -83,19 +90,33 @@ impl GeminiValidator {
             client,
             config,
             strategies,
+            cache: Some(Arc::new(InMemoryValidationCache::new())),
         })
     }
 
+    /// Use `cache` for results instead of the default in-memory cache.
+    pub fn with_cache(mut self, cache: Arc<dyn ValidationCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Always call the model, never reusing earlier results.
+    pub fn without_cache(mut self) -> Self {
+        self.cache = None;
+        self
+    }
+
     /// Initialize validation strategies from config.
+    ///
+    /// Names resolve through [`StrategyFactory::create`], so strategies
+    /// added with [`StrategyFactory::register`] are available too.
     fn init_strategies(config: &AdversarialConfig) -> Vec<Box<dyn ValidationStrategy>> {
         let mut strategies: Vec<Box<dyn ValidationStrategy>> = Vec::new();
 
         for name in &config.strategies {
-            match name.as_str() {
-                "critic" => strategies.push(Box::new(CriticStrategy::new())),
-                "edge_case" => strategies.push(Box::new(EdgeCaseStrategy::new())),
-                "security" => strategies.push(Box::new(SecurityStrategy::new())),
-                _ => {
+            match StrategyFactory::create(name) {
+                Some(strategy) => strategies.push(strategy),
+                None => {
                     warn!("Unknown validation strategy: {}", name);
                 }
             }
@@ -108,349 +129,630 @@ impl GeminiValidator {
 
         strategies
     }
+}
 
-    /// Build the validation prompt.
-    fn build_prompt(&self, context: &ValidationContext) -> String {
-        let mut prompt = String::new();
-
-        prompt.push_str("You are an adversarial code reviewer. Your job is to find issues, bugs, and potential problems in the following code change.\n\n");
-        prompt.push_str("## Original Request\n");
-        prompt.push_str(&context.request);
-        prompt.push_str("\n\n## Response Being Reviewed\n");
-        prompt.push_str(&context.response);
-        prompt.push_str("\n\n");
-
-        // Add code context
-        if !context.code_context.is_empty() {
-            prompt.push_str("## Code Context\n");
-            for file in &context.code_context {
-                prompt.push_str(&format!("### {}\n```", file.path));
-                if let Some(ref lang) = file.language {
-                    prompt.push_str(lang);
-                }
-                prompt.push_str("\n");
-                prompt.push_str(&file.content);
-                prompt.push_str("\n```\n\n");
-            }
-        }
+#[async_trait]
+impl AdversarialValidator for GeminiValidator {
+    #[instrument(skip(self, context), fields(validation_id = %context.id))]
+    async fn validate(&self, context: &ValidationContext) -> Result<ValidationResult> {
+        review(
+            &self.client,
+            &self.config,
+            &self.strategies,
+            self.cache.as_deref(),
+            context,
+            |response| {
+                response.usage.input_tokens as f64 * 0.000075 / 1000.0
+                    + response.usage.output_tokens as f64 * 0.0003 / 1000.0
+            },
+        )
+        .await
+    }
 
-        // Add tool outputs
-        if !context.tool_outputs.is_empty() {
-            prompt.push_str("## Tool Outputs\n");
-            for output in &context.tool_outputs {
-                prompt.push_str(&format!(
-                    "### {} ({})\nInput: {}\nOutput: {}\n\n",
-                    output.tool,
-                    if output.success { "success" } else { "failed" },
-                    output.input,
-                    output.output
-                ));
-            }
-        }
+    async fn validate_iterative(
+        &self,
+        context: &mut ValidationContext,
+        max_iterations: usize,
+    ) -> Result<ValidationResult> {
+        review_iterative(self, context, max_iterations).await
+    }
 
-        // Add prior iterations
-        if !context.prior_iterations.is_empty() {
-            prompt.push_str("## Prior Review Iterations\n");
-            for iter in &context.prior_iterations {
-                prompt.push_str(&format!("### Iteration {}\n", iter.iteration));
-                prompt.push_str("Issues found:\n");
-                for issue in &iter.issues {
-                    prompt.push_str(&format!(
-                        "- [{:?}] {}: {}\n",
-                        issue.severity, issue.title, issue.description
-                    ));
-                }
-                if let Some(ref response) = iter.response {
-                    prompt.push_str(&format!("Response: {}\n", response));
-                }
-                prompt.push('\n');
-            }
-        }
+    fn config(&self) -> &AdversarialConfig {
+        &self.config
+    }
+}
+
+/// Adversarial validator using OpenAI models.
+///
+/// Cross-provider alternative to [`GeminiValidator`] for teams without
+/// Gemini access. Prompts, issue parsing and verdicts are shared with it.
+pub struct OpenAIValidator {
+    client: OpenAIClient,
+    config: AdversarialConfig,
+    strategies: Vec<Box<dyn ValidationStrategy>>,
+    cache: Option<Arc<dyn ValidationCache>>,
+}
+
+impl OpenAIValidator {
+    /// Create a new OpenAI validator.
+    ///
+    /// `config.model` should name an OpenAI model (e.g. `"gpt-4o"`).
+    pub fn new(api_key: &str, config: AdversarialConfig) -> Result<Self> {
+        let client_config = ClientConfig::new(api_key)
+            .with_default_model(&config.model)
+            .with_timeout(120);
+
+        Ok(Self::with_client(OpenAIClient::new(client_config), config))
+    }
 
-        // Add strategy-specific instructions
-        prompt.push_str("## Review Focus Areas\n");
-        for strategy in &self.strategies {
-            prompt.push_str(&format!("- {}\n", strategy.description()));
+    /// Create a validator around an existing client (e.g. an Azure or
+    /// proxy endpoint configured via [`ClientConfig::with_base_url`]).
+    pub fn with_client(client: OpenAIClient, config: AdversarialConfig) -> Self {
+        let strategies = GeminiValidator::init_strategies(&config);
+        Self {
+            client,
+            config,
+            strategies,
+            cache: Some(Arc::new(InMemoryValidationCache::new())),
         }
+    }
 
-        prompt.push_str("\n## Output Format\n");
-        prompt.push_str("For each issue found, output in this exact format:\n");
-        prompt.push_str("```\nISSUE: [severity] [category] - Title\nDESCRIPTION: Detailed description\nLOCATION: file:line (or \"response\" if in the response text)\nSUGGESTION: How to fix it\nCONFIDENCE: 0.0-1.0\n```\n\n");
-        prompt.push_str("Severities: critical, high, medium, low, info\n");
-        prompt.push_str("Categories: logic_error, security, error_handling, testing, performance, api_misuse, traceability, consistency, edge_case, architecture, documentation, other\n\n");
-        prompt.push_str("If no issues are found, respond with: NO_ISSUES_FOUND\n");
+    /// Use `cache` for results instead of the default in-memory cache.
+    pub fn with_cache(mut self, cache: Arc<dyn ValidationCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 
-        prompt
+    /// Always call the model, never reusing earlier results.
+    pub fn without_cache(mut self) -> Self {
+        self.cache = None;
+        self
     }
+}
 
-    /// Parse issues from the response.
-    fn parse_issues(&self, response: &str) -> Vec<Issue> {
-        let mut issues = Vec::new();
+#[async_trait]
+impl AdversarialValidator for OpenAIValidator {
+    #[instrument(skip(self, context), fields(validation_id = %context.id))]
+    async fn validate(&self, context: &ValidationContext) -> Result<ValidationResult> {
+        review(
+            &self.client,
+            &self.config,
+            &self.strategies,
+            self.cache.as_deref(),
+            context,
+            |response| {
+                response.cost.unwrap_or_else(|| {
+                    ModelSpec::gpt4o()
+                        .calculate_cost(response.usage.input_tokens, response.usage.output_tokens)
+                })
+            },
+        )
+        .await
+    }
 
-        if response.contains("NO_ISSUES_FOUND") {
-            return issues;
-        }
+    async fn validate_iterative(
+        &self,
+        context: &mut ValidationContext,
+        max_iterations: usize,
+    ) -> Result<ValidationResult> {
+        review_iterative(self, context, max_iterations).await
+    }
 
-        // Parse ISSUE blocks
-        let lines: Vec<&str> = response.lines().collect();
-        let mut i = 0;
+    fn config(&self) -> &AdversarialConfig {
+        &self.config
+    }
+}
 
-        while i < lines.len() {
-            if lines[i].starts_with("ISSUE:") {
-                let issue_line = lines[i].trim_start_matches("ISSUE:").trim();
+/// Run a single review pass against `client`.
+///
+/// If `cache` holds a result for the same context and strategy set it is
+/// returned (marked `cached`, at no cost) without calling the model.
+///
+/// Otherwise each strategy is reviewed in its own request, up to
+/// `config.max_concurrent_strategies` at a time, and the issues are merged
+/// with [`merge_issues`]. A strategy whose request fails is recorded in
+/// `failed_strategies` without aborting the others; the pass only errors if
+/// every strategy failed. Every request carries exactly one user message
+/// built from `context`, so the adversary never sees the primary model's
+/// conversation history.
+async fn review(
+    client: &dyn LLMClient,
+    config: &AdversarialConfig,
+    strategies: &[Box<dyn ValidationStrategy>],
+    cache: Option<&dyn ValidationCache>,
+    context: &ValidationContext,
+    cost: impl Fn(&CompletionResponse) -> f64,
+) -> Result<ValidationResult> {
+    let names: Vec<&str> = strategies.iter().map(|s| s.name()).collect();
+    let key = cache_key(context, config, &names);
+    if let Some(mut result) = cache.and_then(|cache| cache.get(&key)) {
+        info!("Reusing cached adversarial validation");
+        result.id = context.id.clone();
+        result.cached = true;
+        result.cost_usd = 0.0;
+        return Ok(result);
+    }
 
-                // Parse severity and category from "[severity] [category] - Title"
-                if let Some((severity, category, title)) = Self::parse_issue_header(issue_line) {
-                    let mut description = String::new();
-                    let mut location = None;
-                    let mut suggestion = None;
-                    let mut confidence = 0.8;
+    info!(
+        "Starting adversarial validation with {} strategies",
+        strategies.len()
+    );
+    let start = std::time::Instant::now();
+
+    // Futures are built in a plain loop rather than an iterator closure so
+    // the async-trait callers stay `Send`.
+    let cost = &cost;
+    let mut pending = Vec::with_capacity(strategies.len());
+    for strategy in strategies {
+        let strategy = strategy.as_ref();
+        pending.push(async move {
+            let outcome = review_strategy(client, config, strategy, context, cost).await;
+            (strategy.name(), outcome)
+        });
+    }
+    let outcomes: Vec<(&str, Result<StrategyOutcome>)> = futures::stream::iter(pending)
+        .buffered(config.max_concurrent_strategies.max(1))
+        .collect()
+        .await;
+
+    let mut issues = Vec::new();
+    let mut tokens_used = 0;
+    let mut cost_usd = 0.0;
+    let mut failed_strategies = Vec::new();
+    let mut first_error = None;
+
+    for (name, outcome) in outcomes {
+        match outcome {
+            Ok(mut outcome) => {
+                issues.append(&mut outcome.issues);
+                tokens_used += outcome.tokens_used;
+                cost_usd += outcome.cost_usd;
+            }
+            Err(e) => {
+                warn!("Validation strategy '{}' failed: {}", name, e);
+                failed_strategies.push(name.to_string());
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    if failed_strategies.len() == strategies.len() {
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+    }
 
-                    // Read subsequent lines
-                    i += 1;
-                    while i < lines.len() && !lines[i].starts_with("ISSUE:") {
-                        let line = lines[i];
-                        if line.starts_with("DESCRIPTION:") {
-                            description =
-                                line.trim_start_matches("DESCRIPTION:").trim().to_string();
-                        } else if line.starts_with("LOCATION:") {
-                            let loc = line.trim_start_matches("LOCATION:").trim();
-                            location = Self::parse_location(loc);
-                        } else if line.starts_with("SUGGESTION:") {
-                            suggestion =
-                                Some(line.trim_start_matches("SUGGESTION:").trim().to_string());
-                        } else if line.starts_with("CONFIDENCE:") {
-                            if let Ok(c) =
-                                line.trim_start_matches("CONFIDENCE:").trim().parse::<f64>()
-                            {
-                                confidence = c.clamp(0.0, 1.0);
-                            }
-                        }
-                        i += 1;
-                    }
+    let issues = merge_issues(issues);
+    info!("Found {} issues", issues.len());
+
+    let mut stats = ValidationStats::from_issues(&issues);
+    stats.latency_ms = start.elapsed().as_millis() as u64;
+    stats.tokens_used = tokens_used;
+
+    let verdict = if issues.is_empty() {
+        ValidationVerdict::Approved
+    } else if issues.iter().any(|i| i.blocking) {
+        ValidationVerdict::Rejected
+    } else {
+        ValidationVerdict::ApprovedWithComments
+    };
+
+    let mut result = ValidationResult::new(context.id.clone());
+    result.issues = issues;
+    result.stats = stats;
+    result.iterations = 1;
+    result.cost_usd = cost_usd;
+    result.failed_strategies = failed_strategies;
+    result = result.complete(verdict);
+
+    // Partial results are not cached so failed strategies are retried.
+    if let Some(cache) = cache.filter(|_| result.failed_strategies.is_empty()) {
+        if let Err(e) = cache.put(&key, &result) {
+            warn!("Failed to cache validation result: {}", e);
+        }
+    }
 
-                    // Only include issues above minimum confidence
-                    if confidence >= self.config.min_confidence {
-                        let mut issue = Issue::new(severity, category, title, description)
-                            .with_confidence(confidence);
+    Ok(result)
+}
 
-                        if let Some(loc) = location {
-                            issue = issue.with_location(loc);
-                        }
-                        if let Some(sug) = suggestion {
-                            issue = issue.with_suggestion(sug);
-                        }
+/// Issues and usage from one strategy's request.
+struct StrategyOutcome {
+    issues: Vec<Issue>,
+    tokens_used: u32,
+    cost_usd: f64,
+}
 
-                        issues.push(issue);
-                    }
+/// Review `context` with a single strategy.
+///
+/// Uses the strategy's own prompt template and parser when it has them,
+/// otherwise the standard prompt focused on that strategy.
+async fn review_strategy(
+    client: &dyn LLMClient,
+    config: &AdversarialConfig,
+    strategy: &dyn ValidationStrategy,
+    context: &ValidationContext,
+    cost: &impl Fn(&CompletionResponse) -> f64,
+) -> Result<StrategyOutcome> {
+    let prompt = strategy
+        .prompt_template(context)
+        .unwrap_or_else(|| build_prompt(&[strategy], context));
+    debug!(
+        "Built {} validation prompt ({} bytes)",
+        strategy.name(),
+        prompt.len()
+    );
+
+    let request = CompletionRequest {
+        model: Some(config.model.clone()),
+        messages: vec![ChatMessage::user(prompt)],
+        max_tokens: Some(8192),
+        temperature: Some(0.3),
+        system: None,
+        stop: None,
+        enable_caching: false,
+        metadata: None,
+        tools: Vec::new(),
+        response_format: None,
+        top_logprobs: None,
+        cache_breakpoints: Vec::new(),
+    };
+    let response = client.complete(request).await?;
+
+    let mut issues = strategy
+        .parse_issues(&response.content)
+        .unwrap_or_else(|| parse_issues(&response.content, config.min_confidence));
+    strategy.post_process(&mut issues);
+
+    Ok(StrategyOutcome {
+        issues,
+        tokens_used: (response.usage.input_tokens + response.usage.output_tokens) as u32,
+        cost_usd: cost(&response),
+    })
+}
 
-                    continue;
+/// Merge issues reported by different strategies.
+///
+/// Issues in the same category at the same place (file and line, or the
+/// same title when unlocated) count once, keeping the more severe report.
+/// A merged issue is blocking if any of its reports was.
+fn merge_issues(issues: Vec<Issue>) -> Vec<Issue> {
+    let mut merged: Vec<Issue> = Vec::new();
+
+    for issue in issues {
+        let duplicate = merged
+            .iter_mut()
+            .find(|existing| existing.category == issue.category && same_place(existing, &issue));
+        match duplicate {
+            Some(existing) => {
+                let blocking = existing.blocking || issue.blocking;
+                if severity_rank(issue.severity) > severity_rank(existing.severity) {
+                    *existing = issue;
                 }
+                existing.blocking = blocking;
             }
-            i += 1;
-        }
-
-        issues
-    }
-
-    /// Parse issue header: "[severity] [category] - Title"
-    fn parse_issue_header(
-        header: &str,
-    ) -> Option<(
-        super::types::IssueSeverity,
-        super::types::IssueCategory,
-        String,
-    )> {
-        let _parts: Vec<&str> = header
-            .splitn(3, |c| c == '[' || c == ']' || c == '-')
-            .collect();
-
-        // Try to find severity and category in brackets
-        let header_lower = header.to_lowercase();
-
-        let severity = if header_lower.contains("critical") {
-            super::types::IssueSeverity::Critical
-        } else if header_lower.contains("high") {
-            super::types::IssueSeverity::High
-        } else if header_lower.contains("medium") {
-            super::types::IssueSeverity::Medium
-        } else if header_lower.contains("low") {
-            super::types::IssueSeverity::Low
-        } else if header_lower.contains("info") {
-            super::types::IssueSeverity::Info
-        } else {
-            super::types::IssueSeverity::Medium
-        };
+            None => merged.push(issue),
+        }
+    }
 
-        let category = if header_lower.contains("security") {
-            super::types::IssueCategory::Security
-        } else if header_lower.contains("logic") {
-            super::types::IssueCategory::LogicError
-        } else if header_lower.contains("error") || header_lower.contains("handling") {
-            super::types::IssueCategory::ErrorHandling
-        } else if header_lower.contains("test") {
-            super::types::IssueCategory::Testing
-        } else if header_lower.contains("perf") {
-            super::types::IssueCategory::Performance
-        } else if header_lower.contains("api") {
-            super::types::IssueCategory::ApiMisuse
-        } else if header_lower.contains("trace") {
-            super::types::IssueCategory::Traceability
-        } else if header_lower.contains("consist") {
-            super::types::IssueCategory::Consistency
-        } else if header_lower.contains("edge") {
-            super::types::IssueCategory::EdgeCase
-        } else if header_lower.contains("arch") {
-            super::types::IssueCategory::Architecture
-        } else if header_lower.contains("doc") {
-            super::types::IssueCategory::Documentation
-        } else {
-            super::types::IssueCategory::Other
-        };
+    merged
+}
 
-        // Extract title (everything after the last '-' or after brackets)
-        let title = if let Some(idx) = header.rfind('-') {
-            header[idx + 1..].trim().to_string()
-        } else {
-            header.to_string()
-        };
+fn same_place(a: &Issue, b: &Issue) -> bool {
+    let place = |issue: &Issue| {
+        issue
+            .location
+            .as_ref()
+            .filter(|loc| loc.file.is_some())
+            .map(|loc| (loc.file.clone(), loc.line))
+    };
+    match (place(a), place(b)) {
+        (Some(a), Some(b)) => a == b,
+        (None, None) => a.title.trim().eq_ignore_ascii_case(b.title.trim()),
+        _ => false,
+    }
+}
 
-        Some((severity, category, title))
+fn severity_rank(severity: IssueSeverity) -> u8 {
+    match severity {
+        IssueSeverity::Critical => 4,
+        IssueSeverity::High => 3,
+        IssueSeverity::Medium => 2,
+        IssueSeverity::Low => 1,
+        IssueSeverity::Info => 0,
     }
+}
 
-    /// Parse location string.
-    fn parse_location(loc: &str) -> Option<super::types::IssueLocation> {
-        if loc.eq_ignore_ascii_case("response") {
-            return Some(super::types::IssueLocation::in_response(0, 0));
-        }
+/// Re-run `validator` until no blocking issues remain or `max_iterations`
+/// is reached, feeding each round's issues into the next.
+///
+/// Every round is appended to `context.prior_iterations`, including the one
+/// that converged (even when it found nothing).
+async fn review_iterative<V: AdversarialValidator + ?Sized>(
+    validator: &V,
+    context: &mut ValidationContext,
+    max_iterations: usize,
+) -> Result<ValidationResult> {
+    let mut result = ValidationResult::new(context.id.clone());
+    let mut total_cost = 0.0;
+
+    for iteration in 1..=max_iterations {
+        info!("Validation iteration {}/{}", iteration, max_iterations);
+
+        let iter_result = validator.validate(context).await?;
+        total_cost += iter_result.cost_usd;
+
+        // Record this iteration
+        let iter_record = ValidationIteration {
+            iteration,
+            issues: iter_result.issues.clone(),
+            response: None,
+            resolved: iter_result.issues.is_empty() || !iter_result.has_blocking_issues(),
+            timestamp: chrono::Utc::now(),
+        };
 
-        // Try to parse "file:line"
-        if let Some(colon_idx) = loc.rfind(':') {
-            let file = loc[..colon_idx].to_string();
-            if let Ok(line) = loc[colon_idx + 1..].trim().parse::<u32>() {
-                return Some(super::types::IssueLocation::in_file(file, line));
+        // Add issues from this iteration
+        for issue in &iter_result.issues {
+            // Avoid duplicates
+            if !result.issues.iter().any(|i| i.title == issue.title) {
+                result.issues.push(issue.clone());
             }
         }
 
-        None
+        let resolved = iter_record.resolved;
+        context.prior_iterations.push(iter_record);
+
+        // Check for convergence
+        if resolved {
+            info!("Validation converged after {} iterations", iteration);
+            result.iterations = iteration;
+            result.converged = true;
+            result.cost_usd = total_cost;
+            result.stats = ValidationStats::from_issues(&result.issues);
+
+            let verdict = if result.issues.is_empty() {
+                ValidationVerdict::Approved
+            } else {
+                ValidationVerdict::ApprovedWithComments
+            };
+
+            return Ok(result.complete(verdict));
+        }
     }
+
+    // Did not converge
+    result.iterations = max_iterations;
+    result.converged = false;
+    result.cost_usd = total_cost;
+    result.stats = ValidationStats::from_issues(&result.issues);
+
+    Ok(result.complete(ValidationVerdict::Rejected))
 }
 
-#[async_trait]
-impl AdversarialValidator for GeminiValidator {
-    #[instrument(skip(self, context), fields(validation_id = %context.id))]
-    async fn validate(&self, context: &ValidationContext) -> Result<ValidationResult> {
-        info!("Starting adversarial validation");
-
-        let prompt = self.build_prompt(context);
-        debug!("Built validation prompt ({} bytes)", prompt.len());
-
-        let request = CompletionRequest {
-            model: Some(self.config.model.clone()),
-            messages: vec![ChatMessage::user(prompt)],
-            max_tokens: Some(8192),
-            temperature: Some(0.3),
-            system: None,
-            stop: None,
-            enable_caching: false,
-            metadata: None,
-        };
+/// Build the validation prompt.
+fn build_prompt(strategies: &[&dyn ValidationStrategy], context: &ValidationContext) -> String {
+    let mut prompt = String::new();
+
+    prompt.push_str("You are an adversarial code reviewer. Your job is to find issues, bugs, and potential problems in the following code change.\n\n");
+    prompt.push_str("## Original Request\n");
+    prompt.push_str(&context.request);
+    prompt.push_str("\n\n## Response Being Reviewed\n");
+    prompt.push_str(&context.response);
+    prompt.push_str("\n\n");
+
+    // Add code context
+    if !context.code_context.is_empty() {
+        prompt.push_str("## Code Context\n");
+        for file in &context.code_context {
+            prompt.push_str(&format!("### {}\n```", file.path));
+            if let Some(ref lang) = file.language {
+                prompt.push_str(lang);
+            }
+            prompt.push_str("\n");
+            prompt.push_str(&file.content);
+            prompt.push_str("\n```\n\n");
+        }
+    }
 
-        let start = std::time::Instant::now();
-        let response = self.client.complete(request).await?;
-        let latency_ms = start.elapsed().as_millis() as u64;
+    // Add tool outputs
+    if !context.tool_outputs.is_empty() {
+        prompt.push_str("## Tool Outputs\n");
+        for output in &context.tool_outputs {
+            prompt.push_str(&format!(
+                "### {} ({})\nInput: {}\nOutput: {}\n\n",
+                output.tool,
+                if output.success { "success" } else { "failed" },
+                output.input,
+                output.output
+            ));
+        }
+    }
 
-        let issues = self.parse_issues(&response.content);
-        info!("Found {} issues", issues.len());
+    // Add prior iterations
+    if !context.prior_iterations.is_empty() {
+        prompt.push_str("## Prior Review Iterations\n");
+        for iter in &context.prior_iterations {
+            prompt.push_str(&format!("### Iteration {}\n", iter.iteration));
+            prompt.push_str("Issues found:\n");
+            for issue in &iter.issues {
+                prompt.push_str(&format!(
+                    "- [{:?}] {}: {}\n",
+                    issue.severity, issue.title, issue.description
+                ));
+            }
+            if let Some(ref response) = iter.response {
+                prompt.push_str(&format!("Response: {}\n", response));
+            }
+            prompt.push('\n');
+        }
+    }
 
-        let stats = ValidationStats::from_issues(&issues);
-        let mut stats = stats;
-        stats.latency_ms = latency_ms;
-        stats.tokens_used = (response.usage.input_tokens + response.usage.output_tokens) as u32;
+    // Add strategy-specific instructions
+    prompt.push_str("## Review Focus Areas\n");
+    for strategy in strategies {
+        prompt.push_str(&format!("- {}\n", strategy.description()));
+    }
+    for strategy in strategies {
+        prompt.push('\n');
+        prompt.push_str(&strategy.prompt_additions(context));
+    }
 
-        let verdict = if issues.is_empty() {
-            ValidationVerdict::Approved
-        } else if issues.iter().any(|i| i.blocking) {
-            ValidationVerdict::Rejected
-        } else {
-            ValidationVerdict::ApprovedWithComments
-        };
+    prompt.push_str("\n## Output Format\n");
+    prompt.push_str("For each issue found, output in this exact format:\n");
+    prompt.push_str("```\nISSUE: [severity] [category] - Title\nDESCRIPTION: Detailed description\nLOCATION: file:line (or \"response\" if in the response text)\nSUGGESTION: How to fix it\nCONFIDENCE: 0.0-1.0\n```\n\n");
+    prompt.push_str("Severities: critical, high, medium, low, info\n");
+    prompt.push_str("Categories: logic_error, security, error_handling, testing, performance, api_misuse, traceability, consistency, edge_case, architecture, documentation, other\n\n");
+    prompt.push_str("If no issues are found, respond with: NO_ISSUES_FOUND\n");
 
-        let mut result = ValidationResult::new(context.id.clone());
-        result.issues = issues;
-        result.stats = stats;
-        result.iterations = 1;
-        result.cost_usd = response.usage.input_tokens as f64 * 0.000075 / 1000.0
-            + response.usage.output_tokens as f64 * 0.0003 / 1000.0;
-        result = result.complete(verdict);
+    prompt
+}
 
-        Ok(result)
-    }
+/// Parse issues from the response.
+fn parse_issues(response: &str, min_confidence: f64) -> Vec<Issue> {
+    let mut issues = Vec::new();
 
-    async fn validate_iterative(
-        &self,
-        context: &mut ValidationContext,
-        max_iterations: usize,
-    ) -> Result<ValidationResult> {
-        let mut result = ValidationResult::new(context.id.clone());
-        let mut total_cost = 0.0;
+    if response.contains("NO_ISSUES_FOUND") {
+        return issues;
+    }
 
-        for iteration in 1..=max_iterations {
-            info!("Validation iteration {}/{}", iteration, max_iterations);
+    // Parse ISSUE blocks
+    let lines: Vec<&str> = response.lines().collect();
+    let mut i = 0;
+
+    while i < lines.len() {
+        if lines[i].starts_with("ISSUE:") {
+            let issue_line = lines[i].trim_start_matches("ISSUE:").trim();
+
+            // Parse severity and category from "[severity] [category] - Title"
+            if let Some((severity, category, title)) = parse_issue_header(issue_line) {
+                let mut description = String::new();
+                let mut location = None;
+                let mut suggestion = None;
+                let mut confidence = 0.8;
+
+                // Read subsequent lines
+                i += 1;
+                while i < lines.len() && !lines[i].starts_with("ISSUE:") {
+                    let line = lines[i];
+                    if line.starts_with("DESCRIPTION:") {
+                        description = line.trim_start_matches("DESCRIPTION:").trim().to_string();
+                    } else if line.starts_with("LOCATION:") {
+                        let loc = line.trim_start_matches("LOCATION:").trim();
+                        location = parse_location(loc);
+                    } else if line.starts_with("SUGGESTION:") {
+                        suggestion =
+                            Some(line.trim_start_matches("SUGGESTION:").trim().to_string());
+                    } else if line.starts_with("CONFIDENCE:") {
+                        if let Ok(c) = line.trim_start_matches("CONFIDENCE:").trim().parse::<f64>()
+                        {
+                            confidence = c.clamp(0.0, 1.0);
+                        }
+                    }
+                    i += 1;
+                }
 
-            let iter_result = self.validate(context).await?;
-            total_cost += iter_result.cost_usd;
+                // Only include issues above minimum confidence
+                if confidence >= min_confidence {
+                    let mut issue = Issue::new(severity, category, title, description)
+                        .with_confidence(confidence);
 
-            // Record this iteration
-            let iter_record = ValidationIteration {
-                iteration,
-                issues: iter_result.issues.clone(),
-                response: None,
-                resolved: iter_result.issues.is_empty() || !iter_result.has_blocking_issues(),
-                timestamp: chrono::Utc::now(),
-            };
+                    if let Some(loc) = location {
+                        issue = issue.with_location(loc);
+                    }
+                    if let Some(sug) = suggestion {
+                        issue = issue.with_suggestion(sug);
+                    }
 
-            // Add issues from this iteration
-            for issue in &iter_result.issues {
-                // Avoid duplicates
-                if !result.issues.iter().any(|i| i.title == issue.title) {
-                    result.issues.push(issue.clone());
+                    issues.push(issue);
                 }
-            }
 
-            // Check for convergence
-            if iter_record.resolved {
-                info!("Validation converged after {} iterations", iteration);
-                result.iterations = iteration;
-                result.converged = true;
-                result.cost_usd = total_cost;
-                result.stats = ValidationStats::from_issues(&result.issues);
-
-                let verdict = if result.issues.is_empty() {
-                    ValidationVerdict::Approved
-                } else {
-                    ValidationVerdict::ApprovedWithComments
-                };
-
-                return Ok(result.complete(verdict));
+                continue;
             }
-
-            // Add to context for next iteration
-            context.prior_iterations.push(iter_record);
         }
+        i += 1;
+    }
+
+    issues
+}
 
-        // Did not converge
-        result.iterations = max_iterations;
-        result.converged = false;
-        result.cost_usd = total_cost;
-        result.stats = ValidationStats::from_issues(&result.issues);
+/// Parse issue header: "[severity] [category] - Title"
+fn parse_issue_header(
+    header: &str,
+) -> Option<(
+    super::types::IssueSeverity,
+    super::types::IssueCategory,
+    String,
+)> {
+    let _parts: Vec<&str> = header
+        .splitn(3, |c| c == '[' || c == ']' || c == '-')
+        .collect();
+
+    // Try to find severity and category in brackets
+    let header_lower = header.to_lowercase();
+
+    let severity = if header_lower.contains("critical") {
+        super::types::IssueSeverity::Critical
+    } else if header_lower.contains("high") {
+        super::types::IssueSeverity::High
+    } else if header_lower.contains("medium") {
+        super::types::IssueSeverity::Medium
+    } else if header_lower.contains("low") {
+        super::types::IssueSeverity::Low
+    } else if header_lower.contains("info") {
+        super::types::IssueSeverity::Info
+    } else {
+        super::types::IssueSeverity::Medium
+    };
+
+    let category = if header_lower.contains("security") {
+        super::types::IssueCategory::Security
+    } else if header_lower.contains("logic") {
+        super::types::IssueCategory::LogicError
+    } else if header_lower.contains("error") || header_lower.contains("handling") {
+        super::types::IssueCategory::ErrorHandling
+    } else if header_lower.contains("test") {
+        super::types::IssueCategory::Testing
+    } else if header_lower.contains("perf") {
+        super::types::IssueCategory::Performance
+    } else if header_lower.contains("api") {
+        super::types::IssueCategory::ApiMisuse
+    } else if header_lower.contains("trace") {
+        super::types::IssueCategory::Traceability
+    } else if header_lower.contains("consist") {
+        super::types::IssueCategory::Consistency
+    } else if header_lower.contains("edge") {
+        super::types::IssueCategory::EdgeCase
+    } else if header_lower.contains("arch") {
+        super::types::IssueCategory::Architecture
+    } else if header_lower.contains("doc") {
+        super::types::IssueCategory::Documentation
+    } else {
+        super::types::IssueCategory::Other
+    };
+
+    // Extract title (everything after the last '-' or after brackets)
+    let title = if let Some(idx) = header.rfind('-') {
+        header[idx + 1..].trim().to_string()
+    } else {
+        header.to_string()
+    };
+
+    Some((severity, category, title))
+}
 
-        Ok(result.complete(ValidationVerdict::Rejected))
+/// Parse location string.
+fn parse_location(loc: &str) -> Option<super::types::IssueLocation> {
+    if loc.eq_ignore_ascii_case("response") {
+        return Some(super::types::IssueLocation::in_response(0, 0));
     }
 
-    fn config(&self) -> &AdversarialConfig {
-        &self.config
+    // Try to parse "file:line"
+    if let Some(colon_idx) = loc.rfind(':') {
+        let file = loc[..colon_idx].to_string();
+        if let Ok(line) = loc[colon_idx + 1..].trim().parse::<u32>() {
+            return Some(super::types::IssueLocation::in_file(file, line));
+        }
     }
+
+    None
 }
 
 /// A mock validator for testing.
@@ -509,35 +811,88 @@ impl AdversarialValidator for MockValidator {
 
 #[cfg(test)]
 mod tests {
+    use super::super::invoker::InvocationStats;
+    use super::super::types::{CodeFile, IssueCategory, IssueSeverity};
     use super::*;
+    use crate::error::Error;
+    use crate::llm::{EmbeddingRequest, EmbeddingResponse, Provider, RetryPolicy, TokenUsage};
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serve a single OpenAI chat completion, returning the request body.
+    fn mock_openai(content: &str) -> (String, std::thread::JoinHandle<serde_json::Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let body = serde_json::json!({
+            "id": "chatcmpl-1",
+            "model": "gpt-4o",
+            "choices": [{
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 1200, "completion_tokens": 300 }
+        })
+        .to_string();
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut request = vec![0; content_length];
+            reader.read_exact(&mut request).unwrap();
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            serde_json::from_slice(&request).unwrap()
+        });
+
+        (url, handle)
+    }
 
     #[test]
     fn test_parse_issue_header() {
-        let (sev, cat, title) = GeminiValidator::parse_issue_header(
-            "[critical] [security] - SQL Injection vulnerability",
-        )
-        .unwrap();
+        let (sev, cat, title) =
+            parse_issue_header("[critical] [security] - SQL Injection vulnerability").unwrap();
 
-        assert_eq!(sev, super::super::types::IssueSeverity::Critical);
-        assert_eq!(cat, super::super::types::IssueCategory::Security);
+        assert_eq!(sev, IssueSeverity::Critical);
+        assert_eq!(cat, IssueCategory::Security);
         assert_eq!(title, "SQL Injection vulnerability");
     }
 
     #[test]
     fn test_parse_location() {
-        let loc = GeminiValidator::parse_location("src/main.rs:42").unwrap();
+        let loc = parse_location("src/main.rs:42").unwrap();
         assert_eq!(loc.file, Some("src/main.rs".to_string()));
         assert_eq!(loc.line, Some(42));
 
-        let loc = GeminiValidator::parse_location("response").unwrap();
+        let loc = parse_location("response").unwrap();
         assert!(loc.response_span.is_some());
     }
 
     #[tokio::test]
     async fn test_mock_validator() {
         let validator = MockValidator::new().with_issues(vec![Issue::new(
-            super::super::types::IssueSeverity::High,
-            super::super::types::IssueCategory::Security,
+            IssueSeverity::High,
+            IssueCategory::Security,
             "Test issue",
             "Test description",
         )]);
@@ -548,4 +903,306 @@ mod tests {
         assert_eq!(result.issues.len(), 1);
         assert_eq!(result.verdict, ValidationVerdict::Rejected);
     }
+
+    #[tokio::test]
+    async fn test_openai_validator() {
+        let (url, server) = mock_openai(
+            "ISSUE: [high] [security] - Password check removed\n\
+             DESCRIPTION: Any password is accepted\n\
+             LOCATION: src/auth.rs:12\n\
+             SUGGESTION: Restore the check\n\
+             CONFIDENCE: 0.9\n\
+             ISSUE: [low] [documentation] - Stale comment\n\
+             DESCRIPTION: Comment still mentions the old flow\n",
+        );
+        let config = AdversarialConfig {
+            enabled: true,
+            model: "gpt-4o".to_string(),
+            strategies: vec!["critic".to_string()],
+            ..Default::default()
+        };
+        let client = OpenAIClient::new(ClientConfig::new("test-key").with_base_url(url));
+        let validator = OpenAIValidator::with_client(client, config);
+        assert!(validator.should_validate("review"));
+        assert!(!validator.should_validate("commit"));
+
+        let ctx = ValidationContext::new("Fix the login bug", "Removed the password check");
+        let result = validator.validate(&ctx).await.unwrap();
+
+        assert_eq!(result.issues.len(), 2);
+        assert_eq!(result.issues[0].severity, IssueSeverity::High);
+        assert_eq!(result.issues[0].category, IssueCategory::Security);
+        assert_eq!(result.issues[0].location.as_ref().unwrap().line, Some(12));
+        assert_eq!(result.issues[1].severity, IssueSeverity::Low);
+        assert!(!result.issues[1].blocking);
+        assert_eq!(result.verdict, ValidationVerdict::Rejected);
+        assert_eq!(result.stats.tokens_used, 1500);
+
+        // Fresh context: one user message, no system prompt or history.
+        let request = server.join().unwrap();
+        let messages = request["messages"].as_array().unwrap();
+        assert_eq!(request["model"], "gpt-4o");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["role"], "user");
+        assert!(messages[0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Removed the password check"));
+    }
+
+    #[tokio::test]
+    async fn test_cached_validation_skips_invocation() {
+        let (url, server) = mock_openai("NO_ISSUES_FOUND");
+        let config = AdversarialConfig {
+            enabled: true,
+            model: "gpt-4o".to_string(),
+            strategies: vec!["critic".to_string()],
+            ..Default::default()
+        };
+        let client = OpenAIClient::new(
+            ClientConfig::new("test-key")
+                .with_base_url(url)
+                .with_retry_policy(RetryPolicy::none()),
+        );
+        let cache = Arc::new(InMemoryValidationCache::new());
+        let validator = OpenAIValidator::with_client(client, config).with_cache(cache.clone());
+        let context = || {
+            ValidationContext::new("Fix the bug", "Fixed it")
+                .with_code_file(CodeFile::new("src/lib.rs", "fn a() {}"))
+        };
+        let mut stats = InvocationStats::default();
+
+        let first = validator.validate(&context()).await.unwrap();
+        server.join().unwrap();
+        stats.record_result(&first, 10);
+
+        // The mock server is gone, so this only succeeds from the cache.
+        let second_ctx = context();
+        let second = validator.validate(&second_ctx).await.unwrap();
+        stats.record_result(&second, 0);
+
+        assert!(!first.cached);
+        assert!(second.cached);
+        assert_eq!(second.id, second_ctx.id);
+        assert_eq!(second.verdict, ValidationVerdict::Approved);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(stats.total_invocations, 1);
+        assert_eq!(stats.cache_hits, 1);
+
+        // A changed code file misses the cache (and fails to reach the model).
+        let edited = ValidationContext::new("Fix the bug", "Fixed it")
+            .with_code_file(CodeFile::new("src/lib.rs", "fn a() { 1 }"));
+        assert!(validator.validate(&edited).await.is_err());
+    }
+
+    /// Strategy with a fixed prompt, parsed with the standard format.
+    struct PromptStrategy(&'static str);
+
+    impl ValidationStrategy for PromptStrategy {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            self.0
+        }
+
+        fn categories(&self) -> Vec<IssueCategory> {
+            vec![IssueCategory::Security]
+        }
+
+        fn prompt_additions(&self, _context: &ValidationContext) -> String {
+            String::new()
+        }
+
+        fn prompt_template(&self, _context: &ValidationContext) -> Option<String> {
+            Some(self.0.to_string())
+        }
+    }
+
+    /// Answers each prompt from a table, tracking peak concurrency.
+    struct RoutedClient {
+        responses: HashMap<&'static str, &'static str>,
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LLMClient for RoutedClient {
+        async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let prompt = request.messages[0].content.as_str();
+            let content = self
+                .responses
+                .get(prompt)
+                .ok_or_else(|| Error::LLM(format!("no response for {}", prompt)))?;
+            Ok(CompletionResponse {
+                id: "routed".to_string(),
+                model: "mock-model".to_string(),
+                content: content.to_string(),
+                stop_reason: None,
+                usage: TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    cache_read_tokens: None,
+                    cache_creation_tokens: None,
+                },
+                timestamp: chrono::Utc::now(),
+                cost: None,
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        }
+
+        async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+            Err(Error::LLM("not implemented".to_string()))
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::OpenAI
+        }
+
+        fn available_models(&self) -> Vec<ModelSpec> {
+            vec![]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_strategies_merge_issues() {
+        let client = RoutedClient {
+            responses: HashMap::from([
+                (
+                    "alpha",
+                    "ISSUE: [medium] [security] - Token logged\nLOCATION: src/auth.rs:10\n\
+                     ISSUE: [low] [documentation] - Typo in docs\nLOCATION: README.md:3\n",
+                ),
+                (
+                    "beta",
+                    "ISSUE: [high] [security] - Secret written to log\nLOCATION: src/auth.rs:10\n",
+                ),
+            ]),
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        };
+        let config = AdversarialConfig {
+            max_concurrent_strategies: 2,
+            ..Default::default()
+        };
+        let strategies: Vec<Box<dyn ValidationStrategy>> = vec![
+            Box::new(PromptStrategy("alpha")),
+            Box::new(PromptStrategy("beta")),
+            Box::new(PromptStrategy("broken")),
+        ];
+        let ctx = ValidationContext::new("request", "response");
+
+        let result = review(&client, &config, &strategies, None, &ctx, |_| 0.01)
+            .await
+            .unwrap();
+
+        // Overlapping security issue counts once, at the higher severity.
+        assert_eq!(result.issues.len(), 2);
+        let security = &result.issues_by_category(IssueCategory::Security);
+        assert_eq!(security.len(), 1);
+        assert_eq!(security[0].severity, IssueSeverity::High);
+        assert_eq!(security[0].title, "Secret written to log");
+        assert_eq!(result.verdict, ValidationVerdict::Rejected);
+
+        // The failing strategy is isolated and reported.
+        assert_eq!(result.failed_strategies, vec!["broken".to_string()]);
+        assert!((result.cost_usd - 0.02).abs() < 1e-9);
+        assert_eq!(client.peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_all_strategies_failing_is_an_error() {
+        let client = RoutedClient {
+            responses: HashMap::new(),
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        };
+        let strategies: Vec<Box<dyn ValidationStrategy>> = vec![Box::new(PromptStrategy("a"))];
+        let ctx = ValidationContext::new("request", "response");
+
+        let result = review(
+            &client,
+            &AdversarialConfig::default(),
+            &strategies,
+            None,
+            &ctx,
+            |_| 0.0,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    struct ChecklistStrategy;
+
+    impl ValidationStrategy for ChecklistStrategy {
+        fn name(&self) -> &str {
+            "checklist"
+        }
+
+        fn description(&self) -> &str {
+            "Team review checklist"
+        }
+
+        fn categories(&self) -> Vec<IssueCategory> {
+            vec![IssueCategory::Other]
+        }
+
+        fn prompt_additions(&self, _context: &ValidationContext) -> String {
+            String::new()
+        }
+
+        fn prompt_template(&self, context: &ValidationContext) -> Option<String> {
+            Some(format!("CHECKLIST REVIEW\n{}", context.response))
+        }
+
+        fn parse_issues(&self, response: &str) -> Option<Vec<Issue>> {
+            Some(
+                response
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("FAIL: "))
+                    .map(|title| Issue::new(IssueSeverity::Medium, IssueCategory::Other, title, ""))
+                    .collect(),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_strategy_without_issues() {
+        StrategyFactory::register("checklist_validator_test", Box::new(ChecklistStrategy));
+        let (url, server) = mock_openai("All checklist items pass.");
+        let config = AdversarialConfig {
+            enabled: true,
+            model: "gpt-4o".to_string(),
+            strategies: vec!["checklist_validator_test".to_string()],
+            ..Default::default()
+        };
+        let client = OpenAIClient::new(ClientConfig::new("test-key").with_base_url(url));
+        let validator = OpenAIValidator::with_client(client, config);
+        StrategyFactory::unregister("checklist_validator_test");
+
+        let mut ctx = ValidationContext::new("request", "response");
+        let result = validator.validate_iterative(&mut ctx, 3).await.unwrap();
+
+        // Only the custom strategy's own prompt was sent.
+        let request = server.join().unwrap();
+        assert!(request["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .starts_with("CHECKLIST REVIEW"));
+
+        assert!(result.issues.is_empty());
+        assert!(result.converged);
+        assert_eq!(result.iterations, 1);
+        assert_eq!(result.verdict, ValidationVerdict::Approved);
+        assert_eq!(ctx.prior_iterations.len(), 1);
+        assert!(ctx.prior_iterations[0].resolved);
+    }
 }
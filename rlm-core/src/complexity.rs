@@ -8,6 +8,7 @@
 //! - Historical signals (previous turn state)
 
 use crate::context::SessionContext;
+use crate::error::{Error, Result};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
@@ -214,6 +215,121 @@ impl ActivationDecision {
     }
 }
 
+/// How a [`PatternRule`] matches a query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMatcher {
+    /// Matches if any keyword appears as a whole word (case-insensitive)
+    Keywords(Vec<String>),
+    /// Matches if the regex matches anywhere in the query
+    Regex(String),
+}
+
+/// A user-defined complexity rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternRule {
+    /// Rule name, reported in the activation reason when it matches
+    pub name: String,
+    /// Score added when the rule matches (may be negative)
+    pub weight: i32,
+    /// What the rule matches on
+    #[serde(flatten)]
+    pub matcher: RuleMatcher,
+}
+
+impl PatternRule {
+    /// Create a rule matching any of the given keywords.
+    pub fn keywords(name: impl Into<String>, weight: i32, keywords: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            weight,
+            matcher: RuleMatcher::Keywords(keywords.iter().map(|k| k.to_string()).collect()),
+        }
+    }
+
+    /// Create a rule matching a regex.
+    pub fn regex(name: impl Into<String>, weight: i32, pattern: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            weight,
+            matcher: RuleMatcher::Regex(pattern.into()),
+        }
+    }
+
+    fn compile(&self) -> Result<Regex> {
+        let pattern = match &self.matcher {
+            RuleMatcher::Keywords(keywords) if keywords.is_empty() => {
+                return Err(Error::Config(format!(
+                    "rule '{}' has no keywords",
+                    self.name
+                )));
+            }
+            RuleMatcher::Keywords(keywords) => {
+                let alternatives: Vec<String> = keywords.iter().map(|k| regex::escape(k)).collect();
+                format!(r"(?i)\b(?:{})\b", alternatives.join("|"))
+            }
+            RuleMatcher::Regex(pattern) => pattern.clone(),
+        };
+        Regex::new(&pattern)
+            .map_err(|e| Error::Config(format!("invalid pattern for rule '{}': {}", self.name, e)))
+    }
+}
+
+/// Configuration for a [`PatternClassifier`] with custom rules.
+///
+/// Serializable so that domain-specific rules can be loaded from a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassifierConfig {
+    /// Minimum score threshold for activation
+    #[serde(default = "default_activation_threshold")]
+    pub activation_threshold: i32,
+    /// Whether the built-in signals also contribute to the score
+    #[serde(default = "default_true")]
+    pub include_default_rules: bool,
+    /// Custom rules scored on top of (or instead of) the built-in signals
+    #[serde(default)]
+    pub rules: Vec<PatternRule>,
+}
+
+fn default_activation_threshold() -> i32 {
+    // Threshold of 2 matches Python implementation behavior
+    2
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ClassifierConfig {
+    fn default() -> Self {
+        Self {
+            activation_threshold: default_activation_threshold(),
+            include_default_rules: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl ClassifierConfig {
+    /// Add a custom rule.
+    pub fn with_rule(mut self, rule: PatternRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Set the activation threshold.
+    pub fn with_threshold(mut self, threshold: i32) -> Self {
+        self.activation_threshold = threshold;
+        self
+    }
+
+    /// Enable or disable the built-in signals.
+    pub fn with_default_rules(mut self, include: bool) -> Self {
+        self.include_default_rules = include;
+        self
+    }
+}
+
 /// Pattern-based complexity classifier.
 ///
 /// Analyzes queries and context to determine task complexity using
@@ -224,14 +340,19 @@ pub struct PatternClassifier {
     pub activation_threshold: i32,
     /// Whether to always activate (for testing)
     pub force_activation: bool,
+    /// Whether the built-in signals contribute to the score
+    include_default_rules: bool,
+    /// Custom rules with their compiled patterns
+    rules: Vec<(PatternRule, Regex)>,
 }
 
 impl Default for PatternClassifier {
     fn default() -> Self {
         Self {
-            // Threshold of 2 matches Python implementation behavior
-            activation_threshold: 2,
+            activation_threshold: default_activation_threshold(),
             force_activation: false,
+            include_default_rules: true,
+            rules: Vec::new(),
         }
     }
 }
@@ -306,10 +427,37 @@ impl PatternClassifier {
     pub fn with_threshold(threshold: i32) -> Self {
         Self {
             activation_threshold: threshold,
-            force_activation: false,
+            ..Self::default()
         }
     }
 
+    /// Create a classifier from custom rules.
+    ///
+    /// Fails if any rule's pattern does not compile.
+    pub fn with_patterns(config: ClassifierConfig) -> Result<Self> {
+        let rules = config
+            .rules
+            .into_iter()
+            .map(|rule| rule.compile().map(|regex| (rule, regex)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            activation_threshold: config.activation_threshold,
+            force_activation: false,
+            include_default_rules: config.include_default_rules,
+            rules,
+        })
+    }
+
+    /// Custom rules matching the query, with their weights.
+    pub fn matching_rules(&self, query: &str) -> Vec<&PatternRule> {
+        self.rules
+            .iter()
+            .filter(|(_, regex)| regex.is_match(query))
+            .map(|(rule, _)| rule)
+            .collect()
+    }
+
     /// Analyze a query and context to extract complexity signals.
     pub fn analyze(&self, query: &str, context: &SessionContext) -> TaskComplexitySignals {
         let mut signals = TaskComplexitySignals::default();
@@ -364,8 +512,15 @@ impl PatternClassifier {
         }
 
         let signals = self.analyze(query, context);
-        let score = signals.score();
-        let active = signals.active_signals();
+        let matched = self.matching_rules(query);
+
+        let mut score: i32 = matched.iter().map(|rule| rule.weight).sum();
+        let mut active: Vec<&str> = Vec::new();
+        if self.include_default_rules {
+            score += signals.score();
+            active.extend(signals.active_signals());
+        }
+        active.extend(matched.iter().map(|rule| rule.name.as_str()));
 
         if score >= self.activation_threshold {
             // Format reason to match Python test expectations
@@ -483,4 +638,85 @@ mod tests {
         assert!(decision.should_activate);
         assert_eq!(decision.score, 100);
     }
+
+    fn legal_config() -> ClassifierConfig {
+        ClassifierConfig::default()
+            .with_threshold(4)
+            .with_rule(PatternRule::keywords(
+                "indemnity",
+                5,
+                &["indemnify", "indemnification", "hold harmless"],
+            ))
+            .with_rule(PatternRule::regex(
+                "statute",
+                2,
+                r"(?i)\b\d+\s+U\.?S\.?C\.?",
+            ))
+    }
+
+    #[test]
+    fn test_custom_rule_activates() {
+        let ctx = SessionContext::new();
+        let query = "Where does the contract say we indemnify the vendor?";
+
+        let default_decision = PatternClassifier::new().should_activate(query, &ctx);
+        assert!(!default_decision.should_activate);
+
+        let classifier = PatternClassifier::with_patterns(legal_config()).unwrap();
+        let decision = classifier.should_activate(query, &ctx);
+        assert!(decision.should_activate);
+        assert!(decision.score >= 5);
+        assert!(decision.reason.contains("indemnity"));
+        assert!(!decision.reason.contains("statute"));
+    }
+
+    #[test]
+    fn test_custom_rules_only() {
+        let ctx = SessionContext::new();
+        let classifier =
+            PatternClassifier::with_patterns(legal_config().with_default_rules(false)).unwrap();
+
+        let decision = classifier.should_activate("Find all security issues in 42 USC 1983", &ctx);
+        assert_eq!(decision.score, 2);
+        assert!(!decision.should_activate);
+        assert_eq!(decision.reason, "simple_task");
+    }
+
+    #[test]
+    fn test_classifier_config_serde() {
+        let json = r#"{
+            "activation_threshold": 3,
+            "rules": [
+                {"name": "clause", "weight": 3, "keywords": ["clause"]},
+                {"name": "section", "weight": 1, "regex": "§\\s*\\d+"}
+            ]
+        }"#;
+        let config: ClassifierConfig = serde_json::from_str(json).unwrap();
+        assert!(config.include_default_rules);
+        assert_eq!(
+            config.rules[1].matcher,
+            RuleMatcher::Regex(r"§\s*\d+".to_string())
+        );
+
+        let roundtrip: ClassifierConfig =
+            serde_json::from_value(serde_json::to_value(&config).unwrap()).unwrap();
+        assert_eq!(roundtrip, config);
+
+        let classifier = PatternClassifier::with_patterns(config).unwrap();
+        assert_eq!(classifier.matching_rules("see § 4 of the clause").len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_rule_pattern() {
+        let config = ClassifierConfig::default().with_rule(PatternRule::regex("bad", 1, "("));
+        let err = PatternClassifier::with_patterns(config).unwrap_err();
+        assert!(err.to_string().contains("bad"));
+    }
+
+    #[test]
+    fn test_empty_keyword_rule_is_rejected() {
+        let config = ClassifierConfig::default().with_rule(PatternRule::keywords("empty", 1, &[]));
+        let err = PatternClassifier::with_patterns(config).unwrap_err();
+        assert!(matches!(err, Error::Config(ref msg) if msg.contains("empty")));
+    }
 }
@@ -19,6 +19,7 @@
 //! - SPEC-25.04: Size tracking and limits
 
 use super::types::SessionContext;
+use crate::repl::floor_char_boundary;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -431,7 +432,10 @@ impl ExternalizedContext {
                 let content = msg.content.replace('\\', "\\\\").replace('"', "\\\"");
                 // Truncate very long messages in setup
                 let content = if content.len() > 1000 {
-                    format!("{}...[truncated]", &content[..1000])
+                    format!(
+                        "{}...[truncated]",
+                        &content[..floor_char_boundary(&content, 1000)]
+                    )
                 } else {
                     content
                 };
@@ -452,7 +456,7 @@ impl ExternalizedContext {
                 let content = if content.len() > 5000 {
                     format!(
                         "{}...[truncated, use search() for full access]",
-                        &content[..5000]
+                        &content[..floor_char_boundary(&content, 5000)]
                     )
                 } else {
                     content
@@ -468,7 +472,10 @@ impl ExternalizedContext {
             for output in &ctx.tool_outputs {
                 let content = output.content.replace('\\', "\\\\").replace('"', "\\\"");
                 let content = if content.len() > 2000 {
-                    format!("{}...[truncated]", &content[..2000])
+                    format!(
+                        "{}...[truncated]",
+                        &content[..floor_char_boundary(&content, 2000)]
+                    )
                 } else {
                     content
                 };
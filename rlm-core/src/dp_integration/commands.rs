@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
+use crate::repl::floor_char_boundary;
 
 use super::coverage::CoverageScanner;
 use super::proof_status::LeanProofScanner;
@@ -724,7 +725,10 @@ fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        format!(
+            "{}...",
+            &s[..floor_char_boundary(s, max_len.saturating_sub(3))]
+        )
     }
 }
 
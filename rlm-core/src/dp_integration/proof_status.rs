@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
 use crate::lean::{LeanRepl, LeanReplConfig};
+use crate::repl::floor_char_boundary;
 use crate::repl::ReplEnvironment;
 
 use super::types::{ProofStatus, SpecId, TheoremInfo};
@@ -183,7 +184,7 @@ impl LeanProofScanner {
 
                     // Store truncated proof text
                     let truncated = if body.len() > 500 {
-                        format!("{}...", &body[..500])
+                        format!("{}...", &body[..floor_char_boundary(body, 500)])
                     } else {
                         body.to_string()
                     };
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::types::{CoverageReport, CoverageSummary, ProofStatus, SpecCoverage, SpecId};
+use crate::repl::floor_char_boundary;
 
 /// Result of a review check.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -552,7 +553,10 @@ fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        format!(
+            "{}...",
+            &s[..floor_char_boundary(s, max_len.saturating_sub(3))]
+        )
     }
 }
 
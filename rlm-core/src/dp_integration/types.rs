@@ -3,6 +3,7 @@
 //! This module defines the core types for tracking spec coverage and proof status
 //! in the Disciplined Process workflow.
 
+use crate::repl::floor_char_boundary;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -10,7 +11,9 @@ use std::path::PathBuf;
 ///
 /// Format: SPEC-{major}.{minor} where major and minor are zero-padded numbers.
 /// Examples: SPEC-01.01, SPEC-02.03, SPEC-10.15
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Ordering is numeric (SPEC-1.2 sorts before SPEC-1.10).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct SpecId {
     /// Major version (XX in SPEC-XX.YY).
     pub major: u32,
@@ -118,6 +121,28 @@ impl ProofStatus {
         }
     }
 
+    /// Short key used for styling (CSS class suffix).
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::NotFormalized => "missing",
+            Self::Stated => "stated",
+            Self::HasSorry => "sorry",
+            Self::Complete => "complete",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// All statuses, from best to worst.
+    pub fn all() -> [Self; 5] {
+        [
+            Self::Complete,
+            Self::Stated,
+            Self::HasSorry,
+            Self::Failed,
+            Self::NotFormalized,
+        ]
+    }
+
     /// Get an emoji indicator for CLI output.
     pub fn indicator(&self) -> &'static str {
         match self {
@@ -379,6 +404,11 @@ impl CoverageSummary {
         }
     }
 
+    /// Calculate the coverage percentage (specs with a formalization).
+    pub fn coverage_percentage(&self) -> f64 {
+        self.formalization_percentage()
+    }
+
     /// Calculate the completion percentage (complete proofs).
     pub fn completion_percentage(&self) -> f64 {
         if self.total_specs == 0 {
@@ -524,6 +554,139 @@ impl CoverageReport {
 
         output
     }
+
+    /// Percentage of specs with a Lean formalization.
+    pub fn coverage_percentage(&self) -> f64 {
+        self.summary.coverage_percentage()
+    }
+
+    /// Specs sorted by ID (numerically, so SPEC-1.2 precedes SPEC-1.10).
+    fn sorted_specs(&self) -> Vec<&SpecCoverage> {
+        let mut specs: Vec<&SpecCoverage> = self.specs.iter().collect();
+        specs.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+        specs
+    }
+
+    /// Format the report as a Markdown table (e.g. for PR descriptions).
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Spec Coverage\n\n");
+        md.push_str(&format!(
+            "**Coverage: {:.1}%** ({}/{} formalized, {}/{} complete)\n\n",
+            self.coverage_percentage(),
+            self.summary.formalized_count,
+            self.summary.total_specs,
+            self.summary.complete_count,
+            self.summary.total_specs
+        ));
+
+        md.push_str("| Spec | Status | Theorems | Requirement |\n");
+        md.push_str("|------|--------|----------|-------------|\n");
+        for spec in self.sorted_specs() {
+            md.push_str(&format!(
+                "| {} | {} {} | {} | {} |\n",
+                spec.spec_id,
+                spec.proof_status.indicator(),
+                spec.proof_status,
+                theorem_names(spec, |name| format!("`{}`", name)).replace('|', "\\|"),
+                spec.requirement_text.replace('|', "\\|").replace('\n', " ")
+            ));
+        }
+
+        md.push_str("\n**Legend:** ");
+        let legend: Vec<String> = ProofStatus::all()
+            .iter()
+            .map(|status| format!("`{}` {}", status.indicator(), status))
+            .collect();
+        md.push_str(&legend.join(" · "));
+        md.push('\n');
+
+        md
+    }
+
+    /// Format the report as a standalone HTML page with rows colored by status.
+    pub fn to_html(&self) -> String {
+        let mut rows = String::new();
+        for spec in self.sorted_specs() {
+            rows.push_str(&format!(
+                "      <tr class=\"status-{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                spec.proof_status.css_class(),
+                spec.spec_id,
+                spec.proof_status,
+                theorem_names(spec, |name| format!("<code>{}</code>", escape_html(name))),
+                escape_html(&spec.requirement_text)
+            ));
+        }
+
+        let legend: String = ProofStatus::all()
+            .iter()
+            .map(|status| {
+                format!(
+                    "<span class=\"status-{}\">{}</span>",
+                    status.css_class(),
+                    status
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Spec Coverage</title>
+  <style>
+    body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; margin: 2em; }}
+    table {{ border-collapse: collapse; width: 100%; }}
+    th, td {{ border: 1px solid #ddd; padding: 6px 10px; text-align: left; }}
+    th {{ background: #f5f5f5; }}
+    .legend span {{ padding: 2px 8px; border-radius: 4px; margin-right: 6px; }}
+    .status-complete {{ background: #C8E6C9; }}
+    .status-stated {{ background: #BBDEFB; }}
+    .status-sorry {{ background: #FFE0B2; }}
+    .status-failed {{ background: #FFCDD2; }}
+    .status-missing {{ background: #EEEEEE; }}
+  </style>
+</head>
+<body>
+  <h1>Spec Coverage</h1>
+  <p class="summary"><strong>Coverage: {coverage:.1}%</strong> ({formalized}/{total} formalized, {complete}/{total} complete)</p>
+  <p class="legend">{legend}</p>
+  <table>
+    <thead>
+      <tr><th>Spec</th><th>Status</th><th>Theorems</th><th>Requirement</th></tr>
+    </thead>
+    <tbody>
+{rows}    </tbody>
+  </table>
+</body>
+</html>
+"#,
+            coverage = self.coverage_percentage(),
+            formalized = self.summary.formalized_count,
+            total = self.summary.total_specs,
+            complete = self.summary.complete_count,
+            legend = legend,
+            rows = rows,
+        )
+    }
+}
+
+/// Comma-separated qualified theorem names, each formatted by `render`.
+fn theorem_names(spec: &SpecCoverage, render: impl Fn(&str) -> String) -> String {
+    spec.theorems
+        .iter()
+        .map(|t| render(&t.qualified_name()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escape text for inclusion in HTML.
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 /// Truncate text to a maximum length.
@@ -531,7 +694,10 @@ fn truncate_text(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        format!(
+            "{}...",
+            &s[..floor_char_boundary(s, max_len.saturating_sub(3))]
+        )
     }
 }
 
@@ -623,4 +789,63 @@ mod tests {
         assert_eq!(report.summary.complete_count, 1);
         assert_eq!(report.summary.has_sorry_count, 1);
     }
+
+    #[test]
+    fn test_report_rendering() {
+        let mut report = CoverageReport::new("/project");
+        let statuses = [
+            (SpecId::new(1, 10), Some(ProofStatus::Complete)),
+            (SpecId::new(1, 2), Some(ProofStatus::HasSorry)),
+            (SpecId::new(2, 1), Some(ProofStatus::Failed)),
+            (SpecId::new(1, 1), None),
+        ];
+        for (i, (id, status)) in statuses.into_iter().enumerate() {
+            let mut spec = SpecCoverage::new(id, format!("Req <{}>", i));
+            if let Some(status) = status {
+                spec.add_theorem(
+                    TheoremInfo::new(format!("thm_{}", i), "t.lean", i as u32).with_status(status),
+                );
+            }
+            report.add_spec(spec);
+        }
+
+        assert_eq!(report.coverage_percentage(), 75.0);
+
+        let md = report.to_markdown();
+        assert!(md.contains("**Coverage: 75.0%**"));
+        assert!(md.contains("`thm_0`"));
+        let pos = |s: &str| md.find(s).unwrap();
+        assert!(pos("| SPEC-01.01 |") < pos("| SPEC-01.02 |"));
+        assert!(pos("| SPEC-01.02 |") < pos("| SPEC-01.10 |"));
+        assert!(pos("| SPEC-01.10 |") < pos("| SPEC-02.01 |"));
+
+        let html = report.to_html();
+        assert!(html.contains("Coverage: 75.0%"));
+        assert!(html.contains("Req &lt;0&gt;"));
+        assert!(html.contains("<code>thm_0</code>"));
+        for status in [
+            ProofStatus::Complete,
+            ProofStatus::HasSorry,
+            ProofStatus::Failed,
+            ProofStatus::NotFormalized,
+        ] {
+            assert!(md.contains(&format!("{} |", status)));
+            assert!(html.contains(&format!("<tr class=\"status-{}\">", status.css_class())));
+        }
+
+        // Theorem names are escaped individually; markup in a name stays inert.
+        let mut hostile = SpecCoverage::new(SpecId::new(3, 1), "Req");
+        hostile.add_theorem(TheoremInfo::new(
+            "</code><script>x</script><code>",
+            "t.lean",
+            1,
+        ));
+        let mut report = CoverageReport::new("/project");
+        report.add_spec(hostile);
+        let html = report.to_html();
+        assert!(!html.contains("<script>"));
+        assert!(
+            html.contains("<code>&lt;/code&gt;&lt;script&gt;x&lt;/script&gt;&lt;code&gt;</code>")
+        );
+    }
 }
@@ -0,0 +1,159 @@
+//! Claim-level cache of verification results.
+//!
+//! Verifying a claim costs at least one model call, so re-verifying the same
+//! claim against the same context is wasted work. Entries are keyed by the
+//! claim text together with a hash of the context and evidence it was
+//! verified against, so the same claim under a different context never
+//! reuses a result. Because p0 is estimated by sampling, entries expire
+//! after a TTL rather than living forever.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use super::types::{BudgetResult, ClaimId};
+
+/// Cache key for a claim verified against a particular context.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClaimCacheKey {
+    /// Claim text, verbatim
+    pub claim: String,
+    /// SHA-256 of the context and evidence
+    pub context_hash: String,
+}
+
+impl ClaimCacheKey {
+    /// Build a key from the claim text, context, and evidence.
+    pub fn new(claim: &str, context: &str, evidence: &[String]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"context:");
+        hasher.update(context.as_bytes());
+        for item in evidence {
+            // Length-prefix so ["ab", "c"] and ["a", "bc"] hash differently
+            hasher.update(format!("\nevidence:{}:", item.len()).as_bytes());
+            hasher.update(item.as_bytes());
+        }
+
+        Self {
+            claim: claim.to_string(),
+            context_hash: format!("{:x}", hasher.finalize()),
+        }
+    }
+}
+
+/// TTL-bounded cache of [`BudgetResult`]s keyed by [`ClaimCacheKey`].
+#[derive(Debug)]
+pub struct ClaimCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<ClaimCacheKey, (BudgetResult, Instant)>>,
+}
+
+impl ClaimCache {
+    /// Create a cache whose entries expire `ttl` after insertion.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Time-to-live for entries.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Look up a fresh result, re-labelled with `claim_id`.
+    ///
+    /// Expired entries are dropped on access.
+    pub async fn get(&self, key: &ClaimCacheKey, claim_id: &ClaimId) -> Option<BudgetResult> {
+        {
+            let entries = self.entries.read().await;
+            match entries.get(key) {
+                Some((result, inserted)) if inserted.elapsed() < self.ttl => {
+                    let mut result = result.clone();
+                    result.claim_id = claim_id.clone();
+                    return Some(result);
+                }
+                Some(_) => {}
+                None => return None,
+            }
+        }
+
+        self.entries.write().await.remove(key);
+        None
+    }
+
+    /// Store a result.
+    pub async fn insert(&self, key: ClaimCacheKey, result: BudgetResult) {
+        self.entries
+            .write()
+            .await
+            .insert(key, (result, Instant::now()));
+    }
+
+    /// Drop expired entries, returning how many were removed.
+    pub async fn purge_expired(&self) -> usize {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|_, (_, inserted)| inserted.elapsed() < self.ttl);
+        before - entries.len()
+    }
+
+    /// Number of entries, including any not yet purged.
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Whether the cache is empty.
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+
+    /// Remove all entries.
+    pub async fn clear(&self) {
+        self.entries.write().await.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epistemic::types::Probability;
+
+    fn result() -> BudgetResult {
+        BudgetResult::new(
+            ClaimId::new(),
+            Probability::point(0.3),
+            Probability::point(0.9),
+            1.0,
+        )
+    }
+
+    #[test]
+    fn test_key_separates_contexts_and_evidence() {
+        let base = ClaimCacheKey::new("x is 1", "ctx", &[]);
+        assert_eq!(base, ClaimCacheKey::new("x is 1", "ctx", &[]));
+        assert_ne!(base, ClaimCacheKey::new("x is 1", "other ctx", &[]));
+        assert_ne!(
+            ClaimCacheKey::new("x is 1", "ctx", &["ab".to_string(), "c".to_string()]),
+            ClaimCacheKey::new("x is 1", "ctx", &["a".to_string(), "bc".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_relabels_and_expires() {
+        let cache = ClaimCache::new(Duration::from_secs(60));
+        let key = ClaimCacheKey::new("claim", "ctx", &[]);
+        cache.insert(key.clone(), result()).await;
+
+        let id = ClaimId::new();
+        let hit = cache.get(&key, &id).await.unwrap();
+        assert_eq!(hit.claim_id, id);
+
+        let expired = ClaimCache::new(Duration::ZERO);
+        expired.insert(key.clone(), result()).await;
+        assert!(expired.get(&key, &id).await.is_none());
+        assert!(expired.is_empty().await);
+    }
+}
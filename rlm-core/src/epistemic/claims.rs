@@ -5,9 +5,79 @@
 //! evaluated for grounding.
 
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 use super::types::{Claim, ClaimCategory, EvidenceRef, EvidenceType};
+use crate::error::{Error, Result};
+
+/// A domain-specific claim pattern for [`ClaimExtractor::with_rules`].
+///
+/// A sentence matching `pattern` is classified as `category` with the
+/// given specificity instead of the default heuristics. Each participating
+/// capture group becomes an [`EvidenceRef`] of `evidence_type`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimRule {
+    /// Rule name, recorded in the claim's metadata when it matches
+    pub name: String,
+    /// Regex matched against each sentence
+    pub pattern: String,
+    /// Category assigned to matching claims
+    pub category: ClaimCategory,
+    /// Specificity assigned to matching claims (0.0-1.0)
+    pub specificity: f64,
+    /// Evidence type for captured groups
+    #[serde(default = "default_rule_evidence_type")]
+    pub evidence_type: EvidenceType,
+}
+
+fn default_rule_evidence_type() -> EvidenceType {
+    EvidenceType::CodeRef
+}
+
+impl ClaimRule {
+    /// Create a rule; captured groups default to code references.
+    pub fn new(
+        name: impl Into<String>,
+        pattern: impl Into<String>,
+        category: ClaimCategory,
+        specificity: f64,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            pattern: pattern.into(),
+            category,
+            specificity: specificity.clamp(0.0, 1.0),
+            evidence_type: default_rule_evidence_type(),
+        }
+    }
+
+    /// Set the evidence type for captured groups.
+    pub fn with_evidence_type(mut self, evidence_type: EvidenceType) -> Self {
+        self.evidence_type = evidence_type;
+        self
+    }
+
+    /// Evidence references from every match's participating capture groups.
+    fn evidence(&self, regex: &Regex, text: &str) -> Vec<EvidenceRef> {
+        let names: Vec<Option<&str>> = regex.capture_names().collect();
+        let mut refs = Vec::new();
+        for caps in regex.captures_iter(text) {
+            for (index, group) in caps.iter().enumerate().skip(1) {
+                let Some(group) = group else { continue };
+                let label = names[index]
+                    .map(str::to_string)
+                    .unwrap_or_else(|| index.to_string());
+                refs.push(EvidenceRef::new(
+                    group.as_str(),
+                    self.evidence_type,
+                    format!("{} ({}): {}", self.name, label, group.as_str()),
+                ));
+            }
+        }
+        refs
+    }
+}
 
 /// Extract atomic claims from an LLM response.
 pub struct ClaimExtractor {
@@ -21,6 +91,8 @@ pub struct ClaimExtractor {
     factual_signals: Vec<String>,
     /// Words that signal hedging/uncertainty
     hedge_words: Vec<String>,
+    /// Custom rules, tried in order before the default classification
+    rules: Vec<(ClaimRule, Regex)>,
 }
 
 impl Default for ClaimExtractor {
@@ -66,9 +138,31 @@ impl ClaimExtractor {
                 "I believe".to_string(),
                 "may".to_string(),
             ],
+            rules: Vec::new(),
         }
     }
 
+    /// Add custom claim rules on top of the default heuristics.
+    ///
+    /// Rules are tried in order and the first match decides the category
+    /// and specificity; every matching rule contributes evidence. A sentence
+    /// yields at most one claim however many rules match it, and duplicate
+    /// evidence references are dropped.
+    ///
+    /// Returns [`Error::Config`] if a pattern fails to compile.
+    pub fn with_rules(mut self, rules: Vec<ClaimRule>) -> Result<Self> {
+        for rule in rules {
+            let regex = Regex::new(&rule.pattern).map_err(|e| {
+                Error::Config(format!(
+                    "invalid pattern for claim rule '{}': {}",
+                    rule.name, e
+                ))
+            })?;
+            self.rules.push((rule, regex));
+        }
+        Ok(self)
+    }
+
     /// Set minimum claim length.
     pub fn with_min_length(mut self, len: usize) -> Self {
         self.min_length = len;
@@ -112,8 +206,18 @@ impl ClaimExtractor {
                 continue;
             }
 
+            // Custom rules take precedence over the default heuristics
+            let matched: Vec<&(ClaimRule, Regex)> = self
+                .rules
+                .iter()
+                .filter(|(_, regex)| regex.is_match(trimmed))
+                .collect();
+
             // Classify the claim
-            let category = self.classify_claim(trimmed);
+            let category = match matched.first() {
+                Some((rule, _)) => rule.category,
+                None => self.classify_claim(trimmed),
+            };
 
             // Filter by category if specified
             if let Some(ref allowed) = self.categories {
@@ -123,7 +227,10 @@ impl ClaimExtractor {
             }
 
             // Calculate specificity
-            let specificity = self.estimate_specificity(trimmed);
+            let specificity = match matched.first() {
+                Some((rule, _)) => rule.specificity,
+                None => self.estimate_specificity(trimmed),
+            };
 
             // Calculate span in original text
             let span = self.find_span(response, trimmed, idx);
@@ -148,12 +255,30 @@ impl ClaimExtractor {
                 claim.metadata = Some(meta);
             }
 
+            if let Some((rule, _)) = matched.first() {
+                claim
+                    .metadata
+                    .get_or_insert_with(Default::default)
+                    .insert("rule".to_string(), serde_json::json!(rule.name));
+            }
+            for (rule, regex) in &matched {
+                claim.evidence_refs.extend(rule.evidence(regex, trimmed));
+            }
+
             claims.push(claim);
         }
 
         // Extract evidence references from the claims
         self.link_evidence(&mut claims, response);
 
+        // Overlapping rules and default linking can find the same reference
+        for claim in &mut claims {
+            let mut seen = HashSet::new();
+            claim
+                .evidence_refs
+                .retain(|e| seen.insert((e.id.clone(), e.evidence_type)));
+        }
+
         claims
     }
 
@@ -629,4 +754,74 @@ mod tests {
             assert!(claim.text.len() >= 20);
         }
     }
+
+    fn returns_rule() -> ClaimRule {
+        ClaimRule::new(
+            "returns",
+            r"[Ff]unction `?(?P<function>\w+)`? returns (?P<value>\w+)",
+            ClaimCategory::Factual,
+            0.9,
+        )
+    }
+
+    #[test]
+    fn test_custom_rule_sets_category_and_evidence() {
+        let extractor = ClaimExtractor::new()
+            .with_rules(vec![returns_rule()])
+            .unwrap();
+        let response =
+            "The function `parse_config` returns None on empty input. The cache has some entries.";
+
+        let claims = extractor.extract(response);
+        assert_eq!(claims.len(), 2);
+
+        let claim = &claims[0];
+        assert_eq!(claim.category, ClaimCategory::Factual);
+        assert_eq!(claim.specificity, 0.9);
+        assert_eq!(
+            claim.metadata.as_ref().unwrap()["rule"],
+            serde_json::json!("returns")
+        );
+        let ids: Vec<&str> = claim.evidence_refs.iter().map(|e| e.id.as_str()).collect();
+        // `parse_config` is also found as inline code but appears once
+        assert_eq!(ids, vec!["parse_config", "None"]);
+        assert!(claim.evidence_refs[0].description.contains("(function)"));
+
+        // Unmatched sentences keep the default heuristics
+        assert_ne!(
+            claims[1].metadata.as_ref().map(|m| m.contains_key("rule")),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_overlapping_rules_yield_one_claim() {
+        let extractor = ClaimExtractor::new()
+            .with_rules(vec![
+                returns_rule(),
+                ClaimRule::new("mentions-none", r"(None)", ClaimCategory::Numerical, 0.2)
+                    .with_evidence_type(EvidenceType::Citation),
+            ])
+            .unwrap();
+
+        let claims = extractor.extract("The function lookup returns None when missing.");
+        assert_eq!(claims.len(), 1);
+        // First rule wins; both contribute evidence, distinct by type
+        assert_eq!(claims[0].category, ClaimCategory::Factual);
+        assert_eq!(claims[0].evidence_refs.len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_rule_pattern() {
+        let err = ClaimExtractor::new()
+            .with_rules(vec![ClaimRule::new(
+                "bad",
+                "(",
+                ClaimCategory::Factual,
+                0.5,
+            )])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("claim rule 'bad'"));
+    }
 }
@@ -4,13 +4,21 @@
 //! stored in long-term memory. Only claims that pass epistemic verification
 //! are promoted to persistent storage.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono::Utc;
+use serde_json::{json, Value};
+
 use crate::error::Result;
-use crate::memory::{Node, NodeType, Tier};
+use crate::memory::{Node, NodeType, Provenance, ProvenanceSource, Tier};
+use crate::repl::floor_char_boundary;
 use crate::trajectory::{TrajectoryEvent, TrajectoryEventType};
 
-use super::types::{BudgetResult, Claim, ClaimCategory, GroundingStatus};
+use super::types::{
+    BudgetResult, Claim, ClaimCategory, EvidenceContribution, EvidenceEffect, EvidenceRef,
+    GroundingStatus,
+};
 use super::verifier::EpistemicVerifier;
 
 /// Configuration for the memory gate.
@@ -87,6 +95,60 @@ pub struct GateDecision {
     pub budget_result: Option<BudgetResult>,
     /// Recommendation for the node
     pub recommendation: GateRecommendation,
+    /// Evidence that informed the verification, if any
+    pub evidence: Vec<EvidenceContribution>,
+}
+
+impl GateDecision {
+    /// Apply an admitting decision to a node before it is stored.
+    ///
+    /// Sets the adjusted confidence and replaces the provenance with a
+    /// [`ProvenanceSource::Verification`] record whose context holds the
+    /// original and adjusted confidence, the budget figures, the supporting
+    /// evidence, and the node's prior provenance. Returns whether the node
+    /// was admitted; rejected nodes are left untouched.
+    pub fn apply(&self, node: &mut Node) -> bool {
+        if !self.allowed {
+            return false;
+        }
+
+        let original_confidence = node.confidence;
+        let adjusted_confidence = self.adjusted_confidence.unwrap_or(original_confidence);
+
+        let mut context: HashMap<String, Value> = HashMap::new();
+        context.insert("original_confidence".into(), json!(original_confidence));
+        context.insert("adjusted_confidence".into(), json!(adjusted_confidence));
+        context.insert(
+            "recommendation".into(),
+            json!(self.recommendation.to_string()),
+        );
+        context.insert("reason".into(), json!(self.reason));
+        if let Some(budget) = &self.budget_result {
+            context.insert("status".into(), json!(budget.status.to_string()));
+            context.insert("budget_gap".into(), json!(budget.budget_gap));
+            context.insert("observed_bits".into(), json!(budget.observed_bits));
+            context.insert("required_bits".into(), json!(budget.required_bits));
+        }
+        context.insert(
+            "evidence".into(),
+            serde_json::to_value(&self.evidence).unwrap_or(Value::Null),
+        );
+        if let Some(prior) = &node.provenance {
+            context.insert(
+                "prior_provenance".into(),
+                serde_json::to_value(prior).unwrap_or(Value::Null),
+            );
+        }
+
+        node.confidence = adjusted_confidence.clamp(0.0, 1.0);
+        node.provenance = Some(Provenance {
+            source_type: ProvenanceSource::Verification,
+            source_ref: self.budget_result.as_ref().map(|b| b.claim_id.to_string()),
+            observed_at: Utc::now(),
+            context: Some(context),
+        });
+        true
+    }
 }
 
 /// Recommendation from the memory gate.
@@ -117,12 +179,12 @@ impl std::fmt::Display for GateRecommendation {
 }
 
 /// Memory gate that filters ungrounded facts.
-pub struct MemoryGate<V: EpistemicVerifier> {
+pub struct MemoryGate<V: EpistemicVerifier + ?Sized> {
     verifier: Arc<V>,
     config: MemoryGateConfig,
 }
 
-impl<V: EpistemicVerifier> MemoryGate<V> {
+impl<V: EpistemicVerifier + ?Sized> MemoryGate<V> {
     /// Create a new memory gate.
     pub fn new(verifier: Arc<V>, config: MemoryGateConfig) -> Self {
         Self { verifier, config }
@@ -145,6 +207,22 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
 
     /// Evaluate a node for storage.
     pub async fn evaluate(&self, node: &Node, context: &str) -> Result<GateDecision> {
+        self.evaluate_with_evidence(node, context, &[]).await
+    }
+
+    /// Evaluate a node for storage against explicit evidence.
+    ///
+    /// The evidence descriptions are passed to the verifier, and the
+    /// resulting [`GateDecision::evidence`] records each reference's share
+    /// of the observed bits. Verifiers that report their own
+    /// [`BudgetResult::evidence_breakdown`] take precedence; otherwise bits
+    /// are split in proportion to evidence strength.
+    pub async fn evaluate_with_evidence(
+        &self,
+        node: &Node,
+        context: &str,
+        evidence: &[EvidenceRef],
+    ) -> Result<GateDecision> {
         // Skip verification for non-verified types/tiers
         if !self.requires_verification(node) {
             return Ok(GateDecision {
@@ -153,6 +231,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
                 adjusted_confidence: Some(node.confidence),
                 budget_result: None,
                 recommendation: GateRecommendation::Allow,
+                evidence: Vec::new(),
             });
         }
 
@@ -160,14 +239,19 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
         let claim = self.node_to_claim(node);
 
         // Verify the claim
-        let evidence: Vec<String> = Vec::new(); // Could extract from node metadata
+        let descriptions: Vec<String> = evidence.iter().map(|e| e.description.clone()).collect();
         let budget_result = self
             .verifier
-            .verify_claim(&claim, context, &evidence)
+            .verify_claim(&claim, context, &descriptions)
             .await?;
+        let budget_result = attribute_evidence(budget_result, evidence);
 
         // Make decision based on budget result
-        self.make_decision(node, budget_result)
+        let mut decision = self.make_decision(node, budget_result)?;
+        if let Some(budget) = &decision.budget_result {
+            decision.evidence = budget.evidence_breakdown.clone();
+        }
+        Ok(decision)
     }
 
     /// Evaluate a promotion (tier upgrade).
@@ -184,6 +268,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
                 adjusted_confidence: Some(node.confidence),
                 budget_result: None,
                 recommendation: GateRecommendation::Allow,
+                evidence: Vec::new(),
             });
         }
 
@@ -195,6 +280,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
                 adjusted_confidence: Some(node.confidence),
                 budget_result: None,
                 recommendation: GateRecommendation::Allow,
+                evidence: Vec::new(),
             });
         }
 
@@ -253,6 +339,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
                 adjusted_confidence: Some(node.confidence),
                 budget_result: Some(budget_result),
                 recommendation: GateRecommendation::Allow,
+                evidence: Vec::new(),
             });
         }
 
@@ -269,6 +356,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
                     adjusted_confidence: Some(adjusted),
                     budget_result: Some(budget_result),
                     recommendation: GateRecommendation::AllowWithPenalty,
+                    evidence: Vec::new(),
                 });
             }
         }
@@ -284,6 +372,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
                 adjusted_confidence: None,
                 budget_result: Some(budget_result),
                 recommendation: GateRecommendation::Reject,
+                evidence: Vec::new(),
             });
         }
 
@@ -295,6 +384,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
                 adjusted_confidence: None,
                 budget_result: Some(budget_result),
                 recommendation: GateRecommendation::Defer,
+                evidence: Vec::new(),
             });
         }
 
@@ -306,6 +396,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
             adjusted_confidence: Some(adjusted),
             budget_result: Some(budget_result),
             recommendation: GateRecommendation::AllowWithPenalty,
+            evidence: Vec::new(),
         })
     }
 
@@ -329,7 +420,7 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
             } else {
                 "REJECTED"
             },
-            &node.content[..node.content.len().min(50)],
+            &node.content[..floor_char_boundary(&node.content, 50)],
             decision.reason
         );
 
@@ -353,18 +444,162 @@ impl<V: EpistemicVerifier> MemoryGate<V> {
     }
 }
 
+/// Split a result's observed bits across `evidence` by strength.
+///
+/// Leaves results that already carry a breakdown unchanged.
+fn attribute_evidence(mut result: BudgetResult, evidence: &[EvidenceRef]) -> BudgetResult {
+    if !result.evidence_breakdown.is_empty() || evidence.is_empty() {
+        return result;
+    }
+
+    let effect = if result.p1.estimate > result.p0.estimate {
+        EvidenceEffect::Supporting
+    } else if result.p1.estimate < result.p0.estimate {
+        EvidenceEffect::Contradicting
+    } else {
+        EvidenceEffect::Neutral
+    };
+    let total_strength: f64 = evidence.iter().map(|e| e.strength).sum();
+    let observed_bits = result.observed_bits;
+
+    for item in evidence {
+        let share = if total_strength > 0.0 {
+            item.strength / total_strength
+        } else {
+            1.0 / evidence.len() as f64
+        };
+        result = result.with_evidence_contribution(EvidenceContribution {
+            evidence_id: item.id.clone(),
+            bits_contributed: observed_bits * share,
+            effect,
+        });
+    }
+    result
+}
+
 /// Simple gate that uses threshold-based filtering without full verification.
 ///
 /// Useful for high-throughput scenarios where full LLM-based verification
-/// is too expensive.
+/// is too expensive. Budget gaps from an earlier verification can be checked
+/// with [`ThresholdGate::evaluate_budget`] against a per-category threshold,
+/// so stricter categories (e.g. numerical claims) reject smaller gaps than
+/// lenient ones (e.g. user intent).
 pub struct ThresholdGate {
     config: MemoryGateConfig,
+    default_threshold: f64,
+    category_thresholds: HashMap<ClaimCategory, f64>,
 }
 
 impl ThresholdGate {
-    /// Create a new threshold gate.
+    /// Create a new threshold gate using `config.rejection_threshold` for
+    /// every category.
     pub fn new(config: MemoryGateConfig) -> Self {
-        Self { config }
+        let default_threshold = config.rejection_threshold;
+        Self {
+            config,
+            default_threshold,
+            category_thresholds: HashMap::new(),
+        }
+    }
+
+    /// Create a gate with per-category budget-gap thresholds.
+    ///
+    /// Categories missing from `category_thresholds` use `default_threshold`.
+    pub fn with_thresholds(
+        config: MemoryGateConfig,
+        default_threshold: f64,
+        category_thresholds: HashMap<ClaimCategory, f64>,
+    ) -> Self {
+        Self {
+            config,
+            default_threshold,
+            category_thresholds,
+        }
+    }
+
+    /// Override the threshold for one category.
+    pub fn with_category_threshold(mut self, category: ClaimCategory, threshold: f64) -> Self {
+        self.category_thresholds.insert(category, threshold);
+        self
+    }
+
+    /// Budget-gap threshold applied to a category.
+    pub fn threshold_for(&self, category: ClaimCategory) -> f64 {
+        self.category_thresholds
+            .get(&category)
+            .copied()
+            .unwrap_or(self.default_threshold)
+    }
+
+    /// Decide on a node from a budget result already computed for it.
+    ///
+    /// A non-positive gap is grounded and allowed as-is. A gap above the
+    /// category's threshold is rejected. Gaps in between are borderline:
+    /// the node is stored with reduced confidence, or queued for full
+    /// verification when weak grounding is not allowed.
+    pub fn evaluate_budget(
+        &self,
+        node: &Node,
+        category: ClaimCategory,
+        budget_result: BudgetResult,
+    ) -> GateDecision {
+        let budget_gap = budget_result.budget_gap;
+        let threshold = self.threshold_for(category);
+
+        if budget_gap <= 0.0 {
+            return GateDecision {
+                allowed: true,
+                reason: format!(
+                    "{} claim grounded (budget_gap={:.2}, threshold={:.2})",
+                    category, budget_gap, threshold
+                ),
+                adjusted_confidence: Some(node.confidence),
+                budget_result: Some(budget_result),
+                recommendation: GateRecommendation::Allow,
+                evidence: Vec::new(),
+            };
+        }
+
+        if budget_gap > threshold {
+            return GateDecision {
+                allowed: false,
+                reason: format!(
+                    "{} claim ungrounded (budget_gap={:.2} > {} threshold={:.2})",
+                    category, budget_gap, category, threshold
+                ),
+                adjusted_confidence: None,
+                budget_result: Some(budget_result),
+                recommendation: GateRecommendation::Reject,
+                evidence: Vec::new(),
+            };
+        }
+
+        if !self.config.allow_weak_grounding {
+            return GateDecision {
+                allowed: false,
+                reason: format!(
+                    "{} claim borderline (budget_gap={:.2} <= {} threshold={:.2}), queued for verification",
+                    category, budget_gap, category, threshold
+                ),
+                adjusted_confidence: None,
+                budget_result: Some(budget_result),
+                recommendation: GateRecommendation::QueueForVerification,
+                evidence: Vec::new(),
+            };
+        }
+
+        let adjusted = node.confidence * (1.0 - self.config.weak_grounding_penalty);
+        GateDecision {
+            allowed: true,
+            reason: format!(
+                "{} claim borderline (budget_gap={:.2} <= {} threshold={:.2}), storing with reduced confidence",
+                category, budget_gap, category, threshold
+            ),
+            adjusted_confidence: Some(adjusted),
+            budget_result: Some(budget_result),
+            recommendation: GateRecommendation::AllowWithPenalty,
+            evidence: Vec::new(),
+        }
     }
 
     /// Evaluate a node based on heuristics (no LLM calls).
@@ -377,6 +612,7 @@ impl ThresholdGate {
                 adjusted_confidence: Some(node.confidence),
                 budget_result: None,
                 recommendation: GateRecommendation::Allow,
+                evidence: Vec::new(),
             };
         }
 
@@ -388,6 +624,7 @@ impl ThresholdGate {
                 adjusted_confidence: None,
                 budget_result: None,
                 recommendation: GateRecommendation::Reject,
+                evidence: Vec::new(),
             };
         }
 
@@ -411,6 +648,7 @@ impl ThresholdGate {
                 adjusted_confidence: Some(adjusted),
                 budget_result: None,
                 recommendation: GateRecommendation::AllowWithPenalty,
+                evidence: Vec::new(),
             };
         }
 
@@ -425,6 +663,7 @@ impl ThresholdGate {
                 adjusted_confidence: None,
                 budget_result: None,
                 recommendation: GateRecommendation::QueueForVerification,
+                evidence: Vec::new(),
             };
         }
 
@@ -434,6 +673,7 @@ impl ThresholdGate {
             adjusted_confidence: Some(node.confidence),
             budget_result: None,
             recommendation: GateRecommendation::Allow,
+            evidence: Vec::new(),
         }
     }
 }
@@ -583,6 +823,7 @@ mod tests {
             adjusted_confidence: Some(0.8),
             budget_result: None,
             recommendation: GateRecommendation::Allow,
+            evidence: Vec::new(),
         });
 
         stats.record(&GateDecision {
@@ -591,6 +832,7 @@ mod tests {
             adjusted_confidence: None,
             budget_result: None,
             recommendation: GateRecommendation::Reject,
+            evidence: Vec::new(),
         });
 
         assert_eq!(stats.total_evaluated, 2);
@@ -609,4 +851,139 @@ mod tests {
         assert!(permissive.rejection_threshold >= 1.0);
         assert!(permissive.allow_weak_grounding);
     }
+
+    /// Returns a fixed weakly grounded result for every claim.
+    struct WeakVerifier {
+        config: super::super::types::VerificationConfig,
+    }
+
+    #[async_trait::async_trait]
+    impl EpistemicVerifier for WeakVerifier {
+        async fn verify_claim(
+            &self,
+            claim: &Claim,
+            _context: &str,
+            evidence: &[String],
+        ) -> Result<BudgetResult> {
+            assert_eq!(evidence, ["Cargo.toml pins tokio 1.40"]);
+            let p0 = super::super::types::Probability::point(0.5);
+            let p1 = super::super::types::Probability::point(0.9);
+            let observed = p0.kl_divergence(&p1);
+            Ok(BudgetResult::new(claim.id.clone(), p0, p1, observed + 0.2))
+        }
+
+        async fn verify_response(
+            &self,
+            _response: &str,
+            _context: &str,
+        ) -> Result<super::super::types::VerificationResult> {
+            Err(crate::error::Error::LLM("not implemented".into()))
+        }
+
+        fn config(&self) -> &super::super::types::VerificationConfig {
+            &self.config
+        }
+
+        async fn get_events(&self) -> Vec<TrajectoryEvent> {
+            Vec::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gated_node_persists_evidence_provenance() {
+        let gate = MemoryGate::new(
+            Arc::new(WeakVerifier {
+                config: Default::default(),
+            }),
+            MemoryGateConfig::default(),
+        );
+        let mut node =
+            create_test_node("The project uses tokio 1.40", NodeType::Fact, Tier::Session);
+        let evidence = EvidenceRef::new(
+            "Cargo.toml",
+            super::super::types::EvidenceType::CodeRef,
+            "Cargo.toml pins tokio 1.40",
+        );
+
+        let decision = gate
+            .evaluate_with_evidence(&node, "dependency audit", &[evidence])
+            .await
+            .unwrap();
+        assert_eq!(
+            decision.recommendation,
+            GateRecommendation::AllowWithPenalty
+        );
+        assert_eq!(decision.evidence.len(), 1);
+        assert_eq!(decision.evidence[0].effect, EvidenceEffect::Supporting);
+
+        assert!(decision.apply(&mut node));
+        let store = crate::memory::SqliteMemoryStore::in_memory().unwrap();
+        store.add_node(&node).unwrap();
+
+        let provenance = store.provenance_for(&node.id).unwrap().unwrap();
+        assert_eq!(provenance.source_type, ProvenanceSource::Verification);
+        let context = provenance.context.unwrap();
+        assert_eq!(context["original_confidence"], json!(0.8));
+        let adjusted = context["adjusted_confidence"].as_f64().unwrap();
+        assert!((adjusted - 0.8 * 0.7).abs() < 1e-9);
+        assert_eq!(context["evidence"][0]["evidence_id"], json!("Cargo.toml"));
+        assert_eq!(context["status"], json!("weakly_grounded"));
+        assert!((store.get_node(&node.id).unwrap().unwrap().confidence - adjusted).abs() < 1e-9);
+
+        assert!(store
+            .provenance_for(&crate::memory::NodeId::new())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_threshold_gate_per_category_thresholds() {
+        let gate = ThresholdGate::with_thresholds(
+            MemoryGateConfig::default(),
+            0.5,
+            HashMap::from([(ClaimCategory::UserIntent, 0.8)]),
+        )
+        .with_category_threshold(ClaimCategory::Numerical, 0.2);
+        assert_eq!(gate.threshold_for(ClaimCategory::Factual), 0.5);
+
+        let node = create_test_node("Latency budget is 250ms", NodeType::Fact, Tier::Session);
+        let p0 = super::super::types::Probability::point(0.5);
+        let p1 = super::super::types::Probability::point(0.7);
+        let required = p0.kl_divergence(&p1) + 0.3;
+        let budget = BudgetResult::new(crate::epistemic::ClaimId::new(), p0, p1, required);
+
+        let lenient = gate.evaluate_budget(&node, ClaimCategory::UserIntent, budget.clone());
+        assert!(lenient.allowed);
+        assert_eq!(lenient.recommendation, GateRecommendation::AllowWithPenalty);
+        assert!(lenient.adjusted_confidence.unwrap() < node.confidence);
+        assert!(lenient.reason.contains("user_intent threshold=0.80"));
+        assert!(lenient.reason.contains("reduced confidence"));
+
+        let strict = gate.evaluate_budget(&node, ClaimCategory::Numerical, budget.clone());
+        assert!(!strict.allowed);
+        assert_eq!(strict.recommendation, GateRecommendation::Reject);
+        assert!(strict.reason.contains("numerical threshold=0.20"));
+
+        let queued = ThresholdGate::new(MemoryGateConfig {
+            allow_weak_grounding: false,
+            ..MemoryGateConfig::default()
+        })
+        .evaluate_budget(&node, ClaimCategory::Factual, budget);
+        assert_eq!(
+            queued.recommendation,
+            GateRecommendation::QueueForVerification
+        );
+    }
+
+    #[test]
+    fn test_rejected_decision_leaves_node_untouched() {
+        let gate = ThresholdGate::new(MemoryGateConfig::default());
+        let mut node = create_test_node("Low confidence fact", NodeType::Fact, Tier::Session);
+        node.confidence = 0.1;
+
+        let decision = gate.evaluate(&node);
+        assert!(!decision.apply(&mut node));
+        assert!(node.provenance.is_none());
+        assert_eq!(node.confidence, 0.1);
+    }
 }
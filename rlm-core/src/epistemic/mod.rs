@@ -23,6 +23,10 @@
 //! - Estimate p0 from agreement rate across samples
 //! - Compare to p1 from original response
 //!
+//! Where the provider does expose logprobs (OpenAI), [`LogprobVerifier`] or
+//! [`VerificationStrategy::Logprob`] reads p0 and p1 from the probability of
+//! "Yes" to a yes/no probe, with and without evidence, in two calls per claim.
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -78,6 +82,7 @@
 //! - `audit_reasoning(trace)` - Audit a reasoning trace for hallucinations
 //! - `evidence_dependence(response, context)` - Measure evidence dependence
 
+pub mod cache;
 pub mod claims;
 pub mod kl;
 pub mod memory_gate;
@@ -89,7 +94,8 @@ pub mod verifier;
 mod proptest;
 
 // Re-exports for convenience
-pub use claims::{extract_doc_claims, extract_numerical_claims, ClaimExtractor};
+pub use cache::{ClaimCache, ClaimCacheKey};
+pub use claims::{extract_doc_claims, extract_numerical_claims, ClaimExtractor, ClaimRule};
 pub use kl::{
     aggregate_evidence_bits, aggregate_evidence_bits_with_correlation, bernoulli_kl_bits,
     bernoulli_kl_nats, binary_entropy_bits, binary_entropy_nats, jensen_shannon_bits, kl_interval,
@@ -105,9 +111,11 @@ pub use scrubber::{
 pub use types::{
     BudgetResult, Claim, ClaimCategory, ClaimId, Evidence, EvidenceContribution, EvidenceEffect,
     EvidenceRef, EvidenceType, GroundingStatus, Probability, VerificationConfig,
-    VerificationResult, VerificationStats, VerificationVerdict,
+    VerificationResult, VerificationStats, VerificationStrategy, VerificationVerdict,
+};
+pub use verifier::{
+    BatchVerifier, EpistemicVerifier, HaikuVerifier, LogprobVerifier, SelfVerifier,
 };
-pub use verifier::{BatchVerifier, EpistemicVerifier, HaikuVerifier, SelfVerifier};
 
 /// Verify a claim and return the budget result.
 ///
@@ -506,6 +506,12 @@ pub struct VerificationStats {
     pub max_budget_gap: f64,
     /// Total LLM samples used
     pub total_samples: u32,
+    /// Claims served from the claim cache without an LLM call
+    #[serde(default)]
+    pub cache_hits: u32,
+    /// Claims whose verification errored and are missing from the results
+    #[serde(default)]
+    pub failed_claims: u32,
 }
 
 impl VerificationStats {
@@ -547,6 +553,32 @@ pub struct VerificationConfig {
     pub verify_all_claims: bool,
     /// Maximum claims to verify if sampling
     pub max_claims: Option<u32>,
+    /// How p0/p1 are estimated
+    #[serde(default)]
+    pub strategy: VerificationStrategy,
+    /// Cache results per claim and context for this many seconds
+    /// (None = no caching)
+    #[serde(default)]
+    pub cache_ttl_secs: Option<u64>,
+    /// Maximum claims verified concurrently in batch mode
+    #[serde(default = "default_max_parallel")]
+    pub max_parallel: usize,
+}
+
+fn default_max_parallel() -> usize {
+    crate::llm::DEFAULT_MAX_PARALLEL
+}
+
+/// How a verifier estimates claim probabilities.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStrategy {
+    /// Sample completions with evidence masked and measure agreement
+    #[default]
+    Sampling,
+    /// Read p0/p1 from token logprobs; falls back to sampling when the
+    /// client does not expose logprobs
+    Logprob,
 }
 
 impl Default for VerificationConfig {
@@ -560,6 +592,9 @@ impl Default for VerificationConfig {
             verification_model: None, // Use Haiku by default
             verify_all_claims: false,
             max_claims: Some(10),
+            strategy: VerificationStrategy::Sampling,
+            cache_ttl_secs: None,
+            max_parallel: default_max_parallel(),
         }
     }
 }
@@ -576,6 +611,9 @@ impl VerificationConfig {
             verification_model: Some("claude-3-5-haiku-20241022".to_string()),
             verify_all_claims: false,
             max_claims: Some(5),
+            strategy: VerificationStrategy::Sampling,
+            cache_ttl_secs: None,
+            max_parallel: default_max_parallel(),
         }
     }
 
@@ -590,8 +628,29 @@ impl VerificationConfig {
             verification_model: Some("claude-3-5-sonnet-20241022".to_string()),
             verify_all_claims: true,
             max_claims: None,
+            strategy: VerificationStrategy::Sampling,
+            cache_ttl_secs: None,
+            max_parallel: default_max_parallel(),
         }
     }
+
+    /// Cache verification results per claim for `ttl`.
+    pub fn with_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.cache_ttl_secs = Some(ttl.as_secs());
+        self
+    }
+
+    /// Set how many claims batch mode verifies concurrently (at least 1).
+    pub fn with_max_parallel(mut self, max: usize) -> Self {
+        self.max_parallel = max.max(1);
+        self
+    }
+
+    /// Set the probability estimation strategy.
+    pub fn with_strategy(mut self, strategy: VerificationStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -4,26 +4,36 @@
 //! for different verification backends:
 //! - Self-verification (same model, different context)
 //! - Haiku-assisted (fast, cheap verification)
+//! - Logprob-based (reads p0/p1 from token probabilities where exposed)
 //! - External API (for specialized verification services)
 
 use async_trait::async_trait;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, Semaphore};
 
 use crate::error::{Error, Result};
-use crate::llm::{ChatMessage, CompletionRequest, LLMClient};
+use crate::llm::{
+    default_provider_rate_limits, ChatMessage, CompletionRequest, LLMClient, Provider,
+    ProviderRateLimiter, TokenLogprob, DEFAULT_RATE_LIMIT_WINDOW_MS,
+};
+use crate::repl::floor_char_boundary;
 use crate::trajectory::{TrajectoryEvent, TrajectoryEventType};
 
+use super::cache::{ClaimCache, ClaimCacheKey};
 use super::claims::ClaimExtractor;
 use super::kl::required_bits_for_specificity;
 use super::scrubber::{create_p0_prompt, EvidenceScrubber, ScrubConfig};
 use super::types::{
     BudgetResult, Claim, GroundingStatus, Probability, VerificationConfig, VerificationResult,
-    VerificationStats, VerificationVerdict,
+    VerificationStats, VerificationStrategy, VerificationVerdict,
 };
 
+/// Alternatives requested per token when reading logprobs.
+const LOGPROB_ALTERNATIVES: u8 = 5;
+
 /// Trait for epistemic verification backends.
 #[async_trait]
 pub trait EpistemicVerifier: Send + Sync {
@@ -85,6 +95,32 @@ impl SelfVerifier {
         self.events.write().await.push(event);
     }
 
+    /// Whether p0/p1 come from logprobs rather than sampling.
+    ///
+    /// Requires both the [`VerificationStrategy::Logprob`] strategy and a
+    /// client that exposes logprobs.
+    pub fn uses_logprobs(&self) -> bool {
+        self.config.strategy == VerificationStrategy::Logprob && self.client.supports_logprobs()
+    }
+
+    /// Estimate p0 and p1 from the probability mass on "Yes" when asked
+    /// whether the claim holds, with evidence scrubbed and with it present.
+    async fn estimate_from_logprobs(
+        &self,
+        claim: &Claim,
+        context: &str,
+        evidence: &[String],
+    ) -> Result<(Probability, Probability)> {
+        estimate_from_logprobs(
+            self.client.as_ref(),
+            &self.scrubber,
+            claim,
+            context,
+            evidence,
+        )
+        .await
+    }
+
     /// Estimate p0 by sampling with masked evidence.
     async fn estimate_p0(
         &self,
@@ -178,16 +214,21 @@ impl EpistemicVerifier for SelfVerifier {
             0,
             format!(
                 "Verifying claim: {}",
-                &claim.text[..claim.text.len().min(50)]
+                &claim.text[..floor_char_boundary(&claim.text, 50)]
             ),
         ))
         .await;
 
-        // Estimate p0 (prior without evidence)
-        let p0 = self.estimate_p0(claim, context, evidence).await?;
-
-        // Estimate p1 (posterior with evidence)
-        let p1 = self.estimate_p1(claim);
+        let (p0, p1) = if self.uses_logprobs() {
+            self.estimate_from_logprobs(claim, context, evidence)
+                .await?
+        } else {
+            // Estimate p0 (prior without evidence), p1 (posterior with evidence)
+            (
+                self.estimate_p0(claim, context, evidence).await?,
+                self.estimate_p1(claim),
+            )
+        };
 
         // Calculate required bits based on specificity
         let required_bits = required_bits_for_specificity(claim.specificity);
@@ -243,7 +284,7 @@ impl EpistemicVerifier for SelfVerifier {
                 format!(
                     "[{}] {}",
                     claim.category,
-                    &claim.text[..claim.text.len().min(60)]
+                    &claim.text[..floor_char_boundary(&claim.text, 60)]
                 ),
             ))
             .await;
@@ -357,7 +398,13 @@ impl SelfVerifier {
             stats.max_budget_gap = max_gap;
         }
 
-        stats.total_samples = self.config.n_samples * stats.total_claims;
+        // Logprob estimation makes one call each for p0 and p1
+        let samples_per_claim = if self.uses_logprobs() {
+            2
+        } else {
+            self.config.n_samples
+        };
+        stats.total_samples = samples_per_claim * stats.total_claims;
 
         stats
     }
@@ -407,9 +454,74 @@ impl EpistemicVerifier for HaikuVerifier {
     }
 }
 
+/// Logprob-based verification.
+///
+/// Estimates p0 and p1 directly from token probabilities of a yes/no probe,
+/// which takes two calls per claim instead of `n_samples`. Only available
+/// for clients whose [`LLMClient::supports_logprobs`] is true.
+pub struct LogprobVerifier {
+    inner: SelfVerifier,
+}
+
+impl LogprobVerifier {
+    /// Create a logprob verifier, failing if the client lacks logprobs.
+    pub fn new(client: Arc<dyn LLMClient>, config: VerificationConfig) -> Result<Self> {
+        if !client.supports_logprobs() {
+            return Err(Error::Config(format!(
+                "{} client does not expose logprobs",
+                client.provider()
+            )));
+        }
+
+        Ok(Self {
+            inner: SelfVerifier::new(client, config.with_strategy(VerificationStrategy::Logprob)),
+        })
+    }
+
+    /// Create with custom claim extractor.
+    pub fn with_extractor(mut self, extractor: ClaimExtractor) -> Self {
+        self.inner = self.inner.with_extractor(extractor);
+        self
+    }
+
+    /// Create with custom scrubber.
+    pub fn with_scrubber(mut self, scrubber: EvidenceScrubber) -> Self {
+        self.inner = self.inner.with_scrubber(scrubber);
+        self
+    }
+}
+
+#[async_trait]
+impl EpistemicVerifier for LogprobVerifier {
+    async fn verify_claim(
+        &self,
+        claim: &Claim,
+        context: &str,
+        evidence: &[String],
+    ) -> Result<BudgetResult> {
+        self.inner.verify_claim(claim, context, evidence).await
+    }
+
+    async fn verify_response(&self, response: &str, context: &str) -> Result<VerificationResult> {
+        self.inner.verify_response(response, context).await
+    }
+
+    fn config(&self) -> &VerificationConfig {
+        self.inner.config()
+    }
+
+    async fn get_events(&self) -> Vec<TrajectoryEvent> {
+        self.inner.get_events().await
+    }
+}
+
 /// Batch verifier for efficient verification of multiple claims.
 ///
-/// Sends all p0 estimation requests in parallel for lower latency.
+/// Sends p0 estimation requests concurrently, at most `config.max_parallel`
+/// at a time and within the provider's request rate limit. With the
+/// [`VerificationStrategy::Logprob`] strategy and a client that exposes
+/// logprobs, each claim's p0 and p1 come from a pair of yes/no probes, as in
+/// [`LogprobVerifier`]; otherwise a single sampled completion estimates p0.
 pub struct BatchVerifier {
     client: Arc<dyn LLMClient>,
     config: VerificationConfig,
@@ -417,40 +529,238 @@ pub struct BatchVerifier {
     #[allow(dead_code)] // Reserved for evidence scrubbing in verification pipeline
     scrubber: EvidenceScrubber,
     events: Arc<RwLock<Vec<TrajectoryEvent>>>,
+    cache: Option<Arc<ClaimCache>>,
+    rate_limits: HashMap<Provider, u32>,
+    rate_limiter: Arc<ProviderRateLimiter>,
 }
 
 impl BatchVerifier {
     /// Create a new batch verifier.
+    ///
+    /// Results are cached per claim if `config.cache_ttl_secs` is set.
     pub fn new(client: Arc<dyn LLMClient>, config: VerificationConfig) -> Self {
+        let cache = config
+            .cache_ttl_secs
+            .map(|ttl| Arc::new(ClaimCache::new(Duration::from_secs(ttl))));
         Self {
             client,
             config,
             claim_extractor: ClaimExtractor::new(),
             scrubber: EvidenceScrubber::new(ScrubConfig::default()),
             events: Arc::new(RwLock::new(Vec::new())),
+            cache,
+            rate_limits: default_provider_rate_limits(),
+            rate_limiter: Arc::new(ProviderRateLimiter::new(
+                default_provider_rate_limits(),
+                Duration::from_millis(DEFAULT_RATE_LIMIT_WINDOW_MS),
+            )),
         }
     }
 
+    /// Override the requests-per-minute limit for one provider.
+    ///
+    /// A limit of 0 disables throttling for that provider.
+    pub fn with_provider_rate_limit(
+        mut self,
+        provider: Provider,
+        requests_per_minute: u32,
+    ) -> Self {
+        self.rate_limits.insert(provider, requests_per_minute);
+        self.rate_limiter = Arc::new(ProviderRateLimiter::new(
+            self.rate_limits.clone(),
+            Duration::from_millis(DEFAULT_RATE_LIMIT_WINDOW_MS),
+        ));
+        self
+    }
+
+    /// Use a shared claim cache, e.g. one reused across verifiers.
+    pub fn with_cache(mut self, cache: Arc<ClaimCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The claim cache, if caching is enabled.
+    pub fn cache(&self) -> Option<&Arc<ClaimCache>> {
+        self.cache.as_ref()
+    }
+
     async fn emit_event(&self, event: TrajectoryEvent) {
         self.events.write().await.push(event);
     }
 
-    /// Verify multiple claims in parallel.
+    /// Whether p0/p1 come from logprobs rather than a sampled completion.
+    ///
+    /// Requires both the [`VerificationStrategy::Logprob`] strategy and a
+    /// client that exposes logprobs.
+    pub fn uses_logprobs(&self) -> bool {
+        self.config.strategy == VerificationStrategy::Logprob && self.client.supports_logprobs()
+    }
+
+    /// Samples counted per verified claim in the stats.
+    fn samples_per_claim(&self) -> u32 {
+        // Logprob estimation makes one call each for p0 and p1
+        if self.uses_logprobs() {
+            2
+        } else {
+            self.config.n_samples
+        }
+    }
+
+    /// Verify already-extracted claims, e.g. from a reasoning-trace audit.
+    ///
+    /// `budget_results` follow the order of `claims`. Claims whose
+    /// verification errors are left out of `budget_results`, reported as
+    /// error events, and counted in `stats.failed_claims`.
+    pub async fn verify_claims(&self, claims: Vec<Claim>, context: &str) -> VerificationResult {
+        let start = Instant::now();
+        self.emit_event(TrajectoryEvent::new(
+            TrajectoryEventType::VerifyStart,
+            0,
+            format!("Starting batch verification of {} claims", claims.len()),
+        ))
+        .await;
+        self.aggregate(claims, context, start).await
+    }
+
+    /// Verify `claims` and aggregate them into a single result.
+    async fn aggregate(
+        &self,
+        claims: Vec<Claim>,
+        context: &str,
+        start: Instant,
+    ) -> VerificationResult {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let (results, cache_hits) = self.verify_claims_batch(&claims, context, &[]).await;
+
+        let mut budget_results = Vec::new();
+        let mut failed_claims = 0;
+        for result in results {
+            match result {
+                Ok(r) => {
+                    if r.should_flag(self.config.hallucination_threshold) {
+                        self.emit_event(TrajectoryEvent::hallucination_flag(
+                            0,
+                            "Claim flagged".to_string(),
+                            r.budget_gap,
+                            r.status.to_string(),
+                        ))
+                        .await;
+                    }
+                    budget_results.push(r);
+                }
+                Err(e) => {
+                    failed_claims += 1;
+                    self.emit_event(TrajectoryEvent::error(
+                        0,
+                        format!("Batch verification error: {}", e),
+                    ))
+                    .await;
+                }
+            }
+        }
+
+        // Calculate statistics
+        let samples_per_claim = self.samples_per_claim();
+        let mut stats = calculate_verification_stats(&budget_results, samples_per_claim);
+        stats.cache_hits = cache_hits;
+        stats.failed_claims = failed_claims;
+        stats.total_samples = stats
+            .total_samples
+            .saturating_sub(cache_hits * samples_per_claim);
+
+        let verdict = if stats.ungrounded_claims > 0 {
+            VerificationVerdict::Unverified
+        } else if stats.weakly_grounded_claims > 0 {
+            VerificationVerdict::PartiallyVerified
+        } else if stats.total_claims > 0 {
+            VerificationVerdict::Verified
+        } else {
+            VerificationVerdict::Error
+        };
+
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        self.emit_event(TrajectoryEvent::new(
+            TrajectoryEventType::VerifyComplete,
+            0,
+            format!(
+                "Batch verification complete: {} claims, latency {}ms",
+                stats.total_claims, latency_ms
+            ),
+        ))
+        .await;
+
+        VerificationResult {
+            session_id,
+            claims,
+            budget_results,
+            verdict,
+            stats,
+            completed_at: Utc::now(),
+            latency_ms,
+        }
+    }
+
+    /// Verify multiple claims concurrently, serving what it can from the cache.
+    ///
+    /// Returns results in claim order and the number of cache hits.
     async fn verify_claims_batch(
         &self,
         claims: &[Claim],
         context: &str,
-    ) -> Vec<Result<BudgetResult>> {
-        let futures: Vec<_> = claims
+        evidence: &[String],
+    ) -> (Vec<Result<BudgetResult>>, u32) {
+        let mut results: Vec<Option<Result<BudgetResult>>> = Vec::with_capacity(claims.len());
+        let mut misses = Vec::new();
+        for claim in claims {
+            let key = ClaimCacheKey::new(&claim.text, context, evidence);
+            let cached = match &self.cache {
+                Some(cache) => cache.get(&key, &claim.id).await,
+                None => None,
+            };
+            if cached.is_none() {
+                misses.push((results.len(), key));
+            }
+            results.push(cached.map(Ok));
+        }
+        let hits = (claims.len() - misses.len()) as u32;
+
+        let semaphore = Arc::new(Semaphore::new(self.config.max_parallel.max(1)));
+        let provider = self.client.provider();
+        let use_logprobs = self.uses_logprobs();
+        let futures: Vec<_> = misses
             .iter()
+            .map(|(index, _)| &claims[*index])
             .map(|claim| {
                 let client = self.client.clone();
                 let config = self.config.clone();
                 let scrubber = EvidenceScrubber::new(ScrubConfig::default());
                 let claim = claim.clone();
                 let context = context.to_string();
+                let evidence = evidence.to_vec();
+                let semaphore = semaphore.clone();
+                let rate_limiter = self.rate_limiter.clone();
 
                 async move {
+                    let _permit = semaphore
+                        .acquire()
+                        .await
+                        .map_err(|e| Error::Internal(e.to_string()))?;
+                    rate_limiter.acquire(provider).await;
+                    let required_bits = required_bits_for_specificity(claim.specificity);
+
+                    if use_logprobs {
+                        let (p0, p1) = estimate_from_logprobs(
+                            client.as_ref(),
+                            &scrubber,
+                            &claim,
+                            &context,
+                            &evidence,
+                        )
+                        .await?;
+                        return Ok(BudgetResult::new(claim.id, p0, p1, required_bits));
+                    }
+
                     let p0_prompt = create_p0_prompt(&context, &claim.text, &scrubber);
 
                     // Single sample for batch mode (faster)
@@ -471,14 +781,24 @@ impl BatchVerifier {
                     // p1 from original response
                     let p1 = Probability::point(0.85 * claim.specificity + 0.15);
 
-                    let required_bits = required_bits_for_specificity(claim.specificity);
-
                     Ok(BudgetResult::new(claim.id, p0, p1, required_bits))
                 }
             })
             .collect();
 
-        futures::future::join_all(futures).await
+        let fresh = futures::future::join_all(futures).await;
+        for ((index, key), result) in misses.into_iter().zip(fresh) {
+            if let (Some(cache), Ok(result)) = (&self.cache, &result) {
+                cache.insert(key, result.clone()).await;
+            }
+            results[index] = Some(result);
+        }
+
+        let results = results
+            .into_iter()
+            .map(|r| r.unwrap_or_else(|| Err(Error::Internal("Claim not verified".to_string()))))
+            .collect();
+        (results, hits)
     }
 }
 
@@ -488,9 +808,11 @@ impl EpistemicVerifier for BatchVerifier {
         &self,
         claim: &Claim,
         context: &str,
-        _evidence: &[String],
+        evidence: &[String],
     ) -> Result<BudgetResult> {
-        let results = self.verify_claims_batch(&[claim.clone()], context).await;
+        let (results, _) = self
+            .verify_claims_batch(&[claim.clone()], context, evidence)
+            .await;
         results
             .into_iter()
             .next()
@@ -499,7 +821,6 @@ impl EpistemicVerifier for BatchVerifier {
 
     async fn verify_response(&self, response: &str, context: &str) -> Result<VerificationResult> {
         let start = Instant::now();
-        let session_id = uuid::Uuid::new_v4().to_string();
 
         self.emit_event(TrajectoryEvent::new(
             TrajectoryEventType::VerifyStart,
@@ -523,77 +844,103 @@ impl EpistemicVerifier for BatchVerifier {
             }
         }
 
-        // Verify all claims in parallel
-        let results = self.verify_claims_batch(&claims, context).await;
+        Ok(self.aggregate(claims, context, start).await)
+    }
 
-        let mut budget_results = Vec::new();
-        for result in results {
-            match result {
-                Ok(r) => {
-                    if r.should_flag(self.config.hallucination_threshold) {
-                        self.emit_event(TrajectoryEvent::hallucination_flag(
-                            0,
-                            "Claim flagged".to_string(),
-                            r.budget_gap,
-                            r.status.to_string(),
-                        ))
-                        .await;
-                    }
-                    budget_results.push(r);
-                }
-                Err(e) => {
-                    self.emit_event(TrajectoryEvent::error(
-                        0,
-                        format!("Batch verification error: {}", e),
-                    ))
-                    .await;
-                }
-            }
+    fn config(&self) -> &VerificationConfig {
+        &self.config
+    }
+
+    async fn get_events(&self) -> Vec<TrajectoryEvent> {
+        self.events.read().await.clone()
+    }
+}
+
+/// Estimate p0 and p1 from the probability mass on "Yes" when asked
+/// whether the claim holds, with evidence scrubbed and with it present.
+async fn estimate_from_logprobs(
+    client: &dyn LLMClient,
+    scrubber: &EvidenceScrubber,
+    claim: &Claim,
+    context: &str,
+    evidence: &[String],
+) -> Result<(Probability, Probability)> {
+    let scrubbed = scrubber.scrub(context).scrubbed_text;
+    let mut full = context.to_string();
+    if !evidence.is_empty() {
+        full.push_str("\n\nEvidence:\n");
+        for item in evidence {
+            full.push_str(&format!("- {}\n", item));
         }
+    }
 
-        // Calculate statistics
-        let stats = calculate_verification_stats(&budget_results, self.config.n_samples);
+    let p0 = logprob_yes(client, &claim_probe_prompt(&scrubbed, &claim.text)).await?;
+    let p1 = logprob_yes(client, &claim_probe_prompt(&full, &claim.text)).await?;
+    Ok((Probability::point(p0), Probability::point(p1)))
+}
 
-        let verdict = if stats.ungrounded_claims > 0 {
-            VerificationVerdict::Unverified
-        } else if stats.weakly_grounded_claims > 0 {
-            VerificationVerdict::PartiallyVerified
-        } else if stats.total_claims > 0 {
-            VerificationVerdict::Verified
-        } else {
-            VerificationVerdict::Error
-        };
+/// Ask a yes/no probe and return P(yes) from the first token's logprobs.
+async fn logprob_yes(client: &dyn LLMClient, prompt: &str) -> Result<f64> {
+    let request = CompletionRequest::new()
+        .with_message(ChatMessage::user(prompt))
+        .with_temperature(0.0)
+        .with_max_tokens(1)
+        .with_logprobs(LOGPROB_ALTERNATIVES);
+
+    let response = client.complete(request).await?;
+    let logprobs = response
+        .logprobs
+        .ok_or_else(|| Error::LLM("Provider returned no logprobs".to_string()))?;
+    yes_probability(&logprobs)
+        .ok_or_else(|| Error::LLM("No Yes/No token among top logprobs".to_string()))
+}
 
-        let latency_ms = start.elapsed().as_millis() as u64;
+/// Build a yes/no probe asking whether `claim` holds given `context`.
+fn claim_probe_prompt(context: &str, claim: &str) -> String {
+    format!(
+        r#"Given this context:
 
-        self.emit_event(TrajectoryEvent::new(
-            TrajectoryEventType::VerifyComplete,
-            0,
-            format!(
-                "Batch verification complete: {} claims, latency {}ms",
-                stats.total_claims, latency_ms
-            ),
-        ))
-        .await;
+{}
 
-        Ok(VerificationResult {
-            session_id,
-            claims,
-            budget_results,
-            verdict,
-            stats,
-            completed_at: Utc::now(),
-            latency_ms,
-        })
-    }
+Is the following claim true?
 
-    fn config(&self) -> &VerificationConfig {
-        &self.config
-    }
+Claim: "{}"
 
-    async fn get_events(&self) -> Vec<TrajectoryEvent> {
-        self.events.read().await.clone()
+Answer with a single word: Yes or No."#,
+        context, claim
+    )
+}
+
+/// Probability of "yes" from the first token's logprobs.
+///
+/// Sums the mass of yes-like and no-like candidates and renormalizes, so
+/// mass on unrelated tokens is ignored. Returns `None` if neither appears.
+fn yes_probability(logprobs: &[TokenLogprob]) -> Option<f64> {
+    let first = logprobs.first()?;
+    let candidates: Vec<(&str, f64)> = if first.top_logprobs.is_empty() {
+        vec![(first.token.as_str(), first.logprob)]
+    } else {
+        first
+            .top_logprobs
+            .iter()
+            .map(|t| (t.token.as_str(), t.logprob))
+            .collect()
+    };
+
+    let (mut yes, mut no) = (0.0, 0.0);
+    for (token, logprob) in candidates {
+        let normalized = token
+            .trim()
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        match normalized.as_str() {
+            "yes" | "true" => yes += logprob.exp(),
+            "no" | "false" => no += logprob.exp(),
+            _ => {}
+        }
     }
+
+    (yes + no > 0.0).then(|| yes / (yes + no))
 }
 
 /// Parse probability from text response.
@@ -667,7 +1014,314 @@ fn calculate_verification_stats(results: &[BudgetResult], n_samples: u32) -> Ver
 
 #[cfg(test)]
 mod tests {
+    use super::super::types::ClaimCategory;
     use super::*;
+    use crate::llm::{
+        CompletionResponse, EmbeddingRequest, EmbeddingResponse, ModelSpec, Provider, TokenUsage,
+        TopLogprob,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn yes_no(p_yes: f64) -> Vec<TokenLogprob> {
+        vec![TokenLogprob {
+            token: "Yes".to_string(),
+            logprob: p_yes.ln(),
+            top_logprobs: vec![
+                TopLogprob {
+                    token: "Yes".to_string(),
+                    logprob: p_yes.ln(),
+                },
+                TopLogprob {
+                    token: " no".to_string(),
+                    logprob: (1.0 - p_yes).ln(),
+                },
+            ],
+        }]
+    }
+
+    /// Counts calls. Answers logprob probes with P(yes) = 0.9 when evidence
+    /// is present and 0.4 otherwise; answers sampling prompts with "0.7".
+    struct MockClient {
+        supports_logprobs: bool,
+        calls: AtomicUsize,
+    }
+
+    impl MockClient {
+        fn new(supports_logprobs: bool) -> Self {
+            Self {
+                supports_logprobs,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LLMClient for MockClient {
+        async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let (content, logprobs) = match request.top_logprobs {
+                Some(top) => {
+                    assert_eq!(top, LOGPROB_ALTERNATIVES);
+                    let p_yes = if request.messages[0].content.contains("Evidence:") {
+                        0.9
+                    } else {
+                        0.4
+                    };
+                    ("Yes", Some(yes_no(p_yes)))
+                }
+                None => ("0.7", None),
+            };
+            Ok(CompletionResponse {
+                id: "mock".to_string(),
+                model: "gpt-4o".to_string(),
+                content: content.to_string(),
+                stop_reason: None,
+                usage: TokenUsage::default(),
+                timestamp: Utc::now(),
+                cost: None,
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs,
+            })
+        }
+
+        async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+            Err(Error::LLM("not implemented".to_string()))
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::OpenAI
+        }
+
+        fn available_models(&self) -> Vec<ModelSpec> {
+            vec![]
+        }
+
+        fn supports_logprobs(&self) -> bool {
+            self.supports_logprobs
+        }
+    }
+
+    #[test]
+    fn test_yes_probability() {
+        let p = yes_probability(&yes_no(0.75)).unwrap();
+        assert!((p - 0.75).abs() < 1e-9);
+
+        // Mass on unrelated tokens is renormalized away
+        let mut logprobs = yes_no(0.6);
+        logprobs[0].top_logprobs[1].logprob = 0.2f64.ln();
+        logprobs[0].top_logprobs.push(TopLogprob {
+            token: "Maybe".to_string(),
+            logprob: 0.2f64.ln(),
+        });
+        let p = yes_probability(&logprobs).unwrap();
+        assert!((p - 0.75).abs() < 1e-9);
+
+        logprobs[0].top_logprobs.retain(|t| t.token == "Maybe");
+        assert_eq!(yes_probability(&logprobs), None);
+        assert_eq!(yes_probability(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_logprob_verifier_estimates_p0_and_p1() {
+        let client = Arc::new(MockClient::new(true));
+        let verifier = LogprobVerifier::new(client, VerificationConfig::default()).unwrap();
+        let claim = Claim::new("The cache holds 100 entries", ClaimCategory::Factual);
+
+        let result = verifier
+            .verify_claim(&claim, "Cache settings", &["max_entries = 100".to_string()])
+            .await
+            .unwrap();
+
+        assert!((result.p0.estimate - 0.4).abs() < 1e-9);
+        assert!((result.p1.estimate - 0.9).abs() < 1e-9);
+        assert_eq!(
+            result,
+            BudgetResult::new(
+                claim.id.clone(),
+                Probability::point(0.4),
+                Probability::point(0.9),
+                required_bits_for_specificity(claim.specificity),
+            )
+        );
+        assert_eq!(verifier.config().strategy, VerificationStrategy::Logprob);
+    }
+
+    #[tokio::test]
+    async fn test_batch_verifier_honors_logprob_strategy() {
+        let client = Arc::new(MockClient::new(true));
+        let config = VerificationConfig::default().with_strategy(VerificationStrategy::Logprob);
+        let verifier = BatchVerifier::new(client.clone(), config);
+        assert!(verifier.uses_logprobs());
+        let claim = Claim::new("The cache holds 100 entries", ClaimCategory::Factual);
+
+        let result = verifier
+            .verify_claim(&claim, "Cache settings", &["max_entries = 100".to_string()])
+            .await
+            .unwrap();
+        assert!((result.p0.estimate - 0.4).abs() < 1e-9);
+        assert!((result.p1.estimate - 0.9).abs() < 1e-9);
+        assert_eq!(client.calls.load(Ordering::SeqCst), 2);
+
+        let batch = verifier.verify_claims(vec![claim], "Cache settings").await;
+        assert_eq!(batch.stats.total_samples, 2);
+
+        // Without logprob support the batch falls back to sampling
+        let verifier = BatchVerifier::new(
+            Arc::new(MockClient::new(false)),
+            VerificationConfig::default().with_strategy(VerificationStrategy::Logprob),
+        );
+        assert!(!verifier.uses_logprobs());
+    }
+
+    #[tokio::test]
+    async fn test_batch_verifier_caches_claims_per_context() {
+        let client = Arc::new(MockClient::new(false));
+        let config = VerificationConfig::default().with_cache_ttl(Duration::from_secs(300));
+        let verifier = BatchVerifier::new(client.clone(), config);
+        let response = "The function returns an integer. It is called from the main module.";
+
+        let first = verifier.verify_response(response, "ctx A").await.unwrap();
+        let calls = client.calls.load(Ordering::SeqCst);
+        assert_eq!(calls, 2);
+        assert_eq!(first.stats.cache_hits, 0);
+
+        let second = verifier.verify_response(response, "ctx A").await.unwrap();
+        assert_eq!(client.calls.load(Ordering::SeqCst), calls);
+        assert_eq!(second.stats.cache_hits, 2);
+        assert_eq!(second.stats.total_samples, 0);
+        for (claim, result) in second.claims.iter().zip(&second.budget_results) {
+            assert_eq!(result.claim_id, claim.id);
+        }
+        assert_eq!(
+            first.budget_results[0].budget_gap,
+            second.budget_results[0].budget_gap
+        );
+
+        // Same claims under a different context are verified afresh
+        let third = verifier.verify_response(response, "ctx B").await.unwrap();
+        assert_eq!(client.calls.load(Ordering::SeqCst), calls * 2);
+        assert_eq!(third.stats.cache_hits, 0);
+    }
+
+    #[tokio::test]
+    async fn test_batch_verifier_without_cache() {
+        let client = Arc::new(MockClient::new(false));
+        let verifier = BatchVerifier::new(client.clone(), VerificationConfig::default());
+        assert!(verifier.cache().is_none());
+
+        let claim = Claim::new("The function returns an integer", ClaimCategory::Factual);
+        verifier.verify_claim(&claim, "ctx", &[]).await.unwrap();
+        verifier.verify_claim(&claim, "ctx", &[]).await.unwrap();
+        assert_eq!(client.calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Answers later claims sooner, fails claims mentioning "broken", and
+    /// records peak concurrency.
+    struct SlowClient {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LLMClient for SlowClient {
+        async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            let prompt = &request.messages[0].content;
+            let index: u64 = regex::Regex::new(r"Step (\d+)")
+                .unwrap()
+                .captures(prompt)
+                .map(|c| c[1].parse().unwrap())
+                .unwrap_or(0);
+            tokio::time::sleep(Duration::from_millis(40 - 2 * index)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if prompt.contains("broken") {
+                return Err(Error::LLM("provider error".to_string()));
+            }
+            Ok(CompletionResponse {
+                id: "mock".to_string(),
+                model: "mock".to_string(),
+                content: format!("0.{:02}", index + 10),
+                stop_reason: None,
+                usage: TokenUsage::default(),
+                timestamp: Utc::now(),
+                cost: None,
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        }
+
+        async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+            Err(Error::LLM("not implemented".to_string()))
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::Anthropic
+        }
+
+        fn available_models(&self) -> Vec<ModelSpec> {
+            vec![]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_verifier_bounds_parallelism_and_preserves_order() {
+        let client = Arc::new(SlowClient {
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        });
+        let config = VerificationConfig::default().with_max_parallel(4);
+        let verifier = BatchVerifier::new(client.clone(), config)
+            .with_provider_rate_limit(Provider::Anthropic, 0);
+
+        let claims: Vec<Claim> = (0..20)
+            .map(|i| {
+                let text = if i == 7 {
+                    format!("Step {} used the broken parser", i)
+                } else {
+                    format!("Step {} returned a value", i)
+                };
+                Claim::new(text, ClaimCategory::Factual)
+            })
+            .collect();
+
+        let result = verifier.verify_claims(claims.clone(), "trace audit").await;
+
+        assert_eq!(client.peak.load(Ordering::SeqCst), 4);
+        assert_eq!(result.claims.len(), 20);
+        assert_eq!(result.stats.total_claims, 19);
+        assert_eq!(result.stats.failed_claims, 1);
+
+        let expected: Vec<_> = claims
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 7)
+            .map(|(i, claim)| (claim.id.clone(), (i + 10) as f64 / 100.0))
+            .collect();
+        let actual: Vec<_> = result
+            .budget_results
+            .iter()
+            .map(|r| (r.claim_id.clone(), r.p0.estimate))
+            .collect();
+        assert_eq!(actual.len(), expected.len());
+        for ((id, p0), (expected_id, expected_p0)) in actual.iter().zip(&expected) {
+            assert_eq!(id, expected_id);
+            assert!((p0 - expected_p0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_logprob_strategy_requires_support() {
+        let client = Arc::new(MockClient::new(false));
+        assert!(LogprobVerifier::new(client.clone(), VerificationConfig::default()).is_err());
+
+        // SelfVerifier falls back to sampling instead
+        let config = VerificationConfig::default().with_strategy(VerificationStrategy::Logprob);
+        assert!(!SelfVerifier::new(client, config).uses_logprobs());
+    }
 
     #[test]
     fn test_parse_probability() {
@@ -28,6 +28,14 @@ pub enum Error {
     #[error("LLM API error: {provider} - {message}")]
     LlmApi { provider: String, message: String },
 
+    /// LLM provider returned an HTTP error status
+    #[error("LLM API error: {provider} returned {status} - {message}")]
+    LlmStatus {
+        provider: String,
+        status: u16,
+        message: String,
+    },
+
     /// LLM error (simple variant)
     #[error("LLM error: {0}")]
     LLM(String),
@@ -52,6 +60,22 @@ pub enum Error {
     #[error("Budget exhausted: {resource}")]
     BudgetExhausted { resource: String },
 
+    /// A state machine was asked to make a move it doesn't allow
+    #[error("Invalid state transition: {from} -> {to}")]
+    InvalidTransition { from: String, to: String },
+
+    /// Every instance in a bounded pool stayed checked out past the wait limit
+    #[error("{pool} pool exhausted: all {max_size} instances in use after waiting {waited_ms}ms")]
+    PoolExhausted {
+        pool: String,
+        max_size: usize,
+        waited_ms: u64,
+    },
+
+    /// A PreToolUse hook blocked a tool call
+    #[error("Tool {tool} blocked by hook: {reason}")]
+    ToolBlocked { tool: String, reason: String },
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
@@ -85,6 +109,31 @@ impl Error {
         }
     }
 
+    /// Create an LLM API error for an HTTP error status.
+    pub fn llm_status(
+        provider: impl Into<String>,
+        status: u16,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::LlmStatus {
+            provider: provider.into(),
+            status,
+            message: message.into(),
+        }
+    }
+
+    /// Whether this is a 4xx provider error that will fail the same way on
+    /// any retry, such as an invalid API key or a malformed request.
+    ///
+    /// Request timeouts (408) and rate limits (429) are transient.
+    pub fn is_deterministic_client_error(&self) -> bool {
+        matches!(
+            self,
+            Self::LlmStatus { status, .. }
+                if (400..500).contains(status) && !matches!(status, 408 | 429)
+        )
+    }
+
     /// Create a timeout error.
     pub fn timeout(duration_ms: u64) -> Self {
         Self::Timeout { duration_ms }
@@ -30,6 +30,8 @@ mod memory;
 mod orchestrator;
 mod reasoning;
 mod repl;
+mod router;
+mod signature;
 mod trajectory;
 mod types;
 
@@ -41,6 +43,8 @@ pub use memory::*;
 pub use orchestrator::*;
 pub use reasoning::*;
 pub use repl::*;
+pub use router::*;
+pub use signature::*;
 pub use trajectory::*;
 pub use types::*;
 
@@ -365,6 +369,94 @@ mod tests {
         unsafe { rlm_pattern_classifier_free(classifier) };
     }
 
+    #[test]
+    fn test_smart_router() {
+        let router = rlm_smart_router_new();
+        assert!(!router.is_null());
+
+        let query = std::ffi::CString::new("Analyze the architecture of this system").unwrap();
+        let decision = unsafe { rlm_smart_router_route(router, query.as_ptr(), 0, -1.0) };
+        assert!(!decision.is_null());
+
+        let model_id = unsafe { rlm_routing_decision_model_id(decision) };
+        assert!(!model_id.is_null());
+        let id = unsafe { std::ffi::CStr::from_ptr(model_id) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(!id.is_empty());
+        unsafe { rlm_string_free(model_id) };
+
+        let reason = unsafe { rlm_routing_decision_reason(decision) };
+        assert!(!reason.is_null());
+        unsafe { rlm_string_free(reason) };
+
+        // Deeper calls never route to a more capable tier.
+        let deep = unsafe { rlm_smart_router_route(router, query.as_ptr(), 3, 0.5) };
+        assert!(!deep.is_null());
+        let tier = unsafe { rlm_routing_decision_tier(decision) } as i32;
+        let deep_tier = unsafe { rlm_routing_decision_tier(deep) } as i32;
+        assert!(deep_tier >= tier);
+
+        let missing = unsafe { rlm_smart_router_route(router, std::ptr::null(), 0, -1.0) };
+        assert!(missing.is_null());
+        assert_eq!(rlm_has_error(), 1);
+
+        unsafe { rlm_routing_decision_free(deep) };
+        unsafe { rlm_routing_decision_free(decision) };
+        unsafe { rlm_smart_router_free(router) };
+    }
+
+    #[test]
+    fn test_signature_registry() {
+        use std::ffi::{CStr, CString};
+
+        let instructions = CString::new("Summarize the text").unwrap();
+        let inputs = CString::new(
+            r#"[{"name": "text", "field_type": {"type": "string"}, "description": "Text to summarize", "required": true}]"#,
+        )
+        .unwrap();
+        let outputs = CString::new(
+            r#"[{"name": "summary", "field_type": {"type": "string"}, "description": "One-line summary", "required": true}]"#,
+        )
+        .unwrap();
+
+        let id = unsafe {
+            rlm_signature_register(instructions.as_ptr(), inputs.as_ptr(), outputs.as_ptr())
+        };
+        assert!(id > 0);
+
+        let values = CString::new(r#"{"text": "A long story"}"#).unwrap();
+        let prompt = unsafe { rlm_signature_to_prompt(id, values.as_ptr()) };
+        assert!(!prompt.is_null());
+        let prompt_str = unsafe { CStr::from_ptr(prompt) }.to_str().unwrap();
+        assert!(prompt_str.contains("Summarize the text"));
+        assert!(prompt_str.contains("A long story"));
+        unsafe { rlm_string_free(prompt) };
+
+        let schema = rlm_signature_output_schema(id);
+        assert!(!schema.is_null());
+        let schema_json: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(schema) }.to_str().unwrap()).unwrap();
+        assert_eq!(schema_json["required"], serde_json::json!(["summary"]));
+        unsafe { rlm_string_free(schema) };
+
+        // Invalid field specs are reported through the last error.
+        let bad = CString::new(r#"[{"name": "text"}]"#).unwrap();
+        let bad_id = unsafe {
+            rlm_signature_register(instructions.as_ptr(), bad.as_ptr(), outputs.as_ptr())
+        };
+        assert_eq!(bad_id, -1);
+        let error = unsafe { CStr::from_ptr(rlm_last_error()) }
+            .to_str()
+            .unwrap();
+        assert!(error.contains("invalid input field specs"));
+
+        assert_eq!(rlm_signature_free(id), 0);
+        assert_eq!(rlm_signature_free(id), -1);
+        assert!(rlm_signature_output_schema(id).is_null());
+    }
+
     #[test]
     fn test_cost_tracker_lifecycle() {
         let tracker = rlm_cost_tracker_new();
@@ -552,4 +644,181 @@ mod tests {
 
         unsafe { rlm_string_free(features) };
     }
+    /// Orchestrator that emits a fixed trajectory, or panics when asked.
+    #[cfg(feature = "tokio-runtime")]
+    struct ScriptedOrchestrator {
+        panic: bool,
+        config: crate::orchestrator::OrchestratorConfig,
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[async_trait::async_trait]
+    impl crate::orchestrator::Orchestrator for ScriptedOrchestrator {
+        fn should_activate(
+            &self,
+            _query: &str,
+            _context: &crate::context::SessionContext,
+        ) -> crate::complexity::ActivationDecision {
+            crate::complexity::ActivationDecision::activate("test", 1, Default::default())
+        }
+
+        async fn run(
+            &self,
+            query: &str,
+            _context: &crate::context::SessionContext,
+        ) -> crate::error::Result<crate::orchestrator::TrajectoryStream> {
+            use crate::trajectory::TrajectoryEvent;
+            if self.panic {
+                panic!("scripted failure");
+            }
+            let events = vec![
+                TrajectoryEvent::rlm_start(query),
+                TrajectoryEvent::analyze(0, "looking"),
+                TrajectoryEvent::final_answer(0, "42"),
+            ];
+            Ok(Box::pin(futures::stream::iter(events)))
+        }
+
+        async fn recursive_call(
+            &self,
+            _query: &str,
+            _context: &str,
+            _depth: u32,
+            _spawn_repl: bool,
+        ) -> crate::error::Result<crate::orchestrator::RecursiveResult> {
+            Err(crate::error::Error::Internal("unused".to_string()))
+        }
+
+        fn execution_mode(&self) -> crate::orchestrator::ExecutionMode {
+            crate::orchestrator::ExecutionMode::Fast
+        }
+
+        fn set_execution_mode(&mut self, _mode: crate::orchestrator::ExecutionMode) {}
+
+        fn config(&self) -> &crate::orchestrator::OrchestratorConfig {
+            &self.config
+        }
+    }
+
+    /// C-side callback: counts events and remembers the last one's type.
+    #[cfg(feature = "tokio-runtime")]
+    extern "C" fn count_events(event: *const RlmTrajectoryEvent, user_data: *mut std::ffi::c_void) {
+        let seen = unsafe { &mut *(user_data as *mut Vec<RlmTrajectoryEventType>) };
+        seen.push(unsafe { rlm_trajectory_event_type(event) });
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[test]
+    fn test_orchestrator_run_streaming() {
+        let handle = RlmOrchestrator::into_raw(std::sync::Arc::new(ScriptedOrchestrator {
+            panic: false,
+            config: Default::default(),
+        }));
+        let request = std::ffi::CString::new(r#"{"query": "What is 6 * 7?"}"#).unwrap();
+        let mut seen: Vec<RlmTrajectoryEventType> = Vec::new();
+
+        let delivered = unsafe {
+            rlm_orchestrator_run_streaming(
+                handle,
+                request.as_ptr(),
+                Some(count_events),
+                &mut seen as *mut _ as *mut std::ffi::c_void,
+            )
+        };
+
+        assert_eq!(delivered, 3);
+        assert_eq!(
+            seen,
+            vec![
+                RlmTrajectoryEventType::RlmStart,
+                RlmTrajectoryEventType::Analyze,
+                RlmTrajectoryEventType::Final,
+            ]
+        );
+        assert_eq!(rlm_has_error(), 0);
+
+        // A NULL callback still runs and counts the events.
+        let counted = unsafe {
+            rlm_orchestrator_run_streaming(handle, request.as_ptr(), None, std::ptr::null_mut())
+        };
+        assert_eq!(counted, 3);
+        assert_eq!(rlm_has_error(), 0);
+
+        // Malformed requests are rejected before running.
+        let bad = std::ffi::CString::new("not json").unwrap();
+        let result = unsafe {
+            rlm_orchestrator_run_streaming(
+                handle,
+                bad.as_ptr(),
+                Some(count_events),
+                &mut seen as *mut _ as *mut std::ffi::c_void,
+            )
+        };
+        assert_eq!(result, -1);
+        assert_eq!(rlm_has_error(), 1);
+
+        unsafe { rlm_orchestrator_free(handle) };
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[test]
+    fn test_orchestrator_new_runs_from_config() {
+        assert!(unsafe { rlm_orchestrator_new(std::ptr::null()) }.is_null());
+        assert_eq!(rlm_has_error(), 1);
+
+        let config = rlm_orchestrator_config_default();
+        let handle = unsafe { rlm_orchestrator_new(config) };
+        unsafe { rlm_orchestrator_config_free(config) };
+        assert!(!handle.is_null());
+
+        // A simple query is declined before any REPL work.
+        let request = std::ffi::CString::new(r#"{"query": "What is 2 + 2?"}"#).unwrap();
+        let mut seen: Vec<RlmTrajectoryEventType> = Vec::new();
+        let delivered = unsafe {
+            rlm_orchestrator_run_streaming(
+                handle,
+                request.as_ptr(),
+                Some(count_events),
+                &mut seen as *mut _ as *mut std::ffi::c_void,
+            )
+        };
+        assert_eq!(delivered, 2);
+        assert_eq!(
+            seen,
+            vec![
+                RlmTrajectoryEventType::RlmStart,
+                RlmTrajectoryEventType::Analyze,
+            ]
+        );
+        assert_eq!(rlm_has_error(), 0);
+
+        unsafe { rlm_orchestrator_free(handle) };
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[test]
+    fn test_orchestrator_run_streaming_catches_panics() {
+        let handle = RlmOrchestrator::into_raw(std::sync::Arc::new(ScriptedOrchestrator {
+            panic: true,
+            config: Default::default(),
+        }));
+        let request = std::ffi::CString::new(r#"{"query": "boom"}"#).unwrap();
+        let mut seen: Vec<RlmTrajectoryEventType> = Vec::new();
+
+        let result = unsafe {
+            rlm_orchestrator_run_streaming(
+                handle,
+                request.as_ptr(),
+                Some(count_events),
+                &mut seen as *mut _ as *mut std::ffi::c_void,
+            )
+        };
+
+        assert_eq!(result, -1);
+        assert!(seen.is_empty());
+        let err = unsafe { CStr::from_ptr(rlm_last_error()).to_str().unwrap() };
+        assert!(err.contains("scripted failure"));
+
+        unsafe { rlm_orchestrator_free(handle) };
+    }
 }
@@ -4,16 +4,21 @@
 //! - ExecutionMode: Mode selection for orchestration (Micro, Fast, Balanced, Thorough)
 //! - OrchestratorConfig: Configuration for orchestration behavior
 //! - OrchestratorBuilder: Builder pattern for creating configs
+//! - Orchestrator: Creating an orchestrator and streaming its runs
 //!
-//! Note: The actual Orchestrator trait involves async LLM calls and streams,
-//! which remain in language-specific implementations. This module exposes
-//! the pure configuration and mode selection logic.
+//! `rlm_orchestrator_new()` creates a [`ClaudeCodeOrchestrator`] from a
+//! config; Rust hosts can hand over any other implementation with
+//! [`RlmOrchestrator::into_raw`]. Either can then be run with
+//! `rlm_orchestrator_run_streaming()`, which is only built with the
+//! `tokio-runtime` feature (enabled by default).
 
+use crate::adapters::ClaudeCodeOrchestrator;
 use crate::complexity::TaskComplexitySignals;
-use crate::orchestrator::{ExecutionMode, OrchestratorBuilder, OrchestratorConfig};
+use crate::orchestrator::{ExecutionMode, Orchestrator, OrchestratorBuilder, OrchestratorConfig};
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::os::raw::c_char;
+use std::sync::Arc;
 
 // ============================================================================
 // ExecutionMode FFI
@@ -215,6 +220,38 @@ pub unsafe extern "C" fn rlm_orchestrator_config_cost_budget_usd(
     (*config).0.cost_budget_usd
 }
 
+/// Get the total recursive call limit from config (0 = unlimited).
+///
+/// # Safety
+/// - `config` must be a valid pointer or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_orchestrator_config_max_total_calls(
+    config: *const RlmOrchestratorConfig,
+) -> u32 {
+    if config.is_null() {
+        return 0; // default unlimited
+    }
+    (*config).0.max_total_calls.unwrap_or(0)
+}
+
+/// Get whether recursive-call cycle detection is enabled.
+///
+/// # Safety
+/// - `config` must be a valid pointer or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_orchestrator_config_cycle_detection(
+    config: *const RlmOrchestratorConfig,
+) -> i32 {
+    if config.is_null() {
+        return 0; // default false
+    }
+    if (*config).0.cycle_detection {
+        1
+    } else {
+        0
+    }
+}
+
 /// Serialize config to JSON.
 ///
 /// # Safety
@@ -382,6 +419,44 @@ pub unsafe extern "C" fn rlm_orchestrator_builder_cost_budget_usd(
     )))
 }
 
+/// Set the total recursive call limit.
+///
+/// # Safety
+/// - `builder` must be a valid pointer.
+/// - Returns a new builder pointer; the old one is consumed.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_orchestrator_builder_max_total_calls(
+    builder: *mut RlmOrchestratorBuilder,
+    max_calls: u32,
+) -> *mut RlmOrchestratorBuilder {
+    if builder.is_null() {
+        return std::ptr::null_mut();
+    }
+    let b = Box::from_raw(builder);
+    Box::into_raw(Box::new(RlmOrchestratorBuilder(
+        b.0.max_total_calls(max_calls),
+    )))
+}
+
+/// Set whether recursive-call cycle detection is enabled.
+///
+/// # Safety
+/// - `builder` must be a valid pointer.
+/// - Returns a new builder pointer; the old one is consumed.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_orchestrator_builder_cycle_detection(
+    builder: *mut RlmOrchestratorBuilder,
+    enabled: i32,
+) -> *mut RlmOrchestratorBuilder {
+    if builder.is_null() {
+        return std::ptr::null_mut();
+    }
+    let b = Box::from_raw(builder);
+    Box::into_raw(Box::new(RlmOrchestratorBuilder(
+        b.0.cycle_detection(enabled != 0),
+    )))
+}
+
 /// Set the execution mode.
 ///
 /// # Safety
@@ -522,3 +597,184 @@ pub unsafe extern "C" fn rlm_complexity_signals_has_strong_signal(json: *const c
         0
     }
 }
+
+// ============================================================================
+// Orchestrator FFI
+// ============================================================================
+
+/// Opaque handle for a running orchestrator implementation.
+// Only read by the streaming entry point, which needs the tokio runtime.
+#[cfg_attr(not(feature = "tokio-runtime"), allow(dead_code))]
+pub struct RlmOrchestrator(pub(crate) Arc<dyn Orchestrator>);
+
+impl RlmOrchestrator {
+    /// Wrap an orchestrator in a handle for C callers.
+    ///
+    /// The returned handle must be freed with `rlm_orchestrator_free()`.
+    pub fn into_raw(orchestrator: Arc<dyn Orchestrator>) -> *mut RlmOrchestrator {
+        Box::into_raw(Box::new(RlmOrchestrator(orchestrator)))
+    }
+}
+
+/// Create an orchestrator that runs queries through the Claude Code adapter.
+///
+/// The config's cost, token and depth limits become the orchestrator's
+/// budget. Returns NULL on error (see `rlm_last_error()`).
+///
+/// # Safety
+/// - `config` must be a valid pointer from an rlm_orchestrator_config_*
+///   function; it is not consumed.
+/// - The returned handle must be freed with `rlm_orchestrator_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_orchestrator_new(
+    config: *const RlmOrchestratorConfig,
+) -> *mut RlmOrchestrator {
+    if config.is_null() {
+        super::error::set_last_error("null config pointer");
+        return std::ptr::null_mut();
+    }
+    match ClaudeCodeOrchestrator::new((*config).0.clone()) {
+        Ok(orchestrator) => RlmOrchestrator::into_raw(Arc::new(orchestrator)),
+        Err(e) => {
+            super::error::set_last_error(&e.to_string());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Free an orchestrator handle.
+///
+/// # Safety
+/// - `handle` must be a pointer returned by `rlm_orchestrator_new()` or
+///   `RlmOrchestrator::into_raw()`, or NULL.
+/// - After calling this function, `handle` must not be used.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_orchestrator_free(handle: *mut RlmOrchestrator) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Request accepted by `rlm_orchestrator_run_streaming()`.
+#[cfg(feature = "tokio-runtime")]
+#[derive(serde::Deserialize)]
+struct RunRequest {
+    query: String,
+    #[serde(default)]
+    context: crate::context::SessionContext,
+}
+
+/// Runtime shared by streaming runs, created on first use.
+#[cfg(feature = "tokio-runtime")]
+static RUNTIME: std::sync::LazyLock<Result<tokio::runtime::Runtime, String>> =
+    std::sync::LazyLock::new(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .thread_name("rlm-ffi")
+            .enable_all()
+            .build()
+            .map_err(|e| format!("failed to start runtime: {}", e))
+    });
+
+/// Run an orchestrator, invoking `callback` for each trajectory event.
+///
+/// `request` is a JSON object: `{"query": "...", "context": {...}}`, where
+/// `context` is an optional serialized `SessionContext`.
+///
+/// Returns the number of events delivered, or -1 on error (see
+/// `rlm_last_error()`). Events delivered before an error are not retracted.
+/// A NULL `callback` runs the orchestrator and only counts the events.
+///
+/// # Threading
+/// The call blocks until the run finishes. `callback` may be invoked from a
+/// worker thread rather than the calling thread, but invocations never
+/// overlap and all of them happen before this function returns. Do not call
+/// this function from inside `callback`.
+///
+/// # Safety
+/// - `handle` must be a valid pointer from `rlm_orchestrator_new()` or
+///   `RlmOrchestrator::into_raw()`.
+/// - `request` must be a valid null-terminated string.
+/// - The event pointer passed to `callback` is only valid for the duration
+///   of the callback; it is freed afterwards and must not be freed by the
+///   callee. Copy out anything needed (e.g. `rlm_trajectory_event_to_json()`).
+/// - `user_data` is passed through unchanged and must be safe to use from
+///   the thread `callback` runs on.
+#[cfg(feature = "tokio-runtime")]
+#[no_mangle]
+pub unsafe extern "C" fn rlm_orchestrator_run_streaming(
+    handle: *const RlmOrchestrator,
+    request: *const c_char,
+    callback: Option<super::types::RlmTrajectoryEventCallback>,
+    user_data: *mut std::ffi::c_void,
+) -> i64 {
+    use super::error::{clear_last_error, cstr_to_str, set_last_error};
+    use futures::StreamExt;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    if handle.is_null() {
+        set_last_error("null orchestrator handle");
+        return -1;
+    }
+    let request = match cstr_to_str(request) {
+        Ok(request) => request,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
+    };
+    let request: RunRequest = match serde_json::from_str(request) {
+        Ok(request) => request,
+        Err(e) => {
+            set_last_error(&format!("invalid request: {}", e));
+            return -1;
+        }
+    };
+    let runtime = match RUNTIME.as_ref() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
+    };
+
+    let orchestrator = Arc::clone(&(*handle).0);
+    // Raw pointers are not `Send`; the caller guarantees `user_data` may be
+    // used from the callback's thread.
+    let user_data = user_data as usize;
+
+    let outcome = catch_unwind(AssertUnwindSafe(|| {
+        runtime.block_on(async move {
+            let mut stream = orchestrator.run(&request.query, &request.context).await?;
+            let mut delivered = 0i64;
+            while let Some(event) = stream.next().await {
+                // Transient wrapper, dropped as soon as the callback returns.
+                let event = super::types::RlmTrajectoryEvent(event);
+                if let Some(callback) = callback {
+                    callback(&event, user_data as *mut std::ffi::c_void);
+                }
+                delivered += 1;
+            }
+            Ok::<_, crate::error::Error>(delivered)
+        })
+    }));
+
+    match outcome {
+        Ok(Ok(delivered)) => {
+            clear_last_error();
+            delivered
+        }
+        Ok(Err(e)) => {
+            set_last_error(&e.to_string());
+            -1
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(&format!("orchestrator panicked: {}", message));
+            -1
+        }
+    }
+}
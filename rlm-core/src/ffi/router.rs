@@ -0,0 +1,151 @@
+//! FFI bindings for model routing.
+//!
+//! Provides C-compatible bindings for:
+//! - SmartRouter: Selects a model for a query
+//! - RoutingDecision: The selected model, tier, and rationale
+
+use std::os::raw::c_char;
+
+use super::error::{cstr_to_str, ffi_try, set_last_error, str_to_cstring};
+use crate::llm::{ModelTier, RoutingContext, RoutingDecision, SmartRouter};
+
+/// Model tier enum.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RlmModelTier {
+    Flagship = 0,
+    Balanced = 1,
+    Fast = 2,
+}
+
+impl From<ModelTier> for RlmModelTier {
+    fn from(t: ModelTier) -> Self {
+        match t {
+            ModelTier::Flagship => RlmModelTier::Flagship,
+            ModelTier::Balanced => RlmModelTier::Balanced,
+            ModelTier::Fast => RlmModelTier::Fast,
+        }
+    }
+}
+
+// ============================================================================
+// SmartRouter FFI
+// ============================================================================
+
+/// Opaque handle for SmartRouter.
+pub struct RlmSmartRouter(SmartRouter);
+
+/// Opaque handle for RoutingDecision.
+pub struct RlmRoutingDecision(RoutingDecision);
+
+/// Create a smart router with the default model catalog.
+///
+/// # Safety
+/// The returned router must be freed with `rlm_smart_router_free()`.
+#[no_mangle]
+pub extern "C" fn rlm_smart_router_new() -> *mut RlmSmartRouter {
+    Box::into_raw(Box::new(RlmSmartRouter(SmartRouter::new())))
+}
+
+/// Free a smart router.
+///
+/// # Safety
+/// - `router` must be a pointer returned by `rlm_smart_router_new()`, or NULL.
+/// - After calling this function, `router` must not be used.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_smart_router_free(router: *mut RlmSmartRouter) {
+    if !router.is_null() {
+        drop(Box::from_raw(router));
+    }
+}
+
+/// Route a query to a model.
+///
+/// `depth` is the current recursion depth. `budget` is the remaining budget
+/// in USD; pass a negative value for no budget constraint.
+///
+/// # Safety
+/// - `router` must be a valid pointer.
+/// - `query` must be a valid null-terminated string.
+/// - The returned pointer must be freed with `rlm_routing_decision_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_smart_router_route(
+    router: *const RlmSmartRouter,
+    query: *const c_char,
+    depth: u32,
+    budget: f64,
+) -> *mut RlmRoutingDecision {
+    if router.is_null() {
+        set_last_error("null pointer");
+        return std::ptr::null_mut();
+    }
+    let query = ffi_try!(cstr_to_str(query));
+
+    let mut context = RoutingContext::new().with_depth(depth);
+    if budget >= 0.0 {
+        context = context.with_budget(budget);
+    }
+
+    let decision = (*router).0.route(query, &context);
+    Box::into_raw(Box::new(RlmRoutingDecision(decision)))
+}
+
+// ============================================================================
+// RoutingDecision FFI
+// ============================================================================
+
+/// Free a routing decision.
+///
+/// # Safety
+/// - `decision` must be a pointer returned by `rlm_smart_router_route()`, or NULL.
+/// - After calling this function, `decision` must not be used.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_routing_decision_free(decision: *mut RlmRoutingDecision) {
+    if !decision.is_null() {
+        drop(Box::from_raw(decision));
+    }
+}
+
+/// Get the selected model ID.
+///
+/// # Safety
+/// The returned string must be freed with `rlm_string_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_routing_decision_model_id(
+    decision: *const RlmRoutingDecision,
+) -> *mut c_char {
+    if decision.is_null() {
+        set_last_error("null pointer");
+        return std::ptr::null_mut();
+    }
+    str_to_cstring(&(*decision).0.model.id)
+}
+
+/// Get the recommended model tier.
+///
+/// # Safety
+/// `decision` must be a valid pointer, or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_routing_decision_tier(
+    decision: *const RlmRoutingDecision,
+) -> RlmModelTier {
+    if decision.is_null() {
+        return RlmModelTier::Balanced;
+    }
+    (*decision).0.tier.into()
+}
+
+/// Get the reasoning behind the selection.
+///
+/// # Safety
+/// The returned string must be freed with `rlm_string_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_routing_decision_reason(
+    decision: *const RlmRoutingDecision,
+) -> *mut c_char {
+    if decision.is_null() {
+        set_last_error("null pointer");
+        return std::ptr::null_mut();
+    }
+    str_to_cstring(&(*decision).0.reason)
+}
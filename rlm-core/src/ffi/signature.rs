@@ -0,0 +1,120 @@
+//! FFI bindings for typed signatures.
+//!
+//! `Signature` is generic over its input and output types, so it cannot
+//! cross the C boundary directly. Instead, callers register a
+//! [`DynamicSignature`] built from JSON field specs and refer to it by ID.
+//!
+//! Field specs use the serde form of `FieldSpec`, e.g.
+//! `{"name": "code", "field_type": {"type": "string"}, "description": "...", "required": true}`.
+
+use std::collections::HashMap;
+use std::os::raw::c_char;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use super::error::{cstr_to_str, ffi_try, set_last_error, str_to_cstring};
+use crate::signature::{DynamicSignature, FieldSpec};
+
+/// Registered signatures keyed by ID.
+static SIGNATURES: LazyLock<Mutex<HashMap<i64, DynamicSignature>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_SIGNATURE_ID: AtomicI64 = AtomicI64::new(1);
+
+/// Run `f` against a registered signature, setting the last error if the ID is unknown.
+fn with_signature<T>(id: i64, f: impl FnOnce(&DynamicSignature) -> T) -> Option<T> {
+    let signatures = SIGNATURES.lock().unwrap_or_else(|e| e.into_inner());
+    match signatures.get(&id) {
+        Some(signature) => Some(f(signature)),
+        None => {
+            set_last_error(&format!("unknown signature id: {}", id));
+            None
+        }
+    }
+}
+
+/// Parse a JSON array of field specs.
+unsafe fn parse_fields(json: *const c_char, what: &str) -> Result<Vec<FieldSpec>, String> {
+    let json = cstr_to_str(json).map_err(|e| format!("{}: {}", what, e))?;
+    serde_json::from_str(json).map_err(|e| format!("invalid {} field specs: {}", what, e))
+}
+
+/// Register a signature built from instructions and field specs.
+///
+/// `input_fields_json` and `output_fields_json` are JSON arrays of field
+/// specs. Returns a positive signature ID, or -1 on error.
+///
+/// # Safety
+/// - All arguments must be valid null-terminated strings.
+/// - The registration must be released with `rlm_signature_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_signature_register(
+    instructions: *const c_char,
+    input_fields_json: *const c_char,
+    output_fields_json: *const c_char,
+) -> i64 {
+    let instructions = ffi_try!(cstr_to_str(instructions), -1);
+    let input_fields = ffi_try!(parse_fields(input_fields_json, "input"), -1);
+    let output_fields = ffi_try!(parse_fields(output_fields_json, "output"), -1);
+
+    let id = NEXT_SIGNATURE_ID.fetch_add(1, Ordering::Relaxed);
+    SIGNATURES.lock().unwrap_or_else(|e| e.into_inner()).insert(
+        id,
+        DynamicSignature::new(instructions, input_fields, output_fields),
+    );
+    id
+}
+
+/// Release a registered signature.
+///
+/// Returns 0 on success, -1 if the ID is not registered.
+#[no_mangle]
+pub extern "C" fn rlm_signature_free(id: i64) -> i32 {
+    let removed = SIGNATURES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&id);
+    if removed.is_some() {
+        0
+    } else {
+        set_last_error(&format!("unknown signature id: {}", id));
+        -1
+    }
+}
+
+/// Generate a prompt for a registered signature.
+///
+/// `inputs_json` is a JSON object of input values keyed by field name.
+///
+/// # Safety
+/// - `inputs_json` must be a valid null-terminated string.
+/// - The returned string must be freed with `rlm_string_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn rlm_signature_to_prompt(
+    id: i64,
+    inputs_json: *const c_char,
+) -> *mut c_char {
+    let inputs_json = ffi_try!(cstr_to_str(inputs_json));
+    let inputs: serde_json::Value = ffi_try!(serde_json::from_str(inputs_json));
+    if !inputs.is_object() {
+        set_last_error("inputs must be a JSON object");
+        return std::ptr::null_mut();
+    }
+
+    match with_signature(id, |signature| signature.to_prompt(&inputs)) {
+        Some(prompt) => str_to_cstring(&prompt),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Get the JSON schema for a registered signature's outputs.
+///
+/// # Safety
+/// The returned string must be freed with `rlm_string_free()`.
+#[no_mangle]
+pub extern "C" fn rlm_signature_output_schema(id: i64) -> *mut c_char {
+    match with_signature(id, |signature| signature.output_schema().to_string()) {
+        Some(schema) => str_to_cstring(&schema),
+        None => std::ptr::null_mut(),
+    }
+}
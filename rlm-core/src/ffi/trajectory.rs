@@ -303,6 +303,8 @@ pub extern "C" fn rlm_trajectory_event_type_name(
         RlmTrajectoryEventType::CriticInvoked => "CRITIC_INVOKED",
         RlmTrajectoryEventType::IssueFound => "ISSUE_FOUND",
         RlmTrajectoryEventType::AdversarialComplete => "ADVERSARIAL_COMPLETE",
+        RlmTrajectoryEventType::BudgetExhausted => "BUDGET_EXHAUSTED",
+        RlmTrajectoryEventType::CycleDetected => "CYCLE_DETECTED",
     };
     str_to_cstring(name)
 }
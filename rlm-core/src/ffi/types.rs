@@ -169,6 +169,8 @@ pub enum RlmTrajectoryEventType {
     CriticInvoked = 22,
     IssueFound = 23,
     AdversarialComplete = 24,
+    BudgetExhausted = 25,
+    CycleDetected = 26,
 }
 
 impl From<crate::trajectory::TrajectoryEventType> for RlmTrajectoryEventType {
@@ -231,6 +233,12 @@ impl From<crate::trajectory::TrajectoryEventType> for RlmTrajectoryEventType {
             crate::trajectory::TrajectoryEventType::AdversarialComplete => {
                 RlmTrajectoryEventType::AdversarialComplete
             }
+            crate::trajectory::TrajectoryEventType::BudgetExhausted => {
+                RlmTrajectoryEventType::BudgetExhausted
+            }
+            crate::trajectory::TrajectoryEventType::CycleDetected => {
+                RlmTrajectoryEventType::CycleDetected
+            }
         }
     }
 }
@@ -295,6 +303,12 @@ impl From<RlmTrajectoryEventType> for crate::trajectory::TrajectoryEventType {
             RlmTrajectoryEventType::AdversarialComplete => {
                 crate::trajectory::TrajectoryEventType::AdversarialComplete
             }
+            RlmTrajectoryEventType::BudgetExhausted => {
+                crate::trajectory::TrajectoryEventType::BudgetExhausted
+            }
+            RlmTrajectoryEventType::CycleDetected => {
+                crate::trajectory::TrajectoryEventType::CycleDetected
+            }
         }
     }
 }
@@ -312,6 +326,15 @@ impl From<RlmTrajectoryEventType> for crate::trajectory::TrajectoryEventType {
 pub type RlmTrajectoryCallback =
     extern "C" fn(event: *mut RlmTrajectoryEvent, user_data: *mut std::ffi::c_void);
 
+/// Callback for observing trajectory events during a streaming run.
+///
+/// The event is borrowed: it is valid only for the duration of the callback
+/// and is freed by the library afterwards.
+///
+/// The `user_data` pointer is passed through unchanged.
+pub type RlmTrajectoryEventCallback =
+    extern "C" fn(event: *const RlmTrajectoryEvent, user_data: *mut std::ffi::c_void);
+
 /// Callback for receiving error messages.
 ///
 /// The error string is valid only for the duration of the callback.
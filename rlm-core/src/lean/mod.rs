@@ -11,8 +11,11 @@
 pub mod repl;
 pub mod types;
 
-pub use repl::{LeanRepl, LeanReplConfig, LeanReplPool};
+#[cfg(all(test, unix))]
+pub(crate) mod testing;
+
+pub use repl::{LeanRepl, LeanReplConfig, LeanReplPool, PooledLeanRepl};
 pub use types::{
-    Goal, LeanCommand, LeanEventMetadata, LeanMessage, LeanResponse, MessageSeverity, ProofState,
-    ProofStep, Sorry, TacticSuggestion,
+    DiagnosticCode, Goal, LeanCommand, LeanDiagnostic, LeanEventMetadata, LeanMessage,
+    LeanResponse, MessageSeverity, ProofState, ProofStep, Sorry, TacticSuggestion,
 };
@@ -11,13 +11,17 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Condvar, Mutex};
 use std::time::{Duration, Instant};
 
 use super::types::{Goal, LeanCommand, LeanEventMetadata, LeanResponse, ProofState, ProofStep};
 
 const SHUTDOWN_GRACE_MS: u64 = 2_000;
 const SHUTDOWN_POLL_MS: u64 = 10;
+/// Upper bound on how long a checkout health check waits for a response.
+const HEALTH_CHECK_TIMEOUT_MS: u64 = 5_000;
 
 fn wait_for_exit_with_timeout(child: &mut Child, timeout: Duration, context: &str) -> Result<()> {
     let deadline = Instant::now() + timeout;
@@ -74,6 +78,18 @@ pub struct LeanReplConfig {
 
     /// Whether to enable verbose logging.
     pub verbose: bool,
+
+    /// Idle time after which a pooled REPL is discarded instead of lent out.
+    /// If None, idle REPLs are kept indefinitely.
+    pub max_idle: Option<Duration>,
+
+    /// Whether [`LeanReplPool::acquire`] runs [`LeanRepl::health_check`]
+    /// on a pooled REPL before lending it.
+    pub health_check_on_checkout: bool,
+
+    /// How long [`LeanReplPool::acquire`] waits for a free slot when every
+    /// REPL in the pool is checked out.
+    pub checkout_timeout_ms: u64,
 }
 
 impl Default for LeanReplConfig {
@@ -84,6 +100,9 @@ impl Default for LeanReplConfig {
             timeout_ms: 60_000, // Lean type checking can be slow
             max_retries: 2,
             verbose: false,
+            max_idle: Some(Duration::from_secs(600)),
+            health_check_on_checkout: true,
+            checkout_timeout_ms: 30_000,
         }
     }
 }
@@ -108,16 +127,66 @@ impl LeanReplConfig {
         self.verbose = verbose;
         self
     }
+
+    /// Set the maximum idle time for pooled REPLs (None keeps them forever).
+    pub fn with_max_idle(mut self, max_idle: Option<Duration>) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    /// Set whether pooled REPLs are health-checked on checkout.
+    pub fn with_health_check_on_checkout(mut self, enabled: bool) -> Self {
+        self.health_check_on_checkout = enabled;
+        self
+    }
+
+    /// Set how long pool checkout waits for a free slot.
+    pub fn with_checkout_timeout(mut self, timeout_ms: u64) -> Self {
+        self.checkout_timeout_ms = timeout_ms;
+        self
+    }
+}
+
+/// Lines read from the subprocess's stdout.
+type StdoutLines = Receiver<std::io::Result<String>>;
+
+/// How an environment was created, so it can be rebuilt in a new process.
+#[derive(Debug, Clone)]
+enum EnvOrigin {
+    /// Elaborating `cmd` on top of `parent` (or a fresh environment).
+    Command { parent: Option<u64>, cmd: String },
+    /// Unpickling a saved environment.
+    Unpickle(PathBuf),
+}
+
+/// How a proof state was reached, so it can be rebuilt in a new process.
+#[derive(Debug, Clone)]
+enum ProofStateOrigin {
+    /// The `index`-th sorry of the command that produced `env`.
+    Sorry { env: u64, index: usize },
+    /// Applying `tactic` to `parent`.
+    Tactic { parent: u64, tactic: String },
 }
 
 /// Handle to a running Lean REPL subprocess.
+///
+/// Environment and proof-state IDs are only meaningful to the process that
+/// issued them. When a command times out, the process is killed rather than
+/// left working on it, and the next use respawns it and replays the commands
+/// and tactics behind the current environment (and, on
+/// [`restore_proof_state`](Self::restore_proof_state), the proof state being
+/// restored). Replayed states get new IDs.
 pub struct LeanRepl {
     /// Child process handle.
     child: Child,
     /// Stdin writer.
     stdin: Option<ChildStdin>,
-    /// Stdout reader.
-    stdout: BufReader<ChildStdout>,
+    /// Lines read from stdout by a background thread, so reads can time out.
+    /// (Wrapped in a mutex only so `LeanRepl` stays `Sync`.)
+    stdout_lines: Mutex<StdoutLines>,
+    /// A command timed out and the subprocess was killed; it is respawned
+    /// before the next command.
+    needs_respawn: bool,
     /// Current environment ID.
     current_env: Option<u64>,
     /// Configuration.
@@ -126,12 +195,36 @@ pub struct LeanRepl {
     pending_sorries: Vec<String>,
     /// Active proof states.
     proof_states: HashMap<u64, ProofState>,
+    /// How each environment issued by this process was created.
+    env_origins: HashMap<u64, EnvOrigin>,
+    /// How each proof state issued by this process was reached.
+    proof_state_origins: HashMap<u64, ProofStateOrigin>,
 }
 
 impl LeanRepl {
     /// Spawn a new Lean REPL subprocess.
     pub fn spawn(config: LeanReplConfig) -> Result<Self> {
-        let mut cmd = Self::build_command(&config)?;
+        let (child, stdin, stdout_lines) = Self::spawn_process(&config)?;
+
+        let repl = Self {
+            child,
+            stdin: Some(stdin),
+            stdout_lines: Mutex::new(stdout_lines),
+            needs_respawn: false,
+            current_env: None,
+            config,
+            pending_sorries: Vec::new(),
+            proof_states: HashMap::new(),
+            env_origins: HashMap::new(),
+            proof_state_origins: HashMap::new(),
+        };
+
+        Ok(repl)
+    }
+
+    /// Start the subprocess and the thread reading its stdout.
+    fn spawn_process(config: &LeanReplConfig) -> Result<(Child, ChildStdin, StdoutLines)> {
+        let mut cmd = Self::build_command(config)?;
 
         // Configure I/O
         cmd.stdin(Stdio::piped())
@@ -155,19 +248,29 @@ impl LeanRepl {
             Error::SubprocessComm("Failed to get stdout handle for Lean REPL".to_string())
         })?;
 
-        let stdout = BufReader::new(stdout);
-
-        let repl = Self {
-            child,
-            stdin: Some(stdin),
-            stdout,
-            current_env: None,
-            config,
-            pending_sorries: Vec::new(),
-            proof_states: HashMap::new(),
-        };
+        // A blocking read_line can't be interrupted, so read on a separate
+        // thread and wait on the channel with a deadline instead.
+        let (tx, stdout_lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                let mut line = String::new();
+                match reader.read_line(&mut line) {
+                    Ok(0) => break,
+                    Ok(_) => {
+                        if tx.send(Ok(line)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
 
-        Ok(repl)
+        Ok((child, stdin, stdout_lines))
     }
 
     /// Build the command to spawn the REPL.
@@ -190,6 +293,20 @@ impl LeanRepl {
 
     /// Send a JSON command to the REPL and read the response.
     fn send_command(&mut self, command: &LeanCommand) -> Result<LeanResponse> {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        self.send_command_with_timeout(command, timeout)
+    }
+
+    /// Send a JSON command and wait at most `timeout` for its response.
+    ///
+    /// The REPL works through commands one at a time, so a command that
+    /// times out would hold up every later one. The subprocess is killed
+    /// instead and respawned on next use.
+    fn send_command_with_timeout(
+        &mut self,
+        command: &LeanCommand,
+        timeout: Duration,
+    ) -> Result<LeanResponse> {
         let request_json = serde_json::to_string(command)?;
 
         if self.config.verbose {
@@ -209,24 +326,19 @@ impl LeanRepl {
         })?;
 
         // Read response with timeout
-        let start = Instant::now();
-        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let deadline = Instant::now() + timeout;
 
         loop {
-            // Check timeout
-            if start.elapsed() > timeout {
-                return Err(Error::timeout(self.config.timeout_ms));
-            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
 
-            let mut line = String::new();
+            let received = self
+                .stdout_lines
+                .get_mut()
+                .map_err(|_| Error::SubprocessComm("Lean REPL reader lock poisoned".to_string()))?
+                .recv_timeout(remaining);
 
-            match self.stdout.read_line(&mut line) {
-                Ok(0) => {
-                    return Err(Error::SubprocessComm(
-                        "Lean REPL subprocess closed unexpectedly".to_string(),
-                    ));
-                }
-                Ok(_) => {
+            match received {
+                Ok(Ok(line)) => {
                     let line = line.trim();
                     if line.is_empty() {
                         continue;
@@ -245,22 +357,30 @@ impl LeanRepl {
 
                     return Ok(response);
                 }
-                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    std::thread::sleep(Duration::from_millis(10));
-                    continue;
-                }
-                Err(e) => {
+                Ok(Err(e)) => {
                     return Err(Error::SubprocessComm(format!(
                         "Failed to read from Lean REPL: {}",
                         e
                     )));
                 }
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = self.child.kill();
+                    let _ = self.child.wait();
+                    self.needs_respawn = true;
+                    return Err(Error::timeout(timeout.as_millis() as u64));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(Error::SubprocessComm(
+                        "Lean REPL subprocess closed unexpectedly".to_string(),
+                    ));
+                }
             }
         }
     }
 
     /// Execute a Lean command (definition, theorem, #check, etc.).
     pub fn execute_command(&mut self, code: &str) -> Result<LeanResponse> {
+        self.respawn_if_needed(None)?;
         let command = if let Some(env) = self.current_env {
             LeanCommand::command_with_env(code, env)
         } else {
@@ -268,6 +388,7 @@ impl LeanRepl {
         };
 
         let response = self.send_command(&command)?;
+        self.record_command(self.current_env, code, &response);
 
         // Update current environment if successful
         if let Some(env) = response.env {
@@ -287,10 +408,59 @@ impl LeanRepl {
         Ok(response)
     }
 
+    /// Remember how the environment and sorries in `response` were created.
+    fn record_command(&mut self, parent: Option<u64>, code: &str, response: &LeanResponse) {
+        let Some(env) = response.env else {
+            return;
+        };
+        self.env_origins.insert(
+            env,
+            EnvOrigin::Command {
+                parent,
+                cmd: code.to_string(),
+            },
+        );
+        for (index, sorry) in response.sorries.iter().enumerate() {
+            if let Some(proof_state) = sorry.proof_state {
+                self.proof_state_origins
+                    .insert(proof_state, ProofStateOrigin::Sorry { env, index });
+            }
+        }
+    }
+
     /// Apply a tactic in proof mode.
     pub fn apply_tactic(&mut self, tactic: &str, proof_state: u64) -> Result<LeanResponse> {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        self.apply_tactic_with_timeout(tactic, proof_state, timeout)
+    }
+
+    /// Apply a tactic, giving up after `timeout`.
+    ///
+    /// On timeout the subprocess is killed; call
+    /// [`restore_proof_state`](Self::restore_proof_state) to respawn it and
+    /// get back to the state the tactic was applied to. If the REPL is used
+    /// again without that, `proof_state` is replayed here instead.
+    pub fn apply_tactic_with_timeout(
+        &mut self,
+        tactic: &str,
+        proof_state: u64,
+        timeout: Duration,
+    ) -> Result<LeanResponse> {
+        let proof_state = match self.respawn_if_needed(Some(proof_state))? {
+            Some(replayed) => replayed,
+            None => proof_state,
+        };
         let command = LeanCommand::tactic(tactic, proof_state);
-        let response = self.send_command(&command)?;
+        let response = self.send_command_with_timeout(&command, timeout)?;
+        if let (Some(next), false) = (response.proof_state, response.has_errors()) {
+            self.proof_state_origins.insert(
+                next,
+                ProofStateOrigin::Tactic {
+                    parent: proof_state,
+                    tactic: tactic.to_string(),
+                },
+            );
+        }
 
         // Update proof state tracking
         if let Some(state) = self.proof_states.get_mut(&proof_state) {
@@ -377,6 +547,7 @@ impl LeanRepl {
 
     /// Save the current environment to a pickle file.
     pub fn pickle(&mut self, path: &Path) -> Result<()> {
+        self.respawn_if_needed(None)?;
         let env = self
             .current_env
             .ok_or_else(|| Error::repl_execution("No environment to pickle"))?;
@@ -396,6 +567,7 @@ impl LeanRepl {
 
     /// Restore environment from a pickle file.
     pub fn unpickle(&mut self, path: &Path) -> Result<u64> {
+        self.respawn_if_needed(None)?;
         let command = LeanCommand::unpickle(path.to_path_buf());
         let response = self.send_command(&command)?;
 
@@ -410,6 +582,8 @@ impl LeanRepl {
             .env
             .ok_or_else(|| Error::repl_execution("Unpickle did not return environment ID"))?;
 
+        self.env_origins
+            .insert(env, EnvOrigin::Unpickle(path.to_path_buf()));
         self.current_env = Some(env);
         Ok(env)
     }
@@ -442,6 +616,192 @@ impl LeanRepl {
         Ok(state)
     }
 
+    /// Point the tracked proof that `proof_state` belongs to back at it.
+    ///
+    /// Used after a tactic timed out (or a tactic sequence was abandoned), so
+    /// later tactics start from `proof_state` again. If the subprocess was
+    /// killed by a timeout it is respawned first and `proof_state` replayed,
+    /// after which [`active_proof_state_id`](Self::active_proof_state_id)
+    /// returns its new ID. Unknown proof states are left alone.
+    pub fn restore_proof_state(&mut self, proof_state: u64) {
+        match self.respawn_if_needed(Some(proof_state)) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                let root = self.root_proof_state(proof_state);
+                match self.proof_states.get_mut(&root) {
+                    Some(state) => state.proof_state_id = Some(proof_state),
+                    None => tracing::warn!(proof_state, "cannot restore untracked proof state"),
+                }
+            }
+            Err(e) => {
+                tracing::warn!(proof_state, error = %e, "failed to restore Lean REPL after timeout");
+            }
+        }
+    }
+
+    /// Whether a timed-out command killed the subprocess, so it must be
+    /// respawned before it can be used again.
+    pub fn needs_respawn(&self) -> bool {
+        self.needs_respawn
+    }
+
+    /// The proof state `proof_state` was reached from by applying tactics.
+    fn root_proof_state(&self, mut proof_state: u64) -> u64 {
+        while let Some(ProofStateOrigin::Tactic { parent, .. }) =
+            self.proof_state_origins.get(&proof_state)
+        {
+            proof_state = *parent;
+        }
+        proof_state
+    }
+
+    /// Respawn the subprocess if a timeout killed it, replaying the current
+    /// environment and, if given, `proof_state`.
+    ///
+    /// Returns the replayed ID of `proof_state`, or None if no respawn was
+    /// needed (or `proof_state` could not be rebuilt). Tracked state that
+    /// isn't replayed is dropped, since its IDs mean nothing to the new
+    /// process.
+    fn respawn_if_needed(&mut self, proof_state: Option<u64>) -> Result<Option<u64>> {
+        if !self.needs_respawn {
+            return Ok(None);
+        }
+
+        // Work out what to rebuild before the old IDs are discarded.
+        let mut tactics = Vec::new();
+        let mut root = None;
+        let mut cursor = proof_state;
+        while let Some(id) = cursor {
+            match self.proof_state_origins.get(&id) {
+                Some(ProofStateOrigin::Tactic { parent, tactic }) => {
+                    tactics.push(tactic.clone());
+                    cursor = Some(*parent);
+                }
+                Some(ProofStateOrigin::Sorry { env, index }) => {
+                    root = Some((id, *env, *index));
+                    cursor = None;
+                }
+                None => cursor = None,
+            }
+        }
+        tactics.reverse();
+        let tracked = root.and_then(|(id, _, _)| self.proof_states.remove(&id));
+
+        let env_origins = std::mem::take(&mut self.env_origins);
+        self.proof_state_origins.clear();
+        self.proof_states.clear();
+        self.pending_sorries.clear();
+
+        let (child, stdin, stdout_lines) = Self::spawn_process(&self.config)?;
+        let mut old_child = std::mem::replace(&mut self.child, child);
+        let _ = old_child.kill();
+        let _ = old_child.wait();
+        self.stdin = Some(stdin);
+        self.stdout_lines = Mutex::new(stdout_lines);
+        self.needs_respawn = false;
+
+        let mut replayed = HashMap::new();
+        self.current_env = match self.current_env {
+            Some(env) => Some(self.replay_env(env, &env_origins, &mut replayed)?),
+            None => None,
+        };
+
+        let Some((_, root_env, index)) = root else {
+            return Ok(None);
+        };
+        let env = self.replay_env(root_env, &env_origins, &mut replayed)?;
+        let Some(mut replayed_state) = self
+            .proof_state_origins
+            .iter()
+            .find(|(_, origin)| {
+                matches!(origin, ProofStateOrigin::Sorry { env: e, index: i } if *e == env && *i == index)
+            })
+            .map(|(id, _)| *id)
+        else {
+            return Ok(None);
+        };
+        let root_id = replayed_state;
+
+        let mut goals = None;
+        for tactic in tactics {
+            let response = self.send_command(&LeanCommand::tactic(&tactic, replayed_state))?;
+            let next = match (response.proof_state, response.has_errors()) {
+                (Some(next), false) => next,
+                _ => {
+                    return Err(Error::repl_execution(format!(
+                        "Replaying tactic `{}` after respawn failed: {}",
+                        tactic,
+                        response.format_errors()
+                    )))
+                }
+            };
+            self.proof_state_origins.insert(
+                next,
+                ProofStateOrigin::Tactic {
+                    parent: replayed_state,
+                    tactic,
+                },
+            );
+            goals = response.goals;
+            replayed_state = next;
+        }
+
+        if let Some(mut state) = tracked {
+            state.env = env;
+            state.proof_state_id = Some(replayed_state);
+            if let Some(goals) = goals {
+                state.goals = goals.into_iter().map(Goal::from_string).collect();
+            }
+            self.proof_states.insert(root_id, state);
+        }
+        Ok(Some(replayed_state))
+    }
+
+    /// Rebuild environment `env` (from the killed process) in the current
+    /// one, returning its new ID.
+    fn replay_env(
+        &mut self,
+        env: u64,
+        origins: &HashMap<u64, EnvOrigin>,
+        replayed: &mut HashMap<u64, u64>,
+    ) -> Result<u64> {
+        if let Some(new_env) = replayed.get(&env) {
+            return Ok(*new_env);
+        }
+        let origin = origins.get(&env).cloned().ok_or_else(|| {
+            Error::repl_execution(format!("Cannot replay unknown Lean environment {}", env))
+        })?;
+
+        let new_env = match origin {
+            EnvOrigin::Command { parent, cmd } => {
+                let parent = match parent {
+                    Some(parent) => Some(self.replay_env(parent, origins, replayed)?),
+                    None => None,
+                };
+                let command = match parent {
+                    Some(parent) => LeanCommand::command_with_env(&cmd, parent),
+                    None => LeanCommand::command(&cmd),
+                };
+                let response = self.send_command(&command)?;
+                self.record_command(parent, &cmd, &response);
+                response.env
+            }
+            EnvOrigin::Unpickle(path) => {
+                let response = self.send_command(&LeanCommand::unpickle(path.clone()))?;
+                if let Some(env) = response.env {
+                    self.env_origins.insert(env, EnvOrigin::Unpickle(path));
+                }
+                response.env
+            }
+        }
+        .ok_or_else(|| {
+            Error::repl_execution(format!("Replaying Lean environment {} failed", env))
+        })?;
+
+        replayed.insert(env, new_env);
+        Ok(new_env)
+    }
+
     /// Get the current environment ID.
     pub fn current_env(&self) -> Option<u64> {
         self.current_env
@@ -464,6 +824,34 @@ impl LeanRepl {
         matches!(self.child.try_wait(), Ok(None))
     }
 
+    /// Check that the subprocess is running and answering commands.
+    ///
+    /// Fails if the process has exited, was killed after a command timed
+    /// out, or doesn't answer `#check True` cleanly within
+    /// `timeout`. The check runs outside the current environment and leaves
+    /// tracked state untouched.
+    pub fn health_check(&mut self, timeout: Duration) -> Result<()> {
+        if !self.is_alive() {
+            return Err(Error::SubprocessComm(
+                "Lean REPL subprocess has exited".to_string(),
+            ));
+        }
+        if self.needs_respawn {
+            return Err(Error::SubprocessComm(
+                "Lean REPL was killed after a command timed out".to_string(),
+            ));
+        }
+        let response =
+            self.send_command_with_timeout(&LeanCommand::command("#check True"), timeout)?;
+        if response.has_errors() {
+            return Err(Error::SubprocessComm(format!(
+                "Lean REPL health check failed: {}",
+                response.format_errors()
+            )));
+        }
+        Ok(())
+    }
+
     /// Shutdown the REPL subprocess.
     pub fn shutdown(&mut self) -> Result<()> {
         // Close stdin to signal EOF.
@@ -543,6 +931,8 @@ impl ReplEnvironment for LeanRepl {
             execution_time_ms: elapsed_ms,
             pending_operations: self.pending_sorries.clone(),
             submit_result: None, // Lean doesn't support SUBMIT mechanism
+            timed_out: false,
+            truncated: false,
         })
     }
 
@@ -656,10 +1046,23 @@ impl Drop for LeanRepl {
     }
 }
 
+/// Idle REPLs and the number currently lent out.
+struct PoolState {
+    idle: Vec<(LeanRepl, Instant)>,
+    checked_out: usize,
+}
+
 /// Pool of Lean REPL instances for concurrent usage.
+///
+/// At most `max_size` REPLs exist at once. A pooled REPL that has exited,
+/// sat idle past [`LeanReplConfig::max_idle`], or fails its checkout health
+/// check is discarded and a fresh one spawned in its place. Acquired REPLs
+/// come wrapped in a [`PooledLeanRepl`], which frees its slot when dropped,
+/// so a slot can't leak even on an early return or panic.
 pub struct LeanReplPool {
     config: LeanReplConfig,
-    handles: std::sync::Mutex<Vec<LeanRepl>>,
+    state: Mutex<PoolState>,
+    slot_freed: Condvar,
     max_size: usize,
 }
 
@@ -668,47 +1071,169 @@ impl LeanReplPool {
     pub fn new(config: LeanReplConfig, max_size: usize) -> Self {
         Self {
             config,
-            handles: std::sync::Mutex::new(Vec::new()),
-            max_size,
+            state: Mutex::new(PoolState {
+                idle: Vec::new(),
+                checked_out: 0,
+            }),
+            slot_freed: Condvar::new(),
+            max_size: max_size.max(1),
         }
     }
 
     /// Acquire a REPL handle from the pool.
-    pub fn acquire(&self) -> Result<LeanRepl> {
-        let mut handles = self
-            .handles
-            .lock()
-            .map_err(|e| Error::Internal(format!("Failed to lock pool: {}", e)))?;
+    ///
+    /// Reuses the most recently released healthy REPL, or spawns one if the
+    /// pool has room. When all `max_size` REPLs are checked out, waits up to
+    /// [`LeanReplConfig::checkout_timeout_ms`] for one to be released and
+    /// then fails with [`Error::PoolExhausted`].
+    pub fn acquire(&self) -> Result<PooledLeanRepl<'_>> {
+        let timeout = Duration::from_millis(self.config.checkout_timeout_ms);
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock_state()?;
+
+        let pooled = loop {
+            if let Some(handle) = self.take_idle(&mut state) {
+                break Some(handle);
+            }
+            if state.checked_out < self.max_size {
+                break None;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::PoolExhausted {
+                    pool: "Lean REPL".to_string(),
+                    max_size: self.max_size,
+                    waited_ms: timeout.as_millis() as u64,
+                });
+            }
+            state = self
+                .slot_freed
+                .wait_timeout(state, remaining)
+                .map_err(|e| Error::Internal(format!("Failed to lock pool: {}", e)))?
+                .0;
+        };
+        // Reserve the slot before doing any slow work outside the lock
+        state.checked_out += 1;
+        drop(state);
 
-        // Try to get an existing handle
-        while let Some(mut handle) = handles.pop() {
-            if handle.is_alive() {
-                return Ok(handle);
+        if let Some(mut handle) = pooled {
+            if !self.config.health_check_on_checkout {
+                return Ok(self.lend(handle));
+            }
+            let check_timeout =
+                Duration::from_millis(self.config.timeout_ms.min(HEALTH_CHECK_TIMEOUT_MS));
+            match handle.health_check(check_timeout) {
+                Ok(()) => return Ok(self.lend(handle)),
+                Err(e) => {
+                    tracing::warn!(error = %e, "replacing unhealthy Lean REPL from pool");
+                }
             }
-            // Handle is dead, drop it and try another
         }
 
-        // No available handles, spawn a new one
         LeanRepl::spawn(self.config.clone())
+            .map(|handle| self.lend(handle))
+            .inspect_err(|_| self.free_slot())
+    }
+
+    /// Wrap a REPL whose slot is already reserved.
+    fn lend(&self, handle: LeanRepl) -> PooledLeanRepl<'_> {
+        PooledLeanRepl {
+            pool: self,
+            repl: Some(handle),
+        }
     }
 
-    /// Return a REPL handle to the pool.
-    pub fn release(&self, mut handle: LeanRepl) {
+    /// Return a REPL handle to the pool and free its slot.
+    fn release(&self, mut handle: LeanRepl) {
         // Reset the handle before returning to pool
         handle.reset();
 
-        if let Ok(mut handles) = self.handles.lock() {
-            if handles.len() < self.max_size && handle.is_alive() {
-                handles.push(handle);
+        if let Ok(mut state) = self.state.lock() {
+            state.checked_out = state.checked_out.saturating_sub(1);
+            if state.idle.len() < self.max_size && handle.is_alive() {
+                state.idle.push((handle, Instant::now()));
             }
             // Otherwise, the handle is dropped
         }
+        self.slot_freed.notify_one();
+    }
+
+    /// Number of REPLs currently checked out.
+    pub fn checked_out(&self) -> usize {
+        self.state.lock().map(|s| s.checked_out).unwrap_or(0)
+    }
+
+    /// Number of idle REPLs waiting in the pool.
+    pub fn idle_count(&self) -> usize {
+        self.state.lock().map(|s| s.idle.len()).unwrap_or(0)
+    }
+
+    fn lock_state(&self) -> Result<std::sync::MutexGuard<'_, PoolState>> {
+        self.state
+            .lock()
+            .map_err(|e| Error::Internal(format!("Failed to lock pool: {}", e)))
+    }
+
+    /// Pop the newest idle REPL that is alive and not idle too long,
+    /// dropping any that aren't.
+    fn take_idle(&self, state: &mut PoolState) -> Option<LeanRepl> {
+        while let Some((mut handle, idle_since)) = state.idle.pop() {
+            let expired = self
+                .config
+                .max_idle
+                .is_some_and(|max_idle| idle_since.elapsed() > max_idle);
+            if !expired && handle.is_alive() {
+                return Some(handle);
+            }
+        }
+        None
+    }
+
+    fn free_slot(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.checked_out = state.checked_out.saturating_sub(1);
+        }
+        self.slot_freed.notify_one();
+    }
+}
+
+/// REPL checked out of a [`LeanReplPool`].
+///
+/// Derefs to [`LeanRepl`]. Dropping it resets the REPL and returns it to the
+/// pool, or discards it if it has died; either way its slot is freed.
+pub struct PooledLeanRepl<'a> {
+    pool: &'a LeanReplPool,
+    /// Always `Some` until dropped.
+    repl: Option<LeanRepl>,
+}
+
+impl std::ops::Deref for PooledLeanRepl<'_> {
+    type Target = LeanRepl;
+
+    fn deref(&self) -> &LeanRepl {
+        self.repl.as_ref().expect("REPL is held until drop")
+    }
+}
+
+impl std::ops::DerefMut for PooledLeanRepl<'_> {
+    fn deref_mut(&mut self) -> &mut LeanRepl {
+        self.repl.as_mut().expect("REPL is held until drop")
+    }
+}
+
+impl Drop for PooledLeanRepl<'_> {
+    fn drop(&mut self) {
+        if let Some(repl) = self.repl.take() {
+            self.pool.release(repl);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(unix)]
+    use crate::lean::testing::{fake_repl_config, logged_requests};
 
     #[test]
     fn test_lean_repl_config_default() {
@@ -784,6 +1309,174 @@ mod tests {
         assert!(matches!(child.try_wait(), Ok(Some(_))));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_pool_replaces_killed_repl_on_checkout() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = LeanReplPool::new(fake_repl_config(&dir), 1);
+
+        let mut repl = pool.acquire().unwrap();
+        assert!(repl.health_check(Duration::from_secs(2)).is_ok());
+        let first_pid = repl.child.id();
+        drop(repl);
+        assert_eq!(pool.idle_count(), 1);
+
+        // Kill the pooled subprocess behind the pool's back
+        {
+            let mut state = pool.state.lock().unwrap();
+            let (idle, _) = state.idle.last_mut().unwrap();
+            idle.child.kill().unwrap();
+            idle.child.wait().unwrap();
+        }
+
+        let mut repl = pool.acquire().unwrap();
+        assert_ne!(repl.child.id(), first_pid);
+        assert!(repl.is_alive());
+        assert!(repl.health_check(Duration::from_secs(2)).is_ok());
+        assert_eq!(pool.checked_out(), 1);
+        drop(repl);
+        assert_eq!(pool.checked_out(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pool_replaces_wedged_repl_and_expires_idle() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = LeanReplPool::new(fake_repl_config(&dir), 1);
+
+        // A REPL killed by a timed-out tactic is not lent out again
+        let mut repl = pool.acquire().unwrap();
+        let first_pid = repl.child.id();
+        let timed_out = repl.apply_tactic_with_timeout("sleep", 0, Duration::from_millis(50));
+        assert!(matches!(timed_out, Err(Error::Timeout { .. })));
+        drop(repl);
+        let repl = pool.acquire().unwrap();
+        assert_ne!(repl.child.id(), first_pid);
+        drop(repl);
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = fake_repl_config(&dir).with_max_idle(Some(Duration::ZERO));
+        let pool = LeanReplPool::new(config, 1);
+        let repl = pool.acquire().unwrap();
+        let first_pid = repl.child.id();
+        drop(repl);
+        std::thread::sleep(Duration::from_millis(5));
+        let repl = pool.acquire().unwrap();
+        assert_ne!(repl.child.id(), first_pid);
+        drop(repl);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pool_frees_slot_when_checkout_unwinds() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = fake_repl_config(&dir).with_checkout_timeout(50);
+        let pool = LeanReplPool::new(config, 1);
+
+        let unwound = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _repl = pool.acquire().unwrap();
+            panic!("caller bailed out mid-checkout");
+        }));
+        assert!(unwound.is_err());
+        assert_eq!(pool.checked_out(), 0);
+        assert_eq!(pool.idle_count(), 1);
+        assert!(pool.acquire().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pool_checkout_times_out_when_exhausted() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = fake_repl_config(&dir).with_checkout_timeout(50);
+        let pool = LeanReplPool::new(config, 1);
+
+        let repl = pool.acquire().unwrap();
+        let err = pool
+            .acquire()
+            .err()
+            .expect("pool of one should be exhausted");
+        assert!(matches!(
+            err,
+            Error::PoolExhausted {
+                max_size: 1,
+                waited_ms: 50,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("pool exhausted"));
+
+        drop(repl);
+        assert!(pool.acquire().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_timed_out_tactic_respawns_and_replays_proof_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut repl = LeanRepl::spawn(fake_repl_config(&dir)).unwrap();
+        repl.execute_command("def helper := 0").unwrap();
+        let root = repl
+            .start_proof("example : True")
+            .unwrap()
+            .proof_state_id
+            .unwrap();
+        let stepped = repl
+            .apply_tactic("step", root)
+            .unwrap()
+            .proof_state
+            .unwrap();
+        let first_pid = repl.child.id();
+
+        // The hung tactic is abandoned at the timeout and its process killed
+        let start = Instant::now();
+        let err = repl
+            .apply_tactic_with_timeout("sleep", stepped, Duration::from_millis(100))
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout { .. }));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(repl.needs_respawn());
+        assert!(repl.health_check(Duration::from_secs(1)).is_err());
+
+        // Restoring respawns and replays the environment and tactic path
+        repl.restore_proof_state(stepped);
+        assert!(!repl.needs_respawn());
+        assert_ne!(repl.child.id(), first_pid);
+        let replayed = repl.active_proof_state_id().unwrap();
+        let start = Instant::now();
+        let response = repl.apply_tactic("decide", replayed).unwrap();
+        assert!(!response.has_errors());
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        let requests = logged_requests(&dir);
+        assert_eq!(requests.matches("def helper").count(), 2);
+        assert_eq!(requests.matches("example : True").count(), 2);
+        assert_eq!(requests.matches("\"tactic\":\"step\"").count(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restore_proof_state_ignores_unknown_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut repl = LeanRepl::spawn(fake_repl_config(&dir)).unwrap();
+        let root = repl
+            .start_proof("example : True")
+            .unwrap()
+            .proof_state_id
+            .unwrap();
+        let stepped = repl
+            .apply_tactic("step", root)
+            .unwrap()
+            .proof_state
+            .unwrap();
+
+        assert_eq!(repl.active_proof_state_id(), Some(stepped));
+
+        repl.restore_proof_state(stepped + 100);
+        assert_eq!(repl.active_proof_state_id(), Some(stepped));
+        repl.restore_proof_state(root);
+        assert_eq!(repl.active_proof_state_id(), Some(root));
+    }
+
     #[test]
     fn test_parse_proof_state_from_operation_id() {
         assert_eq!(parse_proof_state_from_operation_id("sorry:42:0"), Some(42));
@@ -0,0 +1,75 @@
+//! Stand-in Lean REPL for tests that run without a Lean installation.
+//!
+//! The fake is a `/bin/sh` script speaking the REPL's JSON protocol. It logs
+//! every request to `requests.log` next to the script and answers:
+//!
+//! - commands with a fresh environment ID (IDs count up per request);
+//! - statements mentioning `ill_typed` with an elaboration error and no
+//!   sorry;
+//! - statements mentioning `1 + 1 = 2` or `True` with a sorry whose proof
+//!   state closes only with `rfl` or `decide` respectively;
+//! - the tactic `step` with a new proof state that closes like its parent;
+//! - the tactic `sleep` not at all for five seconds, like a hung tactic;
+//! - any other tactic with an error.
+
+use std::os::unix::fs::PermissionsExt;
+
+use tempfile::TempDir;
+
+use super::repl::LeanReplConfig;
+
+const FAKE_REPL: &str = r#"#!/bin/sh
+log="$(dirname "$0")/requests.log"
+n=1
+while IFS= read -r line; do
+  [ -z "$line" ] && continue
+  echo "$line" >> "$log"
+  n=$((n+1))
+  case "$line" in
+    *'"tactic"'*)
+      ps=$(echo "$line" | sed 's/.*"proofState":\([0-9]*\).*/\1/')
+      tac=$(echo "$line" | sed 's/.*"tactic":"\([^"]*\)".*/\1/')
+      eval "want=\$want_$ps"
+      if [ "$tac" = sleep ]; then
+        sleep 5
+        echo '{"messages":[{"severity":"error","data":"tactic failed"}]}'
+      elif [ "$tac" = step ]; then
+        eval "want_$n=$want"
+        echo '{"goals":["|- stepped"],"proofState":'$n'}'
+      elif [ "$tac" = "$want" ]; then
+        echo '{"goals":[],"proofState":'$n'}'
+      else
+        echo '{"messages":[{"severity":"error","data":"tactic failed"}]}'
+      fi;;
+    *'#check'*) echo '{"env":'$n'}';;
+    *ill_typed*)
+      echo '{"env":'$n',"messages":[{"severity":"error","data":"type mismatch"}]}';;
+    *'1 + 1 = 2'*)
+      eval "want_$n=rfl"
+      echo '{"env":'$n',"sorries":[{"goal":"|- 1 + 1 = 2","proofState":'$n'}]}';;
+    *True*)
+      eval "want_$n=decide"
+      echo '{"env":'$n',"sorries":[{"goal":"|- True","proofState":'$n'}]}';;
+    *) echo '{"env":'$n'}';;
+  esac
+  echo
+done
+"#;
+
+/// Write the fake REPL into `dir` and return a config that runs it.
+pub(crate) fn fake_repl_config(dir: &TempDir) -> LeanReplConfig {
+    let script = dir.path().join("fake-repl");
+    std::fs::write(&script, FAKE_REPL).unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    LeanReplConfig {
+        repl_path: Some(script),
+        timeout_ms: 2_000,
+        ..Default::default()
+    }
+}
+
+/// Every request the fake REPL in `dir` has received, one per line.
+pub(crate) fn logged_requests(dir: &TempDir) -> String {
+    std::fs::read_to_string(dir.path().join("requests.log")).unwrap_or_default()
+}
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::PathBuf;
+use std::sync::LazyLock;
 
 /// Lean REPL command types.
 ///
@@ -152,6 +153,11 @@ impl LeanResponse {
             .collect()
     }
 
+    /// Structured diagnostics for every message, in order.
+    pub fn diagnostics(&self) -> Vec<LeanDiagnostic> {
+        self.messages.iter().map(LeanMessage::diagnostic).collect()
+    }
+
     /// Format output as a human-readable string.
     pub fn format_output(&self) -> String {
         let mut parts = Vec::new();
@@ -223,8 +229,183 @@ pub struct LeanMessage {
     pub data: String,
 }
 
+impl LeanMessage {
+    /// Parse this message into a structured diagnostic.
+    ///
+    /// The position comes from `pos` when the REPL reports one, otherwise
+    /// from a `file:line:col: severity:` prefix in the message text as
+    /// printed by `lake build`. The raw text stays available in `data`.
+    pub fn diagnostic(&self) -> LeanDiagnostic {
+        LeanDiagnostic::parse(self)
+    }
+}
+
+/// Matches Lean's textual `[file:]line:col: [severity:]` message prefix.
+static LOCATION_PREFIX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"^(?:[^\n]*?:)?(\d+):(\d+):\s*(?:(?:error|warning|info):\s*)?").unwrap()
+});
+
+/// Kind of a Lean diagnostic, recognised from its first line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticCode {
+    /// `type mismatch`
+    TypeMismatch,
+    /// `application type mismatch`
+    ApplicationTypeMismatch,
+    /// `unknown identifier '...'`
+    UnknownIdentifier,
+    /// `unknown constant '...'`
+    UnknownConstant,
+    /// `unsolved goals`
+    UnsolvedGoals,
+    /// `failed to synthesize ...`
+    FailedToSynthesize,
+    /// `declaration uses 'sorry'`
+    UsesSorry,
+    /// Anything else
+    Other,
+}
+
+impl DiagnosticCode {
+    /// Recognise the kind from a message's first line.
+    pub fn from_headline(headline: &str) -> Self {
+        let headline = headline.trim();
+        if headline.starts_with("type mismatch") {
+            Self::TypeMismatch
+        } else if headline.starts_with("application type mismatch") {
+            Self::ApplicationTypeMismatch
+        } else if headline.starts_with("unknown identifier") {
+            Self::UnknownIdentifier
+        } else if headline.starts_with("unknown constant") {
+            Self::UnknownConstant
+        } else if headline.starts_with("unsolved goals") {
+            Self::UnsolvedGoals
+        } else if headline.starts_with("failed to synthesize") {
+            Self::FailedToSynthesize
+        } else if headline.starts_with("declaration uses 'sorry'") {
+            Self::UsesSorry
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Stable identifier for the kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TypeMismatch => "type_mismatch",
+            Self::ApplicationTypeMismatch => "application_type_mismatch",
+            Self::UnknownIdentifier => "unknown_identifier",
+            Self::UnknownConstant => "unknown_constant",
+            Self::UnsolvedGoals => "unsolved_goals",
+            Self::FailedToSynthesize => "failed_to_synthesize",
+            Self::UsesSorry => "uses_sorry",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A Lean message parsed into its components.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeanDiagnostic {
+    /// 1-indexed line of the offending source, if positional.
+    pub line: Option<u32>,
+
+    /// 0-indexed column of the offending source, if positional.
+    pub column: Option<u32>,
+
+    /// Message severity.
+    pub severity: MessageSeverity,
+
+    /// Kind of diagnostic.
+    pub code: DiagnosticCode,
+
+    /// Message text without any location prefix; may span several lines.
+    pub message: String,
+
+    /// Remaining goals, for [`DiagnosticCode::UnsolvedGoals`].
+    pub goals: Vec<String>,
+
+    /// The message exactly as Lean reported it.
+    pub raw: String,
+}
+
+impl LeanDiagnostic {
+    fn parse(msg: &LeanMessage) -> Self {
+        let raw = msg.data.clone();
+        let (prefix_pos, text) = match LOCATION_PREFIX.captures(&msg.data) {
+            Some(caps) => {
+                let line = caps[1].parse().ok();
+                let column = caps[2].parse().ok();
+                (line.zip(column), &msg.data[caps[0].len()..])
+            }
+            None => (None, msg.data.as_str()),
+        };
+        let message = text.trim().to_string();
+        let code = DiagnosticCode::from_headline(message.lines().next().unwrap_or_default());
+
+        // Unsolved goals are reported at the declaration, not at a faulty term
+        let (line, column, goals) = if code == DiagnosticCode::UnsolvedGoals {
+            (None, None, Self::parse_goals(&message))
+        } else {
+            let pos = msg.pos.as_ref().map(|p| (p.line, p.column)).or(prefix_pos);
+            (pos.map(|p| p.0), pos.map(|p| p.1), Vec::new())
+        };
+
+        Self {
+            line,
+            column,
+            severity: msg.severity,
+            code,
+            message,
+            goals,
+            raw,
+        }
+    }
+
+    /// Split the body of an `unsolved goals` message into one entry per goal.
+    fn parse_goals(message: &str) -> Vec<String> {
+        let body: Vec<&str> = message.lines().skip(1).collect();
+        let mut goals = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        for line in body {
+            // Goals are separated by blank lines; a `case` tag starts a new one
+            if line.trim().is_empty() || (line.starts_with("case ") && !current.is_empty()) {
+                if !current.is_empty() {
+                    goals.push(current.join("\n"));
+                    current.clear();
+                }
+                if line.trim().is_empty() {
+                    continue;
+                }
+            }
+            current.push(line.trim_end());
+        }
+        if !current.is_empty() {
+            goals.push(current.join("\n"));
+        }
+        goals
+    }
+
+    /// First line of the message.
+    pub fn headline(&self) -> &str {
+        self.message.lines().next().unwrap_or_default()
+    }
+
+    /// Whether the diagnostic points at a source position.
+    pub fn is_positional(&self) -> bool {
+        self.line.is_some()
+    }
+}
+
 /// Message severity levels.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageSeverity {
     Error,
@@ -653,6 +834,59 @@ mod tests {
         assert_eq!(response.errors().len(), 1);
     }
 
+    #[test]
+    fn test_type_mismatch_diagnostic() {
+        let json = r#"{
+            "severity": "error",
+            "pos": {"line": 3, "column": 8},
+            "endPos": {"line": 3, "column": 9},
+            "data": "type mismatch\n  h\nhas type\n  a = b : Prop\nbut is expected to have type\n  b = a : Prop"
+        }"#;
+        let msg: LeanMessage = serde_json::from_str(json).unwrap();
+
+        let diag = msg.diagnostic();
+        assert_eq!(diag.line, Some(3));
+        assert_eq!(diag.column, Some(8));
+        assert_eq!(diag.severity, MessageSeverity::Error);
+        assert_eq!(diag.code, DiagnosticCode::TypeMismatch);
+        assert_eq!(diag.headline(), "type mismatch");
+        assert!(diag
+            .message
+            .contains("but is expected to have type\n  b = a : Prop"));
+        assert_eq!(diag.raw, msg.data);
+
+        // Position parsed from lake's textual prefix when `pos` is absent
+        let msg = LeanMessage {
+            severity: MessageSeverity::Error,
+            pos: None,
+            end_pos: None,
+            data: "Foo/Bar.lean:12:4: error: unknown identifier 'baz'".to_string(),
+        };
+        let diag = msg.diagnostic();
+        assert_eq!((diag.line, diag.column), (Some(12), Some(4)));
+        assert_eq!(diag.code, DiagnosticCode::UnknownIdentifier);
+        assert_eq!(diag.message, "unknown identifier 'baz'");
+    }
+
+    #[test]
+    fn test_unsolved_goals_diagnostic() {
+        let msg = LeanMessage {
+            severity: MessageSeverity::Error,
+            pos: Some(Position { line: 1, column: 0 }),
+            end_pos: None,
+            data: "unsolved goals\ncase left\np q : Prop\n⊢ p\n\ncase right\np q : Prop\n⊢ q"
+                .to_string(),
+        };
+
+        let diag = msg.diagnostic();
+        assert_eq!(diag.code, DiagnosticCode::UnsolvedGoals);
+        assert!(!diag.is_positional());
+        assert_eq!(
+            diag.goals,
+            vec!["case left\np q : Prop\n⊢ p", "case right\np q : Prop\n⊢ q"]
+        );
+    }
+
     #[test]
     fn test_proof_state() {
         let mut state = ProofState::new(1);
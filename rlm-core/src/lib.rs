@@ -55,21 +55,26 @@ pub mod trajectory;
 // Re-exports for convenience
 pub use adapters::{
     suggested_output_path, trace_visualize, trace_visualize_from_json, AdapterConfig,
-    AdapterSessionContext, AdapterStatus, ClaudeCodeAdapter, CompactData, HookContext, HookHandler,
-    HookResult, HookTrigger, HtmlPreset, McpTool, McpToolRegistry, PromptEnhancement, RlmRequest,
-    RlmResponse, RlmSkill, TraceVisualizeFormat, TraceVisualizeOptions, TraceVisualizeResult,
+    AdapterSessionContext, AdapterStatus, ClaudeCodeAdapter, ClaudeCodeOrchestrator, CompactData,
+    HookContext, HookData, HookHandler, HookResult, HookResultData, HookTrigger, HtmlPreset,
+    McpTool, McpToolRegistry, PromptEnhancement, RlmRequest, RlmResponse, RlmSkill,
+    TraceVisualizeFormat, TraceVisualizeOptions, TraceVisualizeResult,
 };
 #[cfg(feature = "adversarial")]
 pub use adversarial::{
     AdversarialConfig, AdversarialTrigger, AdversarialValidator, CodeFile, CriticStrategy,
-    EdgeCaseStrategy, FreshContextInvoker, FreshInvokerBuilder, GeminiFreshInvoker,
-    GeminiValidator, InvocationStats, Issue, IssueCategory, IssueLocation, IssueSeverity,
-    PerformanceStrategy, PooledFreshInvoker, SecurityStrategy, StrategyFactory, TestingStrategy,
-    ToolOutput as AdversarialToolOutput, TraceabilityStrategy, ValidationContext, ValidationId,
-    ValidationIteration, ValidationResult as AdversarialValidationResult,
+    EdgeCaseStrategy, FileValidationCache, FreshContextInvoker, FreshInvokerBuilder,
+    GeminiFreshInvoker, GeminiValidator, InMemoryValidationCache, InvocationStats, Issue,
+    IssueCategory, IssueLocation, IssueSeverity, OpenAIValidator, PerformanceStrategy,
+    PooledFreshInvoker, SecurityStrategy, StrategyFactory, TestingStrategy,
+    ToolOutput as AdversarialToolOutput, TraceabilityStrategy, ValidationCache, ValidationContext,
+    ValidationId, ValidationIteration, ValidationResult as AdversarialValidationResult,
     ValidationStats as AdversarialValidationStats, ValidationStrategy, ValidationVerdict,
 };
-pub use complexity::{ActivationDecision, PatternClassifier, TaskComplexitySignals};
+pub use complexity::{
+    ActivationDecision, ClassifierConfig, PatternClassifier, PatternRule, RuleMatcher,
+    TaskComplexitySignals,
+};
 pub use context::{
     ContextSizeTracker, ContextVarType, ContextVariable, ExternalizationConfig,
     ExternalizedContext, Message, Role, SessionContext, SizeConfig, SizeWarning, ToolOutput,
@@ -83,16 +88,17 @@ pub use dp_integration::{
 pub use epistemic::{
     audit_reasoning, evidence_dependence, quick_hallucination_check, verify_claim, BatchVerifier,
     BudgetResult, Claim, ClaimCategory, ClaimExtractor, EpistemicVerifier, EvidenceScrubber,
-    GateDecision, GroundingStatus, HaikuVerifier, MemoryGate, MemoryGateConfig, Probability,
-    SelfVerifier, ThresholdGate, VerificationConfig, VerificationResult, VerificationStats,
-    VerificationVerdict,
+    GateDecision, GroundingStatus, HaikuVerifier, LogprobVerifier, MemoryGate, MemoryGateConfig,
+    Probability, SelfVerifier, ThresholdGate, VerificationConfig, VerificationResult,
+    VerificationStats, VerificationStrategy, VerificationVerdict,
 };
 pub use error::{Error, Result};
 pub use llm::{
     AnthropicClient, BatchConfig, BatchExecutor, BatchQueryResult, BatchedLLMQuery,
     BatchedQueryResults, ClientConfig, CompletionRequest, CompletionResponse, CostTracker,
     DualModelConfig, LLMClient, ModelCallTier, ModelSpec, ModelTier, Provider, QueryType,
-    RoutingContext, SmartRouter, SwitchStrategy, TierBreakdown,
+    RecordingLLMClient, ReplayLLMClient, ResponseFormat, RoutingContext, SmartRouter,
+    SwitchStrategy, TierBreakdown,
 };
 pub use memory::{Node, NodeId, NodeType, SqliteMemoryStore, Tier};
 pub use module::{
@@ -100,7 +106,9 @@ pub use module::{
     NamedMetric, OptimizationStats, OptimizedModule, Optimizer, ParallelVec, Predict,
     PredictConfig, Predictor,
 };
-pub use orchestrator::{FallbackLoop, FallbackLoopStep, OrchestrationRoutingRuntime, Orchestrator};
+pub use orchestrator::{
+    FallbackLoop, FallbackLoopStep, OrchestrationRoutingRuntime, Orchestrator, RecursionGuard,
+};
 pub use proof::{
     AIAssistantConfig, AIProofAssistant, AutomationTier, HelperLemma, HelperProofStatus,
     LimitReason, ProofAttempt, ProofAutomation, ProofAutomationBuilder, ProofContext, ProofSession,
@@ -110,14 +118,15 @@ pub use proof::{
 pub use reasoning::{
     DecisionNode, DecisionNodeId, DecisionNodeType, DecisionPath, DecisionPoint, DecisionTree,
     DotConfig, HtmlConfig, HtmlTheme, NetworkXGraph, NetworkXGraphAttrs, NetworkXLink,
-    NetworkXNode, OptionStatus, ReasoningTrace, ReasoningTraceStore, TraceAnalyzer,
+    NetworkXNode, OptionStatus, PruneConfig, ReasoningTrace, ReasoningTraceStore, TraceAnalyzer,
     TraceComparison, TraceEdge, TraceEdgeLabel, TraceId, TraceQuery, TraceStats, TraceStoreStats,
 };
 pub use repl::{ExecuteResult, ReplConfig, ReplHandle, ReplPool};
 pub use signature::{
     apply_defaults, validate_fields, validate_value, ExecutionLimits, ExecutionResult,
-    FallbackConfig, FallbackExtractor, FallbackTrigger, FieldSpec, FieldType, HistoryEntry,
-    HistoryEntryType, ParseError, ReplHistory, Signature, ValidationError, ValidationResult,
+    FallbackConfig, FallbackExtractor, FallbackTrigger, FieldShape, FieldSpec, FieldType,
+    HistoryEntry, HistoryEntryType, ParseError, ParseFormat, PartialOutputs, ReplHistory,
+    Signature, ValidationError, ValidationResult,
 };
 pub use sync::{
     DriftReport, DriftType, DualTrackSync, FormalizationLevel, SyncDirection, SyncResult,
@@ -125,4 +134,4 @@ pub use sync::{
 pub use topos::{
     IndexBuilder, LeanRef, Link, LinkIndex, LinkType, ToposClient, ToposClientConfig, ToposRef,
 };
-pub use trajectory::{TrajectoryEvent, TrajectoryEventType};
+pub use trajectory::{TrajectoryEvent, TrajectoryEventType, TrajectoryFilter};
@@ -41,7 +41,7 @@ pub const DEFAULT_MAX_PARALLEL: usize = 5;
 /// Default rate-limit window for provider throttling.
 pub const DEFAULT_RATE_LIMIT_WINDOW_MS: u64 = 60_000;
 
-fn default_provider_rate_limits() -> HashMap<Provider, u32> {
+pub(crate) fn default_provider_rate_limits() -> HashMap<Provider, u32> {
     #[allow(unused_mut)]
     let mut limits = HashMap::from([
         (Provider::Anthropic, 60),
@@ -59,15 +59,16 @@ struct ProviderWindowState {
     used: u32,
 }
 
+/// Fixed-window request limiter keyed by provider.
 #[derive(Debug)]
-struct ProviderRateLimiter {
+pub(crate) struct ProviderRateLimiter {
     limits: HashMap<Provider, u32>,
     window: Duration,
     state: Mutex<HashMap<Provider, ProviderWindowState>>,
 }
 
 impl ProviderRateLimiter {
-    fn new(limits: HashMap<Provider, u32>, window: Duration) -> Self {
+    pub(crate) fn new(limits: HashMap<Provider, u32>, window: Duration) -> Self {
         Self {
             limits,
             window,
@@ -75,7 +76,8 @@ impl ProviderRateLimiter {
         }
     }
 
-    async fn acquire(&self, provider: Provider) {
+    /// Wait until `provider` has capacity in the current window.
+    pub(crate) async fn acquire(&self, provider: Provider) {
         let limit = match self.limits.get(&provider).copied() {
             Some(limit) if limit > 0 => limit,
             _ => return,
@@ -439,6 +441,9 @@ impl<C: LLMClient + 'static> BatchExecutor<C> {
             Error::Timeout { .. } => true,
             Error::LLM(message) => Self::is_retryable_message(message),
             Error::LlmApi { message, .. } => Self::is_retryable_message(message),
+            Error::LlmStatus {
+                status, message, ..
+            } => matches!(status, 408 | 429) || Self::is_retryable_message(message),
             _ => false,
         }
     }
@@ -778,6 +783,9 @@ mod tests {
                 },
                 timestamp: chrono::Utc::now(),
                 cost: Some(0.0),
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
             })
         }
 
@@ -10,7 +10,13 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use super::types::ChatMessage;
+use super::types::{ChatMessage, CompletionRequest};
+
+/// Most cache breakpoints a request may carry (Anthropic's limit).
+pub const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+/// Smallest prefix, in tokens, worth a cache breakpoint (Anthropic's minimum).
+pub const MIN_CACHE_TOKENS: usize = 1024;
 
 /// Cache key for a prompt.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -69,6 +75,9 @@ pub struct CacheEntry {
     pub token_count: u64,
     /// Model this was cached for
     pub model: String,
+    /// Size of the cached prompt content in bytes
+    #[serde(default)]
+    pub size_bytes: u64,
 }
 
 impl CacheEntry {
@@ -81,9 +90,17 @@ impl CacheEntry {
             hit_count: 0,
             token_count,
             model: model.into(),
+            // Approximate bytes as tokens * 4
+            size_bytes: token_count * 4,
         }
     }
 
+    /// Set the content size in bytes.
+    pub fn with_size_bytes(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = size_bytes;
+        self
+    }
+
     /// Record a cache hit.
     pub fn record_hit(&mut self) {
         self.hit_count += 1;
@@ -109,6 +126,12 @@ pub struct CacheStats {
     pub estimated_savings: f64,
     /// Number of active entries
     pub entry_count: u64,
+    /// Total bytes of prompt content across active entries
+    #[serde(default)]
+    pub total_bytes: u64,
+    /// Entries evicted to stay within the size bounds
+    #[serde(default)]
+    pub evictions: u64,
 }
 
 impl CacheStats {
@@ -135,33 +158,119 @@ impl CacheStats {
     }
 }
 
+/// Cache entries with a logical clock for LRU ordering.
+#[derive(Default)]
+struct EntryStore {
+    /// Entries with the clock value of their last use
+    entries: HashMap<CacheKey, (CacheEntry, u64)>,
+    clock: u64,
+    total_bytes: u64,
+}
+
+impl EntryStore {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn insert(&mut self, entry: CacheEntry) {
+        let used = self.tick();
+        self.total_bytes += entry.size_bytes;
+        if let Some((old, _)) = self.entries.insert(entry.key.clone(), (entry, used)) {
+            self.total_bytes -= old.size_bytes;
+        }
+    }
+
+    fn get_mut(&mut self, key: &CacheKey) -> Option<&mut CacheEntry> {
+        let used = self.tick();
+        self.entries.get_mut(key).map(|(entry, last_used)| {
+            *last_used = used;
+            entry
+        })
+    }
+
+    fn remove_lru(&mut self) -> Option<CacheEntry> {
+        let key = self
+            .entries
+            .iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(key, _)| key.clone())?;
+        let (entry, _) = self.entries.remove(&key)?;
+        self.total_bytes -= entry.size_bytes;
+        Some(entry)
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&CacheEntry) -> bool) {
+        let mut removed_bytes = 0;
+        self.entries.retain(|_, (entry, _)| {
+            let kept = keep(entry);
+            if !kept {
+                removed_bytes += entry.size_bytes;
+            }
+            kept
+        });
+        self.total_bytes -= removed_bytes;
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.total_bytes = 0;
+    }
+
+    fn sync_stats(&self, stats: &mut CacheStats) {
+        stats.entry_count = self.entries.len() as u64;
+        stats.total_bytes = self.total_bytes;
+    }
+}
+
 /// Prompt cache tracker.
 ///
 /// Tracks which prompts have been cached and their usage statistics.
 /// Note: This tracks local awareness of provider-side caching, not
 /// an actual cache implementation.
+///
+/// Optional `max_entries` / `max_bytes` bounds evict the least recently
+/// used entries so long sessions don't grow without limit.
 pub struct PromptCache {
-    entries: Arc<RwLock<HashMap<CacheKey, CacheEntry>>>,
+    entries: Arc<RwLock<EntryStore>>,
     stats: Arc<RwLock<CacheStats>>,
     /// Time-to-live for cache entries (provider-dependent)
     ttl: Duration,
     /// Cost savings per cached token (default: 90% of input cost)
     savings_rate: f64,
+    /// Maximum number of tracked entries
+    max_entries: Option<usize>,
+    /// Maximum total bytes of tracked prompt content
+    max_bytes: Option<u64>,
 }
 
 impl PromptCache {
     /// Create a new prompt cache.
     pub fn new() -> Self {
         Self {
-            entries: Arc::new(RwLock::new(HashMap::new())),
+            entries: Arc::new(RwLock::new(EntryStore::default())),
             stats: Arc::new(RwLock::new(CacheStats::default())),
             // Anthropic cache TTL is 5 minutes
             ttl: Duration::minutes(5),
             // 90% savings on cached tokens
             savings_rate: 0.9,
+            max_entries: None,
+            max_bytes: None,
         }
     }
 
+    /// Bound the number of tracked entries (LRU eviction).
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Bound the total bytes of tracked prompt content (LRU eviction).
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
     /// Create with custom TTL.
     pub fn with_ttl(mut self, ttl: Duration) -> Self {
         self.ttl = ttl;
@@ -178,18 +287,42 @@ impl PromptCache {
     pub async fn is_cached(&self, key: &CacheKey) -> bool {
         let entries = self.entries.read().await;
         entries
+            .entries
             .get(key)
-            .map(|e| !e.is_expired(self.ttl))
+            .map(|(e, _)| !e.is_expired(self.ttl))
             .unwrap_or(false)
     }
 
     /// Record a cache creation (prompt was sent with cache control).
+    ///
+    /// Content size is approximated from `token_count`; use
+    /// [`record_entry`](Self::record_entry) to supply it.
     pub async fn record_creation(&self, key: CacheKey, model: impl Into<String>, token_count: u64) {
+        self.record_entry(CacheEntry::new(key, model, token_count))
+            .await;
+    }
+
+    /// Record a cache creation from a prepared entry, evicting least
+    /// recently used entries if a bound is exceeded.
+    pub async fn record_entry(&self, entry: CacheEntry) {
         let mut entries = self.entries.write().await;
-        entries.insert(key.clone(), CacheEntry::new(key, model, token_count));
+        entries.insert(entry);
+
+        let mut evicted = 0;
+        while self
+            .max_entries
+            .is_some_and(|max| entries.entries.len() > max)
+            || self.max_bytes.is_some_and(|max| entries.total_bytes > max)
+        {
+            if entries.remove_lru().is_none() {
+                break;
+            }
+            evicted += 1;
+        }
 
         let mut stats = self.stats.write().await;
-        stats.entry_count = entries.len() as u64;
+        stats.evictions += evicted;
+        entries.sync_stats(&mut stats);
     }
 
     /// Record a cache hit.
@@ -219,17 +352,19 @@ impl PromptCache {
     /// Clean up expired entries.
     pub async fn cleanup(&self) {
         let mut entries = self.entries.write().await;
-        entries.retain(|_, e| !e.is_expired(self.ttl));
+        entries.retain(|e| !e.is_expired(self.ttl));
 
         let mut stats = self.stats.write().await;
-        stats.entry_count = entries.len() as u64;
+        entries.sync_stats(&mut stats);
     }
 
     /// Get all active entries.
     pub async fn entries(&self) -> Vec<CacheEntry> {
         let entries = self.entries.read().await;
         entries
+            .entries
             .values()
+            .map(|(e, _)| e)
             .filter(|e| !e.is_expired(self.ttl))
             .cloned()
             .collect()
@@ -295,6 +430,79 @@ pub fn apply_cache_markers(messages: &mut [ChatMessage], breakpoints: &[usize])
     }
 }
 
+/// Place cache markers on a request's messages.
+///
+/// Manual breakpoints from
+/// [`CompletionRequest::with_cache_after_message`] and
+/// [`CompletionRequest::with_cache_after_system`] are used as given;
+/// without them, [`find_cache_breakpoints`] picks positions. Either way at
+/// most [`MAX_CACHE_BREAKPOINTS`] are kept (see [`cap_cache_breakpoints`]).
+/// Returns the breakpoints applied, in order. The system prompt is not a
+/// message, so a returned `0` must be marked by the client when it builds
+/// the system prompt; `0` is dropped when the request has none.
+pub fn apply_request_cache_markers(
+    request: &mut CompletionRequest,
+    min_tokens: usize,
+) -> Vec<usize> {
+    let mut breakpoints = if request.cache_breakpoints.is_empty() {
+        find_cache_breakpoints(request.system.as_deref(), &request.messages, min_tokens)
+    } else {
+        request
+            .cache_breakpoints
+            .iter()
+            .copied()
+            .filter(|&bp| bp <= request.messages.len() && (bp > 0 || request.system.is_some()))
+            .collect()
+    };
+    breakpoints = cap_cache_breakpoints(
+        request.system.as_deref(),
+        &request.messages,
+        &breakpoints,
+        MAX_CACHE_BREAKPOINTS,
+    );
+    apply_cache_markers(&mut request.messages, &breakpoints);
+    breakpoints
+}
+
+/// Reduce breakpoints to at most `max`, dropping the least valuable.
+///
+/// A breakpoint's value is the content it adds to the cache beyond the
+/// previous breakpoint. The one adding the least is dropped (its content
+/// then counts toward the next) until `max` remain. The last breakpoint,
+/// which caches the longest prefix, is always kept.
+pub fn cap_cache_breakpoints(
+    system: Option<&str>,
+    messages: &[ChatMessage],
+    breakpoints: &[usize],
+    max: usize,
+) -> Vec<usize> {
+    let mut kept = breakpoints.to_vec();
+    kept.sort_unstable();
+    kept.dedup();
+
+    // Characters cached up to each breakpoint
+    let system_len = system.map_or(0, str::len);
+    let prefix_len = |bp: usize| {
+        system_len
+            + messages[..bp.min(messages.len())]
+                .iter()
+                .map(|m| m.content.len())
+                .sum::<usize>()
+    };
+
+    while kept.len() > max.max(1) {
+        let last = kept.len() - 1;
+        let drop = (0..last)
+            .min_by_key(|&i| {
+                let previous = if i == 0 { 0 } else { prefix_len(kept[i - 1]) };
+                prefix_len(kept[i]) - previous
+            })
+            .unwrap_or(0);
+        kept.remove(drop);
+    }
+    kept
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,11 +515,13 @@ mod tests {
                 role: ChatRole::User,
                 content: "Hello".to_string(),
                 cache_control: None,
+                images: Vec::new(),
             },
             ChatMessage {
                 role: ChatRole::Assistant,
                 content: "Hi there".to_string(),
                 cache_control: None,
+                images: Vec::new(),
             },
         ];
 
@@ -407,6 +617,63 @@ mod tests {
         assert!(messages[2].cache_control.is_none());
     }
 
+    #[test]
+    fn test_manual_cache_breakpoints() {
+        let mut request = CompletionRequest::new()
+            .with_system("You are a release assistant")
+            .with_messages(vec![
+                ChatMessage::user("Tool catalogue ".repeat(200)),
+                ChatMessage::assistant("Ready"),
+                ChatMessage::user("Ship it"),
+            ])
+            .with_cache_after_system()
+            .with_cache_after_message(1);
+        assert!(request.enable_caching);
+
+        // The heuristic would mark every message; the hints win
+        let applied = apply_request_cache_markers(&mut request, 1);
+        assert_eq!(applied, vec![0, 2]);
+        assert!(request.messages[0].cache_control.is_none());
+        assert!(request.messages[1].cache_control.is_some());
+        assert!(request.messages[2].cache_control.is_none());
+    }
+
+    #[test]
+    fn test_system_breakpoint_dropped_without_system_prompt() {
+        let mut request = CompletionRequest::new()
+            .with_messages(vec![ChatMessage::user("Hello")])
+            .with_cache_after_system()
+            .with_cache_after_message(0);
+
+        assert_eq!(apply_request_cache_markers(&mut request, 1), vec![1]);
+    }
+
+    #[test]
+    fn test_cache_breakpoints_capped_by_value() {
+        let sizes = [4000, 10, 3000, 20, 2000, 30];
+        let messages: Vec<ChatMessage> = sizes
+            .iter()
+            .map(|&n| ChatMessage::user("x".repeat(n)))
+            .collect();
+        let mut request = CompletionRequest::new().with_messages(messages);
+        for i in 0..sizes.len() {
+            request = request.with_cache_after_message(i);
+        }
+
+        let applied = apply_request_cache_markers(&mut request, 1024);
+        assert_eq!(applied.len(), MAX_CACHE_BREAKPOINTS);
+        // Breakpoints after the 10- and 20-char messages add the least
+        assert_eq!(applied, vec![1, 3, 5, 6]);
+        let marked: Vec<usize> = request
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.cache_control.is_some())
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(marked, vec![0, 2, 4, 5]);
+    }
+
     #[tokio::test]
     async fn test_cache_cleanup() {
         let cache = PromptCache::new().with_ttl(Duration::zero());
@@ -420,4 +687,55 @@ mod tests {
         let stats = cache.stats().await;
         assert_eq!(stats.entry_count, 0);
     }
+
+    #[tokio::test]
+    async fn test_lru_eviction_by_entries() {
+        let cache = PromptCache::new().with_max_entries(2);
+        let a = CacheKey::from_content("a");
+        let b = CacheKey::from_content("b");
+        let c = CacheKey::from_content("c");
+
+        cache.record_creation(a.clone(), "claude", 100).await;
+        cache.record_creation(b.clone(), "claude", 100).await;
+        // Touch `a` so `b` becomes least recently used
+        cache.record_hit(&a, 100, 0.000003).await;
+        cache.record_miss().await;
+        cache.record_creation(c.clone(), "claude", 100).await;
+
+        assert!(cache.is_cached(&a).await);
+        assert!(!cache.is_cached(&b).await);
+        assert!(cache.is_cached(&c).await);
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entry_count, 2);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.total_bytes, 800);
+        assert!((stats.hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_lru_eviction_by_bytes() {
+        let cache = PromptCache::new().with_max_bytes(1000);
+        let a = CacheKey::from_content("a");
+        let b = CacheKey::from_content("b");
+
+        cache
+            .record_entry(CacheEntry::new(a.clone(), "claude", 100).with_size_bytes(600))
+            .await;
+        cache
+            .record_entry(CacheEntry::new(b.clone(), "claude", 100).with_size_bytes(600))
+            .await;
+
+        assert!(!cache.is_cached(&a).await);
+        assert!(cache.is_cached(&b).await);
+        let stats = cache.stats().await;
+        assert_eq!(stats.total_bytes, 600);
+        assert_eq!(stats.evictions, 1);
+
+        // Re-recording an entry replaces its size rather than adding to it
+        cache
+            .record_entry(CacheEntry::new(b.clone(), "claude", 100).with_size_bytes(300))
+            .await;
+        assert_eq!(cache.stats().await.total_bytes, 300);
+    }
 }
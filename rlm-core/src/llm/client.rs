@@ -5,16 +5,27 @@ use chrono::Utc;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tracing::Instrument;
 
 use crate::error::{Error, Result};
 
+use super::cache::{apply_request_cache_markers, MIN_CACHE_TOKENS};
+use super::retry::{parse_retry_after, AttemptError, RetryPolicy};
+use super::stream::{
+    ndjson_stream, ollama_tool_call_id, parse_anthropic_stop_reason, parse_ollama_stop_reason,
+    parse_openai_stop_reason, response_bytes, single_chunk_stream, sse_stream, with_retries,
+    AnthropicStreamHandler, CompletionStream, OllamaChatResponse, OllamaStreamHandler,
+    OpenAIStreamHandler,
+};
 use super::types::{
-    CompletionRequest, CompletionResponse, EmbeddingRequest, EmbeddingResponse, ModelSpec,
-    Provider, StopReason, TokenUsage,
+    CacheControl, ChatMessage, CompletionRequest, CompletionResponse, EmbeddingRequest,
+    EmbeddingResponse, ImageSource, ModelSpec, ModelTier, Provider, ResponseFormat, StopReason,
+    TokenLogprob, TokenUsage, ToolCall, ToolDef,
 };
 
 /// LLM client trait for making completions and embeddings.
@@ -23,6 +34,15 @@ pub trait LLMClient: Send + Sync {
     /// Complete a prompt.
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse>;
 
+    /// Complete a prompt, yielding text as it is generated.
+    ///
+    /// The last chunk carries the full [`CompletionResponse`]. Providers
+    /// without streaming support yield the whole completion as one chunk.
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<CompletionStream> {
+        let response = self.complete(request).await?;
+        Ok(single_chunk_stream(response))
+    }
+
     /// Create embeddings for texts.
     async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse>;
 
@@ -31,6 +51,11 @@ pub trait LLMClient: Send + Sync {
 
     /// List available models.
     fn available_models(&self) -> Vec<ModelSpec>;
+
+    /// Whether completions honour [`CompletionRequest::top_logprobs`].
+    fn supports_logprobs(&self) -> bool {
+        false
+    }
 }
 
 /// Configuration for LLM clients.
@@ -44,8 +69,8 @@ pub struct ClientConfig {
     pub default_model: Option<String>,
     /// Request timeout in seconds
     pub timeout_secs: u64,
-    /// Max retries on failure
-    pub max_retries: u32,
+    /// Retry policy for transient failures
+    pub retry_policy: RetryPolicy,
 }
 
 impl ClientConfig {
@@ -55,7 +80,7 @@ impl ClientConfig {
             base_url: None,
             default_model: None,
             timeout_secs: 120,
-            max_retries: 3,
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -73,6 +98,11 @@ impl ClientConfig {
         self.timeout_secs = secs;
         self
     }
+
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
 }
 
 fn build_http_client(timeout_secs: u64) -> Client {
@@ -92,6 +122,49 @@ fn build_http_client(timeout_secs: u64) -> Client {
     }
 }
 
+/// Reject image input for models without vision support.
+fn check_vision(request: &CompletionRequest, spec: &ModelSpec) -> Result<()> {
+    if request.has_images() && !spec.supports_vision {
+        return Err(Error::LLM(format!(
+            "Model {} does not support image input; choose a vision-capable model",
+            spec.id
+        )));
+    }
+    Ok(())
+}
+
+/// Span around one provider completion.
+///
+/// `model` is the requested model, if any; the served model and usage are
+/// recorded by [`traced_completion`]. Nothing is allocated when no
+/// subscriber is interested in the span.
+fn completion_span(provider: Provider, model: Option<&str>) -> tracing::Span {
+    tracing::info_span!(
+        "llm.complete",
+        %provider,
+        model,
+        input_tokens = tracing::field::Empty,
+        output_tokens = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+    )
+}
+
+/// Run `completion` inside `span`, recording usage and duration on it.
+async fn traced_completion(
+    span: tracing::Span,
+    completion: impl Future<Output = Result<CompletionResponse>>,
+) -> Result<CompletionResponse> {
+    let started = Instant::now();
+    let result = completion.instrument(span.clone()).await;
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+    if let Ok(response) = &result {
+        span.record("model", response.model.as_str());
+        span.record("input_tokens", response.usage.input_tokens);
+        span.record("output_tokens", response.usage.output_tokens);
+    }
+    result
+}
+
 /// Anthropic Claude client.
 pub struct AnthropicClient {
     config: ClientConfig,
@@ -101,6 +174,8 @@ pub struct AnthropicClient {
 impl AnthropicClient {
     const DEFAULT_BASE_URL: &'static str = "https://api.anthropic.com";
     const API_VERSION: &'static str = "2023-06-01";
+    /// Tool forced when a structured response is requested.
+    const STRUCTURED_OUTPUT_TOOL: &'static str = "structured_output";
 
     pub fn new(config: ClientConfig) -> Self {
         let http = build_http_client(config.timeout_secs);
@@ -114,74 +189,18 @@ impl AnthropicClient {
             .as_deref()
             .unwrap_or(Self::DEFAULT_BASE_URL)
     }
-}
-
-// Anthropic API types
-#[derive(Debug, Serialize)]
-struct AnthropicRequest {
-    model: String,
-    messages: Vec<AnthropicMessage>,
-    max_tokens: u32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    temperature: Option<f64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    stop_sequences: Option<Vec<String>>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct AnthropicMessage {
-    role: String,
-    content: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct AnthropicResponse {
-    id: String,
-    model: String,
-    content: Vec<AnthropicContent>,
-    stop_reason: Option<String>,
-    usage: AnthropicUsage,
-}
-
-#[derive(Debug, Deserialize)]
-struct AnthropicContent {
-    #[serde(rename = "type")]
-    #[allow(dead_code)]
-    content_type: String,
-    text: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct AnthropicUsage {
-    input_tokens: u64,
-    output_tokens: u64,
-    #[serde(default)]
-    cache_read_input_tokens: Option<u64>,
-    #[serde(default)]
-    cache_creation_input_tokens: Option<u64>,
-}
-
-#[derive(Debug, Deserialize)]
-struct AnthropicError {
-    error: AnthropicErrorDetail,
-}
-
-#[derive(Debug, Deserialize)]
-struct AnthropicErrorDetail {
-    message: String,
-    #[serde(rename = "type")]
-    error_type: String,
-}
 
-#[async_trait]
-impl LLMClient for AnthropicClient {
-    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+    fn api_request(&self, mut request: CompletionRequest) -> Result<AnthropicRequest> {
         let model = request
             .model
+            .clone()
             .or(self.config.default_model.clone())
             .unwrap_or_else(|| "claude-3-5-sonnet-20241022".to_string());
+        check_vision(&request, &self.model_spec(&model))?;
+
+        // Breakpoint 0 is the end of the system prompt, marked below.
+        let cache_system = request.enable_caching
+            && apply_request_cache_markers(&mut request, MIN_CACHE_TOKENS).contains(&0);
 
         let messages: Vec<AnthropicMessage> = request
             .messages
@@ -192,19 +211,70 @@ impl LLMClient for AnthropicClient {
                     super::types::ChatRole::Assistant => "assistant".to_string(),
                     super::types::ChatRole::System => "user".to_string(), // System handled separately
                 },
-                content: m.content.clone(),
+                content: AnthropicMessageContent::from_message(m),
             })
             .collect();
 
-        let api_request = AnthropicRequest {
-            model: model.clone(),
+        let mut tools: Vec<AnthropicTool> = request
+            .tools
+            .into_iter()
+            .map(|t| AnthropicTool {
+                name: t.name,
+                description: t.description,
+                input_schema: t.parameters,
+            })
+            .collect();
+
+        // Anthropic has no JSON mode; force a tool whose input schema is the
+        // requested output schema and read the answer from its arguments.
+        let tool_choice = request.response_format.map(|format| {
+            tools.push(AnthropicTool {
+                name: Self::STRUCTURED_OUTPUT_TOOL.to_string(),
+                description: "Return the final answer as structured output.".to_string(),
+                input_schema: format.schema(),
+            });
+            AnthropicToolChoice {
+                choice_type: "tool",
+                name: Self::STRUCTURED_OUTPUT_TOOL,
+            }
+        });
+
+        Ok(AnthropicRequest {
+            model,
             messages,
             max_tokens: request.max_tokens.unwrap_or(4096),
-            system: request.system,
+            system: request.system.map(|text| {
+                if cache_system {
+                    AnthropicSystem::Blocks(vec![AnthropicContentBlock::Text {
+                        text,
+                        cache_control: Some(CacheControl::Ephemeral.into()),
+                    }])
+                } else {
+                    AnthropicSystem::Text(text)
+                }
+            }),
             temperature: request.temperature,
             stop_sequences: request.stop,
-        };
+            stream: None,
+            tools,
+            tool_choice,
+        })
+    }
+
+    /// Send a messages request, retrying transient failures.
+    ///
+    /// Returns the response and the number of retries it took.
+    async fn send(&self, api_request: &AnthropicRequest) -> Result<(reqwest::Response, u32)> {
+        self.config
+            .retry_policy
+            .run(|| self.send_once(api_request))
+            .await
+    }
 
+    async fn send_once(
+        &self,
+        api_request: &AnthropicRequest,
+    ) -> std::result::Result<reqwest::Response, AttemptError> {
         let url = format!("{}/v1/messages", self.base_url());
 
         let response = self
@@ -213,34 +283,46 @@ impl LLMClient for AnthropicClient {
             .header("x-api-key", &self.config.api_key)
             .header("anthropic-version", Self::API_VERSION)
             .header("content-type", "application/json")
-            .json(&api_request)
+            .json(api_request)
             .send()
             .await
-            .map_err(|e| Error::LLM(format!("HTTP request failed: {}", e)))?;
+            .map_err(AttemptError::from_transport)?;
 
         let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let retry_after = parse_retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        let message = match serde_json::from_str::<AnthropicError>(&body) {
+            Ok(error) => format!("{}: {}", error.error.error_type, error.error.message),
+            Err(_) => body,
+        };
+        let error = Error::llm_status(Provider::Anthropic.to_string(), status.as_u16(), message);
+        Err(self
+            .config
+            .retry_policy
+            .classify(status.as_u16(), retry_after, error))
+    }
+
+    /// Run a completion; [`LLMClient::complete`] wraps it in a span.
+    async fn send_completion(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let structured = request.response_format.is_some();
+        let api_request = self.api_request(request)?;
+        let model = api_request.model.clone();
+
+        let (response, retries) = self.send(&api_request).await?;
         let body = response
             .text()
             .await
             .map_err(|e| Error::LLM(format!("Failed to read response: {}", e)))?;
 
-        if !status.is_success() {
-            if let Ok(error) = serde_json::from_str::<AnthropicError>(&body) {
-                return Err(Error::LLM(format!(
-                    "Anthropic API error ({}): {}",
-                    error.error.error_type, error.error.message
-                )));
-            }
-            return Err(Error::LLM(format!(
-                "Anthropic API error ({}): {}",
-                status, body
-            )));
-        }
-
         let api_response: AnthropicResponse = serde_json::from_str(&body)
             .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
 
-        let content = api_response
+        let mut tool_calls = api_response.tool_calls();
+        let mut content = api_response
             .content
             .iter()
             .filter_map(|c| c.text.as_ref())
@@ -248,13 +330,23 @@ impl LLMClient for AnthropicClient {
             .collect::<Vec<_>>()
             .join("");
 
-        let stop_reason = api_response.stop_reason.as_deref().map(|r| match r {
-            "end_turn" => StopReason::EndTurn,
-            "max_tokens" => StopReason::MaxTokens,
-            "stop_sequence" => StopReason::StopSequence,
-            "tool_use" => StopReason::ToolUse,
-            _ => StopReason::EndTurn,
-        });
+        let mut stop_reason = api_response
+            .stop_reason
+            .as_deref()
+            .map(parse_anthropic_stop_reason);
+
+        // Surface the forced structured-output call as the JSON content.
+        if structured {
+            if let Some(pos) = tool_calls
+                .iter()
+                .position(|c| c.name == Self::STRUCTURED_OUTPUT_TOOL)
+            {
+                content = tool_calls.remove(pos).arguments.to_string();
+                if stop_reason == Some(StopReason::ToolUse) {
+                    stop_reason = Some(StopReason::EndTurn);
+                }
+            }
+        }
 
         let usage = TokenUsage {
             input_tokens: api_response.usage.input_tokens,
@@ -264,12 +356,9 @@ impl LLMClient for AnthropicClient {
         };
 
         // Calculate cost based on model
-        let model_spec = self
-            .available_models()
-            .into_iter()
-            .find(|m| m.id == model)
-            .unwrap_or_else(ModelSpec::claude_sonnet);
-        let cost = model_spec.calculate_cost(usage.input_tokens, usage.output_tokens);
+        let cost = self
+            .model_spec(&model)
+            .calculate_cost(usage.input_tokens, usage.output_tokens);
 
         Ok(CompletionResponse {
             id: api_response.id,
@@ -279,9 +368,250 @@ impl LLMClient for AnthropicClient {
             usage,
             timestamp: Utc::now(),
             cost: Some(cost),
+            retries,
+            tool_calls,
+            logprobs: None,
         })
     }
 
+    fn model_spec(&self, model: &str) -> ModelSpec {
+        self.available_models()
+            .into_iter()
+            .find(|m| m.id == model)
+            .unwrap_or_else(ModelSpec::claude_sonnet)
+    }
+}
+
+// Anthropic API types
+#[derive(Debug, Serialize)]
+struct AnthropicRequest {
+    model: String,
+    messages: Vec<AnthropicMessage>,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<AnthropicSystem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<AnthropicTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<AnthropicToolChoice>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicToolChoice {
+    #[serde(rename = "type")]
+    choice_type: &'static str,
+    name: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicTool {
+    name: String,
+    description: String,
+    input_schema: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicMessage {
+    role: String,
+    content: AnthropicMessageContent,
+}
+
+/// Plain text, or a text block when the system prompt is cached.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum AnthropicSystem {
+    Text(String),
+    Blocks(Vec<AnthropicContentBlock>),
+}
+
+/// Plain text, or content blocks when the message carries images or a
+/// cache marker.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum AnthropicMessageContent {
+    Text(String),
+    Blocks(Vec<AnthropicContentBlock>),
+}
+
+impl AnthropicMessageContent {
+    /// Images go before the text, as Anthropic recommends. A cache marker
+    /// goes on the last block, caching everything up to it.
+    fn from_message(message: &ChatMessage) -> Self {
+        if message.images.is_empty() && message.cache_control.is_none() {
+            return Self::Text(message.content.clone());
+        }
+        let mut blocks: Vec<AnthropicContentBlock> = message
+            .images
+            .iter()
+            .map(|image| AnthropicContentBlock::Image {
+                source: match &image.source {
+                    ImageSource::Base64 { data } => AnthropicImageSource::Base64 {
+                        media_type: image.media_type.clone(),
+                        data: data.clone(),
+                    },
+                    ImageSource::Url { url } => AnthropicImageSource::Url { url: url.clone() },
+                },
+                cache_control: None,
+            })
+            .collect();
+        if !message.content.is_empty() || blocks.is_empty() {
+            blocks.push(AnthropicContentBlock::Text {
+                text: message.content.clone(),
+                cache_control: None,
+            });
+        }
+        if let (Some(control), Some(last)) = (message.cache_control, blocks.last_mut()) {
+            match last {
+                AnthropicContentBlock::Text { cache_control, .. }
+                | AnthropicContentBlock::Image { cache_control, .. } => {
+                    *cache_control = Some(control.into())
+                }
+            }
+        }
+        Self::Blocks(blocks)
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicContentBlock {
+    Text {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<AnthropicCacheControl>,
+    },
+    Image {
+        source: AnthropicImageSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<AnthropicCacheControl>,
+    },
+}
+
+/// `{"type": "ephemeral"}`
+#[derive(Debug, Serialize)]
+struct AnthropicCacheControl {
+    #[serde(rename = "type")]
+    control_type: &'static str,
+}
+
+impl From<CacheControl> for AnthropicCacheControl {
+    fn from(control: CacheControl) -> Self {
+        match control {
+            CacheControl::Ephemeral => Self {
+                control_type: "ephemeral",
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicImageSource {
+    Base64 { media_type: String, data: String },
+    Url { url: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    id: String,
+    model: String,
+    content: Vec<AnthropicContent>,
+    stop_reason: Option<String>,
+    usage: AnthropicUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContent {
+    #[serde(rename = "type")]
+    content_type: String,
+    text: Option<String>,
+    /// Tool use ID (tool_use blocks)
+    id: Option<String>,
+    /// Tool name (tool_use blocks)
+    name: Option<String>,
+    /// Tool input (tool_use blocks)
+    input: Option<serde_json::Value>,
+}
+
+impl AnthropicResponse {
+    /// Collect `tool_use` content blocks in order.
+    fn tool_calls(&self) -> Vec<ToolCall> {
+        self.content
+            .iter()
+            .filter(|c| c.content_type == "tool_use")
+            .map(|c| ToolCall {
+                id: c.id.clone().unwrap_or_default(),
+                name: c.name.clone().unwrap_or_default(),
+                arguments: c
+                    .input
+                    .clone()
+                    .unwrap_or_else(|| serde_json::Value::Object(Default::default())),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicError {
+    error: AnthropicErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicErrorDetail {
+    message: String,
+    #[serde(rename = "type")]
+    error_type: String,
+}
+
+#[async_trait]
+impl LLMClient for AnthropicClient {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let span = completion_span(
+            Provider::Anthropic,
+            request
+                .model
+                .as_deref()
+                .or(self.config.default_model.as_deref()),
+        );
+        traced_completion(span, self.send_completion(request)).await
+    }
+
+    async fn complete_stream(&self, mut request: CompletionRequest) -> Result<CompletionStream> {
+        // A forced tool call would stream as argument deltas rather than
+        // text, so streamed structured output relies on the prompt instead.
+        if let Some(format) = request.response_format.take() {
+            request.system = Some(format.apply_to_system(request.system));
+        }
+        let mut api_request = self.api_request(request)?;
+        api_request.stream = Some(true);
+
+        let (response, retries) = self.send(&api_request).await?;
+        let handler = AnthropicStreamHandler::new(
+            api_request.model.clone(),
+            self.model_spec(&api_request.model),
+        );
+        Ok(with_retries(
+            sse_stream(response_bytes(response), handler),
+            retries,
+        ))
+    }
+
     async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
         // Anthropic doesn't have a native embedding API
         // In production, this would use a partner service or Voyage AI
@@ -324,25 +654,305 @@ impl OpenAIClient {
             .as_deref()
             .unwrap_or(Self::DEFAULT_BASE_URL)
     }
-}
 
-// OpenAI API types
-#[derive(Debug, Serialize)]
-struct OpenAIRequest {
-    model: String,
-    messages: Vec<OpenAIMessage>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    max_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    temperature: Option<f64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    stop: Option<Vec<String>>,
-}
+    fn api_request(&self, request: CompletionRequest) -> Result<OpenAIRequest> {
+        let model = request
+            .model
+            .clone()
+            .or(self.config.default_model.clone())
+            .unwrap_or_else(|| "gpt-4o".to_string());
+        check_vision(&request, &self.model_spec(&model))?;
 
-#[derive(Debug, Serialize, Deserialize)]
+        let mut messages: Vec<OpenAIMessage> = Vec::new();
+
+        // Add system message if present
+        if let Some(system) = &request.system {
+            messages.push(OpenAIMessage {
+                role: "system".to_string(),
+                content: OpenAIMessageContent::Text(system.clone()),
+            });
+        }
+
+        // Add conversation messages
+        for m in &request.messages {
+            messages.push(OpenAIMessage {
+                role: match m.role {
+                    super::types::ChatRole::User => "user".to_string(),
+                    super::types::ChatRole::Assistant => "assistant".to_string(),
+                    super::types::ChatRole::System => "system".to_string(),
+                },
+                content: OpenAIMessageContent::from_message(m),
+            });
+        }
+
+        Ok(OpenAIRequest {
+            model,
+            messages,
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            stop: request.stop,
+            stream: None,
+            stream_options: None,
+            tools: request.tools.into_iter().map(OpenAITool::from).collect(),
+            response_format: request.response_format.map(OpenAIResponseFormat::from),
+            logprobs: request.top_logprobs.map(|_| true),
+            top_logprobs: request.top_logprobs,
+        })
+    }
+
+    /// Send a request to `path`, retrying transient failures.
+    ///
+    /// Returns the response and the number of retries it took.
+    async fn send<T: Serialize>(
+        &self,
+        path: &str,
+        api_request: &T,
+    ) -> Result<(reqwest::Response, u32)> {
+        self.config
+            .retry_policy
+            .run(|| self.send_once(path, api_request))
+            .await
+    }
+
+    async fn send_once<T: Serialize>(
+        &self,
+        path: &str,
+        api_request: &T,
+    ) -> std::result::Result<reqwest::Response, AttemptError> {
+        let url = format!("{}{}", self.base_url(), path);
+
+        let response = self
+            .http
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("content-type", "application/json")
+            .json(api_request)
+            .send()
+            .await
+            .map_err(AttemptError::from_transport)?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let retry_after = parse_retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        let message = match serde_json::from_str::<OpenAIError>(&body) {
+            Ok(error) => error.error.message,
+            Err(_) => body,
+        };
+        let error = Error::llm_status(Provider::OpenAI.to_string(), status.as_u16(), message);
+        Err(self
+            .config
+            .retry_policy
+            .classify(status.as_u16(), retry_after, error))
+    }
+
+    /// Run a completion; [`LLMClient::complete`] wraps it in a span.
+    async fn send_completion(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let api_request = self.api_request(request)?;
+        let model = api_request.model.clone();
+
+        let (response, retries) = self.send("/v1/chat/completions", &api_request).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::LLM(format!("Failed to read response: {}", e)))?;
+
+        let api_response: OpenAIResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
+
+        let choice = api_response
+            .choices
+            .first()
+            .ok_or_else(|| Error::LLM("No choices in response".to_string()))?;
+
+        let stop_reason = choice
+            .finish_reason
+            .as_deref()
+            .map(parse_openai_stop_reason);
+
+        let usage = TokenUsage {
+            input_tokens: api_response.usage.prompt_tokens,
+            output_tokens: api_response.usage.completion_tokens,
+            cache_read_tokens: None,
+            cache_creation_tokens: None,
+        };
+
+        // Calculate cost based on model
+        let cost = self
+            .model_spec(&model)
+            .calculate_cost(usage.input_tokens, usage.output_tokens);
+
+        Ok(CompletionResponse {
+            id: api_response.id,
+            model: api_response.model,
+            content: choice.message.content.clone().unwrap_or_default(),
+            stop_reason,
+            usage,
+            timestamp: Utc::now(),
+            cost: Some(cost),
+            retries,
+            tool_calls: choice.message.tool_calls(),
+            logprobs: choice.logprobs.as_ref().and_then(|l| l.content.clone()),
+        })
+    }
+
+    fn model_spec(&self, model: &str) -> ModelSpec {
+        self.available_models()
+            .into_iter()
+            .find(|m| m.id == model || model.starts_with(&m.id))
+            .unwrap_or_else(ModelSpec::gpt4o)
+    }
+
+    /// Cost of embedding `tokens` with `model`, or `None` if it has no known price.
+    fn embedding_cost(model: &str, tokens: u64) -> Option<f64> {
+        [
+            ModelSpec::text_embedding_3_small(),
+            ModelSpec::text_embedding_3_large(),
+            ModelSpec::text_embedding_ada_002(),
+        ]
+        .into_iter()
+        .find(|m| m.id == model || model.starts_with(&m.id))
+        .map(|m| m.calculate_embedding_cost(tokens))
+    }
+}
+
+// OpenAI API types
+#[derive(Debug, Serialize)]
+struct OpenAIRequest {
+    model: String,
+    messages: Vec<OpenAIMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<OpenAIStreamOptions>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<OpenAITool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<OpenAIResponseFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logprobs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_logprobs: Option<u8>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OpenAIResponseFormat {
+    JsonObject,
+    JsonSchema { json_schema: OpenAIJsonSchema },
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAIJsonSchema {
+    name: &'static str,
+    schema: serde_json::Value,
+    /// Strict mode rejects schemas with optional properties, which
+    /// signatures commonly have.
+    strict: bool,
+}
+
+impl From<ResponseFormat> for OpenAIResponseFormat {
+    fn from(format: ResponseFormat) -> Self {
+        match format {
+            ResponseFormat::JsonObject => Self::JsonObject,
+            ResponseFormat::JsonSchema { schema } => Self::JsonSchema {
+                json_schema: OpenAIJsonSchema {
+                    name: "output",
+                    schema,
+                    strict: false,
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAITool {
+    #[serde(rename = "type")]
+    tool_type: &'static str,
+    function: OpenAIFunction,
+}
+
+impl From<ToolDef> for OpenAITool {
+    fn from(tool: ToolDef) -> Self {
+        Self {
+            tool_type: "function",
+            function: OpenAIFunction {
+                name: tool.name,
+                description: tool.description,
+                parameters: tool.parameters,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAIFunction {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAIStreamOptions {
+    include_usage: bool,
+}
+
+#[derive(Debug, Serialize)]
 struct OpenAIMessage {
     role: String,
-    content: String,
+    content: OpenAIMessageContent,
+}
+
+/// Plain text, or content parts when the message carries images.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum OpenAIMessageContent {
+    Text(String),
+    Parts(Vec<OpenAIContentPart>),
+}
+
+impl OpenAIMessageContent {
+    fn from_message(message: &ChatMessage) -> Self {
+        if message.images.is_empty() {
+            return Self::Text(message.content.clone());
+        }
+        let mut parts = vec![OpenAIContentPart::Text {
+            text: message.content.clone(),
+        }];
+        parts.extend(
+            message
+                .images
+                .iter()
+                .map(|image| OpenAIContentPart::ImageUrl {
+                    image_url: OpenAIImageUrl {
+                        url: image.to_url(),
+                    },
+                }),
+        );
+        Self::Parts(parts)
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OpenAIContentPart {
+    Text { text: String },
+    ImageUrl { image_url: OpenAIImageUrl },
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAIImageUrl {
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -355,8 +965,46 @@ struct OpenAIResponse {
 
 #[derive(Debug, Deserialize)]
 struct OpenAIChoice {
-    message: OpenAIMessage,
+    message: OpenAIResponseMessage,
     finish_reason: Option<String>,
+    #[serde(default)]
+    logprobs: Option<OpenAILogprobs>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAILogprobs {
+    /// Null when the response is a pure tool call
+    content: Option<Vec<TokenLogprob>>,
+}
+
+/// Assistant message in a response; `content` is null for pure tool calls.
+#[derive(Debug, Deserialize)]
+struct OpenAIResponseMessage {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<OpenAIToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIToolCall {
+    id: String,
+    function: OpenAIFunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIFunctionCall {
+    name: String,
+    /// JSON-encoded arguments
+    arguments: String,
+}
+
+impl OpenAIResponseMessage {
+    fn tool_calls(&self) -> Vec<ToolCall> {
+        self.tool_calls
+            .iter()
+            .map(|c| ToolCall::from_raw_arguments(&c.id, &c.function.name, &c.function.arguments))
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -407,129 +1055,588 @@ struct OpenAIEmbeddingUsage {
 #[async_trait]
 impl LLMClient for OpenAIClient {
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let span = completion_span(
+            Provider::OpenAI,
+            request
+                .model
+                .as_deref()
+                .or(self.config.default_model.as_deref()),
+        );
+        traced_completion(span, self.send_completion(request)).await
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<CompletionStream> {
+        let mut api_request = self.api_request(request)?;
+        api_request.stream = Some(true);
+        api_request.stream_options = Some(OpenAIStreamOptions {
+            include_usage: true,
+        });
+
+        let (response, retries) = self.send("/v1/chat/completions", &api_request).await?;
+        let handler = OpenAIStreamHandler::new(
+            api_request.model.clone(),
+            self.model_spec(&api_request.model),
+        );
+        Ok(with_retries(
+            sse_stream(response_bytes(response), handler),
+            retries,
+        ))
+    }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
         let model = request
             .model
+            .unwrap_or_else(|| "text-embedding-3-small".to_string());
+
+        let api_request = OpenAIEmbeddingRequest {
+            model: model.clone(),
+            input: request.texts,
+        };
+
+        let (response, _) = self.send("/v1/embeddings", &api_request).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::LLM(format!("Failed to read response: {}", e)))?;
+
+        let api_response: OpenAIEmbeddingResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
+
+        let embeddings = api_response.data.into_iter().map(|d| d.embedding).collect();
+        let cost = Self::embedding_cost(&api_response.model, api_response.usage.prompt_tokens);
+        if cost.is_none() {
+            tracing::warn!(model = %api_response.model, "no embedding price for model; cost not recorded");
+        }
+
+        Ok(EmbeddingResponse {
+            model: api_response.model,
+            embeddings,
+            usage: TokenUsage {
+                input_tokens: api_response.usage.prompt_tokens,
+                output_tokens: 0,
+                cache_read_tokens: None,
+                cache_creation_tokens: None,
+            },
+            cost,
+        })
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::OpenAI
+    }
+
+    fn available_models(&self) -> Vec<ModelSpec> {
+        vec![ModelSpec::gpt4o(), ModelSpec::gpt4o_mini()]
+    }
+
+    fn supports_logprobs(&self) -> bool {
+        true
+    }
+}
+
+/// Ollama client for locally served models.
+///
+/// Talks to Ollama's native `/api/chat` endpoint. The API key is only sent
+/// when set, for instances behind an authenticating proxy.
+pub struct OllamaClient {
+    config: ClientConfig,
+    http: Client,
+    /// Pulled models that accept images, beyond the built-in specs
+    vision_models: Vec<String>,
+}
+
+impl OllamaClient {
+    const DEFAULT_BASE_URL: &'static str = "http://localhost:11434";
+
+    pub fn new(config: ClientConfig) -> Self {
+        let http = build_http_client(config.timeout_secs);
+
+        Self {
+            config,
+            http,
+            vision_models: Vec::new(),
+        }
+    }
+
+    /// Client for an Ollama instance on the default local port.
+    pub fn local() -> Self {
+        Self::new(ClientConfig::new(""))
+    }
+
+    /// Declare models that accept image input, e.g. `llava:13b`.
+    ///
+    /// Requests with images are rejected for any other model unless its
+    /// built-in spec supports vision.
+    pub fn with_vision_models<I, S>(mut self, models: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.vision_models
+            .extend(models.into_iter().map(Into::into));
+        self
+    }
+
+    fn base_url(&self) -> &str {
+        self.config
+            .base_url
+            .as_deref()
+            .unwrap_or(Self::DEFAULT_BASE_URL)
+    }
+
+    fn api_request(&self, request: CompletionRequest) -> Result<OllamaRequest> {
+        let model = request
+            .model
+            .clone()
             .or(self.config.default_model.clone())
-            .unwrap_or_else(|| "gpt-4o".to_string());
+            .unwrap_or_else(|| ModelSpec::llama3_1_8b().id);
+        check_vision(&request, &self.model_spec(&model))?;
 
-        let mut messages: Vec<OpenAIMessage> = Vec::new();
+        let mut messages: Vec<OllamaMessage> = Vec::new();
 
-        // Add system message if present
         if let Some(system) = &request.system {
-            messages.push(OpenAIMessage {
+            messages.push(OllamaMessage {
                 role: "system".to_string(),
                 content: system.clone(),
+                images: Vec::new(),
             });
         }
 
-        // Add conversation messages
         for m in &request.messages {
-            messages.push(OpenAIMessage {
+            messages.push(OllamaMessage {
                 role: match m.role {
                     super::types::ChatRole::User => "user".to_string(),
                     super::types::ChatRole::Assistant => "assistant".to_string(),
                     super::types::ChatRole::System => "system".to_string(),
                 },
                 content: m.content.clone(),
+                images: m
+                    .images
+                    .iter()
+                    .map(|image| match &image.source {
+                        ImageSource::Base64 { data } => Ok(data.clone()),
+                        ImageSource::Url { .. } => Err(Error::LLM(
+                            "Ollama only accepts base64 images, not URLs".to_string(),
+                        )),
+                    })
+                    .collect::<Result<_>>()?,
             });
         }
 
-        let api_request = OpenAIRequest {
-            model: model.clone(),
+        Ok(OllamaRequest {
+            model,
             messages,
-            max_tokens: request.max_tokens,
-            temperature: request.temperature,
-            stop: request.stop,
-        };
+            stream: false,
+            options: OllamaOptions {
+                temperature: request.temperature,
+                num_predict: request.max_tokens,
+                stop: request.stop,
+            },
+            // Ollama accepts tools in the OpenAI format.
+            tools: request.tools.into_iter().map(OpenAITool::from).collect(),
+            format: request.response_format.map(|format| match format {
+                ResponseFormat::JsonObject => serde_json::Value::String("json".to_string()),
+                ResponseFormat::JsonSchema { schema } => schema,
+            }),
+        })
+    }
 
-        let url = format!("{}/v1/chat/completions", self.base_url());
+    /// Send a request to `path`, retrying transient failures.
+    ///
+    /// Returns the response and the number of retries it took.
+    async fn send<T: Serialize>(
+        &self,
+        path: &str,
+        api_request: &T,
+    ) -> Result<(reqwest::Response, u32)> {
+        self.config
+            .retry_policy
+            .run(|| self.send_once(path, api_request))
+            .await
+    }
 
-        let response = self
+    async fn send_once<T: Serialize>(
+        &self,
+        path: &str,
+        api_request: &T,
+    ) -> std::result::Result<reqwest::Response, AttemptError> {
+        let url = format!("{}{}", self.base_url(), path);
+
+        let mut builder = self
             .http
             .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("content-type", "application/json")
-            .json(&api_request)
+            .header("content-type", "application/json");
+        if !self.config.api_key.is_empty() {
+            builder = builder.header("Authorization", format!("Bearer {}", self.config.api_key));
+        }
+
+        let response = builder
+            .json(api_request)
             .send()
             .await
-            .map_err(|e| Error::LLM(format!("HTTP request failed: {}", e)))?;
+            .map_err(AttemptError::from_transport)?;
 
         let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let retry_after = parse_retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        let message = match serde_json::from_str::<OllamaError>(&body) {
+            Ok(error) => error.error,
+            Err(_) => body,
+        };
+        let error = Error::llm_status(Provider::Ollama.to_string(), status.as_u16(), message);
+        Err(self
+            .config
+            .retry_policy
+            .classify(status.as_u16(), retry_after, error))
+    }
+
+    /// Run a completion; [`LLMClient::complete`] wraps it in a span.
+    async fn send_completion(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let api_request = self.api_request(request)?;
+        let spec = self.model_spec(&api_request.model);
+
+        let (response, retries) = self.send("/api/chat", &api_request).await?;
         let body = response
             .text()
             .await
             .map_err(|e| Error::LLM(format!("Failed to read response: {}", e)))?;
 
-        if !status.is_success() {
-            if let Ok(error) = serde_json::from_str::<OpenAIError>(&body) {
-                return Err(Error::LLM(format!(
-                    "OpenAI API error: {}",
-                    error.error.message
-                )));
-            }
-            return Err(Error::LLM(format!(
-                "OpenAI API error ({}): {}",
-                status, body
-            )));
+        let api_response: OllamaChatResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
+        if let Some(error) = api_response.error {
+            return Err(Error::LLM(format!("Ollama API error: {}", error)));
         }
 
-        let api_response: OpenAIResponse = serde_json::from_str(&body)
+        let mut response = api_response.into_completion(&spec);
+        response.retries = retries;
+        Ok(response)
+    }
+
+    /// Spec for `model`, treating unknown local models as fast-tier.
+    fn model_spec(&self, model: &str) -> ModelSpec {
+        let mut spec = self
+            .available_models()
+            .into_iter()
+            .find(|m| m.id == model)
+            .unwrap_or_else(|| ModelSpec::ollama(model, ModelTier::Fast));
+        if self.vision_models.iter().any(|m| m == model) {
+            spec.supports_vision = true;
+        }
+        spec
+    }
+}
+
+// Ollama API types
+#[derive(Debug, Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<OllamaMessage>,
+    stream: bool,
+    options: OllamaOptions,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<OpenAITool>,
+    /// `"json"` or a JSON schema
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaMessage {
+    role: String,
+    content: String,
+    /// Base64-encoded images
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    /// Maximum tokens to generate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaError {
+    error: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaEmbeddingRequest {
+    model: String,
+    input: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbeddingResponse {
+    model: String,
+    embeddings: Vec<Vec<f32>>,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+}
+
+impl OllamaChatResponse {
+    fn into_completion(self, spec: &ModelSpec) -> CompletionResponse {
+        let usage = self.usage();
+        let cost = spec.calculate_cost(usage.input_tokens, usage.output_tokens);
+        let message = self.message.unwrap_or_default();
+        let tool_calls: Vec<ToolCall> = message
+            .tool_calls
+            .into_iter()
+            .enumerate()
+            .map(|(i, call)| ToolCall {
+                id: ollama_tool_call_id(i),
+                name: call.function.name,
+                arguments: call.function.arguments,
+            })
+            .collect();
+        let stop_reason = parse_ollama_stop_reason(
+            self.done_reason.as_deref().unwrap_or("stop"),
+            !tool_calls.is_empty(),
+        );
+
+        CompletionResponse {
+            id: format!("ollama-{}", uuid::Uuid::new_v4()),
+            model: self.model,
+            content: message.content,
+            stop_reason: Some(stop_reason),
+            usage,
+            timestamp: Utc::now(),
+            cost: Some(cost),
+            retries: 0,
+            tool_calls,
+            logprobs: None,
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for OllamaClient {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let span = completion_span(
+            Provider::Ollama,
+            request
+                .model
+                .as_deref()
+                .or(self.config.default_model.as_deref()),
+        );
+        traced_completion(span, self.send_completion(request)).await
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<CompletionStream> {
+        let mut api_request = self.api_request(request)?;
+        api_request.stream = true;
+
+        let (response, retries) = self.send("/api/chat", &api_request).await?;
+        let handler = OllamaStreamHandler::new(
+            api_request.model.clone(),
+            self.model_spec(&api_request.model),
+        );
+        Ok(with_retries(
+            ndjson_stream(response_bytes(response), handler),
+            retries,
+        ))
+    }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        let model = request
+            .model
+            .unwrap_or_else(|| "nomic-embed-text".to_string());
+
+        let api_request = OllamaEmbeddingRequest {
+            model,
+            input: request.texts,
+        };
+
+        let (response, _) = self.send("/api/embed", &api_request).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::LLM(format!("Failed to read response: {}", e)))?;
+
+        let api_response: OllamaEmbeddingResponse = serde_json::from_str(&body)
             .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
 
-        let choice = api_response
-            .choices
+        Ok(EmbeddingResponse {
+            model: api_response.model,
+            embeddings: api_response.embeddings,
+            usage: TokenUsage {
+                input_tokens: api_response.prompt_eval_count.unwrap_or(0),
+                output_tokens: 0,
+                cache_read_tokens: None,
+                cache_creation_tokens: None,
+            },
+            cost: Some(0.0),
+        })
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Ollama
+    }
+
+    fn available_models(&self) -> Vec<ModelSpec> {
+        vec![
+            ModelSpec::llama3_1_8b(),
+            ModelSpec::qwen2_5_32b(),
+            ModelSpec::llama3_2_vision_11b(),
+        ]
+    }
+}
+
+/// Google Gemini client.
+#[cfg(feature = "gemini")]
+pub struct GoogleClient {
+    config: ClientConfig,
+    http: Client,
+}
+
+#[cfg(feature = "gemini")]
+impl GoogleClient {
+    const DEFAULT_BASE_URL: &'static str = "https://generativelanguage.googleapis.com";
+
+    pub fn new(config: ClientConfig) -> Self {
+        let http = build_http_client(config.timeout_secs);
+
+        Self { config, http }
+    }
+
+    fn base_url(&self) -> &str {
+        self.config
+            .base_url
+            .as_deref()
+            .unwrap_or(Self::DEFAULT_BASE_URL)
+    }
+
+    /// Map a `generateContent` response body to a [`CompletionResponse`].
+    ///
+    /// Thinking tokens are billed as output, so they count toward
+    /// `output_tokens`.
+    fn parse_response(model: String, body: &str) -> Result<CompletionResponse> {
+        let api_response: GeminiResponse = serde_json::from_str(body)
+            .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
+
+        let candidate = api_response
+            .candidates
             .first()
-            .ok_or_else(|| Error::LLM("No choices in response".to_string()))?;
+            .ok_or_else(|| Error::LLM("No candidates in response".to_string()))?;
+
+        let content = candidate
+            .content
+            .parts
+            .iter()
+            .map(|p| p.text.clone())
+            .collect::<Vec<_>>()
+            .join("");
 
-        let stop_reason = choice.finish_reason.as_deref().map(|r| match r {
-            "stop" => StopReason::EndTurn,
-            "length" => StopReason::MaxTokens,
-            "tool_calls" => StopReason::ToolUse,
+        let stop_reason = candidate.finish_reason.as_deref().map(|r| match r {
+            "STOP" => StopReason::EndTurn,
+            "MAX_TOKENS" => StopReason::MaxTokens,
+            "STOP_SEQUENCE" => StopReason::StopSequence,
             _ => StopReason::EndTurn,
         });
 
+        let usage_metadata = api_response.usage_metadata.unwrap_or_default();
         let usage = TokenUsage {
-            input_tokens: api_response.usage.prompt_tokens,
-            output_tokens: api_response.usage.completion_tokens,
-            cache_read_tokens: None,
+            input_tokens: usage_metadata.prompt_token_count,
+            output_tokens: usage_metadata.candidates_token_count.unwrap_or(0)
+                + usage_metadata.thoughts_token_count.unwrap_or(0),
+            cache_read_tokens: usage_metadata.cached_content_token_count,
             cache_creation_tokens: None,
         };
 
-        // Calculate cost based on model
-        let model_spec = self
-            .available_models()
-            .into_iter()
-            .find(|m| m.id == model || model.starts_with(&m.id))
-            .unwrap_or_else(ModelSpec::gpt4o);
-        let cost = model_spec.calculate_cost(usage.input_tokens, usage.output_tokens);
+        let cost = Self::model_spec(&model).calculate_cost(usage.input_tokens, usage.output_tokens);
+
+        // Generate a unique ID since Gemini doesn't return one
+        let id = format!("gemini-{}", Utc::now().timestamp_millis());
 
         Ok(CompletionResponse {
-            id: api_response.id,
-            model: api_response.model,
-            content: choice.message.content.clone(),
+            id,
+            model,
+            content,
             stop_reason,
             usage,
             timestamp: Utc::now(),
             cost: Some(cost),
+            retries: 0,
+            tool_calls: Vec::new(),
+            logprobs: None,
         })
     }
 
-    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+    /// Run a completion; [`LLMClient::complete`] wraps it in a span.
+    async fn send_completion(&self, request: CompletionRequest) -> Result<CompletionResponse> {
         let model = request
             .model
-            .unwrap_or_else(|| "text-embedding-3-small".to_string());
+            .clone()
+            .or(self.config.default_model.clone())
+            .unwrap_or_else(|| "gemini-2.0-flash".to_string());
+        check_vision(&request, &Self::model_spec(&model))?;
 
-        let api_request = OpenAIEmbeddingRequest {
-            model: model.clone(),
-            input: request.texts,
+        // Build contents from messages
+        let contents: Vec<GeminiContent> = request
+            .messages
+            .iter()
+            .map(|m| GeminiContent {
+                role: match m.role {
+                    super::types::ChatRole::User => "user".to_string(),
+                    super::types::ChatRole::Assistant => "model".to_string(),
+                    super::types::ChatRole::System => "user".to_string(), // Handled separately
+                },
+                parts: std::iter::once(GeminiPart::text(m.content.clone()))
+                    .chain(m.images.iter().map(GeminiPart::image))
+                    .collect(),
+            })
+            .collect();
+
+        // Gemini's response schema is an OpenAPI subset that rejects many
+        // JSON schemas, so only the MIME type is constrained natively and
+        // the schema itself goes in the prompt.
+        let response_mime_type = request
+            .response_format
+            .as_ref()
+            .map(|_| "application/json".to_string());
+        let system = match &request.response_format {
+            Some(format) => Some(format.apply_to_system(request.system)),
+            None => request.system,
+        };
+
+        // System instruction (Gemini's equivalent of system prompt)
+        let system_instruction = system.map(|s| GeminiContent {
+            role: "user".to_string(),
+            parts: vec![GeminiPart::text(s)],
+        });
+
+        let generation_config = Some(GeminiGenerationConfig {
+            max_output_tokens: request.max_tokens,
+            temperature: request.temperature,
+            stop_sequences: request.stop,
+            response_mime_type,
+        });
+
+        let api_request = GeminiRequest {
+            contents,
+            system_instruction,
+            generation_config,
         };
 
-        let url = format!("{}/v1/embeddings", self.base_url());
+        let url = format!(
+            "{}/v1beta/models/{}:generateContent?key={}",
+            self.base_url(),
+            model,
+            self.config.api_key
+        );
 
         let response = self
             .http
             .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
             .header("content-type", "application/json")
             .json(&api_request)
             .send()
@@ -543,66 +1650,34 @@ impl LLMClient for OpenAIClient {
             .map_err(|e| Error::LLM(format!("Failed to read response: {}", e)))?;
 
         if !status.is_success() {
-            if let Ok(error) = serde_json::from_str::<OpenAIError>(&body) {
-                return Err(Error::LLM(format!(
-                    "OpenAI API error: {}",
-                    error.error.message
-                )));
-            }
-            return Err(Error::LLM(format!(
-                "OpenAI API error ({}): {}",
-                status, body
-            )));
+            let message = match serde_json::from_str::<GeminiError>(&body) {
+                Ok(error) => error.error.message,
+                Err(_) => body,
+            };
+            return Err(Error::llm_status(
+                Provider::Google.to_string(),
+                status.as_u16(),
+                message,
+            ));
         }
 
-        let api_response: OpenAIEmbeddingResponse = serde_json::from_str(&body)
-            .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
-
-        let embeddings = api_response.data.into_iter().map(|d| d.embedding).collect();
-
-        Ok(EmbeddingResponse {
-            model: api_response.model,
-            embeddings,
-            usage: TokenUsage {
-                input_tokens: api_response.usage.prompt_tokens,
-                output_tokens: 0,
-                cache_read_tokens: None,
-                cache_creation_tokens: None,
-            },
-        })
-    }
-
-    fn provider(&self) -> Provider {
-        Provider::OpenAI
-    }
-
-    fn available_models(&self) -> Vec<ModelSpec> {
-        vec![ModelSpec::gpt4o(), ModelSpec::gpt4o_mini()]
+        Self::parse_response(model, &body)
     }
-}
-
-/// Google Gemini client.
-#[cfg(feature = "gemini")]
-pub struct GoogleClient {
-    config: ClientConfig,
-    http: Client,
-}
 
-#[cfg(feature = "gemini")]
-impl GoogleClient {
-    const DEFAULT_BASE_URL: &'static str = "https://generativelanguage.googleapis.com";
-
-    pub fn new(config: ClientConfig) -> Self {
-        let http = build_http_client(config.timeout_secs);
-
-        Self { config, http }
+    /// Spec for a Gemini model id, matching versioned ids by prefix.
+    fn model_spec(model: &str) -> ModelSpec {
+        Self::models()
+            .into_iter()
+            .find(|m| m.id == model || model.starts_with(&m.id))
+            .unwrap_or_else(ModelSpec::gemini_2_0_flash)
     }
 
-    fn base_url(&self) -> &str {
-        self.config
-            .base_url
-            .as_deref()
-            .unwrap_or(Self::DEFAULT_BASE_URL)
+    fn models() -> Vec<ModelSpec> {
+        vec![
+            ModelSpec::gemini_2_0_flash(),
+            ModelSpec::gemini_1_5_pro(),
+            ModelSpec::gemini_1_5_flash(),
+        ]
     }
 }
 
@@ -626,9 +1701,61 @@ struct GeminiContent {
 }
 
 #[cfg(feature = "gemini")]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct GeminiPart {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    inline_data: Option<GeminiInlineData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file_data: Option<GeminiFileData>,
+}
+
+#[cfg(feature = "gemini")]
+impl GeminiPart {
+    fn text(text: String) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
+    }
+
+    fn image(image: &super::types::ImagePart) -> Self {
+        let mime_type = image.media_type.clone();
+        match &image.source {
+            ImageSource::Base64 { data } => Self {
+                inline_data: Some(GeminiInlineData {
+                    mime_type,
+                    data: data.clone(),
+                }),
+                ..Default::default()
+            },
+            ImageSource::Url { url } => Self {
+                file_data: Some(GeminiFileData {
+                    mime_type,
+                    file_uri: url.clone(),
+                }),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "gemini")]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiInlineData {
+    mime_type: String,
+    data: String,
+}
+
+#[cfg(feature = "gemini")]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiFileData {
+    mime_type: String,
+    file_uri: String,
 }
 
 #[cfg(feature = "gemini")]
@@ -641,6 +1768,8 @@ struct GeminiGenerationConfig {
     temperature: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
 }
 
 #[cfg(feature = "gemini")]
@@ -660,11 +1789,13 @@ struct GeminiCandidate {
 }
 
 #[cfg(feature = "gemini")]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GeminiUsageMetadata {
+    #[serde(default)]
     prompt_token_count: u64,
     candidates_token_count: Option<u64>,
+    thoughts_token_count: Option<u64>,
     #[allow(dead_code)]
     total_token_count: Option<u64>,
     cached_content_token_count: Option<u64>,
@@ -688,137 +1819,14 @@ struct GeminiErrorDetail {
 #[async_trait]
 impl LLMClient for GoogleClient {
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
-        let model = request
-            .model
-            .or(self.config.default_model.clone())
-            .unwrap_or_else(|| "gemini-2.0-flash".to_string());
-
-        // Build contents from messages
-        let contents: Vec<GeminiContent> = request
-            .messages
-            .iter()
-            .map(|m| GeminiContent {
-                role: match m.role {
-                    super::types::ChatRole::User => "user".to_string(),
-                    super::types::ChatRole::Assistant => "model".to_string(),
-                    super::types::ChatRole::System => "user".to_string(), // Handled separately
-                },
-                parts: vec![GeminiPart {
-                    text: m.content.clone(),
-                }],
-            })
-            .collect();
-
-        // System instruction (Gemini's equivalent of system prompt)
-        let system_instruction = request.system.map(|s| GeminiContent {
-            role: "user".to_string(),
-            parts: vec![GeminiPart { text: s }],
-        });
-
-        let generation_config = Some(GeminiGenerationConfig {
-            max_output_tokens: request.max_tokens,
-            temperature: request.temperature,
-            stop_sequences: request.stop,
-        });
-
-        let api_request = GeminiRequest {
-            contents,
-            system_instruction,
-            generation_config,
-        };
-
-        let url = format!(
-            "{}/v1beta/models/{}:generateContent?key={}",
-            self.base_url(),
-            model,
-            self.config.api_key
+        let span = completion_span(
+            Provider::Google,
+            request
+                .model
+                .as_deref()
+                .or(self.config.default_model.as_deref()),
         );
-
-        let response = self
-            .http
-            .post(&url)
-            .header("content-type", "application/json")
-            .json(&api_request)
-            .send()
-            .await
-            .map_err(|e| Error::LLM(format!("HTTP request failed: {}", e)))?;
-
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .map_err(|e| Error::LLM(format!("Failed to read response: {}", e)))?;
-
-        if !status.is_success() {
-            if let Ok(error) = serde_json::from_str::<GeminiError>(&body) {
-                return Err(Error::LLM(format!(
-                    "Gemini API error: {}",
-                    error.error.message
-                )));
-            }
-            return Err(Error::LLM(format!(
-                "Gemini API error ({}): {}",
-                status, body
-            )));
-        }
-
-        let api_response: GeminiResponse = serde_json::from_str(&body)
-            .map_err(|e| Error::LLM(format!("Failed to parse response: {}", e)))?;
-
-        let candidate = api_response
-            .candidates
-            .first()
-            .ok_or_else(|| Error::LLM("No candidates in response".to_string()))?;
-
-        let content = candidate
-            .content
-            .parts
-            .iter()
-            .map(|p| p.text.clone())
-            .collect::<Vec<_>>()
-            .join("");
-
-        let stop_reason = candidate.finish_reason.as_deref().map(|r| match r {
-            "STOP" => StopReason::EndTurn,
-            "MAX_TOKENS" => StopReason::MaxTokens,
-            "STOP_SEQUENCE" => StopReason::StopSequence,
-            _ => StopReason::EndTurn,
-        });
-
-        let usage_metadata = api_response.usage_metadata.unwrap_or(GeminiUsageMetadata {
-            prompt_token_count: 0,
-            candidates_token_count: Some(0),
-            total_token_count: Some(0),
-            cached_content_token_count: None,
-        });
-
-        let usage = TokenUsage {
-            input_tokens: usage_metadata.prompt_token_count,
-            output_tokens: usage_metadata.candidates_token_count.unwrap_or(0),
-            cache_read_tokens: usage_metadata.cached_content_token_count,
-            cache_creation_tokens: None,
-        };
-
-        // Calculate cost based on model
-        let model_spec = self
-            .available_models()
-            .into_iter()
-            .find(|m| m.id == model || model.contains(&m.id))
-            .unwrap_or_else(ModelSpec::gemini_2_0_flash);
-        let cost = model_spec.calculate_cost(usage.input_tokens, usage.output_tokens);
-
-        // Generate a unique ID since Gemini doesn't return one
-        let id = format!("gemini-{}", Utc::now().timestamp_millis());
-
-        Ok(CompletionResponse {
-            id,
-            model,
-            content,
-            stop_reason,
-            usage,
-            timestamp: Utc::now(),
-            cost: Some(cost),
-        })
+        traced_completion(span, self.send_completion(request)).await
     }
 
     async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
@@ -830,15 +1838,11 @@ impl LLMClient for GoogleClient {
     }
 
     fn provider(&self) -> Provider {
-        Provider::Google
-    }
-
-    fn available_models(&self) -> Vec<ModelSpec> {
-        vec![
-            ModelSpec::gemini_2_0_flash(),
-            ModelSpec::gemini_1_5_pro(),
-            ModelSpec::gemini_1_5_flash(),
-        ]
+        Provider::Google
+    }
+
+    fn available_models(&self) -> Vec<ModelSpec> {
+        Self::models()
     }
 }
 
@@ -925,10 +1929,161 @@ impl Default for MultiProviderClient {
     }
 }
 
+/// Client that tries an ordered list of providers until one succeeds.
+///
+/// Each entry pairs a client with the model it should run, so a request is
+/// rewritten for every fallback rather than sent with the primary's model
+/// name. Errors that would recur on any retry (see
+/// [`Error::is_deterministic_client_error`]) are returned immediately.
+/// Spend is recorded against the provider that actually served the request.
+pub struct FallbackChain {
+    entries: Vec<(Arc<dyn LLMClient>, ModelSpec)>,
+    costs: Arc<RwLock<super::types::CostTracker>>,
+}
+
+/// A completion served by a [`FallbackChain`].
+#[derive(Debug, Clone)]
+pub struct FallbackOutcome {
+    /// The successful response
+    pub response: CompletionResponse,
+    /// Provider that served the request
+    pub provider: Provider,
+    /// Model that served the request
+    pub model: ModelSpec,
+    /// Errors from the entries tried before it, in order
+    pub failures: Vec<(Provider, String)>,
+}
+
+impl FallbackChain {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            costs: Arc::new(RwLock::new(super::types::CostTracker::new())),
+        }
+    }
+
+    /// Append a client and the model to run on it.
+    pub fn with_entry(mut self, client: Arc<dyn LLMClient>, model: ModelSpec) -> Self {
+        self.entries.push((client, model));
+        self
+    }
+
+    /// Complete with the first entry that succeeds.
+    ///
+    /// Returns the last error if every entry fails.
+    pub async fn complete_with_fallback(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<FallbackOutcome> {
+        let mut failures = Vec::new();
+        let mut last_error = None;
+
+        for (client, model) in &self.entries {
+            let provider = client.provider();
+            let attempt = request.clone().with_model(model.id.clone());
+
+            match client.complete(attempt).await {
+                Ok(response) => {
+                    let mut costs = self.costs.write().await;
+                    costs.record_for_provider(
+                        provider,
+                        &response.model,
+                        &response.usage,
+                        response.cost,
+                    );
+                    costs.record_retries(response.retries);
+
+                    return Ok(FallbackOutcome {
+                        response,
+                        provider,
+                        model: model.clone(),
+                        failures,
+                    });
+                }
+                Err(error) if error.is_deterministic_client_error() => return Err(error),
+                Err(error) => {
+                    tracing::warn!(%provider, model = %model.id, %error, "provider failed, falling back");
+                    failures.push((provider, error.to_string()));
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| Error::Config("Fallback chain is empty".to_string())))
+    }
+
+    /// Get current cost summary.
+    pub async fn get_costs(&self) -> super::types::CostTracker {
+        self.costs.read().await.clone()
+    }
+
+    /// Reset cost tracking.
+    pub async fn reset_costs(&self) {
+        let mut costs = self.costs.write().await;
+        *costs = super::types::CostTracker::new();
+    }
+}
+
+impl Default for FallbackChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LLMClient for FallbackChain {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        self.complete_with_fallback(request)
+            .await
+            .map(|outcome| outcome.response)
+    }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        let mut last_error = None;
+        for (client, _) in &self.entries {
+            match client.embed(request.clone()).await {
+                Ok(response) => {
+                    self.costs.write().await.record_embedding(
+                        client.provider(),
+                        &response.model,
+                        response.usage.input_tokens,
+                        response.cost,
+                    );
+                    return Ok(response);
+                }
+                Err(error) if error.is_deterministic_client_error() => return Err(error),
+                Err(error) => last_error = Some(error),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| Error::Config("Fallback chain is empty".to_string())))
+    }
+
+    /// The primary provider.
+    fn provider(&self) -> Provider {
+        self.entries
+            .first()
+            .map(|(client, _)| client.provider())
+            .unwrap_or(Provider::Anthropic)
+    }
+
+    fn available_models(&self) -> Vec<ModelSpec> {
+        self.entries
+            .iter()
+            .map(|(_, model)| model.clone())
+            .collect()
+    }
+
+    /// True only if every provider in the chain supports logprobs.
+    fn supports_logprobs(&self) -> bool {
+        !self.entries.is_empty() && self.entries.iter().all(|(c, _)| c.supports_logprobs())
+    }
+}
+
 /// Thread-safe client wrapper with cost tracking.
 pub struct TrackedClient {
     inner: Arc<dyn LLMClient>,
     costs: Arc<RwLock<super::types::CostTracker>>,
+    samples: bool,
 }
 
 impl TrackedClient {
@@ -936,15 +2091,53 @@ impl TrackedClient {
         Self {
             inner: client,
             costs: Arc::new(RwLock::new(super::types::CostTracker::new())),
+            samples: false,
+        }
+    }
+
+    /// Also track per-call cost and latency distributions.
+    ///
+    /// See [`CostTracker::with_samples`](super::types::CostTracker::with_samples).
+    pub fn with_samples(mut self) -> Self {
+        self.samples = true;
+        self.costs = Arc::new(RwLock::new(self.empty_tracker()));
+        self
+    }
+
+    fn empty_tracker(&self) -> super::types::CostTracker {
+        let tracker = super::types::CostTracker::new();
+        if self.samples {
+            tracker.with_samples()
+        } else {
+            tracker
         }
     }
 
     /// Complete and track costs.
     pub async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let started = Instant::now();
         let response = self.inner.complete(request).await?;
+        let latency_ms = started.elapsed().as_millis() as u64;
 
         let mut costs = self.costs.write().await;
         costs.record(&response.model, &response.usage, response.cost);
+        costs.record_retries(response.retries);
+        costs.record_latency(latency_ms);
+
+        Ok(response)
+    }
+
+    /// Embed and track costs.
+    pub async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        let response = self.inner.embed(request).await?;
+
+        let mut costs = self.costs.write().await;
+        costs.record_embedding(
+            self.inner.provider(),
+            &response.model,
+            response.usage.input_tokens,
+            response.cost,
+        );
 
         Ok(response)
     }
@@ -957,13 +2150,14 @@ impl TrackedClient {
     /// Reset cost tracking.
     pub async fn reset_costs(&self) {
         let mut costs = self.costs.write().await;
-        *costs = super::types::CostTracker::new();
+        *costs = self.empty_tracker();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::llm::types::{ChatMessage, CostTracker, ImagePart, StopReason};
 
     #[test]
     fn test_client_config_builder() {
@@ -978,6 +2172,781 @@ mod tests {
         assert_eq!(config.timeout_secs, 60);
     }
 
+    struct RetriedClient;
+
+    #[async_trait]
+    impl LLMClient for RetriedClient {
+        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+            Ok(CompletionResponse {
+                id: "r".to_string(),
+                model: "m".to_string(),
+                content: "ok".to_string(),
+                stop_reason: None,
+                usage: TokenUsage::default(),
+                timestamp: Utc::now(),
+                cost: Some(0.01),
+                retries: 2,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        }
+
+        async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+            Err(Error::LLM("unsupported".to_string()))
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::Anthropic
+        }
+
+        fn available_models(&self) -> Vec<ModelSpec> {
+            Vec::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tracked_client_counts_retries_separately() {
+        let client = TrackedClient::new(Arc::new(RetriedClient));
+        client.complete(CompletionRequest::new()).await.unwrap();
+        client.complete(CompletionRequest::new()).await.unwrap();
+
+        let costs = client.get_costs().await;
+        assert_eq!(costs.request_count, 2);
+        assert_eq!(costs.retry_count, 4);
+    }
+
+    #[test]
+    fn test_client_config_retry_policy() {
+        let config = ClientConfig::new("key").with_retry_policy(RetryPolicy::none());
+        assert_eq!(config.retry_policy.max_attempts, 1);
+        assert_eq!(
+            ClientConfig::new("key").retry_policy,
+            RetryPolicy::default()
+        );
+    }
+
+    fn weather_tool() -> ToolDef {
+        ToolDef::from_fields(
+            "get_weather",
+            "Look up the weather",
+            vec![crate::signature::FieldSpec::new(
+                "city",
+                crate::signature::FieldType::String,
+            )],
+        )
+    }
+
+    #[test]
+    fn test_anthropic_request_includes_tools() {
+        let client = AnthropicClient::new(ClientConfig::new("test"));
+        let request = client
+            .api_request(CompletionRequest::new().with_tools(vec![weather_tool()]))
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["tools"][0]["name"], "get_weather");
+        assert_eq!(json["tools"][0]["input_schema"]["required"][0], "city");
+
+        let bare =
+            serde_json::to_value(client.api_request(CompletionRequest::new()).unwrap()).unwrap();
+        assert!(bare.get("tools").is_none());
+    }
+
+    #[test]
+    fn test_openai_request_includes_tools() {
+        let client = OpenAIClient::new(ClientConfig::new("test"));
+        let request = client
+            .api_request(CompletionRequest::new().with_tools(vec![weather_tool()]))
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["tools"][0]["type"], "function");
+        assert_eq!(json["tools"][0]["function"]["name"], "get_weather");
+        assert_eq!(
+            json["tools"][0]["function"]["parameters"]["properties"]["city"]["type"],
+            "string"
+        );
+    }
+
+    #[test]
+    fn test_openai_request_json_schema() {
+        let client = OpenAIClient::new(ClientConfig::new("test"));
+        let schema = weather_tool().parameters;
+        let request = client
+            .api_request(CompletionRequest::new().with_json_schema(schema.clone()))
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["response_format"]["type"], "json_schema");
+        assert_eq!(json["response_format"]["json_schema"]["schema"], schema);
+        assert_eq!(json["response_format"]["json_schema"]["strict"], false);
+
+        let request = client
+            .api_request(CompletionRequest::new().with_json_mode())
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["response_format"],
+            serde_json::json!({"type": "json_object"})
+        );
+
+        let json =
+            serde_json::to_value(client.api_request(CompletionRequest::new()).unwrap()).unwrap();
+        assert!(json.get("response_format").is_none());
+    }
+
+    #[test]
+    fn test_openai_logprobs() {
+        let client = OpenAIClient::new(ClientConfig::new("test"));
+        assert!(client.supports_logprobs());
+        let json = serde_json::to_value(
+            client
+                .api_request(CompletionRequest::new().with_logprobs(5))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["logprobs"], true);
+        assert_eq!(json["top_logprobs"], 5);
+
+        let body = r#"{
+            "id": "c1", "model": "gpt-4o",
+            "usage": {"prompt_tokens": 10, "completion_tokens": 1},
+            "choices": [{
+                "finish_reason": "length",
+                "message": {"role": "assistant", "content": "Yes"},
+                "logprobs": {"content": [{
+                    "token": "Yes", "logprob": -0.1, "bytes": [89, 101, 115],
+                    "top_logprobs": [
+                        {"token": "Yes", "logprob": -0.1, "bytes": [89, 101, 115]},
+                        {"token": "No", "logprob": -2.4, "bytes": [78, 111]}
+                    ]
+                }]}
+            }]
+        }"#;
+        let response: OpenAIResponse = serde_json::from_str(body).unwrap();
+        let logprobs = response.choices[0]
+            .logprobs
+            .as_ref()
+            .and_then(|l| l.content.clone())
+            .unwrap();
+        assert_eq!(logprobs[0].token, "Yes");
+        assert_eq!(logprobs[0].top_logprobs[1].token, "No");
+    }
+
+    #[test]
+    fn test_anthropic_request_forces_structured_output_tool() {
+        let client = AnthropicClient::new(ClientConfig::new("test"));
+        let schema = weather_tool().parameters;
+        let request = client
+            .api_request(
+                CompletionRequest::new()
+                    .with_tools(vec![weather_tool()])
+                    .with_json_schema(schema.clone()),
+            )
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["tools"][1]["name"], "structured_output");
+        assert_eq!(json["tools"][1]["input_schema"], schema);
+        assert_eq!(
+            json["tool_choice"],
+            serde_json::json!({"type": "tool", "name": "structured_output"})
+        );
+    }
+
+    /// Subscriber that keeps every span's name, parent, and fields.
+    #[derive(Clone, Default)]
+    struct SpanCapture {
+        spans: Arc<std::sync::Mutex<Vec<CapturedSpan>>>,
+        stack: Arc<std::sync::Mutex<Vec<u64>>>,
+    }
+
+    struct CapturedSpan {
+        name: &'static str,
+        parent: Option<u64>,
+        fields: HashMap<String, String>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl SpanCapture {
+        fn find(&self, name: &str) -> Option<(u64, Option<u64>, HashMap<String, String>)> {
+            let spans = self.spans.lock().unwrap();
+            spans.iter().enumerate().find_map(|(i, span)| {
+                (span.name == name).then(|| (i as u64 + 1, span.parent, span.fields.clone()))
+            })
+        }
+    }
+
+    impl tracing::Subscriber for SpanCapture {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let parent = match attrs.parent() {
+                Some(id) => Some(id.into_u64()),
+                None if attrs.is_contextual() => self.stack.lock().unwrap().last().copied(),
+                None => None,
+            };
+            let mut fields = HashMap::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(CapturedSpan {
+                name: attrs.metadata().name(),
+                parent,
+                fields,
+            });
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let fields = &mut spans[span.into_u64() as usize - 1].fields;
+            values.record(&mut FieldVisitor(fields));
+        }
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, _event: &tracing::Event<'_>) {}
+
+        fn enter(&self, span: &tracing::span::Id) {
+            self.stack.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _span: &tracing::span::Id) {
+            self.stack.lock().unwrap().pop();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_completion_span_records_model_and_usage() {
+        let capture = SpanCapture::default();
+        let _guard = tracing::subscriber::set_default(capture.clone());
+
+        let completion = async {
+            // Crossing an await must keep the completion span current
+            tokio::task::yield_now().await;
+            tracing::info_span!("provider.request").in_scope(|| {});
+            Ok(CompletionResponse {
+                id: "msg_1".to_string(),
+                model: "claude-3-5-haiku-20241022".to_string(),
+                content: "ok".to_string(),
+                stop_reason: None,
+                usage: TokenUsage {
+                    input_tokens: 120,
+                    output_tokens: 30,
+                    cache_read_tokens: None,
+                    cache_creation_tokens: None,
+                },
+                timestamp: Utc::now(),
+                cost: None,
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        };
+        let parent = tracing::info_span!("orchestrator.iteration");
+        async { traced_completion(completion_span(Provider::Anthropic, None), completion).await }
+            .instrument(parent)
+            .await
+            .unwrap();
+
+        let (parent_id, _, _) = capture.find("orchestrator.iteration").unwrap();
+        let (span_id, span_parent, fields) = capture.find("llm.complete").unwrap();
+        assert_eq!(span_parent, Some(parent_id));
+        assert_eq!(fields["provider"], "anthropic");
+        assert_eq!(fields["model"], "claude-3-5-haiku-20241022");
+        assert_eq!(fields["input_tokens"], "120");
+        assert_eq!(fields["output_tokens"], "30");
+        assert!(fields.contains_key("duration_ms"));
+
+        let (_, request_parent, _) = capture.find("provider.request").unwrap();
+        assert_eq!(request_parent, Some(span_id));
+    }
+
+    #[test]
+    fn test_vision_request_serializes_image_blocks() {
+        let request = || {
+            CompletionRequest::new().with_message(ChatMessage::user_with_images(
+                "What is in this chart?",
+                vec![
+                    ImagePart::base64("iVBORw0KGgo=", "image/png"),
+                    ImagePart::url("https://example.com/cat.jpg", "image/jpeg"),
+                ],
+            ))
+        };
+
+        let anthropic = AnthropicClient::new(ClientConfig::new("test"));
+        let json = serde_json::to_value(anthropic.api_request(request()).unwrap()).unwrap();
+        assert_eq!(
+            json["messages"][0]["content"],
+            serde_json::json!([
+                {"type": "image", "source": {
+                    "type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo="
+                }},
+                {"type": "image", "source": {"type": "url", "url": "https://example.com/cat.jpg"}},
+                {"type": "text", "text": "What is in this chart?"}
+            ])
+        );
+
+        let openai = OpenAIClient::new(ClientConfig::new("test"));
+        let json = serde_json::to_value(openai.api_request(request()).unwrap()).unwrap();
+        assert_eq!(
+            json["messages"][0]["content"],
+            serde_json::json!([
+                {"type": "text", "text": "What is in this chart?"},
+                {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBORw0KGgo="}},
+                {"type": "image_url", "image_url": {"url": "https://example.com/cat.jpg"}}
+            ])
+        );
+
+        // Text-only messages keep the plain string form
+        let json = serde_json::to_value(
+            openai
+                .api_request(CompletionRequest::new().with_message(ChatMessage::user("Hi")))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["messages"][0]["content"], "Hi");
+    }
+
+    #[test]
+    fn test_anthropic_request_carries_cache_markers() {
+        let client = AnthropicClient::new(ClientConfig::new("test"));
+        let request = CompletionRequest::new()
+            .with_system("You are a release assistant")
+            .with_messages(vec![
+                ChatMessage::user("Tool catalogue"),
+                ChatMessage::assistant("Ready"),
+                ChatMessage::user("Ship it"),
+            ])
+            .with_cache_after_system()
+            .with_cache_after_message(1);
+
+        let json = serde_json::to_value(client.api_request(request).unwrap()).unwrap();
+        assert_eq!(
+            json["system"],
+            serde_json::json!([{
+                "type": "text",
+                "text": "You are a release assistant",
+                "cache_control": {"type": "ephemeral"}
+            }])
+        );
+        assert_eq!(json["messages"][0]["content"], "Tool catalogue");
+        assert_eq!(
+            json["messages"][1]["content"],
+            serde_json::json!([{
+                "type": "text",
+                "text": "Ready",
+                "cache_control": {"type": "ephemeral"}
+            }])
+        );
+        assert_eq!(json["messages"][2]["content"], "Ship it");
+
+        // Without caching the system prompt stays a plain string
+        let json = serde_json::to_value(
+            client
+                .api_request(CompletionRequest::new().with_system("Be brief"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["system"], "Be brief");
+    }
+
+    #[test]
+    fn test_images_rejected_for_non_vision_model() {
+        let request = CompletionRequest::new()
+            .with_model("llama3.1:8b")
+            .with_message(ChatMessage::user_with_images(
+                "Describe",
+                vec![ImagePart::base64("AAAA", "image/png")],
+            ));
+
+        let err = OllamaClient::local()
+            .api_request(request)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("llama3.1:8b"));
+        assert!(err.contains("does not support image input"));
+    }
+
+    #[test]
+    fn test_ollama_vision_request_sends_base64_images() {
+        let request = |model: &str| {
+            CompletionRequest::new()
+                .with_model(model)
+                .with_message(ChatMessage::user_with_images(
+                    "Describe",
+                    vec![ImagePart::base64("iVBORw0KGgo=", "image/png")],
+                ))
+        };
+
+        let client = OllamaClient::local().with_vision_models(["llava:13b"]);
+        for model in ["llava:13b", "llama3.2-vision:11b"] {
+            let json = serde_json::to_value(client.api_request(request(model)).unwrap()).unwrap();
+            assert_eq!(json["messages"][0]["content"], "Describe");
+            assert_eq!(
+                json["messages"][0]["images"],
+                serde_json::json!(["iVBORw0KGgo="])
+            );
+        }
+
+        // Declaring one model does not open up the others
+        assert!(client.api_request(request("llama3.1:8b")).is_err());
+
+        let url_request = CompletionRequest::new()
+            .with_model("llava:13b")
+            .with_message(ChatMessage::user_with_images(
+                "Describe",
+                vec![ImagePart::url("https://example.com/cat.jpg", "image/jpeg")],
+            ));
+        let err = client.api_request(url_request).unwrap_err().to_string();
+        assert!(err.contains("only accepts base64 images"));
+    }
+
+    #[test]
+    fn test_ollama_request_format() {
+        let client = OllamaClient::local();
+        let json = serde_json::to_value(
+            client
+                .api_request(CompletionRequest::new().with_json_mode())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["format"], "json");
+    }
+
+    #[test]
+    fn test_response_format_instruction_fallback() {
+        let format = ResponseFormat::JsonSchema {
+            schema: weather_tool().parameters,
+        };
+        let system = format.apply_to_system(Some("Be terse.".to_string()));
+
+        assert!(system.starts_with("Be terse.\n\n"));
+        assert!(system.contains("conforms to this JSON schema"));
+        assert!(system.contains("\"city\""));
+        assert_eq!(format.apply_to_system(None), format.instruction());
+    }
+
+    #[test]
+    fn test_anthropic_tool_use_blocks() {
+        let body = r#"{
+            "id": "msg_1", "model": "claude", "stop_reason": "tool_use",
+            "usage": {"input_tokens": 10, "output_tokens": 5},
+            "content": [
+                {"type": "text", "text": "Checking both."},
+                {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {"city": "Oslo"}},
+                {"type": "tool_use", "id": "toolu_2", "name": "get_weather", "input": {"city": "Lima"}}
+            ]
+        }"#;
+        let response: AnthropicResponse = serde_json::from_str(body).unwrap();
+        let calls = response.tool_calls();
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "toolu_1");
+        assert_eq!(calls[1].arguments, serde_json::json!({"city": "Lima"}));
+    }
+
+    #[test]
+    fn test_openai_tool_calls() {
+        let body = r#"{
+            "id": "c1", "model": "gpt-4o",
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5},
+            "choices": [{
+                "finish_reason": "tool_calls",
+                "message": {
+                    "role": "assistant", "content": null,
+                    "tool_calls": [
+                        {"id": "call_1", "type": "function", "function": {"name": "get_weather", "arguments": "{\"city\":\"Oslo\"}"}},
+                        {"id": "call_2", "type": "function", "function": {"name": "get_weather", "arguments": "not json"}}
+                    ]
+                }
+            }]
+        }"#;
+        let response: OpenAIResponse = serde_json::from_str(body).unwrap();
+        let message = &response.choices[0].message;
+        let calls = message.tool_calls();
+
+        assert!(message.content.is_none());
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].arguments, serde_json::json!({"city": "Oslo"}));
+        assert_eq!(calls[1].arguments, serde_json::json!("not json"));
+    }
+
+    #[test]
+    fn test_ollama_request_options() {
+        let client = OllamaClient::local();
+        assert_eq!(client.base_url(), "http://localhost:11434");
+
+        let request = client
+            .api_request(
+                CompletionRequest::new()
+                    .with_system("Be brief.")
+                    .with_message(ChatMessage::user("Hi"))
+                    .with_max_tokens(64)
+                    .with_tools(vec![weather_tool()]),
+            )
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["model"], "llama3.1:8b");
+        assert_eq!(json["stream"], false);
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert_eq!(json["options"]["num_predict"], 64);
+        assert!(json["options"].get("temperature").is_none());
+        assert_eq!(json["tools"][0]["function"]["name"], "get_weather");
+    }
+
+    #[test]
+    fn test_ollama_response_mapping() {
+        let body = r#"{
+            "model": "qwen2.5:32b", "created_at": "2024-11-01T00:00:00Z",
+            "message": {"role": "assistant", "content": "", "tool_calls": [
+                {"function": {"name": "get_weather", "arguments": {"city": "Oslo"}}},
+                {"function": {"name": "get_weather", "arguments": {"city": "Lima"}}}
+            ]},
+            "done": true, "done_reason": "stop", "eval_count": 20
+        }"#;
+        let api_response: OllamaChatResponse = serde_json::from_str(body).unwrap();
+        let response = api_response.into_completion(&ModelSpec::qwen2_5_32b());
+
+        assert_eq!(response.model, "qwen2.5:32b");
+        assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+        // Missing prompt_eval_count (cached prompt) counts as zero.
+        assert_eq!(response.usage.input_tokens, 0);
+        assert_eq!(response.usage.output_tokens, 20);
+        assert_eq!(response.cost, Some(0.0));
+        assert_eq!(response.tool_calls.len(), 2);
+        assert_eq!(response.tool_calls[1].id, "call_1");
+        assert_eq!(
+            response.tool_calls[1].arguments,
+            serde_json::json!({"city": "Lima"})
+        );
+    }
+
+    #[cfg(feature = "gemini")]
+    #[test]
+    fn test_gemini_response_mapping() {
+        let body = r#"{
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "Use a "}, {"text": "queue."}]},
+                "finishReason": "MAX_TOKENS"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 1200, "candidatesTokenCount": 300,
+                "thoughtsTokenCount": 50, "cachedContentTokenCount": 1000,
+                "totalTokenCount": 1550
+            },
+            "modelVersion": "gemini-1.5-pro-002"
+        }"#;
+        let response = GoogleClient::parse_response("gemini-1.5-pro-002".into(), body).unwrap();
+
+        assert_eq!(response.content, "Use a queue.");
+        assert_eq!(response.stop_reason, Some(StopReason::MaxTokens));
+        assert_eq!(response.usage.input_tokens, 1200);
+        assert_eq!(response.usage.output_tokens, 350);
+        assert_eq!(response.usage.cache_read_tokens, Some(1000));
+        let expected = ModelSpec::gemini_1_5_pro().calculate_cost(1200, 350);
+        assert_eq!(response.cost, Some(expected));
+
+        let spec = GoogleClient::model_spec(&response.model);
+        assert_eq!(spec.provider, Provider::Google);
+        assert_eq!(spec.routing_tier(), ModelTier::Balanced);
+        assert_eq!(
+            GoogleClient::model_spec("gemini-2.0-flash-001").routing_tier(),
+            ModelTier::Fast
+        );
+
+        // Missing usage metadata counts as zero rather than failing
+        let body = r#"{"candidates": [{"content": {"role": "model", "parts": [{}]}}]}"#;
+        let response = GoogleClient::parse_response("gemini-2.0-flash".into(), body).unwrap();
+        assert_eq!(response.usage.total(), 0);
+        assert_eq!(response.content, "");
+    }
+
+    #[test]
+    fn test_ollama_models_are_local_and_free() {
+        let client = OllamaClient::new(ClientConfig::new("").with_base_url("http://gpu-box:11434"));
+        assert_eq!(client.base_url(), "http://gpu-box:11434");
+        assert_eq!(client.provider(), Provider::Ollama);
+
+        for model in client.available_models() {
+            assert!(model.is_local);
+            assert_eq!(model.provider, Provider::Ollama);
+            assert_eq!(model.calculate_cost(1_000_000, 1_000_000), 0.0);
+        }
+
+        let unknown = client.model_spec("mistral:7b");
+        assert_eq!(unknown.id, "mistral:7b");
+        assert_eq!(unknown.tier, ModelTier::Fast);
+
+        let mut costs = CostTracker::new();
+        let usage = TokenUsage {
+            input_tokens: 500,
+            output_tokens: 200,
+            ..Default::default()
+        };
+        costs.record(
+            "llama3.1:8b",
+            &usage,
+            Some(unknown.calculate_cost(500, 200)),
+        );
+        assert_eq!(costs.total_cost, 0.0);
+    }
+
+    /// Client that fails with `status` (if set) and otherwise echoes the
+    /// requested model.
+    struct ScriptedClient {
+        provider: Provider,
+        status: Option<u16>,
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    impl ScriptedClient {
+        fn new(provider: Provider, status: Option<u16>) -> Arc<Self> {
+            Arc::new(Self {
+                provider,
+                status,
+                calls: Default::default(),
+            })
+        }
+
+        fn calls(&self) -> u32 {
+            self.calls.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl LLMClient for ScriptedClient {
+        async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if let Some(status) = self.status {
+                return Err(Error::llm_status(
+                    self.provider.to_string(),
+                    status,
+                    "scripted",
+                ));
+            }
+            Ok(CompletionResponse {
+                id: "ok".to_string(),
+                model: request.model.unwrap_or_default(),
+                content: "served".to_string(),
+                stop_reason: None,
+                usage: TokenUsage {
+                    input_tokens: 100,
+                    output_tokens: 10,
+                    ..Default::default()
+                },
+                timestamp: Utc::now(),
+                cost: Some(0.02),
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        }
+
+        async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+            Err(Error::LLM("unsupported".to_string()))
+        }
+
+        fn provider(&self) -> Provider {
+            self.provider
+        }
+
+        fn available_models(&self) -> Vec<ModelSpec> {
+            vec![]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_chain_falls_over_on_server_error() {
+        let primary = ScriptedClient::new(Provider::Anthropic, Some(529));
+        let secondary = ScriptedClient::new(Provider::OpenAI, None);
+        let chain = FallbackChain::new()
+            .with_entry(primary.clone(), ModelSpec::claude_sonnet())
+            .with_entry(secondary.clone(), ModelSpec::gpt4o());
+
+        let request = CompletionRequest::new().with_model("claude-3-5-sonnet-20241022");
+        let outcome = chain.complete_with_fallback(request).await.unwrap();
+
+        assert_eq!(outcome.provider, Provider::OpenAI);
+        assert_eq!(outcome.response.model, "gpt-4o");
+        assert_eq!(outcome.failures.len(), 1);
+        assert_eq!(outcome.failures[0].0, Provider::Anthropic);
+        assert_eq!((primary.calls(), secondary.calls()), (1, 1));
+
+        let costs = chain.get_costs().await;
+        assert_eq!(costs.request_count, 1);
+        assert!(costs.by_model.contains_key("gpt-4o"));
+        assert!(!costs.by_provider.contains_key(&Provider::Anthropic));
+        assert_eq!(costs.by_provider[&Provider::OpenAI].cost, 0.02);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_chain_falls_over_on_rate_limit() {
+        let primary = ScriptedClient::new(Provider::Anthropic, Some(429));
+        let secondary = ScriptedClient::new(Provider::OpenAI, None);
+        let chain = FallbackChain::new()
+            .with_entry(primary, ModelSpec::claude_sonnet())
+            .with_entry(secondary, ModelSpec::gpt4o());
+
+        let response = chain.complete(CompletionRequest::new()).await.unwrap();
+        assert_eq!(response.model, "gpt-4o");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_chain_stops_on_deterministic_error() {
+        let primary = ScriptedClient::new(Provider::Anthropic, Some(401));
+        let secondary = ScriptedClient::new(Provider::OpenAI, None);
+        let chain = FallbackChain::new()
+            .with_entry(primary, ModelSpec::claude_sonnet())
+            .with_entry(secondary.clone(), ModelSpec::gpt4o());
+
+        let error = chain
+            .complete_with_fallback(CompletionRequest::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::LlmStatus { status: 401, .. }));
+        assert_eq!(secondary.calls(), 0);
+        assert_eq!(chain.get_costs().await.request_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_chain_returns_last_error_when_all_fail() {
+        let chain = FallbackChain::new()
+            .with_entry(
+                ScriptedClient::new(Provider::Anthropic, Some(503)),
+                ModelSpec::claude_sonnet(),
+            )
+            .with_entry(
+                ScriptedClient::new(Provider::OpenAI, Some(500)),
+                ModelSpec::gpt4o(),
+            );
+
+        let error = chain.complete(CompletionRequest::new()).await.unwrap_err();
+        assert!(matches!(error, Error::LlmStatus { status: 500, .. }));
+
+        let empty = FallbackChain::new();
+        assert!(matches!(
+            empty.complete(CompletionRequest::new()).await,
+            Err(Error::Config(_))
+        ));
+    }
+
     #[test]
     fn test_multi_provider_client() {
         let client = MultiProviderClient::new().with_default_provider(Provider::OpenAI);
@@ -1006,4 +2975,16 @@ mod tests {
         assert!(models.iter().any(|m| m.id == "gpt-4o"));
         assert!(models.iter().any(|m| m.id == "gpt-4o-mini"));
     }
+
+    #[test]
+    fn test_openai_embedding_cost_uses_model_prices() {
+        let ada = OpenAIClient::embedding_cost("text-embedding-ada-002", 1_000_000).unwrap();
+        assert!((ada - 0.10).abs() < 1e-9);
+        let large = OpenAIClient::embedding_cost("text-embedding-3-large", 1_000_000).unwrap();
+        assert!((large - 0.13).abs() < 1e-9);
+        assert_eq!(
+            OpenAIClient::embedding_cost("unknown-embedder", 1_000),
+            None
+        );
+    }
 }
@@ -34,27 +34,44 @@
 mod batch;
 mod cache;
 mod client;
+mod replay;
+mod retry;
 mod router;
+mod stats;
+mod stream;
+mod tokens;
 mod types;
 
+pub(crate) use batch::{default_provider_rate_limits, ProviderRateLimiter};
 pub use batch::{
     BatchConfig, BatchExecutor, BatchQueryResult, BatchedLLMQuery, BatchedQueryResults,
-    DEFAULT_MAX_PARALLEL,
+    DEFAULT_MAX_PARALLEL, DEFAULT_RATE_LIMIT_WINDOW_MS,
 };
 pub use cache::{
-    apply_cache_markers, find_cache_breakpoints, CacheEntry, CacheKey, CacheStats, PromptCache,
+    apply_cache_markers, apply_request_cache_markers, cap_cache_breakpoints,
+    find_cache_breakpoints, CacheEntry, CacheKey, CacheStats, PromptCache, MAX_CACHE_BREAKPOINTS,
+    MIN_CACHE_TOKENS,
 };
 #[cfg(feature = "gemini")]
 pub use client::GoogleClient;
 pub use client::{
-    AnthropicClient, ClientConfig, LLMClient, MultiProviderClient, OpenAIClient, TrackedClient,
+    AnthropicClient, ClientConfig, FallbackChain, FallbackOutcome, LLMClient, MultiProviderClient,
+    OllamaClient, OpenAIClient, TrackedClient,
 };
+pub use replay::{
+    request_hash, RecordedExchange, RecordingLLMClient, ReplayLLMClient, REPLAY_PREVIEW_CHARS,
+};
+pub use retry::RetryPolicy;
 pub use router::{
-    DualModelConfig, QueryType, RoutingContext, RoutingDecision, SmartRouter, SwitchStrategy,
-    TierDefaults,
+    DualModelConfig, ModelStats, QueryType, RouterStats, RoutingContext, RoutingDecision,
+    SavingsEstimate, SmartRouter, SwitchStrategy, TierDefaults, TRACE_OUTPUT_SHARE,
 };
+pub use stats::{SampleStats, DEFAULT_RELATIVE_ACCURACY, MAX_SKETCH_BUCKETS};
+pub use stream::{single_chunk_stream, CompletionStream};
+pub use tokens::{estimate_tokens, provider_for_model, MESSAGE_OVERHEAD_TOKENS};
 pub use types::{
     CacheControl, ChatMessage, ChatRole, CompletionRequest, CompletionResponse, CostTracker,
-    EmbeddingRequest, EmbeddingResponse, ModelCallTier, ModelCosts, ModelSpec, ModelTier, Provider,
-    StopReason, TierBreakdown, TierCosts, TokenUsage,
+    EmbeddingRequest, EmbeddingResponse, ImagePart, ImageSource, ModelCallTier, ModelCosts,
+    ModelSpec, ModelTier, Provider, ResponseFormat, StopReason, StreamChunk, TierBreakdown,
+    TierCosts, TokenLogprob, TokenUsage, ToolCall, ToolCallDelta, ToolDef, TopLogprob,
 };
@@ -0,0 +1,327 @@
+//! Record and replay LLM traffic for deterministic runs.
+//!
+//! [`RecordingLLMClient`] wraps a live client and appends every completion
+//! exchange to a JSON Lines file. [`ReplayLLMClient`] loads that file and
+//! answers requests from it without touching the network, so a captured
+//! production run can be replayed in tests.
+//!
+//! Requests are matched by a SHA-256 hash of their serialized form.
+//! `metadata` is left out of the hash because it carries per-run tracking
+//! values such as trace IDs. Identical requests are served in the order they
+//! were recorded. Replayed responses keep their recorded usage and cost, so a
+//! [`CostTracker`](super::CostTracker) fed from them reports the original
+//! spend.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::error::{Error, Result};
+
+use super::client::LLMClient;
+use super::types::{
+    CompletionRequest, CompletionResponse, EmbeddingRequest, EmbeddingResponse, ModelSpec, Provider,
+};
+
+/// Characters of the prompt shown when replay meets an unrecorded request.
+pub const REPLAY_PREVIEW_CHARS: usize = 200;
+
+/// One recorded request/response pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    /// Hash from [`request_hash`]
+    pub request_hash: String,
+    /// Provider that served the request
+    pub provider: Provider,
+    pub request: CompletionRequest,
+    pub response: CompletionResponse,
+}
+
+/// Stable hash of a request, ignoring `metadata`.
+pub fn request_hash(request: &CompletionRequest) -> Result<String> {
+    let mut value = serde_json::to_value(request)?;
+    if let Some(object) = value.as_object_mut() {
+        object.remove("metadata");
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_string(&value)?.as_bytes());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Short, human-readable summary of a request for error messages.
+fn request_preview(request: &CompletionRequest) -> String {
+    let text = request
+        .messages
+        .last()
+        .map(|m| m.content.as_str())
+        .or(request.system.as_deref())
+        .unwrap_or("");
+    let mut preview: String = text.chars().take(REPLAY_PREVIEW_CHARS).collect();
+    if text.chars().count() > REPLAY_PREVIEW_CHARS {
+        preview.push_str("...");
+    }
+    format!(
+        "model={} messages={} last={:?}",
+        request.model.as_deref().unwrap_or("<default>"),
+        request.messages.len(),
+        preview
+    )
+}
+
+/// Client decorator that logs every completion to a JSON Lines file.
+///
+/// Streaming completions are recorded through [`LLMClient::complete`], so
+/// the wrapped client answers them in one chunk. Embeddings pass through
+/// unrecorded.
+pub struct RecordingLLMClient {
+    inner: Arc<dyn LLMClient>,
+    path: PathBuf,
+    file: Mutex<std::fs::File>,
+}
+
+impl RecordingLLMClient {
+    /// Wrap `inner`, appending exchanges to `path`.
+    pub fn new(inner: Arc<dyn LLMClient>, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| Error::Internal(format!("Failed to open recording file: {}", e)))?;
+        Ok(Self {
+            inner,
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// File the exchanges are written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn append(&self, exchange: &RecordedExchange) -> Result<()> {
+        let mut line = serde_json::to_string(exchange)?;
+        line.push('\n');
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|e| Error::Internal(format!("Recording file lock poisoned: {}", e)))?;
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|e| Error::Internal(format!("Failed to write recording: {}", e)))
+    }
+}
+
+#[async_trait]
+impl LLMClient for RecordingLLMClient {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let request_hash = request_hash(&request)?;
+        let response = self.inner.complete(request.clone()).await?;
+        self.append(&RecordedExchange {
+            request_hash,
+            provider: self.inner.provider(),
+            request,
+            response: response.clone(),
+        })?;
+        Ok(response)
+    }
+
+    async fn embed(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        self.inner.embed(request).await
+    }
+
+    fn provider(&self) -> Provider {
+        self.inner.provider()
+    }
+
+    fn available_models(&self) -> Vec<ModelSpec> {
+        self.inner.available_models()
+    }
+
+    fn supports_logprobs(&self) -> bool {
+        self.inner.supports_logprobs()
+    }
+}
+
+/// Client that answers completions from a recording.
+pub struct ReplayLLMClient {
+    provider: Provider,
+    exchanges: Vec<RecordedExchange>,
+    consumed: Mutex<Vec<bool>>,
+}
+
+impl ReplayLLMClient {
+    /// Replay the given exchanges.
+    pub fn new(exchanges: Vec<RecordedExchange>) -> Self {
+        let provider = exchanges
+            .first()
+            .map(|e| e.provider)
+            .unwrap_or(Provider::Anthropic);
+        let consumed = Mutex::new(vec![false; exchanges.len()]);
+        Self {
+            provider,
+            exchanges,
+            consumed,
+        }
+    }
+
+    /// Load a file written by [`RecordingLLMClient`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let contents = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| Error::Internal(format!("Failed to load recording: {}", e)))?;
+        let exchanges = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<std::result::Result<Vec<RecordedExchange>, _>>()?;
+        Ok(Self::new(exchanges))
+    }
+
+    /// Recorded exchanges not yet served.
+    pub fn remaining(&self) -> usize {
+        self.consumed
+            .lock()
+            .map(|consumed| consumed.iter().filter(|c| !**c).count())
+            .unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl LLMClient for ReplayLLMClient {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let hash = request_hash(&request)?;
+        let mut consumed = self
+            .consumed
+            .lock()
+            .map_err(|e| Error::Internal(format!("Replay state lock poisoned: {}", e)))?;
+        let index = self
+            .exchanges
+            .iter()
+            .zip(consumed.iter())
+            .position(|(e, used)| !used && e.request_hash == hash)
+            .ok_or_else(|| {
+                Error::LLM(format!(
+                    "Replay has no recorded response for request {} ({})",
+                    &hash[..16],
+                    request_preview(&request)
+                ))
+            })?;
+        consumed[index] = true;
+        Ok(self.exchanges[index].response.clone())
+    }
+
+    async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        Err(Error::LLM(
+            "Replay recordings do not contain embeddings".to_string(),
+        ))
+    }
+
+    fn provider(&self) -> Provider {
+        self.provider
+    }
+
+    fn available_models(&self) -> Vec<ModelSpec> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{ChatMessage, TokenUsage, TrackedClient};
+    use chrono::Utc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Answers with a counter so a second live run would differ.
+    struct CountingClient {
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl LLMClient for CountingClient {
+        async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(CompletionResponse {
+                id: format!("resp-{}", n),
+                model: request.model.unwrap_or_default(),
+                content: format!("answer {}", n),
+                stop_reason: None,
+                usage: TokenUsage {
+                    input_tokens: 100,
+                    output_tokens: 20,
+                    cache_read_tokens: None,
+                    cache_creation_tokens: None,
+                },
+                timestamp: Utc::now(),
+                cost: Some(0.25),
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        }
+
+        async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+            Err(Error::LLM("not implemented".into()))
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::OpenAI
+        }
+
+        fn available_models(&self) -> Vec<ModelSpec> {
+            Vec::new()
+        }
+    }
+
+    fn ask(text: &str) -> CompletionRequest {
+        CompletionRequest::new()
+            .with_model("gpt-4o")
+            .with_message(ChatMessage::user(text))
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.jsonl");
+
+        let live = Arc::new(CountingClient {
+            calls: AtomicU32::new(0),
+        });
+        let recorder = RecordingLLMClient::new(live, &path).unwrap();
+        let mut recorded = Vec::new();
+        for text in ["plan", "execute", "plan"] {
+            recorded.push(recorder.complete(ask(text)).await.unwrap().content);
+        }
+
+        let replay = Arc::new(ReplayLLMClient::load(&path).unwrap());
+        assert_eq!(replay.provider(), Provider::OpenAI);
+        assert_eq!(replay.remaining(), 3);
+
+        let tracked = TrackedClient::new(replay.clone());
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("trace_id".to_string(), "replayed".to_string());
+        let mut first = ask("plan");
+        first.metadata = Some(metadata);
+
+        let mut replayed = vec![tracked.complete(first).await.unwrap().content];
+        for text in ["execute", "plan"] {
+            replayed.push(tracked.complete(ask(text)).await.unwrap().content);
+        }
+        assert_eq!(replayed, recorded);
+        assert_eq!(replay.remaining(), 0);
+
+        let costs = tracked.get_costs().await;
+        assert_eq!(costs.request_count, 3);
+        assert!((costs.total_cost - 0.75).abs() < 1e-9);
+        assert_eq!(costs.total_input_tokens, 300);
+
+        let err = tracked.complete(ask("plan")).await.unwrap_err().to_string();
+        assert!(err.contains("no recorded response"));
+        assert!(err.contains("model=gpt-4o"));
+        assert!(err.contains("\"plan\""));
+    }
+}
@@ -0,0 +1,341 @@
+//! Retry with exponential backoff for transient provider errors.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+
+/// Retry policy for transient API failures (rate limits, overload, 5xx).
+///
+/// Delays grow exponentially from `base_delay`, capped at `max_delay`, with
+/// random jitter. A `Retry-After` header from the provider takes precedence
+/// over the computed delay. No retry is started that would end past the
+/// overall `deadline`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles for each retry after that
+    pub base_delay: Duration,
+    /// Upper bound on a computed delay
+    pub max_delay: Duration,
+    /// Random jitter as a fraction of the delay (0.0 - 1.0)
+    pub jitter: f64,
+    /// HTTP status codes that are retried
+    pub retryable_statuses: Vec<u16>,
+    /// Time limit across all attempts and delays
+    pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            // 529 is Anthropic's "overloaded" status.
+            retryable_statuses: vec![408, 429, 500, 502, 503, 504, 529],
+            deadline: Some(Duration::from_secs(300)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_retryable_statuses(mut self, statuses: Vec<u16>) -> Self {
+        self.retryable_statuses = statuses;
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Option<Duration>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Check if an HTTP status code should be retried.
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// Backoff delay before retry number `retry` (starting at 0), with jitter.
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        let factor = 2f64.powi(retry.min(30) as i32);
+        let base = (self.base_delay.as_secs_f64() * factor).min(self.max_delay.as_secs_f64());
+        let jitter = base * self.jitter * (2.0 * random_unit() - 1.0);
+        Duration::from_secs_f64((base + jitter).max(0.0))
+    }
+
+    /// Classify a failed HTTP response.
+    pub(super) fn classify(
+        &self,
+        status: u16,
+        retry_after: Option<Duration>,
+        error: Error,
+    ) -> AttemptError {
+        if self.is_retryable_status(status) {
+            AttemptError::Retryable { error, retry_after }
+        } else {
+            AttemptError::Fatal(error)
+        }
+    }
+
+    /// Run `attempt` until it succeeds, fails fatally, or retries run out.
+    ///
+    /// Returns the value and the number of retries it took.
+    pub(super) async fn run<T, F, Fut>(&self, mut attempt: F) -> Result<(T, u32)>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = std::result::Result<T, AttemptError>>,
+    {
+        let started = Instant::now();
+        let mut retries = 0;
+
+        loop {
+            let (error, retry_after) = match attempt().await {
+                Ok(value) => return Ok((value, retries)),
+                Err(AttemptError::Fatal(error)) => return Err(error),
+                Err(AttemptError::Retryable { error, retry_after }) => (error, retry_after),
+            };
+
+            if retries + 1 >= self.max_attempts {
+                return Err(error);
+            }
+
+            let delay = retry_after.unwrap_or_else(|| self.backoff_delay(retries));
+            if let Some(deadline) = self.deadline {
+                if started.elapsed() + delay > deadline {
+                    return Err(error);
+                }
+            }
+
+            tracing::debug!(retry = retries + 1, ?delay, %error, "retrying LLM request");
+            tokio::time::sleep(delay).await;
+            retries += 1;
+        }
+    }
+}
+
+/// Outcome of a failed attempt.
+#[derive(Debug)]
+pub(super) enum AttemptError {
+    /// Transient failure that may succeed on retry
+    Retryable {
+        error: Error,
+        retry_after: Option<Duration>,
+    },
+    /// Failure that retrying will not fix (auth, invalid request)
+    Fatal(Error),
+}
+
+impl AttemptError {
+    /// Classify a transport-level failure; timeouts and connection errors
+    /// are transient.
+    pub fn from_transport(error: reqwest::Error) -> Self {
+        let transient = error.is_timeout() || error.is_connect();
+        let error = Error::LLM(format!("HTTP request failed: {}", error));
+        if transient {
+            Self::Retryable {
+                error,
+                retry_after: None,
+            }
+        } else {
+            Self::Fatal(error)
+        }
+    }
+}
+
+/// Parse a `Retry-After` header given as seconds or an HTTP date.
+pub(super) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<f64>() {
+        return (secs >= 0.0).then(|| Duration::from_secs_f64(secs));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    (at.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+/// Uniform random value in `[0, 1)` from the standard library's hasher seed.
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(1))
+            .with_jitter(0.0)
+    }
+
+    fn retryable(message: &str) -> AttemptError {
+        AttemptError::Retryable {
+            error: Error::LLM(message.to_string()),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_and_caps() {
+        let policy = RetryPolicy::default()
+            .with_jitter(0.0)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(350));
+
+        assert_eq!(policy.backoff_delay(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff_delay(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff_delay(2), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_in_range() {
+        let policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(100))
+            .with_jitter(0.5);
+
+        for _ in 0..50 {
+            let delay = policy.backoff_delay(0);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(150));
+        }
+    }
+
+    #[test]
+    fn test_classify_status() {
+        let policy = RetryPolicy::default();
+
+        assert!(matches!(
+            policy.classify(429, None, Error::LLM("rate".into())),
+            AttemptError::Retryable { .. }
+        ));
+        assert!(matches!(
+            policy.classify(529, None, Error::LLM("overloaded".into())),
+            AttemptError::Retryable { .. }
+        ));
+        assert!(matches!(
+            policy.classify(401, None, Error::LLM("auth".into())),
+            AttemptError::Fatal(_)
+        ));
+        assert!(matches!(
+            policy.classify(400, None, Error::LLM("invalid".into())),
+            AttemptError::Fatal(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(3)));
+
+        let future = (Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(&future).unwrap());
+        let parsed = parse_retry_after(&headers).unwrap();
+        assert!(parsed > Duration::from_secs(55) && parsed <= Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let (value, retries) = fast_policy()
+            .run(|| async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(retryable("busy"))
+                } else {
+                    Ok("done")
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(value, "done");
+        assert_eq!(retries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_fatal_short_circuits() {
+        let calls = AtomicU32::new(0);
+        let result: Result<((), u32)> = fast_policy()
+            .run(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(AttemptError::Fatal(Error::LLM("unauthorized".into())))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_at_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let result: Result<((), u32)> = fast_policy()
+            .with_max_attempts(3)
+            .run(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(retryable("busy"))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_respects_deadline() {
+        let calls = AtomicU32::new(0);
+        let result: Result<((), u32)> = fast_policy()
+            .with_max_attempts(10)
+            .with_deadline(Some(Duration::from_secs(1)))
+            .run(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(AttemptError::Retryable {
+                    error: Error::LLM("slow down".into()),
+                    retry_after: Some(Duration::from_secs(5)),
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -5,6 +5,7 @@
 //! - Recursion depth (deeper calls use cheaper models)
 //! - Budget constraints
 //! - Provider availability
+//! - Observed latency, cost, and failures, when [`RouterStats`] are attached
 //!
 //! # Dual-Model Optimization
 //!
@@ -14,11 +15,24 @@
 //!
 //! This can achieve 30-50% cost savings without significant quality loss.
 
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::OnceLock;
+use std::time::Duration;
 
-use super::types::{ModelCallTier, ModelSpec, ModelTier, Provider};
+use crate::error::{Error, Result};
+
+use super::tokens::estimate_tokens;
+use super::types::{CompletionRequest, ModelCallTier, ModelSpec, ModelTier, Provider};
+
+/// Output tokens assumed when a request doesn't set `max_tokens`.
+const DEFAULT_OUTPUT_ESTIMATE: u64 = 1_000;
+
+/// Share of a traced call's tokens priced as output by
+/// [`DualModelConfig::estimate_savings`].
+pub const TRACE_OUTPUT_SHARE: f64 = 0.2;
 
 /// Query type classification for routing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -463,6 +477,69 @@ impl DualModelConfig {
             .switch_strategy
             .should_use_recursive(depth, tokens_used, None)
     }
+
+    /// Project the cost of a recorded call trace under this configuration
+    /// and under [`SwitchStrategy::AlwaysRoot`].
+    ///
+    /// Each entry is `(depth, tokens, query_type)`. Tokens are split into
+    /// input and output using [`TRACE_OUTPUT_SHARE`], and the running total
+    /// feeds token-based strategies. Calls classified as
+    /// [`QueryType::Extraction`] are priced on the extraction model; the
+    /// always-root baseline prices every call on the root model.
+    pub fn estimate_savings(&self, call_trace: &[(u32, u64, QueryType)]) -> SavingsEstimate {
+        let mut estimate = SavingsEstimate::default();
+        let mut tokens_used = 0;
+
+        for &(depth, tokens, query_type) in call_trace {
+            let output_tokens = (tokens as f64 * TRACE_OUTPUT_SHARE).round() as u64;
+            let input_tokens = tokens - output_tokens;
+
+            let model = if query_type == QueryType::Extraction {
+                estimate.extraction_calls += 1;
+                self.extraction_model()
+            } else if self.switch_strategy.should_use_recursive(
+                depth,
+                tokens_used,
+                Some(query_type),
+            ) {
+                estimate.recursive_calls += 1;
+                &self.recursive_model
+            } else {
+                estimate.root_calls += 1;
+                &self.root_model
+            };
+
+            estimate.projected_cost += model.calculate_cost(input_tokens, output_tokens);
+            estimate.baseline_cost += self.root_model.calculate_cost(input_tokens, output_tokens);
+            tokens_used += tokens;
+        }
+
+        estimate.savings = estimate.baseline_cost - estimate.projected_cost;
+        if estimate.baseline_cost > 0.0 {
+            estimate.savings_percentage = estimate.savings / estimate.baseline_cost * 100.0;
+        }
+        estimate
+    }
+}
+
+/// Projected cost of a call trace under a [`DualModelConfig`], from
+/// [`DualModelConfig::estimate_savings`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavingsEstimate {
+    /// Cost under the configuration's strategy (USD)
+    pub projected_cost: f64,
+    /// Cost with every call on the root model (USD)
+    pub baseline_cost: f64,
+    /// `baseline_cost - projected_cost` (USD)
+    pub savings: f64,
+    /// Savings as a percentage of the baseline
+    pub savings_percentage: f64,
+    /// Calls priced on the root model
+    pub root_calls: usize,
+    /// Calls priced on the recursive model
+    pub recursive_calls: usize,
+    /// Calls priced on the extraction model
+    pub extraction_calls: usize,
 }
 
 impl Default for DualModelConfig {
@@ -554,12 +631,201 @@ pub struct RoutingDecision {
     pub estimated_cost: Option<f64>,
 }
 
+/// Projected token usage for a request, used to price candidate models.
+struct UsageEstimate<'a> {
+    /// Text whose tokens are estimated per provider
+    text: &'a str,
+    /// Fixed tokens added on top of the text (message overhead)
+    overhead: u64,
+    /// Expected output tokens
+    output_tokens: u64,
+}
+
+impl UsageEstimate<'_> {
+    fn cost(&self, model: &ModelSpec) -> f64 {
+        let input_tokens = estimate_tokens(self.text, model.provider) + self.overhead;
+        model.calculate_cost(input_tokens, self.output_tokens)
+    }
+}
+
+/// Observed outcomes for one model, as exponential moving averages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelStats {
+    /// Average latency in milliseconds
+    pub latency_ms: f64,
+    /// Average cost per request in USD
+    pub cost: f64,
+    /// Fraction of recent requests that failed or timed out (0.0 - 1.0)
+    pub failure_rate: f64,
+    /// Number of outcomes recorded
+    pub samples: u64,
+    /// When the last outcome was recorded
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome statistics the router uses to penalize unreliable models.
+///
+/// Each outcome moves a model's averages by `alpha`. Penalties also fade
+/// with time since the last outcome (halving every `half_life`), so a model
+/// that stops being routed to after an outage is retried eventually.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouterStats {
+    /// Weight of the newest outcome in each average (0.0 - 1.0)
+    pub alpha: f64,
+    /// Time for a model's penalty to halve without new outcomes
+    pub half_life: Duration,
+    /// Average latency above which a model is penalized
+    pub latency_threshold_ms: f64,
+    /// Penalties below this are ignored, so a faded penalty stops mattering
+    pub min_penalty: f64,
+    models: HashMap<String, ModelStats>,
+}
+
+impl Default for RouterStats {
+    fn default() -> Self {
+        Self {
+            alpha: 0.2,
+            half_life: Duration::from_secs(15 * 60),
+            latency_threshold_ms: 30_000.0,
+            min_penalty: 0.1,
+            models: HashMap::new(),
+        }
+    }
+}
+
+impl RouterStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_half_life(mut self, half_life: Duration) -> Self {
+        self.half_life = half_life;
+        self
+    }
+
+    pub fn with_latency_threshold_ms(mut self, threshold_ms: f64) -> Self {
+        self.latency_threshold_ms = threshold_ms;
+        self
+    }
+
+    pub fn with_min_penalty(mut self, min_penalty: f64) -> Self {
+        self.min_penalty = min_penalty;
+        self
+    }
+
+    /// Record the outcome of a request; timeouts count as failures.
+    pub fn record_outcome(&mut self, model_id: &str, latency_ms: u64, cost: f64, success: bool) {
+        let failure = if success { 0.0 } else { 1.0 };
+        let latency_ms = latency_ms as f64;
+        let now = Utc::now();
+
+        match self.models.get_mut(model_id) {
+            Some(stats) => {
+                let alpha = self.alpha;
+                stats.latency_ms += alpha * (latency_ms - stats.latency_ms);
+                stats.cost += alpha * (cost - stats.cost);
+                stats.failure_rate += alpha * (failure - stats.failure_rate);
+                stats.samples += 1;
+                stats.updated_at = now;
+            }
+            None => {
+                self.models.insert(
+                    model_id.to_string(),
+                    ModelStats {
+                        latency_ms,
+                        cost,
+                        failure_rate: failure,
+                        samples: 1,
+                        updated_at: now,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Statistics for a model, if any outcomes were recorded.
+    pub fn get(&self, model_id: &str) -> Option<&ModelStats> {
+        self.models.get(model_id)
+    }
+
+    /// Mutable statistics for a model.
+    pub fn get_mut(&mut self, model_id: &str) -> Option<&mut ModelStats> {
+        self.models.get_mut(model_id)
+    }
+
+    /// Penalty for routing to a model, 0.0 when it has behaved as expected.
+    ///
+    /// Sums the failure rate, the fraction by which average latency exceeds
+    /// the threshold, and the fraction by which average cost exceeds
+    /// `expected_cost`, then decays the sum by time since the last outcome.
+    /// Returns 0.0 when the result is below `min_penalty`.
+    pub fn penalty(&self, model_id: &str, expected_cost: f64) -> f64 {
+        self.penalty_at(model_id, expected_cost, Utc::now())
+    }
+
+    /// [`penalty`](Self::penalty) as of `now` rather than the current time.
+    pub fn penalty_at(&self, model_id: &str, expected_cost: f64, now: DateTime<Utc>) -> f64 {
+        let Some(stats) = self.models.get(model_id) else {
+            return 0.0;
+        };
+
+        let latency = if self.latency_threshold_ms > 0.0 {
+            (stats.latency_ms / self.latency_threshold_ms - 1.0).max(0.0)
+        } else {
+            0.0
+        };
+        let cost = if expected_cost > 0.0 {
+            (stats.cost / expected_cost - 1.0).max(0.0)
+        } else {
+            0.0
+        };
+
+        let penalty = (stats.failure_rate + latency + cost) * self.decay(stats.updated_at, now);
+        if penalty < self.min_penalty {
+            0.0
+        } else {
+            penalty
+        }
+    }
+
+    fn decay(&self, updated_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let half_life = self.half_life.as_secs_f64();
+        if half_life <= 0.0 {
+            return 1.0;
+        }
+        let elapsed = (now - updated_at).num_milliseconds().max(0) as f64 / 1000.0;
+        0.5f64.powf(elapsed / half_life)
+    }
+
+    /// Short description of why a model is penalized, for routing reasons.
+    fn describe(&self, model_id: &str, expected_cost: f64) -> String {
+        match self.models.get(model_id) {
+            Some(stats) => format!(
+                "{} penalized {:.2}: {:.0}% failures, {:.0}ms avg latency, ${:.4} avg cost",
+                model_id,
+                self.penalty(model_id, expected_cost),
+                stats.failure_rate * 100.0,
+                stats.latency_ms,
+                stats.cost,
+            ),
+            None => model_id.to_string(),
+        }
+    }
+}
+
 /// Smart router for model selection.
 pub struct SmartRouter {
     /// Available models
     models: Vec<ModelSpec>,
     /// Default model for each tier
     tier_defaults: TierDefaults,
+    /// Observed outcomes, if the router is learning
+    stats: Option<RouterStats>,
 }
 
 /// Default models for each tier.
@@ -582,6 +848,9 @@ impl Default for TierDefaults {
 
 impl SmartRouter {
     /// Create a new router with default Anthropic models.
+    ///
+    /// Other providers' models (e.g. Gemini) join via
+    /// [`add_model`](Self::add_model) or [`with_models`](Self::with_models).
     pub fn new() -> Self {
         Self {
             models: vec![
@@ -592,6 +861,7 @@ impl SmartRouter {
                 ModelSpec::gpt4o_mini(),
             ],
             tier_defaults: TierDefaults::default(),
+            stats: None,
         }
     }
 
@@ -600,22 +870,22 @@ impl SmartRouter {
         // Find best model for each tier
         let flagship = models
             .iter()
-            .filter(|m| m.tier == ModelTier::Flagship)
-            .min_by(|a, b| a.input_cost_per_m.partial_cmp(&b.input_cost_per_m).unwrap())
+            .filter(|m| m.routing_tier() == ModelTier::Flagship)
+            .min_by(|a, b| a.input_cost_per_m.total_cmp(&b.input_cost_per_m))
             .cloned()
             .unwrap_or_else(ModelSpec::claude_opus);
 
         let balanced = models
             .iter()
-            .filter(|m| m.tier == ModelTier::Balanced)
-            .min_by(|a, b| a.input_cost_per_m.partial_cmp(&b.input_cost_per_m).unwrap())
+            .filter(|m| m.routing_tier() == ModelTier::Balanced)
+            .min_by(|a, b| a.input_cost_per_m.total_cmp(&b.input_cost_per_m))
             .cloned()
             .unwrap_or_else(ModelSpec::claude_sonnet);
 
         let fast = models
             .iter()
-            .filter(|m| m.tier == ModelTier::Fast)
-            .min_by(|a, b| a.input_cost_per_m.partial_cmp(&b.input_cost_per_m).unwrap())
+            .filter(|m| m.routing_tier() == ModelTier::Fast)
+            .min_by(|a, b| a.input_cost_per_m.total_cmp(&b.input_cost_per_m))
             .cloned()
             .unwrap_or_else(ModelSpec::claude_haiku);
 
@@ -626,6 +896,7 @@ impl SmartRouter {
                 balanced,
                 fast,
             },
+            stats: None,
         }
     }
 
@@ -635,32 +906,160 @@ impl SmartRouter {
         self
     }
 
+    /// Route using observed outcome statistics.
+    pub fn with_stats(mut self, stats: RouterStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Observed outcome statistics, if any.
+    pub fn stats(&self) -> Option<&RouterStats> {
+        self.stats.as_ref()
+    }
+
+    /// Record a request outcome, enabling statistics if needed.
+    pub fn record_outcome(&mut self, model_id: &str, latency_ms: u64, cost: f64, success: bool) {
+        self.stats
+            .get_or_insert_with(RouterStats::default)
+            .record_outcome(model_id, latency_ms, cost, success);
+    }
+
     /// Route a query to the best model.
+    ///
+    /// When the context has a remaining budget, the query's estimated cost
+    /// is checked against it and the tier is downgraded until a model fits.
+    /// If none fits, the cheapest eligible model is returned; use
+    /// [`SmartRouter::route_request`] to reject instead.
     pub fn route(&self, query: &str, context: &RoutingContext) -> RoutingDecision {
+        let estimate = UsageEstimate {
+            text: query,
+            overhead: 0,
+            output_tokens: DEFAULT_OUTPUT_ESTIMATE,
+        };
+        self.route_estimated(query, &estimate, context).0
+    }
+
+    /// Route a full completion request, rejecting it if over budget.
+    ///
+    /// Input tokens are estimated from the system prompt and messages, and
+    /// output tokens from `max_tokens`. Returns
+    /// [`Error::BudgetExhausted`] when no eligible model's estimated cost
+    /// fits `context.remaining_budget`.
+    ///
+    /// Requests with images only route to vision-capable models, and fail
+    /// with [`Error::Config`] when the router has none.
+    pub fn route_request(
+        &self,
+        request: &CompletionRequest,
+        context: &RoutingContext,
+    ) -> Result<RoutingDecision> {
+        let vision_context;
+        let context = if request.has_images() && !context.require_vision {
+            vision_context = context.clone().requiring_vision();
+            &vision_context
+        } else {
+            context
+        };
+
+        let query = request
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.role == super::types::ChatRole::User)
+            .map(|m| m.content.as_str())
+            .unwrap_or_default();
+
+        let mut text = request.system.clone().unwrap_or_default();
+        for message in &request.messages {
+            text.push('\n');
+            text.push_str(&message.content);
+        }
+        let estimate = UsageEstimate {
+            text: &text,
+            overhead: super::tokens::MESSAGE_OVERHEAD_TOKENS
+                * (request.messages.len() as u64 + u64::from(request.system.is_some())),
+            output_tokens: request
+                .max_tokens
+                .map(u64::from)
+                .unwrap_or(DEFAULT_OUTPUT_ESTIMATE),
+        };
+
+        let (decision, fits) = self.route_estimated(query, &estimate, context);
+        if request.has_images() && !decision.model.supports_vision {
+            return Err(Error::Config(format!(
+                "no vision-capable model available for a request with images (routed to {})",
+                decision.model.id
+            )));
+        }
+        if !fits {
+            return Err(Error::budget_exhausted(format!(
+                "estimated cost ${:.4} exceeds remaining budget ${:.4}",
+                decision.estimated_cost.unwrap_or_default(),
+                context.remaining_budget.unwrap_or_default(),
+            )));
+        }
+        Ok(decision)
+    }
+
+    /// Route with a usage estimate, reporting whether the choice fits the budget.
+    fn route_estimated(
+        &self,
+        query: &str,
+        estimate: &UsageEstimate<'_>,
+        context: &RoutingContext,
+    ) -> (RoutingDecision, bool) {
         let query_type = QueryType::classify(query);
         let base_tier = query_type.base_tier();
 
         // Adjust tier based on depth (deeper = cheaper)
-        let adjusted_tier = self.adjust_tier_for_depth(base_tier, context.depth);
+        let depth_tier = self.adjust_tier_for_depth(base_tier, context.depth);
+
+        // Downgrade until a model fits the budget
+        let mut adjusted_tier = depth_tier;
+        let mut selected = self.select_model(adjusted_tier, context, estimate);
+        while selected.is_none() {
+            match adjusted_tier.downgrade() {
+                Some(lower) => {
+                    adjusted_tier = lower;
+                    selected = self.select_model(adjusted_tier, context, estimate);
+                }
+                None => break,
+            }
+        }
 
-        // Find best model matching requirements
-        let model = self.select_model(adjusted_tier, context);
+        let fits = selected.is_some();
+        let (model, stats_note) = match selected {
+            Some((model, note)) => (model, note),
+            None => (self.cheapest_model(context, estimate), None),
+        };
+        let estimated_cost = estimate.cost(&model);
 
-        let reason = format!(
+        let mut reason = format!(
             "Query type '{}' at depth {} -> {} tier (adjusted from {})",
             format!("{:?}", query_type).to_lowercase(),
             context.depth,
             format!("{:?}", adjusted_tier).to_lowercase(),
             format!("{:?}", base_tier).to_lowercase(),
         );
+        if adjusted_tier != depth_tier {
+            reason.push_str("; downgraded to fit budget");
+        }
+        if !fits {
+            reason.push_str("; estimated cost exceeds budget");
+        }
+        if let Some(note) = stats_note {
+            reason.push_str("; ");
+            reason.push_str(&note);
+        }
 
-        RoutingDecision {
+        let decision = RoutingDecision {
             model,
             query_type,
             tier: adjusted_tier,
             reason,
-            estimated_cost: None,
-        }
+            estimated_cost: Some(estimated_cost),
+        };
+        (decision, fits)
     }
 
     /// Route an RLM query using dual-model configuration.
@@ -740,12 +1139,18 @@ impl SmartRouter {
             tier_label, context.depth, config.switch_strategy, query_type,
         );
 
+        let estimate = UsageEstimate {
+            text: query,
+            overhead: 0,
+            output_tokens: DEFAULT_OUTPUT_ESTIMATE,
+        };
+
         RoutingDecision {
             model: model.clone(),
             query_type,
             tier: model.tier,
             reason,
-            estimated_cost: None,
+            estimated_cost: Some(estimate.cost(model)),
         }
     }
 
@@ -778,43 +1183,121 @@ impl SmartRouter {
         }
     }
 
+    /// Whether a model meets the context's provider and capability requirements.
+    fn is_eligible(model: &ModelSpec, context: &RoutingContext) -> bool {
+        // Check provider preference
+        context.preferred_provider.map_or(true, |p| model.provider == p)
+            // Check capability requirements
+            && (!context.require_caching || model.supports_caching)
+            && (!context.require_vision || model.supports_vision)
+            && (!context.require_tools || model.supports_tools)
+    }
+
     /// Select the best model for the tier and constraints.
-    fn select_model(&self, tier: ModelTier, context: &RoutingContext) -> ModelSpec {
+    ///
+    /// Returns `None` when a budget is set and no model at or above the tier
+    /// fits it. Without a budget, falls back to the tier default. With
+    /// stats, the least penalized candidate wins and a note explains any
+    /// model passed over.
+    fn select_model(
+        &self,
+        tier: ModelTier,
+        context: &RoutingContext,
+        estimate: &UsageEstimate<'_>,
+    ) -> Option<(ModelSpec, Option<String>)> {
         // Filter models by requirements
         let candidates: Vec<_> = self
             .models
             .iter()
             .filter(|m| {
                 // Check tier
-                m.tier <= tier
-                    // Check provider preference
-                    && context.preferred_provider.map_or(true, |p| m.provider == p)
-                    // Check capability requirements
-                    && (!context.require_caching || m.supports_caching)
-                    && (!context.require_vision || m.supports_vision)
-                    && (!context.require_tools || m.supports_tools)
-                    // Check budget (rough estimate for 10k tokens)
-                    && context.remaining_budget.map_or(true, |b| {
-                        m.calculate_cost(10_000, 1_000) < b
-                    })
+                m.routing_tier() <= tier
+                    && Self::is_eligible(m, context)
+                    // Check budget against the estimated cost
+                    && context
+                        .remaining_budget
+                        .map_or(true, |b| estimate.cost(m) <= b)
             })
             .collect();
 
+        let penalty = |m: &ModelSpec| {
+            self.stats
+                .as_ref()
+                .map_or(0.0, |s| s.penalty(&m.id, estimate.cost(m)))
+        };
+        let by_cost =
+            |a: &&&ModelSpec, b: &&&ModelSpec| a.input_cost_per_m.total_cmp(&b.input_cost_per_m);
+        let by_penalty_then_cost = |a: &&&ModelSpec, b: &&&ModelSpec| {
+            penalty(a)
+                .total_cmp(&penalty(b))
+                .then_with(|| by_cost(a, b))
+        };
+
         // Pick the best candidate (prefer exact tier match, then cheapest)
-        candidates
+        let exact: Vec<&&ModelSpec> = candidates
+            .iter()
+            .filter(|m| m.routing_tier() == tier)
+            .collect();
+        let pool: Vec<&&ModelSpec> = if exact.is_empty() {
+            candidates.iter().collect()
+        } else {
+            exact
+        };
+
+        let best = pool
+            .iter()
+            .copied()
+            .min_by(by_penalty_then_cost)
+            .map(|m| (*m).clone());
+        let note = match (&self.stats, &best, pool.iter().copied().min_by(by_cost)) {
+            (Some(stats), Some(best), Some(unadjusted)) if unadjusted.id != best.id => {
+                Some(format!(
+                    "chose {} over {}",
+                    best.id,
+                    stats.describe(&unadjusted.id, estimate.cost(unadjusted))
+                ))
+            }
+            _ => None,
+        };
+
+        match best {
+            Some(model) => Some((model, note)),
+            None if context.remaining_budget.is_some() => None,
+            None => Some((self.fallback_model(tier, context), None)),
+        }
+    }
+
+    /// Model used when no candidate matches the tier.
+    ///
+    /// The tier default, unless it is ineligible (e.g. a preferred provider
+    /// it doesn't belong to, or a missing capability). Then the most capable
+    /// eligible model below the tier is used, so a provider without a
+    /// flagship (e.g. Gemini) still serves flagship queries.
+    fn fallback_model(&self, tier: ModelTier, context: &RoutingContext) -> ModelSpec {
+        let default = self.tier_default(tier);
+        if Self::is_eligible(&default, context) {
+            return default;
+        }
+        self.models
             .iter()
-            .filter(|m| m.tier == tier)
-            .min_by(|a, b| a.input_cost_per_m.partial_cmp(&b.input_cost_per_m).unwrap())
+            .filter(|m| m.routing_tier() >= tier && Self::is_eligible(m, context))
+            .min_by(|a, b| {
+                a.routing_tier()
+                    .cmp(&b.routing_tier())
+                    .then_with(|| a.input_cost_per_m.total_cmp(&b.input_cost_per_m))
+            })
             .cloned()
+            .unwrap_or(default)
+    }
+
+    /// The eligible model with the lowest estimated cost.
+    fn cheapest_model(&self, context: &RoutingContext, estimate: &UsageEstimate<'_>) -> ModelSpec {
+        self.models
+            .iter()
+            .filter(|m| Self::is_eligible(m, context))
+            .min_by(|a, b| estimate.cost(a).total_cmp(&estimate.cost(b)))
             .cloned()
-            .or_else(|| {
-                candidates
-                    .iter()
-                    .min_by(|a, b| a.input_cost_per_m.partial_cmp(&b.input_cost_per_m).unwrap())
-                    .cloned()
-                    .cloned()
-            })
-            .unwrap_or_else(|| self.tier_default(tier))
+            .unwrap_or_else(|| self.tier_default(ModelTier::Fast))
     }
 
     /// Get the default model for a tier.
@@ -969,6 +1452,28 @@ mod tests {
         assert_eq!(decision.model.provider, Provider::OpenAI);
     }
 
+    #[cfg(feature = "gemini")]
+    #[test]
+    fn test_router_routes_gemini_tiers() {
+        let mut router = SmartRouter::new();
+        router.add_model(ModelSpec::gemini_2_0_flash());
+        router.add_model(ModelSpec::gemini_1_5_pro());
+        let context = RoutingContext::new().with_provider(Provider::Google);
+
+        let simple = router.route("Hello, how are you?", &context);
+        assert_eq!(simple.model.id, "gemini-2.0-flash");
+        assert_eq!(simple.tier, ModelTier::Fast);
+
+        let debugging = router.route("Why is this test failing?", &context);
+        assert_eq!(debugging.model.id, "gemini-1.5-pro");
+        assert_eq!(debugging.tier, ModelTier::Balanced);
+
+        // No Gemini flagship: architecture work goes to the strongest Gemini
+        let architecture = router.route("Design the system architecture", &context);
+        assert_eq!(architecture.model.provider, Provider::Google);
+        assert_eq!(architecture.model.id, "gemini-1.5-pro");
+    }
+
     #[test]
     fn test_router_caching_requirement() {
         let router = SmartRouter::new();
@@ -978,6 +1483,75 @@ mod tests {
         assert!(decision.model.supports_caching);
     }
 
+    #[test]
+    fn test_route_reports_estimated_cost() {
+        let router = SmartRouter::new();
+        let decision = router.route("Design a new architecture", &RoutingContext::new());
+
+        let expected = decision.model.calculate_cost(
+            estimate_tokens("Design a new architecture", Provider::Anthropic),
+            1_000,
+        );
+        assert_eq!(decision.estimated_cost, Some(expected));
+    }
+
+    #[test]
+    fn test_route_downgrades_to_fit_budget() {
+        let router = SmartRouter::new();
+        let query = "Design a new architecture";
+        let unbounded = router.route(query, &RoutingContext::new());
+        assert_eq!(unbounded.tier, ModelTier::Flagship);
+
+        // Enough for a balanced model but not a flagship one.
+        let budget = ModelSpec::claude_sonnet().calculate_cost(100, 1_000);
+        let decision = router.route(query, &RoutingContext::new().with_budget(budget));
+
+        assert_eq!(decision.tier, ModelTier::Balanced);
+        assert!(decision.estimated_cost.unwrap() <= budget);
+        assert!(decision.reason.contains("downgraded"));
+    }
+
+    #[test]
+    fn test_route_request_rejects_over_budget() {
+        let router = SmartRouter::new();
+        let request = CompletionRequest::new()
+            .with_message(super::super::types::ChatMessage::user("x ".repeat(50_000)))
+            .with_max_tokens(4_000);
+
+        let result = router.route_request(&request, &RoutingContext::new().with_budget(0.001));
+        assert!(matches!(result, Err(Error::BudgetExhausted { .. })));
+
+        let decision = router
+            .route_request(&request, &RoutingContext::new().with_budget(10.0))
+            .unwrap();
+        assert!(decision.estimated_cost.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_route_request_with_images_requires_vision() {
+        let request = CompletionRequest::new().with_message(
+            super::super::types::ChatMessage::user_with_images(
+                "What does this screenshot show?",
+                vec![super::super::types::ImagePart::base64("AAAA", "image/png")],
+            ),
+        );
+
+        let router = SmartRouter::with_models(vec![
+            ModelSpec::llama3_1_8b(),
+            ModelSpec::qwen2_5_32b(),
+            ModelSpec::gpt4o_mini(),
+        ]);
+        let decision = router
+            .route_request(&request, &RoutingContext::new())
+            .unwrap();
+        assert_eq!(decision.model.id, ModelSpec::gpt4o_mini().id);
+
+        let local_only =
+            SmartRouter::with_models(vec![ModelSpec::llama3_1_8b(), ModelSpec::qwen2_5_32b()]);
+        let result = local_only.route_request(&request, &RoutingContext::new());
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
     #[test]
     fn test_routing_context_builder() {
         let context = RoutingContext::new()
@@ -1018,6 +1592,116 @@ mod tests {
         assert_eq!(decision.tier, ModelTier::Balanced);
     }
 
+    #[test]
+    fn test_local_models_route_as_fast_or_balanced() {
+        let oversized = ModelSpec::ollama("llama3.1:405b", ModelTier::Flagship);
+        assert_eq!(oversized.routing_tier(), ModelTier::Balanced);
+
+        let router = SmartRouter::with_models(vec![
+            ModelSpec::claude_opus(),
+            ModelSpec::llama3_1_8b(),
+            ModelSpec::qwen2_5_32b(),
+        ]);
+        let context = RoutingContext::new();
+
+        let simple = router.route("Hello, how are you?", &context);
+        assert_eq!(simple.model.id, "llama3.1:8b");
+        assert_eq!(simple.estimated_cost, Some(0.0));
+
+        let debugging = router.route("Why is this test failing?", &context);
+        assert_eq!(debugging.model.id, "qwen2.5:32b");
+
+        // Local models never stand in for the flagship default.
+        let router = SmartRouter::with_models(vec![oversized, ModelSpec::qwen2_5_32b()]);
+        assert_eq!(
+            router.tier_default(ModelTier::Flagship).id,
+            "claude-3-opus-20240229"
+        );
+    }
+
+    #[test]
+    fn test_router_without_stats_is_unchanged() {
+        let plain = SmartRouter::new();
+        let mut learning = SmartRouter::new().with_stats(RouterStats::new());
+        learning.record_outcome("unrelated-model", 90_000, 5.0, false);
+        let context = RoutingContext::new();
+
+        for query in [
+            "Hello, how are you?",
+            "Why is this test failing?",
+            "Design the architecture for a new service",
+        ] {
+            let (a, b) = (
+                plain.route(query, &context),
+                learning.route(query, &context),
+            );
+            assert_eq!(a.model.id, b.model.id);
+            assert_eq!(a.reason, b.reason);
+        }
+    }
+
+    #[test]
+    fn test_router_avoids_failing_model_within_tier() {
+        let mut router = SmartRouter::new();
+        let context = RoutingContext::new();
+        let query = "Why is this test failing?";
+        assert_eq!(router.route(query, &context).model.id, "gpt-4o");
+
+        for _ in 0..3 {
+            router.record_outcome("gpt-4o", 120_000, 0.01, false);
+        }
+
+        let decision = router.route(query, &context);
+        assert_eq!(decision.model.id, "claude-3-5-sonnet-20241022");
+        assert_eq!(decision.tier, ModelTier::Balanced);
+        assert!(decision
+            .reason
+            .contains("chose claude-3-5-sonnet-20241022 over gpt-4o penalized"));
+        assert!(decision.reason.contains("100% failures"));
+    }
+
+    #[test]
+    fn test_router_stats_penalize_cost_overrun() {
+        let mut stats = RouterStats::new();
+        stats.record_outcome("m", 1_000, 0.05, true);
+        let now = stats.get("m").unwrap().updated_at;
+
+        assert_eq!(stats.penalty_at("m", 0.05, now), 0.0);
+        assert!((stats.penalty_at("m", 0.01, now) - 4.0).abs() < 1e-6);
+        assert_eq!(stats.penalty_at("unknown", 0.01, now), 0.0);
+    }
+
+    #[test]
+    fn test_router_stats_moving_average_and_decay() {
+        let mut stats = RouterStats::new().with_alpha(0.5);
+        stats.record_outcome("m", 1_000, 0.0, false);
+        stats.record_outcome("m", 3_000, 0.0, true);
+        stats.record_outcome("m", 3_000, 0.0, true);
+
+        let recorded = stats.get("m").unwrap();
+        assert_eq!(recorded.samples, 3);
+        assert_eq!(recorded.failure_rate, 0.25);
+        assert_eq!(recorded.latency_ms, 2_500.0);
+        assert_eq!(stats.penalty_at("m", 0.0, recorded.updated_at), 0.25);
+
+        // An outage two hours ago (eight half-lives) has faded out.
+        stats.record_outcome("down", 60_000, 0.0, false);
+        stats.get_mut("down").unwrap().updated_at = Utc::now() - chrono::Duration::hours(2);
+        assert_eq!(stats.penalty("down", 0.0), 0.0);
+
+        let mut router = SmartRouter::new().with_stats(stats);
+        router.record_outcome("gpt-4o", 60_000, 0.0, false);
+        router
+            .stats
+            .as_mut()
+            .unwrap()
+            .get_mut("gpt-4o")
+            .unwrap()
+            .updated_at = Utc::now() - chrono::Duration::hours(2);
+        let decision = router.route("Why is this test failing?", &RoutingContext::new());
+        assert_eq!(decision.model.id, "gpt-4o");
+    }
+
     // ==========================================================================
     // Dual-Model Configuration Tests
     // ==========================================================================
@@ -1185,6 +1869,45 @@ mod tests {
         assert_eq!(model.id, "claude-3-5-sonnet-20241022");
     }
 
+    #[test]
+    fn test_estimate_savings_ranks_strategies() {
+        let trace = [
+            (0, 8_000, QueryType::Architecture),
+            (1, 4_000, QueryType::MultiFile),
+            (1, 3_000, QueryType::Debugging),
+            (2, 2_000, QueryType::Simple),
+            (2, 2_000, QueryType::Extraction),
+            (3, 1_000, QueryType::Simple),
+        ];
+
+        let aggressive = DualModelConfig::aggressive().estimate_savings(&trace);
+        let quality = DualModelConfig::quality_first().estimate_savings(&trace);
+        assert_eq!(aggressive.baseline_cost, quality.baseline_cost);
+        assert!(aggressive.savings > quality.savings);
+        assert!(aggressive.savings_percentage > quality.savings_percentage);
+        assert!(quality.savings > 0.0);
+
+        assert_eq!(aggressive.root_calls, 1);
+        assert_eq!(aggressive.recursive_calls, 4);
+        assert_eq!(aggressive.extraction_calls, 1);
+        assert_eq!(quality.root_calls, 4);
+
+        let expected = ModelSpec::claude_opus().calculate_cost(16_000, 4_000);
+        assert!((aggressive.baseline_cost - expected).abs() < 1e-9);
+
+        let always_root = DualModelConfig::aggressive()
+            .with_strategy(SwitchStrategy::AlwaysRoot)
+            .with_extraction_model(ModelSpec::claude_opus());
+        let none = always_root.estimate_savings(&trace);
+        assert_eq!(none.savings, 0.0);
+        assert_eq!(
+            DualModelConfig::balanced()
+                .estimate_savings(&[])
+                .savings_percentage,
+            0.0
+        );
+    }
+
     #[test]
     fn test_route_rlm() {
         let router = SmartRouter::new();
@@ -0,0 +1,258 @@
+//! Streaming distribution summaries for per-call samples.
+//!
+//! [`SampleStats`] keeps the count, sum, minimum and maximum exactly and
+//! estimates percentiles with a log-bucketed sketch: a sample `x > 0` lands
+//! in bucket `ceil(log_γ(x))` with `γ = (1 + α) / (1 − α)`, so every
+//! percentile is within relative error `α` of a recorded value. Memory grows
+//! with the logarithm of the value range rather than with the number of
+//! samples, and is capped at [`MAX_SKETCH_BUCKETS`] by folding the lowest
+//! buckets together.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Relative accuracy of percentile estimates (1%).
+pub const DEFAULT_RELATIVE_ACCURACY: f64 = 0.01;
+
+/// Bucket limit; at 1% accuracy this spans about 18 orders of magnitude.
+pub const MAX_SKETCH_BUCKETS: usize = 2048;
+
+/// Count, mean, maximum and approximate percentiles of a stream of samples.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleStats {
+    relative_accuracy: f64,
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    /// Samples equal to zero, which have no logarithmic bucket
+    zero_count: u64,
+    buckets: BTreeMap<i32, u64>,
+}
+
+impl Default for SampleStats {
+    fn default() -> Self {
+        Self::with_accuracy(DEFAULT_RELATIVE_ACCURACY)
+    }
+}
+
+impl SampleStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sketch whose percentiles are within `relative_accuracy` (0–1).
+    pub fn with_accuracy(relative_accuracy: f64) -> Self {
+        Self {
+            relative_accuracy: relative_accuracy.clamp(1e-4, 0.5),
+            count: 0,
+            sum: 0.0,
+            min: 0.0,
+            max: 0.0,
+            zero_count: 0,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// Record a sample. Negative values count as zero; NaN is ignored.
+    pub fn record(&mut self, value: f64) {
+        self.record_n(value, 1);
+    }
+
+    fn record_n(&mut self, value: f64, n: u64) {
+        if value.is_nan() || n == 0 {
+            return;
+        }
+        let value = value.max(0.0);
+
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += n;
+        self.sum += value * n as f64;
+
+        if value == 0.0 {
+            self.zero_count += n;
+        } else {
+            *self.buckets.entry(self.bucket(value)).or_insert(0) += n;
+            self.collapse();
+        }
+    }
+
+    /// Number of samples recorded.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Mean of the samples, or `None` if there are none.
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// Largest sample, or `None` if there are none.
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Smallest sample, or `None` if there are none.
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Estimated `p`th percentile (0–100), or `None` if there are no samples.
+    ///
+    /// The 0th and 100th percentiles are the exact minimum and maximum.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let p = p.clamp(0.0, 100.0);
+        if p == 0.0 {
+            return Some(self.min);
+        }
+        if p == 100.0 {
+            return Some(self.max);
+        }
+
+        // Zero-based rank of the sample at this percentile
+        let rank = (p / 100.0 * (self.count - 1) as f64).round() as u64;
+        if rank < self.zero_count {
+            return Some(0.0);
+        }
+        let mut seen = self.zero_count;
+        for (&key, &n) in &self.buckets {
+            seen += n;
+            if rank < seen {
+                return Some(self.value(key).clamp(self.min, self.max));
+            }
+        }
+        Some(self.max)
+    }
+
+    /// Add another summary's samples to this one.
+    pub fn merge(&mut self, other: &SampleStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.relative_accuracy != other.relative_accuracy {
+            // Re-bucket at this sketch's accuracy, keeping exact aggregates
+            let (was_empty, min, max, sum) = (self.count == 0, self.min, self.max, self.sum);
+            self.record_n(0.0, other.zero_count);
+            for (&key, &n) in &other.buckets {
+                self.record_n(other.value(key), n);
+            }
+            self.sum = sum + other.sum;
+            (self.min, self.max) = if was_empty {
+                (other.min, other.max)
+            } else {
+                (min.min(other.min), max.max(other.max))
+            };
+            return;
+        }
+
+        if self.count == 0 {
+            self.min = other.min;
+            self.max = other.max;
+        } else {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+        self.count += other.count;
+        self.sum += other.sum;
+        self.zero_count += other.zero_count;
+        for (&key, &n) in &other.buckets {
+            *self.buckets.entry(key).or_insert(0) += n;
+        }
+        self.collapse();
+    }
+
+    fn gamma(&self) -> f64 {
+        (1.0 + self.relative_accuracy) / (1.0 - self.relative_accuracy)
+    }
+
+    fn bucket(&self, value: f64) -> i32 {
+        (value.ln() / self.gamma().ln()).ceil() as i32
+    }
+
+    /// Representative value of a bucket, equidistant in relative terms from
+    /// both of its bounds.
+    fn value(&self, key: i32) -> f64 {
+        let gamma = self.gamma();
+        2.0 * gamma.powi(key) / (gamma + 1.0)
+    }
+
+    /// Fold the lowest buckets together until under the limit.
+    fn collapse(&mut self) {
+        while self.buckets.len() > MAX_SKETCH_BUCKETS {
+            let Some((_, n)) = self.buckets.pop_first() else {
+                return;
+            };
+            if let Some(mut lowest) = self.buckets.first_entry() {
+                *lowest.get_mut() += n;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<f64>, expected: f64, tolerance: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() <= expected * tolerance,
+            "expected {} within {}, got {}",
+            expected,
+            tolerance,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_percentiles_of_known_sequence() {
+        let mut stats = SampleStats::new();
+        assert_eq!(stats.percentile(50.0), None);
+        assert_eq!(stats.mean(), None);
+
+        // Shuffled so insertion order doesn't matter
+        for i in 0..1000u64 {
+            stats.record(((i * 7919) % 1000 + 1) as f64);
+        }
+
+        assert_eq!(stats.count(), 1000);
+        assert_eq!(stats.mean(), Some(500.5));
+        assert_eq!(stats.max(), Some(1000.0));
+        assert_eq!(stats.percentile(0.0), Some(1.0));
+        assert_close(stats.percentile(50.0), 500.0, 0.02);
+        assert_close(stats.percentile(95.0), 950.0, 0.02);
+        assert_close(stats.percentile(99.0), 990.0, 0.02);
+        assert!(stats.buckets.len() < 400);
+    }
+
+    #[test]
+    fn test_zeros_and_merge() {
+        let mut a = SampleStats::new();
+        let mut b = SampleStats::new();
+        for _ in 0..50 {
+            a.record(0.0);
+            b.record(0.002);
+        }
+
+        a.merge(&b);
+        assert_eq!(a.count(), 100);
+        assert_eq!(a.percentile(25.0), Some(0.0));
+        assert_close(a.percentile(90.0), 0.002, 0.02);
+        assert_close(a.mean(), 0.001, 1e-9);
+
+        let mut coarse = SampleStats::with_accuracy(0.05);
+        coarse.merge(&a);
+        assert_eq!(coarse.count(), 100);
+        assert_eq!(coarse.max(), Some(0.002));
+        assert_close(coarse.mean(), 0.001, 1e-9);
+    }
+}
@@ -0,0 +1,1100 @@
+//! Server-sent event decoding for streamed completions.
+//!
+//! Anthropic and OpenAI both stream completions as SSE. The byte stream is
+//! split into events by [`SseDecoder`], and a provider-specific
+//! [`SseHandler`] turns events into [`StreamChunk`]s while assembling the
+//! final [`CompletionResponse`]. Ollama streams newline-delimited JSON
+//! instead, which [`LineDecoder`] splits into one event per line.
+
+use chrono::Utc;
+use futures::stream::{self, BoxStream, StreamExt};
+use futures::Stream;
+use serde::Deserialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::pin::Pin;
+
+use crate::error::{Error, Result};
+
+use super::types::{
+    CompletionResponse, ModelSpec, StopReason, StreamChunk, TokenUsage, ToolCall, ToolCallDelta,
+};
+
+/// Stream of completion chunks, ending with a chunk that carries the full response.
+pub type CompletionStream = Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send>>;
+
+/// Stream that yields a completed response as a single chunk.
+///
+/// Used by providers that cannot stream.
+pub fn single_chunk_stream(response: CompletionResponse) -> CompletionStream {
+    let mut first = StreamChunk::finished(response.clone());
+    first.delta = response.content;
+    Box::pin(stream::once(async move { Ok(first) }))
+}
+
+/// Stamp the retry count onto the final response of a stream.
+pub(super) fn with_retries(stream: CompletionStream, retries: u32) -> CompletionStream {
+    Box::pin(stream.map(move |chunk| {
+        chunk.map(|mut chunk| {
+            if let Some(response) = chunk.response.as_mut() {
+                response.retries = retries;
+            }
+            chunk
+        })
+    }))
+}
+
+pub(super) fn parse_anthropic_stop_reason(reason: &str) -> StopReason {
+    match reason {
+        "end_turn" => StopReason::EndTurn,
+        "max_tokens" => StopReason::MaxTokens,
+        "stop_sequence" => StopReason::StopSequence,
+        "tool_use" => StopReason::ToolUse,
+        _ => StopReason::EndTurn,
+    }
+}
+
+pub(super) fn parse_openai_stop_reason(reason: &str) -> StopReason {
+    match reason {
+        "stop" => StopReason::EndTurn,
+        "length" => StopReason::MaxTokens,
+        "tool_calls" => StopReason::ToolUse,
+        _ => StopReason::EndTurn,
+    }
+}
+
+/// Ollama reports tool calls with `done_reason: "stop"`, so they are
+/// checked separately.
+pub(super) fn parse_ollama_stop_reason(reason: &str, has_tool_calls: bool) -> StopReason {
+    match reason {
+        _ if has_tool_calls => StopReason::ToolUse,
+        "length" => StopReason::MaxTokens,
+        _ => StopReason::EndTurn,
+    }
+}
+
+/// A single server-sent event.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+}
+
+/// Incremental SSE decoder that tolerates events split across reads.
+#[derive(Debug, Default)]
+pub(super) struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    /// Feed bytes and return every event completed by them.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend(bytes.iter().filter(|&&b| b != b'\r'));
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.windows(2).position(|w| w == b"\n\n") {
+            let block: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            if let Some(event) = parse_event(&String::from_utf8_lossy(&block)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Flush an event left unterminated at end of stream.
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        let block = std::mem::take(&mut self.buffer);
+        parse_event(&String::from_utf8_lossy(&block))
+    }
+}
+
+fn parse_event(block: &str) -> Option<SseEvent> {
+    let mut event = None;
+    let mut data: Vec<&str> = Vec::new();
+
+    for line in block.lines() {
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => event = Some(value.to_string()),
+            "data" => data.push(value),
+            // Comments (empty field name), `id`, and `retry` are ignored.
+            _ => {}
+        }
+    }
+
+    if data.is_empty() {
+        return None;
+    }
+    Some(SseEvent {
+        event,
+        data: data.join("\n"),
+    })
+}
+
+/// Incremental decoder for newline-delimited JSON.
+///
+/// Each non-empty line becomes an unnamed event.
+#[derive(Debug, Default)]
+pub(super) struct LineDecoder {
+    buffer: Vec<u8>,
+}
+
+impl LineDecoder {
+    /// Feed bytes and return every line completed by them.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..end + 1).collect();
+            events.extend(line_event(&line));
+        }
+        events
+    }
+
+    /// Flush a final line without a trailing newline.
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        line_event(&std::mem::take(&mut self.buffer))
+    }
+}
+
+fn line_event(line: &[u8]) -> Option<SseEvent> {
+    let data = String::from_utf8_lossy(line).trim().to_string();
+    (!data.is_empty()).then_some(SseEvent { event: None, data })
+}
+
+/// How a response body is split into events.
+enum Framing {
+    Sse(SseDecoder),
+    Lines(LineDecoder),
+}
+
+impl Framing {
+    fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        match self {
+            Self::Sse(decoder) => decoder.push(bytes),
+            Self::Lines(decoder) => decoder.push(bytes),
+        }
+    }
+
+    fn finish(&mut self) -> Option<SseEvent> {
+        match self {
+            Self::Sse(decoder) => decoder.finish(),
+            Self::Lines(decoder) => decoder.finish(),
+        }
+    }
+}
+
+/// Assembles complete tool calls from streamed deltas.
+#[derive(Debug, Default)]
+struct ToolCallAssembler {
+    /// (id, name, raw arguments) keyed by call index
+    calls: BTreeMap<usize, (String, String, String)>,
+}
+
+impl ToolCallAssembler {
+    fn apply(&mut self, chunks: &[StreamChunk]) {
+        for delta in chunks.iter().filter_map(|c| c.tool_call.as_ref()) {
+            let (id, name, arguments) = self.calls.entry(delta.index).or_default();
+            if let Some(delta_id) = &delta.id {
+                id.clone_from(delta_id);
+            }
+            if let Some(delta_name) = &delta.name {
+                name.clone_from(delta_name);
+            }
+            arguments.push_str(&delta.arguments);
+        }
+    }
+
+    fn finish(&mut self) -> Vec<ToolCall> {
+        std::mem::take(&mut self.calls)
+            .into_values()
+            .map(|(id, name, arguments)| ToolCall::from_raw_arguments(id, name, &arguments))
+            .collect()
+    }
+}
+
+/// Provider-specific interpretation of SSE events.
+pub(super) trait SseHandler: Send + 'static {
+    /// Handle one event, returning the chunks it produces.
+    fn on_event(&mut self, event: SseEvent) -> Result<Vec<StreamChunk>>;
+
+    /// Whether the provider has signalled the end of the stream.
+    fn is_done(&self) -> bool;
+
+    /// Assemble the final response from everything seen so far.
+    fn finish(&mut self) -> Result<CompletionResponse>;
+}
+
+/// Read the body of an HTTP response as a byte stream.
+pub(super) fn response_bytes(response: reqwest::Response) -> BoxStream<'static, Result<Vec<u8>>> {
+    stream::unfold(Some(response), |response| async move {
+        let mut response = response?;
+        match response.chunk().await {
+            Ok(Some(bytes)) => Some((Ok(bytes.to_vec()), Some(response))),
+            Ok(None) => None,
+            Err(e) => Some((
+                Err(Error::LLM(format!("Failed to read stream: {}", e))),
+                None,
+            )),
+        }
+    })
+    .boxed()
+}
+
+struct DriverState<H> {
+    bytes: BoxStream<'static, Result<Vec<u8>>>,
+    decoder: Framing,
+    handler: H,
+    pending: VecDeque<Result<StreamChunk>>,
+    finished: bool,
+}
+
+impl<H: SseHandler> DriverState<H> {
+    fn handle(&mut self, event: SseEvent) {
+        if self.finished {
+            return;
+        }
+        match self.handler.on_event(event) {
+            Ok(chunks) => self.pending.extend(chunks.into_iter().map(Ok)),
+            Err(e) => {
+                self.pending.push_back(Err(e));
+                self.finished = true;
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            let last = self.handler.finish().map(StreamChunk::finished);
+            self.pending.push_back(last);
+        }
+    }
+}
+
+/// Turn an SSE byte stream into completion chunks using `handler`.
+pub(super) fn sse_stream<H: SseHandler>(
+    bytes: BoxStream<'static, Result<Vec<u8>>>,
+    handler: H,
+) -> CompletionStream {
+    drive(bytes, Framing::Sse(SseDecoder::default()), handler)
+}
+
+/// Turn a newline-delimited JSON byte stream into completion chunks using
+/// `handler`.
+pub(super) fn ndjson_stream<H: SseHandler>(
+    bytes: BoxStream<'static, Result<Vec<u8>>>,
+    handler: H,
+) -> CompletionStream {
+    drive(bytes, Framing::Lines(LineDecoder::default()), handler)
+}
+
+fn drive<H: SseHandler>(
+    bytes: BoxStream<'static, Result<Vec<u8>>>,
+    decoder: Framing,
+    handler: H,
+) -> CompletionStream {
+    let state = DriverState {
+        bytes,
+        decoder,
+        handler,
+        pending: VecDeque::new(),
+        finished: false,
+    };
+
+    Box::pin(stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+            if state.finished {
+                return None;
+            }
+            if state.handler.is_done() {
+                state.finish();
+                continue;
+            }
+
+            match state.bytes.next().await {
+                Some(Ok(bytes)) => {
+                    for event in state.decoder.push(&bytes) {
+                        state.handle(event);
+                    }
+                }
+                Some(Err(e)) => {
+                    state.pending.push_back(Err(e));
+                    state.finished = true;
+                }
+                None => {
+                    if let Some(event) = state.decoder.finish() {
+                        state.handle(event);
+                    }
+                    state.finish();
+                }
+            }
+        }
+    }))
+}
+
+fn parse_data<'a, T: Deserialize<'a>>(data: &'a str) -> Result<T> {
+    serde_json::from_str(data)
+        .map_err(|e| Error::LLM(format!("Failed to parse stream event: {}", e)))
+}
+
+// Anthropic streaming event types
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicEvent {
+    MessageStart {
+        message: AnthropicStreamMessage,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: AnthropicStreamBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: AnthropicStreamDelta,
+    },
+    MessageDelta {
+        delta: AnthropicMessageDelta,
+        usage: Option<AnthropicDeltaUsage>,
+    },
+    MessageStop,
+    Error {
+        error: AnthropicStreamError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamMessage {
+    id: String,
+    model: String,
+    usage: AnthropicStartUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStartUsage {
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamBlock {
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamDelta {
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessageDelta {
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicDeltaUsage {
+    output_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamError {
+    message: String,
+    #[serde(rename = "type")]
+    error_type: String,
+}
+
+/// Handler for the Anthropic Messages streaming format.
+pub(super) struct AnthropicStreamHandler {
+    model_spec: ModelSpec,
+    id: Option<String>,
+    model: String,
+    content: String,
+    stop_reason: Option<StopReason>,
+    usage: TokenUsage,
+    tool_calls: ToolCallAssembler,
+    done: bool,
+}
+
+impl AnthropicStreamHandler {
+    pub fn new(model: impl Into<String>, model_spec: ModelSpec) -> Self {
+        Self {
+            model_spec,
+            id: None,
+            model: model.into(),
+            content: String::new(),
+            stop_reason: None,
+            usage: TokenUsage::default(),
+            tool_calls: ToolCallAssembler::default(),
+            done: false,
+        }
+    }
+}
+
+impl SseHandler for AnthropicStreamHandler {
+    fn on_event(&mut self, event: SseEvent) -> Result<Vec<StreamChunk>> {
+        let chunk = match parse_data::<AnthropicEvent>(&event.data)? {
+            AnthropicEvent::MessageStart { message } => {
+                self.id = Some(message.id);
+                self.model = message.model;
+                self.usage = TokenUsage {
+                    input_tokens: message.usage.input_tokens,
+                    output_tokens: message.usage.output_tokens,
+                    cache_read_tokens: message.usage.cache_read_input_tokens,
+                    cache_creation_tokens: message.usage.cache_creation_input_tokens,
+                };
+                None
+            }
+            AnthropicEvent::ContentBlockStart {
+                index,
+                content_block: AnthropicStreamBlock::ToolUse { id, name },
+            } => Some(StreamChunk {
+                tool_call: Some(ToolCallDelta {
+                    index,
+                    id: Some(id),
+                    name: Some(name),
+                    arguments: String::new(),
+                }),
+                ..Default::default()
+            }),
+            AnthropicEvent::ContentBlockStart { .. } => None,
+            AnthropicEvent::ContentBlockDelta { delta, index } => match delta {
+                AnthropicStreamDelta::TextDelta { text } => {
+                    self.content.push_str(&text);
+                    Some(StreamChunk::text(text))
+                }
+                AnthropicStreamDelta::InputJsonDelta { partial_json } => Some(StreamChunk {
+                    tool_call: Some(ToolCallDelta {
+                        index,
+                        arguments: partial_json,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                AnthropicStreamDelta::Other => None,
+            },
+            AnthropicEvent::MessageDelta { delta, usage } => {
+                if let Some(usage) = usage {
+                    self.usage.output_tokens = usage.output_tokens;
+                }
+                self.stop_reason = delta
+                    .stop_reason
+                    .as_deref()
+                    .map(parse_anthropic_stop_reason);
+                None
+            }
+            AnthropicEvent::MessageStop => {
+                self.done = true;
+                None
+            }
+            AnthropicEvent::Error { error } => {
+                return Err(Error::LLM(format!(
+                    "Anthropic API error ({}): {}",
+                    error.error_type, error.message
+                )));
+            }
+            AnthropicEvent::Other => None,
+        };
+
+        let chunks: Vec<_> = chunk.into_iter().collect();
+        self.tool_calls.apply(&chunks);
+        Ok(chunks)
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn finish(&mut self) -> Result<CompletionResponse> {
+        let id = self
+            .id
+            .take()
+            .ok_or_else(|| Error::LLM("Stream ended before message_start".to_string()))?;
+        let cost = self
+            .model_spec
+            .calculate_cost(self.usage.input_tokens, self.usage.output_tokens);
+
+        Ok(CompletionResponse {
+            id,
+            model: self.model.clone(),
+            content: std::mem::take(&mut self.content),
+            stop_reason: self.stop_reason,
+            usage: self.usage.clone(),
+            timestamp: Utc::now(),
+            cost: Some(cost),
+            retries: 0,
+            tool_calls: self.tool_calls.finish(),
+            logprobs: None,
+        })
+    }
+}
+
+// OpenAI streaming chunk types
+#[derive(Debug, Deserialize)]
+struct OpenAIStreamChunk {
+    id: String,
+    model: String,
+    #[serde(default)]
+    choices: Vec<OpenAIStreamChoice>,
+    usage: Option<OpenAIStreamUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIStreamChoice {
+    delta: OpenAIStreamDelta,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIStreamDelta {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<OpenAIToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIToolCallDelta {
+    index: usize,
+    id: Option<String>,
+    function: Option<OpenAIFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIFunctionDelta {
+    name: Option<String>,
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIStreamUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+/// Handler for the OpenAI chat completions streaming format.
+pub(super) struct OpenAIStreamHandler {
+    model_spec: ModelSpec,
+    id: Option<String>,
+    model: String,
+    content: String,
+    stop_reason: Option<StopReason>,
+    usage: TokenUsage,
+    tool_calls: ToolCallAssembler,
+    done: bool,
+}
+
+impl OpenAIStreamHandler {
+    /// Terminal `data` payload sent after the last chunk.
+    const DONE: &'static str = "[DONE]";
+
+    pub fn new(model: impl Into<String>, model_spec: ModelSpec) -> Self {
+        Self {
+            model_spec,
+            id: None,
+            model: model.into(),
+            content: String::new(),
+            stop_reason: None,
+            usage: TokenUsage::default(),
+            tool_calls: ToolCallAssembler::default(),
+            done: false,
+        }
+    }
+}
+
+impl SseHandler for OpenAIStreamHandler {
+    fn on_event(&mut self, event: SseEvent) -> Result<Vec<StreamChunk>> {
+        if event.data.trim() == Self::DONE {
+            self.done = true;
+            return Ok(Vec::new());
+        }
+
+        let chunk: OpenAIStreamChunk = parse_data(&event.data)?;
+        self.id.get_or_insert(chunk.id);
+        self.model = chunk.model;
+        if let Some(usage) = chunk.usage {
+            self.usage.input_tokens = usage.prompt_tokens;
+            self.usage.output_tokens = usage.completion_tokens;
+        }
+
+        let mut chunks = Vec::new();
+        for choice in chunk.choices {
+            if let Some(text) = choice.delta.content.filter(|t| !t.is_empty()) {
+                self.content.push_str(&text);
+                chunks.push(StreamChunk::text(text));
+            }
+            for call in choice.delta.tool_calls {
+                let (name, arguments) = call
+                    .function
+                    .map(|f| (f.name, f.arguments.unwrap_or_default()))
+                    .unwrap_or_default();
+                chunks.push(StreamChunk {
+                    tool_call: Some(ToolCallDelta {
+                        index: call.index,
+                        id: call.id,
+                        name,
+                        arguments,
+                    }),
+                    ..Default::default()
+                });
+            }
+            if let Some(reason) = choice.finish_reason.as_deref() {
+                self.stop_reason = Some(parse_openai_stop_reason(reason));
+            }
+        }
+
+        self.tool_calls.apply(&chunks);
+        Ok(chunks)
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn finish(&mut self) -> Result<CompletionResponse> {
+        let id = self
+            .id
+            .take()
+            .ok_or_else(|| Error::LLM("Stream ended before any chunk".to_string()))?;
+        let cost = self
+            .model_spec
+            .calculate_cost(self.usage.input_tokens, self.usage.output_tokens);
+
+        Ok(CompletionResponse {
+            id,
+            model: self.model.clone(),
+            content: std::mem::take(&mut self.content),
+            stop_reason: self.stop_reason,
+            usage: self.usage.clone(),
+            timestamp: Utc::now(),
+            cost: Some(cost),
+            retries: 0,
+            tool_calls: self.tool_calls.finish(),
+            logprobs: None,
+        })
+    }
+}
+
+// Ollama chat types, shared by streamed lines and whole responses
+#[derive(Debug, Deserialize)]
+pub(super) struct OllamaChatResponse {
+    pub model: String,
+    #[serde(default)]
+    pub message: Option<OllamaResponseMessage>,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub done_reason: Option<String>,
+    /// Absent when the prompt was served from Ollama's KV cache
+    #[serde(default)]
+    pub prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    pub eval_count: Option<u64>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct OllamaResponseMessage {
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub tool_calls: Vec<OllamaToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct OllamaToolCall {
+    pub function: OllamaFunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct OllamaFunctionCall {
+    pub name: String,
+    /// Arguments as a JSON object, not an encoded string
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+impl OllamaChatResponse {
+    /// Best-effort usage; Ollama omits counts it did not compute.
+    pub fn usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.prompt_eval_count.unwrap_or(0),
+            output_tokens: self.eval_count.unwrap_or(0),
+            cache_read_tokens: None,
+            cache_creation_tokens: None,
+        }
+    }
+}
+
+/// Ollama does not assign tool call IDs, so they are derived from position.
+pub(super) fn ollama_tool_call_id(index: usize) -> String {
+    format!("call_{}", index)
+}
+
+/// Handler for Ollama's newline-delimited `/api/chat` stream.
+pub(super) struct OllamaStreamHandler {
+    model_spec: ModelSpec,
+    id: String,
+    model: String,
+    content: String,
+    stop_reason: Option<StopReason>,
+    usage: TokenUsage,
+    tool_calls: ToolCallAssembler,
+    tool_call_count: usize,
+    done: bool,
+}
+
+impl OllamaStreamHandler {
+    pub fn new(model: impl Into<String>, model_spec: ModelSpec) -> Self {
+        Self {
+            model_spec,
+            id: format!("ollama-{}", uuid::Uuid::new_v4()),
+            model: model.into(),
+            content: String::new(),
+            stop_reason: None,
+            usage: TokenUsage::default(),
+            tool_calls: ToolCallAssembler::default(),
+            tool_call_count: 0,
+            done: false,
+        }
+    }
+}
+
+impl SseHandler for OllamaStreamHandler {
+    fn on_event(&mut self, event: SseEvent) -> Result<Vec<StreamChunk>> {
+        let line: OllamaChatResponse = parse_data(&event.data)?;
+        if let Some(error) = &line.error {
+            return Err(Error::LLM(format!("Ollama API error: {}", error)));
+        }
+        let usage = line.usage();
+
+        let mut chunks = Vec::new();
+        let message = line.message.unwrap_or_default();
+        if !message.content.is_empty() {
+            self.content.push_str(&message.content);
+            chunks.push(StreamChunk::text(message.content));
+        }
+        // Tool calls arrive whole rather than as argument fragments.
+        for call in message.tool_calls {
+            chunks.push(StreamChunk {
+                tool_call: Some(ToolCallDelta {
+                    index: self.tool_call_count,
+                    id: Some(ollama_tool_call_id(self.tool_call_count)),
+                    name: Some(call.function.name),
+                    arguments: call.function.arguments.to_string(),
+                }),
+                ..Default::default()
+            });
+            self.tool_call_count += 1;
+        }
+
+        self.model = line.model;
+        if line.done {
+            self.usage = usage;
+            self.stop_reason = Some(parse_ollama_stop_reason(
+                line.done_reason.as_deref().unwrap_or("stop"),
+                self.tool_call_count > 0,
+            ));
+            self.done = true;
+        }
+
+        self.tool_calls.apply(&chunks);
+        Ok(chunks)
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn finish(&mut self) -> Result<CompletionResponse> {
+        if !self.done {
+            return Err(Error::LLM("Ollama stream ended before done".to_string()));
+        }
+        let cost = self
+            .model_spec
+            .calculate_cost(self.usage.input_tokens, self.usage.output_tokens);
+
+        Ok(CompletionResponse {
+            id: self.id.clone(),
+            model: self.model.clone(),
+            content: std::mem::take(&mut self.content),
+            stop_reason: self.stop_reason,
+            usage: self.usage.clone(),
+            timestamp: Utc::now(),
+            cost: Some(cost),
+            retries: 0,
+            tool_calls: self.tool_calls.finish(),
+            logprobs: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn byte_stream(parts: &[&str]) -> BoxStream<'static, Result<Vec<u8>>> {
+        let parts: Vec<_> = parts.iter().map(|p| Ok(p.as_bytes().to_vec())).collect();
+        stream::iter(parts).boxed()
+    }
+
+    async fn collect(stream: CompletionStream) -> Vec<Result<StreamChunk>> {
+        stream.collect().await
+    }
+
+    #[test]
+    fn test_decoder_handles_split_events() {
+        let mut decoder = SseDecoder::default();
+
+        assert!(decoder.push(b"event: ping\nda").is_empty());
+        let events = decoder.push(b"ta: {}\r\n\r\n: comment\n\ndata: a\ndata: b\n\n");
+
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: Some("ping".to_string()),
+                    data: "{}".to_string(),
+                },
+                SseEvent {
+                    event: None,
+                    data: "a\nb".to_string(),
+                },
+            ]
+        );
+        assert!(decoder.finish().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_stream() {
+        let body = [
+            "event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"model\":\"claude-3-5-haiku-20241022\",\"usage\":{\"input_tokens\":12,\"output_tokens\":1}}}\n\n",
+            "event: content_block_start\ndata: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hel\"}}\n\n",
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"lo\"}}\n\n",
+            "event: content_block_start\ndata: {\"type\":\"content_block_start\",\"index\":1,\"content_block\":{\"type\":\"tool_use\",\"id\":\"toolu_1\",\"name\":\"search\",\"input\":{}}}\n\n",
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"{\\\"q\\\":1}\"}}\n\n",
+            "event: ping\ndata: {\"type\":\"ping\"}\n\n",
+            "event: message_delta\ndata: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"tool_use\"},\"usage\":{\"output_tokens\":7}}\n\n",
+            "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+        ];
+        let handler = AnthropicStreamHandler::new("claude", ModelSpec::claude_haiku());
+        let chunks = collect(sse_stream(byte_stream(&body), handler)).await;
+        let chunks: Vec<StreamChunk> = chunks.into_iter().map(|c| c.unwrap()).collect();
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "Hello");
+
+        let tool_deltas: Vec<_> = chunks.iter().filter_map(|c| c.tool_call.clone()).collect();
+        assert_eq!(tool_deltas.len(), 2);
+        assert_eq!(tool_deltas[0].name.as_deref(), Some("search"));
+        assert_eq!(tool_deltas[1].arguments, "{\"q\":1}");
+
+        let last = chunks.last().unwrap();
+        let response = last.response.as_ref().unwrap();
+        assert_eq!(response.id, "msg_1");
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.usage.input_tokens, 12);
+        assert_eq!(response.usage.output_tokens, 7);
+        assert_eq!(last.stop_reason, Some(StopReason::ToolUse));
+        assert!(response.cost.unwrap() > 0.0);
+        assert_eq!(
+            response.tool_calls,
+            vec![ToolCall {
+                id: "toolu_1".to_string(),
+                name: "search".to_string(),
+                arguments: serde_json::json!({"q": 1}),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_stream_error_event() {
+        let body = [
+            "event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+        ];
+        let handler = AnthropicStreamHandler::new("claude", ModelSpec::claude_haiku());
+        let chunks = collect(sse_stream(byte_stream(&body), handler)).await;
+
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("overloaded_error"));
+    }
+
+    #[tokio::test]
+    async fn test_openai_stream() {
+        let body = [
+            "data: {\"id\":\"c1\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"c1\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"c1\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"function\":{\"name\":\"lookup\",\"arguments\":\"{\\\"k\\\"\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"c1\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: {\"id\":\"c1\",\"model\":\"gpt-4o\",\"choices\":[],\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":3,\"total_tokens\":8}}\n\n",
+            "data: [DONE]\n\n",
+        ];
+        let handler = OpenAIStreamHandler::new("gpt-4o", ModelSpec::gpt4o());
+        let chunks = collect(sse_stream(byte_stream(&body), handler)).await;
+        let chunks: Vec<StreamChunk> = chunks.into_iter().map(|c| c.unwrap()).collect();
+
+        assert_eq!(chunks[0].delta, "Hi");
+        let call = chunks[1].tool_call.as_ref().unwrap();
+        assert_eq!(call.id.as_deref(), Some("call_1"));
+        assert_eq!(call.arguments, "{\"k\"");
+
+        let last = chunks.last().unwrap();
+        let response = last.response.as_ref().unwrap();
+        assert_eq!(response.content, "Hi");
+        assert_eq!(response.usage.output_tokens, 3);
+        assert_eq!(last.stop_reason, Some(StopReason::ToolUse));
+    }
+
+    #[tokio::test]
+    async fn test_openai_stream_assembles_multiple_tool_calls() {
+        let body = [
+            "data: {\"id\":\"c2\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_a\",\"function\":{\"name\":\"read\",\"arguments\":\"\"}},{\"index\":1,\"id\":\"call_b\",\"function\":{\"name\":\"grep\",\"arguments\":\"\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"c2\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":1,\"function\":{\"arguments\":\"{\\\"pattern\\\":\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"c2\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"{\\\"path\\\":\\\"a.rs\\\"}\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"c2\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":1,\"function\":{\"arguments\":\"\\\"fn\\\"}\"}}]},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n",
+        ];
+        let handler = OpenAIStreamHandler::new("gpt-4o", ModelSpec::gpt4o());
+        let chunks = collect(sse_stream(byte_stream(&body), handler)).await;
+        let last = chunks.last().unwrap().as_ref().unwrap();
+        let calls = &last.response.as_ref().unwrap().tool_calls;
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_a");
+        assert_eq!(calls[0].arguments, serde_json::json!({"path": "a.rs"}));
+        assert_eq!(calls[1].name, "grep");
+        assert_eq!(calls[1].arguments, serde_json::json!({"pattern": "fn"}));
+    }
+
+    #[test]
+    fn test_line_decoder_handles_split_lines() {
+        let mut decoder = LineDecoder::default();
+
+        assert!(decoder.push(b"{\"a\":").is_empty());
+        let events = decoder.push(b"1}\n\n{\"b\":2}\r\n{\"c\"");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].data, "{\"a\":1}");
+        assert_eq!(events[1].data, "{\"b\":2}");
+        assert_eq!(decoder.finish().unwrap().data, "{\"c\"");
+    }
+
+    #[tokio::test]
+    async fn test_ollama_stream() {
+        let body = [
+            "{\"model\":\"llama3.1:8b\",\"message\":{\"role\":\"assistant\",\"content\":\"Hel\"},\"done\":false}\n{\"model\":\"llama3.1:8b\",\"message\":{\"role\":\"assistant\",\"content\":\"lo\"},\"done\":false}\n",
+            "{\"model\":\"llama3.1:8b\",\"message\":{\"role\":\"assistant\",\"content\":\"\",\"tool_calls\":[{\"function\":{\"name\":\"search\",\"arguments\":{\"q\":\"rust\"}}}]},\"done\":false}\n",
+            "{\"model\":\"llama3.1:8b\",\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,\"done_reason\":\"stop\",\"prompt_eval_count\":12,\"eval_count\":3}\n",
+        ];
+        let handler = OllamaStreamHandler::new("llama3.1:8b", ModelSpec::llama3_1_8b());
+        let chunks: Vec<_> = collect(ndjson_stream(byte_stream(&body), handler))
+            .await
+            .into_iter()
+            .map(|c| c.unwrap())
+            .collect();
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "Hello");
+
+        let response = chunks.last().unwrap().response.as_ref().unwrap();
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.usage.input_tokens, 12);
+        assert_eq!(response.usage.output_tokens, 3);
+        assert_eq!(response.cost, Some(0.0));
+        assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+        assert_eq!(
+            response.tool_calls,
+            vec![ToolCall {
+                id: "call_0".to_string(),
+                name: "search".to_string(),
+                arguments: serde_json::json!({"q": "rust"}),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ollama_stream_error_line() {
+        let body = ["{\"model\":\"\",\"error\":\"model 'nope' not found\"}\n"];
+        let handler = OllamaStreamHandler::new("nope", ModelSpec::llama3_1_8b());
+        let chunks = collect(ndjson_stream(byte_stream(&body), handler)).await;
+
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_without_terminal_event_still_finishes() {
+        let body = ["data: {\"id\":\"c1\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"content\":\"x\"},\"finish_reason\":\"stop\"}]}"];
+        let handler = OpenAIStreamHandler::new("gpt-4o", ModelSpec::gpt4o());
+        let chunks = collect(sse_stream(byte_stream(&body), handler)).await;
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            chunks[1]
+                .as_ref()
+                .unwrap()
+                .response
+                .as_ref()
+                .unwrap()
+                .content,
+            "x"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_single_chunk_stream() {
+        let response = CompletionResponse {
+            id: "r".to_string(),
+            model: "m".to_string(),
+            content: "all at once".to_string(),
+            stop_reason: Some(StopReason::EndTurn),
+            usage: TokenUsage::default(),
+            timestamp: Utc::now(),
+            cost: None,
+            retries: 0,
+            tool_calls: Vec::new(),
+            logprobs: None,
+        };
+        let chunks = collect(single_chunk_stream(response)).await;
+
+        assert_eq!(chunks.len(), 1);
+        let chunk = chunks[0].as_ref().unwrap();
+        assert_eq!(chunk.delta, "all at once");
+        assert!(chunk.is_final());
+    }
+}
@@ -0,0 +1,228 @@
+//! Token estimation for pre-dispatch budget checks.
+//!
+//! Estimates are approximate and do not load any vocabulary. For OpenAI
+//! models the text is pre-tokenized the way tiktoken splits it (words with
+//! their leading space, digit groups of three, punctuation runs, newlines)
+//! and each piece is costed by length. Other providers use a
+//! characters-per-token heuristic. Both are within roughly 10% for English
+//! prose.
+
+use super::types::{CompletionRequest, Provider};
+
+/// Average characters per token for the character heuristic.
+const CHARS_PER_TOKEN: f64 = 3.5;
+
+/// Tokens added per chat message for role markers and separators.
+pub const MESSAGE_OVERHEAD_TOKENS: u64 = 4;
+
+/// Estimate the number of tokens `text` encodes to for `provider`.
+pub fn estimate_tokens(text: &str, provider: Provider) -> u64 {
+    match provider {
+        Provider::OpenAI => estimate_bpe_tokens(text),
+        _ => estimate_heuristic_tokens(text),
+    }
+}
+
+/// Infer the provider whose tokenizer a model name uses.
+pub fn provider_for_model(model: &str) -> Option<Provider> {
+    let model = model.rsplit('/').next().unwrap_or(model);
+    if model.starts_with("claude") {
+        Some(Provider::Anthropic)
+    } else if ["gpt", "o1", "o3", "o4", "chatgpt", "text-embedding"]
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+    {
+        Some(Provider::OpenAI)
+    } else {
+        #[cfg(feature = "gemini")]
+        if model.starts_with("gemini") {
+            return Some(Provider::Google);
+        }
+        None
+    }
+}
+
+impl CompletionRequest {
+    /// Estimate the input tokens for this request.
+    ///
+    /// The tokenizer is chosen from the request's model, falling back to the
+    /// Anthropic heuristic when no model is set.
+    pub fn estimate_input_tokens(&self) -> u64 {
+        let provider = self
+            .model
+            .as_deref()
+            .and_then(provider_for_model)
+            .unwrap_or(Provider::Anthropic);
+        self.estimate_input_tokens_for(provider)
+    }
+
+    /// Estimate the input tokens for this request with `provider`'s tokenizer.
+    pub fn estimate_input_tokens_for(&self, provider: Provider) -> u64 {
+        let system = self
+            .system
+            .as_deref()
+            .map(|s| estimate_tokens(s, provider) + MESSAGE_OVERHEAD_TOKENS)
+            .unwrap_or(0);
+
+        self.messages
+            .iter()
+            .map(|m| estimate_tokens(&m.content, provider) + MESSAGE_OVERHEAD_TOKENS)
+            .sum::<u64>()
+            + system
+    }
+}
+
+/// Characters-per-token estimate, counting each CJK character as a token.
+fn estimate_heuristic_tokens(text: &str) -> u64 {
+    let (wide, narrow) = text.chars().fold((0u64, 0u64), |(wide, narrow), c| {
+        if is_wide(c) {
+            (wide + 1, narrow)
+        } else {
+            (wide, narrow + 1)
+        }
+    });
+    wide + (narrow as f64 / CHARS_PER_TOKEN).ceil() as u64
+}
+
+/// Estimate from a tiktoken-style pre-tokenization.
+fn estimate_bpe_tokens(text: &str) -> u64 {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = 0u64;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if is_wide(c) {
+            tokens += 1;
+            i += 1;
+        } else if c.is_alphabetic() {
+            while i < chars.len() && chars[i].is_alphabetic() && !is_wide(chars[i]) {
+                i += 1;
+            }
+            tokens += word_tokens(i - start);
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            tokens += (i - start).div_ceil(3) as u64;
+        } else if c == ' ' && chars.get(i + 1).is_some_and(|n| !n.is_whitespace()) {
+            // A single space is merged into the following piece.
+            i += 1;
+        } else if c.is_whitespace() {
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            tokens += 1;
+        } else if c.is_ascii() {
+            while i < chars.len() && chars[i].is_ascii_punctuation() && i - start < 3 {
+                i += 1;
+            }
+            i = i.max(start + 1);
+            tokens += 1;
+        } else {
+            // Symbols and emoji outside common merges cost about one token
+            // per two UTF-8 bytes.
+            tokens += c.len_utf8().div_ceil(2) as u64;
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Tokens for a run of letters: common words are a single token and longer
+/// words split into a few sub-word pieces.
+fn word_tokens(len: usize) -> u64 {
+    match len {
+        0..=9 => 1,
+        10..=14 => 2,
+        _ => len.div_ceil(5) as u64,
+    }
+}
+
+/// CJK ideographs, kana, and hangul, which encode to about one token each.
+fn is_wide(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'
+        | '\u{3400}'..='\u{9fff}'
+        | '\u{ac00}'..='\u{d7af}'
+        | '\u{f900}'..='\u{faff}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::types::ChatMessage;
+
+    const PROSE: &str = "Rust is a general-purpose programming language emphasizing \
+        performance, type safety, and concurrency. It enforces memory safety, meaning \
+        that all references point to valid memory, without a garbage collector. To \
+        simultaneously enforce memory safety and prevent data races, its borrow checker \
+        tracks the object lifetime of all references in a program during compilation.";
+
+    #[test]
+    fn test_bpe_estimate_matches_known_count() {
+        // cl100k_base encodes this sentence to 10 tokens.
+        let text = "The quick brown fox jumps over the lazy dog.";
+        assert_eq!(estimate_tokens(text, Provider::OpenAI), 10);
+    }
+
+    #[test]
+    fn test_bpe_estimate_within_tolerance_for_prose() {
+        // cl100k_base splits this paragraph into about 66 tokens.
+        let estimate = estimate_tokens(PROSE, Provider::OpenAI) as f64;
+        assert!(
+            (estimate - 66.0).abs() / 66.0 <= 0.1,
+            "estimate {}",
+            estimate
+        );
+    }
+
+    #[test]
+    fn test_heuristic_estimate_is_conservative() {
+        // Claude's tokenizer yields more tokens than cl100k for the same text.
+        assert!(
+            estimate_tokens(PROSE, Provider::Anthropic) >= estimate_tokens(PROSE, Provider::OpenAI)
+        );
+    }
+
+    #[test]
+    fn test_bpe_estimate_pieces() {
+        assert_eq!(estimate_tokens("", Provider::OpenAI), 0);
+        // Digits group in threes.
+        assert_eq!(estimate_tokens("1234567", Provider::OpenAI), 3);
+        // Newline runs are a single token.
+        assert_eq!(estimate_tokens("a\n\n\nb", Provider::OpenAI), 3);
+        // CJK characters are a token each.
+        assert_eq!(estimate_tokens("你好世界", Provider::OpenAI), 4);
+        assert_eq!(estimate_tokens("你好世界", Provider::Anthropic), 4);
+    }
+
+    #[test]
+    fn test_provider_for_model() {
+        assert_eq!(provider_for_model("gpt-4o-mini"), Some(Provider::OpenAI));
+        assert_eq!(provider_for_model("openai/o3-mini"), Some(Provider::OpenAI));
+        assert_eq!(
+            provider_for_model("claude-3-5-haiku-20241022"),
+            Some(Provider::Anthropic)
+        );
+        assert_eq!(provider_for_model("mistral-large"), None);
+    }
+
+    #[test]
+    fn test_request_estimate_includes_overhead() {
+        let request = CompletionRequest::new()
+            .with_model("gpt-4o")
+            .with_system("Be brief.")
+            .with_message(ChatMessage::user(
+                "The quick brown fox jumps over the lazy dog.",
+            ));
+
+        assert_eq!(
+            request.estimate_input_tokens(),
+            3 + 10 + 2 * MESSAGE_OVERHEAD_TOKENS
+        );
+    }
+}
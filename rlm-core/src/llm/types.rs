@@ -3,6 +3,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::signature::{FieldSpec, FieldType};
+
+use super::stats::SampleStats;
 
 /// LLM provider.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -14,6 +20,7 @@ pub enum Provider {
     OpenRouter,
     #[cfg(feature = "gemini")]
     Google,
+    Ollama,
 }
 
 impl std::fmt::Display for Provider {
@@ -24,6 +31,7 @@ impl std::fmt::Display for Provider {
             Self::OpenRouter => write!(f, "openrouter"),
             #[cfg(feature = "gemini")]
             Self::Google => write!(f, "google"),
+            Self::Ollama => write!(f, "ollama"),
         }
     }
 }
@@ -40,6 +48,17 @@ pub enum ModelTier {
     Fast = 2,
 }
 
+impl ModelTier {
+    /// The next cheaper tier, if any.
+    pub fn downgrade(self) -> Option<Self> {
+        match self {
+            Self::Flagship => Some(Self::Balanced),
+            Self::Balanced => Some(Self::Fast),
+            Self::Fast => None,
+        }
+    }
+}
+
 /// Orchestration call tier for dual-model accounting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -77,6 +96,12 @@ pub struct ModelSpec {
     pub supports_vision: bool,
     /// Supports tool use
     pub supports_tools: bool,
+    /// Runs on local hardware (e.g., via Ollama)
+    #[serde(default)]
+    pub is_local: bool,
+    /// Embedding cost per million input tokens (USD), for embedding models
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_cost_per_m: Option<f64>,
 }
 
 impl ModelSpec {
@@ -86,6 +111,23 @@ impl ModelSpec {
         let output_cost = (output_tokens as f64 / 1_000_000.0) * self.output_cost_per_m;
         input_cost + output_cost
     }
+
+    /// Calculate cost for embedding the given number of input tokens.
+    ///
+    /// Falls back to the input price for models without an embedding price.
+    pub fn calculate_embedding_cost(&self, input_tokens: u64) -> f64 {
+        let per_m = self.embedding_cost_per_m.unwrap_or(self.input_cost_per_m);
+        (input_tokens as f64 / 1_000_000.0) * per_m
+    }
+
+    /// Tier used for routing. Local models are never routed as flagship.
+    pub fn routing_tier(&self) -> ModelTier {
+        if self.is_local {
+            self.tier.max(ModelTier::Balanced)
+        } else {
+            self.tier
+        }
+    }
 }
 
 /// Well-known models.
@@ -103,6 +145,8 @@ impl ModelSpec {
             supports_caching: true,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
         }
     }
 
@@ -119,6 +163,8 @@ impl ModelSpec {
             supports_caching: true,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
         }
     }
 
@@ -135,6 +181,8 @@ impl ModelSpec {
             supports_caching: true,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
         }
     }
 
@@ -151,6 +199,8 @@ impl ModelSpec {
             supports_caching: false,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
         }
     }
 
@@ -167,6 +217,48 @@ impl ModelSpec {
             supports_caching: false,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
+        }
+    }
+
+    // OpenAI embedding models
+
+    pub fn text_embedding_3_small() -> Self {
+        Self {
+            id: "text-embedding-3-small".to_string(),
+            name: "Text Embedding 3 Small".to_string(),
+            provider: Provider::OpenAI,
+            tier: ModelTier::Fast,
+            context_window: 8191,
+            max_output: 0,
+            input_cost_per_m: 0.02,
+            output_cost_per_m: 0.0,
+            supports_caching: false,
+            supports_vision: false,
+            supports_tools: false,
+            is_local: false,
+            embedding_cost_per_m: Some(0.02),
+        }
+    }
+
+    pub fn text_embedding_3_large() -> Self {
+        Self {
+            id: "text-embedding-3-large".to_string(),
+            name: "Text Embedding 3 Large".to_string(),
+            input_cost_per_m: 0.13,
+            embedding_cost_per_m: Some(0.13),
+            ..Self::text_embedding_3_small()
+        }
+    }
+
+    pub fn text_embedding_ada_002() -> Self {
+        Self {
+            id: "text-embedding-ada-002".to_string(),
+            name: "Text Embedding Ada 002".to_string(),
+            input_cost_per_m: 0.10,
+            embedding_cost_per_m: Some(0.10),
+            ..Self::text_embedding_3_small()
         }
     }
 
@@ -186,6 +278,8 @@ impl ModelSpec {
             supports_caching: true,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
         }
     }
 
@@ -203,6 +297,8 @@ impl ModelSpec {
             supports_caching: true,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
         }
     }
 
@@ -220,6 +316,59 @@ impl ModelSpec {
             supports_caching: true,
             supports_vision: true,
             supports_tools: true,
+            is_local: false,
+            embedding_cost_per_m: None,
+        }
+    }
+
+    // Local models served by Ollama. Cost is zero.
+
+    /// A model served by a local Ollama instance.
+    ///
+    /// `tier` should be `Fast` or `Balanced`; local models are capped at
+    /// `Balanced` for routing.
+    pub fn ollama(id: impl Into<String>, tier: ModelTier) -> Self {
+        let id = id.into();
+        Self {
+            name: format!("{} (Ollama)", id),
+            id,
+            provider: Provider::Ollama,
+            tier,
+            context_window: 32_768,
+            max_output: 4096,
+            input_cost_per_m: 0.0,
+            output_cost_per_m: 0.0,
+            supports_caching: false,
+            supports_vision: false,
+            supports_tools: true,
+            is_local: true,
+            embedding_cost_per_m: None,
+        }
+    }
+
+    pub fn llama3_1_8b() -> Self {
+        Self {
+            name: "Llama 3.1 8B (Ollama)".to_string(),
+            context_window: 128_000,
+            ..Self::ollama("llama3.1:8b", ModelTier::Fast)
+        }
+    }
+
+    pub fn qwen2_5_32b() -> Self {
+        Self {
+            name: "Qwen 2.5 32B (Ollama)".to_string(),
+            ..Self::ollama("qwen2.5:32b", ModelTier::Balanced)
+        }
+    }
+
+    pub fn llama3_2_vision_11b() -> Self {
+        Self {
+            name: "Llama 3.2 Vision 11B (Ollama)".to_string(),
+            context_window: 128_000,
+            supports_vision: true,
+            // Ollama does not support tool calling for this model
+            supports_tools: false,
+            ..Self::ollama("llama3.2-vision:11b", ModelTier::Fast)
         }
     }
 }
@@ -241,6 +390,9 @@ pub struct ChatMessage {
     /// Cache control for prompt caching
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_control: Option<CacheControl>,
+    /// Images attached to the message (vision input)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImagePart>,
 }
 
 impl ChatMessage {
@@ -249,6 +401,7 @@ impl ChatMessage {
             role: ChatRole::System,
             content: content.into(),
             cache_control: None,
+            images: Vec::new(),
         }
     }
 
@@ -257,6 +410,7 @@ impl ChatMessage {
             role: ChatRole::User,
             content: content.into(),
             cache_control: None,
+            images: Vec::new(),
         }
     }
 
@@ -265,6 +419,15 @@ impl ChatMessage {
             role: ChatRole::Assistant,
             content: content.into(),
             cache_control: None,
+            images: Vec::new(),
+        }
+    }
+
+    /// User message with images attached.
+    pub fn user_with_images(content: impl Into<String>, images: Vec<ImagePart>) -> Self {
+        Self {
+            images,
+            ..Self::user(content)
         }
     }
 
@@ -275,6 +438,50 @@ impl ChatMessage {
     }
 }
 
+/// Where an image's bytes come from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    /// Base64-encoded image data, without a `data:` prefix
+    Base64 { data: String },
+    /// URL the provider fetches the image from
+    Url { url: String },
+}
+
+/// An image attached to a chat message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImagePart {
+    pub source: ImageSource,
+    /// MIME type, e.g. `image/png`
+    pub media_type: String,
+}
+
+impl ImagePart {
+    /// Image from base64-encoded data.
+    pub fn base64(data: impl Into<String>, media_type: impl Into<String>) -> Self {
+        Self {
+            source: ImageSource::Base64 { data: data.into() },
+            media_type: media_type.into(),
+        }
+    }
+
+    /// Image fetched from a URL.
+    pub fn url(url: impl Into<String>, media_type: impl Into<String>) -> Self {
+        Self {
+            source: ImageSource::Url { url: url.into() },
+            media_type: media_type.into(),
+        }
+    }
+
+    /// The image as a URL, encoding base64 data as a `data:` URL.
+    pub fn to_url(&self) -> String {
+        match &self.source {
+            ImageSource::Base64 { data } => format!("data:{};base64,{}", self.media_type, data),
+            ImageSource::Url { url } => url.clone(),
+        }
+    }
+}
+
 /// Cache control directive.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -301,6 +508,22 @@ pub struct CompletionRequest {
     pub enable_caching: bool,
     /// Metadata for tracking
     pub metadata: Option<HashMap<String, String>>,
+    /// Tools the model may call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolDef>,
+    /// Constrain the response to JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+    /// Return token logprobs with this many alternatives per position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<u8>,
+    /// Manual cache breakpoints, overriding the automatic placement.
+    ///
+    /// Uses the [`find_cache_breakpoints`](super::find_cache_breakpoints)
+    /// convention: `0` is the end of the system prompt and `n` the end of
+    /// the first `n` messages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cache_breakpoints: Vec<usize>,
 }
 
 impl Default for CompletionRequest {
@@ -314,6 +537,10 @@ impl Default for CompletionRequest {
             stop: None,
             enable_caching: false,
             metadata: None,
+            tools: Vec::new(),
+            response_format: None,
+            top_logprobs: None,
+            cache_breakpoints: Vec::new(),
         }
     }
 }
@@ -353,10 +580,113 @@ impl CompletionRequest {
         self
     }
 
+    pub fn with_tools(mut self, tools: Vec<ToolDef>) -> Self {
+        self.tools = tools;
+        self
+    }
+
     pub fn with_caching(mut self, enable: bool) -> Self {
         self.enable_caching = enable;
         self
     }
+
+    /// Pin a cache breakpoint at the end of the system prompt.
+    pub fn with_cache_after_system(self) -> Self {
+        self.with_cache_breakpoint(0)
+    }
+
+    /// Pin a cache breakpoint after the message at `index`.
+    ///
+    /// Manual breakpoints replace the automatic placement; see
+    /// [`apply_request_cache_markers`](super::apply_request_cache_markers).
+    pub fn with_cache_after_message(self, index: usize) -> Self {
+        self.with_cache_breakpoint(index + 1)
+    }
+
+    fn with_cache_breakpoint(mut self, breakpoint: usize) -> Self {
+        self.enable_caching = true;
+        if !self.cache_breakpoints.contains(&breakpoint) {
+            self.cache_breakpoints.push(breakpoint);
+        }
+        self
+    }
+
+    /// Request token logprobs with `top` alternatives per position.
+    ///
+    /// Only honoured by clients where [`LLMClient::supports_logprobs`]
+    /// is true; others ignore it.
+    ///
+    /// [`LLMClient::supports_logprobs`]: super::LLMClient::supports_logprobs
+    pub fn with_logprobs(mut self, top: u8) -> Self {
+        self.top_logprobs = Some(top);
+        self
+    }
+
+    /// Whether any message carries images.
+    pub fn has_images(&self) -> bool {
+        self.messages.iter().any(|m| !m.images.is_empty())
+    }
+
+    /// Require the response to be a single JSON object.
+    pub fn with_json_mode(mut self) -> Self {
+        self.response_format = Some(ResponseFormat::JsonObject);
+        self
+    }
+
+    /// Require the response to be JSON matching `schema`.
+    pub fn with_json_schema(mut self, schema: serde_json::Value) -> Self {
+        self.response_format = Some(ResponseFormat::JsonSchema { schema });
+        self
+    }
+}
+
+/// Structured-output constraint on a completion.
+///
+/// Clients map this onto the provider's native mechanism (OpenAI
+/// `response_format`, a forced tool call on Anthropic, Ollama `format`).
+/// Where no native mechanism exists they fall back to
+/// [`ResponseFormat::instruction`] in the system prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Any JSON object
+    JsonObject,
+    /// JSON conforming to a JSON schema
+    JsonSchema { schema: serde_json::Value },
+}
+
+impl ResponseFormat {
+    /// The schema to enforce; a bare object schema for [`ResponseFormat::JsonObject`].
+    pub fn schema(&self) -> serde_json::Value {
+        match self {
+            Self::JsonObject => serde_json::json!({"type": "object"}),
+            Self::JsonSchema { schema } => schema.clone(),
+        }
+    }
+
+    /// Prompt instruction for providers without native JSON support.
+    pub fn instruction(&self) -> String {
+        match self {
+            Self::JsonObject => "Respond with a single valid JSON object and nothing else. \
+                Do not wrap it in markdown code fences or add commentary."
+                .to_string(),
+            Self::JsonSchema { schema } => format!(
+                "Respond with a single valid JSON object that conforms to this JSON schema, \
+                 and nothing else. Do not wrap it in markdown code fences or add commentary.\n\n{}",
+                serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string())
+            ),
+        }
+    }
+
+    /// Append [`Self::instruction`] to an optional system prompt.
+    pub fn apply_to_system(&self, system: Option<String>) -> String {
+        match system {
+            Some(system) if !system.is_empty() => {
+                format!("{}\n\n{}", system, self.instruction())
+            }
+            _ => self.instruction(),
+        }
+    }
 }
 
 /// Token usage statistics.
@@ -400,6 +730,104 @@ pub struct CompletionResponse {
     pub timestamp: DateTime<Utc>,
     /// Cost in USD (if calculable)
     pub cost: Option<f64>,
+    /// Attempts retried before this response succeeded
+    #[serde(default)]
+    pub retries: u32,
+    /// Tool calls requested by the model, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    /// Per-token logprobs, when requested and supported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<Vec<TokenLogprob>>,
+}
+
+/// Log probability of a generated token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenLogprob {
+    /// The sampled token
+    pub token: String,
+    /// Natural-log probability of the sampled token
+    pub logprob: f64,
+    /// Most likely alternatives at this position, including the sampled token
+    #[serde(default)]
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+/// A candidate token at a position and its log probability.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopLogprob {
+    pub token: String,
+    pub logprob: f64,
+}
+
+/// Tool definition offered to the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDef {
+    /// Tool name
+    pub name: String,
+    /// What the tool does and when to use it
+    pub description: String,
+    /// JSON schema for the tool's input
+    pub parameters: serde_json::Value,
+}
+
+impl ToolDef {
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: serde_json::Value,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            parameters,
+        }
+    }
+
+    /// Create a tool whose input is an object with the given fields.
+    pub fn from_fields(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        fields: Vec<FieldSpec>,
+    ) -> Self {
+        Self::new(
+            name,
+            description,
+            FieldType::Object(fields).to_json_schema(),
+        )
+    }
+}
+
+/// A tool call requested by the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// Provider-assigned call ID
+    pub id: String,
+    /// Name of the tool to call
+    pub name: String,
+    /// Tool input
+    pub arguments: serde_json::Value,
+}
+
+impl ToolCall {
+    /// Parse JSON-encoded arguments, keeping unparseable input as a string.
+    pub fn from_raw_arguments(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        arguments: &str,
+    ) -> Self {
+        let arguments = if arguments.trim().is_empty() {
+            serde_json::Value::Object(Default::default())
+        } else {
+            serde_json::from_str(arguments)
+                .unwrap_or_else(|_| serde_json::Value::String(arguments.to_string()))
+        };
+        Self {
+            id: id.into(),
+            name: name.into(),
+            arguments,
+        }
+    }
 }
 
 /// Reason the model stopped generating.
@@ -412,6 +840,62 @@ pub enum StopReason {
     ToolUse,
 }
 
+/// Incremental piece of a streamed completion.
+///
+/// Text arrives as `delta`s; the last chunk of a stream carries the
+/// assembled [`CompletionResponse`] with final token usage.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamChunk {
+    /// Text generated since the previous chunk
+    pub delta: String,
+    /// Partial tool call, if this chunk extends one
+    pub tool_call: Option<ToolCallDelta>,
+    /// Stop reason, once the model has finished
+    pub stop_reason: Option<StopReason>,
+    /// Complete response (only on the final chunk)
+    pub response: Option<CompletionResponse>,
+}
+
+impl StreamChunk {
+    /// Chunk carrying a text delta.
+    pub fn text(delta: impl Into<String>) -> Self {
+        Self {
+            delta: delta.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Final chunk wrapping the assembled response.
+    pub fn finished(response: CompletionResponse) -> Self {
+        Self {
+            stop_reason: response.stop_reason,
+            response: Some(response),
+            ..Default::default()
+        }
+    }
+
+    /// Whether this is the final chunk of the stream.
+    pub fn is_final(&self) -> bool {
+        self.response.is_some()
+    }
+}
+
+/// Incremental update to a tool call in a streamed completion.
+///
+/// `id` and `name` are set on the first delta for a call; `arguments`
+/// carries the next fragment of the JSON-encoded input.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallDelta {
+    /// Position of the tool call within the response
+    pub index: usize,
+    /// Tool call ID
+    pub id: Option<String>,
+    /// Tool name
+    pub name: Option<String>,
+    /// Fragment of the JSON arguments
+    pub arguments: String,
+}
+
 /// Embedding request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingRequest {
@@ -428,12 +912,20 @@ pub struct EmbeddingResponse {
     pub model: String,
     /// Embedding vectors
     pub embeddings: Vec<Vec<f32>>,
-    /// Token usage
+    /// Token usage (input tokens only)
     pub usage: TokenUsage,
+    /// Cost in USD, if known
+    #[serde(default)]
+    pub cost: Option<f64>,
 }
 
 /// Cost tracking for a component or session.
+///
+/// Serializes to JSON for persistence across sessions. Missing fields load
+/// as zero and unknown fields are ignored, so files written by other
+/// versions still load.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CostTracker {
     /// Total input tokens
     pub total_input_tokens: u64,
@@ -447,21 +939,31 @@ pub struct CostTracker {
     pub total_cost: f64,
     /// Number of requests
     pub request_count: u64,
+    /// Number of retried attempts (not counted in `request_count`)
+    pub retry_count: u64,
     /// Per-model breakdown
     pub by_model: HashMap<String, ModelCosts>,
+    /// Per-provider breakdown, for requests recorded with a provider
+    pub by_provider: HashMap<Provider, ModelCosts>,
     /// Costs from root-level (premium) model calls
-    #[serde(default)]
     pub root_costs: TierCosts,
     /// Costs from recursive (budget) model calls
-    #[serde(default)]
     pub recursive_costs: TierCosts,
     /// Costs from extraction/fallback model calls
-    #[serde(default)]
     pub extraction_costs: TierCosts,
+    /// Costs from embedding calls, kept out of the completion totals
+    pub embedding_costs: ModelCosts,
+    /// Per-call cost distribution, when enabled with [`CostTracker::with_samples`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_samples: Option<SampleStats>,
+    /// Per-call latency distribution in milliseconds, when enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_samples: Option<SampleStats>,
 }
 
 /// Costs breakdown by model tier (for dual-model optimization).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TierCosts {
     /// Input tokens used
     pub input_tokens: u64,
@@ -475,6 +977,7 @@ pub struct TierCosts {
 
 /// Costs for a specific model.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ModelCosts {
     pub input_tokens: u64,
     pub output_tokens: u64,
@@ -487,6 +990,32 @@ impl CostTracker {
         Self::default()
     }
 
+    /// Also keep per-call cost and latency distributions.
+    ///
+    /// Off by default; memory stays bounded however many calls are recorded.
+    pub fn with_samples(mut self) -> Self {
+        self.cost_samples.get_or_insert_with(SampleStats::new);
+        self.latency_samples.get_or_insert_with(SampleStats::new);
+        self
+    }
+
+    /// Record how long a completion took, if samples are enabled.
+    pub fn record_latency(&mut self, latency_ms: u64) {
+        if let Some(samples) = &mut self.latency_samples {
+            samples.record(latency_ms as f64);
+        }
+    }
+
+    /// Estimated `p`th percentile (0–100) of per-call cost.
+    pub fn cost_percentile(&self, p: f64) -> Option<f64> {
+        self.cost_samples.as_ref()?.percentile(p)
+    }
+
+    /// Estimated `p`th percentile (0–100) of per-call latency in milliseconds.
+    pub fn latency_percentile(&self, p: f64) -> Option<f64> {
+        self.latency_samples.as_ref()?.percentile(p)
+    }
+
     /// Record usage from a completion response.
     pub fn record(&mut self, model: &str, usage: &TokenUsage, cost: Option<f64>) {
         self.total_input_tokens += usage.input_tokens;
@@ -498,6 +1027,9 @@ impl CostTracker {
         if let Some(c) = cost {
             self.total_cost += c;
         }
+        if let (Some(samples), Some(c)) = (&mut self.cost_samples, cost) {
+            samples.record(c);
+        }
 
         let model_costs = self.by_model.entry(model.to_string()).or_default();
         model_costs.input_tokens += usage.input_tokens;
@@ -508,6 +1040,65 @@ impl CostTracker {
         }
     }
 
+    /// Record usage and attribute it to the provider that served it.
+    pub fn record_for_provider(
+        &mut self,
+        provider: Provider,
+        model: &str,
+        usage: &TokenUsage,
+        cost: Option<f64>,
+    ) {
+        self.record(model, usage, cost);
+
+        let provider_costs = self.by_provider.entry(provider).or_default();
+        provider_costs.input_tokens += usage.input_tokens;
+        provider_costs.output_tokens += usage.output_tokens;
+        provider_costs.request_count += 1;
+        if let Some(c) = cost {
+            provider_costs.cost += c;
+        }
+    }
+
+    /// Record usage from an embedding response served by `provider`.
+    ///
+    /// The cost is added to `total_cost` and the model and provider
+    /// breakdowns, but the tokens and request count go to `embedding_costs`
+    /// only, so completion totals stay comparable. As with [`Self::record`],
+    /// an unknown cost leaves every cost total untouched.
+    pub fn record_embedding(
+        &mut self,
+        provider: Provider,
+        model: &str,
+        tokens: u64,
+        cost: Option<f64>,
+    ) {
+        if let Some(c) = cost {
+            self.total_cost += c;
+        }
+
+        for costs in [
+            &mut self.embedding_costs,
+            self.by_model.entry(model.to_string()).or_default(),
+            self.by_provider.entry(provider).or_default(),
+        ] {
+            costs.input_tokens += tokens;
+            costs.request_count += 1;
+            if let Some(c) = cost {
+                costs.cost += c;
+            }
+        }
+    }
+
+    /// Spend on completions, excluding embeddings.
+    pub fn completion_cost(&self) -> f64 {
+        self.total_cost - self.embedding_costs.cost
+    }
+
+    /// Record attempts that were retried before a request succeeded.
+    pub fn record_retries(&mut self, retries: u32) {
+        self.retry_count += u64::from(retries);
+    }
+
     /// Merge another tracker into this one.
     pub fn merge(&mut self, other: &CostTracker) {
         self.total_input_tokens += other.total_input_tokens;
@@ -516,6 +1107,7 @@ impl CostTracker {
         self.total_cache_creation_tokens += other.total_cache_creation_tokens;
         self.total_cost += other.total_cost;
         self.request_count += other.request_count;
+        self.retry_count += other.retry_count;
 
         for (model, costs) in &other.by_model {
             let entry = self.by_model.entry(model.clone()).or_default();
@@ -525,6 +1117,27 @@ impl CostTracker {
             entry.request_count += costs.request_count;
         }
 
+        for (provider, costs) in &other.by_provider {
+            let entry = self.by_provider.entry(*provider).or_default();
+            entry.input_tokens += costs.input_tokens;
+            entry.output_tokens += costs.output_tokens;
+            entry.cost += costs.cost;
+            entry.request_count += costs.request_count;
+        }
+
+        self.embedding_costs.input_tokens += other.embedding_costs.input_tokens;
+        self.embedding_costs.cost += other.embedding_costs.cost;
+        self.embedding_costs.request_count += other.embedding_costs.request_count;
+
+        for (samples, other_samples) in [
+            (&mut self.cost_samples, &other.cost_samples),
+            (&mut self.latency_samples, &other.latency_samples),
+        ] {
+            if let (Some(samples), Some(other_samples)) = (samples, other_samples) {
+                samples.merge(other_samples);
+            }
+        }
+
         // Merge tier costs
         self.root_costs.merge(&other.root_costs);
         self.recursive_costs.merge(&other.recursive_costs);
@@ -583,6 +1196,51 @@ impl CostTracker {
         );
     }
 
+    /// Serialize to JSON.
+    ///
+    /// The output includes a computed `tier_breakdown` for readers of the
+    /// file; it is ignored on load and recomputed from the tier totals.
+    pub fn to_json(&self) -> Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert(
+                "tier_breakdown".to_string(),
+                serde_json::to_value(self.tier_breakdown())?,
+            );
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// Deserialize from JSON written by [`CostTracker::to_json`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(Error::Serialization)
+    }
+
+    /// Save to a file, replacing it atomically.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, self.to_json()?)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| Error::Internal(format!("Failed to save cost tracker: {}", e)))
+    }
+
+    /// Load from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| Error::Internal(format!("Failed to load cost tracker: {}", e)))?;
+        Self::from_json(&json)
+    }
+
+    /// Load from a file, or start empty if it does not exist yet.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
     /// Get a breakdown report of costs by tier.
     pub fn tier_breakdown(&self) -> TierBreakdown {
         let root_pct = if self.total_cost > 0.0 {
@@ -618,6 +1276,7 @@ impl CostTracker {
             extraction_tokens: self.extraction_costs.input_tokens
                 + self.extraction_costs.output_tokens,
             extraction_percentage: extraction_pct,
+            embedding_cost: self.embedding_costs.cost,
             total_cost: self.total_cost,
             estimated_single_model_cost: self.estimate_single_model_cost(),
             savings_percentage: self.calculate_savings_percentage(),
@@ -633,6 +1292,7 @@ impl CostTracker {
         self.root_costs.cost
             + (self.recursive_costs.cost * 3.0)
             + (self.extraction_costs.cost * 3.0)
+            + self.embedding_costs.cost
     }
 
     /// Calculate the percentage savings from dual-model optimization.
@@ -691,6 +1351,10 @@ pub struct TierBreakdown {
     /// Percentage of total cost from extraction/fallback model
     pub extraction_percentage: f64,
 
+    /// Cost from embedding calls (not tiered)
+    #[serde(default)]
+    pub embedding_cost: f64,
+
     /// Total actual cost
     pub total_cost: f64,
     /// Estimated cost if only using root model
@@ -766,6 +1430,78 @@ mod tests {
         assert_eq!(model_costs.request_count, 2);
     }
 
+    #[test]
+    fn test_cost_tracker_samples_are_opt_in() {
+        let usage = TokenUsage {
+            input_tokens: 100,
+            output_tokens: 10,
+            cache_read_tokens: None,
+            cache_creation_tokens: None,
+        };
+
+        let mut plain = CostTracker::new();
+        plain.record("m", &usage, Some(0.01));
+        plain.record_latency(250);
+        assert_eq!(plain.cost_percentile(50.0), None);
+        assert!(!plain.to_json().unwrap().contains("samples"));
+
+        let mut tracker = CostTracker::new().with_samples();
+        for i in 1..=200u64 {
+            tracker.record("m", &usage, Some(i as f64 / 1000.0));
+            tracker.record_latency(i * 10);
+        }
+        let p95 = tracker.latency_percentile(95.0).unwrap();
+        assert!((p95 - 1900.0).abs() <= 1900.0 * 0.02, "p95 {}", p95);
+        let costs = tracker.cost_samples.as_ref().unwrap();
+        assert_eq!(costs.max(), Some(0.2));
+        assert!((costs.mean().unwrap() - 0.1005).abs() < 1e-9);
+
+        let restored = CostTracker::from_json(&tracker.to_json().unwrap()).unwrap();
+        assert_eq!(restored.latency_samples, tracker.latency_samples);
+
+        let mut merged = CostTracker::new().with_samples();
+        merged.merge(&tracker);
+        merged.merge(&plain);
+        assert_eq!(merged.cost_samples.unwrap().count(), 200);
+    }
+
+    #[test]
+    fn test_cost_tracker_records_embeddings_separately() {
+        let mut tracker = CostTracker::new();
+        let usage = TokenUsage {
+            input_tokens: 1000,
+            output_tokens: 500,
+            cache_read_tokens: None,
+            cache_creation_tokens: None,
+        };
+        tracker.record("claude-3-5-sonnet", &usage, Some(0.01));
+
+        let spec = ModelSpec::text_embedding_3_small();
+        let cost = spec.calculate_embedding_cost(500_000);
+        assert!((cost - 0.01).abs() < 1e-9);
+        tracker.record_embedding(Provider::OpenAI, &spec.id, 500_000, Some(cost));
+
+        assert!((tracker.total_cost - 0.02).abs() < 1e-9);
+        assert!((tracker.completion_cost() - 0.01).abs() < 1e-9);
+        assert_eq!(tracker.total_input_tokens, 1000);
+        assert_eq!(tracker.request_count, 1);
+        assert_eq!(tracker.embedding_costs.input_tokens, 500_000);
+        assert_eq!(tracker.embedding_costs.request_count, 1);
+        assert_eq!(tracker.by_model[&spec.id].request_count, 1);
+        assert_eq!(tracker.by_provider[&Provider::OpenAI].request_count, 1);
+        assert!((tracker.by_provider[&Provider::OpenAI].cost - 0.01).abs() < 1e-9);
+        assert!((tracker.tier_breakdown().embedding_cost - 0.01).abs() < 1e-9);
+
+        tracker.record_embedding(Provider::OpenAI, "unpriced-embedder", 1_000, None);
+        assert!((tracker.total_cost - 0.02).abs() < 1e-9);
+        assert!((tracker.embedding_costs.cost - 0.01).abs() < 1e-9);
+        assert_eq!(tracker.embedding_costs.request_count, 2);
+        assert_eq!(tracker.by_provider[&Provider::OpenAI].request_count, 2);
+
+        let restored = CostTracker::from_json(&tracker.to_json().unwrap()).unwrap();
+        assert!((restored.embedding_costs.cost - 0.01).abs() < 1e-9);
+    }
+
     #[test]
     fn test_cost_tracker_record_tiered_includes_extraction() {
         let mut tracker = CostTracker::new();
@@ -814,6 +1550,73 @@ mod tests {
         assert!(breakdown.savings_percentage >= 0.0);
     }
 
+    #[test]
+    fn test_cost_tracker_persists_tiered_usage() {
+        let usage = TokenUsage {
+            input_tokens: 1000,
+            output_tokens: 200,
+            cache_read_tokens: Some(50),
+            cache_creation_tokens: None,
+        };
+        let mut tracker = CostTracker::new();
+        tracker.record_tiered("claude-opus", &usage, Some(0.03), ModelCallTier::Root);
+        tracker.record_tiered("claude-haiku", &usage, Some(0.01), ModelCallTier::Recursive);
+        tracker.record_for_provider(Provider::OpenAI, "gpt-4o-mini", &usage, Some(0.005));
+        tracker.record_retries(2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("costs.json");
+        tracker.save(&path).unwrap();
+        let loaded = CostTracker::load(&path).unwrap();
+
+        assert_eq!(loaded.request_count, 3);
+        assert_eq!(loaded.retry_count, 2);
+        assert_eq!(loaded.total_cache_read_tokens, 150);
+        assert!((loaded.total_cost - 0.045).abs() < 1e-12);
+        assert_eq!(loaded.by_model["claude-haiku"].request_count, 1);
+        assert_eq!(loaded.by_provider[&Provider::OpenAI].cost, 0.005);
+
+        let (before, after) = (tracker.tier_breakdown(), loaded.tier_breakdown());
+        assert_eq!(after.root_requests, before.root_requests);
+        assert_eq!(after.recursive_tokens, before.recursive_tokens);
+        assert_eq!(after.root_percentage, before.root_percentage);
+        assert_eq!(after.savings_percentage, before.savings_percentage);
+
+        // Resuming a budget adds to the persisted totals.
+        let mut resumed = CostTracker::load_or_default(&path).unwrap();
+        resumed.merge(&tracker);
+        assert_eq!(resumed.root_costs.request_count, 2);
+        assert!(
+            CostTracker::load_or_default(dir.path().join("missing.json"))
+                .unwrap()
+                .by_model
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cost_tracker_json_is_forward_compatible() {
+        let tracker = CostTracker::from_json(
+            r#"{
+                "total_cost": 1.5,
+                "request_count": 3,
+                "by_model": {"m": {"cost": 1.5, "request_count": 3, "latency_ms": 10}},
+                "root_costs": {"cost": 1.0},
+                "budget_window": "daily"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(tracker.total_cost, 1.5);
+        assert_eq!(tracker.total_input_tokens, 0);
+        assert_eq!(tracker.by_model["m"].request_count, 3);
+        assert_eq!(tracker.root_costs.cost, 1.0);
+        assert_eq!(tracker.root_costs.request_count, 0);
+
+        let json: serde_json::Value = serde_json::from_str(&tracker.to_json().unwrap()).unwrap();
+        assert_eq!(json["tier_breakdown"]["root_cost"], 1.0);
+    }
+
     #[test]
     fn test_token_usage_effective() {
         let usage = TokenUsage {
@@ -0,0 +1,191 @@
+//! Async wrapper around a [`MemoryStore`] backend.
+//!
+//! ## Concurrency model
+//!
+//! Backends are `Send + Sync` but synchronous: [`SqliteMemoryStore`] owns a
+//! single SQLite connection behind a mutex, and its methods block the calling
+//! thread for the duration of the query, which stalls an async executor.
+//! [`AsyncMemoryStore`] shares the backend through an `Arc` and runs every
+//! operation on tokio's blocking pool via `spawn_blocking`, so async callers
+//! only await.
+//!
+//! With SQLite, operations still serialize on the connection mutex, so
+//! concurrent calls queue rather than run in parallel, and each one sees the
+//! effects of the ones that finished before it. No lock is held across an
+//! `.await`, so awaiting several operations at once cannot deadlock.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::error::{Error, Result};
+use crate::memory::backend::MemoryStore;
+use crate::memory::store::{MemoryStats, SqliteMemoryStore};
+use crate::memory::types::*;
+
+/// Async facade over a shared [`MemoryStore`] backend.
+///
+/// Cloning is cheap and clones share the same backend.
+#[derive(Clone)]
+pub struct AsyncMemoryStore {
+    inner: Arc<dyn MemoryStore>,
+}
+
+impl AsyncMemoryStore {
+    /// Wrap an existing store.
+    pub fn new(store: impl MemoryStore + 'static) -> Self {
+        Self {
+            inner: Arc::new(store),
+        }
+    }
+
+    /// Wrap a backend that is already shared.
+    pub fn from_shared(store: Arc<dyn MemoryStore>) -> Self {
+        Self { inner: store }
+    }
+
+    /// Open or create a store at the given path.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let store = tokio::task::spawn_blocking(move || SqliteMemoryStore::open(path))
+            .await
+            .map_err(join_error)??;
+        Ok(Self::new(store))
+    }
+
+    /// Create an in-memory store (for testing).
+    pub fn in_memory() -> Result<Self> {
+        SqliteMemoryStore::in_memory().map(Self::new)
+    }
+
+    /// The underlying synchronous backend.
+    ///
+    /// Calling it from async code blocks the executor.
+    pub fn blocking(&self) -> &dyn MemoryStore {
+        self.inner.as_ref()
+    }
+
+    /// Run `f` against the store on the blocking pool.
+    async fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&dyn MemoryStore) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || f(store.as_ref()))
+            .await
+            .map_err(join_error)?
+    }
+
+    /// Add a node to the store.
+    pub async fn add_node(&self, node: Node) -> Result<()> {
+        self.run(move |store| store.add_node(&node)).await
+    }
+
+    /// Get a node by ID.
+    pub async fn get_node(&self, id: NodeId) -> Result<Option<Node>> {
+        self.run(move |store| store.get_node(&id)).await
+    }
+
+    /// Update an existing node.
+    pub async fn update_node(&self, node: Node) -> Result<()> {
+        self.run(move |store| store.update_node(&node)).await
+    }
+
+    /// Delete a node by ID.
+    pub async fn delete_node(&self, id: NodeId) -> Result<bool> {
+        self.run(move |store| store.delete_node(&id)).await
+    }
+
+    /// Query nodes with filters.
+    pub async fn query_nodes(&self, query: NodeQuery) -> Result<Vec<Node>> {
+        self.run(move |store| store.query_nodes(&query)).await
+    }
+
+    /// Full-text search over node content.
+    pub async fn search_content(
+        &self,
+        query: impl Into<String>,
+        limit: usize,
+    ) -> Result<Vec<Node>> {
+        let query = query.into();
+        self.run(move |store| store.search_content(&query, limit))
+            .await
+    }
+
+    /// Provenance recorded for a node.
+    pub async fn provenance_for(&self, id: NodeId) -> Result<Option<Provenance>> {
+        self.run(move |store| store.provenance_for(&id)).await
+    }
+
+    /// Add a hyperedge.
+    pub async fn add_edge(&self, edge: HyperEdge) -> Result<()> {
+        self.run(move |store| store.add_edge(&edge)).await
+    }
+
+    /// Get all hyperedges containing a node.
+    pub async fn get_edges_for_node(&self, node_id: NodeId) -> Result<Vec<HyperEdge>> {
+        self.run(move |store| store.get_edges_for_node(&node_id))
+            .await
+    }
+
+    /// Promote nodes to the next tier.
+    pub async fn promote(
+        &self,
+        node_ids: Vec<NodeId>,
+        reason: impl Into<String>,
+    ) -> Result<Vec<NodeId>> {
+        let reason = reason.into();
+        self.run(move |store| store.promote(&node_ids, &reason))
+            .await
+    }
+
+    /// Apply decay to nodes based on time and access patterns.
+    pub async fn decay(&self, factor: f64, min_confidence: f64) -> Result<Vec<NodeId>> {
+        self.run(move |store| store.decay(factor, min_confidence))
+            .await
+    }
+
+    /// Store statistics.
+    pub async fn stats(&self) -> Result<MemoryStats> {
+        self.run(|store| store.stats()).await
+    }
+}
+
+impl From<SqliteMemoryStore> for AsyncMemoryStore {
+    fn from(store: SqliteMemoryStore) -> Self {
+        Self::new(store)
+    }
+}
+
+fn join_error(e: tokio::task::JoinError) -> Error {
+    Error::Internal(format!("Memory store task failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_inserts_and_search() {
+        let store = AsyncMemoryStore::in_memory().unwrap();
+
+        let inserts = (0..32).map(|i| {
+            let store = store.clone();
+            async move {
+                let node = Node::new(NodeType::Fact, format!("Service {} uses gRPC", i));
+                let id = node.id.clone();
+                store.add_node(node).await.map(|_| id)
+            }
+        });
+        let ids: Vec<NodeId> = futures::future::try_join_all(inserts).await.unwrap();
+
+        let (found, stats) = tokio::join!(store.search_content("gRPC", 100), store.stats());
+        assert_eq!(found.unwrap().len(), 32);
+        assert_eq!(stats.unwrap().total_nodes, 32);
+
+        let promoted = store.promote(ids[..2].to_vec(), "hot").await.unwrap();
+        assert_eq!(promoted.len(), 2);
+        let node = store.get_node(ids[0].clone()).await.unwrap().unwrap();
+        assert_eq!(node.tier, Tier::Session);
+    }
+}
@@ -0,0 +1,293 @@
+//! Storage-backend abstraction for the hypergraph memory.
+//!
+//! [`MemoryStore`] covers the node, hyperedge, tier-evolution, and stats
+//! operations that callers need regardless of where memory lives.
+//! [`SqliteMemoryStore`] is the bundled implementation. IDs are UUID
+//! newtypes serialized as strings, so they carry over unchanged to any
+//! backend that can store text.
+//!
+//! With the `postgres` feature, `PostgresMemoryStore` provides a shared,
+//! multi-writer backend. Both run the same tier-evolution logic and share
+//! schema versions.
+
+use crate::error::Result;
+#[cfg(feature = "postgres")]
+use crate::memory::postgres::PostgresMemoryStore;
+use crate::memory::store::{EvolutionEntry, MemoryStats, SqliteMemoryStore};
+use crate::memory::types::*;
+
+/// Operations every memory backend supports.
+///
+/// Methods are synchronous; wrap a backend in
+/// [`AsyncMemoryStore`](crate::memory::AsyncMemoryStore) to call it from
+/// async code.
+pub trait MemoryStore: Send + Sync {
+    /// Add a node.
+    fn add_node(&self, node: &Node) -> Result<()>;
+
+    /// Get a node by ID.
+    fn get_node(&self, id: &NodeId) -> Result<Option<Node>>;
+
+    /// Replace an existing node.
+    fn update_node(&self, node: &Node) -> Result<()>;
+
+    /// Delete a node, returning whether it existed.
+    fn delete_node(&self, id: &NodeId) -> Result<bool>;
+
+    /// Query nodes with filters.
+    fn query_nodes(&self, query: &NodeQuery) -> Result<Vec<Node>>;
+
+    /// Full-text search over node content.
+    fn search_content(&self, query: &str, limit: usize) -> Result<Vec<Node>>;
+
+    /// Provenance recorded for a node.
+    fn provenance_for(&self, id: &NodeId) -> Result<Option<Provenance>>;
+
+    /// Add a hyperedge.
+    fn add_edge(&self, edge: &HyperEdge) -> Result<()>;
+
+    /// Get all hyperedges containing a node.
+    fn get_edges_for_node(&self, node_id: &NodeId) -> Result<Vec<HyperEdge>>;
+
+    /// Delete a hyperedge, returning whether it existed.
+    fn delete_edge(&self, id: &EdgeId) -> Result<bool>;
+
+    /// Promote nodes to the next tier, returning those promoted.
+    fn promote(&self, node_ids: &[NodeId], reason: &str) -> Result<Vec<NodeId>>;
+
+    /// Apply time-based confidence decay, returning the nodes decayed.
+    fn decay(&self, factor: f64, min_confidence: f64) -> Result<Vec<NodeId>>;
+
+    /// Move nodes from one tier to another.
+    fn consolidate(&self, from_tier: Tier, to_tier: Tier) -> Result<ConsolidationResult>;
+
+    /// Apply an evolution policy, returning the recorded transitions.
+    fn evolve(&self, policy: &EvolutionPolicy) -> Result<Vec<EvolutionEntry>>;
+
+    /// Tier transitions recorded for a node.
+    fn get_evolution_history(&self, node_id: &NodeId) -> Result<Vec<EvolutionEntry>>;
+
+    /// Store statistics.
+    fn stats(&self) -> Result<MemoryStats>;
+}
+
+impl MemoryStore for SqliteMemoryStore {
+    fn add_node(&self, node: &Node) -> Result<()> {
+        SqliteMemoryStore::add_node(self, node)
+    }
+
+    fn get_node(&self, id: &NodeId) -> Result<Option<Node>> {
+        SqliteMemoryStore::get_node(self, id)
+    }
+
+    fn update_node(&self, node: &Node) -> Result<()> {
+        SqliteMemoryStore::update_node(self, node)
+    }
+
+    fn delete_node(&self, id: &NodeId) -> Result<bool> {
+        SqliteMemoryStore::delete_node(self, id)
+    }
+
+    fn query_nodes(&self, query: &NodeQuery) -> Result<Vec<Node>> {
+        SqliteMemoryStore::query_nodes(self, query)
+    }
+
+    fn search_content(&self, query: &str, limit: usize) -> Result<Vec<Node>> {
+        SqliteMemoryStore::search_content(self, query, limit)
+    }
+
+    fn provenance_for(&self, id: &NodeId) -> Result<Option<Provenance>> {
+        SqliteMemoryStore::provenance_for(self, id)
+    }
+
+    fn add_edge(&self, edge: &HyperEdge) -> Result<()> {
+        SqliteMemoryStore::add_edge(self, edge)
+    }
+
+    fn get_edges_for_node(&self, node_id: &NodeId) -> Result<Vec<HyperEdge>> {
+        SqliteMemoryStore::get_edges_for_node(self, node_id)
+    }
+
+    fn delete_edge(&self, id: &EdgeId) -> Result<bool> {
+        SqliteMemoryStore::delete_edge(self, id)
+    }
+
+    fn promote(&self, node_ids: &[NodeId], reason: &str) -> Result<Vec<NodeId>> {
+        SqliteMemoryStore::promote(self, node_ids, reason)
+    }
+
+    fn decay(&self, factor: f64, min_confidence: f64) -> Result<Vec<NodeId>> {
+        SqliteMemoryStore::decay(self, factor, min_confidence)
+    }
+
+    fn consolidate(&self, from_tier: Tier, to_tier: Tier) -> Result<ConsolidationResult> {
+        SqliteMemoryStore::consolidate(self, from_tier, to_tier)
+    }
+
+    fn evolve(&self, policy: &EvolutionPolicy) -> Result<Vec<EvolutionEntry>> {
+        SqliteMemoryStore::evolve(self, policy)
+    }
+
+    fn get_evolution_history(&self, node_id: &NodeId) -> Result<Vec<EvolutionEntry>> {
+        SqliteMemoryStore::get_evolution_history(self, node_id)
+    }
+
+    fn stats(&self) -> Result<MemoryStats> {
+        SqliteMemoryStore::stats(self)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl MemoryStore for PostgresMemoryStore {
+    fn add_node(&self, node: &Node) -> Result<()> {
+        PostgresMemoryStore::add_node(self, node)
+    }
+
+    fn get_node(&self, id: &NodeId) -> Result<Option<Node>> {
+        PostgresMemoryStore::get_node(self, id)
+    }
+
+    fn update_node(&self, node: &Node) -> Result<()> {
+        PostgresMemoryStore::update_node(self, node)
+    }
+
+    fn delete_node(&self, id: &NodeId) -> Result<bool> {
+        PostgresMemoryStore::delete_node(self, id)
+    }
+
+    fn query_nodes(&self, query: &NodeQuery) -> Result<Vec<Node>> {
+        PostgresMemoryStore::query_nodes(self, query)
+    }
+
+    fn search_content(&self, query: &str, limit: usize) -> Result<Vec<Node>> {
+        PostgresMemoryStore::search_content(self, query, limit)
+    }
+
+    fn provenance_for(&self, id: &NodeId) -> Result<Option<Provenance>> {
+        PostgresMemoryStore::provenance_for(self, id)
+    }
+
+    fn add_edge(&self, edge: &HyperEdge) -> Result<()> {
+        PostgresMemoryStore::add_edge(self, edge)
+    }
+
+    fn get_edges_for_node(&self, node_id: &NodeId) -> Result<Vec<HyperEdge>> {
+        PostgresMemoryStore::get_edges_for_node(self, node_id)
+    }
+
+    fn delete_edge(&self, id: &EdgeId) -> Result<bool> {
+        PostgresMemoryStore::delete_edge(self, id)
+    }
+
+    fn promote(&self, node_ids: &[NodeId], reason: &str) -> Result<Vec<NodeId>> {
+        PostgresMemoryStore::promote(self, node_ids, reason)
+    }
+
+    fn decay(&self, factor: f64, min_confidence: f64) -> Result<Vec<NodeId>> {
+        PostgresMemoryStore::decay(self, factor, min_confidence)
+    }
+
+    fn consolidate(&self, from_tier: Tier, to_tier: Tier) -> Result<ConsolidationResult> {
+        PostgresMemoryStore::consolidate(self, from_tier, to_tier)
+    }
+
+    fn evolve(&self, policy: &EvolutionPolicy) -> Result<Vec<EvolutionEntry>> {
+        PostgresMemoryStore::evolve(self, policy)
+    }
+
+    fn get_evolution_history(&self, node_id: &NodeId) -> Result<Vec<EvolutionEntry>> {
+        PostgresMemoryStore::get_evolution_history(self, node_id)
+    }
+
+    fn stats(&self) -> Result<MemoryStats> {
+        PostgresMemoryStore::stats(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backend-agnostic behaviour every implementation must match.
+    fn check_parity(store: &dyn MemoryStore) {
+        let fact = Node::new(NodeType::Fact, "The billing service retries webhooks");
+        let entity = Node::new(NodeType::Entity, "billing service");
+        store.add_node(&fact).unwrap();
+        store.add_node(&entity).unwrap();
+
+        let found = store.search_content("webhooks", 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, fact.id);
+
+        let edge = HyperEdge::binary(
+            EdgeType::Semantic,
+            fact.id.clone(),
+            entity.id.clone(),
+            "about",
+        );
+        store.add_edge(&edge).unwrap();
+        assert_eq!(store.get_edges_for_node(&entity.id).unwrap().len(), 1);
+
+        let promoted = store
+            .promote(std::slice::from_ref(&fact.id), "confirmed")
+            .unwrap();
+        assert_eq!(promoted, vec![fact.id.clone()]);
+        assert_eq!(
+            store.get_node(&fact.id).unwrap().unwrap().tier,
+            Tier::Session
+        );
+        assert!(!store.get_evolution_history(&fact.id).unwrap().is_empty());
+
+        let stats = store.stats().unwrap();
+        assert_eq!(stats.total_nodes, 2);
+        assert_eq!(stats.total_edges, 1);
+
+        assert!(store.delete_edge(&edge.id).unwrap());
+        assert!(store.delete_node(&entity.id).unwrap());
+        assert!(store.get_node(&entity.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_sqlite_backend_parity() {
+        check_parity(&SqliteMemoryStore::in_memory().unwrap());
+    }
+
+    /// Runs against the database in `RLM_TEST_POSTGRES_URL`, in a fresh
+    /// schema that is dropped afterwards. Skipped when the variable is unset.
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_postgres_backend_parity() {
+        let Ok(url) = std::env::var("RLM_TEST_POSTGRES_URL") else {
+            eprintln!("RLM_TEST_POSTGRES_URL not set, skipping");
+            return;
+        };
+        let schema = format!("rlm_test_{}", uuid::Uuid::new_v4().simple());
+
+        let mut client = postgres::Client::connect(&url, postgres::NoTls).unwrap();
+        client
+            .batch_execute(&format!(
+                "CREATE SCHEMA {schema}; SET search_path TO {schema}"
+            ))
+            .unwrap();
+        let store = PostgresMemoryStore::from_client(client).unwrap();
+        assert_eq!(
+            store.schema_version().unwrap(),
+            crate::memory::SCHEMA_VERSION
+        );
+
+        check_parity(&store);
+
+        // Stemmed search, which the SQLite tokenizer does not do.
+        let retry = Node::new(NodeType::Fact, "Retrying failed payments is safe");
+        store.add_node(&retry).unwrap();
+        let found = store.search_content("payment", 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, retry.id);
+
+        drop(store);
+        let mut client = postgres::Client::connect(&url, postgres::NoTls).unwrap();
+        client
+            .batch_execute(&format!("DROP SCHEMA {schema} CASCADE"))
+            .unwrap();
+    }
+}
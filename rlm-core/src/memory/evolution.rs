@@ -0,0 +1,225 @@
+//! Tier-evolution logic shared by every memory backend.
+//!
+//! Promotion, decay, consolidation and policy sweeps only need to read,
+//! update and log nodes, so they are written once against
+//! [`EvolutionBackend`] and each store supplies the primitives. This keeps
+//! backends in agreement about when a node changes tier.
+
+use chrono::{DateTime, Utc};
+
+use crate::error::Result;
+use crate::memory::store::EvolutionEntry;
+use crate::memory::types::*;
+
+/// Primitive operations the shared evolution logic runs on.
+pub(crate) trait EvolutionBackend {
+    fn get_node(&self, id: &NodeId) -> Result<Option<Node>>;
+
+    fn update_node(&self, node: &Node) -> Result<()>;
+
+    fn query_nodes(&self, query: &NodeQuery) -> Result<Vec<Node>>;
+
+    fn get_edges_for_node(&self, node_id: &NodeId) -> Result<Vec<HyperEdge>>;
+
+    /// Append a tier transition to the evolution log.
+    fn log_evolution(
+        &self,
+        node_id: &NodeId,
+        operation: &str,
+        from_tier: Option<Tier>,
+        to_tier: Option<Tier>,
+        reason: &str,
+    ) -> Result<()>;
+}
+
+/// Promote nodes to a higher tier.
+pub(crate) fn promote(
+    store: &impl EvolutionBackend,
+    node_ids: &[NodeId],
+    reason: &str,
+) -> Result<Vec<NodeId>> {
+    let mut promoted = Vec::new();
+
+    for node_id in node_ids {
+        if let Some(mut node) = store.get_node(node_id)? {
+            if let Some(next_tier) = node.tier.next() {
+                let from_tier = node.tier;
+                node.tier = next_tier;
+                node.updated_at = Utc::now();
+                store.update_node(&node)?;
+                store.log_evolution(
+                    node_id,
+                    "promote",
+                    Some(from_tier),
+                    Some(next_tier),
+                    reason,
+                )?;
+                promoted.push(node_id.clone());
+            }
+        }
+    }
+
+    Ok(promoted)
+}
+
+/// Apply decay to nodes based on time and access patterns.
+pub(crate) fn decay(
+    store: &impl EvolutionBackend,
+    factor: f64,
+    min_confidence: f64,
+) -> Result<Vec<NodeId>> {
+    let nodes = store.query_nodes(&NodeQuery::new().min_confidence(min_confidence))?;
+    let mut decayed = Vec::new();
+
+    for mut node in nodes {
+        // Decay formula: confidence * factor^(hours_since_access / 24)
+        let hours = (Utc::now() - node.last_accessed).num_hours() as f64;
+        let decay_exponent = hours / 24.0;
+        let new_confidence = node.confidence * factor.powf(decay_exponent);
+
+        if new_confidence < node.confidence {
+            node.confidence = new_confidence.max(0.0);
+            node.updated_at = Utc::now();
+            store.update_node(&node)?;
+            decayed.push(node.id.clone());
+        }
+    }
+
+    Ok(decayed)
+}
+
+/// Consolidate nodes from one tier to another.
+pub(crate) fn consolidate(
+    store: &impl EvolutionBackend,
+    from_tier: Tier,
+    to_tier: Tier,
+) -> Result<ConsolidationResult> {
+    let nodes = store.query_nodes(&NodeQuery::new().tiers(vec![from_tier]))?;
+    let source_ids: Vec<NodeId> = nodes.iter().map(|n| n.id.clone()).collect();
+
+    // For now, just promote nodes above a confidence threshold
+    let promoted = promote(
+        store,
+        &source_ids,
+        &format!("Consolidation from {} to {}", from_tier, to_tier),
+    )?;
+
+    Ok(ConsolidationResult {
+        source_nodes: source_ids,
+        consolidated_node: None,
+        promoted_nodes: promoted,
+        archived_nodes: Vec::new(),
+        summary: format!("Consolidated from {} to {}", from_tier, to_tier),
+        merges: Vec::new(),
+    })
+}
+
+/// Run one tier-evolution sweep according to `policy`.
+pub(crate) fn evolve(
+    store: &impl EvolutionBackend,
+    policy: &EvolutionPolicy,
+) -> Result<Vec<EvolutionEntry>> {
+    let now = Utc::now();
+    let nodes = store.query_nodes(&NodeQuery::new().tiers(vec![
+        Tier::Task,
+        Tier::Session,
+        Tier::LongTerm,
+    ]))?;
+
+    let mut moves = Vec::new();
+    for node in &nodes {
+        let idle = now - node.last_accessed;
+
+        if node.confidence < policy.archive_confidence_floor {
+            moves.push((
+                node,
+                "archive",
+                Tier::Archive,
+                format!(
+                    "confidence {:.2} below archive floor {:.2}",
+                    node.confidence, policy.archive_confidence_floor
+                ),
+            ));
+            continue;
+        }
+
+        match node.tier {
+            Tier::Task if node.access_count >= policy.session_min_access => {
+                moves.push((
+                    node,
+                    "promote",
+                    Tier::Session,
+                    format!("accessed {} times", node.access_count),
+                ));
+            }
+            Tier::Session
+                if node.access_count >= policy.long_term_min_access
+                    && now - node.created_at >= policy.long_term_min_age =>
+            {
+                moves.push((
+                    node,
+                    "promote",
+                    Tier::LongTerm,
+                    format!(
+                        "accessed {} times over {}h",
+                        node.access_count,
+                        (now - node.created_at).num_hours()
+                    ),
+                ));
+            }
+            Tier::LongTerm if idle >= policy.archive_after_idle => {
+                if policy.protect_referenced && has_active_edge(store, node, policy, now)? {
+                    continue;
+                }
+                moves.push((
+                    node,
+                    "archive",
+                    Tier::Archive,
+                    format!("not accessed for {} days", idle.num_days()),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let mut entries = Vec::with_capacity(moves.len());
+    for (node, operation, to_tier, reason) in moves {
+        let mut updated = node.clone();
+        updated.tier = to_tier;
+        updated.updated_at = now;
+        store.update_node(&updated)?;
+        store.log_evolution(&node.id, operation, Some(node.tier), Some(to_tier), &reason)?;
+        entries.push(EvolutionEntry {
+            node_id: node.id.clone(),
+            operation: operation.to_string(),
+            from_tier: Some(node.tier),
+            to_tier: Some(to_tier),
+            reason,
+            timestamp: now,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Whether `node` shares a hyperedge with a node that is neither
+/// archived nor idle past the policy's archive window.
+fn has_active_edge(
+    store: &impl EvolutionBackend,
+    node: &Node,
+    policy: &EvolutionPolicy,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    for edge in store.get_edges_for_node(&node.id)? {
+        for member in edge.members.iter().filter(|m| m.node_id != node.id) {
+            if let Some(other) = store.get_node(&member.node_id)? {
+                if other.tier != Tier::Archive
+                    && now - other.last_accessed < policy.archive_after_idle
+                {
+                    return Ok(true);
+                }
+            }
+        }
+    }
+    Ok(false)
+}
@@ -27,13 +27,27 @@
 //! store.promote(&[fact.id], "Frequently accessed")?;
 //! ```
 
+#[cfg(feature = "tokio-runtime")]
+mod async_store;
+mod backend;
+mod evolution;
+#[cfg(feature = "postgres")]
+mod postgres;
 mod schema;
 mod store;
 mod types;
 
+#[cfg(feature = "tokio-runtime")]
+pub use async_store::AsyncMemoryStore;
+pub use backend::MemoryStore;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresMemoryStore;
 pub use schema::{get_schema_version, initialize_schema, is_initialized, SCHEMA_VERSION};
-pub use store::{EvolutionEntry, MemoryStats, SqliteMemoryStore};
+pub use store::{
+    EvolutionEntry, ImportMode, ImportOptions, ImportReport, MemoryExport, MemoryStats,
+    SqliteMemoryStore,
+};
 pub use types::{
-    ConsolidationResult, EdgeId, EdgeMember, EdgeType, HyperEdge, Node, NodeId, NodeQuery,
-    NodeType, Provenance, ProvenanceSource, Tier,
+    ConsolidationResult, EdgeId, EdgeMember, EdgeType, EvolutionPolicy, HyperEdge, Node, NodeId,
+    NodeMerge, NodeQuery, NodeType, Provenance, ProvenanceSource, Tier,
 };
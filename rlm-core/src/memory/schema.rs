@@ -1,9 +1,19 @@
-//! SQLite schema and migrations for the memory system.
+//! Schema and migrations for the memory system.
+//!
+//! Every backend records applied migrations in a `schema_version` table and
+//! shares [`SCHEMA_VERSION`], so version N means the same tables and columns
+//! whether the store is SQLite or Postgres. A migration is added to each
+//! backend's list below in the same change.
 
 use rusqlite::{Connection, Result as SqliteResult};
 
 /// Current schema version.
-pub const SCHEMA_VERSION: i32 = 1;
+pub const SCHEMA_VERSION: i32 = 2;
+
+/// Migrations a store at `current` still needs, oldest first.
+pub(crate) fn pending_migrations(current: i32) -> impl Iterator<Item = i32> {
+    (current + 1)..=SCHEMA_VERSION
+}
 
 /// Initialize the database schema.
 pub fn initialize_schema(conn: &Connection) -> SqliteResult<()> {
@@ -31,9 +41,12 @@ pub fn initialize_schema(conn: &Connection) -> SqliteResult<()> {
         )
         .unwrap_or(0);
 
-    // Apply migrations
-    if current_version < 1 {
-        apply_v1_schema(conn)?;
+    for version in pending_migrations(current_version) {
+        match version {
+            1 => apply_v1_schema(conn)?,
+            2 => apply_v2_schema(conn)?,
+            _ => unreachable!("no SQLite migration for schema version {}", version),
+        }
     }
 
     Ok(())
@@ -169,6 +182,46 @@ fn apply_v1_schema(conn: &Connection) -> SqliteResult<()> {
     Ok(())
 }
 
+/// Apply version 2 schema: embedding dimensions for semantic search.
+///
+/// Semantic search compares vectors in Rust, so the dimension is stored
+/// to let SQL skip nodes without a comparable embedding.
+fn apply_v2_schema(conn: &Connection) -> SqliteResult<()> {
+    // Stores created before embeddings were persisted lack the column.
+    if !column_exists(conn, "nodes", "embedding")? {
+        conn.execute("ALTER TABLE nodes ADD COLUMN embedding BLOB", [])?;
+    }
+    if !column_exists(conn, "nodes", "embedding_dim")? {
+        conn.execute("ALTER TABLE nodes ADD COLUMN embedding_dim INTEGER", [])?;
+    }
+
+    // Embeddings are stored as little-endian f32s.
+    conn.execute(
+        "UPDATE nodes SET embedding_dim = length(embedding) / 4
+         WHERE embedding IS NOT NULL AND embedding_dim IS NULL",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_nodes_embedding_dim ON nodes(embedding_dim)",
+        [],
+    )?;
+
+    conn.execute("INSERT INTO schema_version (version) VALUES (2)", [])?;
+
+    Ok(())
+}
+
+fn column_exists(conn: &Connection, table: &str, column: &str) -> SqliteResult<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    for name in names {
+        if name? == column {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 /// Get the current schema version.
 pub fn get_schema_version(conn: &Connection) -> SqliteResult<i32> {
     conn.query_row(
@@ -189,6 +242,127 @@ pub fn is_initialized(conn: &Connection) -> bool {
     .unwrap_or(false)
 }
 
+/// Postgres DDL for the same schema versions as the SQLite migrations.
+///
+/// Timestamps are `TIMESTAMPTZ`, JSON columns are `JSONB`, and full-text
+/// search uses a generated `tsvector` column with a GIN index in place of
+/// the FTS5 table.
+#[cfg(feature = "postgres")]
+pub(crate) mod postgres {
+    use super::pending_migrations;
+
+    /// Advisory lock key (ASCII "rlm_mem") serializing concurrent migrations.
+    const MIGRATION_LOCK_KEY: i64 = 0x0072_6c6d_5f6d_656d;
+
+    const V1: &str = "
+        CREATE TABLE IF NOT EXISTS nodes (
+            id TEXT PRIMARY KEY,
+            node_type TEXT NOT NULL,
+            subtype TEXT,
+            content TEXT NOT NULL,
+            embedding BYTEA,
+            tier INTEGER NOT NULL DEFAULT 0,
+            confidence DOUBLE PRECISION NOT NULL DEFAULT 1.0,
+            provenance_source TEXT,
+            provenance_ref TEXT,
+            provenance_observed_at TIMESTAMPTZ,
+            provenance_context JSONB,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            last_accessed TIMESTAMPTZ NOT NULL DEFAULT now(),
+            access_count BIGINT NOT NULL DEFAULT 0,
+            metadata JSONB,
+            content_tsv TSVECTOR GENERATED ALWAYS AS (to_tsvector('english', content)) STORED
+        );
+
+        CREATE TABLE IF NOT EXISTS hyperedges (
+            id TEXT PRIMARY KEY,
+            edge_type TEXT NOT NULL,
+            label TEXT,
+            weight DOUBLE PRECISION NOT NULL DEFAULT 1.0,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            metadata JSONB
+        );
+
+        CREATE TABLE IF NOT EXISTS membership (
+            hyperedge_id TEXT NOT NULL REFERENCES hyperedges(id) ON DELETE CASCADE,
+            node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+            role TEXT NOT NULL,
+            position INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (hyperedge_id, node_id, role)
+        );
+
+        CREATE TABLE IF NOT EXISTS evolution_log (
+            id BIGSERIAL PRIMARY KEY,
+            node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+            operation TEXT NOT NULL,
+            from_tier INTEGER,
+            to_tier INTEGER,
+            reason TEXT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_nodes_type ON nodes(node_type);
+        CREATE INDEX IF NOT EXISTS idx_nodes_tier ON nodes(tier);
+        CREATE INDEX IF NOT EXISTS idx_nodes_confidence ON nodes(confidence);
+        CREATE INDEX IF NOT EXISTS idx_nodes_last_accessed ON nodes(last_accessed);
+        CREATE INDEX IF NOT EXISTS idx_nodes_content_tsv ON nodes USING GIN (content_tsv);
+        CREATE INDEX IF NOT EXISTS idx_membership_node ON membership(node_id);
+        CREATE INDEX IF NOT EXISTS idx_evolution_node ON evolution_log(node_id);
+    ";
+
+    const V2: &str = "
+        ALTER TABLE nodes ADD COLUMN IF NOT EXISTS embedding_dim INTEGER;
+        UPDATE nodes SET embedding_dim = octet_length(embedding) / 4
+            WHERE embedding IS NOT NULL AND embedding_dim IS NULL;
+        CREATE INDEX IF NOT EXISTS idx_nodes_embedding_dim ON nodes(embedding_dim);
+    ";
+
+    /// Create or migrate the schema in the client's current search path.
+    ///
+    /// Runs in one transaction under an advisory lock, so processes
+    /// starting against the same database migrate it exactly once.
+    pub(crate) fn initialize_schema(
+        client: &mut ::postgres::Client,
+    ) -> Result<(), ::postgres::Error> {
+        let mut tx = client.transaction()?;
+        tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY])?;
+        tx.batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                version INTEGER PRIMARY KEY,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+        )?;
+
+        let current: i32 = tx
+            .query_one("SELECT COALESCE(MAX(version), 0) FROM schema_version", &[])?
+            .get(0);
+        for version in pending_migrations(current) {
+            let ddl = match version {
+                1 => V1,
+                2 => V2,
+                _ => unreachable!("no Postgres migration for schema version {}", version),
+            };
+            tx.batch_execute(ddl)?;
+            tx.execute(
+                "INSERT INTO schema_version (version) VALUES ($1)",
+                &[&version],
+            )?;
+        }
+
+        tx.commit()
+    }
+
+    /// Get the current schema version.
+    pub(crate) fn get_schema_version(
+        client: &mut ::postgres::Client,
+    ) -> Result<i32, ::postgres::Error> {
+        Ok(client
+            .query_one("SELECT COALESCE(MAX(version), 0) FROM schema_version", &[])?
+            .get(0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,7 +373,7 @@ mod tests {
         initialize_schema(&conn).unwrap();
 
         assert!(is_initialized(&conn));
-        assert_eq!(get_schema_version(&conn).unwrap(), 1);
+        assert_eq!(get_schema_version(&conn).unwrap(), SCHEMA_VERSION);
     }
 
     #[test]
@@ -210,7 +384,38 @@ mod tests {
         initialize_schema(&conn).unwrap();
         initialize_schema(&conn).unwrap();
 
-        assert_eq!(get_schema_version(&conn).unwrap(), 1);
+        assert_eq!(get_schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_v2_migration_backfills_embedding_dim() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE schema_version (
+                version INTEGER PRIMARY KEY,
+                applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )
+        .unwrap();
+        apply_v1_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO nodes (id, node_type, content, embedding) VALUES ('a', 'fact', 'x', ?1)",
+            [vec![0u8; 12]],
+        )
+        .unwrap();
+
+        initialize_schema(&conn).unwrap();
+
+        assert_eq!(get_schema_version(&conn).unwrap(), 2);
+        let dim: i64 = conn
+            .query_row(
+                "SELECT embedding_dim FROM nodes WHERE id = 'a'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(dim, 3);
     }
 
     #[test]
@@ -1,12 +1,15 @@
 //! SQLite-backed memory store implementation.
 
 use crate::error::{Error, Result};
-use crate::memory::schema::{initialize_schema, is_initialized};
+use crate::llm::{EmbeddingRequest, LLMClient};
+use crate::memory::evolution::{self, EvolutionBackend};
+use crate::memory::schema::{get_schema_version, initialize_schema, SCHEMA_VERSION};
 use crate::memory::types::*;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
@@ -20,9 +23,8 @@ impl SqliteMemoryStore {
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
         let conn = Connection::open(path).map_err(|e| Error::MemoryStorage(e.to_string()))?;
 
-        if !is_initialized(&conn) {
-            initialize_schema(&conn).map_err(|e| Error::MemoryStorage(e.to_string()))?;
-        }
+        // Idempotent: creates a fresh schema or migrates an existing one.
+        initialize_schema(&conn).map_err(|e| Error::MemoryStorage(e.to_string()))?;
 
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
@@ -39,7 +41,8 @@ impl SqliteMemoryStore {
         })
     }
 
-    fn with_conn<F, T>(&self, f: F) -> Result<T>
+    /// Run `f` against the locked connection, mapping SQLite errors.
+    pub(crate) fn with_conn<F, T>(&self, f: F) -> Result<T>
     where
         F: FnOnce(&Connection) -> rusqlite::Result<T>,
     {
@@ -54,29 +57,30 @@ impl SqliteMemoryStore {
 
     /// Add a node to the store.
     pub fn add_node(&self, node: &Node) -> Result<()> {
-        self.with_conn(|conn| {
-            let embedding_blob = node
-                .embedding
-                .as_ref()
-                .map(|e| e.iter().flat_map(|f| f.to_le_bytes()).collect::<Vec<u8>>());
-
-            let provenance_context = node
-                .provenance
-                .as_ref()
-                .and_then(|p| p.context.as_ref())
-                .map(|c| serde_json::to_string(c).unwrap_or_default());
-
-            let metadata = node
-                .metadata
-                .as_ref()
-                .map(|m| serde_json::to_string(m).unwrap_or_default());
+        self.with_conn(|conn| Self::insert_node(conn, node))
+    }
 
-            conn.execute(
+    fn insert_node(conn: &Connection, node: &Node) -> rusqlite::Result<()> {
+        let embedding_blob = node.embedding.as_deref().map(embedding_to_blob);
+        let embedding_dim = node.embedding.as_ref().map(|e| e.len() as i64);
+
+        let provenance_context = node
+            .provenance
+            .as_ref()
+            .and_then(|p| p.context.as_ref())
+            .map(|c| serde_json::to_string(c).unwrap_or_default());
+
+        let metadata = node
+            .metadata
+            .as_ref()
+            .map(|m| serde_json::to_string(m).unwrap_or_default());
+
+        conn.execute(
                 "INSERT INTO nodes (
                     id, node_type, subtype, content, embedding, tier, confidence,
                     provenance_source, provenance_ref, provenance_observed_at, provenance_context,
-                    created_at, updated_at, last_accessed, access_count, metadata
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                    created_at, updated_at, last_accessed, access_count, metadata, embedding_dim
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
                 params![
                     node.id.to_string(),
                     node.node_type.to_string(),
@@ -96,10 +100,10 @@ impl SqliteMemoryStore {
                     node.last_accessed.to_rfc3339(),
                     node.access_count as i64,
                     metadata,
+                    embedding_dim,
                 ],
             )?;
-            Ok(())
-        })
+        Ok(())
     }
 
     /// Get a node by ID.
@@ -117,38 +121,125 @@ impl SqliteMemoryStore {
         })
     }
 
+    /// Get a node's provenance without loading the rest of it.
+    ///
+    /// Nodes admitted through the epistemic memory gate carry a
+    /// [`ProvenanceSource::Verification`] record whose context holds the
+    /// evidence and confidence adjustment behind the decision. Returns
+    /// `None` if the node does not exist or has no provenance.
+    pub fn provenance_for(&self, id: &NodeId) -> Result<Option<Provenance>> {
+        self.with_conn(|conn| {
+            conn.query_row(
+                "SELECT provenance_source, provenance_ref, provenance_observed_at, provenance_context
+                 FROM nodes WHERE id = ?1",
+                params![id.to_string()],
+                |row| row_to_provenance_at(row, 0),
+            )
+            .optional()
+            .map(Option::flatten)
+        })
+    }
+
     /// Update a node.
     pub fn update_node(&self, node: &Node) -> Result<()> {
+        self.with_conn(|conn| Self::write_node_update(conn, node))
+    }
+
+    /// Get the node keyed by type, subtype and content.
+    ///
+    /// Returns the same node [`Self::upsert_node`] would update: the first
+    /// stored one matching the key.
+    pub fn find_node(
+        &self,
+        node_type: NodeType,
+        subtype: &str,
+        content: &str,
+    ) -> Result<Option<Node>> {
+        self.with_conn(|conn| Self::keyed_node(conn, node_type, subtype, content))
+    }
+
+    fn keyed_node(
+        conn: &Connection,
+        node_type: NodeType,
+        subtype: &str,
+        content: &str,
+    ) -> rusqlite::Result<Option<Node>> {
+        conn.query_row(
+            "SELECT id, node_type, subtype, content, embedding, tier, confidence,
+                    provenance_source, provenance_ref, provenance_observed_at, provenance_context,
+                    created_at, updated_at, last_accessed, access_count, metadata
+             FROM nodes WHERE node_type = ?1 AND subtype = ?2 AND content = ?3
+             ORDER BY created_at LIMIT 1",
+            params![node_type.to_string(), subtype, content],
+            Self::row_to_node,
+        )
+        .optional()
+    }
+
+    /// Read-modify-write the node keyed by type, subtype and content.
+    ///
+    /// `update` receives the first stored node matching the key (or `None`)
+    /// and returns the node to write, which is updated in place or inserted.
+    /// The read and write run in one immediate transaction, so concurrent
+    /// callers, including other connections to the same database, never
+    /// update from a stale copy.
+    pub fn upsert_node<F>(
+        &self,
+        node_type: NodeType,
+        subtype: &str,
+        content: &str,
+        update: F,
+    ) -> Result<Node>
+    where
+        F: FnOnce(Option<Node>) -> Node,
+    {
         self.with_conn(|conn| {
-            let embedding_blob = node
-                .embedding
-                .as_ref()
-                .map(|e| e.iter().flat_map(|f| f.to_le_bytes()).collect::<Vec<u8>>());
+            let tx = rusqlite::Transaction::new_unchecked(
+                conn,
+                rusqlite::TransactionBehavior::Immediate,
+            )?;
+            let existing = Self::keyed_node(&tx, node_type, subtype, content)?;
+            let existed = existing.is_some();
+            let node = update(existing);
+            if existed {
+                Self::write_node_update(&tx, &node)?;
+            } else {
+                Self::insert_node(&tx, &node)?;
+            }
+            tx.commit()?;
+            Ok(node)
+        })
+    }
 
-            let metadata = node
-                .metadata
-                .as_ref()
-                .map(|m| serde_json::to_string(m).unwrap_or_default());
+    fn write_node_update(conn: &Connection, node: &Node) -> rusqlite::Result<()> {
+        let embedding_blob = node.embedding.as_deref().map(embedding_to_blob);
+        let embedding_dim = node.embedding.as_ref().map(|e| e.len() as i64);
 
-            conn.execute(
-                "UPDATE nodes SET
+        let metadata = node
+            .metadata
+            .as_ref()
+            .map(|m| serde_json::to_string(m).unwrap_or_default());
+
+        conn.execute(
+            "UPDATE nodes SET
                     content = ?2, embedding = ?3, tier = ?4, confidence = ?5,
-                    updated_at = ?6, last_accessed = ?7, access_count = ?8, metadata = ?9
+                    updated_at = ?6, last_accessed = ?7, access_count = ?8, metadata = ?9,
+                    embedding_dim = ?10
                  WHERE id = ?1",
-                params![
-                    node.id.to_string(),
-                    node.content,
-                    embedding_blob,
-                    node.tier as i32,
-                    node.confidence,
-                    node.updated_at.to_rfc3339(),
-                    node.last_accessed.to_rfc3339(),
-                    node.access_count as i64,
-                    metadata,
-                ],
-            )?;
-            Ok(())
-        })
+            params![
+                node.id.to_string(),
+                node.content,
+                embedding_blob,
+                node.tier as i32,
+                node.confidence,
+                node.updated_at.to_rfc3339(),
+                node.last_accessed.to_rfc3339(),
+                node.access_count as i64,
+                metadata,
+                embedding_dim,
+            ],
+        )?;
+        Ok(())
     }
 
     /// Delete a node.
@@ -178,6 +269,11 @@ impl SqliteMemoryStore {
                 }
             }
 
+            if let Some(ref subtype) = query.subtype {
+                sql.push_str(" AND subtype = ?");
+                params_vec.push(Box::new(subtype.clone()));
+            }
+
             if let Some(ref tiers) = query.tiers {
                 let placeholders: Vec<String> = tiers.iter().map(|_| "?".to_string()).collect();
                 sql.push_str(&format!(" AND tier IN ({})", placeholders.join(",")));
@@ -237,41 +333,182 @@ impl SqliteMemoryStore {
         })
     }
 
+    // ==================== Semantic Search ====================
+
+    /// Set (or replace) the embedding of a node.
+    ///
+    /// Returns false if the node does not exist.
+    pub fn set_embedding(&self, id: &NodeId, embedding: &[f32]) -> Result<bool> {
+        self.with_conn(|conn| {
+            let rows = conn.execute(
+                "UPDATE nodes SET embedding = ?2, embedding_dim = ?3 WHERE id = ?1",
+                params![
+                    id.to_string(),
+                    embedding_to_blob(embedding),
+                    embedding.len() as i64
+                ],
+            )?;
+            Ok(rows > 0)
+        })
+    }
+
+    /// Embed the content of every node that has no embedding yet.
+    ///
+    /// Nodes are sent to `client` in batches of `batch_size`. Returns the
+    /// number of nodes that were embedded.
+    pub async fn embed_missing(
+        &self,
+        client: &dyn LLMClient,
+        model: Option<String>,
+        batch_size: usize,
+    ) -> Result<usize> {
+        let batch_size = batch_size.max(1);
+        let mut embedded = 0;
+
+        loop {
+            let pending: Vec<(String, String)> = self.with_conn(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, content FROM nodes WHERE embedding IS NULL
+                     ORDER BY created_at LIMIT ?1",
+                )?;
+                let rows = stmt
+                    .query_map(params![batch_size as i64], |row| {
+                        Ok((row.get(0)?, row.get(1)?))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(rows)
+            })?;
+
+            if pending.is_empty() {
+                return Ok(embedded);
+            }
+
+            let response = client
+                .embed(EmbeddingRequest {
+                    model: model.clone(),
+                    texts: pending.iter().map(|(_, content)| content.clone()).collect(),
+                })
+                .await?;
+
+            if response.embeddings.len() != pending.len() {
+                return Err(Error::LLM(format!(
+                    "Expected {} embeddings, got {}",
+                    pending.len(),
+                    response.embeddings.len()
+                )));
+            }
+            // An empty vector would leave the node unembedded and loop forever.
+            if response.embeddings.iter().any(|e| e.is_empty()) {
+                return Err(Error::LLM(
+                    "Embedding response contained an empty vector".into(),
+                ));
+            }
+
+            self.with_conn(|conn| {
+                let tx = conn.unchecked_transaction()?;
+                for ((id, _), embedding) in pending.iter().zip(&response.embeddings) {
+                    tx.execute(
+                        "UPDATE nodes SET embedding = ?2, embedding_dim = ?3 WHERE id = ?1",
+                        params![id, embedding_to_blob(embedding), embedding.len() as i64],
+                    )?;
+                }
+                tx.commit()
+            })?;
+
+            embedded += pending.len();
+        }
+    }
+
+    /// Rank nodes by cosine similarity to `query_embedding`.
+    ///
+    /// Nodes without an embedding, or with an embedding of a different
+    /// dimension, are skipped. Results are ordered most similar first.
+    pub fn search_semantic(
+        &self,
+        query_embedding: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<(Node, f32)>> {
+        self.semantic_search(query_embedding, top_k, None)
+    }
+
+    /// Like [`search_semantic`](Self::search_semantic), restricted to the given tiers.
+    pub fn search_semantic_in_tiers(
+        &self,
+        query_embedding: &[f32],
+        top_k: usize,
+        tiers: &[Tier],
+    ) -> Result<Vec<(Node, f32)>> {
+        self.semantic_search(query_embedding, top_k, Some(tiers))
+    }
+
+    fn semantic_search(
+        &self,
+        query_embedding: &[f32],
+        top_k: usize,
+        tiers: Option<&[Tier]>,
+    ) -> Result<Vec<(Node, f32)>> {
+        if query_embedding.is_empty() || top_k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let candidates = self.with_conn(|conn| {
+            let mut sql = String::from(
+                "SELECT id, node_type, subtype, content, embedding, tier, confidence,
+                        provenance_source, provenance_ref, provenance_observed_at, provenance_context,
+                        created_at, updated_at, last_accessed, access_count, metadata
+                 FROM nodes WHERE embedding IS NOT NULL AND embedding_dim = ?",
+            );
+            let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> =
+                vec![Box::new(query_embedding.len() as i64)];
+
+            if let Some(tiers) = tiers {
+                let placeholders: Vec<String> = tiers.iter().map(|_| "?".to_string()).collect();
+                sql.push_str(&format!(" AND tier IN ({})", placeholders.join(",")));
+                for t in tiers {
+                    params_vec.push(Box::new(*t as i32));
+                }
+            }
+
+            let params_refs: Vec<&dyn rusqlite::ToSql> =
+                params_vec.iter().map(|b| b.as_ref()).collect();
+
+            let mut stmt = conn.prepare(&sql)?;
+            let nodes: Vec<Node> = stmt
+                .query_map(params_refs.as_slice(), Self::row_to_node)?
+                .filter_map(|r| r.ok())
+                .collect();
+            Ok(nodes)
+        })?;
+
+        let mut scored: Vec<(Node, f32)> = candidates
+            .into_iter()
+            .filter_map(|node| {
+                let score = cosine_similarity(query_embedding, node.embedding.as_deref()?)?;
+                Some((node, score))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(top_k);
+        Ok(scored)
+    }
+
     fn row_to_node(row: &rusqlite::Row) -> rusqlite::Result<Node> {
         let id_str: String = row.get(0)?;
         let node_type_str: String = row.get(1)?;
         let tier_int: i32 = row.get(5)?;
 
-        let embedding: Option<Vec<f32>> = row.get::<_, Option<Vec<u8>>>(4)?.map(|bytes| {
-            bytes
-                .chunks(4)
-                .map(|chunk| {
-                    let arr: [u8; 4] = chunk.try_into().unwrap_or([0; 4]);
-                    f32::from_le_bytes(arr)
-                })
-                .collect()
-        });
+        let embedding: Option<Vec<f32>> = row
+            .get::<_, Option<Vec<u8>>>(4)?
+            .map(|bytes| blob_to_embedding(&bytes));
 
         let metadata: Option<HashMap<String, Value>> = row
             .get::<_, Option<String>>(15)?
             .and_then(|s| serde_json::from_str(&s).ok());
 
-        let node_type = match node_type_str.as_str() {
-            "entity" => NodeType::Entity,
-            "fact" => NodeType::Fact,
-            "experience" => NodeType::Experience,
-            "decision" => NodeType::Decision,
-            "snippet" => NodeType::Snippet,
-            _ => NodeType::Fact,
-        };
+        let node_type = parse_node_type(&node_type_str);
 
-        let tier = match tier_int {
-            0 => Tier::Task,
-            1 => Tier::Session,
-            2 => Tier::LongTerm,
-            3 => Tier::Archive,
-            _ => Tier::Task,
-        };
+        let tier = int_to_tier(tier_int);
 
         Ok(Node {
             id: NodeId::parse(&id_str).unwrap_or_else(|_| NodeId::new()),
@@ -281,7 +518,7 @@ impl SqliteMemoryStore {
             embedding,
             tier,
             confidence: row.get(6)?,
-            provenance: None, // Simplified for now
+            provenance: row_to_provenance(row)?,
             created_at: parse_datetime(row.get::<_, String>(11)?),
             updated_at: parse_datetime(row.get::<_, String>(12)?),
             last_accessed: parse_datetime(row.get::<_, String>(13)?),
@@ -294,41 +531,43 @@ impl SqliteMemoryStore {
 
     /// Add a hyperedge.
     pub fn add_edge(&self, edge: &HyperEdge) -> Result<()> {
-        self.with_conn(|conn| {
-            let metadata = edge
-                .metadata
-                .as_ref()
-                .map(|m| serde_json::to_string(m).unwrap_or_default());
+        self.with_conn(|conn| Self::insert_edge(conn, edge))
+    }
 
-            conn.execute(
-                "INSERT INTO hyperedges (id, edge_type, label, weight, created_at, metadata)
+    fn insert_edge(conn: &Connection, edge: &HyperEdge) -> rusqlite::Result<()> {
+        let metadata = edge
+            .metadata
+            .as_ref()
+            .map(|m| serde_json::to_string(m).unwrap_or_default());
+
+        conn.execute(
+            "INSERT INTO hyperedges (id, edge_type, label, weight, created_at, metadata)
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                edge.id.to_string(),
+                edge.edge_type.to_string(),
+                edge.label,
+                edge.weight,
+                edge.created_at.to_rfc3339(),
+                metadata,
+            ],
+        )?;
+
+        // Add memberships
+        for member in &edge.members {
+            conn.execute(
+                "INSERT INTO membership (hyperedge_id, node_id, role, position)
+                     VALUES (?1, ?2, ?3, ?4)",
                 params![
                     edge.id.to_string(),
-                    edge.edge_type.to_string(),
-                    edge.label,
-                    edge.weight,
-                    edge.created_at.to_rfc3339(),
-                    metadata,
+                    member.node_id.to_string(),
+                    member.role,
+                    member.position,
                 ],
             )?;
+        }
 
-            // Add memberships
-            for member in &edge.members {
-                conn.execute(
-                    "INSERT INTO membership (hyperedge_id, node_id, role, position)
-                     VALUES (?1, ?2, ?3, ?4)",
-                    params![
-                        edge.id.to_string(),
-                        member.node_id.to_string(),
-                        member.role,
-                        member.position,
-                    ],
-                )?;
-            }
-
-            Ok(())
-        })
+        Ok(())
     }
 
     /// Get edges connected to a node.
@@ -369,20 +608,10 @@ impl SqliteMemoryStore {
                 params![edge_id],
                 |row| {
                     let edge_type_str: String = row.get(1)?;
-                    let edge_type = match edge_type_str.as_str() {
-                        "semantic" => EdgeType::Semantic,
-                        "structural" => EdgeType::Structural,
-                        "causal" => EdgeType::Causal,
-                        "temporal" => EdgeType::Temporal,
-                        "reference" => EdgeType::Reference,
-                        "reasoning" => EdgeType::Reasoning,
-                        _ => EdgeType::Semantic,
-                    };
-
                     Ok(HyperEdge {
                         id: EdgeId::parse(&row.get::<_, String>(0)?)
                             .unwrap_or_else(|_| EdgeId::new()),
-                        edge_type,
+                        edge_type: parse_edge_type(&edge_type_str),
                         label: row.get(2)?,
                         weight: row.get(3)?,
                         members: Vec::new(),
@@ -433,99 +662,178 @@ impl SqliteMemoryStore {
 
     /// Promote nodes to a higher tier.
     pub fn promote(&self, node_ids: &[NodeId], reason: &str) -> Result<Vec<NodeId>> {
-        let mut promoted = Vec::new();
-
-        for node_id in node_ids {
-            if let Some(mut node) = self.get_node(node_id)? {
-                if let Some(next_tier) = node.tier.next() {
-                    let from_tier = node.tier;
-                    node.tier = next_tier;
-                    node.updated_at = Utc::now();
-                    self.update_node(&node)?;
-                    self.log_evolution(
-                        node_id,
-                        "promote",
-                        Some(from_tier),
-                        Some(next_tier),
-                        reason,
-                    )?;
-                    promoted.push(node_id.clone());
-                }
-            }
-        }
-
-        Ok(promoted)
+        evolution::promote(self, node_ids, reason)
     }
 
     /// Apply decay to nodes based on time and access patterns.
     pub fn decay(&self, factor: f64, min_confidence: f64) -> Result<Vec<NodeId>> {
-        let nodes = self.query_nodes(&NodeQuery::new().min_confidence(min_confidence))?;
-        let mut decayed = Vec::new();
-
-        for mut node in nodes {
-            // Decay formula: confidence * factor^(hours_since_access / 24)
-            let hours = (Utc::now() - node.last_accessed).num_hours() as f64;
-            let decay_exponent = hours / 24.0;
-            let new_confidence = node.confidence * factor.powf(decay_exponent);
-
-            if new_confidence < node.confidence {
-                node.confidence = new_confidence.max(0.0);
-                node.updated_at = Utc::now();
-                self.update_node(&node)?;
-                decayed.push(node.id.clone());
-            }
-        }
-
-        Ok(decayed)
+        evolution::decay(self, factor, min_confidence)
     }
 
     /// Consolidate nodes from one tier to another.
     pub fn consolidate(&self, from_tier: Tier, to_tier: Tier) -> Result<ConsolidationResult> {
-        let nodes = self.query_nodes(&NodeQuery::new().tiers(vec![from_tier]))?;
-        let source_ids: Vec<NodeId> = nodes.iter().map(|n| n.id.clone()).collect();
-
-        // For now, just promote nodes above a confidence threshold
-        let promoted = self.promote(
-            &source_ids
-                .iter()
-                .filter(|_| true)
-                .cloned()
-                .collect::<Vec<_>>(),
-            &format!("Consolidation from {} to {}", from_tier, to_tier),
-        )?;
+        evolution::consolidate(self, from_tier, to_tier)
+    }
+
+    /// Merge near-duplicate nodes into a single representative.
+    ///
+    /// Nodes of the same type are duplicates when their similarity is at
+    /// least `similarity_threshold`: embedding cosine similarity when both
+    /// have comparable embeddings, token Jaccard similarity otherwise.
+    /// Duplicates are grouped transitively. Each group keeps its
+    /// highest-confidence node, which inherits the most durable tier in the
+    /// group, the summed access count, and every hyperedge membership of the
+    /// nodes it absorbs. Running it again on the result merges nothing.
+    pub fn consolidate_duplicates(&self, similarity_threshold: f64) -> Result<ConsolidationResult> {
+        let nodes = self.query_nodes(&NodeQuery::new())?;
+
+        let mut parent: Vec<usize> = (0..nodes.len()).collect();
+        fn find(parent: &mut [usize], i: usize) -> usize {
+            let mut root = i;
+            while parent[root] != root {
+                root = parent[root];
+            }
+            parent[i] = root;
+            root
+        }
+
+        let tokens: Vec<HashSet<String>> =
+            nodes.iter().map(|n| content_tokens(&n.content)).collect();
+        for i in 0..nodes.len() {
+            for j in (i + 1)..nodes.len() {
+                if nodes[i].node_type != nodes[j].node_type {
+                    continue;
+                }
+                let similarity = match (&nodes[i].embedding, &nodes[j].embedding) {
+                    (Some(a), Some(b)) => cosine_similarity(a, b).map(f64::from),
+                    _ => None,
+                }
+                .unwrap_or_else(|| jaccard_similarity(&tokens[i], &tokens[j]));
+
+                if similarity >= similarity_threshold {
+                    let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                    parent[b] = a;
+                }
+            }
+        }
 
-        Ok(ConsolidationResult {
-            source_nodes: source_ids,
+        let mut groups: HashMap<usize, Vec<&Node>> = HashMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            groups.entry(find(&mut parent, i)).or_default().push(node);
+        }
+        let mut groups: Vec<Vec<&Node>> = groups.into_values().filter(|g| g.len() > 1).collect();
+        groups.sort_by(|a, b| a[0].created_at.cmp(&b[0].created_at));
+
+        let now = Utc::now();
+        let mut result = ConsolidationResult {
+            source_nodes: Vec::new(),
             consolidated_node: None,
-            promoted_nodes: promoted,
+            promoted_nodes: Vec::new(),
             archived_nodes: Vec::new(),
-            summary: format!("Consolidated from {} to {}", from_tier, to_tier),
-        })
+            summary: String::new(),
+            merges: Vec::new(),
+        };
+
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+
+            for group in &groups {
+                let Some(best) = group.iter().copied().max_by(|a, b| {
+                    a.confidence
+                        .total_cmp(&b.confidence)
+                        .then(a.access_count.cmp(&b.access_count))
+                        .then(b.created_at.cmp(&a.created_at))
+                }) else {
+                    continue;
+                };
+
+                let mut survivor = best.clone();
+                let mut merged = Vec::new();
+                for dup in group.iter().filter(|n| n.id != best.id) {
+                    if tier_durability(dup.tier) > tier_durability(survivor.tier) {
+                        survivor.tier = dup.tier;
+                    }
+                    survivor.access_count += dup.access_count;
+                    survivor.last_accessed = survivor.last_accessed.max(dup.last_accessed);
+                    if survivor.embedding.is_none() {
+                        survivor.embedding = dup.embedding.clone();
+                    }
+
+                    let (dup_id, survivor_id) = (dup.id.to_string(), survivor.id.to_string());
+                    // The survivor may already hold the same role on an edge.
+                    tx.execute(
+                        "UPDATE OR IGNORE membership SET node_id = ?2 WHERE node_id = ?1",
+                        params![dup_id, survivor_id],
+                    )?;
+                    tx.execute("DELETE FROM membership WHERE node_id = ?1", params![dup_id])?;
+                    tx.execute("DELETE FROM nodes WHERE id = ?1", params![dup_id])?;
+                    merged.push(dup.id.clone());
+                }
+
+                survivor.updated_at = now;
+                Self::write_node_update(&tx, &survivor)?;
+                Self::insert_evolution(
+                    &tx,
+                    &survivor.id,
+                    "merge",
+                    Some(best.tier),
+                    Some(survivor.tier),
+                    &format!("Merged {} near-duplicate node(s)", merged.len()),
+                )?;
+
+                if survivor.tier != best.tier {
+                    result.promoted_nodes.push(survivor.id.clone());
+                }
+                result
+                    .source_nodes
+                    .extend(group.iter().map(|n| n.id.clone()));
+                result.merges.push(NodeMerge {
+                    survivor: survivor.id,
+                    merged,
+                });
+            }
+
+            tx.commit()
+        })?;
+
+        let removed: usize = result.merges.iter().map(|m| m.merged.len()).sum();
+        result.summary = format!(
+            "Merged {} duplicate node(s) into {} survivor(s)",
+            removed,
+            result.merges.len()
+        );
+        Ok(result)
     }
 
-    /// Log an evolution event.
-    fn log_evolution(
-        &self,
+    /// Run one tier-evolution sweep according to `policy`.
+    ///
+    /// Decisions are made against the store as it was when the sweep began,
+    /// so the order nodes are visited in does not matter. Returns one entry
+    /// per node that changed tier.
+    pub fn evolve(&self, policy: &EvolutionPolicy) -> Result<Vec<EvolutionEntry>> {
+        evolution::evolve(self, policy)
+    }
+
+    fn insert_evolution(
+        conn: &Connection,
         node_id: &NodeId,
         operation: &str,
         from_tier: Option<Tier>,
         to_tier: Option<Tier>,
         reason: &str,
-    ) -> Result<()> {
-        self.with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO evolution_log (node_id, operation, from_tier, to_tier, reason)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![
-                    node_id.to_string(),
-                    operation,
-                    from_tier.map(|t| t as i32),
-                    to_tier.map(|t| t as i32),
-                    reason,
-                ],
-            )?;
-            Ok(())
-        })
+    ) -> rusqlite::Result<()> {
+        conn.execute(
+            "INSERT INTO evolution_log (node_id, operation, from_tier, to_tier, reason)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                node_id.to_string(),
+                operation,
+                from_tier.map(|t| t as i32),
+                to_tier.map(|t| t as i32),
+                reason,
+            ],
+        )?;
+        Ok(())
     }
 
     /// Get evolution history for a node.
@@ -539,6 +847,7 @@ impl SqliteMemoryStore {
             let entries = stmt
                 .query_map(params![node_id.to_string()], |row| {
                     Ok(EvolutionEntry {
+                        node_id: node_id.clone(),
                         operation: row.get(0)?,
                         from_tier: row.get::<_, Option<i32>>(1)?.map(int_to_tier),
                         to_tier: row.get::<_, Option<i32>>(2)?.map(int_to_tier),
@@ -553,6 +862,162 @@ impl SqliteMemoryStore {
         })
     }
 
+    // ==================== Export / Import ====================
+
+    /// Snapshot every node and hyperedge in the store.
+    ///
+    /// Nodes and edges are ordered by creation time, then ID, so repeated
+    /// exports of the same store are identical.
+    pub fn export(&self) -> Result<MemoryExport> {
+        self.with_conn(|conn| {
+            let schema_version = get_schema_version(conn)?;
+
+            let mut stmt = conn.prepare(
+                "SELECT id, node_type, subtype, content, embedding, tier, confidence,
+                        provenance_source, provenance_ref, provenance_observed_at, provenance_context,
+                        created_at, updated_at, last_accessed, access_count, metadata
+                 FROM nodes",
+            )?;
+            let mut nodes = stmt
+                .query_map([], Self::row_to_node)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            nodes.sort_by(|a, b| {
+                a.created_at
+                    .cmp(&b.created_at)
+                    .then_with(|| a.id.0.cmp(&b.id.0))
+            });
+
+            let mut stmt = conn.prepare("SELECT id FROM hyperedges")?;
+            let edge_ids = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let mut edges = Vec::with_capacity(edge_ids.len());
+            for edge_id in edge_ids {
+                if let Some(edge) = self.get_edge_internal(conn, &edge_id)? {
+                    edges.push(edge);
+                }
+            }
+            edges.sort_by(|a, b| {
+                a.created_at
+                    .cmp(&b.created_at)
+                    .then_with(|| a.id.0.cmp(&b.id.0))
+            });
+
+            Ok(MemoryExport {
+                schema_version,
+                nodes,
+                edges,
+            })
+        })
+    }
+
+    /// Export the whole hypergraph as a pretty-printed JSON document.
+    pub fn export_json(&self) -> Result<String> {
+        // Going through `Value` sorts map keys (metadata, context), keeping
+        // the output stable across runs.
+        let value = serde_json::to_value(self.export()?).map_err(Error::Serialization)?;
+        serde_json::to_string_pretty(&value).map_err(Error::Serialization)
+    }
+
+    /// Import a document produced by [`export_json`](Self::export_json).
+    pub fn import_json(&self, json: &str, options: ImportOptions) -> Result<ImportReport> {
+        let export: MemoryExport = serde_json::from_str(json).map_err(Error::Serialization)?;
+        self.import(export, options)
+    }
+
+    /// Import a [`MemoryExport`] into this store.
+    ///
+    /// The import runs in a single transaction: on error the store is left
+    /// unchanged.
+    pub fn import(&self, export: MemoryExport, options: ImportOptions) -> Result<ImportReport> {
+        if export.schema_version != SCHEMA_VERSION {
+            return Err(Error::MemoryStorage(format!(
+                "Cannot import memory export with schema version {} (this store uses version {})",
+                export.schema_version, SCHEMA_VERSION
+            )));
+        }
+
+        let mut report = ImportReport::default();
+
+        let mut nodes = export.nodes;
+        let mut edges = export.edges;
+        if options.remap_ids {
+            for node in &mut nodes {
+                let new_id = NodeId::new();
+                report.id_map.insert(node.id.clone(), new_id.clone());
+                node.id = new_id;
+            }
+            for edge in &mut edges {
+                edge.id = EdgeId::new();
+                for member in &mut edge.members {
+                    if let Some(new_id) = report.id_map.get(&member.node_id) {
+                        member.node_id = new_id.clone();
+                    }
+                }
+            }
+        }
+
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| Error::Internal(format!("Failed to lock connection: {}", e)))?;
+
+        let result: rusqlite::Result<()> = (|| {
+            let tx = conn.unchecked_transaction()?;
+
+            if options.mode == ImportMode::Replace {
+                tx.execute("DELETE FROM membership", [])?;
+                tx.execute("DELETE FROM hyperedges", [])?;
+                tx.execute("DELETE FROM evolution_log", [])?;
+                tx.execute("DELETE FROM nodes", [])?;
+            }
+
+            for node in &nodes {
+                if Self::exists(&tx, "nodes", &node.id.to_string())? {
+                    report.nodes_skipped += 1;
+                } else {
+                    Self::insert_node(&tx, node)?;
+                    report.nodes_imported += 1;
+                }
+            }
+
+            for edge in &edges {
+                if Self::exists(&tx, "hyperedges", &edge.id.to_string())? {
+                    report.edges_skipped += 1;
+                    continue;
+                }
+                for member in &edge.members {
+                    if !Self::exists(&tx, "nodes", &member.node_id.to_string())? {
+                        report.dangling_members.push(member.node_id.clone());
+                    }
+                }
+                Self::insert_edge(&tx, edge)?;
+                report.edges_imported += 1;
+            }
+
+            tx.commit()
+        })();
+
+        match result {
+            Ok(()) => Ok(report),
+            Err(e) if !report.dangling_members.is_empty() => Err(Error::MemoryStorage(format!(
+                "Memory export references {} unknown node(s), e.g. {}: {}",
+                report.dangling_members.len(),
+                report.dangling_members[0],
+                e
+            ))),
+            Err(e) => Err(Error::MemoryStorage(e.to_string())),
+        }
+    }
+
+    fn exists(conn: &Connection, table: &str, id: &str) -> rusqlite::Result<bool> {
+        conn.query_row(
+            &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?1)", table),
+            params![id],
+            |row| row.get(0),
+        )
+    }
+
     /// Get statistics about the memory store.
     pub fn stats(&self) -> Result<MemoryStats> {
         self.with_conn(|conn| {
@@ -576,15 +1041,7 @@ impl SqliteMemoryStore {
                 let rows = stmt.query_map([], |row| {
                     let type_str: String = row.get(0)?;
                     let count: i64 = row.get(1)?;
-                    let node_type = match type_str.as_str() {
-                        "entity" => NodeType::Entity,
-                        "fact" => NodeType::Fact,
-                        "experience" => NodeType::Experience,
-                        "decision" => NodeType::Decision,
-                        "snippet" => NodeType::Snippet,
-                        _ => NodeType::Fact,
-                    };
-                    Ok((node_type, count))
+                    Ok((parse_node_type(&type_str), count))
                 })?;
                 let result: HashMap<NodeType, i64> = rows.filter_map(|r| r.ok()).collect();
                 result
@@ -603,9 +1060,41 @@ impl SqliteMemoryStore {
     }
 }
 
+impl EvolutionBackend for SqliteMemoryStore {
+    fn get_node(&self, id: &NodeId) -> Result<Option<Node>> {
+        SqliteMemoryStore::get_node(self, id)
+    }
+
+    fn update_node(&self, node: &Node) -> Result<()> {
+        SqliteMemoryStore::update_node(self, node)
+    }
+
+    fn query_nodes(&self, query: &NodeQuery) -> Result<Vec<Node>> {
+        SqliteMemoryStore::query_nodes(self, query)
+    }
+
+    fn get_edges_for_node(&self, node_id: &NodeId) -> Result<Vec<HyperEdge>> {
+        SqliteMemoryStore::get_edges_for_node(self, node_id)
+    }
+
+    fn log_evolution(
+        &self,
+        node_id: &NodeId,
+        operation: &str,
+        from_tier: Option<Tier>,
+        to_tier: Option<Tier>,
+        reason: &str,
+    ) -> Result<()> {
+        self.with_conn(|conn| {
+            Self::insert_evolution(conn, node_id, operation, from_tier, to_tier, reason)
+        })
+    }
+}
+
 /// Entry in the evolution log.
 #[derive(Debug, Clone)]
 pub struct EvolutionEntry {
+    pub node_id: NodeId,
     pub operation: String,
     pub from_tier: Option<Tier>,
     pub to_tier: Option<Tier>,
@@ -613,6 +1102,70 @@ pub struct EvolutionEntry {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Portable snapshot of a memory store, see [`SqliteMemoryStore::export`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryExport {
+    /// Schema version of the store that produced the export
+    pub schema_version: i32,
+    /// All nodes, ordered by creation time
+    pub nodes: Vec<Node>,
+    /// All hyperedges, ordered by creation time
+    pub edges: Vec<HyperEdge>,
+}
+
+/// How an import treats data already in the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportMode {
+    /// Keep existing data; entries whose ID already exists are skipped
+    #[default]
+    Merge,
+    /// Delete all existing nodes, edges and evolution history first
+    Replace,
+}
+
+/// Options for [`SqliteMemoryStore::import`].
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Merge into or replace the existing contents
+    pub mode: ImportMode,
+    /// Give every imported node and edge a fresh ID
+    pub remap_ids: bool,
+}
+
+impl ImportOptions {
+    /// Merge into the existing contents.
+    pub fn merge() -> Self {
+        Self::default()
+    }
+
+    /// Replace the existing contents.
+    pub fn replace() -> Self {
+        Self {
+            mode: ImportMode::Replace,
+            ..Self::default()
+        }
+    }
+
+    /// Assign fresh IDs to imported nodes and edges.
+    pub fn with_remap_ids(mut self, remap: bool) -> Self {
+        self.remap_ids = remap;
+        self
+    }
+}
+
+/// Outcome of an import.
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub nodes_imported: usize,
+    pub nodes_skipped: usize,
+    pub edges_imported: usize,
+    pub edges_skipped: usize,
+    /// Original ID to new ID, populated when IDs are remapped
+    pub id_map: HashMap<NodeId, NodeId>,
+    /// Edge members that pointed at nodes missing from both export and store
+    pub dangling_members: Vec<NodeId>,
+}
+
 /// Statistics about the memory store.
 #[derive(Debug, Clone)]
 pub struct MemoryStats {
@@ -622,13 +1175,96 @@ pub struct MemoryStats {
     pub total_edges: u64,
 }
 
+fn row_to_provenance(row: &rusqlite::Row) -> rusqlite::Result<Option<Provenance>> {
+    row_to_provenance_at(row, 7)
+}
+
+/// Read the four provenance columns starting at column `start`.
+fn row_to_provenance_at(row: &rusqlite::Row, start: usize) -> rusqlite::Result<Option<Provenance>> {
+    let Some(source) = row.get::<_, Option<String>>(start)? else {
+        return Ok(None);
+    };
+    Ok(Some(Provenance {
+        source_type: parse_provenance_source(&source),
+        source_ref: row.get(start + 1)?,
+        observed_at: row
+            .get::<_, Option<String>>(start + 2)?
+            .map(parse_datetime)
+            .unwrap_or_else(Utc::now),
+        context: row
+            .get::<_, Option<String>>(start + 3)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+    }))
+}
+
+/// Ordering used when merging duplicates: Archive is the least durable tier.
+fn tier_durability(tier: Tier) -> u8 {
+    match tier {
+        Tier::Archive => 0,
+        Tier::Task => 1,
+        Tier::Session => 2,
+        Tier::LongTerm => 3,
+    }
+}
+
+fn content_tokens(content: &str) -> HashSet<String> {
+    content
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+pub(super) fn embedding_to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+pub(super) fn blob_to_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            let arr: [u8; 4] = chunk.try_into().unwrap_or([0; 4]);
+            f32::from_le_bytes(arr)
+        })
+        .collect()
+}
+
+/// Cosine similarity, or `None` for mismatched dimensions or zero vectors.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a.sqrt() * norm_b.sqrt()))
+}
+
 fn parse_datetime(s: String) -> DateTime<Utc> {
     DateTime::parse_from_rfc3339(&s)
         .map(|dt| dt.with_timezone(&Utc))
         .unwrap_or_else(|_| Utc::now())
 }
 
-fn int_to_tier(i: i32) -> Tier {
+pub(super) fn int_to_tier(i: i32) -> Tier {
     match i {
         0 => Tier::Task,
         1 => Tier::Session,
@@ -638,6 +1274,43 @@ fn int_to_tier(i: i32) -> Tier {
     }
 }
 
+pub(super) fn parse_node_type(s: &str) -> NodeType {
+    match s {
+        "entity" => NodeType::Entity,
+        "fact" => NodeType::Fact,
+        "experience" => NodeType::Experience,
+        "decision" => NodeType::Decision,
+        "snippet" => NodeType::Snippet,
+        _ => NodeType::Fact,
+    }
+}
+
+pub(super) fn parse_edge_type(s: &str) -> EdgeType {
+    match s {
+        "semantic" => EdgeType::Semantic,
+        "structural" => EdgeType::Structural,
+        "causal" => EdgeType::Causal,
+        "temporal" => EdgeType::Temporal,
+        "reference" => EdgeType::Reference,
+        "reasoning" => EdgeType::Reasoning,
+        _ => EdgeType::Semantic,
+    }
+}
+
+/// Parse a provenance source stored via its Debug representation.
+pub(super) fn parse_provenance_source(s: &str) -> ProvenanceSource {
+    match s {
+        "UserMessage" => ProvenanceSource::UserMessage,
+        "AssistantResponse" => ProvenanceSource::AssistantResponse,
+        "ToolOutput" => ProvenanceSource::ToolOutput,
+        "FileContent" => ProvenanceSource::FileContent,
+        "Consolidation" => ProvenanceSource::Consolidation,
+        "Inference" => ProvenanceSource::Inference,
+        "Verification" => ProvenanceSource::Verification,
+        _ => ProvenanceSource::Import,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -750,6 +1423,33 @@ mod tests {
         assert_eq!(history.len(), 2);
     }
 
+    #[test]
+    fn test_find_node_and_subtype_query() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+
+        let keyed = Node::new(NodeType::Experience, "sig").with_subtype("metrics");
+        store.add_node(&keyed).unwrap();
+        store
+            .add_node(&Node::new(NodeType::Experience, "sig").with_subtype("other"))
+            .unwrap();
+
+        let found = store
+            .find_node(NodeType::Experience, "metrics", "sig")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, keyed.id);
+        assert!(store
+            .find_node(NodeType::Experience, "metrics", "missing")
+            .unwrap()
+            .is_none());
+
+        let nodes = store
+            .query_nodes(&NodeQuery::new().subtype("metrics"))
+            .unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].id, keyed.id);
+    }
+
     #[test]
     fn test_stats() {
         let store = SqliteMemoryStore::in_memory().unwrap();
@@ -764,4 +1464,400 @@ mod tests {
         assert_eq!(stats.total_nodes, 3);
         assert_eq!(stats.nodes_by_type.get(&NodeType::Fact), Some(&2));
     }
+
+    #[test]
+    fn test_search_semantic_skips_nodes_without_embeddings() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+
+        let near = Node::new(NodeType::Fact, "near").with_embedding(vec![1.0, 0.1, 0.0]);
+        let far = Node::new(NodeType::Fact, "far").with_embedding(vec![0.0, 1.0, 0.0]);
+        let other_dim = Node::new(NodeType::Fact, "2d").with_embedding(vec![1.0, 0.0]);
+        let plain = Node::new(NodeType::Fact, "no embedding");
+        for node in [&near, &far, &other_dim, &plain] {
+            store.add_node(node).unwrap();
+        }
+
+        let results = store.search_semantic(&[1.0, 0.0, 0.0], 10).unwrap();
+        let contents: Vec<&str> = results.iter().map(|(n, _)| n.content.as_str()).collect();
+        assert_eq!(contents, vec!["near", "far"]);
+        assert!(results[0].1 > 0.99);
+        assert!(results[1].1.abs() < 1e-6);
+
+        let top = store.search_semantic(&[1.0, 0.0, 0.0], 1).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].0.id, near.id);
+
+        // Still usable without any embeddings at all.
+        let empty = SqliteMemoryStore::in_memory().unwrap();
+        empty.add_node(&Node::new(NodeType::Fact, "plain")).unwrap();
+        assert!(empty.search_semantic(&[1.0], 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_semantic_in_tiers() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+
+        let task = Node::new(NodeType::Fact, "task").with_embedding(vec![1.0, 0.0]);
+        let long_term = Node::new(NodeType::Fact, "long term")
+            .with_tier(Tier::LongTerm)
+            .with_embedding(vec![0.9, 0.1]);
+        store.add_node(&task).unwrap();
+        store.add_node(&long_term).unwrap();
+
+        let results = store
+            .search_semantic_in_tiers(&[1.0, 0.0], 10, &[Tier::LongTerm])
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.id, long_term.id);
+    }
+
+    #[test]
+    fn test_set_embedding() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let node = Node::new(NodeType::Fact, "late");
+        store.add_node(&node).unwrap();
+
+        assert!(store.set_embedding(&node.id, &[0.0, 1.0]).unwrap());
+        assert!(!store.set_embedding(&NodeId::new(), &[0.0, 1.0]).unwrap());
+
+        let retrieved = store.get_node(&node.id).unwrap().unwrap();
+        assert_eq!(retrieved.embedding, Some(vec![0.0, 1.0]));
+        assert_eq!(store.search_semantic(&[0.0, 1.0], 1).unwrap().len(), 1);
+    }
+
+    struct LengthEmbedder;
+
+    #[async_trait::async_trait]
+    impl LLMClient for LengthEmbedder {
+        async fn complete(
+            &self,
+            _request: crate::llm::CompletionRequest,
+        ) -> Result<crate::llm::CompletionResponse> {
+            Err(Error::LLM("completion not supported".into()))
+        }
+
+        async fn embed(&self, request: EmbeddingRequest) -> Result<crate::llm::EmbeddingResponse> {
+            Ok(crate::llm::EmbeddingResponse {
+                model: request.model.unwrap_or_else(|| "test-embed".into()),
+                embeddings: request
+                    .texts
+                    .iter()
+                    .map(|t| vec![t.len() as f32, 1.0])
+                    .collect(),
+                usage: crate::llm::TokenUsage::default(),
+                cost: None,
+            })
+        }
+
+        fn provider(&self) -> crate::llm::Provider {
+            crate::llm::Provider::Ollama
+        }
+
+        fn available_models(&self) -> Vec<crate::llm::ModelSpec> {
+            Vec::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_missing() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let existing = Node::new(NodeType::Fact, "kept").with_embedding(vec![0.0, 1.0]);
+        store.add_node(&existing).unwrap();
+        for content in ["a", "bb", "ccc"] {
+            store.add_node(&Node::new(NodeType::Fact, content)).unwrap();
+        }
+
+        let embedded = store.embed_missing(&LengthEmbedder, None, 2).await.unwrap();
+        assert_eq!(embedded, 3);
+        assert_eq!(
+            store.embed_missing(&LengthEmbedder, None, 2).await.unwrap(),
+            0
+        );
+
+        let kept = store.get_node(&existing.id).unwrap().unwrap();
+        assert_eq!(kept.embedding, Some(vec![0.0, 1.0]));
+
+        let results = store.search_semantic(&[3.0, 1.0], 1).unwrap();
+        assert_eq!(results[0].0.content, "ccc");
+    }
+
+    fn sample_graph(store: &SqliteMemoryStore) -> (Node, Node) {
+        let mut context = HashMap::new();
+        context.insert("line".to_string(), serde_json::json!(42));
+        let fact = Node::new(NodeType::Fact, "Auth uses JWT")
+            .with_tier(Tier::LongTerm)
+            .with_confidence(0.8)
+            .with_embedding(vec![0.25, -1.5])
+            .with_provenance(Provenance {
+                source_type: ProvenanceSource::FileContent,
+                source_ref: Some("src/auth.rs".to_string()),
+                observed_at: Utc::now(),
+                context: Some(context),
+            });
+        let entity = Node::new(NodeType::Entity, "AuthService");
+        store.add_node(&fact).unwrap();
+        store.add_node(&entity).unwrap();
+        store
+            .add_edge(&HyperEdge::binary(
+                EdgeType::Semantic,
+                entity.id.clone(),
+                fact.id.clone(),
+                "describes",
+            ))
+            .unwrap();
+        (fact, entity)
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let source = SqliteMemoryStore::in_memory().unwrap();
+        let (fact, _) = sample_graph(&source);
+        let json = source.export_json().unwrap();
+        assert_eq!(json, source.export_json().unwrap());
+
+        let target = SqliteMemoryStore::in_memory().unwrap();
+        let report = target.import_json(&json, ImportOptions::replace()).unwrap();
+        assert_eq!(report.nodes_imported, 2);
+        assert_eq!(report.edges_imported, 1);
+
+        assert_eq!(target.export_json().unwrap(), json);
+        let imported = target.get_node(&fact.id).unwrap().unwrap();
+        assert_eq!(imported.tier, Tier::LongTerm);
+        assert_eq!(imported.confidence, 0.8);
+        assert_eq!(imported.provenance, fact.provenance);
+        assert_eq!(imported.embedding, Some(vec![0.25, -1.5]));
+    }
+
+    #[test]
+    fn test_export_json_sorts_metadata_keys() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let node = Node::new(NodeType::Fact, "multi-key")
+            .with_metadata("zeta", 1)
+            .with_metadata("alpha", 2)
+            .with_metadata("mid", 3);
+        store.add_node(&node).unwrap();
+
+        let json = store.export_json().unwrap();
+        assert_eq!(json, store.export_json().unwrap());
+        let alpha = json.find("\"alpha\"").unwrap();
+        let mid = json.find("\"mid\"").unwrap();
+        let zeta = json.find("\"zeta\"").unwrap();
+        assert!(alpha < mid && mid < zeta);
+    }
+
+    #[test]
+    fn test_import_merge_and_remap() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        sample_graph(&store);
+        let json = store.export_json().unwrap();
+
+        let report = store.import_json(&json, ImportOptions::merge()).unwrap();
+        assert_eq!(report.nodes_skipped, 2);
+        assert_eq!(report.edges_skipped, 1);
+        assert_eq!(store.stats().unwrap().total_nodes, 2);
+
+        let report = store
+            .import_json(&json, ImportOptions::merge().with_remap_ids(true))
+            .unwrap();
+        assert_eq!(report.nodes_imported, 2);
+        assert_eq!(report.id_map.len(), 2);
+        let stats = store.stats().unwrap();
+        assert_eq!(stats.total_nodes, 4);
+        assert_eq!(stats.total_edges, 2);
+
+        let new_id = report.id_map.values().next().unwrap();
+        let edges = store.get_edges_for_node(new_id).unwrap();
+        assert_eq!(edges.len(), 1);
+        assert!(edges[0]
+            .members
+            .iter()
+            .all(|m| report.id_map.values().any(|id| *id == m.node_id)));
+
+        let report = store.import_json(&json, ImportOptions::replace()).unwrap();
+        assert_eq!(report.nodes_imported, 2);
+        assert_eq!(store.stats().unwrap().total_nodes, 2);
+    }
+
+    #[test]
+    fn test_import_rejects_schema_mismatch() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let mut export = store.export().unwrap();
+        export.schema_version = SCHEMA_VERSION + 1;
+
+        let err = store.import(export, ImportOptions::merge()).unwrap_err();
+        assert!(err.to_string().contains("schema version"));
+    }
+
+    #[test]
+    fn test_import_dangling_member_is_rolled_back() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let node = Node::new(NodeType::Fact, "alone");
+        let export = MemoryExport {
+            schema_version: SCHEMA_VERSION,
+            nodes: vec![node.clone()],
+            edges: vec![HyperEdge::binary(
+                EdgeType::Causal,
+                node.id.clone(),
+                NodeId::new(),
+                "causes",
+            )],
+        };
+
+        let err = store.import(export, ImportOptions::merge()).unwrap_err();
+        assert!(err.to_string().contains("unknown node"));
+        assert!(store.get_node(&node.id).unwrap().is_none());
+    }
+
+    fn idle_node(content: &str, days: i64) -> Node {
+        let mut node = Node::new(NodeType::Fact, content)
+            .with_tier(Tier::LongTerm)
+            .with_confidence(0.9);
+        node.last_accessed = Utc::now() - chrono::Duration::days(days);
+        node
+    }
+
+    #[test]
+    fn test_evolve_archives_idle_long_term_node() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let stale = idle_node("stale", 60);
+        let fresh = idle_node("fresh", 1);
+        store.add_node(&stale).unwrap();
+        store.add_node(&fresh).unwrap();
+
+        let entries = store.evolve(&EvolutionPolicy::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].node_id, stale.id);
+        assert_eq!(entries[0].operation, "archive");
+        assert_eq!(entries[0].to_tier, Some(Tier::Archive));
+        assert!(entries[0].reason.contains("60 days"));
+
+        assert_eq!(
+            store.get_node(&stale.id).unwrap().unwrap().tier,
+            Tier::Archive
+        );
+        assert_eq!(
+            store.get_node(&fresh.id).unwrap().unwrap().tier,
+            Tier::LongTerm
+        );
+        assert_eq!(store.get_evolution_history(&stale.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_evolve_keeps_node_referenced_by_active_edge() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let protected = idle_node("protected", 60);
+        let active = Node::new(NodeType::Entity, "in use").with_tier(Tier::Session);
+        store.add_node(&protected).unwrap();
+        store.add_node(&active).unwrap();
+        store
+            .add_edge(&HyperEdge::binary(
+                EdgeType::Reference,
+                active.id.clone(),
+                protected.id.clone(),
+                "cites",
+            ))
+            .unwrap();
+
+        assert!(store
+            .evolve(&EvolutionPolicy::default())
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            store.get_node(&protected.id).unwrap().unwrap().tier,
+            Tier::LongTerm
+        );
+
+        let entries = store
+            .evolve(&EvolutionPolicy::default().protect_referenced(false))
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].node_id, protected.id);
+    }
+
+    #[test]
+    fn test_evolve_promotes_and_archives_by_confidence() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let mut busy = Node::new(NodeType::Fact, "busy");
+        busy.access_count = 5;
+        let doubtful = Node::new(NodeType::Fact, "doubtful").with_confidence(0.05);
+        store.add_node(&busy).unwrap();
+        store.add_node(&doubtful).unwrap();
+
+        store.evolve(&EvolutionPolicy::default()).unwrap();
+
+        assert_eq!(
+            store.get_node(&busy.id).unwrap().unwrap().tier,
+            Tier::Session
+        );
+        assert_eq!(
+            store.get_node(&doubtful.id).unwrap().unwrap().tier,
+            Tier::Archive
+        );
+    }
+
+    #[test]
+    fn test_consolidate_duplicates_across_tiers() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let best = Node::new(NodeType::Fact, "The API uses JWT for auth").with_confidence(0.9);
+        let mut reworded = Node::new(NodeType::Fact, "the api uses JWT for auth.")
+            .with_tier(Tier::LongTerm)
+            .with_confidence(0.6);
+        reworded.access_count = 4;
+        let unrelated = Node::new(NodeType::Fact, "Builds run on CI nightly");
+        let entity = Node::new(NodeType::Entity, "API");
+        for node in [&best, &reworded, &unrelated, &entity] {
+            store.add_node(node).unwrap();
+        }
+        store
+            .add_edge(&HyperEdge::binary(
+                EdgeType::Semantic,
+                entity.id.clone(),
+                reworded.id.clone(),
+                "describes",
+            ))
+            .unwrap();
+
+        let result = store.consolidate_duplicates(0.8).unwrap();
+        assert_eq!(result.merges.len(), 1);
+        assert_eq!(result.merges[0].survivor, best.id);
+        assert_eq!(result.merges[0].merged, vec![reworded.id.clone()]);
+        assert_eq!(result.promoted_nodes, vec![best.id.clone()]);
+
+        let survivor = store.get_node(&best.id).unwrap().unwrap();
+        assert_eq!(survivor.tier, Tier::LongTerm);
+        assert_eq!(survivor.access_count, 4);
+        assert!(store.get_node(&reworded.id).unwrap().is_none());
+
+        let edges = store.get_edges_for_node(&best.id).unwrap();
+        assert_eq!(edges.len(), 1);
+        assert!(edges[0].contains(&entity.id));
+        assert_eq!(store.stats().unwrap().total_nodes, 3);
+
+        // Idempotent
+        let again = store.consolidate_duplicates(0.8).unwrap();
+        assert!(again.merges.is_empty());
+        assert_eq!(store.stats().unwrap().total_nodes, 3);
+    }
+
+    #[test]
+    fn test_consolidate_duplicates_prefers_embeddings() {
+        let store = SqliteMemoryStore::in_memory().unwrap();
+        let a = Node::new(NodeType::Fact, "Tokens expire hourly").with_embedding(vec![1.0, 0.0]);
+        let b = Node::new(NodeType::Fact, "Sessions time out after 60 minutes")
+            .with_embedding(vec![0.99, 0.05]);
+        // Same words, but the embeddings say they differ.
+        let c = Node::new(NodeType::Fact, "Tokens expire hourly").with_embedding(vec![0.0, 1.0]);
+        for node in [&a, &b, &c] {
+            store.add_node(node).unwrap();
+        }
+
+        let result = store.consolidate_duplicates(0.95).unwrap();
+        assert_eq!(result.merges.len(), 1);
+        let mut group = result.source_nodes.clone();
+        group.sort_by_key(|id| id.0);
+        let mut expected = vec![a.id.clone(), b.id.clone()];
+        expected.sort_by_key(|id| id.0);
+        assert_eq!(group, expected);
+        assert!(store.get_node(&c.id).unwrap().is_some());
+    }
 }
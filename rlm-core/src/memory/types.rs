@@ -5,7 +5,7 @@
 //! - **HyperEdges**: N-ary relationships connecting multiple nodes
 //! - **Tiers**: Lifecycle stages for memory evolution
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -180,6 +180,8 @@ pub enum ProvenanceSource {
     Inference,
     /// External import
     Import,
+    /// Admitted by epistemic verification
+    Verification,
 }
 
 /// A memory node in the hypergraph.
@@ -430,6 +432,18 @@ pub struct ConsolidationResult {
     pub archived_nodes: Vec<NodeId>,
     /// Summary of what happened
     pub summary: String,
+    /// Duplicate groups folded into a surviving node
+    #[serde(default)]
+    pub merges: Vec<NodeMerge>,
+}
+
+/// A group of near-duplicate nodes merged into one survivor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMerge {
+    /// Node that was kept
+    pub survivor: NodeId,
+    /// Nodes that were folded into the survivor and deleted
+    pub merged: Vec<NodeId>,
 }
 
 /// Query for searching nodes.
@@ -439,6 +453,8 @@ pub struct NodeQuery {
     pub text: Option<String>,
     /// Filter by node types
     pub node_types: Option<Vec<NodeType>>,
+    /// Filter by subtype
+    pub subtype: Option<String>,
     /// Filter by tiers
     pub tiers: Option<Vec<Tier>>,
     /// Minimum confidence
@@ -468,6 +484,11 @@ impl NodeQuery {
         self
     }
 
+    pub fn subtype(mut self, subtype: impl Into<String>) -> Self {
+        self.subtype = Some(subtype.into());
+        self
+    }
+
     pub fn tiers(mut self, tiers: Vec<Tier>) -> Self {
         self.tiers = Some(tiers);
         self
@@ -489,6 +510,73 @@ impl NodeQuery {
     }
 }
 
+/// Criteria for automatic tier evolution, applied by
+/// [`SqliteMemoryStore::evolve`](super::SqliteMemoryStore::evolve).
+#[derive(Debug, Clone)]
+pub struct EvolutionPolicy {
+    /// Accesses needed to promote a Task node to Session
+    pub session_min_access: u64,
+    /// Accesses needed to promote a Session node to LongTerm
+    pub long_term_min_access: u64,
+    /// Minimum age before a Session node can reach LongTerm
+    pub long_term_min_age: Duration,
+    /// LongTerm nodes not accessed for this long are archived
+    pub archive_after_idle: Duration,
+    /// Nodes below this confidence are archived from any tier
+    pub archive_confidence_floor: f64,
+    /// Keep idle LongTerm nodes that are members of an active hyperedge
+    pub protect_referenced: bool,
+}
+
+impl Default for EvolutionPolicy {
+    fn default() -> Self {
+        Self {
+            session_min_access: 3,
+            long_term_min_access: 10,
+            long_term_min_age: Duration::days(1),
+            archive_after_idle: Duration::days(30),
+            archive_confidence_floor: 0.2,
+            protect_referenced: true,
+        }
+    }
+}
+
+impl EvolutionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn session_min_access(mut self, count: u64) -> Self {
+        self.session_min_access = count;
+        self
+    }
+
+    pub fn long_term_min_access(mut self, count: u64) -> Self {
+        self.long_term_min_access = count;
+        self
+    }
+
+    pub fn long_term_min_age(mut self, age: Duration) -> Self {
+        self.long_term_min_age = age;
+        self
+    }
+
+    pub fn archive_after_idle(mut self, idle: Duration) -> Self {
+        self.archive_after_idle = idle;
+        self
+    }
+
+    pub fn archive_confidence_floor(mut self, floor: f64) -> Self {
+        self.archive_confidence_floor = floor;
+        self
+    }
+
+    pub fn protect_referenced(mut self, protect: bool) -> Self {
+        self.protect_referenced = protect;
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,6 +13,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
 
 use super::{Module, Predictor};
 use crate::error::{Error, Result};
@@ -24,6 +25,9 @@ use crate::signature::{validate_fields, FieldSpec, Signature};
 /// The output of the first module is passed as input to the second module.
 /// This requires the output type of `M1::Sig` to be convertible to the input
 /// type of `M2::Sig`.
+/// The converted inputs are validated against `M2::Sig::input_fields()`
+/// before the second module runs, so a malformed handoff fails with the hop
+/// and field named.
 ///
 /// # Type Parameters
 ///
@@ -86,6 +90,58 @@ where
         self.name = name.into();
         self
     }
+
+    /// Describe the chain's steps in execution order.
+    pub fn steps(&self) -> Vec<ChainStep> {
+        vec![
+            ChainStep::new::<S1>(self.first.name()),
+            ChainStep::new::<S2>(self.second.name()),
+        ]
+    }
+
+    /// Check that the transformed intermediate satisfies the second
+    /// module's input fields.
+    fn validate_handoff(&self, inputs: &S2::Inputs) -> Result<()> {
+        let hop = format!("{} -> {}", self.first.name(), self.second.name());
+        let value = serde_json::to_value(inputs).map_err(|e| {
+            Error::Config(format!(
+                "{}: failed to serialize inputs at hop {}: {}",
+                self.name, hop, e
+            ))
+        })?;
+        validate_fields(&value, &S2::input_fields()).map_err(|errors| {
+            let summary = errors
+                .iter()
+                .map(|err| err.to_string())
+                .collect::<Vec<_>>()
+                .join("; ");
+            Error::Config(format!(
+                "{}: malformed handoff at hop {}: {}",
+                self.name, hop, summary
+            ))
+        })
+    }
+}
+
+/// One step of a [`Chain`], for introspection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainStep {
+    /// Module name
+    pub name: String,
+    /// Input fields of the step's signature
+    pub input_fields: Vec<FieldSpec>,
+    /// Output fields of the step's signature
+    pub output_fields: Vec<FieldSpec>,
+}
+
+impl ChainStep {
+    fn new<S: Signature>(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            input_fields: S::input_fields(),
+            output_fields: S::output_fields(),
+        }
+    }
 }
 
 /// A signature that wraps an existing signature for chaining.
@@ -131,6 +187,7 @@ where
 
         // Transform output to input for second module
         let transformed = (self.transform)(intermediate)?;
+        self.validate_handoff(&transformed)?;
 
         // Execute second module
         self.second.forward(transformed).await
@@ -223,9 +280,39 @@ fn validate_direct_field_mapping(
     Ok(())
 }
 
+/// How [`ParallelVec`] handles modules that fail.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Stop at the first error and return it; pending modules are not started.
+    #[default]
+    FailFast,
+    /// Run every module and return each result in module order.
+    CollectErrors,
+    /// Run every module and return only the successes, with a failure count.
+    SkipErrors,
+}
+
+/// Results of [`ParallelVec::run`], shaped by the [`FailurePolicy`].
+#[derive(Debug)]
+pub enum ParallelOutcome<T> {
+    /// Every module succeeded (`FailFast`).
+    Completed(Vec<T>),
+    /// One result per module, in module order (`CollectErrors`).
+    Collected(Vec<Result<T>>),
+    /// Successful outputs in module order, and how many failed (`SkipErrors`).
+    Skipped {
+        /// Outputs of the modules that succeeded
+        outputs: Vec<T>,
+        /// Number of modules that failed
+        failed: usize,
+    },
+}
+
 /// A module that runs multiple modules in parallel and collects results.
 ///
 /// All modules must have the same input type. Outputs are collected into a Vec.
+/// At most [`with_concurrency`](Self::with_concurrency) modules run at once,
+/// and the [`FailurePolicy`] decides what happens when some fail.
 ///
 /// # Example
 ///
@@ -243,6 +330,8 @@ where
 {
     modules: Vec<M>,
     name: String,
+    concurrency: Option<usize>,
+    failure_policy: FailurePolicy,
     _phantom: std::marker::PhantomData<S>,
 }
 
@@ -257,6 +346,8 @@ where
         Self {
             modules,
             name: format!("Parallel({})", count),
+            concurrency: None,
+            failure_policy: FailurePolicy::default(),
             _phantom: std::marker::PhantomData,
         }
     }
@@ -266,6 +357,54 @@ where
         self.name = name.into();
         self
     }
+
+    /// Run at most `n` modules at once (minimum 1). Unbounded by default.
+    pub fn with_concurrency(mut self, n: usize) -> Self {
+        self.concurrency = Some(n.max(1));
+        self
+    }
+
+    /// Set how failures are handled.
+    pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.failure_policy = policy;
+        self
+    }
+}
+
+impl<M, S> ParallelVec<M, S>
+where
+    M: Module<Sig = S> + Send + Sync,
+    S: Signature + 'static,
+    S::Inputs: Clone,
+{
+    /// Run all modules on `inputs` according to the failure policy.
+    ///
+    /// Returns `Err` only under `FailFast`.
+    pub async fn run(&self, inputs: S::Inputs) -> Result<ParallelOutcome<S::Outputs>> {
+        let limit = self.concurrency.unwrap_or(self.modules.len()).max(1);
+        // Futures are lazy: `buffered` starts at most `limit` at a time and
+        // yields results in module order.
+        let futures: Vec<_> = self
+            .modules
+            .iter()
+            .map(|m| m.forward(inputs.clone()))
+            .collect();
+        let results = stream::iter(futures).buffered(limit);
+
+        match self.failure_policy {
+            FailurePolicy::FailFast => Ok(ParallelOutcome::Completed(results.try_collect().await?)),
+            FailurePolicy::CollectErrors => Ok(ParallelOutcome::Collected(results.collect().await)),
+            FailurePolicy::SkipErrors => {
+                let results: Vec<Result<S::Outputs>> = results.collect().await;
+                let total = results.len();
+                let outputs: Vec<S::Outputs> = results.into_iter().filter_map(|r| r.ok()).collect();
+                Ok(ParallelOutcome::Skipped {
+                    failed: total - outputs.len(),
+                    outputs,
+                })
+            }
+        }
+    }
 }
 
 /// Signature wrapper for parallel execution that returns a Vec of outputs.
@@ -310,22 +449,15 @@ where
 {
     type Sig = ParallelSignature<S>;
 
+    /// Under `CollectErrors`, every module runs and the first error (in
+    /// module order) is returned; use [`ParallelVec::run`] to get them all.
     async fn forward(&self, inputs: S::Inputs) -> Result<Vec<S::Outputs>> {
-        // Execute all modules in parallel
-        let futures: Vec<_> = self
-            .modules
-            .iter()
-            .map(|m| {
-                let inputs = inputs.clone();
-                async move { m.forward(inputs).await }
-            })
-            .collect();
-
-        // Wait for all results
-        let results = futures::future::join_all(futures).await;
-
-        // Collect results, propagating first error
-        results.into_iter().collect()
+        match self.run(inputs).await? {
+            ParallelOutcome::Completed(outputs) | ParallelOutcome::Skipped { outputs, .. } => {
+                Ok(outputs)
+            }
+            ParallelOutcome::Collected(results) => results.into_iter().collect(),
+        }
     }
 
     fn predictors(&self) -> Vec<&dyn Predictor> {
@@ -572,4 +704,213 @@ mod tests {
         let message = err.to_string();
         assert!(message.contains("chain_direct field type mismatch"));
     }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct PartialSinkInputs {
+        value: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    }
+
+    struct PartialSinkSig;
+
+    impl Signature for PartialSinkSig {
+        type Inputs = PartialSinkInputs;
+        type Outputs = SinkOutputs;
+
+        fn instructions() -> &'static str {
+            "sink requiring a label"
+        }
+
+        fn input_fields() -> Vec<FieldSpec> {
+            vec![
+                FieldSpec::new("value", FieldType::String),
+                FieldSpec::new("label", FieldType::String),
+            ]
+        }
+
+        fn output_fields() -> Vec<FieldSpec> {
+            vec![FieldSpec::new("result", FieldType::String)]
+        }
+    }
+
+    #[derive(Default)]
+    struct PartialSinkModule;
+
+    #[async_trait]
+    impl Module for PartialSinkModule {
+        type Sig = PartialSinkSig;
+
+        async fn forward(&self, inputs: PartialSinkInputs) -> Result<SinkOutputs> {
+            Ok(SinkOutputs {
+                result: inputs.label.unwrap_or_default(),
+            })
+        }
+
+        fn predictors(&self) -> Vec<&dyn Predictor> {
+            vec![]
+        }
+
+        fn set_lm(&mut self, _lm: Arc<dyn LLMClient>) {}
+
+        fn get_lm(&self) -> Option<Arc<dyn LLMClient>> {
+            None
+        }
+
+        fn name(&self) -> &str {
+            "PartialSinkModule"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_rejects_malformed_handoff() {
+        let chain = Chain::new(SourceModule, PartialSinkModule, |outputs: SourceOutputs| {
+            Ok(PartialSinkInputs {
+                value: outputs.value,
+                label: None,
+            })
+        });
+
+        let err = chain
+            .forward(SourceInputs {
+                value: "ok".to_string(),
+            })
+            .await
+            .expect_err("handoff missing a required field should fail");
+        let message = err.to_string();
+        assert!(message.contains("SourceModule -> PartialSinkModule"));
+        assert!(message.contains("label"));
+    }
+
+    #[test]
+    fn test_chain_steps() {
+        let chain =
+            chain_direct::<SourceModule, SinkModule, SourceSig, SinkSig>(SourceModule, SinkModule);
+
+        let steps = chain.steps();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].name, "SourceModule");
+        assert_eq!(steps[1].name, "SinkModule");
+        assert_eq!(steps[1].input_fields, SinkSig::input_fields());
+        assert_eq!(steps[1].output_fields[0].name, "result");
+    }
+
+    /// Fails when `fail` is set; tracks peak concurrency across instances.
+    struct FlakyModule {
+        id: usize,
+        fail: bool,
+        active: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Module for FlakyModule {
+        type Sig = SourceSig;
+
+        async fn forward(&self, inputs: SourceInputs) -> Result<SourceOutputs> {
+            use std::sync::atomic::Ordering;
+            let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            // Later modules finish first, to exercise result ordering.
+            tokio::time::sleep(std::time::Duration::from_millis(20 - self.id as u64)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+
+            if self.fail {
+                Err(Error::Internal(format!("module {} failed", self.id)))
+            } else {
+                Ok(SourceOutputs {
+                    value: format!("{}:{}", inputs.value, self.id),
+                })
+            }
+        }
+
+        fn predictors(&self) -> Vec<&dyn Predictor> {
+            vec![]
+        }
+
+        fn set_lm(&mut self, _lm: Arc<dyn LLMClient>) {}
+
+        fn get_lm(&self) -> Option<Arc<dyn LLMClient>> {
+            None
+        }
+    }
+
+    fn flaky_modules(
+        count: usize,
+        fail_on: &[usize],
+    ) -> (Vec<FlakyModule>, Arc<std::sync::atomic::AtomicUsize>) {
+        let active = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let modules = (0..count)
+            .map(|id| FlakyModule {
+                id,
+                fail: fail_on.contains(&id),
+                active: active.clone(),
+                peak: peak.clone(),
+            })
+            .collect();
+        (modules, peak)
+    }
+
+    fn input() -> SourceInputs {
+        SourceInputs {
+            value: "x".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_concurrency_is_bounded() {
+        let (modules, peak) = flaky_modules(8, &[]);
+        let parallel = ParallelVec::new(modules).with_concurrency(3);
+
+        let outputs = parallel.forward(input()).await.unwrap();
+        assert_eq!(outputs.len(), 8);
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_fail_fast() {
+        let (modules, _) = flaky_modules(6, &[1, 4]);
+        let parallel = ParallelVec::new(modules).with_concurrency(2);
+
+        let err = parallel.run(input()).await.unwrap_err();
+        assert!(err.to_string().contains("module 1 failed"));
+        assert!(parallel.forward(input()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_collect_errors_keeps_order() {
+        let (modules, _) = flaky_modules(6, &[1, 4]);
+        let parallel = ParallelVec::new(modules)
+            .with_concurrency(3)
+            .with_failure_policy(FailurePolicy::CollectErrors);
+
+        let ParallelOutcome::Collected(results) = parallel.run(input()).await.unwrap() else {
+            panic!("expected collected results");
+        };
+        assert_eq!(results.len(), 6);
+        for (i, result) in results.iter().enumerate() {
+            match result {
+                Ok(out) => assert_eq!(out.value, format!("x:{}", i)),
+                Err(_) => assert!(i == 1 || i == 4),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_skip_errors() {
+        let (modules, _) = flaky_modules(6, &[1, 4]);
+        let parallel = ParallelVec::new(modules).with_failure_policy(FailurePolicy::SkipErrors);
+
+        let ParallelOutcome::Skipped { outputs, failed } = parallel.run(input()).await.unwrap()
+        else {
+            panic!("expected skipped results");
+        };
+        assert_eq!(failed, 2);
+        let values: Vec<_> = outputs.iter().map(|o| o.value.as_str()).collect();
+        assert_eq!(values, ["x:0", "x:2", "x:3", "x:5"]);
+
+        let outputs = parallel.forward(input()).await.unwrap();
+        assert_eq!(outputs.len(), 4);
+    }
 }
@@ -41,13 +41,16 @@ mod example;
 mod optimize;
 mod predict;
 
-pub use compose::{chain_direct, Chain, ChainSignature, ParallelSignature, ParallelVec};
+pub use compose::{
+    chain_direct, Chain, ChainSignature, ChainStep, FailurePolicy, ParallelOutcome,
+    ParallelSignature, ParallelVec,
+};
 pub use example::{Demonstration, ErasedDemonstration, Example, ExampleMetadata};
 pub use optimize::{
-    metrics, BootstrapFewShot, Metric, MetricFn, NamedMetric, OptimizationStats, OptimizedModule,
-    Optimizer, RoundStats,
+    metrics, BootstrapFewShot, CandidateScore, Metric, MetricFn, NamedMetric, OptimizationStats,
+    OptimizedModule, Optimizer, RoundStats,
 };
-pub use predict::{Predict, PredictConfig};
+pub use predict::{Predict, PredictConfig, RepairAttempt, RepairOutcome};
 
 use crate::error::Result;
 use crate::llm::LLMClient;
@@ -116,6 +119,16 @@ pub trait Predictor: Send + Sync {
     /// Clear all demonstrations.
     fn clear_demonstrations(&mut self);
 
+    /// Replace all demonstrations.
+    ///
+    /// Takes `&self` so optimizers can install candidate demo sets through
+    /// [`Module::predictors`]; implementations use interior mutability. The
+    /// default ignores the demos, for predictors that cannot take any.
+    fn replace_demonstrations(&self, demos: Vec<ErasedDemonstration>) -> Result<()> {
+        let _ = demos;
+        Ok(())
+    }
+
     /// Get the number of demonstrations.
     fn demonstration_count(&self) -> usize;
 
@@ -134,6 +147,9 @@ pub struct ModuleConfig {
     pub max_tokens: Option<u32>,
     /// Whether to include demonstrations in prompts.
     pub use_demonstrations: bool,
+    /// Estimated token budget for demonstrations; later demos that would
+    /// exceed it are dropped.
+    pub max_demonstration_tokens: Option<usize>,
 }
 
 impl Default for ModuleConfig {
@@ -143,6 +159,7 @@ impl Default for ModuleConfig {
             temperature: 0.0,
             max_tokens: None,
             use_demonstrations: true,
+            max_demonstration_tokens: None,
         }
     }
 }
@@ -176,6 +193,12 @@ impl ModuleConfig {
         self.use_demonstrations = false;
         self
     }
+
+    /// Cap the estimated tokens spent on demonstrations.
+    pub fn with_max_demonstration_tokens(mut self, tokens: usize) -> Self {
+        self.max_demonstration_tokens = Some(tokens);
+        self
+    }
 }
 
 #[cfg(test)]
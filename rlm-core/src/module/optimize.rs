@@ -135,6 +135,9 @@ pub trait Optimizer: Send + Sync {
 ///
 /// BootstrapFewShot runs the module on training data, evaluates outputs
 /// using a metric, and selects the best examples as few-shot demonstrations.
+///
+/// With a `validation_split`, part of the labeled data is held out and
+/// several candidate demo sets are scored on it; the best set wins.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BootstrapFewShot {
     /// Maximum number of bootstrapped demonstrations to include.
@@ -162,6 +165,24 @@ pub struct BootstrapFewShot {
 
     /// Whether to deduplicate demonstrations by output.
     pub deduplicate: bool,
+
+    /// Fraction of the labeled examples held out for validation.
+    /// When positive, candidate demo sets are scored on the held-out
+    /// examples and the best-scoring set is selected. 0.0 disables.
+    #[serde(default)]
+    pub validation_split: f64,
+
+    /// Number of candidate demo sets scored on the validation split.
+    #[serde(default = "default_num_candidate_sets")]
+    pub num_candidate_sets: usize,
+
+    /// Seed for the train/validation split and candidate sampling.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+fn default_num_candidate_sets() -> usize {
+    4
 }
 
 impl Default for BootstrapFewShot {
@@ -174,6 +195,9 @@ impl Default for BootstrapFewShot {
             temperature: 1.0,
             include_reasoning: true,
             deduplicate: true,
+            validation_split: 0.0,
+            num_candidate_sets: default_num_candidate_sets(),
+            seed: 0,
         }
     }
 }
@@ -226,6 +250,25 @@ impl BootstrapFewShot {
         self
     }
 
+    /// Hold out `ratio` of the labeled examples to select among candidate
+    /// demo sets.
+    pub fn with_validation_split(mut self, ratio: f64) -> Self {
+        self.validation_split = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the number of candidate demo sets scored on the validation split.
+    pub fn with_num_candidate_sets(mut self, n: usize) -> Self {
+        self.num_candidate_sets = n.max(1);
+        self
+    }
+
+    /// Set the seed for the split and candidate sampling.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
     /// Create a "greedy" configuration optimized for speed.
     pub fn greedy() -> Self {
         Self {
@@ -236,6 +279,7 @@ impl BootstrapFewShot {
             temperature: 0.7,
             include_reasoning: false,
             deduplicate: true,
+            ..Self::default()
         }
     }
 
@@ -249,28 +293,40 @@ impl BootstrapFewShot {
             temperature: 1.0,
             include_reasoning: true,
             deduplicate: true,
+            ..Self::default()
         }
     }
-}
 
-#[async_trait]
-impl Optimizer for BootstrapFewShot {
-    async fn compile<S, M>(
+    /// Split labeled examples into train and validation sets.
+    ///
+    /// Returns `None` when validation is disabled or there are too few
+    /// examples to leave both sides non-empty.
+    fn split<'a, S: Signature>(&self, examples: &'a [Example<S>]) -> Option<TrainValSplit<'a, S>> {
+        let val_len = (examples.len() as f64 * self.validation_split).round() as usize;
+        if val_len == 0 || val_len >= examples.len() {
+            return None;
+        }
+
+        let mut shuffled: Vec<&Example<S>> = examples.iter().collect();
+        SplitMix64::new(self.seed).shuffle(&mut shuffled);
+        let train = shuffled.split_off(val_len);
+        Some((train, shuffled))
+    }
+
+    /// Run bootstrap rounds over `trainset` and return scored candidates,
+    /// best first.
+    async fn bootstrap<S, M>(
         &self,
-        module: M,
-        trainset: &[Example<S>],
-        metric: MetricFn<S::Outputs>,
-    ) -> Result<OptimizedModule<S, M>>
+        module: &M,
+        trainset: &[&Example<S>],
+        metric: &MetricFn<S::Outputs>,
+        stats: &mut OptimizationStats,
+    ) -> Vec<ScoredDemo<S>>
     where
         S: Signature + 'static,
-        M: Module<Sig = S> + Clone + 'static,
+        M: Module<Sig = S>,
     {
-        if trainset.is_empty() {
-            return Err(Error::Config("Training set is empty".to_string()));
-        }
-
         let mut all_candidates: Vec<ScoredDemo<S>> = Vec::new();
-        let mut stats = OptimizationStats::new(self.max_rounds);
 
         // Run bootstrap rounds
         for round in 0..self.max_rounds {
@@ -321,19 +377,7 @@ impl Optimizer for BootstrapFewShot {
 
         // Also add labeled examples from trainset (with score 1.0)
         for example in trainset.iter().take(self.max_labeled_demos) {
-            let demo = ScoredDemo {
-                inputs: example.inputs.clone(),
-                outputs: example.outputs.clone(),
-                gold_outputs: example.outputs.clone(),
-                score: 1.0,
-                reasoning: if self.include_reasoning {
-                    Some(build_labeled_reasoning_summary::<S>(&example.outputs))
-                } else {
-                    None
-                },
-                round: usize::MAX, // Sentinel for labeled demos
-            };
-            all_candidates.push(demo);
+            all_candidates.push(self.labeled_demo(example));
         }
 
         // Sort by score descending
@@ -344,29 +388,190 @@ impl Optimizer for BootstrapFewShot {
         });
 
         // Deduplicate if enabled
-        let candidates = if self.deduplicate {
+        if self.deduplicate {
             deduplicate_demos(all_candidates)
         } else {
             all_candidates
-        };
+        }
+    }
 
-        // Select top demonstrations
-        let total_demos = self.max_bootstrapped_demos + self.max_labeled_demos;
-        let selected: Vec<Demonstration<S>> = candidates
-            .into_iter()
-            .take(total_demos)
-            .map(|d| {
-                let mut demo = Demonstration::new(d.inputs, d.outputs);
-                if let Some(score) = Some(d.score) {
-                    demo = demo.with_metric_score(score);
-                }
-                if let Some(reasoning) = d.reasoning {
-                    demo = demo.set_reasoning(reasoning);
+    fn labeled_demo<S: Signature>(&self, example: &Example<S>) -> ScoredDemo<S> {
+        ScoredDemo {
+            inputs: example.inputs.clone(),
+            outputs: example.outputs.clone(),
+            gold_outputs: example.outputs.clone(),
+            score: 1.0,
+            reasoning: if self.include_reasoning {
+                Some(build_labeled_reasoning_summary::<S>(&example.outputs))
+            } else {
+                None
+            },
+            round: usize::MAX, // Sentinel for labeled demos
+        }
+    }
+
+    /// Build candidate demo sets from the scored pool.
+    ///
+    /// The first set is the top-scoring demos; the rest are seeded random
+    /// samples of the pool.
+    fn candidate_sets<S: Signature>(&self, pool: &[ScoredDemo<S>]) -> Vec<Vec<usize>> {
+        let size = (self.max_bootstrapped_demos + self.max_labeled_demos).min(pool.len());
+        let mut sets = vec![(0..size).collect::<Vec<_>>()];
+        if size == pool.len() {
+            // Every sample would be the whole pool.
+            return sets;
+        }
+
+        let mut rng = SplitMix64::new(self.seed.wrapping_add(1));
+        for _ in 1..self.num_candidate_sets {
+            let mut indices: Vec<usize> = (0..pool.len()).collect();
+            rng.shuffle(&mut indices);
+            indices.truncate(size);
+            indices.sort_unstable();
+            if !sets.contains(&indices) {
+                sets.push(indices);
+            }
+        }
+        sets
+    }
+
+    /// Mean metric score of `module` on `valset` with `demos` installed.
+    async fn validate<S, M>(
+        module: &M,
+        demos: &[Demonstration<S>],
+        valset: &[&Example<S>],
+        metric: &MetricFn<S::Outputs>,
+        stats: &mut OptimizationStats,
+    ) -> Result<f64>
+    where
+        S: Signature + 'static,
+        M: Module<Sig = S>,
+    {
+        install_demonstrations(module, demos)?;
+
+        let mut total = 0.0;
+        for example in valset {
+            match module.forward(example.inputs.clone()).await {
+                Ok(predicted) => total += metric.score(&predicted, &example.outputs),
+                Err(e) => stats.record_error(e.to_string()),
+            }
+        }
+        Ok(total / valset.len() as f64)
+    }
+}
+
+/// Train and validation examples, in that order.
+type TrainValSplit<'a, S> = (Vec<&'a Example<S>>, Vec<&'a Example<S>>);
+
+/// Replace the demonstrations of every predictor in `module`.
+fn install_demonstrations<S: Signature, M: Module<Sig = S>>(
+    module: &M,
+    demos: &[Demonstration<S>],
+) -> Result<()> {
+    let erased: Vec<ErasedDemonstration> =
+        demos.iter().map(ErasedDemonstration::from_typed).collect();
+    for predictor in module.predictors() {
+        predictor.replace_demonstrations(erased.clone())?;
+    }
+    Ok(())
+}
+
+/// SplitMix64 generator, so splits are reproducible from a seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Fisher-Yates shuffle.
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[async_trait]
+impl Optimizer for BootstrapFewShot {
+    async fn compile<S, M>(
+        &self,
+        module: M,
+        trainset: &[Example<S>],
+        metric: MetricFn<S::Outputs>,
+    ) -> Result<OptimizedModule<S, M>>
+    where
+        S: Signature + 'static,
+        M: Module<Sig = S> + Clone + 'static,
+    {
+        if trainset.is_empty() {
+            return Err(Error::Config("Training set is empty".to_string()));
+        }
+
+        let mut stats = OptimizationStats::new(self.max_rounds);
+
+        let selected: Vec<Demonstration<S>> = if self.validation_split <= 0.0 {
+            let examples: Vec<&Example<S>> = trainset.iter().collect();
+            let candidates = self
+                .bootstrap(&module, &examples, &metric, &mut stats)
+                .await;
+            let total_demos = self.max_bootstrapped_demos + self.max_labeled_demos;
+            candidates
+                .into_iter()
+                .take(total_demos)
+                .map(ScoredDemo::into_demonstration)
+                .collect()
+        } else if let Some((train, val)) = self.split(trainset) {
+            let pool = self.bootstrap(&module, &train, &metric, &mut stats).await;
+
+            let mut best: Option<(usize, f64)> = None;
+            let sets = self.candidate_sets(&pool);
+            for (candidate, indices) in sets.iter().enumerate() {
+                let demos: Vec<Demonstration<S>> = indices
+                    .iter()
+                    .map(|&i| pool[i].to_demonstration())
+                    .collect();
+                let score = Self::validate(&module, &demos, &val, &metric, &mut stats).await?;
+                stats.candidate_scores.push(CandidateScore {
+                    candidate,
+                    demonstrations: demos.len(),
+                    validation_score: score,
+                });
+                if best.map_or(true, |(_, best_score)| score > best_score) {
+                    best = Some((candidate, score));
                 }
-                demo
-            })
-            .collect();
+            }
+
+            let (candidate, score) = best.unwrap_or((0, 0.0));
+            stats.selected_candidate = Some(candidate);
+            stats.validation_score = Some(score);
+            sets[candidate]
+                .iter()
+                .map(|&i| pool[i].to_demonstration())
+                .collect()
+        } else {
+            stats.warnings.push(format!(
+                "{} labeled examples are too few for a {:.0}% validation split; \
+                 using all examples as demonstrations",
+                trainset.len(),
+                self.validation_split * 100.0
+            ));
+            trainset
+                .iter()
+                .map(|example| self.labeled_demo(example).into_demonstration())
+                .collect()
+        };
 
+        install_demonstrations(&module, &selected)?;
         stats.set_selected_count(selected.len());
 
         Ok(OptimizedModule {
@@ -390,6 +595,25 @@ struct ScoredDemo<S: Signature> {
     round: usize,
 }
 
+impl<S: Signature> ScoredDemo<S> {
+    fn to_demonstration(&self) -> Demonstration<S> {
+        let mut demo = Demonstration::new(self.inputs.clone(), self.outputs.clone())
+            .with_metric_score(self.score);
+        if let Some(ref reasoning) = self.reasoning {
+            demo = demo.set_reasoning(reasoning.clone());
+        }
+        demo
+    }
+
+    fn into_demonstration(self) -> Demonstration<S> {
+        let mut demo = Demonstration::new(self.inputs, self.outputs).with_metric_score(self.score);
+        if let Some(reasoning) = self.reasoning {
+            demo = demo.set_reasoning(reasoning);
+        }
+        demo
+    }
+}
+
 /// Deduplicate demonstrations by output (keeps highest scoring).
 fn deduplicate_demos<S: Signature>(mut demos: Vec<ScoredDemo<S>>) -> Vec<ScoredDemo<S>> {
     // Already sorted by score descending, so first occurrence of each output wins
@@ -566,6 +790,30 @@ pub struct OptimizationStats {
     pub errors: Vec<String>,
     /// Per-round statistics.
     pub round_stats: Vec<RoundStats>,
+    /// Validation scores of each candidate demo set (empty without a
+    /// validation split).
+    #[serde(default)]
+    pub candidate_scores: Vec<CandidateScore>,
+    /// Index of the selected candidate set.
+    #[serde(default)]
+    pub selected_candidate: Option<usize>,
+    /// Validation score of the selected candidate set.
+    #[serde(default)]
+    pub validation_score: Option<f64>,
+    /// Non-fatal issues, such as falling back when data is scarce.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Validation result for one candidate demo set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateScore {
+    /// Candidate index (0 is the top-scoring bootstrap set).
+    pub candidate: usize,
+    /// Number of demonstrations in the set.
+    pub demonstrations: usize,
+    /// Mean metric score on the validation split.
+    pub validation_score: f64,
 }
 
 impl OptimizationStats {
@@ -582,6 +830,10 @@ impl OptimizationStats {
             min_score: f64::INFINITY,
             errors: Vec::new(),
             round_stats: Vec::new(),
+            candidate_scores: Vec::new(),
+            selected_candidate: None,
+            validation_score: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -890,6 +1142,86 @@ mod tests {
         }
     }
 
+    /// Echoes its input only when a demo with input "key" is installed;
+    /// otherwise answers with a distinct wrong output per input.
+    #[derive(Clone, Default)]
+    struct DemoAwareModule {
+        demos: Arc<std::sync::Mutex<Vec<ErasedDemonstration>>>,
+    }
+
+    impl Predictor for DemoAwareModule {
+        fn add_demonstration(&mut self, inputs: serde_json::Value, outputs: serde_json::Value) {
+            self.demos
+                .lock()
+                .unwrap()
+                .push(ErasedDemonstration::new(inputs, outputs));
+        }
+
+        fn clear_demonstrations(&mut self) {
+            self.demos.lock().unwrap().clear();
+        }
+
+        fn replace_demonstrations(&self, demos: Vec<ErasedDemonstration>) -> Result<()> {
+            *self.demos.lock().unwrap() = demos;
+            Ok(())
+        }
+
+        fn demonstration_count(&self) -> usize {
+            self.demos.lock().unwrap().len()
+        }
+
+        fn predictor_name(&self) -> &str {
+            "demo_aware"
+        }
+    }
+
+    #[async_trait]
+    impl Module for DemoAwareModule {
+        type Sig = MockSignature;
+
+        async fn forward(&self, inputs: MockInputs) -> Result<MockOutputs> {
+            let has_key = self
+                .demos
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|d| d.inputs["text"] == "key");
+            Ok(MockOutputs {
+                result: if has_key {
+                    inputs.text
+                } else {
+                    format!("wrong: {}", inputs.text)
+                },
+            })
+        }
+
+        fn predictors(&self) -> Vec<&dyn Predictor> {
+            vec![self]
+        }
+
+        fn set_lm(&mut self, _lm: Arc<dyn LLMClient>) {}
+
+        fn get_lm(&self) -> Option<Arc<dyn LLMClient>> {
+            None
+        }
+    }
+
+    fn labeled(texts: &[&str]) -> Vec<Example<MockSignature>> {
+        texts
+            .iter()
+            .map(|t| {
+                Example::new(
+                    MockInputs {
+                        text: t.to_string(),
+                    },
+                    MockOutputs {
+                        result: t.to_string(),
+                    },
+                )
+            })
+            .collect()
+    }
+
     fn mock_trainset() -> Vec<Example<MockSignature>> {
         vec![
             Example::new(
@@ -1127,6 +1459,110 @@ mod tests {
             .all(|d| d.reasoning.is_none()));
     }
 
+    #[tokio::test]
+    async fn test_compile_selects_best_candidate_on_validation() {
+        let trainset = labeled(&["key", "a", "b", "c", "d", "e", "f", "g"]);
+        let metric: MetricFn<MockOutputs> = Arc::new(metrics::exact_match);
+        let optimizer = BootstrapFewShot::new()
+            .with_max_bootstrapped_demos(0)
+            .with_max_labeled_demos(1)
+            .with_validation_split(0.25)
+            .with_num_candidate_sets(8)
+            .with_seed(9);
+
+        let module = DemoAwareModule::default();
+        let optimized = optimizer
+            .compile(module.clone(), &trainset, metric.clone())
+            .await
+            .expect("compile should succeed");
+        let stats = optimized.stats();
+
+        assert!(stats.candidate_scores.len() > 1);
+        let best = stats
+            .candidate_scores
+            .iter()
+            .map(|c| c.validation_score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        // Only sets containing "key" score, and the top-scored set does not.
+        assert_eq!(best, 1.0);
+        assert_ne!(stats.selected_candidate, Some(0));
+        assert!(stats
+            .candidate_scores
+            .iter()
+            .any(|c| c.validation_score < best));
+        assert_eq!(stats.validation_score, Some(best));
+        assert_eq!(
+            stats.candidate_scores[stats.selected_candidate.unwrap()].validation_score,
+            best
+        );
+        assert!(stats.warnings.is_empty());
+
+        // The winning set contains the demo the module needs and is
+        // installed in the module.
+        assert!(optimized
+            .demonstrations()
+            .iter()
+            .any(|d| d.inputs.text == "key"));
+        assert_eq!(
+            module.demonstration_count(),
+            optimized.demonstrations().len()
+        );
+
+        // Same seed, same split and selection.
+        let again = optimizer
+            .compile(DemoAwareModule::default(), &trainset, metric)
+            .await
+            .expect("compile should succeed");
+        assert_eq!(again.stats().candidate_scores, stats.candidate_scores);
+        let inputs = |m: &OptimizedModule<MockSignature, DemoAwareModule>| {
+            m.demonstrations()
+                .iter()
+                .map(|d| d.inputs.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(inputs(&again), inputs(&optimized));
+    }
+
+    #[tokio::test]
+    async fn test_compile_validation_falls_back_when_data_scarce() {
+        let trainset = labeled(&["key"]);
+        let metric: MetricFn<MockOutputs> = Arc::new(metrics::exact_match);
+        let optimizer = BootstrapFewShot::new().with_validation_split(0.3);
+
+        let optimized = optimizer
+            .compile(DemoAwareModule::default(), &trainset, metric)
+            .await
+            .expect("compile should succeed");
+
+        assert_eq!(optimized.demonstrations().len(), 1);
+        assert_eq!(optimized.stats().warnings.len(), 1);
+        assert!(optimized.stats().candidate_scores.is_empty());
+    }
+
+    #[test]
+    fn test_split_is_deterministic() {
+        let examples = labeled(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]);
+        let texts = |split: Vec<&Example<MockSignature>>| {
+            split
+                .iter()
+                .map(|e| e.inputs.text.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let optimizer = BootstrapFewShot::new()
+            .with_validation_split(0.3)
+            .with_seed(42);
+        let (train, val) = optimizer.split(&examples).unwrap();
+        assert_eq!(train.len(), 7);
+        assert_eq!(val.len(), 3);
+
+        let (train2, val2) = optimizer.split(&examples).unwrap();
+        assert_eq!(texts(train), texts(train2));
+        assert_eq!(texts(val), texts(val2));
+
+        assert!(BootstrapFewShot::new().split(&examples).is_none());
+    }
+
     #[tokio::test]
     async fn test_optimized_module_save_and_load_roundtrip() {
         let optimizer = BootstrapFewShot::new()
@@ -16,8 +16,11 @@ use tokio::sync::RwLock;
 use super::example::ErasedDemonstration;
 use super::{Module, ModuleConfig, Predictor};
 use crate::error::{Error, Result};
-use crate::llm::{ChatMessage, CompletionRequest, LLMClient};
-use crate::signature::{validate_fields, Signature};
+use crate::llm::{ChatMessage, CompletionRequest, LLMClient, ResponseFormat};
+use crate::signature::{
+    demos_within_budget, render_prompt_with_demos, validate_fields, FieldType, ParseError,
+    Signature, ValidationError,
+};
 
 /// Configuration for a Predict module.
 #[derive(Debug, Clone)]
@@ -28,6 +31,12 @@ pub struct PredictConfig {
     pub model: Option<String>,
     /// Whether to include chain-of-thought reasoning.
     pub chain_of_thought: bool,
+    /// Constrain responses to the signature's output schema via the
+    /// provider's structured-output support.
+    ///
+    /// Takes precedence over `chain_of_thought`, since a schema-constrained
+    /// response has no room for free-text reasoning.
+    pub json_mode: bool,
 }
 
 impl Default for PredictConfig {
@@ -36,6 +45,7 @@ impl Default for PredictConfig {
             module: ModuleConfig::default(),
             model: None,
             chain_of_thought: false,
+            json_mode: false,
         }
     }
 }
@@ -58,6 +68,12 @@ impl PredictConfig {
         self
     }
 
+    /// Request schema-constrained JSON output from the provider.
+    pub fn with_json_mode(mut self) -> Self {
+        self.json_mode = true;
+        self
+    }
+
     /// Set the temperature.
     pub fn with_temperature(mut self, temp: f64) -> Self {
         self.module.temperature = temp;
@@ -71,6 +87,33 @@ impl PredictConfig {
     }
 }
 
+/// A failed parse that triggered a repair round in [`Predict::forward_with_repair`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairAttempt {
+    /// Zero-based index of the LLM call that produced the bad response.
+    pub round: u32,
+    /// The raw response that failed to parse.
+    pub response: String,
+    /// Why the response was rejected.
+    pub error: ParseError,
+}
+
+/// Outputs from [`Predict::forward_with_repair`] along with its repair history.
+#[derive(Debug, Clone)]
+pub struct RepairOutcome<O> {
+    /// Parsed and validated outputs.
+    pub outputs: O,
+    /// Rejected responses, in order; empty if the first response parsed.
+    pub attempts: Vec<RepairAttempt>,
+}
+
+impl<O> RepairOutcome<O> {
+    /// Number of repair rounds needed before the outputs parsed.
+    pub fn repair_rounds(&self) -> usize {
+        self.attempts.len()
+    }
+}
+
 /// A module that predicts outputs for a given signature.
 ///
 /// `Predict` is the fundamental building block for LLM-based modules.
@@ -147,6 +190,28 @@ impl<S: Signature> Predict<S> {
         Ok(())
     }
 
+    /// Render a single-string prompt for `inputs`.
+    ///
+    /// Stored demonstrations are included as few-shot examples when
+    /// `use_demonstrations` is on, capped by `max_demonstration_tokens`.
+    pub async fn to_prompt(&self, inputs: &S::Inputs) -> Result<String> {
+        let input_value = serde_json::to_value(inputs)?;
+        let demos = self.demonstrations.read().await;
+        let demos: &[ErasedDemonstration] = if self.config.module.use_demonstrations {
+            &demos
+        } else {
+            &[]
+        };
+        Ok(render_prompt_with_demos(
+            S::instructions(),
+            &S::input_fields(),
+            &S::output_fields(),
+            demos,
+            self.config.module.max_demonstration_tokens,
+            &input_value,
+        ))
+    }
+
     /// Build the prompt for the LLM.
     async fn build_prompt(&self, inputs: &S::Inputs) -> Result<Vec<ChatMessage>> {
         let mut messages = Vec::new();
@@ -155,10 +220,16 @@ impl<S: Signature> Predict<S> {
         let system_content = self.build_system_prompt();
         messages.push(ChatMessage::system(system_content));
 
-        // Add demonstrations if enabled
+        // Add demonstrations if enabled, capped like the single-string prompt
         if self.config.module.use_demonstrations {
             let demos = self.demonstrations.read().await;
-            for demo in demos.iter() {
+            let kept = demos_within_budget(
+                &S::input_fields(),
+                &S::output_fields(),
+                &demos,
+                self.config.module.max_demonstration_tokens,
+            );
+            for demo in demos.iter().take(kept) {
                 // User message with demo inputs
                 let demo_input = format_inputs_for_prompt(&demo.inputs);
                 messages.push(ChatMessage::user(demo_input));
@@ -223,7 +294,7 @@ impl<S: Signature> Predict<S> {
             ));
         }
 
-        if self.config.chain_of_thought {
+        if self.config.chain_of_thought && !self.config.json_mode {
             prompt.push_str(
                 "\nFirst explain your reasoning step by step, then provide the JSON output.\n",
             );
@@ -234,6 +305,76 @@ impl<S: Signature> Predict<S> {
         prompt
     }
 
+    /// Execute the module, asking the LLM to repair unparseable output.
+    ///
+    /// Unlike [`Module::forward`], which re-sends the same prompt on failure,
+    /// each repair round appends the rejected response and a follow-up
+    /// message describing the [`ParseError`], the output schema, and the
+    /// allowed values of any enum field that was violated. At most
+    /// `max_retries` repair rounds are attempted; LLM errors are returned
+    /// immediately.
+    pub async fn forward_with_repair(
+        &self,
+        inputs: S::Inputs,
+    ) -> Result<RepairOutcome<S::Outputs>> {
+        validate_inputs::<S>(&inputs)?;
+
+        let lm_guard = self.lm.read().await;
+        let lm = lm_guard
+            .as_ref()
+            .ok_or_else(|| Error::Config("No language model set for Predict module".to_string()))?;
+
+        let mut messages = self.build_prompt(&inputs).await?;
+        let mut attempts = Vec::new();
+
+        for round in 0..=self.config.module.max_retries {
+            let response = lm.complete(self.build_request(messages.clone())).await?;
+
+            match S::from_response(&response.content) {
+                Ok(outputs) => return Ok(RepairOutcome { outputs, attempts }),
+                Err(error) => {
+                    messages.push(ChatMessage::assistant(response.content.clone()));
+                    messages.push(ChatMessage::user(build_repair_prompt::<S>(&error)));
+                    attempts.push(RepairAttempt {
+                        round,
+                        response: response.content,
+                        error,
+                    });
+                }
+            }
+        }
+
+        let last = attempts
+            .last()
+            .map(|a| a.error.to_user_message())
+            .unwrap_or_default();
+        Err(Error::Internal(format!(
+            "Failed to parse response after {} repair rounds: {}",
+            attempts.len().saturating_sub(1),
+            last
+        )))
+    }
+
+    /// Build a completion request for the given messages.
+    fn build_request(&self, messages: Vec<ChatMessage>) -> CompletionRequest {
+        CompletionRequest {
+            model: self.config.model.clone(),
+            system: None, // System is in messages
+            messages,
+            max_tokens: self.config.module.max_tokens,
+            temperature: Some(self.config.module.temperature),
+            stop: None,
+            enable_caching: true,
+            metadata: None,
+            tools: Vec::new(),
+            response_format: self.config.json_mode.then(|| ResponseFormat::JsonSchema {
+                schema: S::output_schema(),
+            }),
+            top_logprobs: None,
+            cache_breakpoints: Vec::new(),
+        }
+    }
+
     /// Parse the LLM response into outputs.
     fn parse_response(&self, response: &str) -> Result<S::Outputs> {
         S::from_response(response)
@@ -251,20 +392,10 @@ impl<S: Signature> Default for Predict<S> {
 impl<S: Signature + 'static> Module for Predict<S> {
     type Sig = S;
 
+    #[tracing::instrument(name = "predict.forward", skip_all, fields(signature = S::name()))]
     async fn forward(&self, inputs: S::Inputs) -> Result<S::Outputs> {
         // Validate typed inputs before any LM call for deterministic pre-execution failures.
-        let input_value = serde_json::to_value(&inputs)?;
-        if let Err(errors) = validate_fields(&input_value, &S::input_fields()) {
-            let detail = errors
-                .iter()
-                .map(|e| e.to_user_message())
-                .collect::<Vec<_>>()
-                .join("; ");
-            return Err(Error::Config(format!(
-                "Input validation failed: {}",
-                detail
-            )));
-        }
+        validate_inputs::<S>(&inputs)?;
 
         // Get the LM
         let lm_guard = self.lm.read().await;
@@ -276,16 +407,7 @@ impl<S: Signature + 'static> Module for Predict<S> {
         let messages = self.build_prompt(&inputs).await?;
 
         // Create completion request
-        let request = CompletionRequest {
-            model: self.config.model.clone(),
-            system: None, // System is in messages
-            messages,
-            max_tokens: self.config.module.max_tokens,
-            temperature: Some(self.config.module.temperature),
-            stop: None,
-            enable_caching: true,
-            metadata: None,
-        };
+        let request = self.build_request(messages);
 
         // Call LLM with retries
         let mut last_error = None;
@@ -348,6 +470,17 @@ impl<S: Signature + 'static> Predictor for Predict<S> {
         }
     }
 
+    fn replace_demonstrations(&self, demos: Vec<ErasedDemonstration>) -> Result<()> {
+        let mut guard = self.demonstrations.try_write().map_err(|_| {
+            Error::Internal(format!(
+                "cannot replace demonstrations of {} while a forward pass is running",
+                self.name
+            ))
+        })?;
+        *guard = demos;
+        Ok(())
+    }
+
     fn demonstration_count(&self) -> usize {
         self.demonstrations.try_read().map(|g| g.len()).unwrap_or(0)
     }
@@ -357,6 +490,71 @@ impl<S: Signature + 'static> Predictor for Predict<S> {
     }
 }
 
+/// Validate typed inputs against the signature's input fields.
+fn validate_inputs<S: Signature>(inputs: &S::Inputs) -> Result<()> {
+    let input_value = serde_json::to_value(inputs)?;
+    if let Err(errors) = validate_fields(&input_value, &S::input_fields()) {
+        let detail = errors
+            .iter()
+            .map(|e| e.to_user_message())
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(Error::Config(format!(
+            "Input validation failed: {}",
+            detail
+        )));
+    }
+    Ok(())
+}
+
+/// Build the follow-up message asking the LLM to fix a rejected response.
+fn build_repair_prompt<S: Signature>(error: &ParseError) -> String {
+    let mut prompt = format!(
+        "Your previous response could not be used: {}\n\n",
+        error.to_user_message()
+    );
+
+    // Spell out allowed values for any enum field the response got wrong
+    if let ParseError::ValidationFailed(errors) = error {
+        let output_fields = S::output_fields();
+        let mut listed = Vec::new();
+        for err in errors {
+            let ValidationError::EnumInvalid { field, .. } = innermost(err) else {
+                continue;
+            };
+            let base = field.split(['[', '.']).next().unwrap_or(field);
+            let spec = output_fields.iter().find(|f| f.name == base);
+            if let Some(FieldType::Enum(values)) = spec.map(|f| &f.field_type) {
+                if !listed.contains(&base) {
+                    prompt.push_str(&format!(
+                        "Allowed values for `{}`: {}\n",
+                        base,
+                        values.join(", ")
+                    ));
+                    listed.push(base);
+                }
+            }
+        }
+        if !listed.is_empty() {
+            prompt.push('\n');
+        }
+    }
+
+    let schema = serde_json::to_string_pretty(&S::output_schema()).unwrap_or_default();
+    prompt.push_str("Respond again with only a JSON object matching this schema:\n\n```json\n");
+    prompt.push_str(&schema);
+    prompt.push_str("\n```\n");
+    prompt
+}
+
+/// Unwrap nested validation errors to the underlying error.
+fn innermost(error: &ValidationError) -> &ValidationError {
+    match error {
+        ValidationError::NestedError { error, .. } => innermost(error),
+        other => other,
+    }
+}
+
 /// Format inputs as a prompt string.
 fn format_inputs_for_prompt(inputs: &Value) -> String {
     match inputs {
@@ -454,6 +652,21 @@ mod tests {
         assert!(config.chain_of_thought);
     }
 
+    #[tokio::test]
+    async fn test_replace_demonstrations_reports_contention() {
+        let predict = Predict::<MockSignature>::new();
+        let demo = ErasedDemonstration::new(
+            serde_json::json!({"text": "a"}),
+            serde_json::json!({"result": "b"}),
+        );
+
+        predict.replace_demonstrations(vec![demo]).unwrap();
+        assert_eq!(predict.demonstration_count(), 1);
+
+        let _reader = predict.demonstrations.read().await;
+        assert!(predict.replace_demonstrations(Vec::new()).is_err());
+    }
+
     #[test]
     fn test_system_prompt_generation() {
         let predict = Predict::<MockSignature>::new();
@@ -465,6 +678,81 @@ mod tests {
         assert!(prompt.contains("JSON"));
     }
 
+    #[tokio::test]
+    async fn test_to_prompt_respects_use_demonstrations() {
+        let inputs = MockInputs {
+            text: "real input".to_string(),
+        };
+        let predict = Predict::<MockSignature>::new();
+        predict
+            .add_typed_demonstration(
+                MockInputs {
+                    text: "demo input".to_string(),
+                },
+                MockOutputs {
+                    result: "demo output".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let prompt = predict.to_prompt(&inputs).await.unwrap();
+        assert!(prompt.find("demo input").unwrap() < prompt.find("real input").unwrap());
+
+        let predict = predict.with_config(PredictConfig {
+            module: ModuleConfig::new().without_demonstrations(),
+            ..PredictConfig::default()
+        });
+        let prompt = predict.to_prompt(&inputs).await.unwrap();
+        assert!(!prompt.contains("demo input"));
+        assert_eq!(prompt, MockSignature::to_prompt(&inputs));
+    }
+
+    #[tokio::test]
+    async fn test_build_prompt_caps_demonstration_tokens() {
+        let predict = Predict::<MockSignature>::new();
+        for i in 0..3 {
+            predict
+                .add_typed_demonstration(
+                    MockInputs {
+                        text: format!("demo {} {}", i, "padding ".repeat(20)),
+                    },
+                    MockOutputs {
+                        result: "ok".to_string(),
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let inputs = MockInputs {
+            text: "real input".to_string(),
+        };
+
+        // system + 3 demo turns + input
+        assert_eq!(predict.build_prompt(&inputs).await.unwrap().len(), 8);
+
+        let demos = predict.demonstrations.read().await.clone();
+        let predict = predict.with_config(PredictConfig {
+            module: ModuleConfig::new().with_max_demonstration_tokens(80),
+            ..PredictConfig::default()
+        });
+        let kept = demos_within_budget(
+            &MockSignature::input_fields(),
+            &MockSignature::output_fields(),
+            &demos,
+            Some(80),
+        );
+        assert_eq!(kept, 1);
+        let messages = predict.build_prompt(&inputs).await.unwrap();
+        assert_eq!(messages.len(), 4);
+        assert!(messages[1].content.contains("demo 0"));
+
+        // The single-string prompt keeps the same demonstrations.
+        let prompt = predict.to_prompt(&inputs).await.unwrap();
+        assert!(prompt.contains("demo 0"));
+        assert!(!prompt.contains("demo 1"));
+    }
+
     #[test]
     fn test_format_inputs() {
         let inputs = serde_json::json!({
@@ -498,6 +786,68 @@ mod tests {
                 },
                 timestamp: Utc::now(),
                 cost: Some(0.0),
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
+            })
+        }
+
+        async fn embed(&self, _request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+            Err(Error::LLM("not implemented".to_string()))
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::OpenRouter
+        }
+
+        fn available_models(&self) -> Vec<ModelSpec> {
+            vec![]
+        }
+    }
+
+    /// Client that replays canned responses and records every request.
+    struct ScriptedClient {
+        responses: std::sync::Mutex<Vec<String>>,
+        requests: Arc<std::sync::Mutex<Vec<CompletionRequest>>>,
+    }
+
+    impl ScriptedClient {
+        fn new(responses: &[&str]) -> Self {
+            Self {
+                responses: std::sync::Mutex::new(
+                    responses.iter().rev().map(|r| r.to_string()).collect(),
+                ),
+                requests: Arc::new(std::sync::Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LLMClient for ScriptedClient {
+        async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+            self.requests.lock().unwrap().push(request);
+            let content = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop()
+                .unwrap_or_else(|| "no more responses".to_string());
+            Ok(CompletionResponse {
+                id: "scripted".to_string(),
+                model: "mock-model".to_string(),
+                content,
+                stop_reason: None,
+                usage: TokenUsage {
+                    input_tokens: 1,
+                    output_tokens: 1,
+                    cache_read_tokens: None,
+                    cache_creation_tokens: None,
+                },
+                timestamp: Utc::now(),
+                cost: Some(0.0),
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
             })
         }
 
@@ -514,6 +864,117 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct CategoryOutputs {
+        category: String,
+    }
+
+    struct CategorySignature;
+
+    impl Signature for CategorySignature {
+        type Inputs = MockInputs;
+        type Outputs = CategoryOutputs;
+
+        fn instructions() -> &'static str {
+            "Categorize the text"
+        }
+
+        fn input_fields() -> Vec<FieldSpec> {
+            vec![FieldSpec::new("text", FieldType::String)]
+        }
+
+        fn output_fields() -> Vec<FieldSpec> {
+            vec![FieldSpec::new(
+                "category",
+                FieldType::enum_of(["bug", "feature", "question"]),
+            )]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forward_with_repair_recovers() {
+        let client = ScriptedClient::new(&[
+            "not json at all",
+            r#"{"category": "defect"}"#,
+            r#"{"category": "bug"}"#,
+        ]);
+        let requests = client.requests.clone();
+        let predict = Predict::<CategorySignature>::with_lm(Arc::new(client));
+
+        let outcome = predict
+            .forward_with_repair(MockInputs {
+                text: "it crashes".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.outputs.category, "bug");
+        assert_eq!(outcome.repair_rounds(), 2);
+        assert!(matches!(
+            outcome.attempts[0].error,
+            ParseError::InvalidJson { .. }
+        ));
+        assert_eq!(outcome.attempts[1].round, 1);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        // Each repair round carries the rejected response and the correction request
+        let last = &requests[2].messages;
+        assert_eq!(last[last.len() - 2].content, r#"{"category": "defect"}"#);
+        let repair = &last[last.len() - 1].content;
+        assert!(repair.contains("Allowed values for `category`: bug, feature, question"));
+        assert!(repair.contains("\"enum\""));
+    }
+
+    #[tokio::test]
+    async fn test_forward_json_mode_sends_output_schema() {
+        let client = ScriptedClient::new(&[r#"{"category": "bug"}"#, r#"{"category": "bug"}"#]);
+        let requests = client.requests.clone();
+        let lm: Arc<dyn LLMClient> = Arc::new(client);
+        let inputs = MockInputs {
+            text: "it crashes".to_string(),
+        };
+
+        Predict::<CategorySignature>::with_lm(lm.clone())
+            .forward(inputs.clone())
+            .await
+            .unwrap();
+        let predict = Predict::<CategorySignature>::with_lm(lm)
+            .with_config(PredictConfig::new().with_json_mode());
+        let outputs = predict.forward(inputs).await.unwrap();
+
+        assert_eq!(outputs.category, "bug");
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].response_format.is_none());
+        assert_eq!(
+            requests[1].response_format,
+            Some(ResponseFormat::JsonSchema {
+                schema: CategorySignature::output_schema()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forward_with_repair_gives_up_after_max_retries() {
+        let client = ScriptedClient::new(&["bad", "still bad", "bad again"]);
+        let requests = client.requests.clone();
+        let predict =
+            Predict::<CategorySignature>::with_lm(Arc::new(client)).with_config(PredictConfig {
+                module: ModuleConfig::new().with_max_retries(1),
+                ..PredictConfig::default()
+            });
+
+        let err = predict
+            .forward_with_repair(MockInputs {
+                text: "x".to_string(),
+            })
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("after 1 repair rounds"));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
     #[derive(Debug, Clone, Serialize, Deserialize)]
     struct EnumInputs {
         severity: String,
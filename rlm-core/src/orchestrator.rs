@@ -9,7 +9,7 @@
 
 use crate::complexity::{ActivationDecision, TaskComplexitySignals};
 use crate::context::SessionContext;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::llm::{
     CostTracker, DualModelConfig, ModelCallTier, RoutingContext, RoutingDecision, SmartRouter,
     TokenUsage,
@@ -18,13 +18,16 @@ use crate::signature::{
     ExecutionLimits, ExecutionResult, FallbackExtractor, FallbackTrigger, ReplHistory, Signature,
     SubmitResult,
 };
-use crate::trajectory::TrajectoryEvent;
+use crate::trajectory::{TrajectoryEmitter, TrajectoryEvent};
 use async_trait::async_trait;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::time::Instant;
 
 /// Result of a recursive RLM sub-call.
@@ -60,6 +63,12 @@ pub struct OrchestratorConfig {
     /// Optional dual-model routing configuration.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dual_model: Option<DualModelConfig>,
+    /// Maximum recursive sub-calls across the whole run (default: unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_calls: Option<u32>,
+    /// Refuse to repeat a sub-call with the same query at the same depth
+    #[serde(default)]
+    pub cycle_detection: bool,
 }
 
 impl Default for OrchestratorConfig {
@@ -72,6 +81,8 @@ impl Default for OrchestratorConfig {
             total_token_budget: 100_000,
             cost_budget_usd: 1.0,
             dual_model: None,
+            max_total_calls: None,
+            cycle_detection: false,
         }
     }
 }
@@ -258,6 +269,18 @@ impl OrchestratorBuilder {
         self
     }
 
+    /// Cap the number of recursive sub-calls across the whole run.
+    pub fn max_total_calls(mut self, max_calls: u32) -> Self {
+        self.config.max_total_calls = Some(max_calls);
+        self
+    }
+
+    /// Enable or disable cycle detection for recursive sub-calls.
+    pub fn cycle_detection(mut self, enabled: bool) -> Self {
+        self.config.cycle_detection = enabled;
+        self
+    }
+
     /// Set the execution mode.
     pub fn execution_mode(mut self, mode: ExecutionMode) -> Self {
         self.mode = Some(mode);
@@ -291,6 +314,7 @@ pub struct OrchestrationRoutingRuntime {
     dual_model: DualModelConfig,
     cost_tracker: CostTracker,
     tokens_used: u64,
+    budget: Option<BudgetGuard>,
 }
 
 impl OrchestrationRoutingRuntime {
@@ -299,6 +323,18 @@ impl OrchestrationRoutingRuntime {
         Self::new(SmartRouter::new(), mode.default_dual_model_config())
     }
 
+    /// Create a routing runtime from an orchestrator configuration.
+    ///
+    /// Uses the configured dual-model setup (or the balanced-mode default)
+    /// and guards recursive calls with [`BudgetGuard::from_config`].
+    pub fn from_config(config: &OrchestratorConfig) -> Self {
+        let dual_model = config
+            .dual_model
+            .clone()
+            .unwrap_or_else(|| ExecutionMode::Balanced.default_dual_model_config());
+        Self::new(SmartRouter::new(), dual_model).with_budget(BudgetGuard::from_config(config))
+    }
+
     /// Create a routing runtime with explicit router and dual-model config.
     pub fn new(router: SmartRouter, dual_model: DualModelConfig) -> Self {
         Self {
@@ -306,6 +342,34 @@ impl OrchestrationRoutingRuntime {
             dual_model,
             cost_tracker: CostTracker::new(),
             tokens_used: 0,
+            budget: None,
+        }
+    }
+
+    /// Start from costs recorded earlier, such as by previous requests in
+    /// the same session, so the budget check projects from them.
+    pub fn with_cost_tracker(mut self, cost_tracker: CostTracker) -> Self {
+        self.cost_tracker = cost_tracker;
+        self
+    }
+
+    /// Refuse recursive calls once projected spend would exceed the budget.
+    pub fn with_budget(mut self, budget: BudgetGuard) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Fail with [`Error::BudgetExhausted`] if one more call would take
+    /// spend past the budget.
+    pub fn check_budget(&self) -> Result<()> {
+        match self.budget {
+            Some(budget) if budget.is_exhausted(&self.cost_tracker) => {
+                Err(Error::budget_exhausted(format!(
+                    "projected spend exceeds ${:.4} budget",
+                    budget.budget_usd()
+                )))
+            }
+            _ => Ok(()),
         }
     }
 
@@ -315,7 +379,16 @@ impl OrchestrationRoutingRuntime {
     }
 
     /// Route a root/recursive orchestration call at a given depth.
-    pub fn route_recursive(&self, query: &str, depth: u32) -> (RoutingDecision, ModelCallTier) {
+    ///
+    /// Checks the budget first, so it must be called before every such
+    /// model call. Extraction calls are not budget-checked, so a stopped run
+    /// can still extract its best partial result.
+    pub fn route_recursive(
+        &self,
+        query: &str,
+        depth: u32,
+    ) -> Result<(RoutingDecision, ModelCallTier)> {
+        self.check_budget()?;
         let context = RoutingContext::new().with_depth(depth);
         let decision = self
             .router
@@ -325,7 +398,7 @@ impl OrchestrationRoutingRuntime {
         } else {
             ModelCallTier::Recursive
         };
-        (decision, tier)
+        Ok((decision, tier))
     }
 
     /// Route an extraction/fallback call at a given depth.
@@ -365,6 +438,202 @@ impl OrchestrationRoutingRuntime {
     }
 }
 
+/// Stops orchestration before projected spend passes a USD budget.
+///
+/// Projection assumes the next call costs as much as the average call so far
+/// in the most expensive of the root and recursive tiers, so a run that has
+/// been using the cheap recursive model is still stopped before a root call
+/// it cannot afford.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BudgetGuard {
+    budget_usd: f64,
+}
+
+impl BudgetGuard {
+    /// Create a guard for the given budget.
+    pub fn new(budget_usd: f64) -> Self {
+        Self { budget_usd }
+    }
+
+    /// Create a guard for [`OrchestratorConfig::cost_budget_usd`].
+    pub fn from_config(config: &OrchestratorConfig) -> Self {
+        Self::new(config.cost_budget_usd)
+    }
+
+    /// The configured budget in USD.
+    pub fn budget_usd(&self) -> f64 {
+        self.budget_usd
+    }
+
+    /// Spend after one more call like those recorded so far.
+    pub fn projected_spend(&self, tracker: &CostTracker) -> f64 {
+        let average = |tier: &crate::llm::TierCosts| {
+            if tier.request_count > 0 {
+                tier.cost / tier.request_count as f64
+            } else {
+                0.0
+            }
+        };
+        let next_call = average(&tracker.root_costs).max(average(&tracker.recursive_costs));
+        tracker.total_cost + next_call
+    }
+
+    /// Whether another call would take spend past the budget.
+    pub fn is_exhausted(&self, tracker: &CostTracker) -> bool {
+        self.projected_spend(tracker) > self.budget_usd
+    }
+
+    /// A [`TrajectoryEventType::BudgetExhausted`](crate::trajectory::TrajectoryEventType::BudgetExhausted)
+    /// event with the tier breakdown attached.
+    pub fn exhausted_event(&self, tracker: &CostTracker, depth: u32) -> TrajectoryEvent {
+        TrajectoryEvent::budget_exhausted(
+            depth,
+            tracker.total_cost,
+            self.projected_spend(tracker),
+            self.budget_usd,
+        )
+        .with_metadata("root_cost_usd", tracker.root_costs.cost)
+        .with_metadata("recursive_cost_usd", tracker.recursive_costs.cost)
+        .with_metadata("extraction_cost_usd", tracker.extraction_costs.cost)
+    }
+}
+
+/// Why a [`RecursionGuard`] refused a sub-call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecursionBreak {
+    /// The same query was already issued at this depth.
+    Cycle {
+        /// The repeated query
+        query: String,
+        /// Depth it was repeated at
+        depth: u32,
+    },
+    /// [`OrchestratorConfig::max_total_calls`] sub-calls have already been made.
+    MaxTotalCalls {
+        /// The configured limit
+        limit: u32,
+        /// Depth of the refused call
+        depth: u32,
+    },
+}
+
+impl RecursionBreak {
+    /// The fallback trigger to report for this break.
+    pub fn trigger(&self) -> FallbackTrigger {
+        match self {
+            Self::Cycle { .. } => FallbackTrigger::CycleDetected,
+            Self::MaxTotalCalls { .. } => FallbackTrigger::MaxTotalCalls,
+        }
+    }
+
+    /// A trajectory event describing the break.
+    ///
+    /// Cycles are reported as
+    /// [`TrajectoryEventType::CycleDetected`](crate::trajectory::TrajectoryEventType::CycleDetected);
+    /// hitting the call limit as
+    /// [`TrajectoryEventType::BudgetExhausted`](crate::trajectory::TrajectoryEventType::BudgetExhausted).
+    pub fn event(&self) -> TrajectoryEvent {
+        match self {
+            Self::Cycle { query, depth } => TrajectoryEvent::cycle_detected(*depth, query),
+            Self::MaxTotalCalls { limit, depth } => TrajectoryEvent::new(
+                crate::trajectory::TrajectoryEventType::BudgetExhausted,
+                *depth,
+                format!("Call budget exhausted: {} recursive calls made", limit),
+            )
+            .with_metadata("max_total_calls", *limit as i64),
+        }
+    }
+}
+
+/// Stops pathological self-recursion before it reaches
+/// [`OrchestratorConfig::max_depth`].
+///
+/// Each sub-call is checked with [`enter`](Self::enter) before it runs. With
+/// cycle detection on, the guard remembers a hash of every `(query, depth)`
+/// it has admitted and refuses to admit the same pair twice, which catches a
+/// sub-query that keeps reproducing an earlier one. Independently, it refuses
+/// any call past [`OrchestratorConfig::max_total_calls`].
+#[derive(Debug, Clone, Default)]
+pub struct RecursionGuard {
+    max_total_calls: Option<u32>,
+    cycle_detection: bool,
+    calls: u32,
+    seen: HashSet<u64>,
+}
+
+impl RecursionGuard {
+    /// Create a guard with the given limits.
+    pub fn new(max_total_calls: Option<u32>, cycle_detection: bool) -> Self {
+        Self {
+            max_total_calls,
+            cycle_detection,
+            ..Self::default()
+        }
+    }
+
+    /// Create a guard for [`OrchestratorConfig::max_total_calls`] and
+    /// [`OrchestratorConfig::cycle_detection`].
+    pub fn from_config(config: &OrchestratorConfig) -> Self {
+        Self::new(config.max_total_calls, config.cycle_detection)
+    }
+
+    /// Number of sub-calls admitted so far.
+    pub fn calls(&self) -> u32 {
+        self.calls
+    }
+
+    /// Admit a sub-call, or say why it must not run.
+    ///
+    /// A refused call is not counted.
+    pub fn enter(&mut self, query: &str, depth: u32) -> std::result::Result<(), RecursionBreak> {
+        if let Some(limit) = self.max_total_calls.filter(|limit| self.calls >= *limit) {
+            return Err(RecursionBreak::MaxTotalCalls { limit, depth });
+        }
+        if self.cycle_detection && !self.seen.insert(Self::state_hash(query, depth)) {
+            return Err(RecursionBreak::Cycle {
+                query: query.to_string(),
+                depth,
+            });
+        }
+        self.calls += 1;
+        Ok(())
+    }
+
+    /// Forget all admitted calls, keeping the limits.
+    pub fn reset(&mut self) {
+        self.calls = 0;
+        self.seen.clear();
+    }
+
+    fn state_hash(query: &str, depth: u32) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (query, depth).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// A recursive sub-call made during a [`FallbackLoopStep`], for recursion guarding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepSubCall {
+    /// The sub-query
+    pub query: String,
+    /// Depth the sub-call ran at
+    pub depth: u32,
+}
+
+/// A model call made during a [`FallbackLoopStep`], for budget tracking.
+#[derive(Debug, Clone)]
+pub struct StepModelCall {
+    /// Model that served the call.
+    pub model: String,
+    /// Token usage reported for the call.
+    pub usage: TokenUsage,
+    /// Cost in USD, if known.
+    pub cost: Option<f64>,
+    /// Orchestration tier of the call.
+    pub tier: ModelCallTier,
+}
+
 /// Single execution step consumed by [`FallbackLoop`].
 #[derive(Debug, Clone, Default)]
 pub struct FallbackLoopStep {
@@ -380,6 +649,10 @@ pub struct FallbackLoopStep {
     pub submit_result: Option<SubmitResult>,
     /// Full variable snapshot after the step.
     pub variables: HashMap<String, Value>,
+    /// Model calls made during this step, with their cost.
+    pub model_calls: Vec<StepModelCall>,
+    /// Recursive sub-calls made during this step.
+    pub sub_calls: Vec<StepSubCall>,
 }
 
 impl FallbackLoopStep {
@@ -420,6 +693,38 @@ impl FallbackLoopStep {
         self.variables = variables;
         self
     }
+
+    /// Record a model call made during this step.
+    ///
+    /// Also counts toward [`llm_calls`](Self::llm_calls).
+    pub fn with_model_call(
+        mut self,
+        model: impl Into<String>,
+        usage: TokenUsage,
+        cost: Option<f64>,
+        tier: ModelCallTier,
+    ) -> Self {
+        self.model_calls.push(StepModelCall {
+            model: model.into(),
+            usage,
+            cost,
+            tier,
+        });
+        self.llm_calls += 1;
+        self
+    }
+
+    /// Record a recursive sub-call made during this step.
+    ///
+    /// This only feeds the [`RecursionGuard`]; record the call's cost with
+    /// [`with_model_call`](Self::with_model_call).
+    pub fn with_sub_call(mut self, query: impl Into<String>, depth: u32) -> Self {
+        self.sub_calls.push(StepSubCall {
+            query: query.into(),
+            depth,
+        });
+        self
+    }
 }
 
 /// Minimal fallback-aware execution loop used by orchestrator integrations.
@@ -428,26 +733,74 @@ impl FallbackLoopStep {
 /// - successful `SUBMIT` exits with `ExecutionResult::Submitted`
 /// - submit validation failures terminate without fallback extraction
 /// - max-iteration / max-llm-call / timeout limits trigger fallback extraction
+/// - with a [`BudgetGuard`], projected spend past the budget triggers
+///   fallback extraction of the best partial result
+/// - with a [`RecursionGuard`], a repeated sub-call or one past the total call
+///   limit triggers fallback extraction the same way
 pub struct FallbackLoop<S: Signature> {
     extractor: FallbackExtractor<S>,
     limits: ExecutionLimits,
+    budget: Option<BudgetGuard>,
+    recursion: Option<RecursionGuard>,
+    emitter: Option<Arc<dyn TrajectoryEmitter>>,
 }
 
 impl<S: Signature> FallbackLoop<S> {
     /// Create a fallback loop with default extractor configuration.
     pub fn new(limits: ExecutionLimits) -> Self {
+        Self::with_extractor(limits, FallbackExtractor::new())
+    }
+
+    /// Create a fallback loop guarded by the budget and recursion limits in
+    /// `config`.
+    pub fn from_config(limits: ExecutionLimits, config: &OrchestratorConfig) -> Self {
+        Self::new(limits)
+            .with_budget(BudgetGuard::from_config(config))
+            .with_recursion_guard(RecursionGuard::from_config(config))
+    }
+
+    /// Create a fallback loop with a custom extractor.
+    pub fn with_extractor(limits: ExecutionLimits, extractor: FallbackExtractor<S>) -> Self {
         Self {
-            extractor: FallbackExtractor::new(),
+            extractor,
             limits,
+            budget: None,
+            recursion: None,
+            emitter: None,
         }
     }
 
-    /// Create a fallback loop with a custom extractor.
-    pub fn with_extractor(limits: ExecutionLimits, extractor: FallbackExtractor<S>) -> Self {
-        Self { extractor, limits }
+    /// Stop and extract once projected spend would exceed the budget.
+    ///
+    /// Spend is tracked from the [`FallbackLoopStep::model_calls`] each step
+    /// reports, and checked before the next step is requested. Steps that
+    /// make several model calls should route each through an
+    /// [`OrchestrationRoutingRuntime`] so the budget is checked per call.
+    pub fn with_budget(mut self, budget: BudgetGuard) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Stop and extract once a step makes a sub-call the guard refuses.
+    ///
+    /// Sub-calls are taken from the [`FallbackLoopStep::sub_calls`] each step
+    /// reports. Every [`run`](Self::run) starts from the guard as given here,
+    /// so state does not leak between runs.
+    pub fn with_recursion_guard(mut self, guard: RecursionGuard) -> Self {
+        self.recursion = Some(guard);
+        self
+    }
+
+    /// Emit trajectory events (such as budget exhaustion) to this emitter.
+    pub fn with_emitter(mut self, emitter: Arc<dyn TrajectoryEmitter>) -> Self {
+        self.emitter = Some(emitter);
+        self
     }
 
     /// Run the loop until SUBMIT success, fallback extraction, or terminal failure.
+    ///
+    /// Each iteration runs in an `orchestrator.iteration` span recording the
+    /// step's LLM calls and the run's cumulative token counts.
     pub fn run<NextStep, ExtractResponse>(
         &self,
         mut next_step: NextStep,
@@ -459,19 +812,46 @@ impl<S: Signature> FallbackLoop<S> {
     {
         let mut history = ReplHistory::new();
         let mut variables = HashMap::new();
+        let mut costs = CostTracker::new();
+        let mut recursion = self.recursion.clone();
         let started = Instant::now();
+        let mut iteration = 0u32;
 
         loop {
+            iteration += 1;
+            let span = tracing::info_span!(
+                "orchestrator.iteration",
+                iteration,
+                llm_calls = tracing::field::Empty,
+                input_tokens = tracing::field::Empty,
+                output_tokens = tracing::field::Empty,
+            );
+            let _entered = span.enter();
+
             history.total_time_ms = started.elapsed().as_millis() as u64;
             if let Some(trigger) = self.extractor.should_trigger(&history, &self.limits) {
                 return self.extract_with_trigger(
-                    &history,
+                    &mut history,
                     &variables,
                     trigger,
                     &mut extract_response,
                 );
             }
 
+            // Checked before the step runs, so no model call is made
+            // once the next one is projected past the budget.
+            if let Some(budget) = self.budget.filter(|b| b.is_exhausted(&costs)) {
+                if let Some(emitter) = &self.emitter {
+                    emitter.emit(budget.exhausted_event(&costs, 0));
+                }
+                return self.extract_with_trigger(
+                    &mut history,
+                    &variables,
+                    FallbackTrigger::BudgetExhausted,
+                    &mut extract_response,
+                );
+            }
+
             let Some(step) = next_step()? else {
                 return Ok(ExecutionResult::failed(
                     "Execution ended before SUBMIT and before fallback trigger",
@@ -481,6 +861,12 @@ impl<S: Signature> FallbackLoop<S> {
 
             let timestamp_ms = started.elapsed().as_millis() as u64;
             self.record_step(&mut history, &step, timestamp_ms);
+            for call in &step.model_calls {
+                costs.record_tiered(&call.model, &call.usage, call.cost, call.tier);
+            }
+            span.record("llm_calls", step.llm_calls);
+            span.record("input_tokens", costs.total_input_tokens);
+            span.record("output_tokens", costs.total_output_tokens);
             variables = step.variables;
 
             if let Some(submit_result) = step.submit_result {
@@ -514,10 +900,28 @@ impl<S: Signature> FallbackLoop<S> {
                 }
             }
 
+            if let Some(guard) = recursion.as_mut() {
+                let refused = step
+                    .sub_calls
+                    .iter()
+                    .find_map(|call| guard.enter(&call.query, call.depth).err());
+                if let Some(refused) = refused {
+                    if let Some(emitter) = &self.emitter {
+                        emitter.emit(refused.event());
+                    }
+                    return self.extract_with_trigger(
+                        &mut history,
+                        &variables,
+                        refused.trigger(),
+                        &mut extract_response,
+                    );
+                }
+            }
+
             history.total_time_ms = started.elapsed().as_millis() as u64;
             if let Some(trigger) = self.extractor.should_trigger(&history, &self.limits) {
                 return self.extract_with_trigger(
-                    &history,
+                    &mut history,
                     &variables,
                     trigger,
                     &mut extract_response,
@@ -544,7 +948,7 @@ impl<S: Signature> FallbackLoop<S> {
 
     fn extract_with_trigger<ExtractResponse>(
         &self,
-        history: &ReplHistory,
+        history: &mut ReplHistory,
         variables: &HashMap<String, Value>,
         trigger: FallbackTrigger,
         extract_response: &mut ExtractResponse,
@@ -552,6 +956,7 @@ impl<S: Signature> FallbackLoop<S> {
     where
         ExtractResponse: FnMut(&str, FallbackTrigger) -> Result<String>,
     {
+        history.add_fallback(trigger, history.total_time_ms);
         let prompt = self.extractor.extraction_prompt(history, variables);
         let response = extract_response(&prompt, trigger)?;
         Ok(self.extractor.parse_extraction_response(&response, trigger))
@@ -654,7 +1059,9 @@ mod tests {
     fn test_orchestration_routing_runtime_tracks_root_recursive_extraction() {
         let mut runtime = OrchestrationRoutingRuntime::for_mode(ExecutionMode::Balanced);
 
-        let (root_decision, root_tier) = runtime.route_recursive("Design system architecture", 0);
+        let (root_decision, root_tier) = runtime
+            .route_recursive("Design system architecture", 0)
+            .unwrap();
         assert_eq!(root_tier, ModelCallTier::Root);
         assert_eq!(
             root_decision.model.id,
@@ -669,7 +1076,8 @@ mod tests {
         };
         runtime.record_usage(&root_decision, &root_usage, Some(0.03), root_tier);
 
-        let (recursive_decision, recursive_tier) = runtime.route_recursive("Extract findings", 2);
+        let (recursive_decision, recursive_tier) =
+            runtime.route_recursive("Extract findings", 2).unwrap();
         assert_eq!(recursive_tier, ModelCallTier::Recursive);
         assert_eq!(
             recursive_decision.model.id,
@@ -828,6 +1236,206 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_budget_exhaustion_stops_after_first_call() {
+            let emitter = Arc::new(crate::trajectory::CollectingEmitter::new());
+            let loop_runner =
+                FallbackLoop::<TestSignature>::new(ExecutionLimits::new(10, 10, 60_000))
+                    .with_budget(BudgetGuard::new(0.015))
+                    .with_emitter(emitter.clone());
+            let usage = TokenUsage {
+                input_tokens: 1000,
+                output_tokens: 200,
+                cache_read_tokens: None,
+                cache_creation_tokens: None,
+            };
+
+            let mut steps_taken = 0;
+            let result = loop_runner
+                .run(
+                    || {
+                        steps_taken += 1;
+                        Ok(Some(
+                            FallbackLoopStep::new("LLM_QUERY('summarize')")
+                                .with_stdout("partial summary")
+                                .with_model_call(
+                                    "claude-sonnet",
+                                    usage.clone(),
+                                    Some(0.01),
+                                    ModelCallTier::Root,
+                                ),
+                        ))
+                    },
+                    |prompt, trigger| {
+                        assert_eq!(trigger, FallbackTrigger::BudgetExhausted);
+                        assert!(prompt.contains("partial summary"));
+                        assert!(prompt.contains("[Fallback] budget exhausted"));
+                        Ok("{\"answer\":\"partial\",\"_confidence\":0.5}".to_string())
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(steps_taken, 1);
+            match result {
+                ExecutionResult::Extracted {
+                    outputs,
+                    trigger_reason,
+                    ..
+                } => {
+                    assert_eq!(trigger_reason, FallbackTrigger::BudgetExhausted);
+                    assert_eq!(outputs.answer, "partial");
+                }
+                other => panic!("expected extracted fallback result, got {:?}", other),
+            }
+
+            let events = emitter.events();
+            assert_eq!(events.len(), 1);
+            assert_eq!(
+                events[0].event_type,
+                crate::trajectory::TrajectoryEventType::BudgetExhausted
+            );
+            assert_eq!(events[0].get_metadata("root_cost_usd"), Some(&json!(0.01)));
+            assert_eq!(events[0].get_metadata("projected_usd"), Some(&json!(0.02)));
+        }
+
+        #[test]
+        fn test_routing_runtime_checks_budget_before_each_call() {
+            let config = OrchestratorBuilder::new()
+                .cost_budget_usd(0.015)
+                .build_config();
+            let mut runtime = OrchestrationRoutingRuntime::from_config(&config);
+
+            let (decision, tier) = runtime.route_recursive("summarize", 0).unwrap();
+            runtime.record_usage(&decision, &TokenUsage::default(), Some(0.01), tier);
+
+            // A second call like the first would spend $0.02.
+            assert!(matches!(
+                runtime.route_recursive("summarize more", 1),
+                Err(Error::BudgetExhausted { .. })
+            ));
+            // Extraction of the partial result is still allowed.
+            assert_eq!(
+                runtime.route_extraction("extract", 1).1,
+                ModelCallTier::Extraction
+            );
+        }
+
+        #[test]
+        fn test_routing_runtime_resumes_from_earlier_costs() {
+            let config = OrchestratorBuilder::new()
+                .cost_budget_usd(0.015)
+                .build_config();
+            let mut first = OrchestrationRoutingRuntime::from_config(&config);
+            let (decision, tier) = first.route_recursive("summarize", 0).unwrap();
+            first.record_usage(&decision, &TokenUsage::default(), Some(0.01), tier);
+
+            // A fresh runtime for the next request sees the first one's spend.
+            let second = OrchestrationRoutingRuntime::from_config(&config)
+                .with_cost_tracker(first.cost_tracker().clone());
+            assert!(matches!(
+                second.route_recursive("summarize again", 0),
+                Err(Error::BudgetExhausted { .. })
+            ));
+            assert!(OrchestrationRoutingRuntime::from_config(&config)
+                .route_recursive("summarize again", 0)
+                .is_ok());
+        }
+
+        #[test]
+        fn test_budget_guard_projects_costliest_tier() {
+            let guard = BudgetGuard::new(0.05);
+            let mut tracker = CostTracker::new();
+            let usage = TokenUsage::default();
+            tracker.record_tiered("opus", &usage, Some(0.03), ModelCallTier::Root);
+            tracker.record_tiered("haiku", &usage, Some(0.001), ModelCallTier::Recursive);
+            tracker.record_tiered("haiku", &usage, Some(0.001), ModelCallTier::Recursive);
+
+            assert!((guard.projected_spend(&tracker) - 0.062).abs() < 1e-9);
+            assert!(guard.is_exhausted(&tracker));
+            assert!(!BudgetGuard::new(0.1).is_exhausted(&tracker));
+        }
+
+        #[test]
+        fn test_self_referential_query_terminates_with_cycle_event() {
+            let emitter = Arc::new(crate::trajectory::CollectingEmitter::new());
+            let config = OrchestratorBuilder::new()
+                .cycle_detection(true)
+                .build_config();
+            let loop_runner =
+                FallbackLoop::<TestSignature>::new(ExecutionLimits::new(100, 100, 60_000))
+                    .with_recursion_guard(RecursionGuard::from_config(&config))
+                    .with_emitter(emitter.clone());
+
+            let mut steps_taken = 0;
+            let result = loop_runner
+                .run(
+                    || {
+                        steps_taken += 1;
+                        assert!(steps_taken <= 2, "cycle was not broken");
+                        Ok(Some(
+                            FallbackLoopStep::new("LLM_QUERY('explain the question')")
+                                .with_stdout("restating the question")
+                                .with_sub_call("explain the question", 1),
+                        ))
+                    },
+                    |prompt, trigger| {
+                        assert_eq!(trigger, FallbackTrigger::CycleDetected);
+                        assert!(prompt.contains("restating the question"));
+                        Ok("{\"answer\":\"partial\",\"_confidence\":0.4}".to_string())
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(steps_taken, 2);
+            match result {
+                ExecutionResult::Extracted { trigger_reason, .. } => {
+                    assert_eq!(trigger_reason, FallbackTrigger::CycleDetected);
+                }
+                other => panic!("expected extracted fallback result, got {:?}", other),
+            }
+
+            let events = emitter.events();
+            assert_eq!(events.len(), 1);
+            assert_eq!(
+                events[0].event_type,
+                crate::trajectory::TrajectoryEventType::CycleDetected
+            );
+            assert_eq!(events[0].depth, 1);
+            assert_eq!(
+                events[0].get_metadata("query"),
+                Some(&json!("explain the question"))
+            );
+        }
+
+        #[test]
+        fn test_recursion_guard_limits() {
+            let mut guard = RecursionGuard::new(Some(2), false);
+            assert!(guard.enter("a", 1).is_ok());
+            assert!(guard.enter("a", 1).is_ok());
+            assert_eq!(
+                guard.enter("b", 2),
+                Err(RecursionBreak::MaxTotalCalls { limit: 2, depth: 2 })
+            );
+            assert_eq!(guard.calls(), 2);
+
+            let mut guard = RecursionGuard::new(None, true);
+            assert!(guard.enter("a", 1).is_ok());
+            assert!(guard.enter("a", 2).is_ok());
+            assert!(matches!(
+                guard.enter("a", 1),
+                Err(RecursionBreak::Cycle { depth: 1, .. })
+            ));
+            guard.reset();
+            assert!(guard.enter("a", 1).is_ok());
+
+            let config: OrchestratorConfig = serde_json::from_str(
+                &serde_json::to_string(&OrchestratorConfig::default()).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(config.max_total_calls, None);
+            assert!(!config.cycle_detection);
+        }
+
         #[test]
         fn test_timeout_triggers_fallback_before_step_execution() {
             let loop_runner = FallbackLoop::<TestSignature>::new(ExecutionLimits::new(10, 10, 0));
@@ -538,6 +538,9 @@ mod tests {
                 usage: TokenUsage::default(),
                 timestamp: Utc::now(),
                 cost: Some(0.0),
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
             })
         }
 
@@ -0,0 +1,226 @@
+//! Cache of proven goals.
+//!
+//! Large spec suites re-prove the same goals on every run. The cache maps a
+//! normalized form of each goal to the tactic sequence that closed it, so the
+//! engine can replay the known proof instead of searching again. Replays are
+//! always verified against the REPL because specs (and the lemmas they use)
+//! change between runs.
+//!
+//! Entries are only valid for the Lean toolchain that produced them; loading
+//! or retargeting a cache for a different toolchain discards its entries.
+
+use crate::error::{Error, Result};
+use crate::lean::types::Goal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::types::AutomationTier;
+
+/// A cached proof for one goal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedProof {
+    /// Tactics that closed the goal, in application order.
+    pub tactics: Vec<String>,
+    /// Tier that originally found the proof.
+    pub tier: AutomationTier,
+    /// Number of times this entry was replayed successfully.
+    #[serde(default)]
+    pub hits: u64,
+    /// When the proof was cached.
+    pub cached_at: DateTime<Utc>,
+}
+
+impl CachedProof {
+    /// Create a new cache entry.
+    pub fn new(tactics: Vec<String>, tier: AutomationTier) -> Self {
+        Self {
+            tactics,
+            tier,
+            hits: 0,
+            cached_at: Utc::now(),
+        }
+    }
+}
+
+/// Cache of successful proofs keyed by normalized goal.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProofCache {
+    /// Lean toolchain the cached proofs were checked against.
+    #[serde(default)]
+    toolchain: Option<String>,
+    /// Cached proofs keyed by [`ProofCache::key`].
+    #[serde(default)]
+    entries: HashMap<String, CachedProof>,
+}
+
+impl ProofCache {
+    /// Create an empty cache for a Lean toolchain (e.g. `"leanprover/lean4:v4.15.0"`).
+    pub fn new(toolchain: impl Into<String>) -> Self {
+        Self {
+            toolchain: Some(toolchain.into()),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Load a cache from a JSON file.
+    ///
+    /// A missing file yields an empty cache. Entries recorded under a
+    /// different toolchain are discarded.
+    pub fn load(path: impl AsRef<Path>, toolchain: impl Into<String>) -> Result<Self> {
+        let toolchain = toolchain.into();
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::new(toolchain));
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| Error::Internal(format!("Failed to read proof cache: {}", e)))?;
+        let mut cache: Self = serde_json::from_str(&contents).map_err(Error::Serialization)?;
+        cache.set_toolchain(toolchain);
+        Ok(cache)
+    }
+
+    /// Save the cache to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(Error::Serialization)?;
+        std::fs::write(path, json)
+            .map_err(|e| Error::Internal(format!("Failed to write proof cache: {}", e)))
+    }
+
+    /// Toolchain the cache is valid for.
+    pub fn toolchain(&self) -> Option<&str> {
+        self.toolchain.as_deref()
+    }
+
+    /// Retarget the cache to a toolchain, clearing it if the version changed.
+    ///
+    /// Returns `true` if entries were invalidated.
+    pub fn set_toolchain(&mut self, toolchain: impl Into<String>) -> bool {
+        let toolchain = toolchain.into();
+        if self.toolchain.as_deref() == Some(toolchain.as_str()) {
+            return false;
+        }
+
+        let invalidated = !self.entries.is_empty();
+        self.entries.clear();
+        self.toolchain = Some(toolchain);
+        invalidated
+    }
+
+    /// Normalized cache key for a goal.
+    ///
+    /// Hypotheses keep their order (later ones may depend on earlier ones);
+    /// whitespace runs are collapsed so formatting differences still hit.
+    pub fn key(goal: &Goal) -> String {
+        let mut parts: Vec<String> = goal
+            .hypotheses
+            .iter()
+            .map(|h| {
+                let mut hyp = format!("{} : {}", h.name, normalize_whitespace(&h.ty));
+                if let Some(value) = &h.value {
+                    hyp.push_str(" := ");
+                    hyp.push_str(&normalize_whitespace(value));
+                }
+                hyp
+            })
+            .collect();
+        parts.push(format!("⊢ {}", normalize_whitespace(&goal.target)));
+        parts.join("\n")
+    }
+
+    /// Look up the cached proof for a goal.
+    pub fn get(&self, goal: &Goal) -> Option<&CachedProof> {
+        self.entries.get(&Self::key(goal))
+    }
+
+    /// Cache the tactics that proved a goal, replacing any existing entry.
+    pub fn insert(&mut self, goal: &Goal, tactics: Vec<String>, tier: AutomationTier) {
+        self.entries
+            .insert(Self::key(goal), CachedProof::new(tactics, tier));
+    }
+
+    /// Remove the entry for a goal.
+    pub fn remove(&mut self, goal: &Goal) -> Option<CachedProof> {
+        self.entries.remove(&Self::key(goal))
+    }
+
+    /// Record a successful replay of a goal's cached proof.
+    pub fn record_hit(&mut self, goal: &Goal) {
+        if let Some(entry) = self.entries.get_mut(&Self::key(goal)) {
+            entry.hits += 1;
+        }
+    }
+
+    /// Number of cached proofs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn normalize_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_normalizes_whitespace() {
+        let a = Goal::from_string("x  +  0 =\n x").with_hypothesis("x", "Nat");
+        let b = Goal::from_string("x + 0 = x").with_hypothesis("x", " Nat ");
+        let c = Goal::from_string("x + 0 = x").with_hypothesis("y", "Nat");
+
+        assert_eq!(ProofCache::key(&a), ProofCache::key(&b));
+        assert_ne!(ProofCache::key(&a), ProofCache::key(&c));
+    }
+
+    #[test]
+    fn test_insert_and_hit() {
+        let mut cache = ProofCache::new("v4.15.0");
+        let goal = Goal::from_string("2 = 2");
+
+        cache.insert(&goal, vec!["rfl".to_string()], AutomationTier::Decidable);
+        cache.record_hit(&goal);
+
+        let entry = cache.get(&goal).unwrap();
+        assert_eq!(entry.tactics, vec!["rfl".to_string()]);
+        assert_eq!(entry.hits, 1);
+        assert!(cache.remove(&goal).is_some());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_persist_and_toolchain_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proof_cache.json");
+        let goal = Goal::from_string("2 = 2");
+
+        let mut cache = ProofCache::new("v4.15.0");
+        cache.insert(&goal, vec!["rfl".to_string()], AutomationTier::Decidable);
+        cache.save(&path).unwrap();
+
+        let same = ProofCache::load(&path, "v4.15.0").unwrap();
+        assert_eq!(same.len(), 1);
+        assert_eq!(same.get(&goal).unwrap().tier, AutomationTier::Decidable);
+
+        let upgraded = ProofCache::load(&path, "v4.16.0").unwrap();
+        assert!(upgraded.is_empty());
+        assert_eq!(upgraded.toolchain(), Some("v4.16.0"));
+
+        let missing = ProofCache::load(dir.path().join("missing.json"), "v4.15.0").unwrap();
+        assert!(missing.is_empty());
+    }
+}
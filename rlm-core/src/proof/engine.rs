@@ -8,10 +8,11 @@
 //! 3. AI-assisted tactics (LLM-generated)
 //! 4. Human loop fallback (`sorry` marker for manual completion)
 
-use crate::error::Result;
-use crate::lean::repl::LeanRepl;
-use crate::lean::types::Goal;
+use crate::error::{Error, Result};
+use crate::lean::repl::{LeanRepl, LeanReplPool, PooledLeanRepl};
+use crate::lean::types::{Goal, LeanResponse};
 use crate::memory::{Node, NodeType, SqliteMemoryStore, Tier};
+use crate::proof::cache::ProofCache;
 use crate::proof::tactics::{
     domain_specific_tactics, sorry_placeholder, tactic_variations, tactics_for_goal,
     tactics_for_tier,
@@ -20,7 +21,113 @@ use crate::proof::types::{
     AutomationTier, ProofAttempt, ProofContext, ProofStats, ProofStrategy, SpecDomain, TacticResult,
 };
 use std::collections::HashMap;
-use std::time::Instant;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// REPL operations used by [`ProofAutomation`].
+///
+/// Implemented by [`LeanRepl`]; abstracted so the engine can be driven by
+/// other backends and exercised without a Lean installation.
+pub trait TacticRepl {
+    /// Proof state that tactics should be applied to.
+    fn active_proof_state_id(&self) -> Option<u64>;
+
+    /// Apply `tactic` to `proof_state`, failing with `Error::Timeout` after
+    /// `timeout`.
+    fn apply_tactic_with_timeout(
+        &mut self,
+        tactic: &str,
+        proof_state: u64,
+        timeout: Duration,
+    ) -> Result<LeanResponse>;
+
+    /// Return to `proof_state` after a tactic applied to it timed out.
+    ///
+    /// A timed-out tactic may still be running, so implementations must
+    /// make the REPL usable again (e.g. by restarting it) rather than only
+    /// rewinding bookkeeping.
+    fn restore_proof_state(&mut self, proof_state: u64);
+}
+
+impl TacticRepl for LeanRepl {
+    fn active_proof_state_id(&self) -> Option<u64> {
+        LeanRepl::active_proof_state_id(self)
+    }
+
+    fn apply_tactic_with_timeout(
+        &mut self,
+        tactic: &str,
+        proof_state: u64,
+        timeout: Duration,
+    ) -> Result<LeanResponse> {
+        LeanRepl::apply_tactic_with_timeout(self, tactic, proof_state, timeout)
+    }
+
+    fn restore_proof_state(&mut self, proof_state: u64) {
+        LeanRepl::restore_proof_state(self, proof_state)
+    }
+}
+
+/// REPL checked out of a [`TacticReplPool`] with a goal opened on it.
+///
+/// Dropping it returns the REPL to its pool.
+pub trait PooledTacticRepl {
+    /// Apply `tactic` to the opened goal, failing with `Error::Timeout`
+    /// after `timeout`.
+    fn apply_tactic(&mut self, tactic: &str, timeout: Duration) -> Result<LeanResponse>;
+}
+
+/// Pool of independent REPLs used to try tactics concurrently.
+///
+/// Proof state IDs are local to a REPL process, so each attempt opens the
+/// goal on the REPL it checks out. Implementations must reset the REPL
+/// before returning it to the pool so an abandoned attempt cannot leak
+/// state into the next one.
+pub trait TacticReplPool: Send + Sync {
+    /// Check out a REPL and open `goal` on it.
+    fn checkout(&self, goal: &Goal) -> Result<Box<dyn PooledTacticRepl + '_>>;
+}
+
+impl TacticReplPool for LeanReplPool {
+    fn checkout(&self, goal: &Goal) -> Result<Box<dyn PooledTacticRepl + '_>> {
+        // On error the checkout drops here, returning the REPL to the pool.
+        let mut repl = self.acquire()?;
+        let proof_state = repl
+            .start_proof(&goal_statement(goal))?
+            .proof_state_id
+            .ok_or_else(|| {
+                Error::repl_execution(format!("No proof state for goal `{}`", goal.target))
+            })?;
+        Ok(Box::new(LeanReplCheckout { repl, proof_state }))
+    }
+}
+
+/// [`LeanRepl`] checked out of a [`LeanReplPool`] with a goal opened on it.
+///
+/// Dropping the inner [`PooledLeanRepl`] resets the REPL's environment and
+/// proof states and returns it to the pool.
+struct LeanReplCheckout<'a> {
+    repl: PooledLeanRepl<'a>,
+    proof_state: u64,
+}
+
+impl PooledTacticRepl for LeanReplCheckout<'_> {
+    fn apply_tactic(&mut self, tactic: &str, timeout: Duration) -> Result<LeanResponse> {
+        self.repl
+            .apply_tactic_with_timeout(tactic, self.proof_state, timeout)
+    }
+}
+
+/// Lean statement (without proof) that opens `goal`.
+fn goal_statement(goal: &Goal) -> String {
+    let binders: String = goal
+        .hypotheses
+        .iter()
+        .map(|h| format!(" ({} : {})", h.name, h.ty))
+        .collect();
+    format!("example{} : {}", binders, goal.target)
+}
 
 /// Configuration for the proof automation engine.
 #[derive(Debug, Clone)]
@@ -45,6 +152,25 @@ pub struct ProofAutomationConfig {
 
     /// Whether to try tactic variations.
     pub try_variations: bool,
+
+    /// Maximum automation-tier tactics to run at once when a REPL pool is
+    /// attached. `1` keeps the search sequential.
+    pub max_parallel_tactics: usize,
+
+    /// Whether to replay cached proofs before searching.
+    pub use_cache: bool,
+
+    /// Maximum time a single tactic may run before it is abandoned.
+    ///
+    /// Each tactic also gets no more than what is left of its tier's budget.
+    pub per_tactic_timeout: Duration,
+
+    /// Modules imported once for every goal in [`ProofAutomation::prove_batch`].
+    pub batch_imports: Vec<String>,
+
+    /// Commands (e.g. `open` or shared lemmas) elaborated after the imports
+    /// and visible to every goal in a batch.
+    pub batch_preamble: Vec<String>,
 }
 
 impl Default for ProofAutomationConfig {
@@ -57,6 +183,11 @@ impl Default for ProofAutomationConfig {
             enable_ai: true,
             enable_learning: true,
             try_variations: true,
+            use_cache: true,
+            max_parallel_tactics: 1,
+            per_tactic_timeout: Duration::from_secs(10),
+            batch_imports: Vec::new(),
+            batch_preamble: Vec::new(),
         }
     }
 }
@@ -77,6 +208,12 @@ pub struct ProofAutomation {
 
     /// Memory store for persisting learned strategies.
     memory: Option<SqliteMemoryStore>,
+
+    /// Proofs found in earlier runs, replayed before searching.
+    cache: ProofCache,
+
+    /// REPL pool for concurrent tactic search.
+    pool: Option<Arc<dyn TacticReplPool>>,
 }
 
 impl ProofAutomation {
@@ -89,6 +226,8 @@ impl ProofAutomation {
             strategies,
             stats: ProofStats::default(),
             memory: None,
+            cache: ProofCache::default(),
+            pool: None,
         }
     }
 
@@ -101,6 +240,8 @@ impl ProofAutomation {
             strategies,
             stats: ProofStats::default(),
             memory: Some(memory),
+            cache: ProofCache::default(),
+            pool: None,
         }
     }
 
@@ -132,10 +273,26 @@ impl ProofAutomation {
     }
 
     /// Try to prove a goal using the tiered approach.
-    pub fn prove(&mut self, repl: &mut LeanRepl, goal: &Goal) -> Result<ProofAttempt> {
+    ///
+    /// A cached proof for the goal is replayed first; if it no longer closes
+    /// the goal the entry is dropped and the full search runs.
+    #[tracing::instrument(name = "proof.prove", skip_all, fields(goal = %goal.target))]
+    pub fn prove(&mut self, repl: &mut dyn TacticRepl, goal: &Goal) -> Result<ProofAttempt> {
         let mut attempt = ProofAttempt::new(goal.clone());
         let domain = attempt.domain;
 
+        if self.config.use_cache {
+            if let Some(cached) = self.cache.get(goal).cloned() {
+                if self.replay_cached(repl, goal, &cached.tactics, &mut attempt) {
+                    attempt.mark_success(cached.tier);
+                    self.cache.record_hit(goal);
+                    self.stats.record(&attempt);
+                    return Ok(attempt);
+                }
+                self.cache.remove(goal);
+            }
+        }
+
         // Tier 1: Decidable tactics
         if let Some(result) = self.try_decidable(repl, goal, &mut attempt)? {
             if result.is_complete() {
@@ -177,14 +334,168 @@ impl ProofAutomation {
         Ok(attempt)
     }
 
+    /// Prove several goals in one REPL session, importing only once.
+    ///
+    /// The configured `batch_imports` and `batch_preamble` are elaborated
+    /// into a base environment, and each goal is opened against that
+    /// snapshot. The REPL is rolled back to it before every goal, so
+    /// whatever one goal adds to the environment is not seen by the next;
+    /// declarations goals should share belong in the preamble.
+    ///
+    /// A goal whose statement fails to elaborate, or whose proof search
+    /// errors, gets a failed attempt with its [`error`](ProofAttempt::error)
+    /// set, and the batch moves on. Only a failure to set up the base environment is returned.
+    #[tracing::instrument(name = "proof.prove_batch", skip_all, fields(goals = goals.len()))]
+    pub fn prove_batch(
+        &mut self,
+        repl: &mut LeanRepl,
+        goals: &[Goal],
+    ) -> Result<Vec<ProofAttempt>> {
+        let base_env = self.batch_environment(repl)?;
+
+        let mut attempts = Vec::with_capacity(goals.len());
+        for goal in goals {
+            repl.reset();
+            if let Some(env) = base_env {
+                repl.reset_to_env(env);
+            }
+            let statement = goal_statement(goal);
+            let attempt = match repl.start_proof(&statement) {
+                Ok(state) if state.proof_state_id.is_some() => self.prove(repl, goal),
+                Ok(_) => Err(Error::repl_execution(format!(
+                    "`{}` did not elaborate to a proof goal",
+                    statement
+                ))),
+                Err(e) => Err(e),
+            };
+            attempts.push(attempt.unwrap_or_else(|e| {
+                tracing::warn!(goal = %goal.target, error = %e, "batch goal failed");
+                let mut attempt = ProofAttempt::new(goal.clone());
+                attempt.mark_error(e.to_string());
+                self.stats.record(&attempt);
+                attempt
+            }));
+        }
+
+        repl.reset();
+        if let Some(env) = base_env {
+            repl.reset_to_env(env);
+        }
+        Ok(attempts)
+    }
+
+    /// Elaborate the batch imports and preamble, returning the resulting
+    /// environment (`None` if there is nothing to set up).
+    fn batch_environment(&self, repl: &mut LeanRepl) -> Result<Option<u64>> {
+        repl.reset();
+        let mut commands = Vec::new();
+        if !self.config.batch_imports.is_empty() {
+            commands.push(
+                self.config
+                    .batch_imports
+                    .iter()
+                    .map(|module| format!("import {}", module))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+        commands.extend(self.config.batch_preamble.iter().cloned());
+
+        for command in &commands {
+            let response = repl.execute_command(command)?;
+            if response.has_errors() {
+                return Err(Error::repl_execution(format!(
+                    "Batch setup failed: {}",
+                    response.format_errors()
+                )));
+            }
+        }
+        Ok(repl.current_env())
+    }
+
+    /// Replay a cached tactic sequence, returning whether it still closes the goal.
+    ///
+    /// On failure the REPL is returned to the goal's original proof state.
+    fn replay_cached(
+        &self,
+        repl: &mut dyn TacticRepl,
+        goal: &Goal,
+        tactics: &[String],
+        attempt: &mut ProofAttempt,
+    ) -> bool {
+        let Ok(initial_state) = Self::resolve_proof_state_id(repl.active_proof_state_id(), goal)
+        else {
+            return false;
+        };
+
+        let mut proof_state = initial_state;
+        let mut closed = false;
+        for (i, tactic) in tactics.iter().enumerate() {
+            let start = Instant::now();
+            let response =
+                repl.apply_tactic_with_timeout(tactic, proof_state, self.config.per_tactic_timeout);
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+
+            let resp = match response {
+                Ok(resp) if !resp.has_errors() => resp,
+                Ok(resp) => {
+                    attempt.record_tactic(TacticResult::failure(
+                        tactic.as_str(),
+                        resp.format_errors(),
+                        elapsed_ms,
+                    ));
+                    break;
+                }
+                Err(Error::Timeout { .. }) => {
+                    attempt.record_tactic(TacticResult::timed_out(tactic.as_str(), elapsed_ms));
+                    break;
+                }
+                Err(e) => {
+                    attempt.record_tactic(TacticResult::failure(
+                        tactic.as_str(),
+                        e.to_string(),
+                        elapsed_ms,
+                    ));
+                    break;
+                }
+            };
+
+            let new_goals: Vec<Goal> = resp
+                .goals
+                .map(|goals| goals.into_iter().map(Goal::from_string).collect())
+                .unwrap_or_default();
+            let result = TacticResult::success(tactic.as_str(), new_goals, elapsed_ms);
+            closed = result.is_complete();
+            attempt.record_tactic(result);
+
+            if i + 1 == tactics.len() {
+                break;
+            }
+            match resp.proof_state {
+                Some(next) if !closed => proof_state = next,
+                _ => {
+                    // Closed early or lost track of the state: the cached
+                    // sequence no longer matches this goal.
+                    closed = false;
+                    break;
+                }
+            }
+        }
+
+        if !closed {
+            repl.restore_proof_state(initial_state);
+        }
+        closed
+    }
+
     /// Try decidable tactics (Tier 1).
     fn try_decidable(
         &self,
-        repl: &mut LeanRepl,
+        repl: &mut dyn TacticRepl,
         goal: &Goal,
         attempt: &mut ProofAttempt,
     ) -> Result<Option<TacticResult>> {
-        let start = Instant::now();
+        let deadline = Instant::now() + Duration::from_millis(self.config.decidable_timeout_ms);
         let mut tactics = tactics_for_tier(AutomationTier::Decidable);
 
         // Add learned tactics from strategies
@@ -203,11 +514,11 @@ impl ProofAutomation {
 
         for tactic in tactics {
             // Check timeout
-            if start.elapsed().as_millis() as u64 > self.config.decidable_timeout_ms {
+            if Instant::now() >= deadline {
                 break;
             }
 
-            let result = self.try_single_tactic(repl, goal, tactic)?;
+            let result = self.try_single_tactic(repl, goal, tactic, deadline)?;
             attempt.record_tactic(result.clone());
 
             if result.is_complete() {
@@ -217,11 +528,11 @@ impl ProofAutomation {
             // Also try variations if enabled
             if self.config.try_variations {
                 for variant in tactic_variations(tactic, goal) {
-                    if start.elapsed().as_millis() as u64 > self.config.decidable_timeout_ms {
+                    if Instant::now() >= deadline {
                         break;
                     }
 
-                    let result = self.try_single_tactic(repl, goal, &variant)?;
+                    let result = self.try_single_tactic(repl, goal, &variant, deadline)?;
                     attempt.record_tactic(result.clone());
 
                     if result.is_complete() {
@@ -237,11 +548,11 @@ impl ProofAutomation {
     /// Try automation tactics (Tier 2).
     fn try_automation(
         &self,
-        repl: &mut LeanRepl,
+        repl: &mut dyn TacticRepl,
         goal: &Goal,
         attempt: &mut ProofAttempt,
     ) -> Result<Option<TacticResult>> {
-        let start = Instant::now();
+        let deadline = Instant::now() + Duration::from_millis(self.config.automation_timeout_ms);
         let mut tactics = tactics_for_tier(AutomationTier::Automation);
 
         // Add goal-specific tactics
@@ -254,13 +565,26 @@ impl ProofAutomation {
         // Limit tactics
         tactics.truncate(self.config.max_tactics_per_tier);
 
+        if let Some(pool) = &self.pool {
+            if self.config.max_parallel_tactics > 1 {
+                let mut candidates = Vec::new();
+                for tactic in tactics {
+                    candidates.push(tactic.to_string());
+                    if self.config.try_variations {
+                        candidates.extend(tactic_variations(tactic, goal));
+                    }
+                }
+                return Ok(self.try_parallel(pool, goal, candidates, deadline, attempt));
+            }
+        }
+
         for tactic in tactics {
             // Check timeout
-            if start.elapsed().as_millis() as u64 > self.config.automation_timeout_ms {
+            if Instant::now() >= deadline {
                 break;
             }
 
-            let result = self.try_single_tactic(repl, goal, tactic)?;
+            let result = self.try_single_tactic(repl, goal, tactic, deadline)?;
             attempt.record_tactic(result.clone());
 
             if result.is_complete() {
@@ -270,11 +594,11 @@ impl ProofAutomation {
             // Try variations
             if self.config.try_variations {
                 for variant in tactic_variations(tactic, goal) {
-                    if start.elapsed().as_millis() as u64 > self.config.automation_timeout_ms {
+                    if Instant::now() >= deadline {
                         break;
                     }
 
-                    let result = self.try_single_tactic(repl, goal, &variant)?;
+                    let result = self.try_single_tactic(repl, goal, &variant, deadline)?;
                     attempt.record_tactic(result.clone());
 
                     if result.is_complete() {
@@ -287,6 +611,108 @@ impl ProofAutomation {
         Ok(None)
     }
 
+    /// Run `candidates` concurrently on pooled REPLs (automation tier).
+    ///
+    /// Any candidate that closes the goal may win, but a winner is only
+    /// accepted once every higher-priority (earlier) candidate has failed,
+    /// so the outcome does not depend on scheduling. As soon as a candidate
+    /// closes the goal, workers stop picking up lower-priority candidates;
+    /// once the winner is settled the rest are cancelled and the call
+    /// returns without waiting for them. In-flight tactics finish in the
+    /// background and their REPLs are reset on release.
+    fn try_parallel(
+        &self,
+        pool: &Arc<dyn TacticReplPool>,
+        goal: &Goal,
+        candidates: Vec<String>,
+        deadline: Instant,
+        attempt: &mut ProofAttempt,
+    ) -> Option<TacticResult> {
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let candidates = Arc::new(candidates);
+        let next = Arc::new(AtomicUsize::new(0));
+        // Candidates at or after this index are not worth running.
+        let stop_at = Arc::new(AtomicUsize::new(candidates.len()));
+        let (tx, rx) = mpsc::channel();
+        let workers = self.config.max_parallel_tactics.min(candidates.len());
+        let parent = tracing::Span::current();
+
+        for _ in 0..workers {
+            let pool = Arc::clone(pool);
+            let candidates = Arc::clone(&candidates);
+            let next = Arc::clone(&next);
+            let stop_at = Arc::clone(&stop_at);
+            let tx = tx.clone();
+            let goal = goal.clone();
+            let per_tactic_timeout = self.config.per_tactic_timeout;
+            let parent = parent.clone();
+
+            std::thread::spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= stop_at.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Some(tactic) = candidates.get(index) else {
+                    break;
+                };
+
+                let span = tactic_span(&parent, tactic);
+                let _entered = span.enter();
+                let start = Instant::now();
+                let response = match pool.checkout(&goal) {
+                    Ok(mut repl) => {
+                        // Checking out may block; re-check before spending
+                        // the REPL on a candidate that can no longer win.
+                        if index >= stop_at.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        let timeout = per_tactic_timeout
+                            .min(deadline.saturating_duration_since(Instant::now()));
+                        repl.apply_tactic(tactic, timeout)
+                    }
+                    Err(e) => Err(e),
+                };
+                let result = tactic_result(tactic, response, start.elapsed().as_millis() as u64);
+                record_tactic(&span, &result);
+                if tx.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut slots: Vec<Option<TacticResult>> = vec![None; candidates.len()];
+        let mut settled = 0;
+        let mut winner = None;
+        while winner.is_none() && settled < candidates.len() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Ok((index, result)) = rx.recv_timeout(remaining) else {
+                break;
+            };
+            if result.is_complete() {
+                stop_at.fetch_min(index + 1, Ordering::SeqCst);
+            }
+            slots[index] = Some(result);
+
+            while let Some(Some(result)) = slots.get(settled) {
+                if result.is_complete() {
+                    winner = Some(settled);
+                    break;
+                }
+                settled += 1;
+            }
+        }
+        stop_at.store(0, Ordering::SeqCst);
+
+        for result in slots.iter().flatten() {
+            attempt.record_tactic(result.clone());
+        }
+        winner.and_then(|index| slots[index].take())
+    }
+
     /// Try AI-assisted tactics (Tier 3).
     ///
     /// This tier synthesizes a broader candidate pool from domain tactics,
@@ -294,20 +720,20 @@ impl ProofAutomation {
     /// with the same Lean feedback loop used by other tiers.
     fn try_ai_assisted(
         &self,
-        repl: &mut LeanRepl,
+        repl: &mut dyn TacticRepl,
         goal: &Goal,
         attempt: &mut ProofAttempt,
     ) -> Result<Option<TacticResult>> {
-        let start = Instant::now();
+        let deadline = Instant::now() + Duration::from_millis(self.config.ai_timeout_ms);
         let candidates = self.build_ai_tactic_candidates(goal, attempt);
         let mut best_progress: Option<TacticResult> = None;
 
         for tactic in candidates {
-            if start.elapsed().as_millis() as u64 > self.config.ai_timeout_ms {
+            if Instant::now() >= deadline {
                 break;
             }
 
-            let result = self.try_single_tactic(repl, goal, &tactic)?;
+            let result = self.try_single_tactic(repl, goal, &tactic, deadline)?;
             attempt.record_tactic(result.clone());
 
             if result.is_complete() {
@@ -334,12 +760,18 @@ impl ProofAutomation {
     }
 
     /// Try a single tactic and return the result.
+    ///
+    /// The tactic may run for `per_tactic_timeout` or until the tier
+    /// `deadline`, whichever comes first.
     fn try_single_tactic(
         &self,
-        repl: &mut LeanRepl,
+        repl: &mut dyn TacticRepl,
         goal: &Goal,
         tactic: &str,
+        deadline: Instant,
     ) -> Result<TacticResult> {
+        let span = tactic_span(&tracing::Span::current(), tactic);
+        let _entered = span.enter();
         let start = Instant::now();
 
         let proof_state_id = match Self::resolve_proof_state_id(repl.active_proof_state_id(), goal)
@@ -352,26 +784,25 @@ impl ProofAutomation {
         };
 
         // Apply the tactic against the tracked proof state.
-        let response = repl.apply_tactic(tactic, proof_state_id);
+        let timeout = self
+            .config
+            .per_tactic_timeout
+            .min(deadline.saturating_duration_since(start));
+        let response = repl.apply_tactic_with_timeout(tactic, proof_state_id, timeout);
         let elapsed_ms = start.elapsed().as_millis() as u64;
 
-        match response {
-            Ok(resp) => {
-                if resp.has_errors() {
-                    let error = resp.format_errors();
-                    Ok(TacticResult::failure(tactic, error, elapsed_ms))
-                } else {
-                    // Parse the remaining goals
-                    let new_goals: Vec<Goal> = resp
-                        .goals
-                        .map(|goals| goals.into_iter().map(Goal::from_string).collect())
-                        .unwrap_or_default();
-
-                    Ok(TacticResult::success(tactic, new_goals, elapsed_ms))
-                }
-            }
-            Err(e) => Ok(TacticResult::failure(tactic, e.to_string(), elapsed_ms)),
+        if matches!(response, Err(Error::Timeout { .. })) {
+            repl.restore_proof_state(proof_state_id);
+            tracing::warn!(
+                tactic,
+                timeout_ms = timeout.as_millis() as u64,
+                "tactic timed out"
+            );
         }
+
+        let result = tactic_result(tactic, response, elapsed_ms);
+        record_tactic(&span, &result);
+        Ok(result)
     }
 
     fn resolve_proof_state_id(
@@ -388,6 +819,11 @@ impl ProofAutomation {
 
     /// Record a successful proof for learning.
     pub fn record_success(&mut self, goal: &Goal, tactic: &str, domain: SpecDomain) {
+        if self.config.use_cache {
+            self.cache
+                .insert(goal, vec![tactic.to_string()], tier_for_tactic(tactic));
+        }
+
         if !self.config.enable_learning {
             return;
         }
@@ -409,6 +845,28 @@ impl ProofAutomation {
         &self.stats
     }
 
+    /// Get the proof cache.
+    pub fn cache(&self) -> &ProofCache {
+        &self.cache
+    }
+
+    /// Get the proof cache mutably (e.g. to save it or change toolchain).
+    pub fn cache_mut(&mut self) -> &mut ProofCache {
+        &mut self.cache
+    }
+
+    /// Replace the proof cache, e.g. with one loaded from disk.
+    pub fn set_cache(&mut self, cache: ProofCache) {
+        self.cache = cache;
+    }
+
+    /// Attach a REPL pool for concurrent automation-tier search.
+    ///
+    /// Takes effect when `max_parallel_tactics` is greater than one.
+    pub fn set_repl_pool(&mut self, pool: Arc<dyn TacticReplPool>) {
+        self.pool = Some(pool);
+    }
+
     /// Get strategies for a domain.
     pub fn strategies_for_domain(&self, domain: SpecDomain) -> Option<&Vec<ProofStrategy>> {
         self.strategies.get(&domain)
@@ -542,10 +1000,59 @@ impl ProofAutomation {
     }
 }
 
+/// Span for one tactic attempt under `parent`.
+///
+/// The parent is explicit because parallel attempts run on worker threads
+/// that don't inherit the caller's current span.
+fn tactic_span(parent: &tracing::Span, tactic: &str) -> tracing::Span {
+    tracing::debug_span!(
+        parent: parent,
+        "proof.tactic",
+        tactic,
+        success = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+    )
+}
+
+fn record_tactic(span: &tracing::Span, result: &TacticResult) {
+    span.record("success", result.success);
+    span.record("duration_ms", result.elapsed_ms);
+}
+
+/// Convert a REPL response for `tactic` into a [`TacticResult`].
+fn tactic_result(tactic: &str, response: Result<LeanResponse>, elapsed_ms: u64) -> TacticResult {
+    match response {
+        Ok(resp) if resp.has_errors() => {
+            TacticResult::failure(tactic, resp.format_errors(), elapsed_ms)
+        }
+        Ok(resp) => {
+            // Parse the remaining goals
+            let new_goals: Vec<Goal> = resp
+                .goals
+                .map(|goals| goals.into_iter().map(Goal::from_string).collect())
+                .unwrap_or_default();
+
+            TacticResult::success(tactic, new_goals, elapsed_ms)
+        }
+        Err(Error::Timeout { .. }) => TacticResult::timed_out(tactic, elapsed_ms),
+        Err(e) => TacticResult::failure(tactic, e.to_string(), elapsed_ms),
+    }
+}
+
+/// Tier whose tactic list contains `tactic`; anything else came from the AI tier.
+fn tier_for_tactic(tactic: &str) -> AutomationTier {
+    [AutomationTier::Decidable, AutomationTier::Automation]
+        .into_iter()
+        .find(|tier| tactics_for_tier(*tier).contains(&tactic))
+        .unwrap_or(AutomationTier::AIAssisted)
+}
+
 /// Builder for ProofAutomation with fluent API.
 pub struct ProofAutomationBuilder {
     config: ProofAutomationConfig,
     memory: Option<SqliteMemoryStore>,
+    cache: Option<ProofCache>,
+    pool: Option<Arc<dyn TacticReplPool>>,
 }
 
 impl ProofAutomationBuilder {
@@ -554,6 +1061,8 @@ impl ProofAutomationBuilder {
         Self {
             config: ProofAutomationConfig::default(),
             memory: None,
+            cache: None,
+            pool: None,
         }
     }
 
@@ -575,6 +1084,42 @@ impl ProofAutomationBuilder {
         self
     }
 
+    /// Set the AI-assisted tier timeout.
+    pub fn ai_timeout(mut self, timeout_ms: u64) -> Self {
+        self.config.ai_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Enable or disable the proof cache.
+    pub fn use_cache(mut self, enable: bool) -> Self {
+        self.config.use_cache = enable;
+        self
+    }
+
+    /// Start from an existing proof cache.
+    pub fn cache(mut self, cache: ProofCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Set how many automation-tier tactics may run at once.
+    pub fn max_parallel_tactics(mut self, max: usize) -> Self {
+        self.config.max_parallel_tactics = max;
+        self
+    }
+
+    /// Attach a REPL pool for concurrent tactic search.
+    pub fn repl_pool(mut self, pool: Arc<dyn TacticReplPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Set the maximum time for a single tactic.
+    pub fn per_tactic_timeout(mut self, timeout: Duration) -> Self {
+        self.config.per_tactic_timeout = timeout;
+        self
+    }
+
     /// Enable or disable AI assistance.
     pub fn enable_ai(mut self, enable: bool) -> Self {
         self.config.enable_ai = enable;
@@ -593,6 +1138,18 @@ impl ProofAutomationBuilder {
         self
     }
 
+    /// Set the modules imported once per batch.
+    pub fn batch_imports(mut self, imports: Vec<String>) -> Self {
+        self.config.batch_imports = imports;
+        self
+    }
+
+    /// Set commands shared by every goal in a batch.
+    pub fn batch_preamble(mut self, preamble: Vec<String>) -> Self {
+        self.config.batch_preamble = preamble;
+        self
+    }
+
     /// Set the memory store for learning.
     pub fn with_memory(mut self, memory: SqliteMemoryStore) -> Self {
         self.memory = Some(memory);
@@ -601,10 +1158,17 @@ impl ProofAutomationBuilder {
 
     /// Build the proof automation engine.
     pub fn build(self) -> ProofAutomation {
-        match self.memory {
+        let mut automation = match self.memory {
             Some(memory) => ProofAutomation::with_memory(self.config, memory),
             None => ProofAutomation::new(self.config),
+        };
+        if let Some(cache) = self.cache {
+            automation.set_cache(cache);
+        }
+        if let Some(pool) = self.pool {
+            automation.set_repl_pool(pool);
         }
+        automation
     }
 }
 
@@ -627,6 +1191,305 @@ mod tests {
         assert!(config.enable_learning);
     }
 
+    /// REPL that hangs on every tactic except `rfl`.
+    ///
+    /// Like a real REPL, it stays busy with a hung tactic after the timeout,
+    /// so every later tactic also times out until the proof state is
+    /// restored.
+    struct HangingRepl {
+        proof_state: u64,
+        restored: Vec<u64>,
+        busy: bool,
+    }
+
+    impl HangingRepl {
+        fn new() -> Self {
+            Self {
+                proof_state: 0,
+                restored: Vec::new(),
+                busy: false,
+            }
+        }
+    }
+
+    impl TacticRepl for HangingRepl {
+        fn active_proof_state_id(&self) -> Option<u64> {
+            Some(self.proof_state)
+        }
+
+        fn apply_tactic_with_timeout(
+            &mut self,
+            tactic: &str,
+            _proof_state: u64,
+            timeout: Duration,
+        ) -> Result<LeanResponse> {
+            if tactic == "rfl" && !self.busy {
+                return Ok(serde_json::from_str(r#"{"goals":[],"proofState":1}"#).unwrap());
+            }
+            std::thread::sleep(timeout);
+            self.busy = true;
+            Err(Error::timeout(timeout.as_millis() as u64))
+        }
+
+        fn restore_proof_state(&mut self, proof_state: u64) {
+            self.restored.push(proof_state);
+            self.proof_state = proof_state;
+            self.busy = false;
+        }
+    }
+
+    #[test]
+    fn test_per_tactic_timeout_moves_to_next_tactic() {
+        let mut automation = ProofAutomationBuilder::new()
+            .per_tactic_timeout(Duration::from_millis(20))
+            .try_variations(false)
+            .enable_ai(false)
+            .build();
+        let mut repl = HangingRepl::new();
+        let goal = Goal::from_string("2 = 2");
+
+        let attempt = automation.prove(&mut repl, &goal).unwrap();
+
+        assert!(attempt.success);
+        assert_eq!(attempt.successful_tactics, vec!["rfl".to_string()]);
+        let timed_out: Vec<&str> = attempt
+            .tactics_tried
+            .iter()
+            .filter(|r| r.timed_out)
+            .map(|r| r.tactic.as_str())
+            .collect();
+        assert_eq!(timed_out, vec!["decide", "native_decide", "omega", "simp"]);
+        assert!(attempt
+            .tactics_tried
+            .iter()
+            .all(|r| r.timed_out || r.success));
+        assert_eq!(repl.restored, vec![0; 4]);
+    }
+
+    #[test]
+    fn test_tier_budget_caps_tactic_timeouts() {
+        let automation = ProofAutomationBuilder::new()
+            .per_tactic_timeout(Duration::from_secs(5))
+            .decidable_timeout(50)
+            .try_variations(false)
+            .build();
+        let mut repl = HangingRepl::new();
+        let goal = Goal::from_string("2 = 2");
+        let mut attempt = ProofAttempt::new(goal.clone());
+
+        let start = Instant::now();
+        let result = automation
+            .try_decidable(&mut repl, &goal, &mut attempt)
+            .unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(result.is_none());
+        assert!(!attempt.tactics_tried.is_empty());
+        assert!(attempt.tactics_tried.iter().all(|r| r.timed_out));
+    }
+
+    #[test]
+    fn test_cached_proof_is_replayed() {
+        let mut automation = ProofAutomationBuilder::new()
+            .per_tactic_timeout(Duration::from_millis(5))
+            .try_variations(false)
+            .enable_ai(false)
+            .build();
+        let mut repl = HangingRepl::new();
+        let goal = Goal::from_string("2 = 2");
+
+        let first = automation.prove(&mut repl, &goal).unwrap();
+        assert!(first.tactics_tried.len() > 1);
+        assert_eq!(automation.cache().len(), 1);
+
+        let second = automation.prove(&mut repl, &goal).unwrap();
+        assert!(second.success);
+        assert_eq!(second.tier, AutomationTier::Decidable);
+        assert_eq!(second.tactics_tried.len(), 1);
+        assert_eq!(automation.cache().get(&goal).unwrap().hits, 1);
+    }
+
+    #[test]
+    fn test_stale_cache_entry_falls_back_to_search() {
+        let mut cache = ProofCache::new("v4.15.0");
+        let goal = Goal::from_string("2 = 2");
+        cache.insert(&goal, vec!["omega".to_string()], AutomationTier::Decidable);
+
+        let mut automation = ProofAutomationBuilder::new()
+            .per_tactic_timeout(Duration::from_millis(5))
+            .try_variations(false)
+            .enable_ai(false)
+            .cache(cache)
+            .build();
+        let mut repl = HangingRepl::new();
+
+        let attempt = automation.prove(&mut repl, &goal).unwrap();
+
+        assert!(attempt.success);
+        assert!(attempt.tactics_tried[0].timed_out);
+        assert_eq!(repl.restored[0], 0);
+        assert_eq!(
+            automation.cache().get(&goal).unwrap().tactics,
+            vec!["rfl".to_string()]
+        );
+    }
+
+    /// Pool whose REPLs close the goal with `ring` (instantly) and
+    /// `linarith` (after a delay); every other tactic fails.
+    #[derive(Default)]
+    struct MockPool {
+        acquired: AtomicUsize,
+        released: AtomicUsize,
+    }
+
+    struct MockCheckout<'a> {
+        pool: &'a MockPool,
+    }
+
+    impl TacticReplPool for MockPool {
+        fn checkout(&self, _goal: &Goal) -> Result<Box<dyn PooledTacticRepl + '_>> {
+            self.acquired.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(MockCheckout { pool: self }))
+        }
+    }
+
+    impl PooledTacticRepl for MockCheckout<'_> {
+        fn apply_tactic(&mut self, tactic: &str, _timeout: Duration) -> Result<LeanResponse> {
+            let json = match tactic {
+                "ring" => r#"{"goals":[],"proofState":1}"#,
+                "linarith" => {
+                    std::thread::sleep(Duration::from_millis(50));
+                    r#"{"goals":[],"proofState":1}"#
+                }
+                _ => {
+                    std::thread::sleep(Duration::from_millis(10));
+                    r#"{"messages":[{"severity":"error","pos":{"line":1,"column":0},"data":"failed"}]}"#
+                }
+            };
+            Ok(serde_json::from_str(json).unwrap())
+        }
+    }
+
+    impl Drop for MockCheckout<'_> {
+        fn drop(&mut self) {
+            self.pool.released.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_parallel_search_prefers_higher_priority_winner() {
+        let pool = Arc::new(MockPool::default());
+        let automation = ProofAutomationBuilder::new()
+            .max_parallel_tactics(4)
+            .max_tactics_per_tier(20)
+            .try_variations(false)
+            .repl_pool(pool.clone())
+            .build();
+        let goal = Goal::from_string("a + b = b + a");
+        let mut attempt = ProofAttempt::new(goal.clone());
+        let mut repl = HangingRepl::new();
+
+        let result = automation
+            .try_automation(&mut repl, &goal, &mut attempt)
+            .unwrap()
+            .unwrap();
+
+        // `ring` finishes first, but `linarith` comes earlier in tier order.
+        assert_eq!(result.tactic, "linarith");
+        assert_eq!(attempt.tactics_tried[0].tactic, "aesop");
+        assert!(!attempt.tactics_tried[0].success);
+        assert!(attempt.tactics_tried.iter().any(|r| r.tactic == "ring"));
+        // The main REPL is not used in parallel mode.
+        assert!(repl.restored.is_empty());
+
+        // Remaining candidates are cancelled and every REPL that was
+        // checked out is returned to the pool.
+        let wait = Instant::now();
+        while pool.released.load(Ordering::SeqCst) < pool.acquired.load(Ordering::SeqCst)
+            && wait.elapsed() < Duration::from_secs(1)
+        {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let acquired = pool.acquired.load(Ordering::SeqCst);
+        assert!(acquired > 0 && acquired < 20);
+        assert_eq!(pool.released.load(Ordering::SeqCst), acquired);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prove_batch_shares_imports_and_isolates_goals() {
+        use crate::lean::testing::{fake_repl_config, logged_requests};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut repl = LeanRepl::spawn(fake_repl_config(&dir)).unwrap();
+        let mut automation = ProofAutomationBuilder::new()
+            .batch_imports(vec!["Mathlib".to_string()])
+            .batch_preamble(vec!["def helper := 0".to_string()])
+            .try_variations(false)
+            .enable_ai(false)
+            .build();
+        let goals = vec![Goal::from_string("1 + 1 = 2"), Goal::from_string("True")];
+
+        let attempts = automation.prove_batch(&mut repl, &goals).unwrap();
+
+        assert_eq!(attempts.len(), 2);
+        assert!(attempts.iter().all(|a| a.success));
+        assert_eq!(attempts[0].successful_tactics, vec!["rfl".to_string()]);
+        assert_eq!(attempts[1].successful_tactics, vec!["decide".to_string()]);
+
+        // Imports are sent once, and both goals open on the preamble's
+        // environment rather than on whatever the previous goal produced.
+        let requests = logged_requests(&dir);
+        assert_eq!(requests.matches("import Mathlib").count(), 1);
+        let goal_envs: Vec<&str> = requests
+            .lines()
+            .filter(|line| line.contains("example"))
+            .map(|line| line.rsplit("\"env\":").next().unwrap())
+            .collect();
+        assert_eq!(goal_envs, vec!["3}", "3}"]);
+        assert_eq!(repl.current_env(), Some(3));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prove_batch_continues_past_ill_typed_goal() {
+        use crate::lean::testing::fake_repl_config;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut repl = LeanRepl::spawn(fake_repl_config(&dir)).unwrap();
+        let mut automation = ProofAutomationBuilder::new()
+            .try_variations(false)
+            .enable_ai(false)
+            .build();
+        let goals = vec![
+            Goal::from_string("1 + 1 = 2"),
+            Goal::from_string("ill_typed + True"),
+            Goal::from_string("True"),
+        ];
+
+        let attempts = automation.prove_batch(&mut repl, &goals).unwrap();
+
+        assert_eq!(attempts.len(), 3);
+        assert!(attempts[0].success);
+        assert!(!attempts[1].success);
+        assert_eq!(attempts[1].goal.target, "ill_typed + True");
+        assert!(attempts[1].tactics_tried.is_empty());
+        assert!(attempts[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("did not elaborate"));
+        assert!(attempts[0].error.is_none());
+        assert!(attempts[2].success);
+        assert_eq!(attempts[2].successful_tactics, vec!["decide".to_string()]);
+    }
+
+    #[test]
+    fn test_goal_statement() {
+        let goal = Goal::from_string("x + 0 = x").with_hypothesis("x", "Nat");
+        assert_eq!(goal_statement(&goal), "example (x : Nat) : x + 0 = x");
+    }
+
     #[test]
     fn test_automation_creation() {
         let automation = ProofAutomation::new(ProofAutomationConfig::default());
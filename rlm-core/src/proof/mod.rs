@@ -39,6 +39,7 @@
 //! - **Types** (`types.rs`): Core data structures for proof attempts and strategies
 //! - **Tactics** (`tactics.rs`): Tactic constants and selection functions
 //! - **Engine** (`engine.rs`): Main proof automation orchestration
+//! - **Cache** (`cache.rs`): Previously found proofs, replayed before searching
 //! - **AI Assistant** (`ai_assistant.rs`): LLM-powered tactic suggestion
 //!
 //! ## Domain-Specific Strategies
@@ -63,6 +64,7 @@
 //! ```
 
 pub mod ai_assistant;
+pub mod cache;
 pub mod engine;
 pub mod session;
 pub mod tactics;
@@ -70,7 +72,11 @@ pub mod types;
 
 // Re-export main types
 pub use ai_assistant::{AIAssistantConfig, AIProofAssistant};
-pub use engine::{ProofAutomation, ProofAutomationBuilder, ProofAutomationConfig};
+pub use cache::{CachedProof, ProofCache};
+pub use engine::{
+    PooledTacticRepl, ProofAutomation, ProofAutomationBuilder, ProofAutomationConfig, TacticRepl,
+    TacticReplPool,
+};
 pub use session::{
     select_target, HelperLemma, HelperProofStatus, LimitReason, ProofSession, ProofSessionStatus,
     ProtocolConfig, ProtocolEnforcer, ProtocolError, SorryLocation, TacticAttempt, TacticOutcome,
@@ -281,6 +281,19 @@ pub enum TacticOutcome {
     Rejected { reason: String },
 }
 
+impl std::fmt::Display for TacticOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Complete => write!(f, "complete"),
+            Self::Progress { remaining_goals } => {
+                write!(f, "progress ({} goals remaining)", remaining_goals)
+            }
+            Self::Failed { error } => write!(f, "failed: {}", error),
+            Self::Rejected { reason } => write!(f, "rejected: {}", reason),
+        }
+    }
+}
+
 impl TacticOutcome {
     /// Check if this outcome represents success.
     pub fn is_success(&self) -> bool {
@@ -359,6 +372,9 @@ pub struct ProofSession {
     pub started_at: u64,
     /// Session end time (as unix timestamp ms).
     pub ended_at: Option<u64>,
+    /// Sorries left in the file when the session ended.
+    #[serde(default)]
+    pub remaining_sorries: Vec<SorryLocation>,
 }
 
 impl ProofSession {
@@ -380,6 +396,7 @@ impl ProofSession {
             tactic_limit: None,
             started_at: now,
             ended_at: None,
+            remaining_sorries: Vec::new(),
         }
     }
 
@@ -436,6 +453,11 @@ impl ProofSession {
         self.helpers.push(helper);
     }
 
+    /// Record the sorries still present in the file.
+    pub fn set_remaining_sorries(&mut self, sorries: Vec<SorryLocation>) {
+        self.remaining_sorries = sorries;
+    }
+
     /// Mark the target as complete.
     pub fn mark_target_complete(&mut self) {
         self.status = ProofSessionStatus::TargetComplete;
@@ -518,6 +540,122 @@ impl ProofSession {
             elapsed
         )
     }
+
+    /// Render the session as Markdown, e.g. to attach proof provenance to a PR.
+    ///
+    /// Includes the goal, the tactics that made progress, every attempt with
+    /// its outcome and timing, helper lemmas, and a TODO checklist of
+    /// remaining sorries. Sessions that did not complete are flagged at the top.
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# Proof: `{}`\n\n", self.target.format_location());
+
+        if !self.status.is_success() {
+            md.push_str(&format!(
+                "> **Warning:** proof is not complete (status: `{}`).\n\n",
+                self.status
+            ));
+        }
+        md.push_str(&format!("- **Status:** `{}`\n", self.status));
+        md.push_str(&format!(
+            "- **Tactics:** {} tried, {} succeeded\n",
+            self.tactic_history.len(),
+            self.successful_tactics()
+        ));
+        if self.tokens_used > 0 {
+            md.push_str(&format!("- **Tokens:** {}\n", self.tokens_used));
+        }
+        md.push_str(&format!("- **Elapsed:** {}ms\n\n", self.elapsed_ms()));
+
+        md.push_str("## Goal\n\n");
+        if !self.target.context.is_empty() {
+            md.push_str(&format!("```lean\n{}\n```\n\n", self.target.context));
+        }
+        if let Some(goal) = &self.target.goal {
+            md.push_str(&format!("```\n⊢ {}\n```\n\n", goal));
+        }
+
+        md.push_str("## Tactic Sequence\n\n");
+        let steps: Vec<&str> = self
+            .tactic_history
+            .iter()
+            .filter(|t| t.outcome.is_success())
+            .map(|t| t.tactic.as_str())
+            .collect();
+        if steps.is_empty() {
+            md.push_str("_No tactic made progress._\n\n");
+        } else {
+            md.push_str(&format!("```lean\n{}\n```\n\n", steps.join("\n")));
+        }
+
+        if !self.tactic_history.is_empty() {
+            md.push_str("### Attempts\n\n");
+            md.push_str("| # | Tactic | Outcome | Time |\n|---|--------|---------|------|\n");
+            for (i, attempt) in self.tactic_history.iter().enumerate() {
+                let time = if attempt.elapsed_ms > 0 {
+                    format!("{}ms", attempt.elapsed_ms)
+                } else {
+                    "-".to_string()
+                };
+                md.push_str(&format!(
+                    "| {} | `{}` | {} | {} |\n",
+                    i + 1,
+                    escape_table_cell(&attempt.tactic),
+                    escape_table_cell(&attempt.outcome.to_string()),
+                    time
+                ));
+            }
+            md.push('\n');
+        }
+
+        if !self.helpers.is_empty() {
+            md.push_str("## Helper Lemmas\n\n");
+            for helper in &self.helpers {
+                md.push_str(&format!(
+                    "### `{}` ({})\n\n```lean\n{}\n```\n\n",
+                    helper.name,
+                    helper.proof_status,
+                    helper.to_lean_declaration()
+                ));
+            }
+        }
+
+        let mut todos: Vec<String> = Vec::new();
+        if !self.status.is_success() {
+            todos.push(format!(
+                "`{}` (session target)",
+                self.target.format_location()
+            ));
+        }
+        for helper in &self.helpers {
+            if helper.proof_status != HelperProofStatus::Proven {
+                todos.push(format!(
+                    "helper `{}` ({})",
+                    helper.name, helper.proof_status
+                ));
+            }
+        }
+        for sorry in &self.remaining_sorries {
+            if sorry.matches(&self.target) {
+                continue;
+            }
+            match &sorry.goal {
+                Some(goal) => todos.push(format!("`{}`: `{}`", sorry.format_location(), goal)),
+                None => todos.push(format!("`{}`", sorry.format_location())),
+            }
+        }
+        if !todos.is_empty() {
+            md.push_str("## Remaining Sorries\n\n");
+            for todo in todos {
+                md.push_str(&format!("- [ ] {}\n", todo));
+            }
+        }
+
+        md
+    }
+}
+
+fn escape_table_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
 }
 
 /// Protocol enforcement errors.
@@ -930,6 +1068,61 @@ mod tests {
         assert!(decl.contains("induction n"));
     }
 
+    #[test]
+    fn test_to_markdown_renders_helpers_and_sorries() {
+        let target = SorryLocation::new("Foo.lean", 10, 0)
+            .with_context("theorem foo (n : Nat) : n + 0 = n := by")
+            .with_goal("n + 0 = n");
+        let mut session = ProofSession::new(target.clone());
+        session.record_tactic(TacticAttempt::new(
+            "simp | omega",
+            TacticOutcome::Failed {
+                error: "no progress".into(),
+            },
+            20,
+        ));
+        session.record_tactic(TacticAttempt::new(
+            "exact nat_add_zero n",
+            TacticOutcome::Complete,
+            0,
+        ));
+        session.add_helper(
+            HelperLemma::new("nat_add_zero", "∀ n : Nat, n + 0 = n").with_attribution_for("foo"),
+        );
+        session.set_remaining_sorries(vec![
+            target,
+            SorryLocation::new("Foo.lean", 20, 2).with_goal("m * 1 = m"),
+        ]);
+        session.mark_target_complete();
+
+        let md = session.to_markdown();
+
+        assert!(md.starts_with("# Proof: `Foo.lean:10:0`"));
+        assert!(!md.contains("**Warning:**"));
+        assert!(md.contains("```lean\nexact nat_add_zero n\n```"));
+        assert!(md.contains("| 1 | `simp \\| omega` | failed: no progress | 20ms |"));
+        assert!(md.contains("| 2 | `exact nat_add_zero n` | complete | - |"));
+        assert!(md.contains("## Helper Lemmas"));
+        assert!(md.contains("### `nat_add_zero` (in_progress)"));
+        assert!(md.contains("lemma nat_add_zero : ∀ n : Nat, n + 0 = n := by"));
+        assert!(md.contains("## Remaining Sorries"));
+        assert!(md.contains("- [ ] helper `nat_add_zero` (in_progress)"));
+        assert!(md.contains("- [ ] `Foo.lean:20:2`: `m * 1 = m`"));
+        assert!(!md.contains("(session target)"));
+    }
+
+    #[test]
+    fn test_to_markdown_flags_incomplete_session() {
+        let mut session = ProofSession::new(SorryLocation::new("Foo.lean", 10, 0));
+        session.abandon("stuck");
+
+        let md = session.to_markdown();
+
+        assert!(md.contains("> **Warning:** proof is not complete (status: `abandoned:stuck`)."));
+        assert!(md.contains("_No tactic made progress._"));
+        assert!(md.contains("- [ ] `Foo.lean:10:0` (session target)"));
+    }
+
     #[test]
     fn test_proof_session() {
         let target = SorryLocation::new("Foo.lean", 10, 0);
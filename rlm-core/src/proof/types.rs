@@ -219,6 +219,10 @@ pub struct TacticResult {
 
     /// Time taken to execute the tactic in milliseconds.
     pub elapsed_ms: u64,
+
+    /// Whether the tactic was abandoned for exceeding its time limit.
+    #[serde(default)]
+    pub timed_out: bool,
 }
 
 impl TacticResult {
@@ -230,6 +234,7 @@ impl TacticResult {
             new_goals,
             error: None,
             elapsed_ms,
+            timed_out: false,
         }
     }
 
@@ -241,6 +246,19 @@ impl TacticResult {
             new_goals: Vec::new(),
             error: Some(error.into()),
             elapsed_ms,
+            timed_out: false,
+        }
+    }
+
+    /// Create a result for a tactic that exceeded its time limit.
+    pub fn timed_out(tactic: impl Into<String>, elapsed_ms: u64) -> Self {
+        Self {
+            timed_out: true,
+            ..Self::failure(
+                tactic,
+                format!("timed out after {}ms", elapsed_ms),
+                elapsed_ms,
+            )
         }
     }
 
@@ -262,6 +280,9 @@ pub struct ProofAttempt {
     pub goal: Goal,
 
     /// The tier that ultimately succeeded (or the highest tier tried).
+    ///
+    /// Meaningless for an attempt with an [`error`](Self::error), which
+    /// never reached any tier.
     pub tier: AutomationTier,
 
     /// All tactics tried during the attempt.
@@ -278,6 +299,10 @@ pub struct ProofAttempt {
 
     /// Inferred domain of the goal.
     pub domain: SpecDomain,
+
+    /// Why proof search could not run on the goal, if it could not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 impl ProofAttempt {
@@ -292,6 +317,7 @@ impl ProofAttempt {
             successful_tactics: Vec::new(),
             total_elapsed_ms: 0,
             domain,
+            error: None,
         }
     }
 
@@ -315,6 +341,12 @@ impl ProofAttempt {
         self.tier = tier;
     }
 
+    /// Mark the proof as failed before any tier ran.
+    pub fn mark_error(&mut self, error: impl Into<String>) {
+        self.success = false;
+        self.error = Some(error.into());
+    }
+
     /// Get the number of goals remaining.
     pub fn remaining_goals(&self) -> usize {
         self.tactics_tried
@@ -325,6 +357,9 @@ impl ProofAttempt {
 
     /// Generate a summary of the proof attempt.
     pub fn summary(&self) -> String {
+        if let Some(error) = &self.error {
+            return format!("[error] FAILED: {} (domain: {})", error, self.domain);
+        }
         let status = if self.success { "SUCCESS" } else { "FAILED" };
         let tactics = self.tactics_tried.len();
         let successful = self.successful_tactics.len();
@@ -476,6 +511,11 @@ impl ProofStats {
             self.successful_proofs += 1;
         }
 
+        // Errored attempts never reached a tier
+        if attempt.error.is_some() {
+            return;
+        }
+
         // Update tier stats
         let tier_stats = self.by_tier.entry(attempt.tier).or_default();
         tier_stats.attempts += 1;
@@ -689,4 +729,18 @@ mod tests {
         assert_eq!(stats.successful_proofs, 1);
         assert_eq!(stats.success_rate(), 1.0);
     }
+
+    #[test]
+    fn test_errored_attempt_is_not_attributed_to_a_tier() {
+        let mut stats = ProofStats::default();
+        let mut attempt = ProofAttempt::new(Goal::from_string("x + 0 = x"));
+        attempt.mark_error("did not elaborate");
+
+        stats.record(&attempt);
+
+        assert_eq!(stats.total_attempts, 1);
+        assert!(stats.by_tier.is_empty());
+        assert!(stats.by_domain.is_empty());
+        assert!(attempt.summary().contains("did not elaborate"));
+    }
 }
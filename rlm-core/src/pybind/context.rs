@@ -4,6 +4,7 @@ use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
 use crate::context::{Message, Role, SessionContext, ToolOutput};
+use crate::repl::floor_char_boundary;
 
 /// Python enum for Role.
 #[pyclass(name = "Role", eq, eq_int)]
@@ -324,12 +325,12 @@ fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len])
+        format!("{}...", &s[..floor_char_boundary(s, max_len)])
     }
 }
 
 /// Convert a Python value to serde_json::Value.
-fn python_to_json(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+pub(crate) fn python_to_json(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
     if value.is_none() {
         Ok(serde_json::Value::Null)
     } else if let Ok(b) = value.extract::<bool>() {
@@ -361,7 +362,7 @@ fn python_to_json(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
 }
 
 /// Convert a serde_json::Value to Python.
-fn json_to_python(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+pub(crate) fn json_to_python(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
     use pyo3::IntoPyObject;
     match value {
         serde_json::Value::Null => Ok(py.None()),
@@ -7,7 +7,7 @@ use pyo3::prelude::*;
 use crate::epistemic::{
     self, BudgetResult, Claim, ClaimCategory, ClaimExtractor, ClaimId, EvidenceContribution,
     EvidenceEffect, EvidenceRef, EvidenceType, GroundingStatus, Probability, VerificationConfig,
-    VerificationResult, VerificationStats, VerificationVerdict,
+    VerificationResult, VerificationStats, VerificationStrategy, VerificationVerdict,
 };
 
 /// Python wrapper for ClaimCategory enum.
@@ -452,6 +452,42 @@ impl PyVerificationConfig {
     fn set_max_latency_ms(&mut self, value: u64) {
         self.inner.max_latency_ms = value;
     }
+
+    /// Maximum claims verified concurrently in batch mode.
+    #[getter]
+    fn max_parallel(&self) -> usize {
+        self.inner.max_parallel
+    }
+
+    #[setter]
+    fn set_max_parallel(&mut self, value: usize) {
+        self.inner.max_parallel = value.max(1);
+    }
+
+    /// Probability estimation strategy: "sampling" or "logprob".
+    #[getter]
+    fn strategy(&self) -> String {
+        match self.inner.strategy {
+            VerificationStrategy::Sampling => "sampling",
+            VerificationStrategy::Logprob => "logprob",
+        }
+        .to_string()
+    }
+
+    #[setter]
+    fn set_strategy(&mut self, value: &str) -> PyResult<()> {
+        self.inner.strategy = match value {
+            "sampling" => VerificationStrategy::Sampling,
+            "logprob" => VerificationStrategy::Logprob,
+            other => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown verification strategy: {}",
+                    other
+                )))
+            }
+        };
+        Ok(())
+    }
 }
 
 /// Python wrapper for VerificationStats.
@@ -498,6 +534,12 @@ impl PyVerificationStats {
         self.inner.max_budget_gap
     }
 
+    /// Claims whose verification errored.
+    #[getter]
+    fn failed_claims(&self) -> u32 {
+        self.inner.failed_claims
+    }
+
     /// Calculate hallucination rate (ungrounded / total).
     fn hallucination_rate(&self) -> f64 {
         self.inner.hallucination_rate()
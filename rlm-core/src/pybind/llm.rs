@@ -8,6 +8,7 @@ use crate::llm::{
     ModelTier, Provider, QueryType, RoutingContext, RoutingDecision, SmartRouter, StopReason,
     TokenUsage,
 };
+use crate::repl::floor_char_boundary;
 
 /// Python enum for Provider.
 #[pyclass(name = "Provider", eq, eq_int)]
@@ -18,6 +19,7 @@ pub enum PyProvider {
     OpenRouter = 2,
     #[cfg(feature = "gemini")]
     Google = 3,
+    Ollama = 4,
 }
 
 impl From<Provider> for PyProvider {
@@ -28,6 +30,7 @@ impl From<Provider> for PyProvider {
             Provider::OpenRouter => PyProvider::OpenRouter,
             #[cfg(feature = "gemini")]
             Provider::Google => PyProvider::Google,
+            Provider::Ollama => PyProvider::Ollama,
             // Handle unknown variants for forward compatibility
             #[allow(unreachable_patterns)]
             _ => PyProvider::OpenRouter, // Fallback for unknown providers
@@ -43,6 +46,7 @@ impl From<PyProvider> for Provider {
             PyProvider::OpenRouter => Provider::OpenRouter,
             #[cfg(feature = "gemini")]
             PyProvider::Google => Provider::Google,
+            PyProvider::Ollama => Provider::Ollama,
         }
     }
 }
@@ -56,6 +60,7 @@ impl PyProvider {
             PyProvider::OpenRouter => "Provider.OpenRouter",
             #[cfg(feature = "gemini")]
             PyProvider::Google => "Provider.Google",
+            PyProvider::Ollama => "Provider.Ollama",
         }
     }
 }
@@ -193,6 +198,8 @@ impl PyModelSpec {
                 supports_caching: false,
                 supports_vision: false,
                 supports_tools: false,
+                is_local: false,
+                embedding_cost_per_m: None,
             },
         }
     }
@@ -237,6 +244,14 @@ impl PyModelSpec {
         }
     }
 
+    /// Create spec for a model served by a local Ollama instance.
+    #[staticmethod]
+    fn ollama(id: String, tier: PyModelTier) -> Self {
+        Self {
+            inner: ModelSpec::ollama(id, tier.into()),
+        }
+    }
+
     #[getter]
     fn id(&self) -> String {
         self.inner.id.clone()
@@ -292,6 +307,11 @@ impl PyModelSpec {
         self.inner.supports_tools
     }
 
+    #[getter]
+    fn is_local(&self) -> bool {
+        self.inner.is_local
+    }
+
     /// Calculate cost for given token usage.
     fn calculate_cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
         self.inner.calculate_cost(input_tokens, output_tokens)
@@ -809,6 +829,6 @@ fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len])
+        format!("{}...", &s[..floor_char_boundary(s, max_len)])
     }
 }
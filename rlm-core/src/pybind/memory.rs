@@ -10,6 +10,7 @@ use crate::memory::{
     EdgeId, EdgeType, HyperEdge, Node, NodeId, NodeQuery, NodeType, Provenance, ProvenanceSource,
     SqliteMemoryStore, Tier,
 };
+use crate::repl::floor_char_boundary;
 
 /// Python enum for NodeType.
 #[pyclass(name = "NodeType", eq, eq_int)]
@@ -616,7 +617,7 @@ fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len])
+        format!("{}...", &s[..floor_char_boundary(s, max_len)])
     }
 }
 
@@ -692,6 +693,7 @@ fn parse_provenance_source(s: &str) -> PyResult<ProvenanceSource> {
         "consolidation" => Ok(ProvenanceSource::Consolidation),
         "inference" => Ok(ProvenanceSource::Inference),
         "import" => Ok(ProvenanceSource::Import),
+        "verification" => Ok(ProvenanceSource::Verification),
         _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
             "Invalid provenance source: {}. Valid: user_message, assistant_response, tool_output, file_content, consolidation, inference, import",
             s
@@ -14,6 +14,10 @@ mod llm;
 #[cfg(feature = "python")]
 mod memory;
 #[cfg(feature = "python")]
+mod reasoning;
+#[cfg(feature = "python")]
+mod spec_agent;
+#[cfg(feature = "python")]
 mod trajectory;
 
 #[cfg(feature = "python")]
@@ -55,6 +59,22 @@ fn rlm_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<trajectory::PyTrajectoryEvent>()?;
     m.add_class::<trajectory::PyTrajectoryEventType>()?;
 
+    // Reasoning trace types
+    m.add_class::<reasoning::PyReasoningTrace>()?;
+    m.add_class::<reasoning::PyTraceAnalyzer>()?;
+
+    // Spec agent types
+    m.add_class::<spec_agent::PyFormalizationLevel>()?;
+    m.add_class::<spec_agent::PyCompletenessMode>()?;
+    m.add_class::<spec_agent::PyQuestionCategory>()?;
+    m.add_class::<spec_agent::PyQuestion>()?;
+    m.add_class::<spec_agent::PyAnswer>()?;
+    m.add_class::<spec_agent::PySpecContext>()?;
+    m.add_class::<spec_agent::PyFormalizationResult>()?;
+    m.add_class::<spec_agent::PyVerificationResult>()?;
+    m.add_class::<spec_agent::PyWorkflowResult>()?;
+    m.add_class::<spec_agent::PySpecAgent>()?;
+
     // Complexity types
     m.add_class::<PyActivationDecision>()?;
     m.add_class::<PyPatternClassifier>()?;
@@ -0,0 +1,203 @@
+//! Python bindings for reasoning traces.
+//!
+//! Node IDs cross the boundary as UUID strings.
+
+use pyo3::prelude::*;
+
+use super::context::json_to_python;
+use crate::reasoning::{DecisionNodeId, DotConfig, ReasoningTrace, TraceAnalyzer};
+
+fn parse_node_id(id: &str) -> PyResult<DecisionNodeId> {
+    DecisionNodeId::parse(id).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid node id {:?}: {}", id, e))
+    })
+}
+
+/// Python wrapper for ReasoningTrace.
+#[pyclass(name = "ReasoningTrace")]
+#[derive(Clone)]
+pub struct PyReasoningTrace {
+    pub(crate) inner: ReasoningTrace,
+}
+
+#[pymethods]
+impl PyReasoningTrace {
+    #[new]
+    #[pyo3(signature = (goal, session_id="".to_string()))]
+    fn new(goal: String, session_id: String) -> Self {
+        Self {
+            inner: ReasoningTrace::new(goal, session_id),
+        }
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    /// ID of the root goal node.
+    #[getter]
+    fn root_id(&self) -> String {
+        self.inner.root_goal.to_string()
+    }
+
+    #[getter]
+    fn session_id(&self) -> String {
+        self.inner.session_id.clone()
+    }
+
+    #[getter]
+    fn node_count(&self) -> usize {
+        self.inner.nodes.len()
+    }
+
+    #[getter]
+    fn edge_count(&self) -> usize {
+        self.inner.edges.len()
+    }
+
+    /// Log a decision between options.
+    ///
+    /// Returns the ID of the chosen option node.
+    fn log_decision(
+        &mut self,
+        parent_id: &str,
+        context: &str,
+        options: Vec<String>,
+        chosen_index: usize,
+        reason: &str,
+    ) -> PyResult<String> {
+        if chosen_index >= options.len() {
+            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(format!(
+                "chosen_index {} out of range for {} options",
+                chosen_index,
+                options.len()
+            )));
+        }
+        let parent = parse_node_id(parent_id)?;
+        let options: Vec<&str> = options.iter().map(String::as_str).collect();
+        Ok(self
+            .inner
+            .log_decision(&parent, context, &options, chosen_index, reason)
+            .to_string())
+    }
+
+    /// Log an action and its outcome.
+    ///
+    /// Returns `(action_id, outcome_id)`.
+    fn log_action(
+        &mut self,
+        parent_id: &str,
+        action: &str,
+        outcome: &str,
+    ) -> PyResult<(String, String)> {
+        let parent = parse_node_id(parent_id)?;
+        let (action_id, outcome_id) = self.inner.log_action(&parent, action, outcome);
+        Ok((action_id.to_string(), outcome_id.to_string()))
+    }
+
+    /// Log an observation.
+    ///
+    /// Returns the ID of the observation node.
+    fn log_observation(&mut self, parent_id: &str, observation: &str) -> PyResult<String> {
+        let parent = parse_node_id(parent_id)?;
+        Ok(self.inner.log_observation(&parent, observation).to_string())
+    }
+
+    /// Merge several traces into one graph under a synthetic root goal.
+    #[staticmethod]
+    fn merge(traces: Vec<PyReasoningTrace>) -> Self {
+        let traces: Vec<ReasoningTrace> = traces.into_iter().map(|t| t.inner).collect();
+        Self {
+            inner: ReasoningTrace::merge(&traces),
+        }
+    }
+
+    /// Export as a NetworkX node-link dict, ready for `nx.node_link_graph`.
+    fn to_networkx_json(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let graph = serde_json::to_value(self.inner.to_networkx_graph())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        json_to_python(py, &graph)
+    }
+
+    /// Export as a Graphviz DOT string.
+    fn to_dot(&self) -> String {
+        self.inner.to_dot()
+    }
+
+    /// Export as a Mermaid flowchart.
+    fn to_mermaid(&self) -> String {
+        self.inner.to_mermaid()
+    }
+
+    /// Export as a standalone SVG (no Graphviz needed).
+    fn to_svg(&self) -> String {
+        self.inner.to_svg(&DotConfig::default())
+    }
+
+    /// Export nodes as CSV (e.g. for `pandas.read_csv`).
+    fn to_csv_nodes(&self) -> String {
+        self.inner.to_csv_nodes()
+    }
+
+    /// Export edges as CSV.
+    fn to_csv_edges(&self) -> String {
+        self.inner.to_csv_edges()
+    }
+
+    /// Export to JSON.
+    fn to_json(&self) -> PyResult<String> {
+        self.inner
+            .to_json()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Import from JSON.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str(json)
+            .map(|inner| Self { inner })
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ReasoningTrace(id={}, nodes={}, edges={})",
+            self.inner.id,
+            self.inner.nodes.len(),
+            self.inner.edges.len()
+        )
+    }
+}
+
+/// Python wrapper for TraceAnalyzer.
+///
+/// Analyzes a snapshot of the trace taken at construction.
+#[pyclass(name = "TraceAnalyzer")]
+pub struct PyTraceAnalyzer {
+    trace: ReasoningTrace,
+}
+
+#[pymethods]
+impl PyTraceAnalyzer {
+    #[new]
+    fn new(trace: &PyReasoningTrace) -> Self {
+        Self {
+            trace: trace.inner.clone(),
+        }
+    }
+
+    /// Overall confidence across the trace's decisions.
+    fn overall_confidence(&self) -> f64 {
+        TraceAnalyzer::new(&self.trace).overall_confidence()
+    }
+
+    /// Human-readable narrative of the reasoning.
+    fn narrative(&self) -> String {
+        TraceAnalyzer::new(&self.trace).narrative()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("TraceAnalyzer(trace={})", self.trace.id)
+    }
+}
@@ -0,0 +1,687 @@
+//! Python bindings for the Spec Agent workflow.
+//!
+//! Workflow phases are exposed as coroutines; await them from an asyncio
+//! event loop. Nested results (requirements, cross-references, proof
+//! results) are returned as plain dicts.
+
+use pyo3::prelude::*;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use super::context::{json_to_python, python_to_json};
+use crate::spec_agent::{
+    Answer, CompletenessMode, FormalizationLevel, FormalizationResult, Question, QuestionCategory,
+    SpecAgent, SpecAgentConfig, SpecContext, VerificationResult, WorkflowResult,
+};
+
+fn runtime_error(e: impl std::fmt::Display) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+}
+
+fn value_error(e: impl std::fmt::Display) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+}
+
+/// Serialize a value into a Python dict (or list).
+fn to_python<T: serde::Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_value(value).map_err(value_error)?;
+    json_to_python(py, &json)
+}
+
+/// Deserialize a value from a Python dict.
+fn from_python<T: serde::de::DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+    serde_json::from_value(python_to_json(value)?).map_err(value_error)
+}
+
+/// Python enum for FormalizationLevel.
+#[pyclass(name = "FormalizationLevel", eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PyFormalizationLevel {
+    Types = 0,
+    Invariants = 1,
+    Contracts = 2,
+    FullProofs = 3,
+}
+
+impl From<FormalizationLevel> for PyFormalizationLevel {
+    fn from(l: FormalizationLevel) -> Self {
+        match l {
+            FormalizationLevel::Types => Self::Types,
+            FormalizationLevel::Invariants => Self::Invariants,
+            FormalizationLevel::Contracts => Self::Contracts,
+            FormalizationLevel::FullProofs => Self::FullProofs,
+        }
+    }
+}
+
+impl From<PyFormalizationLevel> for FormalizationLevel {
+    fn from(l: PyFormalizationLevel) -> Self {
+        match l {
+            PyFormalizationLevel::Types => Self::Types,
+            PyFormalizationLevel::Invariants => Self::Invariants,
+            PyFormalizationLevel::Contracts => Self::Contracts,
+            PyFormalizationLevel::FullProofs => Self::FullProofs,
+        }
+    }
+}
+
+/// Python enum for CompletenessMode.
+#[pyclass(name = "CompletenessMode", eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PyCompletenessMode {
+    Baseline = 0,
+    Placeholder = 1,
+}
+
+impl From<CompletenessMode> for PyCompletenessMode {
+    fn from(m: CompletenessMode) -> Self {
+        match m {
+            CompletenessMode::Baseline => Self::Baseline,
+            CompletenessMode::Placeholder => Self::Placeholder,
+        }
+    }
+}
+
+impl From<PyCompletenessMode> for CompletenessMode {
+    fn from(m: PyCompletenessMode) -> Self {
+        match m {
+            PyCompletenessMode::Baseline => Self::Baseline,
+            PyCompletenessMode::Placeholder => Self::Placeholder,
+        }
+    }
+}
+
+/// Python enum for QuestionCategory.
+#[pyclass(name = "QuestionCategory", eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PyQuestionCategory {
+    Scope = 0,
+    DataTypes = 1,
+    Invariants = 2,
+    Behavior = 3,
+    EdgeCases = 4,
+    Performance = 5,
+    Security = 6,
+}
+
+impl From<QuestionCategory> for PyQuestionCategory {
+    fn from(c: QuestionCategory) -> Self {
+        match c {
+            QuestionCategory::Scope => Self::Scope,
+            QuestionCategory::DataTypes => Self::DataTypes,
+            QuestionCategory::Invariants => Self::Invariants,
+            QuestionCategory::Behavior => Self::Behavior,
+            QuestionCategory::EdgeCases => Self::EdgeCases,
+            QuestionCategory::Performance => Self::Performance,
+            QuestionCategory::Security => Self::Security,
+        }
+    }
+}
+
+impl From<PyQuestionCategory> for QuestionCategory {
+    fn from(c: PyQuestionCategory) -> Self {
+        match c {
+            PyQuestionCategory::Scope => Self::Scope,
+            PyQuestionCategory::DataTypes => Self::DataTypes,
+            PyQuestionCategory::Invariants => Self::Invariants,
+            PyQuestionCategory::Behavior => Self::Behavior,
+            PyQuestionCategory::EdgeCases => Self::EdgeCases,
+            PyQuestionCategory::Performance => Self::Performance,
+            PyQuestionCategory::Security => Self::Security,
+        }
+    }
+}
+
+/// Python wrapper for a clarifying Question.
+#[pyclass(name = "Question")]
+#[derive(Clone)]
+pub struct PyQuestion {
+    pub(crate) inner: Question,
+}
+
+#[pymethods]
+impl PyQuestion {
+    #[new]
+    #[pyo3(signature = (id, text, category, rationale="".to_string(), suggestions=None, required=true))]
+    fn new(
+        id: String,
+        text: String,
+        category: PyQuestionCategory,
+        rationale: String,
+        suggestions: Option<Vec<String>>,
+        required: bool,
+    ) -> Self {
+        Self {
+            inner: Question {
+                id,
+                text,
+                category: category.into(),
+                rationale,
+                suggestions: suggestions.unwrap_or_default(),
+                required,
+                priority: 0,
+            },
+        }
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.clone()
+    }
+
+    #[getter]
+    fn text(&self) -> String {
+        self.inner.text.clone()
+    }
+
+    #[getter]
+    fn category(&self) -> PyQuestionCategory {
+        self.inner.category.into()
+    }
+
+    #[getter]
+    fn rationale(&self) -> String {
+        self.inner.rationale.clone()
+    }
+
+    #[getter]
+    fn suggestions(&self) -> Vec<String> {
+        self.inner.suggestions.clone()
+    }
+
+    #[getter]
+    fn required(&self) -> bool {
+        self.inner.required
+    }
+
+    #[getter]
+    fn priority(&self) -> u32 {
+        self.inner.priority
+    }
+
+    /// Convert to a dict.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+
+    /// Create from a dict produced by `to_dict`.
+    #[staticmethod]
+    fn from_dict(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            inner: from_python(value)?,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Question(id={:?}, category={:?}, text={:?})",
+            self.inner.id, self.inner.category, self.inner.text
+        )
+    }
+}
+
+/// Python wrapper for an Answer to a clarifying question.
+#[pyclass(name = "Answer")]
+#[derive(Clone)]
+pub struct PyAnswer {
+    pub(crate) inner: Answer,
+}
+
+#[pymethods]
+impl PyAnswer {
+    #[new]
+    #[pyo3(signature = (question_id, text, notes=None))]
+    fn new(question_id: String, text: String, notes: Option<String>) -> Self {
+        Self {
+            inner: Answer {
+                question_id,
+                text,
+                notes,
+            },
+        }
+    }
+
+    /// Answer a question.
+    #[staticmethod]
+    #[pyo3(signature = (question, text, notes=None))]
+    fn for_question(question: &PyQuestion, text: String, notes: Option<String>) -> Self {
+        Self::new(question.inner.id.clone(), text, notes)
+    }
+
+    #[getter]
+    fn question_id(&self) -> String {
+        self.inner.question_id.clone()
+    }
+
+    #[getter]
+    fn text(&self) -> String {
+        self.inner.text.clone()
+    }
+
+    #[getter]
+    fn notes(&self) -> Option<String> {
+        self.inner.notes.clone()
+    }
+
+    /// Convert to a dict.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+
+    /// Create from a dict produced by `to_dict`.
+    #[staticmethod]
+    fn from_dict(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            inner: from_python(value)?,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Answer(question_id={:?}, text={:?})",
+            self.inner.question_id, self.inner.text
+        )
+    }
+}
+
+/// Python wrapper for SpecContext.
+///
+/// The context carries the questions asked so far; answers are matched to
+/// them by ID, so pass the same context (or one restored with `from_dict`)
+/// back into `SpecAgent.refine`.
+#[pyclass(name = "SpecContext")]
+#[derive(Clone)]
+pub struct PySpecContext {
+    pub(crate) inner: SpecContext,
+}
+
+#[pymethods]
+impl PySpecContext {
+    #[getter]
+    fn nl_input(&self) -> String {
+        self.inner.nl_input.clone()
+    }
+
+    /// Current workflow phase ("Intake", "Refine", "Formalize", or "Verify").
+    #[getter]
+    fn phase(&self) -> String {
+        format!("{:?}", self.inner.phase)
+    }
+
+    #[getter]
+    fn requirements(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner.requirements)
+    }
+
+    #[getter]
+    fn questions(&self) -> Vec<PyQuestion> {
+        self.inner
+            .questions
+            .iter()
+            .map(|q| PyQuestion { inner: q.clone() })
+            .collect()
+    }
+
+    #[getter]
+    fn answers(&self) -> Vec<PyAnswer> {
+        self.inner
+            .answers
+            .iter()
+            .map(|a| PyAnswer { inner: a.clone() })
+            .collect()
+    }
+
+    #[getter]
+    fn ambiguities(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner.ambiguities)
+    }
+
+    #[getter]
+    fn topos_spec(&self) -> Option<String> {
+        self.inner.topos_spec.clone()
+    }
+
+    #[getter]
+    fn lean_spec(&self) -> Option<String> {
+        self.inner.lean_spec.clone()
+    }
+
+    /// Questions that still need an answer.
+    fn unanswered_questions(&self) -> Vec<PyQuestion> {
+        self.inner
+            .unanswered_questions()
+            .into_iter()
+            .map(|q| PyQuestion { inner: q.clone() })
+            .collect()
+    }
+
+    /// Check whether all required questions are answered.
+    fn all_required_answered(&self) -> bool {
+        self.inner.all_required_answered()
+    }
+
+    /// Convert to a dict.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+
+    /// Create from a dict produced by `to_dict`.
+    #[staticmethod]
+    fn from_dict(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            inner: from_python(value)?,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SpecContext(phase={:?}, requirements={}, questions={}, answers={})",
+            self.inner.phase,
+            self.inner.requirements.len(),
+            self.inner.questions.len(),
+            self.inner.answers.len()
+        )
+    }
+}
+
+/// Python wrapper for FormalizationResult.
+#[pyclass(name = "FormalizationResult")]
+#[derive(Clone)]
+pub struct PyFormalizationResult {
+    pub(crate) inner: FormalizationResult,
+}
+
+#[pymethods]
+impl PyFormalizationResult {
+    #[getter]
+    fn topos_content(&self) -> String {
+        self.inner.topos_content.clone()
+    }
+
+    #[getter]
+    fn topos_filename(&self) -> String {
+        self.inner.topos_filename.clone()
+    }
+
+    #[getter]
+    fn lean_content(&self) -> String {
+        self.inner.lean_content.clone()
+    }
+
+    #[getter]
+    fn lean_filename(&self) -> String {
+        self.inner.lean_filename.clone()
+    }
+
+    #[getter]
+    fn cross_refs(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner.cross_refs)
+    }
+
+    #[getter]
+    fn warnings(&self) -> Vec<String> {
+        self.inner.warnings.clone()
+    }
+
+    /// Convert to a dict.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FormalizationResult(topos={:?}, lean={:?}, cross_refs={})",
+            self.inner.topos_filename,
+            self.inner.lean_filename,
+            self.inner.cross_refs.len()
+        )
+    }
+}
+
+/// Python wrapper for VerificationResult.
+#[pyclass(name = "SpecVerificationResult")]
+#[derive(Clone)]
+pub struct PyVerificationResult {
+    pub(crate) inner: VerificationResult,
+}
+
+#[pymethods]
+impl PyVerificationResult {
+    #[getter]
+    fn passed(&self) -> bool {
+        self.inner.passed
+    }
+
+    #[getter]
+    fn lean_type_check_ok(&self) -> bool {
+        self.inner.lean_type_check_ok
+    }
+
+    #[getter]
+    fn lean_errors(&self) -> Vec<String> {
+        self.inner.lean_errors.clone()
+    }
+
+    #[getter]
+    fn topos_valid(&self) -> bool {
+        self.inner.topos_valid
+    }
+
+    #[getter]
+    fn topos_errors(&self) -> Vec<String> {
+        self.inner.topos_errors.clone()
+    }
+
+    #[getter]
+    fn proof_results(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner.proof_results)
+    }
+
+    /// Convert to a dict.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SpecVerificationResult(passed={}, lean_errors={}, topos_errors={})",
+            self.inner.passed,
+            self.inner.lean_errors.len(),
+            self.inner.topos_errors.len()
+        )
+    }
+}
+
+/// Python wrapper for WorkflowResult.
+#[pyclass(name = "WorkflowResult")]
+#[derive(Clone)]
+pub struct PyWorkflowResult {
+    pub(crate) inner: WorkflowResult,
+}
+
+#[pymethods]
+impl PyWorkflowResult {
+    #[getter]
+    fn context(&self) -> PySpecContext {
+        PySpecContext {
+            inner: self.inner.context.clone(),
+        }
+    }
+
+    #[getter]
+    fn formalization(&self) -> PyFormalizationResult {
+        PyFormalizationResult {
+            inner: self.inner.formalization.clone(),
+        }
+    }
+
+    #[getter]
+    fn verification(&self) -> PyVerificationResult {
+        PyVerificationResult {
+            inner: self.inner.verification.clone(),
+        }
+    }
+
+    /// Check if the workflow completed successfully.
+    fn success(&self) -> bool {
+        self.inner.success()
+    }
+
+    /// Get all warnings from the workflow.
+    fn warnings(&self) -> Vec<String> {
+        self.inner
+            .warnings()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Get all errors from verification.
+    fn errors(&self) -> Vec<String> {
+        self.inner.errors().into_iter().map(String::from).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("WorkflowResult(success={})", self.inner.success())
+    }
+}
+
+/// Python wrapper for SpecAgent.
+///
+/// All workflow methods return coroutines. Calls on one agent are
+/// serialized.
+#[pyclass(name = "SpecAgent")]
+pub struct PySpecAgent {
+    inner: Arc<Mutex<SpecAgent>>,
+}
+
+#[pymethods]
+impl PySpecAgent {
+    #[new]
+    #[pyo3(signature = (level=None, completeness_mode=None, validate_with_lean=true, validate_with_topos=true, spec_name=None))]
+    fn new(
+        level: Option<PyFormalizationLevel>,
+        completeness_mode: Option<PyCompletenessMode>,
+        validate_with_lean: bool,
+        validate_with_topos: bool,
+        spec_name: Option<String>,
+    ) -> Self {
+        let mut config = SpecAgentConfig::default();
+        if let Some(level) = level {
+            config = config.with_level(level.into());
+        }
+        if let Some(mode) = completeness_mode {
+            config = config.with_completeness_mode(mode.into());
+        }
+        config.validate_with_lean = validate_with_lean;
+        config.validate_with_topos = validate_with_topos;
+
+        let mut agent = SpecAgent::new(config);
+        if let Some(name) = spec_name {
+            agent = agent.with_spec_name(name);
+        }
+
+        Self {
+            inner: Arc::new(Mutex::new(agent)),
+        }
+    }
+
+    /// Phase 1: parse natural language requirements into a SpecContext.
+    fn intake<'py>(&self, py: Python<'py>, nl_input: String) -> PyResult<Bound<'py, PyAny>> {
+        let agent = Arc::clone(&self.inner);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let ctx = agent
+                .lock()
+                .await
+                .intake(&nl_input)
+                .await
+                .map_err(runtime_error)?;
+            Ok(PySpecContext { inner: ctx })
+        })
+    }
+
+    /// Phase 2: incorporate answers and return new clarifying questions.
+    ///
+    /// `ctx` is updated in place once the coroutine completes.
+    #[pyo3(signature = (ctx, answers=None))]
+    fn refine<'py>(
+        &self,
+        py: Python<'py>,
+        ctx: Py<PySpecContext>,
+        answers: Option<Vec<PyAnswer>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let agent = Arc::clone(&self.inner);
+        let mut spec_ctx = ctx.borrow(py).inner.clone();
+        let answers: Vec<Answer> = answers
+            .unwrap_or_default()
+            .into_iter()
+            .map(|a| a.inner)
+            .collect();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let questions = agent
+                .lock()
+                .await
+                .refine(&mut spec_ctx, &answers)
+                .await
+                .map_err(runtime_error)?;
+            Python::with_gil(|py| ctx.borrow_mut(py).inner = spec_ctx);
+            Ok(questions
+                .into_iter()
+                .map(|q| PyQuestion { inner: q })
+                .collect::<Vec<_>>())
+        })
+    }
+
+    /// Phase 3: generate Topos and Lean specifications.
+    fn formalize<'py>(&self, py: Python<'py>, ctx: &PySpecContext) -> PyResult<Bound<'py, PyAny>> {
+        let agent = Arc::clone(&self.inner);
+        let spec_ctx = ctx.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let result = agent
+                .lock()
+                .await
+                .formalize(&spec_ctx)
+                .await
+                .map_err(runtime_error)?;
+            Ok(PyFormalizationResult { inner: result })
+        })
+    }
+
+    /// Phase 4: type-check and validate generated specifications.
+    fn verify<'py>(
+        &self,
+        py: Python<'py>,
+        formalization: &PyFormalizationResult,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let agent = Arc::clone(&self.inner);
+        let formalization = formalization.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let result = agent
+                .lock()
+                .await
+                .verify(&formalization)
+                .await
+                .map_err(runtime_error)?;
+            Ok(PyVerificationResult { inner: result })
+        })
+    }
+
+    /// Run all phases without interactive clarification.
+    fn run_workflow<'py>(&self, py: Python<'py>, nl_input: String) -> PyResult<Bound<'py, PyAny>> {
+        let agent = Arc::clone(&self.inner);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let result = agent
+                .lock()
+                .await
+                .run_workflow(&nl_input)
+                .await
+                .map_err(runtime_error)?;
+            Ok(PyWorkflowResult { inner: result })
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        "SpecAgent()".to_string()
+    }
+}
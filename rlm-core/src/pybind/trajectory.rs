@@ -3,6 +3,7 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
+use crate::repl::floor_char_boundary;
 use crate::trajectory::{TrajectoryEvent, TrajectoryEventType};
 
 /// Python enum for TrajectoryEventType.
@@ -34,6 +35,8 @@ pub enum PyTrajectoryEventType {
     CriticInvoked = 22,
     IssueFound = 23,
     AdversarialComplete = 24,
+    BudgetExhausted = 25,
+    CycleDetected = 26,
 }
 
 impl From<TrajectoryEventType> for PyTrajectoryEventType {
@@ -64,6 +67,8 @@ impl From<TrajectoryEventType> for PyTrajectoryEventType {
             TrajectoryEventType::CriticInvoked => PyTrajectoryEventType::CriticInvoked,
             TrajectoryEventType::IssueFound => PyTrajectoryEventType::IssueFound,
             TrajectoryEventType::AdversarialComplete => PyTrajectoryEventType::AdversarialComplete,
+            TrajectoryEventType::BudgetExhausted => PyTrajectoryEventType::BudgetExhausted,
+            TrajectoryEventType::CycleDetected => PyTrajectoryEventType::CycleDetected,
         }
     }
 }
@@ -96,6 +101,8 @@ impl From<PyTrajectoryEventType> for TrajectoryEventType {
             PyTrajectoryEventType::CriticInvoked => TrajectoryEventType::CriticInvoked,
             PyTrajectoryEventType::IssueFound => TrajectoryEventType::IssueFound,
             PyTrajectoryEventType::AdversarialComplete => TrajectoryEventType::AdversarialComplete,
+            PyTrajectoryEventType::BudgetExhausted => TrajectoryEventType::BudgetExhausted,
+            PyTrajectoryEventType::CycleDetected => TrajectoryEventType::CycleDetected,
         }
     }
 }
@@ -129,6 +136,8 @@ impl PyTrajectoryEventType {
             PyTrajectoryEventType::CriticInvoked => "TrajectoryEventType.CriticInvoked",
             PyTrajectoryEventType::IssueFound => "TrajectoryEventType.IssueFound",
             PyTrajectoryEventType::AdversarialComplete => "TrajectoryEventType.AdversarialComplete",
+            PyTrajectoryEventType::BudgetExhausted => "TrajectoryEventType.BudgetExhausted",
+            PyTrajectoryEventType::CycleDetected => "TrajectoryEventType.CycleDetected",
         }
     }
 }
@@ -359,6 +368,6 @@ fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len])
+        format!("{}...", &s[..floor_char_boundary(s, max_len)])
     }
 }
@@ -83,13 +83,18 @@ mod types;
 mod visualize;
 
 // Re-export main types
-pub use query::{compare_traces, DecisionPath, TraceAnalyzer, TraceComparison, TraceQuery};
+pub use query::{
+    compare_traces, diff_traces, DecisionPath, DiffStatus, TraceAnalyzer, TraceComparison,
+    TraceDiff, TraceDiffEdge, TraceDiffNode, TraceQuery, DIFF_MATCH_THRESHOLD,
+};
 pub use store::{ReasoningTraceStore, TraceStoreStats};
-pub use trace::{DecisionTree, ReasoningTrace, TraceStats};
+pub use trace::{DecisionTree, PruneConfig, ReasoningTrace, TraceStats};
 pub use types::{
     DecisionNode, DecisionNodeId, DecisionNodeType, DecisionPoint, OptionStatus, TraceEdge,
     TraceEdgeLabel, TraceId,
 };
 pub use visualize::{
-    DotConfig, HtmlConfig, HtmlTheme, NetworkXGraph, NetworkXGraphAttrs, NetworkXLink, NetworkXNode,
+    CytoscapeEdgeData, CytoscapeElement, CytoscapeElements, CytoscapeGraph, CytoscapeNodeData,
+    DotConfig, HtmlConfig, HtmlTheme, NetworkXGraph, NetworkXGraphAttrs, NetworkXLink,
+    NetworkXNode, TraceHtmlPatch, SVG_LABEL_CHARS, SVG_MAX_COLUMNS,
 };
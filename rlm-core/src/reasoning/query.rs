@@ -4,11 +4,14 @@
 //! decision trees stored in the memory system.
 
 use crate::error::Result;
+use crate::memory::Tier;
 use crate::reasoning::store::ReasoningTraceStore;
 use crate::reasoning::trace::{DecisionTree, ReasoningTrace};
 use crate::reasoning::types::*;
+use crate::repl::floor_char_boundary;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Query builder for finding traces.
 #[derive(Debug, Clone, Default)]
@@ -34,6 +37,12 @@ pub struct TraceQuery {
     /// Minimum number of decisions.
     pub min_decisions: Option<usize>,
 
+    /// Node types that must all be present in the trace.
+    pub node_types: Vec<DecisionNodeType>,
+
+    /// Memory tiers; the trace must have a stored node in one of them.
+    pub tiers: Vec<Tier>,
+
     /// Maximum results.
     pub limit: Option<usize>,
 }
@@ -69,6 +78,38 @@ impl TraceQuery {
         self
     }
 
+    /// Only traces created at or after `after`.
+    pub fn created_after(mut self, after: DateTime<Utc>) -> Self {
+        self.created_after = Some(after);
+        self
+    }
+
+    /// Only traces created at or before `before`.
+    pub fn created_before(mut self, before: DateTime<Utc>) -> Self {
+        self.created_before = Some(before);
+        self
+    }
+
+    /// Only traces containing at least one node of `node_type`.
+    ///
+    /// Repeated calls require every given type to be present.
+    pub fn with_node_type(mut self, node_type: DecisionNodeType) -> Self {
+        if !self.node_types.contains(&node_type) {
+            self.node_types.push(node_type);
+        }
+        self
+    }
+
+    /// Only traces with a node stored in `tier`.
+    ///
+    /// Repeated calls match traces touching any of the given tiers.
+    pub fn in_tier(mut self, tier: Tier) -> Self {
+        if !self.tiers.contains(&tier) {
+            self.tiers.push(tier);
+        }
+        self
+    }
+
     /// Filter by goal content.
     pub fn goal_contains(mut self, text: impl Into<String>) -> Self {
         self.goal_contains = Some(text.into());
@@ -88,15 +129,11 @@ impl TraceQuery {
     }
 
     /// Execute the query against a store.
+    ///
+    /// Session, git, time-range, node-type, and tier filters are checked
+    /// against stored node rows, so only matching traces are loaded.
     pub fn execute(&self, store: &ReasoningTraceStore) -> Result<Vec<ReasoningTrace>> {
-        // Start with all traces or filtered by session/commit
-        let trace_ids = if let Some(ref session) = self.session_id {
-            store.find_by_session(session)?
-        } else if let Some(ref commit) = self.git_commit {
-            store.find_by_commit(commit)?
-        } else {
-            store.list_traces()?
-        };
+        let trace_ids = store.find_candidates(self)?;
 
         let mut results = Vec::new();
 
@@ -174,6 +211,15 @@ impl TraceQuery {
             }
         }
 
+        // Node type filter
+        if !self
+            .node_types
+            .iter()
+            .all(|t| trace.nodes.iter().any(|n| n.node_type == *t))
+        {
+            return false;
+        }
+
         true
     }
 }
@@ -216,7 +262,10 @@ impl DecisionPath {
 
         for (i, node) in nodes.iter().enumerate() {
             let content = if node.content.len() > 30 {
-                format!("{}...", &node.content[..30])
+                format!(
+                    "{}...",
+                    &node.content[..floor_char_boundary(&node.content, 30)]
+                )
             } else {
                 node.content.clone()
             };
@@ -526,6 +575,238 @@ pub fn compare_traces(trace_a: &ReasoningTrace, trace_b: &ReasoningTrace) -> Tra
     }
 }
 
+impl TraceComparison {
+    /// Render the node/edge-level diff of the compared traces as DOT.
+    ///
+    /// `baseline` and `current` should be the traces this comparison was
+    /// built from (`trace_a` and `trace_b` respectively).
+    pub fn to_diff_dot(&self, baseline: &ReasoningTrace, current: &ReasoningTrace) -> String {
+        diff_traces(baseline, current).to_dot()
+    }
+}
+
+/// Minimum content similarity for two nodes to be considered the same.
+pub const DIFF_MATCH_THRESHOLD: f64 = 0.6;
+
+/// How a node or edge differs from the baseline trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    /// Only in the current trace.
+    Added,
+    /// Only in the baseline trace.
+    Removed,
+    /// In both, but the content (node) or label (edge) differs.
+    Changed,
+    /// In both and identical.
+    Unchanged,
+}
+
+/// A node in a [`TraceDiff`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDiffNode {
+    /// The node as it appears in the current trace (baseline for removed nodes).
+    pub node: DecisionNode,
+    /// Diff status.
+    pub status: DiffStatus,
+    /// Baseline content, for changed nodes.
+    pub baseline_content: Option<String>,
+}
+
+/// An edge in a [`TraceDiff`], between unified node IDs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDiffEdge {
+    pub from: DecisionNodeId,
+    pub to: DecisionNodeId,
+    /// Label in the current trace (baseline for removed edges).
+    pub label: TraceEdgeLabel,
+    /// Diff status.
+    pub status: DiffStatus,
+    /// Baseline label, for changed edges.
+    pub baseline_label: Option<TraceEdgeLabel>,
+}
+
+impl TraceDiffEdge {
+    /// Whether this edge records a decision that picked a different option.
+    pub fn is_changed_choice(&self) -> bool {
+        self.status == DiffStatus::Changed
+            && (self.label == TraceEdgeLabel::Chooses
+                || self.baseline_label == Some(TraceEdgeLabel::Chooses))
+    }
+}
+
+/// Node- and edge-level diff between a baseline trace and a current trace.
+///
+/// Matched nodes use their current-trace IDs; removed nodes keep their
+/// baseline IDs, so the diff forms a single graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDiff {
+    pub baseline: TraceId,
+    pub current: TraceId,
+    pub nodes: Vec<TraceDiffNode>,
+    pub edges: Vec<TraceDiffEdge>,
+}
+
+impl TraceDiff {
+    /// Nodes with the given status.
+    pub fn nodes_with_status(&self, status: DiffStatus) -> Vec<&TraceDiffNode> {
+        self.nodes.iter().filter(|n| n.status == status).collect()
+    }
+
+    /// Edges whose decision switched to a different option.
+    pub fn changed_choices(&self) -> Vec<&TraceDiffEdge> {
+        self.edges
+            .iter()
+            .filter(|e| e.is_changed_choice())
+            .collect()
+    }
+}
+
+/// Diff `current` against `baseline`.
+///
+/// Nodes are matched by type and content similarity (token Jaccard of at
+/// least [`DIFF_MATCH_THRESHOLD`]), preferring candidates whose parent has
+/// already been matched so that identically named options under different
+/// decisions stay apart.
+pub fn diff_traces(baseline: &ReasoningTrace, current: &ReasoningTrace) -> TraceDiff {
+    // current id -> baseline id
+    let mut matches: HashMap<DecisionNodeId, DecisionNodeId> = HashMap::new();
+    let mut taken: HashSet<DecisionNodeId> = HashSet::new();
+
+    matches.insert(current.root_goal.clone(), baseline.root_goal.clone());
+    taken.insert(baseline.root_goal.clone());
+
+    let best_match = |node: &DecisionNode,
+                      candidates: Vec<&DecisionNode>,
+                      taken: &HashSet<DecisionNodeId>|
+     -> Option<DecisionNodeId> {
+        candidates
+            .into_iter()
+            .filter(|c| c.node_type == node.node_type && !taken.contains(&c.id))
+            .map(|c| (content_similarity(&c.content, &node.content), c))
+            .filter(|(sim, _)| *sim >= DIFF_MATCH_THRESHOLD)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, c)| c.id.clone())
+    };
+
+    // First pass: match children of already-matched parents.
+    for node in &current.nodes {
+        if matches.contains_key(&node.id) {
+            continue;
+        }
+        let Some(parent) = current.parent(&node.id) else {
+            continue;
+        };
+        if let Some(baseline_parent) = matches.get(&parent.id) {
+            let candidates = baseline.children(baseline_parent);
+            if let Some(id) = best_match(node, candidates, &taken) {
+                taken.insert(id.clone());
+                matches.insert(node.id.clone(), id);
+            }
+        }
+    }
+
+    // Second pass: match anything left anywhere in the baseline.
+    for node in &current.nodes {
+        if matches.contains_key(&node.id) {
+            continue;
+        }
+        if let Some(id) = best_match(node, baseline.nodes.iter().collect(), &taken) {
+            taken.insert(id.clone());
+            matches.insert(node.id.clone(), id);
+        }
+    }
+
+    let to_unified: HashMap<&DecisionNodeId, &DecisionNodeId> =
+        matches.iter().map(|(cur, base)| (base, cur)).collect();
+    let unify = |id: &DecisionNodeId| -> DecisionNodeId {
+        to_unified
+            .get(id)
+            .map(|c| (*c).clone())
+            .unwrap_or_else(|| id.clone())
+    };
+
+    let mut nodes = Vec::new();
+    for node in &current.nodes {
+        let (status, baseline_content) =
+            match matches.get(&node.id).and_then(|id| baseline.get_node(id)) {
+                Some(base) if base.content == node.content => (DiffStatus::Unchanged, None),
+                Some(base) => (DiffStatus::Changed, Some(base.content.clone())),
+                None => (DiffStatus::Added, None),
+            };
+        nodes.push(TraceDiffNode {
+            node: node.clone(),
+            status,
+            baseline_content,
+        });
+    }
+    for node in baseline.nodes.iter().filter(|n| !taken.contains(&n.id)) {
+        nodes.push(TraceDiffNode {
+            node: node.clone(),
+            status: DiffStatus::Removed,
+            baseline_content: None,
+        });
+    }
+
+    let mut baseline_edges: HashMap<(DecisionNodeId, DecisionNodeId), TraceEdgeLabel> = baseline
+        .edges
+        .iter()
+        .map(|e| ((unify(&e.from), unify(&e.to)), e.label))
+        .collect();
+
+    let mut edges = Vec::new();
+    for edge in &current.edges {
+        let key = (edge.from.clone(), edge.to.clone());
+        let (status, baseline_label) = match baseline_edges.remove(&key) {
+            Some(label) if label == edge.label => (DiffStatus::Unchanged, None),
+            Some(label) => (DiffStatus::Changed, Some(label)),
+            None => (DiffStatus::Added, None),
+        };
+        edges.push(TraceDiffEdge {
+            from: edge.from.clone(),
+            to: edge.to.clone(),
+            label: edge.label,
+            status,
+            baseline_label,
+        });
+    }
+    for edge in &baseline.edges {
+        let key = (unify(&edge.from), unify(&edge.to));
+        if let Some(label) = baseline_edges.remove(&key) {
+            edges.push(TraceDiffEdge {
+                from: key.0,
+                to: key.1,
+                label,
+                status: DiffStatus::Removed,
+                baseline_label: None,
+            });
+        }
+    }
+
+    TraceDiff {
+        baseline: baseline.id.clone(),
+        current: current.id.clone(),
+        nodes,
+        edges,
+    }
+}
+
+/// Token Jaccard similarity of two strings, case-insensitive.
+fn content_similarity(a: &str, b: &str) -> f64 {
+    let tokens = |s: &str| -> HashSet<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_lowercase())
+            .collect()
+    };
+    let (a, b) = (tokens(a), tokens(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -584,6 +865,68 @@ mod tests {
         assert_eq!(results[0].session_id, "session-a");
     }
 
+    #[test]
+    fn test_query_time_window_node_types_and_tiers() {
+        let store = ReasoningTraceStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        let mut old = ReasoningTrace::new("Old goal", "session-w");
+        old.created_at = now - chrono::Duration::days(3);
+        let mut recent = create_test_trace();
+        recent.created_at = now - chrono::Duration::hours(2);
+        let mut future = ReasoningTrace::new("Scheduled goal", "session-w");
+        future.created_at = now + chrono::Duration::hours(5);
+        for trace in [&old, &recent, &future] {
+            store.save_trace(trace).unwrap();
+        }
+
+        let window = TraceQuery::new()
+            .created_after(now - chrono::Duration::hours(24))
+            .created_before(now)
+            .execute(&store)
+            .unwrap();
+        assert_eq!(window.len(), 1);
+        assert_eq!(window[0].id, recent.id);
+
+        let since = TraceQuery::new()
+            .created_after(now - chrono::Duration::hours(24))
+            .session("session-w")
+            .execute(&store)
+            .unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].id, future.id);
+
+        let with_actions = TraceQuery::new()
+            .with_node_type(DecisionNodeType::Action)
+            .with_node_type(DecisionNodeType::Observation)
+            .execute(&store)
+            .unwrap();
+        assert_eq!(with_actions.len(), 1);
+        assert_eq!(with_actions[0].id, recent.id);
+
+        // Traces are stored in the session tier
+        assert_eq!(
+            TraceQuery::new()
+                .in_tier(Tier::Session)
+                .execute(&store)
+                .unwrap()
+                .len(),
+            3
+        );
+        assert!(TraceQuery::new()
+            .in_tier(Tier::LongTerm)
+            .execute(&store)
+            .unwrap()
+            .is_empty());
+
+        // Empty window
+        assert!(TraceQuery::new()
+            .created_before(now - chrono::Duration::days(30))
+            .execute(&store)
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_analyzer_decision_paths() {
         let trace = create_test_trace();
@@ -719,6 +1062,79 @@ mod tests {
         assert!(comparison.summary.contains("common"));
     }
 
+    #[test]
+    fn test_diff_traces_added_and_removed() {
+        let mut baseline = ReasoningTrace::new("Build API", "session-a");
+        let root = baseline.root_goal.clone();
+        baseline.log_decision(&root, "Choose framework", &["Axum", "Actix"], 0, "Speed");
+        baseline.log_decision(&root, "Choose logging", &["tracing", "log"], 0, "Spans");
+
+        let mut current = ReasoningTrace::new("Build API", "session-b");
+        let root = current.root_goal.clone();
+        current.log_decision(&root, "Choose framework", &["Axum", "Actix"], 0, "Speed");
+        current.log_decision(&root, "Choose database", &["Postgres", "SQLite"], 1, "Ease");
+
+        let diff = diff_traces(&baseline, &current);
+        let content = |status| -> Vec<String> {
+            diff.nodes_with_status(status)
+                .iter()
+                .map(|n| n.node.content.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            content(DiffStatus::Added),
+            vec!["Choose database", "Postgres", "SQLite"]
+        );
+        assert_eq!(
+            content(DiffStatus::Removed),
+            vec!["Choose logging", "tracing", "log"]
+        );
+        assert_eq!(content(DiffStatus::Unchanged).len(), 4);
+        assert!(diff.changed_choices().is_empty());
+
+        let comparison = compare_traces(&baseline, &current);
+        let dot = comparison.to_diff_dot(&baseline, &current);
+        let line_for = |text: &str| {
+            dot.lines()
+                .find(|l| l.contains(&format!("label=\"{}\"", text)))
+                .unwrap()
+                .to_string()
+        };
+        let added = line_for("Choose database");
+        let removed = line_for("Choose logging");
+        let unchanged = line_for("Choose framework");
+        assert!(added.contains("fillcolor=\"#C8E6C9\""));
+        assert!(removed.contains("fillcolor=\"#FFCDD2\""));
+        assert!(removed.contains("dashed"));
+        assert!(unchanged.contains("fillcolor=\"#EEEEEE\""));
+    }
+
+    #[test]
+    fn test_diff_traces_changed_choice() {
+        let mut baseline = ReasoningTrace::new("Build API", "session-a");
+        let root = baseline.root_goal.clone();
+        baseline.log_decision(&root, "Choose framework", &["Axum", "Actix"], 0, "Speed");
+
+        let mut current = ReasoningTrace::new("Build API", "session-b");
+        let root = current.root_goal.clone();
+        current.log_decision(&root, "Choose framework", &["Axum", "Actix"], 1, "Maturity");
+
+        let diff = diff_traces(&baseline, &current);
+        assert!(diff.nodes.iter().all(|n| n.status == DiffStatus::Unchanged));
+
+        let changed = diff.changed_choices();
+        assert_eq!(changed.len(), 2);
+        let now_chosen = changed
+            .iter()
+            .find(|e| e.label == TraceEdgeLabel::Chooses)
+            .unwrap();
+        assert_eq!(now_chosen.baseline_label, Some(TraceEdgeLabel::Rejects));
+
+        let dot = diff.to_dot();
+        assert!(dot.contains("chooses (changed choice, was rejects)"));
+    }
+
     #[test]
     fn test_query_goal_contains() {
         let store = ReasoningTraceStore::in_memory().unwrap();
@@ -7,6 +7,7 @@ use crate::error::{Error, Result};
 use crate::memory::{
     EdgeType, HyperEdge, Node, NodeId, NodeQuery, NodeType, SqliteMemoryStore, Tier,
 };
+use crate::reasoning::query::TraceQuery;
 use crate::reasoning::trace::ReasoningTrace;
 use crate::reasoning::types::*;
 use chrono::Utc;
@@ -544,6 +545,86 @@ impl ReasoningTraceStore {
         Ok(trace_ids)
     }
 
+    /// Find traces matching the row-level filters of `query`.
+    ///
+    /// Session, git, creation time, node types, and tiers are all checked in
+    /// SQL against the stored node rows, without loading or reconstructing
+    /// any trace. Creation times are compared at SQLite's millisecond
+    /// precision, so a trace within a millisecond of a bound may be returned;
+    /// [`TraceQuery::execute`] re-checks times exactly and applies the goal
+    /// and decision-count filters.
+    pub(crate) fn find_candidates(&self, query: &TraceQuery) -> Result<Vec<TraceId>> {
+        const SAME_TRACE: &str = "json_extract(n.metadata, '$.trace_id') = \
+                                  json_extract(root.metadata, '$.trace_id')";
+
+        let mut sql = String::from(
+            "SELECT json_extract(root.metadata, '$.trace_id') FROM nodes root \
+             WHERE root.node_type = ? AND root.subtype = 'trace_root'",
+        );
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> =
+            vec![Box::new(NodeType::Decision.to_string())];
+
+        for (key, expected) in [
+            ("session_id", &query.session_id),
+            ("git_commit", &query.git_commit),
+            ("git_branch", &query.git_branch),
+        ] {
+            if let Some(expected) = expected {
+                sql.push_str(&format!(
+                    " AND json_extract(root.metadata, '$.{}') = ?",
+                    key
+                ));
+                params.push(Box::new(expected.clone()));
+            }
+        }
+
+        for (op, bound) in [(">=", query.created_after), ("<=", query.created_before)] {
+            if let Some(bound) = bound {
+                sql.push_str(&format!(
+                    " AND julianday(json_extract(root.metadata, '$.created_at')) {} julianday(?)",
+                    op
+                ));
+                params.push(Box::new(bound.to_rfc3339()));
+            }
+        }
+
+        if !query.tiers.is_empty() {
+            let placeholders = vec!["?"; query.tiers.len()].join(",");
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM nodes n WHERE {} AND n.tier IN ({}))",
+                SAME_TRACE, placeholders
+            ));
+            for tier in &query.tiers {
+                params.push(Box::new(*tier as i32));
+            }
+        }
+
+        for node_type in &query.node_types {
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM nodes n WHERE {} \
+                 AND n.subtype IS NOT 'trace_root' \
+                 AND json_extract(n.metadata, '$.decision_node_type') = ?)",
+                SAME_TRACE
+            ));
+            params.push(Box::new(node_type.to_string()));
+        }
+
+        sql.push_str(" ORDER BY root.last_accessed DESC");
+
+        let ids: Vec<Option<String>> = self.memory.with_conn(|conn| {
+            let params: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt.query_map(params.as_slice(), |row| row.get(0))?;
+            rows.collect()
+        })?;
+
+        Ok(ids
+            .into_iter()
+            .flatten()
+            .filter_map(|id| TraceId::parse(&id).ok())
+            .collect())
+    }
+
     /// Delete a trace and all its nodes/edges.
     pub fn delete_trace(&self, trace_id: &TraceId) -> Result<bool> {
         let nodes = self.find_trace_nodes(trace_id)?;
@@ -685,6 +766,45 @@ mod tests {
         assert_eq!(commit_traces.len(), 2);
     }
 
+    #[test]
+    fn test_find_candidates_filters_in_sql() {
+        let store = ReasoningTraceStore::in_memory().unwrap();
+
+        let mut acted = ReasoningTrace::new("Goal 1", "session-a").with_git_branch("main");
+        let root = acted.root_goal.clone();
+        acted.log_action(&root, "Act", "Done");
+        let plain = ReasoningTrace::new("Goal 2", "session-a");
+        let other = ReasoningTrace::new("Goal 3", "session-b");
+        for trace in [&acted, &plain, &other] {
+            store.save_trace(trace).unwrap();
+        }
+
+        let ids = |query: TraceQuery| store.find_candidates(&query).unwrap();
+        assert_eq!(ids(TraceQuery::new().session("session-a")).len(), 2);
+        assert_eq!(
+            ids(TraceQuery::new().branch("main")),
+            vec![acted.id.clone()]
+        );
+        assert_eq!(
+            ids(TraceQuery::new().with_node_type(DecisionNodeType::Action)),
+            vec![acted.id.clone()]
+        );
+        assert_eq!(ids(TraceQuery::new().in_tier(Tier::Session)).len(), 3);
+        assert!(ids(TraceQuery::new().in_tier(Tier::Archive)).is_empty());
+
+        let created = other.created_at;
+        assert_eq!(
+            ids(TraceQuery::new()
+                .session("session-b")
+                .created_after(created)
+                .created_before(created)),
+            vec![other.id.clone()]
+        );
+        assert!(
+            ids(TraceQuery::new().created_after(created + chrono::Duration::seconds(1))).is_empty()
+        );
+    }
+
     #[test]
     fn test_delete_trace() {
         let store = ReasoningTraceStore::in_memory().unwrap();
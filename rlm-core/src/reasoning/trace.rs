@@ -6,7 +6,7 @@ use crate::reasoning::types::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// A complete reasoning trace capturing the decision process.
 ///
@@ -398,6 +398,231 @@ impl ReasoningTrace {
         serde_json::to_string_pretty(self)
     }
 
+    // ==================== Pruning ====================
+
+    /// Return a copy of this trace with dead and low-confidence branches removed.
+    ///
+    /// With `drop_rejected`, rejected options and everything reachable only
+    /// through them are dropped, even high-confidence leaves. Nodes no longer
+    /// reachable from the root are dropped too. Nodes below `min_confidence`
+    /// are removed and their surviving descendants reattach to the nearest
+    /// kept ancestor with the label of the edge that entered them. The root
+    /// and both ends of every reachable `Chooses` edge are always kept.
+    pub fn prune(&self, config: &PruneConfig) -> ReasoningTrace {
+        let live_edges: Vec<&TraceEdge> = self
+            .edges
+            .iter()
+            .filter(|e| !(config.drop_rejected && e.label == TraceEdgeLabel::Rejects))
+            .collect();
+
+        // Nodes reachable from the root over live edges
+        let mut reachable: HashSet<&DecisionNodeId> = HashSet::from([&self.root_goal]);
+        let mut stack = vec![&self.root_goal];
+        while let Some(id) = stack.pop() {
+            for edge in live_edges.iter().filter(|e| &e.from == id) {
+                if reachable.insert(&edge.to) {
+                    stack.push(&edge.to);
+                }
+            }
+        }
+        let live_edges: Vec<&TraceEdge> = live_edges
+            .into_iter()
+            .filter(|e| reachable.contains(&e.from) && reachable.contains(&e.to))
+            .collect();
+
+        let mut protected: HashSet<&DecisionNodeId> = HashSet::from([&self.root_goal]);
+        for edge in live_edges
+            .iter()
+            .filter(|e| e.label == TraceEdgeLabel::Chooses)
+        {
+            protected.insert(&edge.from);
+            protected.insert(&edge.to);
+        }
+        let kept: HashSet<&DecisionNodeId> = self
+            .nodes
+            .iter()
+            .filter(|n| reachable.contains(&n.id))
+            .filter(|n| protected.contains(&n.id) || n.confidence >= config.min_confidence)
+            .map(|n| &n.id)
+            .collect();
+
+        let mut incoming: HashMap<&DecisionNodeId, Vec<&TraceEdge>> = HashMap::new();
+        for edge in &live_edges {
+            incoming.entry(&edge.to).or_default().push(edge);
+        }
+        let nearest_kept = |start: &DecisionNodeId| -> Vec<DecisionNodeId> {
+            let mut found = Vec::new();
+            let mut seen = HashSet::new();
+            let mut stack = vec![start];
+            while let Some(id) = stack.pop() {
+                for edge in incoming.get(id).into_iter().flatten() {
+                    if kept.contains(&edge.from) {
+                        found.push(edge.from.clone());
+                    } else if seen.insert(&edge.from) {
+                        stack.push(&edge.from);
+                    }
+                }
+            }
+            found
+        };
+
+        let mut edges = Vec::new();
+        let mut linked = HashSet::new();
+        for edge in live_edges
+            .iter()
+            .filter(|e| kept.contains(&e.from) && kept.contains(&e.to))
+        {
+            linked.insert((edge.from.clone(), edge.to.clone()));
+            edges.push((*edge).clone());
+        }
+        for edge in live_edges
+            .iter()
+            .filter(|e| !kept.contains(&e.from) && kept.contains(&e.to))
+        {
+            for ancestor in nearest_kept(&edge.from) {
+                if linked.insert((ancestor.clone(), edge.to.clone())) {
+                    let mut reattached = (*edge).clone();
+                    reattached.from = ancestor;
+                    edges.push(reattached.with_metadata("reattached", true));
+                }
+            }
+        }
+
+        let mut pruned = self.clone();
+        pruned.nodes.retain(|n| kept.contains(&n.id));
+        pruned.edges = edges;
+        pruned.metadata.get_or_insert_with(HashMap::new).insert(
+            "pruned_nodes".into(),
+            (self.nodes.len() - kept.len()).into(),
+        );
+        pruned
+    }
+
+    // ==================== Merging ====================
+
+    /// Merge several traces into one graph under a synthetic root goal.
+    ///
+    /// Every node gets a fresh ID, so traces never collide. Each merged node
+    /// records its origin in metadata (`source_trace_id`, `source_node_id`,
+    /// `session_id`, `merge_index`); use [`merged_node_id`](Self::merged_node_id)
+    /// to find it again. The root of each input is linked from the synthetic
+    /// root with a `Spawns` edge.
+    ///
+    /// A trace whose ID repeats an earlier input is skipped if it is
+    /// identical to it, and otherwise merged as a separate subgraph,
+    /// distinguishable by `merge_index`.
+    pub fn merge(traces: &[ReasoningTrace]) -> ReasoningTrace {
+        let session_id = match traces.first() {
+            Some(first) if traces.iter().all(|t| t.session_id == first.session_id) => {
+                first.session_id.clone()
+            }
+            _ => "merged".to_string(),
+        };
+        let mut merged = ReasoningTrace::new(
+            format!("Merged {} reasoning traces", traces.len()),
+            session_id,
+        );
+        if let Some(earliest) = traces.iter().map(|t| t.created_at).min() {
+            merged.created_at = earliest;
+        }
+
+        let mut included: Vec<&ReasoningTrace> = Vec::new();
+        for trace in traces {
+            if included.iter().any(|t| t.id == trace.id && *t == trace) {
+                continue;
+            }
+            let merge_index = included.len();
+            included.push(trace);
+
+            let ids: HashMap<&DecisionNodeId, DecisionNodeId> = trace
+                .nodes
+                .iter()
+                .map(|n| (&n.id, DecisionNodeId::new()))
+                .collect();
+
+            for node in &trace.nodes {
+                let mut node = node.clone();
+                let metadata = node.metadata.get_or_insert_with(HashMap::new);
+                metadata.insert("source_trace_id".into(), trace.id.to_string().into());
+                metadata.insert("source_node_id".into(), node.id.to_string().into());
+                metadata.insert("session_id".into(), trace.session_id.clone().into());
+                metadata.insert("merge_index".into(), merge_index.into());
+                node.id = ids[&node.id].clone();
+                merged.nodes.push(node);
+            }
+
+            for edge in &trace.edges {
+                if let (Some(from), Some(to)) = (ids.get(&edge.from), ids.get(&edge.to)) {
+                    let mut edge = edge.clone();
+                    edge.from = from.clone();
+                    edge.to = to.clone();
+                    merged.edges.push(edge);
+                }
+            }
+
+            if let Some(root) = ids.get(&trace.root_goal) {
+                merged.edges.push(TraceEdge::new(
+                    merged.root_goal.clone(),
+                    root.clone(),
+                    TraceEdgeLabel::Spawns,
+                ));
+            }
+        }
+
+        let trace_ids: Vec<Value> = included.iter().map(|t| t.id.to_string().into()).collect();
+        merged
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert("merged_trace_ids".into(), trace_ids.into());
+        merged
+    }
+
+    /// Find the merged ID of `node_id` from the input trace `trace_id`.
+    ///
+    /// Only meaningful on the result of [`merge`](Self::merge). If several
+    /// inputs shared `trace_id`, the first one's node is returned.
+    pub fn merged_node_id(
+        &self,
+        trace_id: &TraceId,
+        node_id: &DecisionNodeId,
+    ) -> Option<DecisionNodeId> {
+        let (trace_id, node_id) = (trace_id.to_string(), node_id.to_string());
+        self.nodes
+            .iter()
+            .filter_map(|n| {
+                let metadata = n.metadata.as_ref()?;
+                let matches = metadata.get("source_trace_id")?.as_str()? == trace_id
+                    && metadata.get("source_node_id")?.as_str()? == node_id;
+                let index = metadata.get("merge_index")?.as_u64()?;
+                matches.then(|| (index, n.id.clone()))
+            })
+            .min_by_key(|(index, _)| *index)
+            .map(|(_, id)| id)
+    }
+
+    /// Link a node of one merged trace to a node of another, e.g. an
+    /// outcome of one agent that seeded another agent's goal.
+    ///
+    /// Adds a `LeadsTo` edge marked `cross_trace`. Returns `false` if
+    /// either node is not part of this merged trace.
+    pub fn add_cross_link(
+        &mut self,
+        from: (&TraceId, &DecisionNodeId),
+        to: (&TraceId, &DecisionNodeId),
+    ) -> bool {
+        let (Some(from), Some(to)) = (
+            self.merged_node_id(from.0, from.1),
+            self.merged_node_id(to.0, to.1),
+        ) else {
+            return false;
+        };
+        self.edges.push(
+            TraceEdge::new(from, to, TraceEdgeLabel::LeadsTo).with_metadata("cross_trace", true),
+        );
+        self.updated_at = Utc::now();
+        true
+    }
+
     /// Get trace statistics.
     pub fn stats(&self) -> TraceStats {
         let mut node_counts: HashMap<DecisionNodeType, usize> = HashMap::new();
@@ -557,6 +782,38 @@ impl<'a> Iterator for DfsIterator<'a> {
     }
 }
 
+/// Options for [`ReasoningTrace::prune`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PruneConfig {
+    /// Drop rejected options and everything reachable only through them.
+    pub drop_rejected: bool,
+    /// Remove nodes below this confidence, reattaching their descendants.
+    pub min_confidence: f64,
+}
+
+impl Default for PruneConfig {
+    fn default() -> Self {
+        Self {
+            drop_rejected: true,
+            min_confidence: 0.0,
+        }
+    }
+}
+
+impl PruneConfig {
+    /// Set the confidence threshold.
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// Keep rejected options.
+    pub fn keep_rejected(mut self) -> Self {
+        self.drop_rejected = false;
+        self
+    }
+}
+
 /// Statistics about a reasoning trace.
 #[derive(Debug, Clone)]
 pub struct TraceStats {
@@ -747,4 +1004,133 @@ mod tests {
                                     // First should be root
         assert_eq!(nodes[0].node_type, DecisionNodeType::Goal);
     }
+
+    #[test]
+    fn test_merge_traces_with_cross_link() {
+        let mut planner = ReasoningTrace::new("Plan the migration", "agent-planner");
+        let planner_root = planner.root_goal.clone();
+        let chosen = planner.log_decision(&planner_root, "Order", &["Schema first"], 0, "Safer");
+        let (_, plan_outcome) = planner.log_action(&chosen, "Write plan", "Plan ready");
+
+        let mut worker = ReasoningTrace::new("Migrate the schema", "agent-worker");
+        let worker_root = worker.root_goal.clone();
+        worker.log_observation(&worker_root, "Two tables need changes");
+
+        // An identical duplicate is dropped
+        let mut merged = ReasoningTrace::merge(&[planner.clone(), worker.clone(), planner.clone()]);
+        assert_eq!(merged.session_id, "merged");
+        assert_eq!(
+            merged.nodes.len(),
+            1 + planner.nodes.len() + worker.nodes.len()
+        );
+        assert_eq!(
+            merged.edges.len(),
+            planner.edges.len() + worker.edges.len() + 2
+        );
+        assert_eq!(merged.children(&merged.root_goal).len(), 2);
+
+        let seeded_goal = merged.merged_node_id(&worker.id, &worker_root).unwrap();
+        assert_ne!(seeded_goal, worker_root);
+        let node = merged.get_node(&seeded_goal).unwrap();
+        assert_eq!(node.content, "Migrate the schema");
+        assert_eq!(
+            node.metadata.as_ref().unwrap()["session_id"],
+            Value::from("agent-worker")
+        );
+
+        assert!(merged.add_cross_link((&planner.id, &plan_outcome), (&worker.id, &worker_root)));
+        assert!(!merged.add_cross_link((&worker.id, &plan_outcome), (&worker.id, &worker_root)));
+        let plan_node = merged.merged_node_id(&planner.id, &plan_outcome).unwrap();
+        let cross: Vec<_> = merged.edges_to(&seeded_goal);
+        assert_eq!(cross.len(), 2);
+        assert!(cross
+            .iter()
+            .any(|e| e.from == plan_node && e.label == TraceEdgeLabel::LeadsTo));
+
+        // Exporters see one connected graph
+        let graph = merged.to_networkx_graph();
+        assert_eq!(graph.nodes.len(), merged.nodes.len());
+        assert_eq!(graph.nodes.iter().filter(|n| n.is_root).count(), 1);
+    }
+
+    #[test]
+    fn test_merge_keeps_diverging_duplicate_ids() {
+        let trace = ReasoningTrace::new("Goal", "session-dup");
+        let mut diverged = trace.clone();
+        let root = diverged.root_goal.clone();
+        diverged.log_observation(&root, "Extra");
+
+        let merged = ReasoningTrace::merge(&[trace.clone(), diverged]);
+        assert_eq!(merged.session_id, "session-dup");
+        assert_eq!(merged.nodes.len(), 1 + 1 + 2);
+        let first = merged.merged_node_id(&trace.id, &root).unwrap();
+        let index = &merged.get_node(&first).unwrap().metadata.as_ref().unwrap()["merge_index"];
+        assert_eq!(index, &Value::from(0));
+    }
+
+    #[test]
+    fn test_prune_drops_rejected_branches_and_reattaches() {
+        let mut trace = ReasoningTrace::new("Ship the release", "session-prune");
+        let root = trace.root_goal.clone();
+        let chosen =
+            trace.log_decision(&root, "Release train", &["Weekly", "Ad hoc"], 0, "Cadence");
+        let rejected = trace
+            .edges
+            .iter()
+            .find(|e| e.label == TraceEdgeLabel::Rejects)
+            .unwrap()
+            .to
+            .clone();
+        // A confident leaf under the rejected option still goes
+        let (rejected_action, _) = trace.log_action(&rejected, "Script it", "Works fine");
+        trace.get_node_mut(&rejected_action).unwrap().confidence = 0.99;
+
+        let (action, outcome) = trace.log_action(&chosen, "Tag builds", "Tagged");
+        trace.get_node_mut(&action).unwrap().confidence = 0.2;
+
+        let pruned = trace.prune(&PruneConfig::default().with_min_confidence(0.5));
+
+        // Original untouched
+        assert_eq!(trace.nodes.len(), 8);
+        assert!(trace.get_node(&action).is_some());
+
+        // goal, decision, chosen option, outcome
+        assert_eq!(pruned.nodes.len(), 4);
+        assert!(pruned.get_node(&rejected).is_none());
+        assert!(pruned.get_node(&rejected_action).is_none());
+        assert!(pruned.get_node(&action).is_none());
+        assert_eq!(pruned.root_goal, root);
+        assert!(pruned.root().is_some());
+
+        // Outcome reattaches to the chosen option
+        let parent = pruned.edges_to(&outcome);
+        assert_eq!(parent.len(), 1);
+        assert_eq!(parent[0].from, chosen);
+        assert_eq!(parent[0].label, TraceEdgeLabel::Produces);
+        assert!(pruned
+            .edges
+            .iter()
+            .any(|e| e.to == chosen && e.label == TraceEdgeLabel::Chooses));
+        for edge in &pruned.edges {
+            assert!(pruned.get_node(&edge.from).is_some());
+            assert!(pruned.get_node(&edge.to).is_some());
+        }
+    }
+
+    #[test]
+    fn test_prune_protects_chosen_path_below_threshold() {
+        let mut trace = ReasoningTrace::new("Goal", "session-prune-2");
+        let root = trace.root_goal.clone();
+        let chosen = trace.log_decision(&root, "Pick", &["Only"], 0, "Forced");
+        for node in &mut trace.nodes {
+            node.confidence = 0.1;
+        }
+
+        let pruned = trace.prune(&PruneConfig::default().with_min_confidence(0.9));
+        assert_eq!(pruned.nodes.len(), 3);
+        assert!(pruned.get_node(&chosen).is_some());
+
+        let kept = trace.prune(&PruneConfig::default().keep_rejected());
+        assert_eq!(kept.nodes.len(), trace.nodes.len());
+    }
 }
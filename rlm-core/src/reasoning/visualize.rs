@@ -4,6 +4,10 @@
 //! - NetworkX-compatible JSON (SPEC-23.02)
 //! - DOT/Graphviz format (SPEC-23.01)
 //! - Interactive HTML with D3.js (SPEC-23.03)
+//! - GraphML for desktop graph tools (yEd, Gephi)
+//! - Cytoscape.js elements JSON
+//! - CSV node and edge tables for spreadsheets and pandas
+//! - Standalone SVG with a simple layered layout (no Graphviz needed)
 //!
 //! # Example
 //!
@@ -22,11 +26,24 @@
 //! let html = trace.to_html(HtmlConfig::default());
 //! ```
 
+use crate::reasoning::query::{DiffStatus, TraceDiff};
 use crate::reasoning::trace::ReasoningTrace;
-use crate::reasoning::types::{DecisionNodeType, TraceEdgeLabel};
+use crate::reasoning::types::{DecisionNodeId, DecisionNodeType, TraceEdgeLabel};
+use crate::repl::floor_char_boundary;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Maximum nodes per row in SVG output before a layer wraps.
+pub const SVG_MAX_COLUMNS: usize = 6;
+/// Maximum label length, in characters, in SVG output.
+pub const SVG_LABEL_CHARS: usize = 24;
+
+const SVG_NODE_WIDTH: f64 = 180.0;
+const SVG_NODE_HEIGHT: f64 = 48.0;
+const SVG_GAP_X: f64 = 30.0;
+const SVG_GAP_Y: f64 = 60.0;
+const SVG_MARGIN: f64 = 20.0;
+
 /// Theme presets for HTML visualization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HtmlTheme {
@@ -73,6 +90,8 @@ pub struct HtmlConfig {
     pub node_colors: HashMap<DecisionNodeType, String>,
     /// Custom CSS to inject.
     pub custom_css: Option<String>,
+    /// Server-sent events URL delivering trace patches to apply live.
+    pub patch_stream_url: Option<String>,
 }
 
 impl Default for HtmlConfig {
@@ -102,6 +121,7 @@ impl Default for HtmlConfig {
             theme: HtmlTheme::Dark,
             node_colors,
             custom_css: None,
+            patch_stream_url: None,
         }
     }
 }
@@ -202,6 +222,15 @@ impl HtmlConfig {
         self.custom_css = Some(css.into());
         self
     }
+
+    /// Subscribe the page to a server-sent events stream of trace patches.
+    ///
+    /// Each event's data must be a JSON [`TraceHtmlPatch`]; it is passed to
+    /// `window.applyTracePatch`.
+    pub fn with_patch_stream(mut self, url: impl Into<String>) -> Self {
+        self.patch_stream_url = Some(url.into());
+        self
+    }
 }
 
 /// DOT export configuration.
@@ -322,6 +351,80 @@ pub struct NetworkXLink {
     pub metadata: Option<serde_json::Value>,
 }
 
+/// Cytoscape.js graph (`cy.add(graph.elements)` or `cytoscape({ elements })`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CytoscapeGraph {
+    /// Node and edge elements.
+    pub elements: CytoscapeElements,
+}
+
+/// Elements of a Cytoscape.js graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CytoscapeElements {
+    /// Node elements.
+    pub nodes: Vec<CytoscapeElement<CytoscapeNodeData>>,
+    /// Edge elements.
+    pub edges: Vec<CytoscapeElement<CytoscapeEdgeData>>,
+}
+
+/// A Cytoscape.js element; attributes live under `data`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CytoscapeElement<T> {
+    /// Element attributes.
+    pub data: T,
+}
+
+/// Data for a Cytoscape.js node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CytoscapeNodeData {
+    /// Node ID (UUID string).
+    pub id: String,
+    /// Node content, used as the display label.
+    pub label: String,
+    /// Node type (goal, decision, option, etc.).
+    pub node_type: String,
+    /// Confidence score.
+    pub confidence: f64,
+    /// Fill color for the node type.
+    pub color: String,
+    /// Whether this is the root node.
+    pub is_root: bool,
+}
+
+/// Data for a Cytoscape.js edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CytoscapeEdgeData {
+    /// Edge ID, derived from its endpoints and label.
+    pub id: String,
+    /// Source node ID.
+    pub source: String,
+    /// Target node ID.
+    pub target: String,
+    /// Edge label/type.
+    pub label: String,
+    /// Edge weight.
+    pub weight: f64,
+}
+
+/// Incremental update for a trace rendered with [`ReasoningTrace::to_html`].
+///
+/// Node IDs are the same UUID strings used in the initial render, and the page
+/// identifies links by `source`, `target` and `label`, so applying a patch
+/// twice (or one that overlaps the initial render) is harmless.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceHtmlPatch {
+    /// Trace the patch belongs to; the page ignores patches for other traces.
+    pub trace_id: String,
+    /// Node count the patch was computed from.
+    pub since_node_count: usize,
+    /// Total node count after applying the patch.
+    pub node_count: usize,
+    /// New nodes.
+    pub nodes: Vec<NetworkXNode>,
+    /// Edges touching at least one new node.
+    pub links: Vec<NetworkXLink>,
+}
+
 impl ReasoningTrace {
     /// Export to NetworkX-compatible JSON format.
     ///
@@ -505,6 +608,427 @@ impl ReasoningTrace {
         dot
     }
 
+    /// Build the delta for nodes added after the first `since_node_count`.
+    ///
+    /// Edges are included when either endpoint is a new node. Edges added
+    /// later between two already-rendered nodes (e.g. via `add_reference`)
+    /// are not part of the patch.
+    pub fn to_html_patch_data(&self, since_node_count: usize) -> TraceHtmlPatch {
+        let graph = self.to_networkx_graph();
+        let since = since_node_count.min(graph.nodes.len());
+        let nodes: Vec<NetworkXNode> = graph.nodes.into_iter().skip(since).collect();
+        let new_ids: std::collections::HashSet<&str> =
+            nodes.iter().map(|n| n.id.as_str()).collect();
+        let links = graph
+            .links
+            .into_iter()
+            .filter(|l| new_ids.contains(l.source.as_str()) || new_ids.contains(l.target.as_str()))
+            .collect();
+
+        TraceHtmlPatch {
+            trace_id: graph.graph.trace_id,
+            since_node_count: since,
+            node_count: self.nodes.len(),
+            nodes,
+            links,
+        }
+    }
+
+    /// JSON delta for `window.applyTracePatch` in the page from [`to_html`](Self::to_html).
+    ///
+    /// A driver keeps the node count it last sent, calls this with it, and
+    /// delivers the result to the page, for example through the stream
+    /// configured with [`HtmlConfig::with_patch_stream`].
+    pub fn to_html_patch(&self, since_node_count: usize) -> String {
+        serde_json::to_string(&self.to_html_patch_data(since_node_count))
+            .unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Export to Cytoscape.js elements JSON, colored like the default DOT export.
+    pub fn to_cytoscape_json(&self) -> String {
+        let graph = self.to_cytoscape_graph(&DotConfig::default());
+        serde_json::to_string_pretty(&graph).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Convert to Cytoscape.js graph structure, using `config.node_colors`.
+    ///
+    /// Edge IDs are built from the endpoints and label, so they stay the same
+    /// across exports of the same trace; repeated edges get a numeric suffix.
+    pub fn to_cytoscape_graph(&self, config: &DotConfig) -> CytoscapeGraph {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|n| CytoscapeElement {
+                data: CytoscapeNodeData {
+                    id: n.id.0.to_string(),
+                    label: n.content.clone(),
+                    node_type: n.node_type.to_string(),
+                    confidence: n.confidence,
+                    color: config
+                        .node_colors
+                        .get(&n.node_type)
+                        .cloned()
+                        .unwrap_or_else(|| "#FFFFFF".to_string()),
+                    is_root: n.id == self.root_goal,
+                },
+            })
+            .collect();
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let edges = self
+            .edges
+            .iter()
+            .map(|e| {
+                let base = format!(
+                    "e{}-{}-{}",
+                    e.from.0.as_simple(),
+                    e.label,
+                    e.to.0.as_simple()
+                );
+                let count = seen.entry(base.clone()).or_insert(0);
+                *count += 1;
+                let id = if *count == 1 {
+                    base
+                } else {
+                    format!("{}-{}", base, count)
+                };
+
+                CytoscapeElement {
+                    data: CytoscapeEdgeData {
+                        id,
+                        source: e.from.0.to_string(),
+                        target: e.to.0.to_string(),
+                        label: e.label.to_string(),
+                        weight: e.weight,
+                    },
+                }
+            })
+            .collect();
+
+        CytoscapeGraph {
+            elements: CytoscapeElements { nodes, edges },
+        }
+    }
+
+    /// Export to SVG using a simple layered layout.
+    ///
+    /// Nodes are ranked by their distance from the root goal, so goals,
+    /// decisions, options, actions, and outcomes fall into successive layers.
+    /// Layers wider than [`SVG_MAX_COLUMNS`] wrap onto extra rows, and labels
+    /// are truncated to [`SVG_LABEL_CHARS`] characters with the full content
+    /// kept in a `<title>` tooltip. Colors, shapes, and the font come from
+    /// `config`; `rankdir = "LR"` lays layers out left to right.
+    pub fn to_svg(&self, config: &DotConfig) -> String {
+        let layers = self.svg_layers();
+        let left_to_right = config.rankdir == "LR";
+
+        // (row, column) of each node, with wrapped layers spanning rows
+        let mut positions: HashMap<&DecisionNodeId, (usize, usize)> = HashMap::new();
+        let mut row = 0;
+        let mut max_columns = 1;
+        for layer in &layers {
+            for (i, id) in layer.iter().enumerate() {
+                positions.insert(id, (row + i / SVG_MAX_COLUMNS, i % SVG_MAX_COLUMNS));
+            }
+            max_columns = max_columns.max(layer.len().min(SVG_MAX_COLUMNS));
+            row += layer.len().div_ceil(SVG_MAX_COLUMNS).max(1);
+        }
+        let rows = row.max(1);
+
+        // Layers advance along y (TB) or x (LR); columns run across them.
+        let (step_x, step_y) = (SVG_NODE_WIDTH + SVG_GAP_X, SVG_NODE_HEIGHT + SVG_GAP_Y);
+        let center = |id: &DecisionNodeId| -> (f64, f64) {
+            let (row, column) = positions[id];
+            let (x_slot, y_slot) = if left_to_right {
+                (row, column)
+            } else {
+                (column, row)
+            };
+            (
+                SVG_MARGIN + x_slot as f64 * step_x + SVG_NODE_WIDTH / 2.0,
+                SVG_MARGIN + y_slot as f64 * step_y + SVG_NODE_HEIGHT / 2.0,
+            )
+        };
+        let (x_slots, y_slots) = if left_to_right {
+            (rows, max_columns)
+        } else {
+            (max_columns, rows)
+        };
+        let width = 2.0 * SVG_MARGIN + x_slots as f64 * step_x - SVG_GAP_X;
+        let height = 2.0 * SVG_MARGIN + y_slots as f64 * step_y - SVG_GAP_Y;
+
+        let mut svg = String::new();
+        svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        svg.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\" font-family=\"{}\" font-size=\"{}\">\n",
+            escape_xml(&config.font_name),
+            config.font_size,
+            w = width,
+            h = height,
+        ));
+        svg.push_str(&format!(
+            "  <title>{}</title>\n",
+            escape_xml(&self.id.to_string())
+        ));
+        svg.push_str(
+            "  <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+             markerWidth=\"8\" markerHeight=\"8\" orient=\"auto-start-reverse\">\
+             <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"context-stroke\"/></marker></defs>\n",
+        );
+
+        for edge in &self.edges {
+            if !positions.contains_key(&edge.from) || !positions.contains_key(&edge.to) {
+                continue;
+            }
+            let ((x1, y1), (x2, y2)) = (center(&edge.from), center(&edge.to));
+            let (x1, y1, x2, y2) = if left_to_right {
+                (x1 + SVG_NODE_WIDTH / 2.0, y1, x2 - SVG_NODE_WIDTH / 2.0, y2)
+            } else {
+                (
+                    x1,
+                    y1 + SVG_NODE_HEIGHT / 2.0,
+                    x2,
+                    y2 - SVG_NODE_HEIGHT / 2.0,
+                )
+            };
+            let (color, dash) = svg_edge_style(edge.label);
+            svg.push_str(&format!(
+                "  <line class=\"edge {}\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" \
+                 stroke=\"{}\"{} marker-end=\"url(#arrow)\"/>\n",
+                edge.label,
+                x1,
+                y1,
+                x2,
+                y2,
+                color,
+                dash.map(|d| format!(" stroke-dasharray=\"{}\"", d))
+                    .unwrap_or_default(),
+            ));
+        }
+
+        for node in &self.nodes {
+            let (cx, cy) = center(&node.id);
+            let fill = if config.filled_nodes {
+                config
+                    .node_colors
+                    .get(&node.node_type)
+                    .map(|s| s.as_str())
+                    .unwrap_or("#FFFFFF")
+            } else {
+                "none"
+            };
+            let stroke_width = if node.id == self.root_goal { 3 } else { 1 };
+            svg.push_str(&format!(
+                "  <g class=\"node {}\" id=\"n{}\">\n",
+                node.node_type,
+                node.id.0.as_simple()
+            ));
+            svg.push_str(&format!(
+                "    <title>{}</title>\n",
+                escape_xml(&node.content)
+            ));
+            svg.push_str(&format!(
+                "    {}\n",
+                svg_node_shape(node.node_type, cx, cy, fill, stroke_width)
+            ));
+            svg.push_str(&format!(
+                "    <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" \
+                 dominant-baseline=\"middle\">{}</text>\n",
+                cx,
+                cy,
+                escape_xml(&truncate_label(&node.content, SVG_LABEL_CHARS))
+            ));
+            svg.push_str("  </g>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Group node IDs into layers by breadth-first distance from the root.
+    ///
+    /// Nodes unreachable from the root go in a final layer.
+    fn svg_layers(&self) -> Vec<Vec<&DecisionNodeId>> {
+        let mut depth: HashMap<&DecisionNodeId, usize> = HashMap::new();
+        let mut layers: Vec<Vec<&DecisionNodeId>> = Vec::new();
+        let mut queue = std::collections::VecDeque::new();
+        if self.get_node(&self.root_goal).is_some() {
+            depth.insert(&self.root_goal, 0);
+            queue.push_back(&self.root_goal);
+        }
+        while let Some(id) = queue.pop_front() {
+            let d = depth[id];
+            if layers.len() <= d {
+                layers.resize_with(d + 1, Vec::new);
+            }
+            layers[d].push(id);
+            for edge in self.edges.iter().filter(|e| &e.from == id) {
+                if self.get_node(&edge.to).is_some() && !depth.contains_key(&edge.to) {
+                    depth.insert(&edge.to, d + 1);
+                    queue.push_back(&edge.to);
+                }
+            }
+        }
+
+        let unreachable: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|n| &n.id)
+            .filter(|id| !depth.contains_key(id))
+            .collect();
+        if !unreachable.is_empty() {
+            layers.push(unreachable);
+        }
+        layers
+    }
+
+    /// Export to GraphML.
+    ///
+    /// Node attributes (`node_type`, `content`, `confidence`, `is_root`) and
+    /// edge attributes (`label`, `weight`) are declared as `<key>`s so tools
+    /// like yEd and Gephi pick them up. Type and label strings match the
+    /// NetworkX export.
+    pub fn to_graphml(&self) -> String {
+        let graph = self.to_networkx_graph();
+        let mut xml = String::new();
+
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n",
+        );
+        for (id, domain, name, ty) in [
+            ("node_type", "node", "node_type", "string"),
+            ("content", "node", "content", "string"),
+            ("confidence", "node", "confidence", "double"),
+            ("is_root", "node", "is_root", "boolean"),
+            ("label", "edge", "label", "string"),
+            ("weight", "edge", "weight", "double"),
+        ] {
+            xml.push_str(&format!(
+                "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+                id, domain, name, ty
+            ));
+        }
+
+        xml.push_str(&format!(
+            "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+            escape_xml(&graph.graph.trace_id)
+        ));
+
+        for node in &graph.nodes {
+            xml.push_str(&format!("    <node id=\"{}\">\n", escape_xml(&node.id)));
+            xml.push_str(&format!(
+                "      <data key=\"node_type\">{}</data>\n",
+                escape_xml(&node.node_type)
+            ));
+            xml.push_str(&format!(
+                "      <data key=\"content\">{}</data>\n",
+                escape_xml(&node.content)
+            ));
+            xml.push_str(&format!(
+                "      <data key=\"confidence\">{}</data>\n",
+                node.confidence
+            ));
+            xml.push_str(&format!(
+                "      <data key=\"is_root\">{}</data>\n",
+                node.is_root
+            ));
+            xml.push_str("    </node>\n");
+        }
+
+        for (i, link) in graph.links.iter().enumerate() {
+            xml.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+                i,
+                escape_xml(&link.source),
+                escape_xml(&link.target)
+            ));
+            xml.push_str(&format!(
+                "      <data key=\"label\">{}</data>\n",
+                escape_xml(&link.label)
+            ));
+            xml.push_str(&format!(
+                "      <data key=\"weight\">{}</data>\n",
+                link.weight
+            ));
+            xml.push_str("    </edge>\n");
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    /// Export nodes as CSV, one row per node.
+    ///
+    /// Columns follow [`NetworkXNode`], except that `metadata` is split:
+    /// `cost_usd` and `timing_ms` get their own columns and the remaining
+    /// keys are written as a JSON object. Fields are quoted per RFC 4180.
+    pub fn to_csv_nodes(&self) -> String {
+        let mut csv = csv_row(&[
+            "id",
+            "node_type",
+            "content",
+            "confidence",
+            "reason",
+            "created_at",
+            "is_root",
+            "cost_usd",
+            "timing_ms",
+            "metadata",
+        ]);
+        for node in self.to_networkx_graph().nodes {
+            let [cost, timing, rest] = flatten_csv_metadata(node.metadata.as_ref());
+            csv.push_str(&csv_row(&[
+                &node.id,
+                &node.node_type,
+                &node.content,
+                &node.confidence.to_string(),
+                node.reason.as_deref().unwrap_or(""),
+                &node.created_at,
+                &node.is_root.to_string(),
+                &cost,
+                &timing,
+                &rest,
+            ]));
+        }
+        csv
+    }
+
+    /// Export edges as CSV, one row per edge.
+    ///
+    /// Columns follow [`NetworkXLink`], with `metadata` split as in
+    /// [`to_csv_nodes`](Self::to_csv_nodes).
+    pub fn to_csv_edges(&self) -> String {
+        let mut csv = csv_row(&[
+            "source",
+            "target",
+            "label",
+            "weight",
+            "created_at",
+            "cost_usd",
+            "timing_ms",
+            "metadata",
+        ]);
+        for link in self.to_networkx_graph().links {
+            let [cost, timing, rest] = flatten_csv_metadata(link.metadata.as_ref());
+            csv.push_str(&csv_row(&[
+                &link.source,
+                &link.target,
+                &link.label,
+                &link.weight.to_string(),
+                &link.created_at,
+                &cost,
+                &timing,
+                &rest,
+            ]));
+        }
+        csv
+    }
+
     /// Export to interactive HTML with D3.js visualization.
     ///
     /// Produces a self-contained HTML file with an interactive force-directed
@@ -530,13 +1054,87 @@ impl ReasoningTrace {
     }
 }
 
+impl TraceDiff {
+    /// Export the diff as DOT.
+    ///
+    /// Added nodes and edges are green, removed ones red and dashed, changed
+    /// ones orange, and unchanged ones grey. Edges whose decision picked a
+    /// different option are labelled "changed choice".
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph TraceDiff {\n");
+        dot.push_str("    rankdir=TB;\n");
+        dot.push_str("    node [fontname=\"Helvetica\", fontsize=12, style=filled];\n");
+        dot.push_str("    edge [fontname=\"Helvetica\", fontsize=10];\n\n");
+        dot.push_str(&format!("    // Baseline: {}\n", self.baseline));
+        dot.push_str(&format!("    // Current: {}\n\n", self.current));
+
+        for diff_node in &self.nodes {
+            let node = &diff_node.node;
+            let mut label = truncate_string(&node.content, 40);
+            if let Some(ref before) = diff_node.baseline_content {
+                label.push_str(&format!("\n(was: {})", truncate_string(before, 40)));
+            }
+            let (fill, border, style) = match diff_node.status {
+                DiffStatus::Added => ("#C8E6C9", "#2E7D32", "filled"),
+                DiffStatus::Removed => ("#FFCDD2", "#C62828", "\"filled,dashed\""),
+                DiffStatus::Changed => ("#FFE0B2", "#EF6C00", "filled"),
+                DiffStatus::Unchanged => ("#EEEEEE", "#9E9E9E", "filled"),
+            };
+
+            dot.push_str(&format!(
+                "    n{} [label=\"{}\", shape={}, fillcolor=\"{}\", color=\"{}\", style={}];\n",
+                node.id.0.as_simple(),
+                escape_dot_string(&label),
+                node_type_to_dot_shape(node.node_type),
+                fill,
+                border,
+                style
+            ));
+        }
+
+        dot.push('\n');
+
+        for edge in &self.edges {
+            let label = match (edge.is_changed_choice(), edge.baseline_label) {
+                (true, Some(before)) => format!("{} (changed choice, was {})", edge.label, before),
+                (false, Some(before)) => format!("{} (was {})", edge.label, before),
+                _ => edge.label.to_string(),
+            };
+            let style = match edge.status {
+                DiffStatus::Added => "color=\"#2E7D32\", penwidth=2",
+                DiffStatus::Removed => "color=\"#C62828\", style=dashed",
+                DiffStatus::Changed if edge.is_changed_choice() => {
+                    "color=\"#EF6C00\", fontcolor=\"#EF6C00\", penwidth=3, style=bold"
+                }
+                DiffStatus::Changed => "color=\"#EF6C00\", penwidth=2",
+                DiffStatus::Unchanged => "color=\"#9E9E9E\"",
+            };
+
+            dot.push_str(&format!(
+                "    n{} -> n{} [label=\"{}\", {}];\n",
+                edge.from.0.as_simple(),
+                edge.to.0.as_simple(),
+                escape_dot_string(&label),
+                style
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
 // Helper functions
 
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        format!(
+            "{}...",
+            &s[..floor_char_boundary(s, max_len.saturating_sub(3))]
+        )
     }
 }
 
@@ -546,6 +1144,89 @@ fn escape_dot_string(s: &str) -> String {
         .replace('\n', "\\n")
 }
 
+/// Metadata keys given their own CSV columns.
+const CSV_METADATA_COLUMNS: [&str; 2] = ["cost_usd", "timing_ms"];
+
+/// Split metadata into `[cost_usd, timing_ms, remaining keys as JSON]`.
+///
+/// Missing values are empty strings.
+fn flatten_csv_metadata(metadata: Option<&serde_json::Value>) -> [String; 3] {
+    let Some(serde_json::Value::Object(map)) = metadata else {
+        return [
+            String::new(),
+            String::new(),
+            metadata.map(|m| m.to_string()).unwrap_or_default(),
+        ];
+    };
+
+    let column = |key: &str| match map.get(key) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    };
+    let rest: serde_json::Map<String, serde_json::Value> = map
+        .iter()
+        .filter(|(key, _)| !CSV_METADATA_COLUMNS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let rest = if rest.is_empty() {
+        String::new()
+    } else {
+        serde_json::Value::Object(rest).to_string()
+    };
+
+    [
+        column(CSV_METADATA_COLUMNS[0]),
+        column(CSV_METADATA_COLUMNS[1]),
+        rest,
+    ]
+}
+
+/// Format one CSV record terminated by CRLF.
+///
+/// Fields containing commas, quotes, or line breaks are quoted, with inner
+/// quotes doubled.
+fn csv_row(fields: &[&str]) -> String {
+    let mut row = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            row.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            row.push('"');
+            row.push_str(&field.replace('"', "\"\""));
+            row.push('"');
+        } else {
+            row.push_str(field);
+        }
+    }
+    row.push_str("\r\n");
+    row
+}
+
+/// Escape text for XML element content and attribute values.
+///
+/// Newlines are kept literally; carriage returns are written as character
+/// references so parsers don't normalize them away, and characters that are
+/// not allowed in XML 1.0 are dropped.
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\r' => out.push_str("&#13;"),
+            '\n' | '\t' => out.push(c),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 fn node_type_to_dot_shape(node_type: DecisionNodeType) -> &'static str {
     match node_type {
         DecisionNodeType::Goal => "doubleoctagon",
@@ -572,6 +1253,100 @@ fn edge_label_to_dot_style(label: TraceEdgeLabel) -> &'static str {
     }
 }
 
+/// Truncate to `max_chars` characters, ending with "..." when cut.
+fn truncate_label(s: &str, max_chars: usize) -> String {
+    let s = s.lines().next().unwrap_or("");
+    if s.chars().count() <= max_chars {
+        s.to_string()
+    } else {
+        let kept: String = s.chars().take(max_chars.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    }
+}
+
+/// SVG shape approximating [`node_type_to_dot_shape`], centered on `(cx, cy)`.
+fn svg_node_shape(
+    node_type: DecisionNodeType,
+    cx: f64,
+    cy: f64,
+    fill: &str,
+    stroke_width: u32,
+) -> String {
+    let (hw, hh) = (SVG_NODE_WIDTH / 2.0, SVG_NODE_HEIGHT / 2.0);
+    let style = format!(
+        "fill=\"{}\" stroke=\"#333333\" stroke-width=\"{}\"",
+        fill, stroke_width
+    );
+    let polygon = |points: &[(f64, f64)]| {
+        let points: Vec<String> = points
+            .iter()
+            .map(|(x, y)| format!("{},{}", cx + x, cy + y))
+            .collect();
+        format!("<polygon points=\"{}\" {}/>", points.join(" "), style)
+    };
+
+    match node_type_to_dot_shape(node_type) {
+        "doubleoctagon" => {
+            let c = hh / 2.0;
+            polygon(&[
+                (-hw + c, -hh),
+                (hw - c, -hh),
+                (hw, -hh + c),
+                (hw, hh - c),
+                (hw - c, hh),
+                (-hw + c, hh),
+                (-hw, hh - c),
+                (-hw, -hh + c),
+            ])
+        }
+        "diamond" => polygon(&[(0.0, -hh), (hw, 0.0), (0.0, hh), (-hw, 0.0)]),
+        "parallelogram" => {
+            let slant = hh / 2.0;
+            polygon(&[(-hw + slant, -hh), (hw, -hh), (hw - slant, hh), (-hw, hh)])
+        }
+        "ellipse" => format!(
+            "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\" {}/>",
+            cx, cy, hw, hh, style
+        ),
+        "note" => {
+            let fold = hh / 2.0;
+            polygon(&[
+                (-hw, -hh),
+                (hw - fold, -hh),
+                (hw, -hh + fold),
+                (hw, hh),
+                (-hw, hh),
+            ])
+        }
+        _ => format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+            cx - hw,
+            cy - hh,
+            SVG_NODE_WIDTH,
+            SVG_NODE_HEIGHT,
+            style
+        ),
+    }
+}
+
+/// Stroke color and optional dash pattern, matching [`edge_label_to_dot_style`].
+fn svg_edge_style(label: TraceEdgeLabel) -> (&'static str, Option<&'static str>) {
+    let style = edge_label_to_dot_style(label);
+    let color = style
+        .split("color=\"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap_or("#333333");
+    let dash = if style.contains("style=dashed") {
+        Some("6,4")
+    } else if style.contains("style=dotted") {
+        Some("2,3")
+    } else {
+        None
+    };
+    (color, dash)
+}
+
 fn generate_html(graph_json: &str, config: &HtmlConfig) -> String {
     let node_colors_json = serde_json::to_string(
         &config
@@ -1040,76 +1815,138 @@ fn generate_html(graph_json: &str, config: &HtmlConfig) -> String {
             .force("center", d3.forceCenter(config.width / 2, config.height / 2))
             .force("collision", d3.forceCollide().radius(40));
 
-        // Draw links
-        const link = container.append("g")
-            .attr("class", "links")
-            .selectAll("path")
-            .data(links)
-            .join("path")
-            .attr("class", d => `link ${{d.label}}`)
-            .attr("stroke", d => getLinkColor(d.label))
-            .attr("stroke-width", d => getLinkWidth(d.label))
-            .attr("stroke-dasharray", d => getLinkDash(d.label))
-            .attr("marker-end", "url(#arrow)");
-
-        // Draw link labels
-        const linkLabel = container.append("g")
-            .attr("class", "link-labels")
-            .selectAll("text")
-            .data(links)
-            .join("text")
-            .attr("class", "link-label")
-            .text(d => d.label)
-            .style("opacity", showEdgeLabels ? 1 : 0);
-
-        // Draw nodes
-        const node = container.append("g")
-            .attr("class", "nodes")
-            .selectAll("g")
-            .data(nodes)
-            .join("g")
-            .attr("class", d => `node ${{d.is_root ? 'root' : ''}}`)
-            .call(d3.drag()
-                .on("start", dragstarted)
-                .on("drag", dragged)
-                .on("end", dragended));
-
-        node.append("circle")
-            .attr("r", d => d.is_root ? 25 : 20)
-            .attr("fill", d => nodeColors[d.node_type] || "#ccc");
-
-        // Node labels
-        const nodeLabel = node.append("text")
-            .attr("dy", 35)
-            .text(d => truncate(d.content, 20))
-            .style("opacity", showLabels ? 1 : 0);
-
         // Tooltip
         const tooltip = d3.select("#tooltip");
 
-        node.on("mouseenter", (event, d) => {{
-            const html = `
-                <span class="type" style="background: ${{nodeColors[d.node_type] || '#ccc'}}">${{d.node_type}}</span>
-                <h3>${{escapeHtml(d.content)}}</h3>
-                ${{d.reason ? `<p><strong>Reason:</strong> ${{escapeHtml(d.reason)}}</p>` : ''}}
-                <p><strong>Confidence:</strong> ${{(d.confidence * 100).toFixed(0)}}%</p>
-                ${{config.showCostBadges && d.metadata && d.metadata.cost_usd !== undefined ? `<p><strong>Cost:</strong> $${{Number(d.metadata.cost_usd).toFixed(4)}}</p>` : ''}}
-                ${{config.showTimingBadges && d.metadata && d.metadata.timing_ms !== undefined ? `<p><strong>Timing:</strong> ${{d.metadata.timing_ms}} ms</p>` : ''}}
-                <p><strong>Created:</strong> ${{new Date(d.created_at).toLocaleString()}}</p>
-            `;
-            tooltip.html(html)
-                .style("left", (event.pageX + 15) + "px")
-                .style("top", (event.pageY - 10) + "px")
-                .classed("visible", true);
-        }})
-        .on("mouseleave", () => {{
-            tooltip.classed("visible", false);
-        }})
-        .on("click", (_, d) => {{
-            if (config.showDetailsPanel) {{
-                renderDetails(d);
+        const linkGroup = container.append("g").attr("class", "links");
+        const linkLabelGroup = container.append("g").attr("class", "link-labels");
+        const nodeGroup = container.append("g").attr("class", "nodes");
+        let link, linkLabel, node, nodeLabel;
+
+        // Draw (or, after a patch, extend) the graph. Elements are keyed by
+        // node id and by source/target/label so existing ones are kept.
+        function render() {{
+            link = linkGroup.selectAll("path")
+                .data(links, linkKey)
+                .join("path")
+                .attr("class", d => `link ${{d.label}}`)
+                .attr("stroke", d => getLinkColor(d.label))
+                .attr("stroke-width", d => getLinkWidth(d.label))
+                .attr("stroke-dasharray", d => getLinkDash(d.label))
+                .attr("marker-end", "url(#arrow)");
+
+            linkLabel = linkLabelGroup.selectAll("text")
+                .data(links, linkKey)
+                .join("text")
+                .attr("class", "link-label")
+                .text(d => d.label)
+                .style("opacity", showEdgeLabels ? 1 : 0);
+
+            node = nodeGroup.selectAll("g.node")
+                .data(nodes, d => d.id)
+                .join(enter => {{
+                    const g = enter.append("g")
+                        .attr("class", d => `node ${{d.is_root ? 'root' : ''}}`)
+                        .call(d3.drag()
+                            .on("start", dragstarted)
+                            .on("drag", dragged)
+                            .on("end", dragended));
+                    g.append("circle")
+                        .attr("r", d => d.is_root ? 25 : 20)
+                        .attr("fill", d => nodeColors[d.node_type] || "#ccc");
+                    g.append("text")
+                        .attr("dy", 35)
+                        .text(d => truncate(d.content, 20));
+                    bindNodeEvents(g);
+                    return g;
+                }});
+
+            nodeLabel = node.select("text")
+                .style("opacity", showLabels ? 1 : 0);
+        }}
+
+        render();
+
+        // Merge a delta from ReasoningTrace::to_html_patch into the live graph
+        // and re-run the layout. Returns false if the patch is for another trace.
+        function applyTracePatch(patch) {{
+            if (typeof patch === "string") {{
+                patch = JSON.parse(patch);
             }}
-        }});
+            if (patch.trace_id && patch.trace_id !== graphData.graph.trace_id) {{
+                console.warn("Ignoring patch for trace", patch.trace_id);
+                return false;
+            }}
+
+            const byId = new Map(nodes.map(n => [n.id, n]));
+            (patch.nodes || []).forEach(d => {{
+                if (byId.has(d.id)) {{
+                    return;
+                }}
+                // Start new nodes next to an existing neighbour instead of the origin.
+                const anchorLink = (patch.links || []).find(l =>
+                    (l.target === d.id && byId.has(l.source)) || (l.source === d.id && byId.has(l.target)));
+                const anchor = anchorLink && byId.get(anchorLink.target === d.id ? anchorLink.source : anchorLink.target);
+                const n = {{...d}};
+                if (anchor) {{
+                    n.x = anchor.x + (Math.random() - 0.5) * 40;
+                    n.y = anchor.y + (Math.random() - 0.5) * 40;
+                }}
+                nodes.push(n);
+                byId.set(n.id, n);
+                graphData.nodes.push(d);
+            }});
+
+            const known = new Set(links.map(linkKey));
+            (patch.links || []).forEach(d => {{
+                const key = linkKey(d);
+                if (known.has(key) || !byId.has(d.source) || !byId.has(d.target)) {{
+                    return;
+                }}
+                links.push({{...d, source: byId.get(d.source), target: byId.get(d.target)}});
+                graphData.links.push(d);
+                known.add(key);
+            }});
+
+            render();
+            simulation.nodes(nodes);
+            simulation.force("link").links(links);
+            simulation.alpha(0.5).restart();
+            renderStats();
+            return true;
+        }}
+        window.applyTracePatch = applyTracePatch;
+
+        const patchStreamUrl = {patch_stream_url};
+        if (patchStreamUrl && window.EventSource) {{
+            new EventSource(patchStreamUrl).onmessage = (event) => applyTracePatch(event.data);
+        }}
+
+        function bindNodeEvents(selection) {{
+            selection.on("mouseenter", (event, d) => {{
+                const html = `
+                    <span class="type" style="background: ${{nodeColors[d.node_type] || '#ccc'}}">${{d.node_type}}</span>
+                    <h3>${{escapeHtml(d.content)}}</h3>
+                    ${{d.reason ? `<p><strong>Reason:</strong> ${{escapeHtml(d.reason)}}</p>` : ''}}
+                    <p><strong>Confidence:</strong> ${{(d.confidence * 100).toFixed(0)}}%</p>
+                    ${{config.showCostBadges && d.metadata && d.metadata.cost_usd !== undefined ? `<p><strong>Cost:</strong> $${{Number(d.metadata.cost_usd).toFixed(4)}}</p>` : ''}}
+                    ${{config.showTimingBadges && d.metadata && d.metadata.timing_ms !== undefined ? `<p><strong>Timing:</strong> ${{d.metadata.timing_ms}} ms</p>` : ''}}
+                    <p><strong>Created:</strong> ${{new Date(d.created_at).toLocaleString()}}</p>
+                `;
+                tooltip.html(html)
+                    .style("left", (event.pageX + 15) + "px")
+                    .style("top", (event.pageY - 10) + "px")
+                    .classed("visible", true);
+            }})
+            .on("mouseleave", () => {{
+                tooltip.classed("visible", false);
+            }})
+            .on("click", (_, d) => {{
+                if (config.showDetailsPanel) {{
+                    renderDetails(d);
+                }}
+            }});
+        }}
 
         // Simulation tick
         simulation.on("tick", () => {{
@@ -1134,11 +1971,14 @@ fn generate_html(graph_json: &str, config: &HtmlConfig) -> String {
 
         // Stats
         const stats = d3.select("#stats");
-        stats.html(`
-            <span><strong>Nodes:</strong> ${{nodes.length}}</span>
-            <span><strong>Edges:</strong> ${{links.length}}</span>
-            <span><strong>Session:</strong> ${{graphData.graph.session_id}}</span>
-        `);
+        function renderStats() {{
+            stats.html(`
+                <span><strong>Nodes:</strong> ${{nodes.length}}</span>
+                <span><strong>Edges:</strong> ${{links.length}}</span>
+                <span><strong>Session:</strong> ${{graphData.graph.session_id}}</span>
+            `);
+        }}
+        renderStats();
 
         const copyButton = document.getElementById("copy-content");
         if (copyButton) {{
@@ -1162,6 +2002,11 @@ fn generate_html(graph_json: &str, config: &HtmlConfig) -> String {
         }}
 
         // Helper functions
+        function linkKey(d) {{
+            const id = end => (typeof end === "object" ? end.id : end);
+            return `${{id(d.source)}}->${{id(d.target)}}:${{d.label}}`;
+        }}
+
         function linkArc(d) {{
             const dx = d.target.x - d.source.x;
             const dy = d.target.y - d.source.y;
@@ -1354,6 +2199,11 @@ fn generate_html(graph_json: &str, config: &HtmlConfig) -> String {
 </html>"##,
         title = config.title,
         graph_json = graph_json,
+        patch_stream_url = config
+            .patch_stream_url
+            .as_ref()
+            .and_then(|u| serde_json::to_string(u).ok())
+            .unwrap_or_else(|| "null".to_string()),
         node_colors_json = node_colors_json,
         width = config.width,
         height = config.height,
@@ -1507,10 +2357,273 @@ mod tests {
         assert!(config.show_export_controls);
     }
 
+    /// Minimal well-formedness check: tags nest properly and no markup
+    /// characters appear unescaped in text.
+    fn assert_well_formed_xml(xml: &str) {
+        let body = xml
+            .strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>")
+            .expect("XML declaration");
+        let mut stack: Vec<String> = Vec::new();
+        let mut rest = body;
+        while let Some(start) = rest.find('<') {
+            let text = &rest[..start];
+            assert!(!text.contains('>'), "unescaped '>' in {:?}", text);
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            assert!(!tag.contains('<'), "unescaped '<' in tag {:?}", tag);
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(stack.pop().as_deref(), Some(name), "mismatched </{}>", name);
+            } else if !tag.ends_with('/') {
+                let name = tag.split_whitespace().next().unwrap();
+                stack.push(name.to_string());
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(stack.is_empty(), "unclosed tags: {:?}", stack);
+        assert!(rest.trim().is_empty());
+    }
+
+    #[test]
+    fn test_graphml_export() {
+        let mut trace = ReasoningTrace::new("Pick <storage> & \"cache\"", "session-g");
+        let root = trace.root_goal.clone();
+        trace.log_decision(
+            &root,
+            "Backend\nwith notes\r\nacross lines",
+            &["SQLite", "Postgres"],
+            0,
+            "Simpler",
+        );
+
+        let xml = trace.to_graphml();
+        assert_well_formed_xml(&xml);
+
+        for key in [
+            "node_type",
+            "content",
+            "confidence",
+            "is_root",
+            "label",
+            "weight",
+        ] {
+            assert!(xml.contains(&format!("<key id=\"{}\"", key)));
+        }
+        for node in &trace.nodes {
+            assert!(xml.contains(&format!("<node id=\"{}\">", node.id.0)));
+        }
+        assert_eq!(xml.matches("<edge ").count(), trace.edges.len());
+
+        assert!(xml.contains("Pick &lt;storage&gt; &amp; &quot;cache&quot;"));
+        assert!(xml.contains("Backend\nwith notes&#13;\nacross lines"));
+        assert!(xml.contains("<data key=\"node_type\">decision</data>"));
+        assert!(xml.contains("<data key=\"label\">chooses</data>"));
+        assert!(xml.contains("<data key=\"is_root\">true</data>"));
+    }
+
+    #[test]
+    fn test_svg_export() {
+        let mut trace = ReasoningTrace::new("Ship the <svg> & \"renderer\"", "session-s");
+        let root = trace.root_goal.clone();
+        let options: Vec<String> = (0..9).map(|i| format!("Option {}", i)).collect();
+        let options: Vec<&str> = options.iter().map(|s| s.as_str()).collect();
+        let decision = trace.log_decision(
+            &root,
+            "Ünïcødé decision whose description is far too long for one box",
+            &options,
+            2,
+            "Fits",
+        );
+        trace.log_action(&decision, "Render", "Rendered");
+
+        let svg = trace.to_svg(&DotConfig::default());
+        assert_well_formed_xml(&svg);
+        assert_eq!(svg.matches("<g class=\"node ").count(), trace.nodes.len());
+        for node in &trace.nodes {
+            assert!(svg.contains(&format!("id=\"n{}\"", node.id.0.as_simple())));
+        }
+        assert_eq!(
+            svg.matches("<line class=\"edge ").count(),
+            trace.edges.len()
+        );
+
+        // Colors and shapes follow the DOT styling
+        assert!(svg.contains("fill=\"#90EE90\""));
+        assert!(svg.contains("<g class=\"node decision\""));
+        assert!(svg.contains("<ellipse "));
+
+        // Long labels are truncated on char boundaries; full text stays in <title>
+        assert!(svg.contains(">Ünïcødé decision whos...</text>"));
+        assert!(svg.contains("<title>Ünïcødé decision whose description is far too long"));
+        assert!(svg.contains("Ship the &lt;svg&gt; &amp; &quot;renderer&quot;"));
+
+        // Nine options wrap at SVG_MAX_COLUMNS, capping the canvas width
+        assert!(svg.contains(&format!(
+            "width=\"{}\"",
+            2.0 * SVG_MARGIN + SVG_MAX_COLUMNS as f64 * (SVG_NODE_WIDTH + SVG_GAP_X) - SVG_GAP_X
+        )));
+    }
+
+    #[test]
+    fn test_cytoscape_export() {
+        let mut trace = ReasoningTrace::new("Choose a queue", "session-c");
+        let root = trace.root_goal.clone();
+        trace.log_decision(&root, "Queue", &["Kafka", "NATS", "SQS"], 1, "Latency");
+
+        let json = trace.to_cytoscape_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["elements"]["nodes"].is_array());
+
+        let graph: CytoscapeGraph = serde_json::from_str(&json).unwrap();
+        let nodes = &graph.elements.nodes;
+        let edges = &graph.elements.edges;
+        assert_eq!(nodes.len(), trace.nodes.len());
+        assert_eq!(edges.len(), trace.edges.len());
+
+        let root_node = nodes.iter().find(|n| n.data.is_root).unwrap();
+        assert_eq!(root_node.data.node_type, "goal");
+        assert_eq!(root_node.data.color, "#90EE90");
+        assert!(nodes.iter().filter(|n| n.data.is_root).count() == 1);
+
+        let rejected: Vec<_> = edges.iter().filter(|e| e.data.label == "rejects").collect();
+        assert_eq!(rejected.len(), 2);
+        assert!(edges.iter().any(|e| e.data.label == "chooses"));
+
+        let ids: std::collections::HashSet<_> = edges.iter().map(|e| &e.data.id).collect();
+        assert_eq!(ids.len(), edges.len());
+
+        // Stable across exports
+        let again = trace.to_cytoscape_graph(&DotConfig::default());
+        let again_ids: Vec<_> = again.elements.edges.iter().map(|e| &e.data.id).collect();
+        let first_ids: Vec<_> = edges.iter().map(|e| &e.data.id).collect();
+        assert_eq!(again_ids, first_ids);
+    }
+
+    /// Minimal RFC 4180 reader for checking the CSV exports.
+    fn parse_csv(input: &str) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut chars = input.chars().peekable();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            match (quoted, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                (true, '"') => quoted = false,
+                (true, c) => field.push(c),
+                (false, '"') => quoted = true,
+                (false, ',') => record.push(std::mem::take(&mut field)),
+                (false, '\r') => {}
+                (false, '\n') => {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                (false, c) => field.push(c),
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn test_csv_export_round_trips() {
+        let mut trace = ReasoningTrace::new("Pick a cache, quickly", "session-csv");
+        let root = trace.root_goal.clone();
+        let chosen = trace.log_decision(
+            &root,
+            "Cache \"backend\"",
+            &["Redis,\nclustered", "Memcached"],
+            0,
+            "Needs persistence, \"probably\"",
+        );
+        let node = trace.nodes.iter_mut().find(|n| n.id == chosen).unwrap();
+        node.metadata = Some(HashMap::from([
+            ("cost_usd".to_string(), serde_json::json!(0.0125)),
+            ("timing_ms".to_string(), serde_json::json!(340)),
+            ("model".to_string(), serde_json::json!("haiku")),
+        ]));
+        trace.edges[0].metadata = Some(HashMap::from([(
+            "note".to_string(),
+            serde_json::json!("a,b"),
+        )]));
+
+        let nodes = parse_csv(&trace.to_csv_nodes());
+        assert_eq!(nodes.len(), trace.nodes.len() + 1);
+        assert_eq!(nodes[0][0], "id");
+        assert_eq!(nodes[0][7..], ["cost_usd", "timing_ms", "metadata"]);
+        assert!(nodes.iter().all(|r| r.len() == 10));
+
+        let row = nodes.iter().find(|r| r[0] == chosen.0.to_string()).unwrap();
+        assert_eq!(row[2], "Redis,\nclustered");
+        assert_eq!(row[7], "0.0125");
+        assert_eq!(row[8], "340");
+        assert_eq!(row[9], r#"{"model":"haiku"}"#);
+        let root_row = nodes.iter().find(|r| r[6] == "true").unwrap();
+        assert_eq!(root_row[2], "Pick a cache, quickly");
+        assert_eq!(root_row[9], "");
+
+        let edges = parse_csv(&trace.to_csv_edges());
+        assert_eq!(edges.len(), trace.edges.len() + 1);
+        assert!(edges.iter().all(|r| r.len() == 8));
+        assert_eq!(edges[1][7], r#"{"note":"a,b"}"#);
+        assert!(edges.iter().any(|r| r[2] == "chooses"));
+    }
+
+    #[test]
+    fn test_html_patch_contains_only_new_elements() {
+        let mut trace = ReasoningTrace::new("Investigate outage", "session-p");
+        let root = trace.root_goal.clone();
+        let chosen = trace.log_decision(&root, "Where to look", &["Logs", "Metrics"], 0, "Fast");
+        let rendered = trace.nodes.len();
+        let initial_ids: Vec<String> = trace.nodes.iter().map(|n| n.id.0.to_string()).collect();
+        let html = trace.to_html(HtmlConfig::default());
+
+        let (action, outcome) = trace.log_action(&chosen, "grep errors", "Found timeout");
+
+        let patch: TraceHtmlPatch = serde_json::from_str(&trace.to_html_patch(rendered)).unwrap();
+        assert_eq!(patch.trace_id, trace.id.to_string());
+        assert_eq!(patch.since_node_count, rendered);
+        assert_eq!(patch.node_count, rendered + 2);
+
+        let ids: Vec<&str> = patch.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec![action.0.to_string(), outcome.0.to_string()]);
+        assert_eq!(patch.links.len(), 2);
+        for link in &patch.links {
+            assert!(ids.contains(&link.source.as_str()) || ids.contains(&link.target.as_str()));
+        }
+        // Parent link points at an id from the initial render.
+        assert!(patch
+            .links
+            .iter()
+            .any(|l| initial_ids.contains(&l.source) && l.target == ids[0]));
+        for id in &initial_ids {
+            assert!(html.contains(id.as_str()));
+        }
+
+        let empty = trace.to_html_patch_data(trace.nodes.len());
+        assert!(empty.nodes.is_empty() && empty.links.is_empty());
+    }
+
+    #[test]
+    fn test_html_exposes_patch_hook() {
+        let trace = ReasoningTrace::new("Goal", "session-h");
+
+        let html = trace.to_html(HtmlConfig::default());
+        assert!(html.contains("window.applyTracePatch = applyTracePatch"));
+        assert!(html.contains("const patchStreamUrl = null;"));
+
+        let html = trace.to_html(HtmlConfig::default().with_patch_stream("/trace/patches"));
+        assert!(html.contains("const patchStreamUrl = \"/trace/patches\";"));
+    }
+
     #[test]
     fn test_truncate_string() {
         assert_eq!(truncate_string("short", 10), "short");
         assert_eq!(truncate_string("this is a long string", 10), "this is...");
+        // Byte 4 falls inside "é"
+        assert_eq!(truncate_string("café crème", 7), "caf...");
+        assert_eq!(truncate_string("🦀🦀🦀🦀", 9), "🦀...");
     }
 
     #[test]
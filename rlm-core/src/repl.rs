@@ -10,15 +10,19 @@
 //! 2. Execute code that calls `SUBMIT(outputs)` when done
 //! 3. Outputs are validated against the registered signature
 //! 4. Results are returned in `ExecuteResult.submit_result`
+//!
+//! Submissions can also be aggregated per signature in a
+//! [`SubmitMetricsStore`] with `record_submit_metrics()`.
 
 use crate::error::{Error, Result};
 use crate::llm::{BatchExecutor, BatchedLLMQuery, BatchedQueryResults, LLMClient};
-use crate::signature::{FieldSpec, SignatureRegistration, SubmitResult};
+use crate::signature::{FieldSpec, SignatureRegistration, SubmitMetricsStore, SubmitResult};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -110,16 +114,64 @@ pub struct ExecuteResult {
     /// Result of SUBMIT call (if signature was registered and SUBMIT was called)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub submit_result: Option<SubmitResult>,
+    /// Execution exceeded the configured timeout and the subprocess was killed
+    #[serde(default)]
+    pub timed_out: bool,
+    /// Captured output exceeded `max_output_bytes` and was cut off
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 impl ExecuteResult {
+    /// Result for an execution that was killed after `timeout_ms`.
+    pub fn timed_out(timeout_ms: u64) -> Self {
+        Self {
+            success: false,
+            result: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: Some(format!("Execution timed out after {}ms", timeout_ms)),
+            error_type: Some("TimeoutError".to_string()),
+            execution_time_ms: timeout_ms as f64,
+            pending_operations: Vec::new(),
+            submit_result: None,
+            timed_out: true,
+            truncated: false,
+        }
+    }
+
+    /// Cut stdout and stderr down to `max_bytes` each, marking the result
+    /// as truncated if anything was removed.
+    pub fn truncate_output(&mut self, max_bytes: usize) {
+        let stdout = truncate_to_char_boundary(&mut self.stdout, max_bytes);
+        let stderr = truncate_to_char_boundary(&mut self.stderr, max_bytes);
+        self.truncated |= stdout || stderr;
+    }
+
     /// Convert this result into a fallback-loop step for orchestrator wiring.
+    ///
+    /// `operations` are the REPL's pending operations (see
+    /// [`ReplHandle::list_pending_operations`]); the `llm_call`s this
+    /// execution queued become the step's sub-calls at `sub_call_depth`.
     pub fn into_fallback_loop_step(
         self,
         code: impl Into<String>,
         llm_calls: usize,
         variables: HashMap<String, Value>,
+        operations: &[PendingOperation],
+        sub_call_depth: u32,
     ) -> crate::orchestrator::FallbackLoopStep {
+        let sub_calls = operations
+            .iter()
+            .filter(|op| {
+                op.operation_type == "llm_call" && self.pending_operations.contains(&op.id)
+            })
+            .map(|op| crate::orchestrator::StepSubCall {
+                query: op.sub_call_query(),
+                depth: sub_call_depth,
+            })
+            .collect();
+
         crate::orchestrator::FallbackLoopStep {
             code: code.into(),
             llm_calls,
@@ -127,6 +179,8 @@ impl ExecuteResult {
             stderr: self.stderr,
             submit_result: self.submit_result,
             variables,
+            model_calls: Vec::new(),
+            sub_calls,
         }
     }
 }
@@ -142,6 +196,20 @@ pub struct PendingOperation {
     pub params: HashMap<String, Value>,
 }
 
+impl PendingOperation {
+    /// The query an `llm_call` sends: its prompt, or for structured calls
+    /// such as `verify_claim`, the parameters as JSON.
+    fn sub_call_query(&self) -> String {
+        match self.params.get("prompt").and_then(Value::as_str) {
+            Some(prompt) => prompt.to_string(),
+            None => {
+                let params: std::collections::BTreeMap<_, _> = self.params.iter().collect();
+                serde_json::to_string(&params).unwrap_or_default()
+            }
+        }
+    }
+}
+
 /// Status of the REPL subprocess.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ReplStatus {
@@ -151,6 +219,34 @@ pub struct ReplStatus {
     pub memory_usage_bytes: Option<u64>,
 }
 
+/// Largest index no greater than `index` that lies on a character
+/// boundary of `s` (`s.len()` if `index` is past the end).
+///
+/// Stands in for `str::floor_char_boundary`, which needs a newer toolchain
+/// than the crate's minimum.
+pub(crate) fn floor_char_boundary(s: &str, index: usize) -> usize {
+    if index >= s.len() {
+        return s.len();
+    }
+    let mut end = index;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    end
+}
+
+/// Truncate `s` to at most `max_bytes` without splitting a character.
+///
+/// Returns `true` if anything was removed.
+fn truncate_to_char_boundary(s: &mut String, max_bytes: usize) -> bool {
+    if s.len() <= max_bytes {
+        return false;
+    }
+    let end = floor_char_boundary(s, max_bytes);
+    s.truncate(end);
+    true
+}
+
 /// Configuration for the REPL subprocess.
 #[derive(Debug, Clone)]
 pub struct ReplConfig {
@@ -159,8 +255,13 @@ pub struct ReplConfig {
     /// Optional directory added to `PYTHONPATH` for importing `rlm_repl`.
     /// Useful in development when running from source checkout.
     pub repl_package_path: Option<String>,
-    /// Timeout for REPL operations in milliseconds
+    /// Wall-clock timeout for REPL operations in milliseconds.
+    ///
+    /// A request that exceeds it kills the subprocess; `execute` then
+    /// returns a result with `timed_out` set.
     pub timeout_ms: u64,
+    /// Maximum bytes kept from each of stdout and stderr per execution
+    pub max_output_bytes: Option<usize>,
     /// Maximum memory in bytes (enforced by ulimit on Unix)
     pub max_memory_bytes: Option<u64>,
     /// Maximum CPU time in seconds
@@ -173,19 +274,43 @@ impl Default for ReplConfig {
             python_path: "python3".to_string(),
             repl_package_path: None,
             timeout_ms: 30_000,
+            max_output_bytes: Some(1024 * 1024),       // 1 MB
             max_memory_bytes: Some(512 * 1024 * 1024), // 512 MB
             max_cpu_seconds: Some(60),
         }
     }
 }
 
+impl ReplConfig {
+    /// Wall-clock timeout for REPL operations.
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    /// Set the wall-clock timeout for REPL operations.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = timeout.as_millis().min(u64::MAX as u128) as u64;
+        self
+    }
+
+    /// Set the maximum bytes kept from stdout and stderr per execution.
+    pub fn with_max_output_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_output_bytes = Some(max_bytes);
+        self
+    }
+}
+
 /// Handle to a running REPL subprocess.
 pub struct ReplHandle {
     child: Child,
     stdin: ChildStdin,
-    stdout: BufReader<ChildStdout>,
+    /// Lines from the subprocess's stdout, read on a background thread so
+    /// that waiting for a response can time out.
+    stdout: Receiver<std::io::Result<String>>,
     next_id: u64,
     config: ReplConfig,
+    signature_name: Option<String>,
+    submit_metrics: Option<Arc<SubmitMetricsStore>>,
 }
 
 impl ReplHandle {
@@ -248,12 +373,43 @@ impl ReplHandle {
         Ok(Self {
             child,
             stdin,
-            stdout,
+            stdout: Self::spawn_stdout_reader(stdout),
             next_id: 1,
             config,
+            signature_name: None,
+            submit_metrics: None,
         })
     }
 
+    /// Forward stdout lines over a channel until the pipe closes.
+    fn spawn_stdout_reader(
+        mut stdout: BufReader<ChildStdout>,
+    ) -> Receiver<std::io::Result<String>> {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            let mut line = String::new();
+            match stdout.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if tx.send(Ok(line)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    break;
+                }
+            }
+        });
+        rx
+    }
+
+    /// Kill the subprocess. The handle is no longer alive afterwards.
+    fn kill(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+
     fn wait_for_ready(
         child: &mut Child,
         stdout: &mut BufReader<ChildStdout>,
@@ -323,64 +479,82 @@ impl ReplHandle {
             .map_err(|e| Error::SubprocessComm(format!("Failed to flush stdin: {}", e)))?;
 
         // Read response with timeout
-        let start = Instant::now();
-        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let deadline = Instant::now() + self.config.timeout();
 
         loop {
-            let mut line = String::new();
-
-            // Check timeout
-            if start.elapsed() > timeout {
-                return Err(Error::timeout(self.config.timeout_ms));
-            }
-
-            // Try to read a line
-            match self.stdout.read_line(&mut line) {
-                Ok(0) => {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match self.stdout.recv_timeout(remaining) {
+                Ok(Ok(line)) => line,
+                Ok(Err(e)) => {
+                    return Err(Error::SubprocessComm(format!(
+                        "Failed to read response: {}",
+                        e
+                    )));
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    // The subprocess is stuck (or its reply would arrive out of
+                    // sync), so it cannot be reused.
+                    self.kill();
+                    return Err(Error::timeout(self.config.timeout_ms));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
                     return Err(Error::SubprocessComm(
                         "REPL subprocess closed unexpectedly".to_string(),
                     ));
                 }
-                Ok(_) => {
-                    let response: JsonRpcResponse = serde_json::from_str(&line)?;
-
-                    // Check if this is our response
-                    if response.id == Some(id) {
-                        if let Some(error) = response.error {
-                            return Err(Error::repl_execution(format!(
-                                "{}: {}",
-                                error.code, error.message
-                            )));
-                        }
-                        return Ok(response.result.unwrap_or(Value::Null));
-                    }
-                    // Otherwise it's a notification or response for a different request
-                    // In a more sophisticated implementation, we'd handle these
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    std::thread::sleep(Duration::from_millis(10));
-                    continue;
-                }
-                Err(e) => {
-                    return Err(Error::SubprocessComm(format!(
-                        "Failed to read response: {}",
-                        e
+            };
+
+            let response: JsonRpcResponse = serde_json::from_str(&line)?;
+
+            // Check if this is our response
+            if response.id == Some(id) {
+                if let Some(error) = response.error {
+                    return Err(Error::repl_execution(format!(
+                        "{}: {}",
+                        error.code, error.message
                     )));
                 }
+                return Ok(response.result.unwrap_or(Value::Null));
             }
+            // Otherwise it's a notification or response for a different request
+            // In a more sophisticated implementation, we'd handle these
         }
     }
 
     /// Execute Python code in the REPL.
+    ///
+    /// If execution exceeds the configured timeout, the subprocess is killed
+    /// and the result has `timed_out` set; the handle is then no longer
+    /// alive. Output beyond `max_output_bytes` is dropped and the result
+    /// marked `truncated`.
     pub fn execute(&mut self, code: &str) -> Result<ExecuteResult> {
         let params = serde_json::json!({
             "code": code,
             "timeout_ms": self.config.timeout_ms,
             "capture_output": true,
+            "max_output_bytes": self.config.max_output_bytes,
         });
 
-        let result = self.send_request("execute", params)?;
-        let execute_result: ExecuteResult = serde_json::from_value(result)?;
+        let result = match self.send_request("execute", params) {
+            Ok(result) => result,
+            Err(Error::Timeout { duration_ms }) => {
+                return Ok(ExecuteResult::timed_out(duration_ms))
+            }
+            Err(e) => return Err(e),
+        };
+        let mut execute_result: ExecuteResult = serde_json::from_value(result)?;
+        if let Some(max_bytes) = self.config.max_output_bytes {
+            execute_result.truncate_output(max_bytes);
+        }
+        if let (Some(store), Some(name), Some(submit_result)) = (
+            &self.submit_metrics,
+            &self.signature_name,
+            &execute_result.submit_result,
+        ) {
+            if let Err(e) = store.record(name, submit_result) {
+                tracing::warn!(signature = %name, error = %e, "failed to record submit metrics");
+            }
+        }
         Ok(execute_result)
     }
 
@@ -499,14 +673,25 @@ impl ReplHandle {
         };
         let params = registration.to_params();
         self.send_request("register_signature", params)?;
+        self.signature_name = registration.signature_name;
         Ok(())
     }
 
+    /// Aggregate SUBMIT results of named signatures into `store`.
+    ///
+    /// Off until called. Submissions for a signature registered without a
+    /// name are not recorded, and a failure to record is logged rather than
+    /// failing the execution.
+    pub fn record_submit_metrics(&mut self, store: Arc<SubmitMetricsStore>) {
+        self.submit_metrics = Some(store);
+    }
+
     /// Clear the registered signature.
     ///
     /// After calling this, `SUBMIT()` calls will return `NoSignatureRegistered` error.
     pub fn clear_signature(&mut self) -> Result<()> {
         self.send_request("clear_signature", Value::Null)?;
+        self.signature_name = None;
         Ok(())
     }
 
@@ -668,7 +853,13 @@ impl ReplPool {
     }
 
     /// Return a REPL handle to the pool.
-    pub fn release(&self, handle: ReplHandle) {
+    ///
+    /// Dead handles (e.g. killed after a timeout) are dropped; the next
+    /// `acquire` spawns a replacement.
+    pub fn release(&self, mut handle: ReplHandle) {
+        if !handle.is_alive() {
+            return;
+        }
         let mut handles = self.handles.lock().ok();
         if let Some(ref mut handles) = handles {
             if handles.len() < self.max_size {
@@ -677,6 +868,11 @@ impl ReplPool {
             // Otherwise, the handle is dropped
         }
     }
+
+    /// Number of idle handles in the pool.
+    pub fn idle_count(&self) -> usize {
+        self.handles.lock().map(|h| h.len()).unwrap_or(0)
+    }
 }
 
 /// REPL environment trait for the orchestrator.
@@ -773,6 +969,9 @@ mod tests {
                 },
                 timestamp: Utc::now(),
                 cost: Some(0.0),
+                retries: 0,
+                tool_calls: Vec::new(),
+                logprobs: None,
             })
         }
 
@@ -1016,6 +1215,8 @@ SUBMIT({'answer': 'second'})
                 "answer": "test",
                 "confidence": 0.95
             }))),
+            timed_out: false,
+            truncated: false,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -1041,6 +1242,8 @@ SUBMIT({'answer': 'second'})
             execution_time_ms: 50.0,
             pending_operations: vec![],
             submit_result: None,
+            timed_out: false,
+            truncated: false,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -1062,11 +1265,32 @@ SUBMIT({'answer': 'second'})
             execution_time_ms: 10.0,
             pending_operations: vec!["op1".to_string()],
             submit_result: Some(SubmitResult::success(serde_json::json!({"answer": "ok"}))),
+            timed_out: false,
+            truncated: false,
         };
 
         let mut vars = HashMap::new();
         vars.insert("answer".to_string(), serde_json::json!("ok"));
-        let step = result.into_fallback_loop_step("SUBMIT({'answer': 'ok'})", 2, vars.clone());
+        let operations = vec![
+            PendingOperation {
+                id: "op1".to_string(),
+                operation_type: "llm_call".to_string(),
+                params: HashMap::from([("prompt".to_string(), serde_json::json!("Summarize"))]),
+            },
+            // Queued by an earlier execution
+            PendingOperation {
+                id: "op0".to_string(),
+                operation_type: "llm_call".to_string(),
+                params: HashMap::from([("prompt".to_string(), serde_json::json!("Earlier"))]),
+            },
+        ];
+        let step = result.into_fallback_loop_step(
+            "SUBMIT({'answer': 'ok'})",
+            2,
+            vars.clone(),
+            &operations,
+            1,
+        );
 
         assert_eq!(step.code, "SUBMIT({'answer': 'ok'})");
         assert_eq!(step.llm_calls, 2);
@@ -1077,6 +1301,13 @@ SUBMIT({'answer': 'second'})
             step.submit_result,
             Some(SubmitResult::Success { .. })
         ));
+        assert_eq!(
+            step.sub_calls,
+            vec![crate::orchestrator::StepSubCall {
+                query: "Summarize".to_string(),
+                depth: 1,
+            }]
+        );
     }
 
     #[test]
@@ -1226,4 +1457,90 @@ SUBMIT({'answer': 'second'})
         assert!(err.to_string().contains("did not exit within"));
         assert!(matches!(child.try_wait(), Ok(Some(_))));
     }
+
+    /// Minimal stand-in for `rlm_repl`: hangs on `while True` code and
+    /// otherwise echoes the code back as stdout.
+    const FAKE_REPL_MAIN: &str = r#"
+import json, sys
+print(json.dumps({"jsonrpc": "2.0", "method": "ready"}), flush=True)
+for line in sys.stdin:
+    req = json.loads(line)
+    if req["method"] == "shutdown":
+        break
+    code = req["params"].get("code", "")
+    if code.startswith("while True"):
+        while True:
+            pass
+    result = {"success": True, "stdout": code, "stderr": "",
+              "execution_time_ms": 1.0, "pending_operations": []}
+    print(json.dumps({"jsonrpc": "2.0", "result": result, "id": req["id"]}), flush=True)
+"#;
+
+    fn fake_repl_config(dir: &tempfile::TempDir) -> ReplConfig {
+        let package = dir.path().join("rlm_repl");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(package.join("__init__.py"), "").unwrap();
+        std::fs::write(package.join("__main__.py"), FAKE_REPL_MAIN).unwrap();
+
+        ReplConfig {
+            repl_package_path: Some(dir.path().to_string_lossy().into_owned()),
+            ..ReplConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_execute_times_out_infinite_loop() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = fake_repl_config(&dir).with_timeout(Duration::from_millis(300));
+        let pool = ReplPool::new(config, 1);
+
+        let mut handle = pool.acquire().unwrap();
+        let start = Instant::now();
+        let result = handle.execute("while True: pass").unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(result.timed_out);
+        assert!(!result.success);
+        assert_eq!(result.error_type.as_deref(), Some("TimeoutError"));
+        assert!(!handle.is_alive());
+
+        // The dead handle is discarded and replaced on the next acquire.
+        pool.release(handle);
+        assert_eq!(pool.idle_count(), 0);
+        let mut handle = pool.acquire().unwrap();
+        let result = handle.execute("1 + 1").unwrap();
+        assert!(result.success);
+        assert!(!result.timed_out);
+    }
+
+    #[test]
+    fn test_execute_truncates_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = fake_repl_config(&dir).with_max_output_bytes(8);
+        let mut handle = ReplHandle::spawn(config).unwrap();
+
+        let result = handle.execute("print('ééééééé')").unwrap();
+        assert!(result.truncated);
+        assert!(result.stdout.len() <= 8);
+        assert_eq!(result.stdout, "print('");
+
+        let result = handle.execute("x").unwrap();
+        assert!(!result.truncated);
+        assert_eq!(result.stdout, "x");
+    }
+
+    #[test]
+    fn test_truncate_to_char_boundary() {
+        let mut s = "añb".to_string();
+        assert!(truncate_to_char_boundary(&mut s, 2));
+        assert_eq!(s, "a");
+
+        let mut s = "abc".to_string();
+        assert!(!truncate_to_char_boundary(&mut s, 3));
+        assert_eq!(s, "abc");
+
+        assert_eq!(floor_char_boundary("añb", 2), 1);
+        assert_eq!(floor_char_boundary("añb", 3), 3);
+        assert_eq!(floor_char_boundary("añb", 10), 4);
+    }
 }
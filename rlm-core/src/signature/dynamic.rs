@@ -0,0 +1,141 @@
+//! Signatures assembled at runtime.
+//!
+//! [`Signature`](super::Signature) is generic over its input and output
+//! types, which rules it out wherever the field set is only known at
+//! runtime (for example across the C FFI). [`DynamicSignature`] carries the
+//! same instructions and field specs as plain data and renders the same
+//! prompt and output schema.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::template::PromptTemplate;
+use super::types::FieldSpec;
+use super::validation::{validate_fields, ValidationResult};
+
+/// A non-generic signature built from [`FieldSpec`]s.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DynamicSignature {
+    /// Task instructions for the LLM
+    pub instructions: String,
+    /// Input field specifications
+    pub input_fields: Vec<FieldSpec>,
+    /// Output field specifications
+    pub output_fields: Vec<FieldSpec>,
+}
+
+impl DynamicSignature {
+    /// Create a signature from instructions and field specs.
+    pub fn new(
+        instructions: impl Into<String>,
+        input_fields: Vec<FieldSpec>,
+        output_fields: Vec<FieldSpec>,
+    ) -> Self {
+        Self {
+            instructions: instructions.into(),
+            input_fields,
+            output_fields,
+        }
+    }
+
+    /// Generate a prompt from a JSON object of inputs.
+    ///
+    /// Produces the same layout as [`Signature::to_prompt`](super::Signature::to_prompt).
+    pub fn to_prompt(&self, inputs: &Value) -> String {
+        super::render_prompt(
+            &self.instructions,
+            &self.input_fields,
+            &self.output_fields,
+            inputs,
+        )
+    }
+
+    /// Generate a prompt laid out by `template`.
+    pub fn to_prompt_with_template<T: PromptTemplate + ?Sized>(
+        &self,
+        inputs: &Value,
+        template: &T,
+    ) -> String {
+        super::render_prompt_with_template(
+            template,
+            &self.instructions,
+            &self.input_fields,
+            &self.output_fields,
+            &[],
+            None,
+            inputs,
+        )
+    }
+
+    /// Generate a JSON schema for the outputs.
+    pub fn output_schema(&self) -> Value {
+        super::object_schema(&self.output_fields)
+    }
+
+    /// Validate a JSON object of inputs against the input field specs.
+    pub fn validate_inputs(&self, inputs: &Value) -> ValidationResult {
+        validate_fields(inputs, &self.input_fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::{FieldType, Signature};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Inputs {
+        text: String,
+    }
+
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Outputs {
+        summary: String,
+        confidence: f64,
+    }
+
+    struct Summarize;
+
+    impl Signature for Summarize {
+        type Inputs = Inputs;
+        type Outputs = Outputs;
+
+        fn instructions() -> &'static str {
+            "Summarize the text"
+        }
+
+        fn input_fields() -> Vec<FieldSpec> {
+            vec![FieldSpec::new("text", FieldType::String).with_description("Text to summarize")]
+        }
+
+        fn output_fields() -> Vec<FieldSpec> {
+            vec![
+                FieldSpec::new("summary", FieldType::String).with_description("One-line summary"),
+                FieldSpec::new("confidence", FieldType::Float),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_matches_static_signature() {
+        let dynamic = DynamicSignature::new(
+            Summarize::instructions(),
+            Summarize::input_fields(),
+            Summarize::output_fields(),
+        );
+        let inputs = Inputs {
+            text: "A long story".to_string(),
+        };
+
+        assert_eq!(
+            dynamic.to_prompt(&serde_json::to_value(&inputs).unwrap()),
+            Summarize::to_prompt(&inputs)
+        );
+        assert_eq!(dynamic.output_schema(), Summarize::output_schema());
+        assert!(dynamic
+            .validate_inputs(&serde_json::json!({"text": "ok"}))
+            .is_ok());
+        assert!(dynamic.validate_inputs(&serde_json::json!({})).is_err());
+    }
+}
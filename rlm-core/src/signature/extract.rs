@@ -0,0 +1,155 @@
+//! Pluggable extraction of JSON candidates from LLM responses.
+//!
+//! Models rarely return a bare JSON object: it may sit in a markdown code
+//! fence, follow a prefix such as `Output:`, or be wrapped in a
+//! provider-specific tag. A [`JsonExtractor`] pulls candidate JSON strings
+//! out of one kind of wrapper, and [`extract_candidates`] tries a list of them
+//! in order:
+//!
+//! 1. a response that is a single JSON object is returned as-is (fast path)
+//! 2. otherwise the candidates of every extractor, in extractor order, so a
+//!    caller that takes the first candidate that parses and validates falls
+//!    through to the next extractor when none of an earlier one's do
+//! 3. if no extractor finds any, the whole response is the only candidate
+//!
+//! [`default_extractors`] are [`CodeBlockExtractor`] then
+//! [`RawObjectExtractor`]. Put a custom extractor ahead of them with
+//! [`ParseConfig::with_extractor`](super::ParseConfig::with_extractor).
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Pulls candidate JSON strings out of a response.
+pub trait JsonExtractor: fmt::Debug + Send + Sync {
+    /// Candidates in the order they appear, or an empty list if this
+    /// extractor's wrapper isn't present.
+    fn extract<'a>(&self, response: &'a str) -> Vec<&'a str>;
+}
+
+/// The contents of each fenced code block.
+///
+/// Blocks holding several objects (or an array of objects) are split into
+/// the objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CodeBlockExtractor;
+
+impl JsonExtractor for CodeBlockExtractor {
+    fn extract<'a>(&self, response: &'a str) -> Vec<&'a str> {
+        let mut candidates = Vec::new();
+        for block in code_blocks(response) {
+            let objects = json_objects(block);
+            if objects.len() > 1 || (objects.len() == 1 && objects[0] != block) {
+                candidates.extend(objects);
+            } else {
+                candidates.push(block);
+            }
+        }
+        candidates
+    }
+}
+
+/// Each balanced top-level `{...}` object in the text.
+///
+/// Handles surrounding prose such as an `Output:` prefix, and splits a raw
+/// array of objects into its elements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RawObjectExtractor;
+
+impl JsonExtractor for RawObjectExtractor {
+    fn extract<'a>(&self, response: &'a str) -> Vec<&'a str> {
+        json_objects(response)
+    }
+}
+
+/// The built-in extractors: [`CodeBlockExtractor`], then [`RawObjectExtractor`].
+pub fn default_extractors() -> Vec<Arc<dyn JsonExtractor>> {
+    vec![Arc::new(CodeBlockExtractor), Arc::new(RawObjectExtractor)]
+}
+
+/// Extract JSON candidates from `response` using `extractors` in order.
+///
+/// See the [module docs](self) for the order candidates are looked for in.
+pub fn extract_candidates<'a>(
+    response: &'a str,
+    extractors: &[Arc<dyn JsonExtractor>],
+) -> Vec<&'a str> {
+    let trimmed = response.trim();
+    if trimmed.starts_with('{')
+        && trimmed.ends_with('}')
+        && serde_json::from_str::<serde::de::IgnoredAny>(trimmed).is_ok()
+    {
+        return vec![trimmed];
+    }
+
+    let mut candidates: Vec<&str> = Vec::new();
+    for candidate in extractors.iter().flat_map(|e| e.extract(response)) {
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    if candidates.is_empty() {
+        candidates.push(response);
+    }
+    candidates
+}
+
+/// Contents of each fenced code block, skipping any language identifier.
+fn code_blocks(response: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = response[pos..].find("```") {
+        let fence_end = pos + offset + 3;
+        // Skip language identifier if present
+        let content_start = response[fence_end..]
+            .find('\n')
+            .map(|i| fence_end + i + 1)
+            .unwrap_or(fence_end);
+        let Some(end) = response[content_start..].find("```") else {
+            break;
+        };
+        blocks.push(response[content_start..content_start + end].trim());
+        pos = content_start + end + 3;
+    }
+
+    blocks
+}
+
+/// Balanced top-level `{...}` spans, ignoring braces inside JSON strings.
+fn json_objects(text: &str) -> Vec<&str> {
+    let mut objects = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' if depth > 0 => in_string = true,
+            '{' => {
+                if depth == 0 {
+                    start = i;
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    objects.push(&text[start..=i]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    objects
+}
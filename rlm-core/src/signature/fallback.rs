@@ -39,7 +39,9 @@ use std::marker::PhantomData;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::Signature;
+use super::types::FieldSpec;
+use super::{apply_defaults, normalize_aliases, validate_fields, Signature, ValidationError};
+use crate::repl::floor_char_boundary;
 
 /// Result of REPL execution with fallback support (SPEC-27.04).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +60,17 @@ pub enum ExecutionResult<O> {
         trigger_reason: FallbackTrigger,
     },
 
+    /// Only some output fields could be extracted (see
+    /// [`FallbackConfig::salvage_partial`]).
+    Partial {
+        /// The fields that parsed and validated, plus what's missing.
+        partial: PartialOutputs,
+        /// Confidence in extraction (0.0 - 1.0).
+        confidence: f64,
+        /// Reason fallback was triggered.
+        trigger_reason: FallbackTrigger,
+    },
+
     /// Failed to extract outputs.
     Failed {
         /// Reason for failure.
@@ -82,6 +95,15 @@ impl<O> ExecutionResult<O> {
         }
     }
 
+    /// Create a partial result.
+    pub fn partial(partial: PartialOutputs, confidence: f64, trigger: FallbackTrigger) -> Self {
+        Self::Partial {
+            partial,
+            confidence: confidence.clamp(0.0, 1.0),
+            trigger_reason: trigger,
+        }
+    }
+
     /// Create a failed result.
     pub fn failed(reason: impl Into<String>, trigger: FallbackTrigger) -> Self {
         Self::Failed {
@@ -100,6 +122,11 @@ impl<O> ExecutionResult<O> {
         matches!(self, Self::Extracted { .. })
     }
 
+    /// Check if only some fields were extracted.
+    pub fn is_partial(&self) -> bool {
+        matches!(self, Self::Partial { .. })
+    }
+
     /// Check if extraction failed.
     pub fn is_failed(&self) -> bool {
         matches!(self, Self::Failed { .. })
@@ -110,15 +137,24 @@ impl<O> ExecutionResult<O> {
         match self {
             Self::Submitted(o) => Some(o),
             Self::Extracted { outputs, .. } => Some(outputs),
-            Self::Failed { .. } => None,
+            Self::Partial { .. } | Self::Failed { .. } => None,
+        }
+    }
+
+    /// Get the salvaged fields of a partial extraction.
+    pub fn partial_outputs(&self) -> Option<&PartialOutputs> {
+        match self {
+            Self::Partial { partial, .. } => Some(partial),
+            _ => None,
         }
     }
 
-    /// Get confidence (1.0 for submitted, actual for extracted, 0.0 for failed).
+    /// Get confidence (1.0 for submitted, actual for extracted or partial,
+    /// 0.0 for failed).
     pub fn confidence(&self) -> f64 {
         match self {
             Self::Submitted(_) => 1.0,
-            Self::Extracted { confidence, .. } => *confidence,
+            Self::Extracted { confidence, .. } | Self::Partial { confidence, .. } => *confidence,
             Self::Failed { .. } => 0.0,
         }
     }
@@ -127,7 +163,9 @@ impl<O> ExecutionResult<O> {
     pub fn trigger(&self) -> Option<FallbackTrigger> {
         match self {
             Self::Submitted(_) => None,
-            Self::Extracted { trigger_reason, .. } => Some(*trigger_reason),
+            Self::Extracted { trigger_reason, .. } | Self::Partial { trigger_reason, .. } => {
+                Some(*trigger_reason)
+            }
             Self::Failed { trigger, .. } => Some(*trigger),
         }
     }
@@ -145,11 +183,91 @@ impl<O> ExecutionResult<O> {
                 confidence,
                 trigger_reason,
             },
+            Self::Partial {
+                partial,
+                confidence,
+                trigger_reason,
+            } => ExecutionResult::Partial {
+                partial,
+                confidence,
+                trigger_reason,
+            },
             Self::Failed { reason, trigger } => ExecutionResult::Failed { reason, trigger },
         }
     }
 }
 
+/// Output fields salvaged from a response that failed validation as a whole.
+///
+/// `fields` is shaped like the signature's outputs but holds only the fields
+/// that validated on their own; callers can re-ask for just
+/// [`unresolved`](Self::unresolved) and merge the answers in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PartialOutputs {
+    /// Fields that parsed and validated, keyed by canonical field name.
+    pub fields: serde_json::Map<String, Value>,
+    /// Required fields that were absent or null.
+    pub missing: Vec<String>,
+    /// Fields that were present but failed validation.
+    pub invalid: Vec<String>,
+    /// Validation errors for the invalid fields.
+    pub errors: Vec<ValidationError>,
+}
+
+impl PartialOutputs {
+    /// Missing and invalid field names, in that order.
+    pub fn unresolved(&self) -> impl Iterator<Item = &str> {
+        self.missing
+            .iter()
+            .chain(self.invalid.iter())
+            .map(String::as_str)
+    }
+
+    /// Whether every output field was salvaged.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.invalid.is_empty()
+    }
+}
+
+/// Split an output object into fields that validate on their own and those
+/// that are missing or invalid.
+///
+/// Expects aliases to be normalized and defaults applied already. A
+/// non-object value salvages nothing and reports every required field as
+/// missing.
+pub fn salvage_fields(value: &Value, output_fields: &[FieldSpec]) -> PartialOutputs {
+    let mut partial = PartialOutputs::default();
+    let empty = serde_json::Map::new();
+    let obj = value.as_object().unwrap_or(&empty);
+
+    for field in output_fields {
+        match obj.get(&field.name) {
+            Some(field_value) if !field_value.is_null() || !field.required => {
+                let single = Value::Object(
+                    [(field.name.clone(), field_value.clone())]
+                        .into_iter()
+                        .collect(),
+                );
+                match validate_fields(&single, std::slice::from_ref(field)) {
+                    Ok(()) => {
+                        partial
+                            .fields
+                            .insert(field.name.clone(), field_value.clone());
+                    }
+                    Err(errors) => {
+                        partial.invalid.push(field.name.clone());
+                        partial.errors.extend(errors);
+                    }
+                }
+            }
+            _ if field.required => partial.missing.push(field.name.clone()),
+            _ => {}
+        }
+    }
+
+    partial
+}
+
 /// Reason for triggering fallback extraction (SPEC-27.01).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -160,6 +278,12 @@ pub enum FallbackTrigger {
     MaxLLMCalls,
     /// Execution timeout.
     Timeout,
+    /// Projected spend would exceed the cost budget.
+    BudgetExhausted,
+    /// A recursive sub-call repeated an earlier query at the same depth.
+    CycleDetected,
+    /// The orchestrator's total recursive call limit was reached.
+    MaxTotalCalls,
     /// Manual trigger (for testing).
     Manual,
 }
@@ -170,6 +294,9 @@ impl std::fmt::Display for FallbackTrigger {
             Self::MaxIterations => write!(f, "max iterations reached"),
             Self::MaxLLMCalls => write!(f, "max LLM calls reached"),
             Self::Timeout => write!(f, "execution timeout"),
+            Self::BudgetExhausted => write!(f, "budget exhausted"),
+            Self::CycleDetected => write!(f, "recursion cycle detected"),
+            Self::MaxTotalCalls => write!(f, "max total calls reached"),
             Self::Manual => write!(f, "manual trigger"),
         }
     }
@@ -202,6 +329,8 @@ pub enum HistoryEntryType {
     LLMResponse,
     /// Variable assignment.
     VariableSet,
+    /// Fallback extraction was triggered.
+    Fallback,
 }
 
 /// REPL execution history.
@@ -270,6 +399,15 @@ impl ReplHistory {
         });
     }
 
+    /// Add an entry recording that fallback extraction was triggered.
+    pub fn add_fallback(&mut self, trigger: FallbackTrigger, timestamp_ms: u64) {
+        self.entries.push(HistoryEntry {
+            entry_type: HistoryEntryType::Fallback,
+            content: trigger.to_string(),
+            timestamp_ms,
+        });
+    }
+
     /// Format history for extraction prompt.
     pub fn format_for_prompt(&self, max_entries: usize) -> String {
         let entries: Vec<_> = if self.entries.len() > max_entries {
@@ -300,11 +438,15 @@ impl ReplHistory {
                 HistoryEntryType::LLMQuery => "[LLM Query] ",
                 HistoryEntryType::LLMResponse => "[LLM Response] ",
                 HistoryEntryType::VariableSet => "[Set] ",
+                HistoryEntryType::Fallback => "[Fallback] ",
             };
 
             // Truncate long content
             let content = if entry.content.len() > 500 {
-                format!("{}... [truncated]", &entry.content[..500])
+                format!(
+                    "{}... [truncated]",
+                    &entry.content[..floor_char_boundary(&entry.content, 500)]
+                )
             } else {
                 entry.content.clone()
             };
@@ -332,6 +474,9 @@ pub struct FallbackConfig {
     pub extraction_temperature: f64,
     /// Max tokens for extraction response.
     pub max_extraction_tokens: u32,
+    /// When the extracted outputs fail validation, return the fields that
+    /// did validate as [`ExecutionResult::Partial`] instead of failing.
+    pub salvage_partial: bool,
 }
 
 impl Default for FallbackConfig {
@@ -342,6 +487,7 @@ impl Default for FallbackConfig {
             extraction_model: None, // Use default
             extraction_temperature: 0.0,
             max_extraction_tokens: 2048,
+            salvage_partial: false,
         }
     }
 }
@@ -375,6 +521,12 @@ impl<S: Signature> FallbackExtractor<S> {
         self
     }
 
+    /// Enable or disable partial salvage (see [`FallbackConfig::salvage_partial`]).
+    pub fn with_salvage_partial(mut self, salvage_partial: bool) -> Self {
+        self.config.salvage_partial = salvage_partial;
+        self
+    }
+
     /// Check if fallback should be triggered (SPEC-27.01).
     pub fn should_trigger(
         &self,
@@ -425,7 +577,7 @@ impl<S: Signature> FallbackExtractor<S> {
                 let truncated = if v_str.len() > 1000 {
                     Value::String(format!(
                         "{}... [truncated, {} chars total]",
-                        &v_str[..1000],
+                        &v_str[..floor_char_boundary(&v_str, 1000)],
                         v_str.len()
                     ))
                 } else {
@@ -490,6 +642,10 @@ impl<S: Signature> FallbackExtractor<S> {
     }
 
     /// Parse extraction response into ExecutionResult.
+    ///
+    /// With [`FallbackConfig::salvage_partial`] on, outputs are validated
+    /// against the signature first; if any field fails, the rest are returned
+    /// as [`ExecutionResult::Partial`].
     pub fn parse_extraction_response(
         &self,
         response: &str,
@@ -520,6 +676,14 @@ impl<S: Signature> FallbackExtractor<S> {
         if let Some(obj) = output_value.as_object_mut() {
             obj.remove("_confidence");
         }
+        let output_fields = S::output_fields();
+        normalize_aliases(&mut output_value, &output_fields);
+        let output_value = apply_defaults(&output_value, &output_fields);
+
+        if self.config.salvage_partial && validate_fields(&output_value, &output_fields).is_err() {
+            let partial = salvage_fields(&output_value, &output_fields);
+            return ExecutionResult::partial(partial, confidence, trigger);
+        }
 
         // Parse into output type
         match serde_json::from_value::<S::Outputs>(output_value) {
@@ -785,6 +949,93 @@ Here is the extracted data:
         assert_eq!(result.outputs().unwrap().answer, "from markdown");
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct ReportOutputs {
+        title: String,
+        score: i64,
+        tags: Vec<String>,
+    }
+
+    struct ReportSignature;
+
+    impl Signature for ReportSignature {
+        type Inputs = ();
+        type Outputs = ReportOutputs;
+
+        fn instructions() -> &'static str {
+            "Report"
+        }
+
+        fn input_fields() -> Vec<FieldSpec> {
+            vec![]
+        }
+
+        fn output_fields() -> Vec<FieldSpec> {
+            vec![
+                FieldSpec::new("title", FieldType::String),
+                FieldSpec::new("score", FieldType::Integer),
+                FieldSpec::new("tags", FieldType::list(FieldType::String)),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_salvage_two_of_three_fields() {
+        let response = r#"{"title": "Q3 report", "score": "high", "tags": ["finance", "q3"], "_confidence": 0.6}"#;
+
+        // Off by default: the bad field fails the whole extraction
+        let strict = FallbackExtractor::<ReportSignature>::new();
+        assert!(strict
+            .parse_extraction_response(response, FallbackTrigger::MaxIterations)
+            .is_failed());
+
+        let extractor = FallbackExtractor::<ReportSignature>::new().with_salvage_partial(true);
+        let result = extractor.parse_extraction_response(response, FallbackTrigger::MaxIterations);
+        assert!(result.is_partial());
+        assert!(result.outputs().is_none());
+        assert_eq!(result.trigger(), Some(FallbackTrigger::MaxIterations));
+        assert!((result.confidence() - 0.6).abs() < 1e-9);
+
+        let partial = result.partial_outputs().unwrap();
+        assert_eq!(partial.fields.len(), 2);
+        assert_eq!(partial.fields["title"], "Q3 report");
+        assert_eq!(partial.fields["tags"], serde_json::json!(["finance", "q3"]));
+        assert!(partial.missing.is_empty());
+        assert_eq!(partial.invalid, vec!["score"]);
+        assert!(matches!(
+            partial.errors.as_slice(),
+            [ValidationError::TypeMismatch { .. }]
+        ));
+        assert!(!partial.is_complete());
+
+        // A missing field is reported separately from an invalid one
+        let response = r#"{"title": "Q3 report", "tags": ["finance"]}"#;
+        let result = extractor.parse_extraction_response(response, FallbackTrigger::Timeout);
+        let partial = result.partial_outputs().unwrap();
+        assert_eq!(partial.missing, vec!["score"]);
+        assert!(partial.invalid.is_empty());
+        assert_eq!(partial.unresolved().collect::<Vec<_>>(), vec!["score"]);
+
+        // Fully valid responses still decode into outputs
+        let response = r#"{"title": "Q3 report", "score": 7, "tags": []}"#;
+        let result = extractor.parse_extraction_response(response, FallbackTrigger::Timeout);
+        assert_eq!(result.outputs().unwrap().score, 7);
+    }
+
+    #[test]
+    fn test_history_records_fallback() {
+        let mut history = ReplHistory::new();
+        history.add_code("x = 1", 0);
+        history.add_fallback(FallbackTrigger::MaxIterations, 50);
+
+        let entry = history.entries.last().unwrap();
+        assert_eq!(entry.entry_type, HistoryEntryType::Fallback);
+        assert_eq!(history.iteration_count, 1);
+        assert!(history
+            .format_for_prompt(10)
+            .contains("[Fallback] max iterations reached"));
+    }
+
     #[test]
     fn test_parse_extraction_response_failure() {
         let extractor = FallbackExtractor::<TestSignature>::new();
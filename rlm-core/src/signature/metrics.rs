@@ -0,0 +1,280 @@
+//! Persistent SUBMIT metrics aggregated per signature.
+//!
+//! [`SubmitMetrics`] describe a single submission and are lost with the
+//! REPL. A [`SubmitMetricsStore`] folds each [`SubmitResult`] into running
+//! totals for its signature and keeps them in a [`SqliteMemoryStore`] as one
+//! `Experience` node per signature (subtype [`SUBMIT_METRICS_SUBTYPE`]), so
+//! long-running agents can later find signatures that often fail validation
+//! or need many iterations.
+//!
+//! Persistence is opt-in: attach a store to a REPL with
+//! [`ReplHandle::record_submit_metrics`](crate::repl::ReplHandle::record_submit_metrics)
+//! or call [`SubmitMetricsStore::record`] directly.
+
+use serde::{Deserialize, Serialize};
+
+use super::submit::{SubmitMetrics, SubmitResult};
+use crate::error::{Error, Result};
+use crate::memory::{Node, NodeQuery, NodeType, SqliteMemoryStore, Tier};
+
+/// Node subtype of persisted signature metrics.
+pub const SUBMIT_METRICS_SUBTYPE: &str = "submit_metrics";
+
+/// Metadata key holding the serialized [`SignatureMetrics`].
+const METRICS_KEY: &str = "submit_metrics";
+
+/// Aggregated SUBMIT metrics for one signature.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SignatureMetrics {
+    /// Signature name
+    pub signature: String,
+    /// SUBMIT calls recorded
+    pub submissions: u64,
+    /// Submissions that passed validation
+    pub successes: u64,
+    /// Iterations beyond the first, summed over successes that reported metrics
+    pub total_retries: u64,
+    /// Execution time in milliseconds, summed over successes that reported metrics
+    pub total_latency_ms: f64,
+    /// Successes that reported [`SubmitMetrics`]
+    pub timed_submissions: u64,
+}
+
+impl SignatureMetrics {
+    /// Empty metrics for a signature.
+    pub fn new(signature: impl Into<String>) -> Self {
+        Self {
+            signature: signature.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Fold one submission into the totals.
+    ///
+    /// Results other than success and validation failure are ignored.
+    pub fn record(&mut self, result: &SubmitResult) {
+        match result {
+            SubmitResult::Success { metrics, .. } => {
+                self.submissions += 1;
+                self.successes += 1;
+                if let Some(SubmitMetrics {
+                    iterations,
+                    execution_time_ms,
+                    ..
+                }) = metrics
+                {
+                    self.total_retries += u64::from(iterations.saturating_sub(1));
+                    self.total_latency_ms += execution_time_ms;
+                    self.timed_submissions += 1;
+                }
+            }
+            SubmitResult::ValidationError { .. } => self.submissions += 1,
+            SubmitResult::NotSubmitted { .. } => {}
+        }
+    }
+
+    /// Fraction of submissions that passed validation (1.0 with none recorded).
+    pub fn success_rate(&self) -> f64 {
+        if self.submissions == 0 {
+            1.0
+        } else {
+            self.successes as f64 / self.submissions as f64
+        }
+    }
+
+    /// Average iterations beyond the first per timed success.
+    pub fn avg_retries(&self) -> f64 {
+        if self.timed_submissions == 0 {
+            0.0
+        } else {
+            self.total_retries as f64 / self.timed_submissions as f64
+        }
+    }
+
+    /// Average execution time in milliseconds per timed success.
+    pub fn avg_latency_ms(&self) -> f64 {
+        if self.timed_submissions == 0 {
+            0.0
+        } else {
+            self.total_latency_ms / self.timed_submissions as f64
+        }
+    }
+
+    fn to_node(&self, node: Option<Node>) -> Node {
+        let mut node = node.unwrap_or_else(|| {
+            Node::new(NodeType::Experience, Self::node_content(&self.signature))
+                .with_subtype(SUBMIT_METRICS_SUBTYPE)
+                .with_tier(Tier::LongTerm)
+                .with_metadata("signature", self.signature.clone())
+        });
+        node.updated_at = chrono::Utc::now();
+        node.with_metadata(METRICS_KEY, serde_json::to_value(self).unwrap_or_default())
+            .with_metadata("success_rate", self.success_rate())
+            .with_metadata("avg_retries", self.avg_retries())
+            .with_metadata("avg_latency_ms", self.avg_latency_ms())
+    }
+
+    fn from_node(node: &Node) -> Option<Self> {
+        let value = node.metadata.as_ref()?.get(METRICS_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    fn node_content(signature: &str) -> String {
+        format!("Submit metrics: {}", signature)
+    }
+}
+
+/// Persists [`SignatureMetrics`] in a memory store.
+///
+/// Each [`record`](Self::record) is a single read-modify-write of the
+/// signature's node inside one transaction, so concurrent submissions for
+/// the same signature never lose counts.
+pub struct SubmitMetricsStore {
+    memory: SqliteMemoryStore,
+}
+
+impl SubmitMetricsStore {
+    /// Create a metrics store backed by the given memory store.
+    pub fn new(memory: SqliteMemoryStore) -> Self {
+        Self { memory }
+    }
+
+    /// Create an in-memory store for testing.
+    pub fn in_memory() -> Result<Self> {
+        Ok(Self::new(SqliteMemoryStore::in_memory()?))
+    }
+
+    /// Get a reference to the underlying memory store.
+    pub fn memory(&self) -> &SqliteMemoryStore {
+        &self.memory
+    }
+
+    /// Record a submission for `signature`, returning the updated totals.
+    pub fn record(&self, signature: &str, result: &SubmitResult) -> Result<SignatureMetrics> {
+        let mut updated = None;
+        self.memory.upsert_node(
+            NodeType::Experience,
+            SUBMIT_METRICS_SUBTYPE,
+            &SignatureMetrics::node_content(signature),
+            |existing| {
+                let mut metrics = existing
+                    .as_ref()
+                    .and_then(SignatureMetrics::from_node)
+                    .unwrap_or_else(|| SignatureMetrics::new(signature));
+                metrics.record(result);
+                let node = metrics.to_node(existing);
+                updated = Some(metrics);
+                node
+            },
+        )?;
+        updated.ok_or_else(|| Error::Internal("submit metrics were not updated".to_string()))
+    }
+
+    /// Aggregated metrics for one signature.
+    pub fn get(&self, signature: &str) -> Result<Option<SignatureMetrics>> {
+        Ok(self
+            .memory
+            .find_node(
+                NodeType::Experience,
+                SUBMIT_METRICS_SUBTYPE,
+                &SignatureMetrics::node_content(signature),
+            )?
+            .as_ref()
+            .and_then(SignatureMetrics::from_node))
+    }
+
+    /// Aggregated metrics for every recorded signature, least reliable first.
+    ///
+    /// Signatures are ordered by ascending success rate, then by descending
+    /// average retries.
+    pub fn all(&self) -> Result<Vec<SignatureMetrics>> {
+        let nodes = self.memory.query_nodes(
+            &NodeQuery::new()
+                .node_types(vec![NodeType::Experience])
+                .subtype(SUBMIT_METRICS_SUBTYPE),
+        )?;
+        let mut all: Vec<SignatureMetrics> = nodes
+            .iter()
+            .filter_map(SignatureMetrics::from_node)
+            .collect();
+        all.sort_by(|a, b| {
+            a.success_rate()
+                .total_cmp(&b.success_rate())
+                .then(b.avg_retries().total_cmp(&a.avg_retries()))
+                .then_with(|| a.signature.cmp(&b.signature))
+        });
+        Ok(all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::SubmitError;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn success(iterations: u32, execution_time_ms: f64) -> SubmitResult {
+        SubmitResult::success_with_metrics(
+            json!({"answer": "ok"}),
+            SubmitMetrics {
+                iterations,
+                execution_time_ms,
+                llm_calls: 1,
+            },
+        )
+    }
+
+    #[test]
+    fn test_record_and_read_back_aggregated_metrics() {
+        let store = SubmitMetricsStore::in_memory().unwrap();
+        let failure = SubmitResult::validation_error(vec![SubmitError::NoSignatureRegistered]);
+
+        store.record("Summarize", &success(1, 100.0)).unwrap();
+        store.record("Summarize", &success(3, 300.0)).unwrap();
+        store.record("Summarize", &failure).unwrap();
+        store
+            .record("Summarize", &SubmitResult::not_submitted("ended"))
+            .unwrap();
+        store.record("Classify", &success(1, 50.0)).unwrap();
+
+        let summarize = store.get("Summarize").unwrap().unwrap();
+        assert_eq!(summarize.submissions, 3);
+        assert_eq!(summarize.successes, 2);
+        assert!((summarize.success_rate() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summarize.avg_retries(), 1.0);
+        assert_eq!(summarize.avg_latency_ms(), 200.0);
+
+        // One node per signature, flakiest first
+        let all = store.all().unwrap();
+        assert_eq!(
+            all.iter().map(|m| m.signature.as_str()).collect::<Vec<_>>(),
+            vec!["Summarize", "Classify"]
+        );
+        assert!(store.get("Unknown").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_concurrent_records_keep_every_count() {
+        let store = Arc::new(SubmitMetricsStore::in_memory().unwrap());
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        store.record("Shared", &success(2, 10.0)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let metrics = store.get("Shared").unwrap().unwrap();
+        assert_eq!(metrics.submissions, 80);
+        assert_eq!(metrics.total_retries, 80);
+        assert_eq!(store.all().unwrap().len(), 1);
+    }
+}
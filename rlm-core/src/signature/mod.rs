@@ -56,8 +56,13 @@
 //! - [`Signature`]: Core trait defining I/O contracts
 //! - [`FieldSpec`]: Field metadata (name, type, description)
 //! - [`FieldType`]: Type information for validation
+//! - [`FieldShape`]: Field specs for nested struct types
+//! - [`DynamicSignature`]: Signature assembled from field specs at runtime
 //! - [`ValidationError`]: Errors from validation
 //! - [`ParseError`]: Errors from parsing LLM responses
+//! - [`ParseFormat`]: Response format (JSON or XML tags) for parsing
+//! - [`JsonExtractor`]: Strategy for unwrapping JSON from a response
+//! - [`SubmitMetricsStore`]: Per-signature SUBMIT metrics in the memory store
 //!
 //! # Related Specs
 //!
@@ -65,27 +70,51 @@
 //! - SPEC-20.02: Field Specification
 //! - SPEC-20.03: Signature Validation
 
+pub mod dynamic;
+pub mod extract;
 pub mod fallback;
+pub mod metrics;
+pub mod shape;
 pub mod submit;
+pub mod template;
 pub mod types;
 pub mod validation;
+pub mod xml;
 
+pub use dynamic::DynamicSignature;
+pub use extract::{
+    default_extractors, extract_candidates, CodeBlockExtractor, JsonExtractor, RawObjectExtractor,
+};
 pub use fallback::{
-    ExecutionLimits, ExecutionResult, FallbackConfig, FallbackExtractor, FallbackTrigger,
-    HistoryEntry, HistoryEntryType, ReplHistory,
+    salvage_fields, ExecutionLimits, ExecutionResult, FallbackConfig, FallbackExtractor,
+    FallbackTrigger, HistoryEntry, HistoryEntryType, PartialOutputs, ReplHistory,
 };
+pub use metrics::{SignatureMetrics, SubmitMetricsStore, SUBMIT_METRICS_SUBTYPE};
+pub use shape::FieldShape;
 pub use submit::{SignatureRegistration, SubmitError, SubmitMetrics, SubmitResult};
-pub use types::{FieldSpec, FieldType};
+pub use template::{
+    InstructionsLastTemplate, MarkdownTemplate, PromptParts, PromptTemplate, RenderedInput,
+};
+pub use types::{FieldSpec, FieldType, NumericRange};
 pub use validation::{
-    apply_defaults, validate_fields, validate_value, ValidationError, ValidationResult,
+    apply_defaults, coerce_scalars, normalize_aliases, validate_fields, validate_value,
+    ValidationError, ValidationResult,
 };
 
-// Re-export derive macro
-pub use rlm_core_derive::Signature;
+// Re-export derive macros
+pub use rlm_core_derive::{FieldShape, Signature};
 
+use crate::llm::{estimate_tokens, Provider};
+use crate::module::{Demonstration, ErasedDemonstration};
+use crate::repl::floor_char_boundary;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
+
+/// Key of the optional per-field confidence object in a response.
+pub const CONFIDENCE_KEY: &str = "_field_confidence";
 
 /// Error that occurs when parsing an LLM response into outputs.
 #[derive(Debug, Clone, PartialEq)]
@@ -174,6 +203,72 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// Format of an LLM response handed to [`Signature::from_response_with_format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseFormat {
+    /// A JSON object, optionally wrapped in a markdown code block.
+    #[default]
+    Json,
+    /// One XML tag per output field (see [`xml`]).
+    Xml,
+}
+
+/// Options for [`Signature::from_response_with_config`].
+#[derive(Debug, Clone)]
+pub struct ParseConfig {
+    /// Response format
+    pub format: ParseFormat,
+    /// Convert string-encoded numbers and booleans to their declared field
+    /// types before validation (see [`coerce_scalars`]). Off by default so
+    /// a model emitting the wrong type is reported rather than masked.
+    pub coerce_scalars: bool,
+    /// JSON extractors tried in order for [`ParseFormat::Json`]; a later
+    /// extractor's candidates are used only if no earlier one's parse and
+    /// validate (see [`extract_candidates`])
+    pub extractors: Vec<Arc<dyn JsonExtractor>>,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self {
+            format: ParseFormat::default(),
+            coerce_scalars: false,
+            extractors: default_extractors(),
+        }
+    }
+}
+
+impl ParseConfig {
+    /// Default configuration: JSON, no coercion, built-in extractors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the response format.
+    pub fn with_format(mut self, format: ParseFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Enable or disable scalar coercion.
+    pub fn with_coercion(mut self, coerce_scalars: bool) -> Self {
+        self.coerce_scalars = coerce_scalars;
+        self
+    }
+
+    /// Try `extractor` before the extractors already configured.
+    pub fn with_extractor(mut self, extractor: impl JsonExtractor + 'static) -> Self {
+        self.extractors.insert(0, Arc::new(extractor));
+        self
+    }
+
+    /// Replace the configured extractors.
+    pub fn with_extractors(mut self, extractors: Vec<Arc<dyn JsonExtractor>>) -> Self {
+        self.extractors = extractors;
+        self
+    }
+}
+
 /// Core trait defining a typed LLM I/O contract.
 ///
 /// A Signature specifies:
@@ -270,53 +365,119 @@ pub trait Signature: Send + Sync + 'static {
     where
         Self: Sized,
     {
-        let mut prompt = String::new();
-
-        // Instructions
-        prompt.push_str("## Task\n\n");
-        prompt.push_str(Self::instructions());
-        prompt.push_str("\n\n");
+        let input_json = serde_json::to_value(inputs).unwrap_or(Value::Null);
+        render_prompt(
+            Self::instructions(),
+            &Self::input_fields(),
+            &Self::output_fields(),
+            &input_json,
+        )
+    }
 
-        // Inputs
-        prompt.push_str("## Inputs\n\n");
+    /// Generate a prompt laid out by `template`.
+    ///
+    /// [`to_prompt`](Self::to_prompt) is this with [`MarkdownTemplate`].
+    fn to_prompt_with_template<T>(inputs: &Self::Inputs, template: &T) -> String
+    where
+        Self: Sized,
+        T: PromptTemplate + ?Sized,
+    {
         let input_json = serde_json::to_value(inputs).unwrap_or(Value::Null);
-        for field in Self::input_fields() {
-            let value = input_json.get(&field.name);
-            let label = field.display_label();
-            match value {
-                Some(v) => {
-                    prompt.push_str(&format!("**{}**: {}\n", label, format_value(v)));
-                }
-                None if !field.required => {
-                    // Skip optional missing fields
-                }
-                None => {
-                    prompt.push_str(&format!("**{}**: (not provided)\n", label));
-                }
-            }
-        }
-        prompt.push('\n');
+        render_prompt_with_template(
+            template,
+            Self::instructions(),
+            &Self::input_fields(),
+            &Self::output_fields(),
+            &[],
+            None,
+            &input_json,
+        )
+    }
 
-        // Output specification
-        prompt.push_str("## Required Output\n\n");
-        prompt.push_str("Respond with a JSON object containing:\n\n");
-        for field in Self::output_fields() {
-            prompt.push_str(&format!("- {}\n", field.to_prompt_line()));
-        }
-        prompt.push_str("\n```json\n");
-        prompt.push_str(&generate_output_template::<Self>());
-        prompt.push_str("\n```\n");
+    /// Generate a few-shot prompt from inputs and demonstrations.
+    ///
+    /// Renders an "## Examples" section with each demonstration's inputs and
+    /// expected JSON output ahead of the real inputs. This method always
+    /// includes the demos it is given; callers honoring
+    /// [`ModuleConfig::use_demonstrations`](crate::module::ModuleConfig) pass
+    /// an empty slice when it is off. With no demos this equals
+    /// [`to_prompt`](Self::to_prompt).
+    fn to_prompt_with_demos(inputs: &Self::Inputs, demos: &[Demonstration<Self>]) -> String
+    where
+        Self: Sized,
+    {
+        Self::to_prompt_with_demo_budget(inputs, demos, None)
+    }
 
-        prompt
+    /// Like [`to_prompt_with_demos`](Self::to_prompt_with_demos), but keeps
+    /// only as many leading demos as fit in `max_demo_tokens` (estimated).
+    fn to_prompt_with_demo_budget(
+        inputs: &Self::Inputs,
+        demos: &[Demonstration<Self>],
+        max_demo_tokens: Option<usize>,
+    ) -> String
+    where
+        Self: Sized,
+    {
+        let input_json = serde_json::to_value(inputs).unwrap_or(Value::Null);
+        let demos: Vec<ErasedDemonstration> =
+            demos.iter().map(ErasedDemonstration::from_typed).collect();
+        render_prompt_with_demos(
+            Self::instructions(),
+            &Self::input_fields(),
+            &Self::output_fields(),
+            &demos,
+            max_demo_tokens,
+            &input_json,
+        )
     }
 
     /// Parse outputs from an LLM response.
     ///
     /// Default implementation:
-    /// 1. Extracts JSON from the response (handles markdown code blocks)
-    /// 2. Parses into the output type
-    /// 3. Validates against output field specs
+    /// 1. Extracts JSON candidates from the response (handles markdown code
+    ///    blocks, several objects, and arrays of objects)
+    /// 2. Parses each candidate into the output type
+    /// 3. Renames aliased keys to their canonical field names
+    /// 4. Fills omitted or `null` optional fields from their declared defaults
+    /// 5. Validates against output field specs, returning the first candidate
+    ///    that passes
     fn from_response(response: &str) -> Result<Self::Outputs, ParseError>
+    where
+        Self: Sized,
+    {
+        Self::from_response_with_config(response, &ParseConfig::default())
+    }
+
+    /// Parse outputs from an LLM response in the given format.
+    ///
+    /// [`ParseFormat::Json`] parses like [`Signature::from_response`].
+    /// [`ParseFormat::Xml`] reads one tag per output field, coerces scalars
+    /// and lists to their field types, then applies defaults and validates
+    /// like the JSON path.
+    fn from_response_with_format(
+        response: &str,
+        format: ParseFormat,
+    ) -> Result<Self::Outputs, ParseError>
+    where
+        Self: Sized,
+    {
+        Self::from_response_with_config(response, &ParseConfig::new().with_format(format))
+    }
+
+    /// Parse outputs from an LLM response with explicit [`ParseConfig`].
+    ///
+    /// JSON candidates are found with the configured
+    /// [`extractors`](ParseConfig::extractors).
+    ///
+    /// With [`coerce_scalars`](ParseConfig::coerce_scalars) on, string
+    /// values such as `"0.95"` or `"true"` are converted to their declared
+    /// field types after alias normalization and before defaults and
+    /// validation.
+    fn from_response_with_config(
+        response: &str,
+        config: &ParseConfig,
+    ) -> Result<Self::Outputs, ParseError>
     where
         Self: Sized,
     {
@@ -326,22 +487,105 @@ pub trait Signature: Send + Sync + 'static {
             return Err(ParseError::EmptyResponse);
         }
 
-        // Extract JSON from response (may be wrapped in markdown)
-        let json_str = extract_json(response);
+        let output_fields = Self::output_fields();
+        let candidates: Vec<Result<Value, ParseError>> = match config.format {
+            // JSON may be wrapped in markdown, or come as several drafts
+            ParseFormat::Json => extract_candidates(response, &config.extractors)
+                .into_iter()
+                .map(|json_str| {
+                    serde_json::from_str::<Value>(json_str)
+                        .map_err(|e| ParseError::invalid_json(&e, json_str))
+                })
+                .collect(),
+            ParseFormat::Xml => vec![xml::parse_xml_fields(response, &output_fields)],
+        };
+
+        // Return the first candidate that parses and validates
+        let mut best_error = None;
+        for candidate in candidates {
+            let result = candidate.and_then(|mut value| {
+                normalize_aliases(&mut value, &output_fields);
+                if config.coerce_scalars {
+                    coerce_scalars(&mut value, &output_fields);
+                }
+                let value = apply_defaults(&value, &output_fields);
+                validate_fields(&value, &output_fields).map_err(ParseError::validation_failed)?;
+                serde_json::from_value(value).map_err(|e| {
+                    ParseError::structure_mismatch(
+                        std::any::type_name::<Self::Outputs>(),
+                        e.to_string(),
+                    )
+                })
+            });
+
+            match result {
+                Ok(outputs) => return Ok(outputs),
+                Err(e) => {
+                    // Prefer reporting a candidate that was at least valid JSON
+                    let replace = match &best_error {
+                        None => true,
+                        Some(ParseError::InvalidJson { .. }) => {
+                            !matches!(e, ParseError::InvalidJson { .. })
+                        }
+                        Some(_) => false,
+                    };
+                    if replace {
+                        best_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        Err(best_error.unwrap_or(ParseError::EmptyResponse))
+    }
+
+    /// Parse outputs from an XML-tagged LLM response.
+    ///
+    /// Shorthand for `from_response_with_format(response, ParseFormat::Xml)`.
+    fn from_response_xml(response: &str) -> Result<Self::Outputs, ParseError>
+    where
+        Self: Sized,
+    {
+        Self::from_response_with_format(response, ParseFormat::Xml)
+    }
 
-        // Parse JSON
-        let value: Value =
-            serde_json::from_str(json_str).map_err(|e| ParseError::invalid_json(&e, json_str))?;
+    /// Generate a prompt that also asks for a per-field confidence object.
+    ///
+    /// Appends a request for a [`CONFIDENCE_KEY`] object alongside the
+    /// outputs; read it back with
+    /// [`from_response_with_confidence`](Self::from_response_with_confidence).
+    fn to_prompt_with_confidence(inputs: &Self::Inputs) -> String
+    where
+        Self: Sized,
+    {
+        let mut prompt = Self::to_prompt(inputs);
+        prompt.push_str(&render_confidence_request(&Self::output_fields()));
+        prompt
+    }
 
-        // Validate against output fields
-        if let Err(errors) = validate_fields(&value, &Self::output_fields()) {
-            return Err(ParseError::validation_failed(errors));
+    /// Parse outputs plus the per-field confidences from a response.
+    ///
+    /// Outputs are parsed exactly as by [`from_response`](Self::from_response),
+    /// which ignores the [`CONFIDENCE_KEY`] object. Confidences come from the
+    /// same JSON candidate, clamped to `0.0..=1.0`; fields without one get
+    /// `1.0`.
+    fn from_response_with_confidence(
+        response: &str,
+    ) -> Result<(Self::Outputs, HashMap<String, f64>), ParseError>
+    where
+        Self: Sized,
+    {
+        let output_fields = Self::output_fields();
+        for json_str in extract_json(response.trim()) {
+            if let Ok(outputs) = Self::from_response(json_str) {
+                let value = serde_json::from_str(json_str).unwrap_or(Value::Null);
+                return Ok((outputs, field_confidences(&value, &output_fields)));
+            }
         }
 
-        // Parse into output type
-        serde_json::from_value(value.clone()).map_err(|e| {
-            ParseError::structure_mismatch(std::any::type_name::<Self::Outputs>(), e.to_string())
-        })
+        // No candidate parses; report the error the primary path would
+        Self::from_response(response)
+            .map(|outputs| (outputs, field_confidences(&Value::Null, &output_fields)))
     }
 
     /// Get the signature name (defaults to type name).
@@ -354,73 +598,253 @@ pub trait Signature: Send + Sync + 'static {
     where
         Self: Sized,
     {
-        let output_fields = Self::output_fields();
+        object_schema(&Self::output_fields())
+    }
+}
 
-        let properties: serde_json::Map<String, Value> = output_fields
-            .iter()
-            .map(|f| (f.name.clone(), f.field_type.to_json_schema()))
-            .collect();
-
-        let required: Vec<String> = output_fields
-            .iter()
-            .filter(|f| f.required)
-            .map(|f| f.name.clone())
-            .collect();
-
-        serde_json::json!({
-            "type": "object",
-            "properties": properties,
-            "required": required
+/// Render the request for a [`CONFIDENCE_KEY`] object.
+fn render_confidence_request(output_fields: &[FieldSpec]) -> String {
+    let example: serde_json::Map<String, Value> = output_fields
+        .iter()
+        .map(|field| (field.name.clone(), Value::from(0.9)))
+        .collect();
+    format!(
+        "\nAlso include a \"{key}\" object giving your confidence in each output field, \
+         from 0.0 (guess) to 1.0 (certain):\n\n```json\n{{\"{key}\": {example}}}\n```\n",
+        key = CONFIDENCE_KEY,
+        example = Value::Object(example),
+    )
+}
+
+/// Read per-field confidences from a response object.
+///
+/// Aliased keys count for their field. Missing or non-numeric entries
+/// default to `1.0`.
+fn field_confidences(value: &Value, output_fields: &[FieldSpec]) -> HashMap<String, f64> {
+    let mut reported = value.get(CONFIDENCE_KEY).cloned().unwrap_or(Value::Null);
+    normalize_aliases(&mut reported, output_fields);
+    output_fields
+        .iter()
+        .map(|field| {
+            let confidence = reported
+                .get(&field.name)
+                .and_then(Value::as_f64)
+                .map_or(1.0, |c| c.clamp(0.0, 1.0));
+            (field.name.clone(), confidence)
         })
-    }
+        .collect()
 }
 
-/// Extract JSON from a response that may contain markdown or other text.
-fn extract_json(response: &str) -> &str {
-    // Try to find JSON in code blocks
-    if let Some(start) = response.find("```json") {
-        let content_start = start + 7;
-        if let Some(end) = response[content_start..].find("```") {
-            return response[content_start..content_start + end].trim();
-        }
-    }
+/// Render the structured prompt used by [`Signature::to_prompt`].
+///
+/// `inputs` is the serialized input object; fields are looked up by name.
+pub(crate) fn render_prompt(
+    instructions: &str,
+    input_fields: &[FieldSpec],
+    output_fields: &[FieldSpec],
+    inputs: &Value,
+) -> String {
+    render_prompt_with_demos(instructions, input_fields, output_fields, &[], None, inputs)
+}
+
+/// Render the structured prompt with a few-shot "## Examples" section.
+///
+/// Demos are kept in order until the next one would push the estimated
+/// size of the section past `max_demo_tokens`.
+pub(crate) fn render_prompt_with_demos(
+    instructions: &str,
+    input_fields: &[FieldSpec],
+    output_fields: &[FieldSpec],
+    demos: &[ErasedDemonstration],
+    max_demo_tokens: Option<usize>,
+    inputs: &Value,
+) -> String {
+    render_prompt_with_template(
+        &MarkdownTemplate,
+        instructions,
+        input_fields,
+        output_fields,
+        demos,
+        max_demo_tokens,
+        inputs,
+    )
+}
+
+/// Render the prompt parts and lay them out with `template`.
+pub(crate) fn render_prompt_with_template<T: PromptTemplate + ?Sized>(
+    template: &T,
+    instructions: &str,
+    input_fields: &[FieldSpec],
+    output_fields: &[FieldSpec],
+    demos: &[ErasedDemonstration],
+    max_demo_tokens: Option<usize>,
+    inputs: &Value,
+) -> String {
+    // Few-shot examples
+    let kept = demos_within_budget(input_fields, output_fields, demos, max_demo_tokens);
+    let examples: String = demos[..kept]
+        .iter()
+        .enumerate()
+        .map(|(i, demo)| render_demo(i + 1, input_fields, output_fields, demo))
+        .collect();
+
+    // Inputs; optional fields that weren't provided are skipped
+    let rendered_inputs = input_fields
+        .iter()
+        .filter_map(|field| {
+            let value = inputs.get(&field.name).map(format_value);
+            (value.is_some() || field.required).then(|| RenderedInput {
+                field,
+                label: field.display_label().to_string(),
+                value,
+            })
+        })
+        .collect();
+
+    template.render(&PromptParts {
+        instructions,
+        inputs: rendered_inputs,
+        examples,
+        output_fields,
+    })
+}
 
-    // Try generic code block
-    if let Some(start) = response.find("```") {
-        let content_start = start + 3;
-        // Skip language identifier if present
-        let content_start = response[content_start..]
-            .find('\n')
-            .map(|i| content_start + i + 1)
-            .unwrap_or(content_start);
-        if let Some(end) = response[content_start..].find("```") {
-            return response[content_start..content_start + end].trim();
+/// Number of leading `demos` whose rendered examples fit in
+/// `max_demo_tokens` (all of them when there is no cap).
+pub(crate) fn demos_within_budget(
+    input_fields: &[FieldSpec],
+    output_fields: &[FieldSpec],
+    demos: &[ErasedDemonstration],
+    max_demo_tokens: Option<usize>,
+) -> usize {
+    let Some(max) = max_demo_tokens else {
+        return demos.len();
+    };
+    let mut used = 0;
+    for (i, demo) in demos.iter().enumerate() {
+        let rendered = render_demo(i + 1, input_fields, output_fields, demo);
+        used += estimate_tokens(&rendered, Provider::Anthropic) as usize;
+        if used > max {
+            return i;
         }
     }
+    demos.len()
+}
 
-    // Try to find raw JSON object
-    if let Some(start) = response.find('{') {
-        if let Some(end) = response.rfind('}') {
-            if end > start {
-                return &response[start..=end];
-            }
+/// Render one demonstration for the "## Examples" section.
+///
+/// Fields absent from the demo (or null) are omitted rather than rendered
+/// as missing.
+fn render_demo(
+    number: usize,
+    input_fields: &[FieldSpec],
+    output_fields: &[FieldSpec],
+    demo: &ErasedDemonstration,
+) -> String {
+    let mut out = format!("### Example {}\n\n", number);
+    for field in input_fields {
+        match demo.inputs.get(&field.name) {
+            Some(Value::Null) | None => {}
+            Some(v) => out.push_str(&format!(
+                "**{}**: {}\n",
+                field.display_label(),
+                format_value(v)
+            )),
         }
     }
+    if let Some(ref reasoning) = demo.reasoning {
+        out.push_str(&format!("\nReasoning: {}\n", reasoning));
+    }
 
-    // Return as-is
-    response
+    let outputs: serde_json::Map<String, Value> = output_fields
+        .iter()
+        .filter_map(|field| {
+            demo.outputs
+                .get(&field.name)
+                .filter(|v| !v.is_null())
+                .map(|v| (field.name.clone(), v.clone()))
+        })
+        .collect();
+    out.push_str("\nOutput:\n```json\n");
+    out.push_str(&serde_json::to_string_pretty(&outputs).unwrap_or_default());
+    out.push_str("\n```\n\n");
+    out
+}
+
+/// JSON schema for an object with the given fields.
+///
+/// `required` lists fields in declaration order and `properties` is keyed
+/// by name (serialized sorted), so the schema serializes identically on
+/// every run and can be hashed or snapshotted.
+pub(crate) fn object_schema(fields: &[FieldSpec]) -> Value {
+    let properties: serde_json::Map<String, Value> = fields
+        .iter()
+        .map(|f| (f.name.clone(), f.field_type.to_json_schema()))
+        .collect();
+
+    let required: Vec<String> = fields
+        .iter()
+        .filter(|f| f.required)
+        .map(|f| f.name.clone())
+        .collect();
+
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required
+    })
+}
+
+/// Extract JSON candidates with the default [`JsonExtractor`]s.
+fn extract_json(response: &str) -> Vec<&str> {
+    extract_candidates(response, &default_extractors())
 }
 
 /// Generate an output template with placeholder values.
-fn generate_output_template<S: Signature>() -> String {
-    let mut obj = serde_json::Map::new();
+///
+/// Rendered by hand, like `serde_json::to_string_pretty`, so fields appear
+/// in declaration order rather than sorted by name.
+fn generate_output_template(output_fields: &[FieldSpec]) -> String {
+    let mut out = String::new();
+    render_object_placeholder(output_fields, 0, &mut out);
+    out
+}
 
-    for field in S::output_fields() {
-        let placeholder = field_placeholder(&field.field_type);
-        obj.insert(field.name.clone(), placeholder);
+/// Render `{ "field": <placeholder>, ... }` at `indent` levels deep.
+fn render_object_placeholder(fields: &[FieldSpec], indent: usize, out: &mut String) {
+    if fields.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+    out.push_str("{\n");
+    for (i, field) in fields.iter().enumerate() {
+        out.push_str(&"  ".repeat(indent + 1));
+        out.push_str(&Value::String(field.name.clone()).to_string());
+        out.push_str(": ");
+        render_placeholder(&field.field_type, indent + 1, out);
+        if i + 1 < fields.len() {
+            out.push(',');
+        }
+        out.push('\n');
     }
+    out.push_str(&"  ".repeat(indent));
+    out.push('}');
+}
 
-    serde_json::to_string_pretty(&Value::Object(obj)).unwrap_or_default()
+/// Render the placeholder for `field_type` at `indent` levels deep.
+fn render_placeholder(field_type: &FieldType, indent: usize, out: &mut String) {
+    match field_type {
+        FieldType::List(inner) => {
+            out.push_str("[\n");
+            out.push_str(&"  ".repeat(indent + 1));
+            render_placeholder(inner, indent + 1, out);
+            out.push('\n');
+            out.push_str(&"  ".repeat(indent));
+            out.push(']');
+        }
+        FieldType::Object(fields) => render_object_placeholder(fields, indent, out),
+        other => out.push_str(&field_placeholder(other).to_string()),
+    }
 }
 
 /// Generate a placeholder value for a field type.
@@ -464,7 +888,7 @@ fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len])
+        format!("{}...", &s[..floor_char_boundary(s, max_len)])
     }
 }
 
@@ -529,6 +953,94 @@ mod tests {
         assert!(prompt.contains("confidence"));
     }
 
+    #[test]
+    fn test_to_prompt_with_template() {
+        let inputs = TestInputs {
+            query: "What is Rust?".to_string(),
+            limit: None,
+        };
+
+        let default = TestSignature::to_prompt(&inputs);
+        assert_eq!(
+            default,
+            TestSignature::to_prompt_with_template(&inputs, &MarkdownTemplate)
+        );
+
+        let last = TestSignature::to_prompt_with_template(&inputs, &InstructionsLastTemplate);
+        let order = |prompt: &str| {
+            let task = prompt.find("## Task").unwrap();
+            let inputs = prompt.find("## Inputs").unwrap();
+            let output = prompt.find("## Required Output").unwrap();
+            (task, inputs, output)
+        };
+        let (task, inputs_at, output) = order(&default);
+        assert!(task < inputs_at && inputs_at < output);
+        let (task, inputs_at, output) = order(&last);
+        assert!(inputs_at < output && output < task);
+        assert!(last
+            .trim_end()
+            .ends_with("Answer the query with confidence"));
+
+        // Same field rendering either way
+        assert!(last.contains("**query**: What is Rust?"));
+        assert!(last.contains("\"answer\": \"<string>\""));
+    }
+
+    fn demo(query: &str, answer: &str) -> Demonstration<TestSignature> {
+        Demonstration::new(
+            TestInputs {
+                query: query.to_string(),
+                limit: None,
+            },
+            TestOutputs {
+                answer: answer.to_string(),
+                confidence: 0.9,
+            },
+        )
+    }
+
+    #[test]
+    fn test_to_prompt_with_demos() {
+        let inputs = TestInputs {
+            query: "What is Rust?".to_string(),
+            limit: Some(100),
+        };
+        let demos = [demo("What is Go?", "A language"), demo("What is 2+2?", "4")];
+
+        let prompt = TestSignature::to_prompt_with_demos(&inputs, &demos);
+
+        let examples = prompt.find("## Examples").unwrap();
+        let first = prompt.find("What is Go?").unwrap();
+        let second = prompt.find("What is 2+2?").unwrap();
+        let query = prompt.find("What is Rust?").unwrap();
+        assert!(examples < first && first < second && second < query);
+        assert!(prompt.contains("\"answer\": \"A language\""));
+        // The omitted optional input is skipped in the demos
+        assert_eq!(prompt.matches("**limit**").count(), 1);
+        assert!(!prompt.contains("null"));
+
+        assert_eq!(
+            TestSignature::to_prompt_with_demos(&inputs, &[]),
+            TestSignature::to_prompt(&inputs)
+        );
+    }
+
+    #[test]
+    fn test_to_prompt_with_demo_budget() {
+        let inputs = TestInputs {
+            query: "What is Rust?".to_string(),
+            limit: None,
+        };
+        let demos = [demo("What is Go?", "A language"), demo("What is 2+2?", "4")];
+
+        let prompt = TestSignature::to_prompt_with_demo_budget(&inputs, &demos, Some(40));
+        assert!(prompt.contains("What is Go?"));
+        assert!(!prompt.contains("What is 2+2?"));
+
+        let prompt = TestSignature::to_prompt_with_demo_budget(&inputs, &demos, Some(0));
+        assert!(!prompt.contains("## Examples"));
+    }
+
     #[test]
     fn test_from_response_json() {
         let response = r#"{"answer": "Rust is a programming language", "confidence": 0.95}"#;
@@ -593,6 +1105,44 @@ I hope this helps!
         assert!(matches!(result.unwrap_err(), ParseError::EmptyResponse));
     }
 
+    #[test]
+    fn test_from_response_xml() {
+        let response = r#"
+Here you go:
+<answer><![CDATA[Rust <3 & memory safety]]></answer>
+<confidence>0.85</confidence>
+"#;
+
+        let outputs = TestSignature::from_response_xml(response).unwrap();
+
+        assert_eq!(outputs.answer, "Rust <3 & memory safety");
+        assert!((outputs.confidence - 0.85).abs() < 0.001);
+        assert_eq!(
+            TestSignature::from_response_with_format(
+                r#"{"answer": "a", "confidence": 1.0}"#,
+                ParseFormat::Json
+            )
+            .unwrap()
+            .answer,
+            "a"
+        );
+    }
+
+    #[test]
+    fn test_from_response_xml_missing_and_invalid() {
+        let missing = TestSignature::from_response_xml("<answer>only</answer>");
+        assert!(matches!(missing, Err(ParseError::ValidationFailed(_))));
+
+        let wrong_type =
+            TestSignature::from_response_xml("<answer>a</answer><confidence>high</confidence>");
+        assert!(matches!(wrong_type, Err(ParseError::ValidationFailed(_))));
+
+        assert!(matches!(
+            TestSignature::from_response_xml("   "),
+            Err(ParseError::EmptyResponse)
+        ));
+    }
+
     #[test]
     fn test_output_schema() {
         let schema = TestSignature::output_schema();
@@ -606,16 +1156,193 @@ I hope this helps!
             .contains(&Value::String("answer".to_string())));
     }
 
+    #[test]
+    fn test_output_schema_declaration_order() {
+        let fields = vec![
+            FieldSpec::new("summary", FieldType::String),
+            FieldSpec::new(
+                "location",
+                FieldType::object(vec![
+                    FieldSpec::new("line", FieldType::Integer),
+                    FieldSpec::new("file", FieldType::String),
+                ]),
+            ),
+            FieldSpec::new("confidence", FieldType::Float).optional(),
+            FieldSpec::new("action", FieldType::String),
+        ];
+
+        let first = serde_json::to_string(&object_schema(&fields)).unwrap();
+        let second = serde_json::to_string(&object_schema(&fields)).unwrap();
+        assert_eq!(first, second);
+
+        let schema = object_schema(&fields);
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["summary", "location", "action"])
+        );
+        assert_eq!(
+            schema["properties"]["location"]["required"],
+            serde_json::json!(["line", "file"])
+        );
+
+        let template = generate_output_template(&fields);
+        let positions: Vec<_> = [
+            "summary",
+            "location",
+            "line",
+            "file",
+            "confidence",
+            "action",
+        ]
+        .iter()
+        .map(|name| template.find(&format!("\"{}\"", name)).unwrap())
+        .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        // The hand-rendered template is still the JSON it looks like.
+        let parsed: Value = serde_json::from_str(&template).unwrap();
+        assert_eq!(parsed, field_placeholder(&FieldType::object(fields)));
+    }
+
+    #[test]
+    fn test_truncate_multibyte() {
+        assert_eq!(truncate("café", 4), "caf...");
+        assert_eq!(truncate("café", 5), "café");
+        assert_eq!(truncate("日本語", 4), "日...");
+        assert_eq!(truncate("👍ok", 2), "...");
+
+        let response = format!("{{\"answer\": \"{}", "é".repeat(150));
+        let err = serde_json::from_str::<Value>(&response).unwrap_err();
+        match ParseError::invalid_json(&err, &response) {
+            ParseError::InvalidJson {
+                response_preview, ..
+            } => assert!(response_preview.ends_with("...")),
+            other => panic!("expected InvalidJson, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_scalar_coercion_is_opt_in() {
+        let response = r#"{"answer": "Rust", "confidence": "0.95"}"#;
+        assert!(matches!(
+            TestSignature::from_response(response),
+            Err(ParseError::ValidationFailed(_))
+        ));
+
+        let config = ParseConfig::new().with_coercion(true);
+        let outputs = TestSignature::from_response_with_config(response, &config).unwrap();
+        assert_eq!(outputs.confidence, 0.95);
+
+        for bad in ["abc", "0,95", "NaN"] {
+            let response = format!(r#"{{"answer": "Rust", "confidence": "{}"}}"#, bad);
+            assert!(
+                matches!(
+                    TestSignature::from_response_with_config(&response, &config),
+                    Err(ParseError::ValidationFailed(_))
+                ),
+                "{} should not coerce",
+                bad
+            );
+        }
+    }
+
+    /// Unwraps `<json>...</json>` tags.
+    #[derive(Debug)]
+    struct JsonTagExtractor;
+
+    impl JsonExtractor for JsonTagExtractor {
+        fn extract<'a>(&self, response: &'a str) -> Vec<&'a str> {
+            response
+                .split("<json>")
+                .skip(1)
+                .filter_map(|rest| rest.split_once("</json>"))
+                .map(|(inner, _)| inner.trim())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_custom_json_extractor() {
+        let config = ParseConfig::new().with_extractor(JsonTagExtractor);
+
+        let tagged = r#"Output: <json>{"answer": "tagged", "confidence": 0.9}</json>"#;
+        let outputs = TestSignature::from_response_with_config(tagged, &config).unwrap();
+        assert_eq!(outputs.answer, "tagged");
+
+        // Built-in strategies still apply when there are no tags
+        let fenced = "```json\n{\"answer\": \"fenced\", \"confidence\": 0.5}\n```";
+        let outputs = TestSignature::from_response_with_config(fenced, &config).unwrap();
+        assert_eq!(outputs.answer, "fenced");
+
+        // Both wrappers present: the first strategy with a valid candidate wins
+        let both = "```json\n{\"answer\": \"fenced\", \"confidence\": 0.5}\n```\n\
+                    <json>{\"answer\": \"tagged\", \"confidence\": 0.9}</json>";
+        let outputs = TestSignature::from_response_with_config(both, &config).unwrap();
+        assert_eq!(outputs.answer, "tagged");
+        assert_eq!(TestSignature::from_response(both).unwrap().answer, "fenced");
+
+        // A strategy whose candidates all fail falls through to the next
+        let bad_tag = "```json\n{\"answer\": \"fenced\", \"confidence\": 0.5}\n```\n\
+                       <json>{\"answer\": \"tagged\"}</json>";
+        let outputs = TestSignature::from_response_with_config(bad_tag, &config).unwrap();
+        assert_eq!(outputs.answer, "fenced");
+
+        // Without the built-ins, untagged text is tried whole and fails
+        let tags_only = ParseConfig::new().with_extractors(vec![Arc::new(JsonTagExtractor)]);
+        assert!(matches!(
+            TestSignature::from_response_with_config(fenced, &tags_only),
+            Err(ParseError::InvalidJson { .. })
+        ));
+    }
+
     #[test]
     fn test_extract_json_code_block() {
         let input = "Here's the result:\n```json\n{\"key\": \"value\"}\n```\nDone!";
-        assert_eq!(extract_json(input), r#"{"key": "value"}"#);
+        assert_eq!(extract_json(input), vec![r#"{"key": "value"}"#]);
     }
 
     #[test]
     fn test_extract_json_raw() {
         let input = r#"Result: {"key": "value"} was found"#;
-        assert_eq!(extract_json(input), r#"{"key": "value"}"#);
+        assert_eq!(extract_json(input), vec![r#"{"key": "value"}"#]);
+    }
+
+    #[test]
+    fn test_extract_json_two_code_blocks() {
+        let input = "Draft:\n```json\n{\"a\": 1}\n```\nFinal:\n```json\n{\"a\": 2}\n```";
+        assert_eq!(extract_json(input), vec![r#"{"a": 1}"#, r#"{"a": 2}"#]);
+    }
+
+    #[test]
+    fn test_extract_json_raw_array() {
+        let input = r#"[{"a": "}"}, {"a": 2}]"#;
+        assert_eq!(extract_json(input), vec![r#"{"a": "}"}"#, r#"{"a": 2}"#]);
+    }
+
+    #[test]
+    fn test_from_response_skips_invalid_draft() {
+        // Draft fails validation (wrong type), corrected object follows
+        let response = r#"First try: {"answer": "draft", "confidence": "high"}
+Corrected: {"answer": "final", "confidence": 0.8}"#;
+
+        let outputs = TestSignature::from_response(response).unwrap();
+        assert_eq!(outputs.answer, "final");
+
+        let blocks =
+            "```json\n{\"answer\": 1}\n```\n```json\n{\"answer\": \"b\", \"confidence\": 0.5}\n```";
+        assert_eq!(TestSignature::from_response(blocks).unwrap().answer, "b");
+    }
+
+    #[test]
+    fn test_from_response_raw_array_of_candidates() {
+        let response = r#"[{"answer": "missing confidence"}, {"answer": "ok", "confidence": 1}]"#;
+        assert_eq!(TestSignature::from_response(response).unwrap().answer, "ok");
+
+        // All candidates fail: the validation error is reported
+        let response = r#"[{"answer": "a"}, {"answer": "b"}]"#;
+        assert!(matches!(
+            TestSignature::from_response(response),
+            Err(ParseError::ValidationFailed(_))
+        ));
     }
 
     #[test]
@@ -788,6 +1515,41 @@ I hope this helps!
             assert!((outputs.confidence - 0.9).abs() < 0.001);
         }
 
+        #[test]
+        fn test_derive_from_response_with_confidence() {
+            let inputs = AnalyzeCodeInputs {
+                code: "eval(input)".to_string(),
+                language: "python".to_string(),
+                max_issues: None,
+            };
+            let prompt = AnalyzeCode::to_prompt_with_confidence(&inputs);
+            assert!(prompt.starts_with(&AnalyzeCode::to_prompt(&inputs)));
+            assert!(prompt.contains(r#""_field_confidence": {"#));
+
+            let response = r#"Here is my analysis:
+```json
+{
+    "vulnerabilities": ["Arbitrary code execution"],
+    "severity": "critical",
+    "confidence": 0.8,
+    "_field_confidence": {"severity": 0.4, "vulnerabilities": 1.7}
+}
+```"#;
+            let (outputs, confidences) =
+                AnalyzeCode::from_response_with_confidence(response).unwrap();
+            assert_eq!(outputs.severity, "critical");
+            assert_eq!(outputs.vulnerabilities, vec!["Arbitrary code execution"]);
+            assert_eq!(confidences.len(), 3);
+            assert!((confidences["severity"] - 0.4).abs() < 1e-9);
+            assert_eq!(confidences["vulnerabilities"], 1.0);
+            assert_eq!(confidences["confidence"], 1.0);
+
+            assert!(matches!(
+                AnalyzeCode::from_response_with_confidence(r#"{"_field_confidence": {}}"#),
+                Err(ParseError::ValidationFailed(_))
+            ));
+        }
+
         /// Test with all supported types
         #[derive(rlm_core_derive::Signature)]
         #[signature(instructions = "Test all types")]
@@ -869,5 +1631,293 @@ I hope this helps!
             let invalid = r#"{"category":"other"}"#;
             assert!(EnumAnnotated::from_response(invalid).is_err());
         }
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, rlm_core_derive::FieldShape)]
+        struct Finding {
+            #[field(desc = "Source line of the issue")]
+            line: u32,
+            title: String,
+            related: Option<Vec<Finding>>,
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        struct Opaque {
+            raw: String,
+        }
+
+        #[derive(rlm_core_derive::Signature)]
+        #[signature(instructions = "Report findings")]
+        #[allow(dead_code)]
+        struct ReportFindings {
+            #[input(desc = "Source code")]
+            code: String,
+
+            #[output(desc = "Most severe finding")]
+            top: Finding,
+
+            #[output(desc = "All findings")]
+            all: Vec<Finding>,
+
+            #[output(desc = "Untyped payload")]
+            extra: Option<Opaque>,
+        }
+
+        #[test]
+        fn test_derive_nested_field_shape() {
+            let fields = ReportFindings::output_fields();
+
+            match &fields[0].field_type {
+                FieldType::Object(inner) => {
+                    assert_eq!(inner[0].name, "line");
+                    assert_eq!(inner[0].description, "Source line of the issue");
+                    assert!(matches!(inner[0].field_type, FieldType::Integer));
+                    // Self-reference is cut off rather than expanded forever
+                    assert_eq!(
+                        inner[2].field_type,
+                        FieldType::list(FieldType::custom("Finding"))
+                    );
+                }
+                other => panic!("expected object field type, got {:?}", other),
+            }
+            assert!(matches!(
+                &fields[1].field_type,
+                FieldType::List(inner) if matches!(**inner, FieldType::Object(_))
+            ));
+            assert_eq!(fields[2].field_type, FieldType::custom("Opaque"));
+        }
+
+        #[test]
+        fn test_derive_nested_output_schema() {
+            let schema = ReportFindings::output_schema();
+
+            let top = &schema["properties"]["top"];
+            assert_eq!(top["type"], "object");
+            assert_eq!(top["properties"]["title"]["type"], "string");
+            assert_eq!(schema["properties"]["all"]["items"]["type"], "object");
+
+            let prompt = ReportFindings::to_prompt(&ReportFindingsInputs {
+                code: "x".to_string(),
+            });
+            assert!(prompt.contains("\"title\": \"<string>\""));
+            assert!(prompt.contains("\"top\": {"));
+        }
+
+        #[test]
+        fn test_derive_generated_structs_are_nestable() {
+            use crate::signature::FieldShape;
+
+            assert_eq!(
+                ReportFindingsInputs::field_specs(),
+                ReportFindings::input_fields()
+            );
+            assert!(matches!(
+                AnalyzeCodeOutputs::field_type(),
+                FieldType::Object(fields) if fields.len() == 3
+            ));
+        }
+
+        #[derive(rlm_core_derive::Signature)]
+        #[signature(instructions = "Score the essay")]
+        #[allow(dead_code)]
+        struct ScoreEssay {
+            #[input(desc = "Essay text")]
+            essay: String,
+
+            #[output(desc = "Overall score")]
+            #[field(range = "0..=100")]
+            score: u32,
+
+            #[output(desc = "Confidence")]
+            #[field(range = "0.0..1")]
+            confidence: f64,
+
+            #[output(desc = "Temperature delta")]
+            #[field(range = "-5..")]
+            delta: Option<i32>,
+        }
+
+        #[test]
+        fn test_derive_field_range_attribute() {
+            let fields = ScoreEssay::output_fields();
+            assert_eq!(
+                fields[0].range,
+                Some(NumericRange::from_bounds(0.0..=100.0))
+            );
+            assert_eq!(fields[1].range, Some(NumericRange::from_bounds(0.0..1.0)));
+            assert_eq!(fields[2].range, Some(NumericRange::from_bounds(-5.0..)));
+            assert!(fields[0].to_prompt_line().contains(">= 0, <= 100"));
+
+            let valid = r#"{"score": 100, "confidence": 0.5, "delta": -5}"#;
+            assert!(ScoreEssay::from_response(valid).is_ok());
+
+            let invalid = r#"{"score": 101, "confidence": 1.0, "delta": -6}"#;
+            match ScoreEssay::from_response(invalid) {
+                Err(ParseError::ValidationFailed(errors)) => {
+                    assert_eq!(errors.len(), 3);
+                    assert!(errors
+                        .iter()
+                        .all(|e| matches!(e, ValidationError::OutOfRange { .. })));
+                }
+                other => panic!("expected range violations, got {:?}", other),
+            }
+        }
+
+        #[derive(rlm_core_derive::Signature)]
+        #[signature(instructions = "Extract the ticker symbol")]
+        #[allow(dead_code)]
+        struct ExtractTicker {
+            #[input(desc = "News headline")]
+            headline: String,
+
+            #[output(desc = "Ticker symbol")]
+            #[field(pattern = "^[A-Z]{2,3}$")]
+            ticker: String,
+        }
+
+        #[test]
+        fn test_derive_field_pattern_attribute() {
+            let fields = ExtractTicker::output_fields();
+            assert_eq!(fields[0].pattern.as_deref(), Some("^[A-Z]{2,3}$"));
+            assert!(fields[0].to_prompt_line().contains("/^[A-Z]{2,3}$/"));
+
+            assert!(ExtractTicker::from_response(r#"{"ticker": "IBM"}"#).is_ok());
+            assert!(matches!(
+                ExtractTicker::from_response(r#"{"ticker": "ibm"}"#),
+                Err(ParseError::ValidationFailed(errors))
+                    if matches!(errors[0], ValidationError::PatternMismatch { .. })
+            ));
+        }
+
+        #[derive(rlm_core_derive::Signature)]
+        #[signature(instructions = "Summarize the incident report")]
+        #[allow(dead_code)]
+        struct SummarizeIncident {
+            #[input(desc = "Incident report")]
+            report: String,
+
+            #[output(desc = "One-paragraph summary", aliases = "summary_text, Summary")]
+            summary: String,
+        }
+
+        #[test]
+        fn test_derive_output_aliases() {
+            let fields = SummarizeIncident::output_fields();
+            assert_eq!(fields[0].aliases, vec!["summary_text", "Summary"]);
+
+            let outputs =
+                SummarizeIncident::from_response(r#"{"summary_text": "Disk filled up"}"#).unwrap();
+            assert_eq!(outputs.summary, "Disk filled up");
+
+            let outputs = SummarizeIncident::from_response_xml(
+                "<Summary>Cache stampede after deploy</Summary>",
+            )
+            .unwrap();
+            assert_eq!(outputs.summary, "Cache stampede after deploy");
+        }
+
+        #[derive(rlm_core_derive::Signature)]
+        #[signature(instructions = "Triage the alert")]
+        #[allow(dead_code)]
+        struct TriageAlert {
+            #[input(desc = "Alert text")]
+            alert: String,
+
+            #[output(desc = "Owning team")]
+            team: String,
+
+            #[output(desc = "Urgency")]
+            #[field(default = "normal")]
+            urgency: String,
+        }
+
+        #[test]
+        fn test_derive_from_response_applies_defaults() {
+            let outputs = TriageAlert::from_response(r#"{"team": "storage"}"#).unwrap();
+            assert_eq!(outputs.urgency, "normal");
+
+            let outputs =
+                TriageAlert::from_response(r#"{"team": "storage", "urgency": null}"#).unwrap();
+            assert_eq!(outputs.urgency, "normal");
+
+            let outputs =
+                TriageAlert::from_response_xml("<team>storage</team><urgency>high</urgency>")
+                    .unwrap();
+            assert_eq!(outputs.urgency, "high");
+
+            assert!(matches!(
+                TriageAlert::from_response(r#"{"urgency": "high"}"#),
+                Err(ParseError::ValidationFailed(errors))
+                    if matches!(&errors[0], ValidationError::MissingField { field, .. } if field == "team")
+            ));
+        }
+
+        /// Enum signature mixing unit and struct variants
+        #[derive(rlm_core_derive::Signature)]
+        #[signature(instructions = "Judge whether the snippet is safe", tag = "verdict")]
+        #[input(name = "snippet", desc = "Code snippet to judge")]
+        #[input(name = "strict", ty = "Option<bool>")]
+        #[allow(dead_code)]
+        enum Verdict {
+            Safe,
+            Unsafe {
+                #[output(desc = "Why the snippet is unsafe")]
+                reason: String,
+                severity: Option<u32>,
+            },
+        }
+
+        #[test]
+        fn test_derive_enum_fields() {
+            let inputs = Verdict::input_fields();
+            assert_eq!(inputs.len(), 2);
+            assert_eq!(inputs[0].name, "snippet");
+            assert!(inputs[0].required);
+            assert!(!inputs[1].required);
+            assert!(matches!(inputs[1].field_type, FieldType::Boolean));
+
+            let outputs = Verdict::output_fields();
+            assert_eq!(outputs[0].name, "verdict");
+            assert_eq!(
+                outputs[0].field_type,
+                FieldType::enum_of(["Safe", "Unsafe"])
+            );
+            assert_eq!(outputs[1].name, "reason");
+            assert!(!outputs[1].required);
+            assert!(outputs[1].description.contains("Unsafe"));
+
+            let variants = Verdict::variant_types();
+            assert_eq!(variants.len(), 2);
+            match &variants[1].1 {
+                FieldType::Object(fields) => {
+                    assert_eq!(fields[0].name, "verdict");
+                    assert_eq!(fields[1].name, "reason");
+                }
+                other => panic!("expected object variant type, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_derive_enum_from_response() {
+            let unit = Verdict::from_response(r#"{"verdict": "Safe"}"#).unwrap();
+            assert_eq!(unit, VerdictOutputs::Safe);
+
+            let with_data = Verdict::from_response(
+                r#"{"verdict": "Unsafe", "reason": "eval of user input", "severity": 3}"#,
+            )
+            .unwrap();
+            assert_eq!(
+                with_data,
+                VerdictOutputs::Unsafe {
+                    reason: "eval of user input".to_string(),
+                    severity: Some(3),
+                }
+            );
+
+            let unknown = Verdict::from_response(r#"{"verdict": "Maybe"}"#);
+            assert!(matches!(unknown, Err(ParseError::ValidationFailed(_))));
+
+            let missing = Verdict::from_response(r#"{"verdict": "Unsafe"}"#);
+            assert!(matches!(missing, Err(ParseError::StructureMismatch { .. })));
+        }
     }
 }
@@ -0,0 +1,174 @@
+//! Nested field shapes for typed signatures.
+//!
+//! The derive macro cannot see the definition of a field's type, so nested
+//! structs opt in by implementing [`FieldShape`] (usually via
+//! `#[derive(FieldShape)]`). The macro probes for the trait at compile time
+//! and falls back to [`FieldType::Custom`] when the type doesn't implement it.
+
+use super::types::{FieldSpec, FieldType};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+/// A type whose fields can be described as [`FieldSpec`]s.
+///
+/// Implemented automatically for the `{Name}Inputs` and `{Name}Outputs`
+/// types generated by `#[derive(Signature)]`, and for any struct that
+/// uses `#[derive(FieldShape)]`.
+///
+/// # Example
+///
+/// ```
+/// use rlm_core::signature::{FieldShape, FieldSpec, FieldType};
+///
+/// struct Location;
+///
+/// impl FieldShape for Location {
+///     fn field_specs() -> Vec<FieldSpec> {
+///         vec![
+///             FieldSpec::new("file", FieldType::String),
+///             FieldSpec::new("line", FieldType::Integer),
+///         ]
+///     }
+/// }
+///
+/// assert!(matches!(Location::field_type(), FieldType::Object(_)));
+/// ```
+pub trait FieldShape {
+    /// Field specifications for the type's fields.
+    fn field_specs() -> Vec<FieldSpec>;
+
+    /// Field type for the whole value, expanding nested shapes once.
+    ///
+    /// A type that (directly or transitively) contains itself is reported
+    /// as [`FieldType::Custom`] at the point of recursion.
+    fn field_type() -> FieldType
+    where
+        Self: Sized,
+    {
+        nested_field_type::<Self>()
+    }
+}
+
+thread_local! {
+    /// Type names currently being expanded, used to break cycles.
+    static EXPANDING: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn nested_field_type<T: FieldShape>() -> FieldType {
+    let type_name = std::any::type_name::<T>();
+    let base_name = type_name.split('<').next().unwrap_or(type_name);
+    let short_name = base_name.rsplit("::").next().unwrap_or(base_name);
+
+    let is_cycle = EXPANDING.with(|stack| stack.borrow().contains(&type_name));
+    if is_cycle {
+        return FieldType::Custom(short_name.to_string());
+    }
+
+    EXPANDING.with(|stack| stack.borrow_mut().push(type_name));
+    let specs = T::field_specs();
+    EXPANDING.with(|stack| {
+        stack.borrow_mut().pop();
+    });
+
+    FieldType::Object(specs)
+}
+
+/// Compile-time probe used by the derive macro to detect [`FieldShape`].
+///
+/// Method resolution prefers [`ShapeViaTrait`] (no autoref) over
+/// [`ShapeViaFallback`] (one autoref), so the trait impl wins whenever
+/// `T: FieldShape` holds.
+#[doc(hidden)]
+pub struct ShapeProbe<T>(PhantomData<T>);
+
+impl<T> ShapeProbe<T> {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for ShapeProbe<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ShapeViaTrait {
+    fn probe_field_type(&self) -> Option<FieldType>;
+}
+
+impl<T: FieldShape> ShapeViaTrait for ShapeProbe<T> {
+    fn probe_field_type(&self) -> Option<FieldType> {
+        Some(T::field_type())
+    }
+}
+
+#[doc(hidden)]
+pub trait ShapeViaFallback {
+    fn probe_field_type(&self) -> Option<FieldType>;
+}
+
+impl<T> ShapeViaFallback for &ShapeProbe<T> {
+    fn probe_field_type(&self) -> Option<FieldType> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Leaf;
+
+    impl FieldShape for Leaf {
+        fn field_specs() -> Vec<FieldSpec> {
+            vec![FieldSpec::new("value", FieldType::Integer)]
+        }
+    }
+
+    struct Node;
+
+    impl FieldShape for Node {
+        fn field_specs() -> Vec<FieldSpec> {
+            vec![
+                FieldSpec::new("leaf", Leaf::field_type()),
+                FieldSpec::new("parent", Node::field_type()).optional(),
+            ]
+        }
+    }
+
+    struct NotShaped;
+
+    #[test]
+    fn test_field_type_expands_nested_shape() {
+        match Node::field_type() {
+            FieldType::Object(fields) => {
+                assert_eq!(fields[0].name, "leaf");
+                assert!(matches!(fields[0].field_type, FieldType::Object(_)));
+            }
+            other => panic!("expected object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_field_type_breaks_cycles() {
+        match Node::field_type() {
+            FieldType::Object(fields) => {
+                assert_eq!(fields[1].field_type, FieldType::Custom("Node".to_string()));
+            }
+            other => panic!("expected object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    // Mirrors the macro expansion; the explicit borrow selects the impl.
+    #[allow(clippy::needless_borrow)]
+    fn test_probe_falls_back_without_trait() {
+        assert!((&ShapeProbe::<Leaf>::new()).probe_field_type().is_some());
+        assert!((&ShapeProbe::<NotShaped>::new())
+            .probe_field_type()
+            .is_none());
+    }
+}
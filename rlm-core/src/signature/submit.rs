@@ -22,7 +22,7 @@
 //! })
 //! ```
 
-use super::types::{FieldSpec, FieldType};
+use super::types::{FieldSpec, FieldType, NumericRange};
 use super::validation::ValidationError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -325,6 +325,37 @@ impl From<ValidationError> for SubmitError {
                 field,
                 reason: constraint,
             },
+            ValidationError::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+                min_exclusive,
+                max_exclusive,
+            } => {
+                let range = NumericRange {
+                    min,
+                    max,
+                    min_exclusive,
+                    max_exclusive,
+                };
+                Self::ValidationFailed {
+                    field,
+                    reason: format!(
+                        "value {} is out of range (expected {})",
+                        value,
+                        range.describe()
+                    ),
+                }
+            }
+            ValidationError::PatternMismatch {
+                field,
+                value,
+                pattern,
+            } => Self::ValidationFailed {
+                field,
+                reason: format!("value '{}' does not match pattern /{}/", value, pattern),
+            },
             ValidationError::NestedError { path, error } => {
                 // Flatten nested errors by prefixing the path
                 match *error {
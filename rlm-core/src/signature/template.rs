@@ -0,0 +1,113 @@
+//! Prompt layouts for signatures.
+//!
+//! A [`PromptTemplate`] decides how the pieces of a signature prompt are
+//! arranged; the pieces themselves (input values, few-shot examples, the
+//! output field list and JSON template) are rendered once into
+//! [`PromptParts`] so layouts don't reimplement field formatting.
+//!
+//! [`MarkdownTemplate`] is the layout used by
+//! [`Signature::to_prompt`](super::Signature::to_prompt).
+//! [`InstructionsLastTemplate`] moves the task statement after the inputs,
+//! which some models follow more closely on long inputs.
+
+use super::types::FieldSpec;
+
+/// An input field value ready for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedInput<'a> {
+    /// The field's spec
+    pub field: &'a FieldSpec,
+    /// Display label (the field's prefix, or its name)
+    pub label: String,
+    /// Formatted value, or `None` for a required field that wasn't provided
+    pub value: Option<String>,
+}
+
+/// Everything a template needs to lay out a prompt.
+#[derive(Debug, Clone)]
+pub struct PromptParts<'a> {
+    /// Task instructions
+    pub instructions: &'a str,
+    /// Inputs in declaration order; missing optional fields are left out
+    pub inputs: Vec<RenderedInput<'a>>,
+    /// Rendered few-shot examples (empty if there are none)
+    pub examples: String,
+    /// Output field specs in declaration order
+    pub output_fields: &'a [FieldSpec],
+}
+
+impl PromptParts<'_> {
+    /// Inputs as `**label**: value` lines.
+    pub fn render_inputs(&self) -> String {
+        self.inputs
+            .iter()
+            .map(|input| {
+                format!(
+                    "**{}**: {}\n",
+                    input.label,
+                    input.value.as_deref().unwrap_or("(not provided)")
+                )
+            })
+            .collect()
+    }
+
+    /// The output field list followed by a JSON template in a code block.
+    pub fn render_output_spec(&self) -> String {
+        let mut out = String::from("Respond with a JSON object containing:\n\n");
+        for field in self.output_fields {
+            out.push_str(&format!("- {}\n", field.to_prompt_line()));
+        }
+        out.push_str("\n```json\n");
+        out.push_str(&super::generate_output_template(self.output_fields));
+        out.push_str("\n```\n");
+        out
+    }
+}
+
+/// Arranges [`PromptParts`] into the final prompt text.
+pub trait PromptTemplate {
+    /// Render the prompt.
+    fn render(&self, parts: &PromptParts<'_>) -> String;
+}
+
+/// Markdown sections in the order Task, Examples, Inputs, Required Output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarkdownTemplate;
+
+impl PromptTemplate for MarkdownTemplate {
+    fn render(&self, parts: &PromptParts<'_>) -> String {
+        let mut prompt = format!("## Task\n\n{}\n\n", parts.instructions);
+        if !parts.examples.is_empty() {
+            prompt.push_str("## Examples\n\n");
+            prompt.push_str(&parts.examples);
+        }
+        prompt.push_str("## Inputs\n\n");
+        prompt.push_str(&parts.render_inputs());
+        prompt.push_str("\n## Required Output\n\n");
+        prompt.push_str(&parts.render_output_spec());
+        prompt
+    }
+}
+
+/// Markdown sections with the task stated last: Examples, Inputs,
+/// Required Output, Task.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstructionsLastTemplate;
+
+impl PromptTemplate for InstructionsLastTemplate {
+    fn render(&self, parts: &PromptParts<'_>) -> String {
+        let mut prompt = String::new();
+        if !parts.examples.is_empty() {
+            prompt.push_str("## Examples\n\n");
+            prompt.push_str(&parts.examples);
+        }
+        prompt.push_str("## Inputs\n\n");
+        prompt.push_str(&parts.render_inputs());
+        prompt.push_str("\n## Required Output\n\n");
+        prompt.push_str(&parts.render_output_spec());
+        prompt.push_str("\n## Task\n\n");
+        prompt.push_str(parts.instructions);
+        prompt.push('\n');
+        prompt
+    }
+}
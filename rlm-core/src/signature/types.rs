@@ -6,6 +6,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::ops::{Bound, RangeBounds};
 
 /// Specification for a field in a signature.
 ///
@@ -35,6 +36,15 @@ pub struct FieldSpec {
     pub required: bool,
     /// Default value (JSON) if not required
     pub default: Option<Value>,
+    /// Allowed numeric range (for numeric fields)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<NumericRange>,
+    /// Regex that string values must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Alternative keys accepted for this field when parsing responses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
 }
 
 impl FieldSpec {
@@ -47,6 +57,9 @@ impl FieldSpec {
             prefix: None,
             required: true,
             default: None,
+            range: None,
+            pattern: None,
+            aliases: Vec::new(),
         }
     }
 
@@ -75,6 +88,54 @@ impl FieldSpec {
         self
     }
 
+    /// Restrict numeric values to a range.
+    ///
+    /// ```
+    /// use rlm_core::signature::{FieldSpec, FieldType};
+    ///
+    /// let score = FieldSpec::new("score", FieldType::Integer).with_range(0.0..=100.0);
+    /// assert!(score.range.unwrap().contains(100.0));
+    /// ```
+    pub fn with_range(mut self, range: impl RangeBounds<f64>) -> Self {
+        self.range = Some(NumericRange::from_bounds(range));
+        self
+    }
+
+    /// Require string values to match a regex.
+    ///
+    /// The pattern is compiled at validation time; an invalid pattern is
+    /// reported as a constraint violation rather than a panic.
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Accept alternative keys for this field when parsing responses.
+    ///
+    /// Aliases are normalized to the canonical name before validation and
+    /// deserialization. See [`normalize_aliases`](super::normalize_aliases).
+    ///
+    /// ```
+    /// use rlm_core::signature::{FieldSpec, FieldType};
+    ///
+    /// let summary = FieldSpec::new("summary", FieldType::String)
+    ///     .with_aliases(["summary_text", "Summary"]);
+    /// assert!(summary.matches_key("summary_text"));
+    /// ```
+    pub fn with_aliases<I, S>(mut self, aliases: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.aliases.extend(aliases.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether `key` is this field's name or one of its aliases.
+    pub fn matches_key(&self, key: &str) -> bool {
+        self.name == key || self.aliases.iter().any(|a| a == key)
+    }
+
     /// Get the display label (prefix if set, otherwise name).
     pub fn display_label(&self) -> &str {
         self.prefix.as_deref().unwrap_or(&self.name)
@@ -84,7 +145,13 @@ impl FieldSpec {
     ///
     /// Returns a string like "Query (string): The search query to execute"
     pub fn to_prompt_line(&self) -> String {
-        let type_hint = self.field_type.to_prompt_hint();
+        let mut type_hint = self.field_type.to_prompt_hint();
+        if let Some(range) = &self.range {
+            type_hint = format!("{type_hint}, {}", range.describe());
+        }
+        if let Some(pattern) = &self.pattern {
+            type_hint = format!("{type_hint}, matching /{pattern}/");
+        }
         let label = self.display_label();
         let required_marker = if self.required { "" } else { " (optional)" };
 
@@ -99,6 +166,79 @@ impl FieldSpec {
     }
 }
 
+/// Numeric bounds for a field.
+///
+/// Missing bounds are open-ended. The lower bound is inclusive unless
+/// `min_exclusive` is set, likewise for the upper bound.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NumericRange {
+    /// Lower bound
+    pub min: Option<f64>,
+    /// Upper bound
+    pub max: Option<f64>,
+    /// Whether the lower bound itself is excluded
+    #[serde(default)]
+    pub min_exclusive: bool,
+    /// Whether the upper bound itself is excluded
+    #[serde(default)]
+    pub max_exclusive: bool,
+}
+
+impl NumericRange {
+    /// Build a range from any Rust range expression (`0.0..=1.0`, `0.0..`, ...).
+    pub fn from_bounds(range: impl RangeBounds<f64>) -> Self {
+        let (min, min_exclusive) = match range.start_bound() {
+            Bound::Included(v) => (Some(*v), false),
+            Bound::Excluded(v) => (Some(*v), true),
+            Bound::Unbounded => (None, false),
+        };
+        let (max, max_exclusive) = match range.end_bound() {
+            Bound::Included(v) => (Some(*v), false),
+            Bound::Excluded(v) => (Some(*v), true),
+            Bound::Unbounded => (None, false),
+        };
+        Self {
+            min,
+            max,
+            min_exclusive,
+            max_exclusive,
+        }
+    }
+
+    /// Check whether a value lies within the range.
+    pub fn contains(&self, value: f64) -> bool {
+        let above_min = match self.min {
+            Some(min) if self.min_exclusive => value > min,
+            Some(min) => value >= min,
+            None => true,
+        };
+        let below_max = match self.max {
+            Some(max) if self.max_exclusive => value < max,
+            Some(max) => value <= max,
+            None => true,
+        };
+        above_min && below_max
+    }
+
+    /// Describe the range for prompts and error messages (e.g., `">= 0, <= 100"`).
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(min) = self.min {
+            let op = if self.min_exclusive { ">" } else { ">=" };
+            parts.push(format!("{op} {min}"));
+        }
+        if let Some(max) = self.max {
+            let op = if self.max_exclusive { "<" } else { "<=" };
+            parts.push(format!("{op} {max}"));
+        }
+        if parts.is_empty() {
+            "any value".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
 /// Type of a field for validation and prompt generation.
 ///
 /// FieldType represents the expected data type for a field, enabling:
@@ -300,6 +440,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_numeric_range_bounds() {
+        let inclusive = NumericRange::from_bounds(0.0..=100.0);
+        assert!(inclusive.contains(0.0));
+        assert!(inclusive.contains(100.0));
+        assert!(!inclusive.contains(100.5));
+
+        let exclusive = NumericRange::from_bounds(0.0..1.0);
+        assert!(!exclusive.contains(1.0));
+        assert_eq!(exclusive.describe(), ">= 0, < 1");
+
+        let open = NumericRange::from_bounds(0.0..);
+        assert!(open.contains(1e12));
+        assert!(!open.contains(-1.0));
+        assert_eq!(open.describe(), ">= 0");
+    }
+
+    #[test]
+    fn test_to_prompt_line_with_range() {
+        let field = FieldSpec::new("score", FieldType::Integer)
+            .with_description("Quality score")
+            .with_range(0.0..=100.0);
+
+        assert_eq!(
+            field.to_prompt_line(),
+            "score (integer, >= 0, <= 100): Quality score"
+        );
+    }
+
+    #[test]
+    fn test_to_prompt_line_with_pattern() {
+        let field = FieldSpec::new("ticker", FieldType::String).with_pattern("^[A-Z]{2,3}$");
+
+        assert_eq!(
+            field.to_prompt_line(),
+            "ticker (string, matching /^[A-Z]{2,3}$/)"
+        );
+    }
+
     #[test]
     fn test_field_type_prompt_hints() {
         assert_eq!(FieldType::String.to_prompt_hint(), "string");
@@ -3,7 +3,9 @@
 //! This module provides validation errors and functions for ensuring
 //! inputs and outputs conform to their signature specifications.
 
-use super::types::{FieldSpec, FieldType};
+use super::types::{FieldSpec, FieldType, NumericRange};
+use crate::repl::floor_char_boundary;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
@@ -50,6 +52,34 @@ pub enum ValidationError {
         constraint: String,
     },
 
+    /// Numeric value falls outside the field's allowed range.
+    OutOfRange {
+        /// Name of the field
+        field: String,
+        /// The value that was provided
+        value: f64,
+        /// Lower bound, if any
+        min: Option<f64>,
+        /// Upper bound, if any
+        max: Option<f64>,
+        /// Whether the lower bound is excluded
+        #[serde(default)]
+        min_exclusive: bool,
+        /// Whether the upper bound is excluded
+        #[serde(default)]
+        max_exclusive: bool,
+    },
+
+    /// String value does not match the field's regex pattern.
+    PatternMismatch {
+        /// Name of the field
+        field: String,
+        /// The value that was provided
+        value: String,
+        /// The pattern it failed to match
+        pattern: String,
+    },
+
     /// Nested object validation failed.
     NestedError {
         /// Path to the nested field (e.g., "user.address.city")
@@ -104,6 +134,31 @@ impl ValidationError {
         }
     }
 
+    /// Create an out-of-range error.
+    pub fn out_of_range(field: impl Into<String>, value: f64, range: &NumericRange) -> Self {
+        Self::OutOfRange {
+            field: field.into(),
+            value,
+            min: range.min,
+            max: range.max,
+            min_exclusive: range.min_exclusive,
+            max_exclusive: range.max_exclusive,
+        }
+    }
+
+    /// Create a pattern mismatch error.
+    pub fn pattern_mismatch(
+        field: impl Into<String>,
+        value: impl Into<String>,
+        pattern: impl Into<String>,
+    ) -> Self {
+        Self::PatternMismatch {
+            field: field.into(),
+            value: value.into(),
+            pattern: pattern.into(),
+        }
+    }
+
     /// Wrap this error with a path prefix for nested fields.
     pub fn with_path(self, parent: impl Into<String>) -> Self {
         let parent = parent.into();
@@ -161,6 +216,37 @@ impl ValidationError {
             Self::ConstraintViolated { field, constraint } => {
                 format!("Field '{}' violates constraint: {}", field, constraint)
             }
+            Self::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+                min_exclusive,
+                max_exclusive,
+            } => {
+                let range = NumericRange {
+                    min: *min,
+                    max: *max,
+                    min_exclusive: *min_exclusive,
+                    max_exclusive: *max_exclusive,
+                };
+                format!(
+                    "Field '{}' value {} is out of range (expected {})",
+                    field,
+                    value,
+                    range.describe()
+                )
+            }
+            Self::PatternMismatch {
+                field,
+                value,
+                pattern,
+            } => {
+                format!(
+                    "Field '{}' value '{}' does not match pattern /{}/",
+                    field, value, pattern
+                )
+            }
             Self::NestedError { path, error } => {
                 format!("At '{}': {}", path, error.to_user_message())
             }
@@ -222,15 +308,17 @@ pub fn validate_fields(value: &Value, fields: &[FieldSpec]) -> ValidationResult
     let mut errors = Vec::new();
 
     for field in fields {
-        match obj.get(&field.name) {
+        match resolve_field_key(obj, field, fields).and_then(|key| obj.get(key)) {
             Some(field_value) => {
                 if field_value.is_null() && !field.required {
                     continue;
                 }
-                // Validate the field type
+                // Check type and field-level constraints independently so a
+                // bad list element doesn't hide constraint failures elsewhere
                 if let Err(e) = validate_value(field_value, &field.field_type, &field.name) {
                     errors.extend(e);
                 }
+                errors.extend(validate_constraints(field_value, field, &field.name));
             }
             None => {
                 if field.required {
@@ -251,6 +339,9 @@ pub fn validate_fields(value: &Value, fields: &[FieldSpec]) -> ValidationResult
 }
 
 /// Validate a single value against a field type.
+///
+/// Lists and objects are checked element by element and every failure is
+/// returned, with paths such as `key_points[3]` or `address.city`.
 pub fn validate_value(value: &Value, field_type: &FieldType, field_name: &str) -> ValidationResult {
     let mut errors = Vec::new();
 
@@ -359,9 +450,70 @@ pub fn validate_value(value: &Value, field_type: &FieldType, field_name: &str) -
     }
 }
 
+/// Check field-level constraints (numeric range, string pattern).
+///
+/// Constraints apply to scalar values and to each element of a list. Range
+/// only looks at numbers and pattern only at strings, so values of the wrong
+/// type are left to [`validate_value`].
+fn validate_constraints(value: &Value, field: &FieldSpec, path: &str) -> Vec<ValidationError> {
+    let pattern = match &field.pattern {
+        Some(pattern) => match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                return vec![ValidationError::constraint_violated(
+                    path,
+                    format!("invalid pattern /{}/: {}", pattern, e),
+                )];
+            }
+        },
+        None => None,
+    };
+    check_constraints(value, field, pattern.as_ref(), path)
+}
+
+fn check_constraints(
+    value: &Value,
+    field: &FieldSpec,
+    pattern: Option<&Regex>,
+    path: &str,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    match value {
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let item_path = format!("{}[{}]", path, i);
+                errors.extend(check_constraints(item, field, pattern, &item_path));
+            }
+        }
+        Value::Number(n) => {
+            if let (Some(range), Some(v)) = (&field.range, n.as_f64()) {
+                if !range.contains(v) {
+                    errors.push(ValidationError::out_of_range(path, v, range));
+                }
+            }
+        }
+        Value::String(text) => {
+            if let Some(regex) = pattern {
+                if !regex.is_match(text) {
+                    errors.push(ValidationError::pattern_mismatch(
+                        path,
+                        text.as_str(),
+                        regex.as_str(),
+                    ));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    errors
+}
+
 /// Apply default values to missing optional fields.
 ///
-/// Returns a new JSON object with defaults applied.
+/// A field that is present but `null` counts as missing. Returns a new JSON
+/// object with defaults applied.
 pub fn apply_defaults(value: &Value, fields: &[FieldSpec]) -> Value {
     let mut obj = match value.as_object() {
         Some(obj) => obj.clone(),
@@ -369,7 +521,7 @@ pub fn apply_defaults(value: &Value, fields: &[FieldSpec]) -> Value {
     };
 
     for field in fields {
-        if !obj.contains_key(&field.name) {
+        if obj.get(&field.name).filter(|v| !v.is_null()).is_none() {
             if let Some(default) = &field.default {
                 obj.insert(field.name.clone(), default.clone());
             }
@@ -379,6 +531,130 @@ pub fn apply_defaults(value: &Value, fields: &[FieldSpec]) -> Value {
     Value::Object(obj)
 }
 
+/// Convert string-encoded scalars in an object to their declared types.
+///
+/// A string becomes a number for `Integer` and `Float` fields when it is a
+/// valid JSON number (so `"0.95"` and `"1e3"` convert but `"0,95"`, `"NaN"`
+/// and `"1 000"` don't), and an `Integer` only if the value is whole. It
+/// becomes a boolean for `Boolean` fields when it is `true` or `false` in
+/// any case. Lists and nested objects are converted element by element.
+/// Anything else is left alone so validation still reports it.
+pub fn coerce_scalars(value: &mut Value, fields: &[FieldSpec]) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    for field in fields {
+        if let Some(field_value) = obj.get_mut(&field.name) {
+            coerce_scalar(field_value, &field.field_type);
+        }
+    }
+}
+
+fn coerce_scalar(value: &mut Value, field_type: &FieldType) {
+    match (field_type, &*value) {
+        (FieldType::List(inner), Value::Array(_)) => {
+            if let Value::Array(items) = value {
+                items.iter_mut().for_each(|item| coerce_scalar(item, inner));
+            }
+        }
+        (FieldType::Object(fields), Value::Object(_)) => coerce_scalars(value, fields),
+        (FieldType::Integer | FieldType::Float, Value::String(s)) => {
+            let Ok(number) = serde_json::from_str::<serde_json::Number>(s.trim()) else {
+                return;
+            };
+            let number = match field_type {
+                FieldType::Integer => match number.as_i64() {
+                    Some(i) => i.into(),
+                    // Whole numbers written with a fraction or exponent
+                    None => match number.as_f64() {
+                        Some(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => {
+                            (f as i64).into()
+                        }
+                        _ => return,
+                    },
+                },
+                _ => number,
+            };
+            *value = Value::Number(number);
+        }
+        (FieldType::Boolean, Value::String(s)) => {
+            let s = s.trim();
+            if s.eq_ignore_ascii_case("true") {
+                *value = Value::Bool(true);
+            } else if s.eq_ignore_ascii_case("false") {
+                *value = Value::Bool(false);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rename aliased keys in an object to their canonical field names.
+///
+/// A field's canonical key always wins. Otherwise its first alias present
+/// in the object is used. As a last resort, keys are compared ignoring case,
+/// `_` and `-`, skipping keys that exactly name another field. Non-object
+/// values are left untouched.
+///
+/// ```
+/// use rlm_core::signature::{normalize_aliases, FieldSpec, FieldType};
+/// use serde_json::json;
+///
+/// let fields = vec![FieldSpec::new("summary", FieldType::String).with_aliases(["summary_text"])];
+/// let mut value = json!({"summary_text": "All good"});
+/// normalize_aliases(&mut value, &fields);
+/// assert_eq!(value, json!({"summary": "All good"}));
+/// ```
+pub fn normalize_aliases(value: &mut Value, fields: &[FieldSpec]) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    for field in fields {
+        let key = match resolve_field_key(obj, field, fields) {
+            Some(key) if key != field.name => key.to_string(),
+            _ => continue,
+        };
+        if let Some(field_value) = obj.remove(&key) {
+            obj.insert(field.name.clone(), field_value);
+        }
+    }
+}
+
+/// Find the key in `obj` that holds `field`, honouring aliases.
+fn resolve_field_key<'a>(
+    obj: &'a serde_json::Map<String, Value>,
+    field: &FieldSpec,
+    fields: &[FieldSpec],
+) -> Option<&'a str> {
+    if let Some((key, _)) = obj.get_key_value(&field.name) {
+        return Some(key.as_str());
+    }
+    if let Some(key) = field
+        .aliases
+        .iter()
+        .find_map(|alias| obj.get_key_value(alias).map(|(k, _)| k.as_str()))
+    {
+        return Some(key);
+    }
+
+    let wanted: Vec<String> = std::iter::once(&field.name)
+        .chain(&field.aliases)
+        .map(|name| loose_key(name))
+        .collect();
+    obj.keys()
+        .filter(|key| !fields.iter().any(|f| f.matches_key(key)))
+        .find(|key| wanted.contains(&loose_key(key)))
+        .map(String::as_str)
+}
+
+/// Key form used for last-resort matching: lowercase without `_` or `-`.
+fn loose_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 /// Get a human-readable type name for a JSON value.
 fn value_type_name(value: &Value) -> String {
     match value {
@@ -402,7 +678,7 @@ fn truncate_preview(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len])
+        format!("{}...", &s[..floor_char_boundary(s, max_len)])
     }
 }
 
@@ -530,6 +806,96 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_collects_all_errors() {
+        let fields = vec![
+            FieldSpec::new("key_points", FieldType::list(FieldType::String)).with_pattern("^[A-Z]"),
+            FieldSpec::new("severity", FieldType::enum_of(["low", "medium", "high"])),
+            FieldSpec::new(
+                "owners",
+                FieldType::list(FieldType::object(vec![FieldSpec::new(
+                    "team",
+                    FieldType::String,
+                )])),
+            ),
+        ];
+
+        let value = json!({
+            "key_points": ["Disk full", "Alerts fired", "Paged on-call", 4, "rolled back"],
+            "severity": "critical",
+            "owners": [{"team": "storage"}, {}],
+        });
+        let errors = validate_fields(&value, &fields).unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().any(|e| matches!(e,
+            ValidationError::TypeMismatch { field, .. } if field == "key_points[3]")));
+        assert!(errors.iter().any(|e| matches!(e,
+            ValidationError::PatternMismatch { field, .. } if field == "key_points[4]")));
+        assert!(errors.iter().any(|e| matches!(e,
+            ValidationError::EnumInvalid { field, .. } if field == "severity")));
+        assert!(errors.iter().any(|e| matches!(e,
+            ValidationError::NestedError { path, .. } if path == "owners[1]")));
+    }
+
+    #[test]
+    fn test_validate_range() {
+        let fields = vec![
+            FieldSpec::new("score", FieldType::Integer).with_range(0.0..=100.0),
+            FieldSpec::new("weights", FieldType::list(FieldType::Float)).with_range(0.0..1.0),
+        ];
+
+        let valid = json!({"score": 100, "weights": [0.0, 0.5]});
+        assert!(validate_fields(&valid, &fields).is_ok());
+
+        let invalid = json!({"score": 101, "weights": [0.5, 1.0]});
+        let errors = validate_fields(&invalid, &fields).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            &errors[0],
+            ValidationError::OutOfRange { field, max: Some(max), .. }
+                if field == "score" && *max == 100.0
+        ));
+        assert!(matches!(
+            &errors[1],
+            ValidationError::OutOfRange { field, max_exclusive: true, .. } if field == "weights[1]"
+        ));
+        assert!(errors[0].to_user_message().contains("<= 100"));
+    }
+
+    #[test]
+    fn test_validate_pattern() {
+        let fields = vec![
+            FieldSpec::new("ticker", FieldType::String).with_pattern("^[A-Z]{2,3}$"),
+            FieldSpec::new("codes", FieldType::list(FieldType::String)).with_pattern("^[a-z]{2}$"),
+        ];
+
+        let valid = json!({"ticker": "IBM", "codes": ["en", "de"]});
+        assert!(validate_fields(&valid, &fields).is_ok());
+
+        let invalid = json!({"ticker": "ibm", "codes": ["en", "deu"]});
+        let errors = validate_fields(&invalid, &fields).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            &errors[0],
+            ValidationError::PatternMismatch { field, value, .. } if field == "ticker" && value == "ibm"
+        ));
+        assert!(matches!(
+            &errors[1],
+            ValidationError::PatternMismatch { field, .. } if field == "codes[1]"
+        ));
+    }
+
+    #[test]
+    fn test_validate_invalid_pattern_is_reported() {
+        let fields = vec![FieldSpec::new("name", FieldType::String).with_pattern("([a-z")];
+
+        let errors = validate_fields(&json!({"name": "abc"}), &fields).unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            ValidationError::ConstraintViolated { constraint, .. } if constraint.contains("invalid pattern")
+        ));
+    }
+
     #[test]
     fn test_apply_defaults() {
         let fields = vec![
@@ -542,6 +908,39 @@ mod tests {
 
         assert_eq!(with_defaults["name"], "test");
         assert_eq!(with_defaults["count"], 10);
+
+        let with_defaults = apply_defaults(&json!({"name": "test", "count": null}), &fields);
+        assert_eq!(with_defaults["count"], 10);
+    }
+
+    #[test]
+    fn test_normalize_aliases() {
+        let fields = vec![
+            FieldSpec::new("summary", FieldType::String).with_aliases(["summary_text", "Summary"]),
+            FieldSpec::new("risk_level", FieldType::String),
+        ];
+
+        // Alias is renamed; loose matching is a last resort
+        let mut value = json!({"summary_text": "ok", "RiskLevel": "low"});
+        assert!(validate_fields(&value, &fields).is_ok());
+        normalize_aliases(&mut value, &fields);
+        assert_eq!(value, json!({"summary": "ok", "risk_level": "low"}));
+
+        // An exact canonical key wins over aliases
+        let mut value = json!({"Summary": "alias", "summary": "canonical"});
+        normalize_aliases(&mut value, &fields);
+        assert_eq!(value["summary"], "canonical");
+        assert_eq!(value["Summary"], "alias");
+
+        // Keys that exactly name another field are never claimed loosely
+        let fields = vec![
+            FieldSpec::new("score", FieldType::Integer),
+            FieldSpec::new("Score", FieldType::Integer).optional(),
+        ];
+        let mut value = json!({"Score": 3});
+        assert!(validate_fields(&value, &fields).is_err());
+        normalize_aliases(&mut value, &fields);
+        assert_eq!(value, json!({"Score": 3}));
     }
 
     #[test]
@@ -663,4 +1062,43 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_coerce_scalars() {
+        let fields = vec![
+            FieldSpec::new("count", FieldType::Integer),
+            FieldSpec::new("big", FieldType::Integer),
+            FieldSpec::new("ratio", FieldType::Float),
+            FieldSpec::new("ok", FieldType::Boolean),
+            FieldSpec::new("unsure", FieldType::Boolean),
+            FieldSpec::new("scores", FieldType::list(FieldType::Float)),
+            FieldSpec::new("half", FieldType::Integer),
+            FieldSpec::new("name", FieldType::String),
+        ];
+        let mut value = json!({
+            "count": " 42 ",
+            "big": "1e3",
+            "ratio": "1e-2",
+            "ok": "TRUE",
+            "unsure": "yes",
+            "scores": ["0.5", 1, "x"],
+            "half": "2.5",
+            "name": "7"
+        });
+
+        coerce_scalars(&mut value, &fields);
+        assert_eq!(
+            value,
+            json!({
+                "count": 42,
+                "big": 1000,
+                "ratio": 0.01,
+                "ok": true,
+                "unsure": "yes",
+                "scores": [0.5, 1, "x"],
+                "half": "2.5",
+                "name": "7"
+            })
+        );
+    }
 }
@@ -0,0 +1,357 @@
+//! XML-tag parsing for signature outputs.
+//!
+//! Some models are more reliable emitting one XML tag per output field than a
+//! JSON object. This module maps each output field name to a tag and coerces
+//! the tag contents into a JSON value that can go through the normal
+//! validation path:
+//!
+//! ```text
+//! <summary>Rust is a systems language</summary>
+//! <key_points>fast</key_points>
+//! <key_points>safe</key_points>
+//! ```
+//!
+//! Lists are written either as repeated tags or as `<item>` children of a
+//! single tag, nested objects as child tags, and `<![CDATA[...]]>` sections
+//! are taken verbatim.
+
+use super::types::{FieldSpec, FieldType};
+use super::ParseError;
+use serde_json::{Map, Number, Value};
+
+/// Tag used for list elements inside a single list tag.
+const LIST_ITEM_TAG: &str = "item";
+
+/// Parse an XML-tagged response into a JSON object keyed by field name.
+///
+/// Missing tags are omitted from the result, so required fields surface as
+/// `MissingField` during validation and optional fields are simply absent.
+pub fn parse_xml_fields(response: &str, fields: &[FieldSpec]) -> Result<Value, ParseError> {
+    let elements = top_level_elements(response);
+    if elements.is_empty() {
+        return Err(ParseError::structure_mismatch(
+            "XML tags for output fields",
+            "no XML elements",
+        ));
+    }
+
+    let obj = fields_to_object(&elements, fields);
+    if obj.is_empty() && fields.iter().any(|f| f.required) {
+        let found: Vec<_> = elements.iter().map(|(name, _)| *name).collect();
+        return Err(ParseError::structure_mismatch(
+            format!(
+                "tags <{}>",
+                fields
+                    .iter()
+                    .map(|f| f.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(">, <")
+            ),
+            format!("tags <{}>", found.join(">, <")),
+        ));
+    }
+
+    Ok(Value::Object(obj))
+}
+
+fn fields_to_object(elements: &[(&str, &str)], fields: &[FieldSpec]) -> Map<String, Value> {
+    let mut obj = Map::new();
+
+    for field in fields {
+        // The canonical tag wins; otherwise take the first alias present
+        let Some(contents) = std::iter::once(&field.name)
+            .chain(&field.aliases)
+            .map(|tag| {
+                elements
+                    .iter()
+                    .filter(|(name, _)| name == tag)
+                    .map(|(_, content)| *content)
+                    .collect::<Vec<&str>>()
+            })
+            .find(|contents| !contents.is_empty())
+        else {
+            continue;
+        };
+
+        let value = match &field.field_type {
+            FieldType::List(inner) => coerce_list(&contents, inner),
+            other => coerce(contents[0], other),
+        };
+        obj.insert(field.name.clone(), value);
+    }
+
+    obj
+}
+
+/// Coerce list contents: repeated tags become one element each, and a single
+/// tag wrapping `<item>` children is expanded into those children.
+fn coerce_list(contents: &[&str], inner: &FieldType) -> Value {
+    if let [single] = contents {
+        let children = top_level_elements(single);
+        if !children.is_empty() && children.iter().all(|(name, _)| *name == LIST_ITEM_TAG) {
+            return Value::Array(children.iter().map(|(_, c)| coerce(c, inner)).collect());
+        }
+    }
+
+    Value::Array(contents.iter().map(|c| coerce(c, inner)).collect())
+}
+
+/// Coerce element content to a JSON value of the expected type.
+///
+/// Values that don't coerce are left as strings so validation reports a
+/// type mismatch with the original text.
+fn coerce(content: &str, field_type: &FieldType) -> Value {
+    match field_type {
+        FieldType::Object(fields) => {
+            Value::Object(fields_to_object(&top_level_elements(content), fields))
+        }
+        FieldType::List(inner) => {
+            let children = top_level_elements(content);
+            Value::Array(children.iter().map(|(_, c)| coerce(c, inner)).collect())
+        }
+        _ => {
+            let text = text_content(content);
+            let trimmed = text.trim();
+            match field_type {
+                FieldType::Integer => trimmed
+                    .parse::<i64>()
+                    .map(Value::from)
+                    .unwrap_or_else(|_| Value::String(trimmed.to_string())),
+                FieldType::Float => trimmed
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .map(Value::Number)
+                    .unwrap_or_else(|| Value::String(trimmed.to_string())),
+                FieldType::Boolean => match trimmed.to_ascii_lowercase().as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    _ => Value::String(trimmed.to_string()),
+                },
+                _ => Value::String(trimmed.to_string()),
+            }
+        }
+    }
+}
+
+/// Split text into its top-level elements as `(tag, inner content)` pairs.
+///
+/// Prose between elements, comments, processing instructions, and unclosed
+/// tags are skipped. Same-named nested tags are matched by depth and CDATA
+/// sections never terminate an element.
+fn top_level_elements(text: &str) -> Vec<(&str, &str)> {
+    let mut elements = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = text[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &text[start..];
+
+        if rest.starts_with("<![CDATA[") {
+            pos = skip_past(text, start, "]]>");
+            continue;
+        }
+        if rest.starts_with("<!--") {
+            pos = skip_past(text, start, "-->");
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") || rest.starts_with("</") {
+            pos = skip_past(text, start, ">");
+            continue;
+        }
+
+        let Some((name, open_end, self_closing)) = parse_open_tag(text, start) else {
+            pos = start + 1;
+            continue;
+        };
+
+        if self_closing {
+            elements.push((name, ""));
+            pos = open_end;
+            continue;
+        }
+
+        match find_close(text, open_end, name) {
+            Some((content_end, close_end)) => {
+                elements.push((name, &text[open_end..content_end]));
+                pos = close_end;
+            }
+            None => pos = start + 1,
+        }
+    }
+
+    elements
+}
+
+/// Parse an opening tag at `start`, returning its name, the index just past
+/// the `>`, and whether it was self-closing.
+fn parse_open_tag(text: &str, start: usize) -> Option<(&str, usize, bool)> {
+    let after = &text[start + 1..];
+    let name_len = after
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-' || c == ':' || c == '.'))
+        .unwrap_or(after.len());
+    if name_len == 0 || !after.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        return None;
+    }
+    let name = &after[..name_len];
+
+    let tag_end = after[name_len..].find('>')? + name_len;
+    let between = &after[name_len..tag_end];
+    if !between.is_empty() && !between.starts_with(|c: char| c.is_whitespace() || c == '/') {
+        return None;
+    }
+
+    Some((
+        name,
+        start + 1 + tag_end + 1,
+        between.trim_end().ends_with('/'),
+    ))
+}
+
+/// Find the close tag matching an element opened before `from`, returning
+/// the content end and the index just past the close tag.
+fn find_close(text: &str, from: usize, name: &str) -> Option<(usize, usize)> {
+    let close = format!("</{}>", name);
+    let mut depth = 0usize;
+    let mut pos = from;
+
+    while let Some(offset) = text[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &text[start..];
+
+        if rest.starts_with("<![CDATA[") {
+            pos = skip_past(text, start, "]]>");
+        } else if rest.starts_with(&close) {
+            if depth == 0 {
+                return Some((start, start + close.len()));
+            }
+            depth -= 1;
+            pos = start + close.len();
+        } else if let Some((inner, end, self_closing)) = parse_open_tag(text, start) {
+            if inner == name && !self_closing {
+                depth += 1;
+            }
+            pos = end;
+        } else {
+            pos = start + 1;
+        }
+    }
+
+    None
+}
+
+fn skip_past(text: &str, start: usize, terminator: &str) -> usize {
+    text[start..]
+        .find(terminator)
+        .map(|i| start + i + terminator.len())
+        .unwrap_or(text.len())
+}
+
+/// Extract text from element content: CDATA is kept verbatim and entities
+/// elsewhere are unescaped.
+fn text_content(content: &str) -> String {
+    let mut out = String::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<![CDATA[") {
+        out.push_str(&unescape(&rest[..start]));
+        let body = &rest[start + 9..];
+        match body.find("]]>") {
+            Some(end) => {
+                out.push_str(&body[..end]);
+                rest = &body[end + 3..];
+            }
+            None => {
+                out.push_str(body);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(&unescape(rest));
+
+    out
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields() -> Vec<FieldSpec> {
+        vec![
+            FieldSpec::new("summary", FieldType::String),
+            FieldSpec::new("points", FieldType::list(FieldType::String)),
+            FieldSpec::new("count", FieldType::Integer).optional(),
+        ]
+    }
+
+    #[test]
+    fn test_parse_repeated_tags_as_list() {
+        let response = "Sure!\n<summary>Short</summary>\n<points>a</points>\n<points>b</points>";
+        let value = parse_xml_fields(response, &fields()).unwrap();
+
+        assert_eq!(value, json!({"summary": "Short", "points": ["a", "b"]}));
+    }
+
+    #[test]
+    fn test_parse_item_children_as_list() {
+        let response = "<summary>S</summary><points><item>x</item><item>y</item></points>";
+        let value = parse_xml_fields(response, &fields()).unwrap();
+
+        assert_eq!(value["points"], json!(["x", "y"]));
+    }
+
+    #[test]
+    fn test_parse_cdata_and_entities() {
+        let response = "<summary><![CDATA[if a < b && c </summary>]]></summary>\
+                        <points>x &amp; y</points><count> 3 </count>";
+        let value = parse_xml_fields(response, &fields()).unwrap();
+
+        assert_eq!(value["summary"], "if a < b && c </summary>");
+        assert_eq!(value["points"], json!(["x & y"]));
+        assert_eq!(value["count"], 3);
+    }
+
+    #[test]
+    fn test_parse_nested_object() {
+        let fields = vec![FieldSpec::new(
+            "location",
+            FieldType::object(vec![
+                FieldSpec::new("file", FieldType::String),
+                FieldSpec::new("line", FieldType::Integer),
+            ]),
+        )];
+        let response = "<location><file>main.rs</file><line>42</line></location>";
+        let value = parse_xml_fields(response, &fields).unwrap();
+
+        assert_eq!(value, json!({"location": {"file": "main.rs", "line": 42}}));
+    }
+
+    #[test]
+    fn test_uncoercible_scalar_kept_as_string() {
+        let response = "<summary>s</summary><points>p</points><count>many</count>";
+        let value = parse_xml_fields(response, &fields()).unwrap();
+
+        assert_eq!(value["count"], "many");
+    }
+
+    #[test]
+    fn test_no_matching_tags() {
+        assert!(matches!(
+            parse_xml_fields("plain text", &fields()),
+            Err(ParseError::StructureMismatch { .. })
+        ));
+        assert!(matches!(
+            parse_xml_fields("<other>x</other>", &fields()),
+            Err(ParseError::StructureMismatch { .. })
+        ));
+    }
+}
@@ -103,7 +103,8 @@ impl SpecAgent {
     ///
     /// Returns a SpecContext with the extracted information.
     pub async fn intake(&mut self, nl_input: &str) -> Result<SpecContext> {
-        let mut ctx = SpecContext::new(nl_input);
+        let mut ctx =
+            SpecContext::new(nl_input).with_deterministic_ids(self.config.deterministic_ids);
 
         // Parse the natural language input
         let _result = NLParser::parse(&mut ctx);
@@ -179,7 +180,8 @@ impl SpecAgent {
     /// - Generates new clarifying questions based on ambiguities
     /// - Iterates until all required questions are answered or max rounds reached
     ///
-    /// Returns a list of new questions to ask.
+    /// Returns the new questions to ask, highest [`Question::priority`]
+    /// first.
     pub async fn refine(
         &mut self,
         ctx: &mut SpecContext,
@@ -232,6 +234,14 @@ impl SpecAgent {
         Ok(fresh_questions)
     }
 
+    /// Id for a requirement created from an answer, distinct from every
+    /// requirement already in `ctx`.
+    fn answer_requirement_id(ctx: &SpecContext, prefix: &str, text: &str) -> String {
+        let mut ids = super::types::SpecIdAllocator::new(ctx.deterministic_ids);
+        ids.reserve(ctx.requirements.iter().map(|r| r.id.as_str()));
+        ids.allocate(prefix, text, ctx.answers.len())
+    }
+
     /// Apply an answer to refine the context.
     fn apply_answer(&self, ctx: &mut SpecContext, answer: &Answer) {
         // Find the question being answered
@@ -254,8 +264,9 @@ impl SpecAgent {
                 }
                 super::types::QuestionCategory::Invariants => {
                     // Create a new constraint requirement from the answer
+                    let id = Self::answer_requirement_id(ctx, "REQ-ANS", &answer.text);
                     ctx.requirements.push(super::types::ExtractedRequirement {
-                        id: format!("REQ-ANS-{}", ctx.answers.len()),
+                        id,
                         text: answer.text.clone(),
                         req_type: super::types::RequirementType::Constraint,
                         confidence: 0.9, // High confidence since user-provided
@@ -266,8 +277,9 @@ impl SpecAgent {
                 }
                 super::types::QuestionCategory::EdgeCases => {
                     // Create error case requirements
+                    let id = Self::answer_requirement_id(ctx, "REQ-ERR", &answer.text);
                     ctx.requirements.push(super::types::ExtractedRequirement {
-                        id: format!("REQ-ERR-{}", ctx.answers.len()),
+                        id,
                         text: answer.text.clone(),
                         req_type: super::types::RequirementType::ErrorCase,
                         confidence: 0.9,
@@ -302,6 +314,9 @@ impl SpecAgent {
     /// - Generates Topos (.tps) specification
     /// - Generates Lean (.lean) specification
     /// - Creates cross-references between them
+    /// - In Baseline mode, rejects output containing placeholder markers
+    ///   with [`Error::Config`] (see
+    ///   [`FormalizationResult::validate_completeness`])
     ///
     /// Returns the formalization result with both specifications.
     pub async fn formalize(&mut self, ctx: &SpecContext) -> Result<FormalizationResult> {
@@ -328,6 +343,16 @@ impl SpecAgent {
             self.config.completeness_mode,
         );
 
+        let violations = result.validate_completeness(self.config.completeness_mode);
+        if !violations.is_empty() {
+            let locations: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+            return Err(Error::Config(format!(
+                "Baseline completeness violated by {} placeholder(s):\n{}",
+                violations.len(),
+                locations.join("\n")
+            )));
+        }
+
         // Store generated specs in context (via a mutable method would be cleaner)
         // For now, we return the result and the caller can update the context
 
@@ -690,6 +715,45 @@ mod tests {
         assert!(!result.formalization.lean_content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_workflow_ids_are_stable_across_runs() {
+        let input = "An Order has items and a Customer. Users can create orders. \
+                     Users can cancel orders. The total must be positive.";
+        let ids = |result: &WorkflowResult| {
+            let reqs: Vec<String> = result
+                .context
+                .requirements
+                .iter()
+                .map(|r| r.id.clone())
+                .collect();
+            let refs: Vec<String> = result
+                .formalization
+                .cross_refs
+                .iter()
+                .map(|r| r.id.clone())
+                .collect();
+            (reqs, refs)
+        };
+
+        let first = SpecAgent::minimal().run_workflow(input).await.unwrap();
+        let second = SpecAgent::minimal().run_workflow(input).await.unwrap();
+        let (reqs, refs) = ids(&first);
+        assert!(reqs.len() >= 3);
+        assert!(!refs.is_empty());
+        assert_eq!(ids(&second), (reqs.clone(), refs));
+        assert_eq!(
+            first.formalization.topos_content,
+            second.formalization.topos_content
+        );
+
+        let unique: std::collections::HashSet<_> = reqs.iter().collect();
+        assert_eq!(unique.len(), reqs.len());
+
+        let config = SpecAgentConfig::minimal().with_deterministic_ids(false);
+        let random = SpecAgent::new(config).run_workflow(input).await.unwrap();
+        assert_ne!(ids(&random).0, reqs);
+    }
+
     #[test]
     fn test_spec_agent_config() {
         let agent = SpecAgent::new(SpecAgentConfig::full());
@@ -7,8 +7,9 @@
 
 use super::types::{
     CompletenessMode, CrossReference, ExtractedRequirement, FormalizationLevel,
-    FormalizationResult, RequirementType, SpecContext, SpecDomain,
+    FormalizationResult, RequirementType, SpecContext, SpecDomain, SpecIdAllocator,
 };
+use crate::repl::floor_char_boundary;
 
 // ============================================================================
 // Topos Spec Generator
@@ -287,7 +288,7 @@ impl ToposGenerator {
         if s.len() <= max_len {
             s
         } else {
-            format!("{}...", &s[..max_len - 3])
+            format!("{}...", &s[..floor_char_boundary(&s, max_len - 3)])
         }
     }
 
@@ -737,7 +738,7 @@ impl LeanGenerator {
         if s.len() <= max_len {
             s
         } else {
-            format!("{}...", &s[..max_len - 3])
+            format!("{}...", &s[..floor_char_boundary(&s, max_len - 3)])
         }
     }
 }
@@ -756,7 +757,17 @@ impl CrossRefGenerator {
         topos_filename: &str,
         lean_filename: &str,
     ) -> Vec<CrossReference> {
-        let mut refs = Vec::new();
+        let mut refs: Vec<CrossReference> = Vec::new();
+        let mut ids = SpecIdAllocator::new(ctx.deterministic_ids);
+        let mut push = |topos_element: String, lean_artifact: String, ref_type: &str| {
+            let key = format!("{}:{}->{}", ref_type, topos_element, lean_artifact);
+            refs.push(CrossReference {
+                id: ids.allocate("XREF", &key, refs.len()),
+                topos_element,
+                lean_artifact,
+                ref_type: ref_type.to_string(),
+            });
+        };
 
         // Generate cross-refs for data structures
         for req in ctx
@@ -765,11 +776,11 @@ impl CrossRefGenerator {
             .filter(|r| r.req_type == RequirementType::DataStructure)
         {
             if let Some(ref name) = req.formal_name {
-                refs.push(CrossReference {
-                    topos_element: format!("{}#{}", topos_filename, name),
-                    lean_artifact: format!("{}#{}", lean_filename, name),
-                    ref_type: "structure".to_string(),
-                });
+                push(
+                    format!("{}#{}", topos_filename, name),
+                    format!("{}#{}", lean_filename, name),
+                    "structure",
+                );
             }
         }
 
@@ -780,18 +791,18 @@ impl CrossRefGenerator {
             .filter(|r| r.req_type == RequirementType::Behavior)
         {
             if let Some(ref name) = req.formal_name {
-                refs.push(CrossReference {
-                    topos_element: format!("{}#{}", topos_filename, name),
-                    lean_artifact: format!("{}#{}", lean_filename, name),
-                    ref_type: "behavior".to_string(),
-                });
+                push(
+                    format!("{}#{}", topos_filename, name),
+                    format!("{}#{}", lean_filename, name),
+                    "behavior",
+                );
 
                 // Also add spec cross-ref
-                refs.push(CrossReference {
-                    topos_element: format!("{}#{}", topos_filename, name),
-                    lean_artifact: format!("{}#{}_spec", lean_filename, name),
-                    ref_type: "spec".to_string(),
-                });
+                push(
+                    format!("{}#{}", topos_filename, name),
+                    format!("{}#{}_spec", lean_filename, name),
+                    "spec",
+                );
             }
         }
 
@@ -117,8 +117,8 @@ pub use generators::{
 };
 pub use parser::{NLParser, ParseResult};
 pub use types::{
-    Ambiguity, AmbiguitySeverity, Answer, CompletenessMode, CrossReference, ExtractedRequirement,
-    FormalizationLevel, FormalizationResult, ProofResult, ProofStrategy, Question,
-    QuestionCategory, RequirementType, SpecAgentConfig, SpecContext, SpecDomain, SpecPhase,
-    VerificationResult,
+    Ambiguity, AmbiguitySeverity, Answer, CompletenessMode, CompletenessViolation, CrossReference,
+    ExtractedRequirement, FormalizationLevel, FormalizationResult, ProofResult, ProofStrategy,
+    Question, QuestionCategory, RequirementType, SpecAgentConfig, SpecContext, SpecDomain,
+    SpecPhase, VerificationResult, PLACEHOLDER_MARKERS,
 };
@@ -7,13 +7,13 @@
 //! - Ambiguities that need clarification
 
 use regex::Regex;
-use std::collections::HashSet;
 use std::sync::LazyLock;
 
 use super::types::{
-    Ambiguity, AmbiguitySeverity, ExtractedRequirement, Question, QuestionCategory,
-    RequirementType, SpecContext, SpecDomain,
+    question_priority, Ambiguity, AmbiguitySeverity, ExtractedRequirement, Question,
+    QuestionCategory, RequirementType, SpecContext, SpecDomain, SpecIdAllocator,
 };
+use crate::repl::floor_char_boundary;
 
 // ============================================================================
 // Regex patterns for NL parsing
@@ -176,8 +176,9 @@ impl NLParser {
         let sentences = Self::split_sentences(input);
 
         // Extract requirements from each sentence
+        let mut ids = SpecIdAllocator::new(ctx.deterministic_ids);
         for (idx, sentence) in sentences.iter().enumerate() {
-            Self::extract_requirements_from_sentence(sentence, idx, &mut result, ctx);
+            Self::extract_requirements_from_sentence(sentence, idx, &mut result, &mut ids);
         }
 
         // Detect domains
@@ -211,7 +212,7 @@ impl NLParser {
         sentence: &str,
         sentence_idx: usize,
         result: &mut ParseResult,
-        _ctx: &SpecContext,
+        ids: &mut SpecIdAllocator,
     ) {
         let entities = Self::extract_entities(sentence);
 
@@ -220,7 +221,7 @@ impl NLParser {
             if let Some(cap) = DATA_STRUCTURE_PATTERN.captures(sentence) {
                 let entity = cap.get(2).map(|m| m.as_str()).unwrap_or("Entity");
                 result.requirements.push(ExtractedRequirement {
-                    id: format!("REQ-DS-{}", sentence_idx),
+                    id: ids.allocate("REQ-DS", sentence, sentence_idx),
                     text: sentence.to_string(),
                     req_type: RequirementType::DataStructure,
                     confidence: 0.8,
@@ -236,7 +237,7 @@ impl NLParser {
             if let Some(cap) = BEHAVIOR_PATTERN.captures(sentence) {
                 let verb = cap.get(3).map(|m| m.as_str()).unwrap_or("perform");
                 result.requirements.push(ExtractedRequirement {
-                    id: format!("REQ-BH-{}", sentence_idx),
+                    id: ids.allocate("REQ-BH", sentence, sentence_idx),
                     text: sentence.to_string(),
                     req_type: RequirementType::Behavior,
                     confidence: 0.75,
@@ -251,7 +252,7 @@ impl NLParser {
         if CONSTRAINT_PATTERN.is_match(sentence) {
             let has_quantity = QUANTITY_PATTERN.is_match(sentence);
             result.requirements.push(ExtractedRequirement {
-                id: format!("REQ-CN-{}", sentence_idx),
+                id: ids.allocate("REQ-CN", sentence, sentence_idx),
                 text: sentence.to_string(),
                 req_type: RequirementType::Constraint,
                 confidence: if has_quantity { 0.85 } else { 0.7 },
@@ -264,7 +265,7 @@ impl NLParser {
         // Check for error case requirements
         if ERROR_CASE_PATTERN.is_match(sentence) {
             result.requirements.push(ExtractedRequirement {
-                id: format!("REQ-ER-{}", sentence_idx),
+                id: ids.allocate("REQ-ER", sentence, sentence_idx),
                 text: sentence.to_string(),
                 req_type: RequirementType::ErrorCase,
                 confidence: 0.8,
@@ -277,7 +278,7 @@ impl NLParser {
 
     /// Extract entity names from text.
     fn extract_entities(text: &str) -> Vec<String> {
-        let mut entities = HashSet::new();
+        let mut entities: Vec<String> = Vec::new();
 
         for cap in ENTITY_PATTERN.captures_iter(text) {
             // Get the matched group (could be backtick, quote, or capitalized word)
@@ -290,13 +291,13 @@ impl NLParser {
 
             if let Some(e) = entity {
                 // Filter out common words
-                if !Self::is_common_word(&e) {
-                    entities.insert(e);
+                if !Self::is_common_word(&e) && !entities.contains(&e) {
+                    entities.push(e);
                 }
             }
         }
 
-        entities.into_iter().collect()
+        entities
     }
 
     /// Check if a word is a common word that shouldn't be treated as an entity.
@@ -359,6 +360,7 @@ impl NLParser {
                 ambiguities.push(Ambiguity {
                     description: format!("Ambiguous term '{}' needs clarification", term),
                     source_text: context.to_string(),
+                    term: term.clone(),
                     interpretations: Self::suggest_interpretations(&term),
                     severity,
                 });
@@ -417,22 +419,27 @@ impl NLParser {
         let mut questions = Vec::new();
         let mut question_id = 0;
 
-        // Questions for high-severity ambiguities
+        // Questions for ambiguities; only high-severity ones must be answered
         for ambiguity in &ctx.ambiguities {
-            if ambiguity.severity == AmbiguitySeverity::High {
-                question_id += 1;
-                questions.push(Question {
-                    id: format!("Q-AMB-{}", question_id),
-                    text: format!(
-                        "The phrase \"{}\" is ambiguous. Could you clarify what specific criteria or constraints you mean?",
-                        ambiguity.source_text.trim()
-                    ),
-                    category: QuestionCategory::Scope,
-                    rationale: ambiguity.description.clone(),
-                    suggestions: ambiguity.interpretations.clone(),
-                    required: true,
-                });
-            }
+            question_id += 1;
+            let term = ambiguity.term.to_lowercase();
+            let dependents = ctx
+                .requirements
+                .iter()
+                .filter(|r| !term.is_empty() && r.text.to_lowercase().contains(&term))
+                .count();
+            questions.push(Question {
+                id: format!("Q-AMB-{}", question_id),
+                text: format!(
+                    "The phrase \"{}\" is ambiguous. Could you clarify what specific criteria or constraints you mean?",
+                    ambiguity.source_text.trim()
+                ),
+                category: QuestionCategory::Scope,
+                rationale: ambiguity.description.clone(),
+                suggestions: ambiguity.interpretations.clone(),
+                required: ambiguity.severity == AmbiguitySeverity::High,
+                priority: question_priority(ambiguity.severity, dependents),
+            });
         }
 
         // Questions for data structure requirements lacking details
@@ -444,6 +451,14 @@ impl NLParser {
             // Check if we have field details
             if !req.text.contains(':') && !req.text.contains("with") {
                 question_id += 1;
+                let dependents = req.formal_name.as_ref().map_or(1, |name| {
+                    ctx.requirements
+                        .iter()
+                        .filter(|r| {
+                            r.entities.contains(name) || r.formal_name.as_ref() == Some(name)
+                        })
+                        .count()
+                });
                 questions.push(Question {
                     id: format!("Q-DS-{}", question_id),
                     text: format!(
@@ -461,6 +476,7 @@ impl NLParser {
                         "status: enum of states".to_string(),
                     ],
                     required: true,
+                    priority: question_priority(AmbiguitySeverity::Medium, dependents),
                 });
             }
         }
@@ -487,6 +503,7 @@ impl NLParser {
                         "Must be exactly N".to_string(),
                     ],
                     required: false,
+                    priority: question_priority(AmbiguitySeverity::Medium, 1),
                 });
             }
         }
@@ -515,9 +532,19 @@ impl NLParser {
                     "Log and continue".to_string(),
                 ],
                 required: false,
+                priority: question_priority(
+                    AmbiguitySeverity::Medium,
+                    ctx.requirements
+                        .iter()
+                        .filter(|r| r.req_type == RequirementType::Behavior)
+                        .count(),
+                ),
             });
         }
 
+        // Highest priority first; the sort is stable, so ties keep
+        // generation order
+        questions.sort_by_key(|q| std::cmp::Reverse(q.priority));
         questions
     }
 
@@ -552,7 +579,7 @@ impl NLParser {
         if s.len() <= max_len {
             s.to_string()
         } else {
-            format!("{}...", &s[..max_len - 3])
+            format!("{}...", &s[..floor_char_boundary(s, max_len - 3)])
         }
     }
 }
@@ -669,6 +696,38 @@ mod tests {
         assert!(questions.iter().any(|q| q.required));
     }
 
+    #[test]
+    fn test_questions_ranked_by_severity_and_dependents() {
+        let mut ctx = SpecContext::new(
+            "Search should be fast. Users can view the dashboard often. \
+             Uploads must use appropriate validation.",
+        );
+        NLParser::parse(&mut ctx);
+
+        let questions = NLParser::generate_questions(&ctx);
+        let amb: Vec<&Question> = questions
+            .iter()
+            .filter(|q| q.id.starts_with("Q-AMB"))
+            .collect();
+        assert_eq!(amb.len(), 3);
+
+        // The high-severity "appropriate" question outranks the low ones,
+        // even though it was generated last.
+        assert!(amb[0].rationale.contains("appropriate"));
+        assert!(amb[0].required);
+        assert!(amb[0].priority > question_priority(AmbiguitySeverity::High, 0));
+        assert_eq!(questions[0].id, amb[0].id);
+
+        // Equal-priority low-severity questions keep generation order.
+        assert!(amb[1].rationale.contains("fast"));
+        assert!(amb[2].rationale.contains("often"));
+        assert_eq!(amb[1].priority, amb[2].priority);
+        assert!(!amb[1].required);
+        assert_eq!(questions.last().unwrap().id, amb[2].id);
+
+        assert!(questions.windows(2).all(|w| w[0].priority >= w[1].priority));
+    }
+
     #[test]
     fn test_to_pascal_case() {
         assert_eq!(NLParser::to_pascal_case("order"), "Order");
@@ -4,7 +4,8 @@
 //! natural language requirements into formal specifications (Topos + Lean).
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 
 /// Level of formalization to target.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -178,6 +179,9 @@ pub struct SpecAgentConfig {
     pub validate_with_topos: bool,
     /// Timeout for LLM calls in milliseconds.
     pub llm_timeout_ms: u64,
+    /// Derive requirement and cross-reference ids from their content, so
+    /// identical input yields identical ids across runs.
+    pub deterministic_ids: bool,
 }
 
 impl Default for SpecAgentConfig {
@@ -192,6 +196,7 @@ impl Default for SpecAgentConfig {
             validate_with_lean: true,
             validate_with_topos: true,
             llm_timeout_ms: 30_000,
+            deterministic_ids: true,
         }
     }
 }
@@ -209,6 +214,7 @@ impl SpecAgentConfig {
             validate_with_lean: false,
             validate_with_topos: false,
             llm_timeout_ms: 30_000,
+            deterministic_ids: true,
         }
     }
 
@@ -247,6 +253,12 @@ impl SpecAgentConfig {
         self.completeness_mode = mode;
         self
     }
+
+    /// Set whether generated ids are derived from content.
+    pub fn with_deterministic_ids(mut self, deterministic: bool) -> Self {
+        self.deterministic_ids = deterministic;
+        self
+    }
 }
 
 /// Phase of the specification workflow.
@@ -299,6 +311,24 @@ pub struct Question {
     pub suggestions: Vec<String>,
     /// Whether an answer is required to proceed.
     pub required: bool,
+    /// Ranking score from [`question_priority`]; higher is asked first.
+    #[serde(default)]
+    pub priority: u32,
+}
+
+/// Priority score for a question.
+///
+/// Severity dominates: every question from a higher-severity source outranks
+/// every lower-severity one. Within a severity, questions whose answer
+/// affects more requirements rank higher.
+pub fn question_priority(severity: AmbiguitySeverity, dependent_requirements: usize) -> u32 {
+    const LEVEL: u32 = 1000;
+    let level = match severity {
+        AmbiguitySeverity::Low => 1,
+        AmbiguitySeverity::Medium => 2,
+        AmbiguitySeverity::High => 3,
+    };
+    level * LEVEL + dependent_requirements.min(LEVEL as usize - 1) as u32
 }
 
 /// Category of clarifying question.
@@ -354,6 +384,13 @@ pub struct SpecContext {
     pub ambiguities: Vec<Ambiguity>,
     /// Metadata for the context.
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Whether ids are derived from content (see [`SpecIdAllocator`]).
+    #[serde(default = "default_deterministic_ids")]
+    pub deterministic_ids: bool,
+}
+
+fn default_deterministic_ids() -> bool {
+    true
 }
 
 impl SpecContext {
@@ -370,9 +407,16 @@ impl SpecContext {
             detected_domains: Vec::new(),
             ambiguities: Vec::new(),
             metadata: HashMap::new(),
+            deterministic_ids: true,
         }
     }
 
+    /// Set whether ids are derived from content.
+    pub fn with_deterministic_ids(mut self, deterministic: bool) -> Self {
+        self.deterministic_ids = deterministic;
+        self
+    }
+
     /// Advance to the next phase.
     pub fn advance_phase(&mut self) -> Option<SpecPhase> {
         if let Some(next) = self.phase.next() {
@@ -453,6 +497,9 @@ pub struct Ambiguity {
     pub description: String,
     /// The ambiguous text from the input.
     pub source_text: String,
+    /// The ambiguous term itself.
+    #[serde(default)]
+    pub term: String,
     /// Possible interpretations.
     pub interpretations: Vec<String>,
     /// Severity of the ambiguity.
@@ -487,9 +534,143 @@ pub struct FormalizationResult {
     pub warnings: Vec<String>,
 }
 
+/// Markers that Baseline completeness mode forbids in generated artifacts.
+///
+/// Words match on identifier boundaries, so `sorry` does not flag `sorrySet`;
+/// `draft:` is the annotation Placeholder mode emits.
+pub const PLACEHOLDER_MARKERS: &[&str] = &["sorry", "TODO", "FIXME", "draft:"];
+
+impl FormalizationResult {
+    /// Find placeholder markers that `mode` does not allow.
+    ///
+    /// Placeholder mode allows everything. Baseline mode reports every
+    /// [`PLACEHOLDER_MARKERS`] occurrence in the Topos and Lean content,
+    /// located by the suggested filename and 1-based line and column.
+    pub fn validate_completeness(&self, mode: CompletenessMode) -> Vec<CompletenessViolation> {
+        if mode == CompletenessMode::Placeholder {
+            return Vec::new();
+        }
+        let mut violations = scan_placeholders(&self.topos_filename, &self.topos_content);
+        violations.extend(scan_placeholders(&self.lean_filename, &self.lean_content));
+        violations
+    }
+}
+
+/// A placeholder marker found where the completeness mode forbids it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletenessViolation {
+    /// File the marker appears in.
+    pub file: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column (in characters).
+    pub column: usize,
+    /// The marker found.
+    pub marker: String,
+    /// The offending line, trimmed.
+    pub text: String,
+}
+
+impl std::fmt::Display for CompletenessViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: `{}` in `{}`",
+            self.file, self.line, self.column, self.marker, self.text
+        )
+    }
+}
+
+fn scan_placeholders(file: &str, content: &str) -> Vec<CompletenessViolation> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '\'';
+    let mut violations = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        for marker in PLACEHOLDER_MARKERS {
+            for (byte, _) in line.match_indices(marker) {
+                let before = line[..byte].chars().next_back();
+                let after = line[byte + marker.len()..].chars().next();
+                let word = marker.chars().all(is_ident);
+                if word && (before.is_some_and(is_ident) || after.is_some_and(is_ident)) {
+                    continue;
+                }
+                violations.push(CompletenessViolation {
+                    file: file.to_string(),
+                    line: idx + 1,
+                    column: line[..byte].chars().count() + 1,
+                    marker: marker.to_string(),
+                    text: line.trim().to_string(),
+                });
+            }
+        }
+    }
+
+    violations.sort_by_key(|v| (v.line, v.column));
+    violations
+}
+
+/// Issues ids for generated spec elements.
+///
+/// Deterministic ids are `{prefix}-{hash}`, where the hash covers the
+/// element's text and index, so re-running on identical input reproduces
+/// them. Otherwise ids keep the legacy `{prefix}-{index}` form. Either way,
+/// an id already issued (or [reserved](Self::reserve)) gets a `-2`, `-3`,
+/// ... suffix, so distinct elements never share one.
+#[derive(Debug, Clone)]
+pub struct SpecIdAllocator {
+    deterministic: bool,
+    issued: HashSet<String>,
+}
+
+impl SpecIdAllocator {
+    /// Hex digits of the hash kept in each id.
+    pub const HASH_LEN: usize = 8;
+
+    /// Create an allocator.
+    pub fn new(deterministic: bool) -> Self {
+        Self {
+            deterministic,
+            issued: HashSet::new(),
+        }
+    }
+
+    /// Mark ids as already issued, e.g. those present in a context.
+    pub fn reserve<'a>(&mut self, ids: impl IntoIterator<Item = &'a str>) {
+        self.issued.extend(ids.into_iter().map(str::to_string));
+    }
+
+    /// Issue an id for the element at `index` with the given text.
+    pub fn allocate(&mut self, prefix: &str, text: &str, index: usize) -> String {
+        if !self.deterministic {
+            return self.claim(format!("{}-{}", prefix, index));
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(text.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(index.to_le_bytes());
+        let digest = format!("{:x}", hasher.finalize());
+        self.claim(format!("{}-{}", prefix, &digest[..Self::HASH_LEN]))
+    }
+
+    /// Reserve `base`, suffixing it if it was already issued.
+    fn claim(&mut self, base: String) -> String {
+        let mut id = base.clone();
+        let mut n = 2;
+        while self.issued.contains(&id) {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+        self.issued.insert(id.clone());
+        id
+    }
+}
+
 /// A cross-reference between Topos and Lean artifacts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrossReference {
+    /// Identifier (see [`SpecIdAllocator`]).
+    #[serde(default)]
+    pub id: String,
     /// Topos element name.
     pub topos_element: String,
     /// Lean artifact name.
@@ -617,6 +798,74 @@ mod tests {
         assert_eq!(config.completeness_mode, CompletenessMode::Baseline);
     }
 
+    #[test]
+    fn test_validate_completeness_flags_sorry_in_baseline() {
+        let result = FormalizationResult {
+            topos_content: "Concept Order:\n  id: `Id`\n".to_string(),
+            topos_filename: "order.tps".to_string(),
+            lean_content: "structure Order where\n  id : Nat\n\ntheorem order_ok : True := by\n  sorry\n-- sorrySet is fine\n".to_string(),
+            lean_filename: "Order.lean".to_string(),
+            cross_refs: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        let violations = result.validate_completeness(CompletenessMode::Baseline);
+        assert_eq!(
+            violations,
+            vec![CompletenessViolation {
+                file: "Order.lean".to_string(),
+                line: 5,
+                column: 3,
+                marker: "sorry".to_string(),
+                text: "sorry".to_string(),
+            }]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "Order.lean:5:3: `sorry` in `sorry`"
+        );
+
+        assert!(result
+            .validate_completeness(CompletenessMode::Placeholder)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_spec_id_allocator_suffixes_collisions() {
+        let mut first = SpecIdAllocator::new(true);
+        let mut second = SpecIdAllocator::new(true);
+        let id = first.allocate("REQ-DS", "An Order has items", 0);
+        assert_eq!(id, second.allocate("REQ-DS", "An Order has items", 0));
+        assert_eq!(id.len(), "REQ-DS-".len() + SpecIdAllocator::HASH_LEN);
+        assert_ne!(id, first.allocate("REQ-DS", "An Order has items", 1));
+
+        // Simulate two different requirements truncating to the same hash.
+        assert_eq!(
+            first.claim("REQ-DS-deadbeef".to_string()),
+            "REQ-DS-deadbeef"
+        );
+        assert_eq!(
+            first.claim("REQ-DS-deadbeef".to_string()),
+            "REQ-DS-deadbeef-2"
+        );
+        assert_eq!(
+            first.claim("REQ-DS-deadbeef".to_string()),
+            "REQ-DS-deadbeef-3"
+        );
+
+        let mut legacy = SpecIdAllocator::new(false);
+        assert_eq!(
+            legacy.allocate("REQ-DS", "An Order has items", 0),
+            "REQ-DS-0"
+        );
+        assert_eq!(
+            legacy.allocate("REQ-DS", "Another sentence", 0),
+            "REQ-DS-0-2"
+        );
+        legacy.reserve(["REQ-ANS-1"]);
+        assert_eq!(legacy.allocate("REQ-ANS", "Answer", 1), "REQ-ANS-1-2");
+    }
+
     #[test]
     fn test_completeness_mode_builder() {
         let config =
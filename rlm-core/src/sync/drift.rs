@@ -11,7 +11,7 @@ use crate::topos::{LeanRef, LinkIndex, LinkType, ToposRef};
 use super::types::{
     Drift, DriftDetails, DriftReport, DriftType, FieldDiff, FieldDiffKind, LeanField,
     LeanStructure, LeanTheorem, SuggestedAction, SyncSuggestion, ToposBehavior, ToposConcept,
-    ToposField, ToposInvariant, TypeMismatch,
+    ToposField, ToposInvariant, ToposMetadata, TypeMismatch,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -87,10 +87,68 @@ impl<T> TypedArena<T> {
     }
 }
 
+/// Parsed type expression used to compare Topos and Lean types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TypeExpr {
+    /// A bare type name (`Nat`, `Order`).
+    Name(String),
+    /// A type constructor applied to arguments (`List Nat`, `Map K V`).
+    App(String, Vec<TypeExpr>),
+    /// A product type (`A × B`), flattened.
+    Tuple(Vec<TypeExpr>),
+}
+
+impl TypeExpr {
+    /// Render in Lean application syntax, parenthesizing compound arguments.
+    fn render(&self) -> String {
+        match self {
+            Self::Name(name) => name.clone(),
+            Self::App(head, args) => {
+                let mut out = head.clone();
+                for arg in args {
+                    out.push(' ');
+                    out.push_str(&arg.render_arg());
+                }
+                out
+            }
+            Self::Tuple(items) => items
+                .iter()
+                .map(Self::render_arg)
+                .collect::<Vec<_>>()
+                .join(" × "),
+        }
+    }
+
+    fn render_arg(&self) -> String {
+        match self {
+            Self::Name(name) => name.clone(),
+            _ => format!("({})", self.render()),
+        }
+    }
+
+    /// Rename type constructors (and bare names) through `mappings`.
+    fn map_heads(self, mappings: &HashMap<String, String>) -> Self {
+        let rename = |name: String| mappings.get(&name).cloned().unwrap_or(name);
+        match self {
+            Self::Name(name) => Self::Name(rename(name)),
+            Self::App(head, args) => Self::App(
+                rename(head),
+                args.into_iter().map(|a| a.map_heads(mappings)).collect(),
+            ),
+            Self::Tuple(items) => {
+                Self::Tuple(items.into_iter().map(|i| i.map_heads(mappings)).collect())
+            }
+        }
+    }
+}
+
 /// Drift detector for comparing Topos and Lean artifacts.
 pub struct DriftDetector {
     /// Type mapping from Topos to Lean.
     type_mappings: HashMap<String, String>,
+    /// Generic type constructors treated as equivalent, mapped to a
+    /// canonical name (e.g. `HashMap` and `Std.HashMap` to `Map`).
+    generic_mappings: HashMap<String, String>,
 }
 
 impl DriftDetector {
@@ -113,8 +171,28 @@ impl DriftDetector {
         type_mappings.insert("array".to_string(), "Array".to_string());
         type_mappings.insert("optional".to_string(), "Option".to_string());
         type_mappings.insert("maybe".to_string(), "Option".to_string());
+        type_mappings.insert("map".to_string(), "Map".to_string());
+        type_mappings.insert("set".to_string(), "Set".to_string());
+
+        // Lean containers that correspond to the same Topos generic
+        let mut generic_mappings = HashMap::new();
+        for map in [
+            "HashMap",
+            "Std.HashMap",
+            "Lean.HashMap",
+            "Std.TreeMap",
+            "RBMap",
+        ] {
+            generic_mappings.insert(map.to_string(), "Map".to_string());
+        }
+        for set in ["HashSet", "Std.HashSet", "Finset"] {
+            generic_mappings.insert(set.to_string(), "Set".to_string());
+        }
 
-        Self { type_mappings }
+        Self {
+            type_mappings,
+            generic_mappings,
+        }
     }
 
     /// Create a drift detector with custom type mappings.
@@ -130,6 +208,14 @@ impl DriftDetector {
             .insert(topos_type.into(), lean_type.into());
     }
 
+    /// Treat the generic type constructor `name` as `canonical` on both sides.
+    ///
+    /// For example `add_generic_mapping("Std.HashMap", "Map")` makes
+    /// `Std.HashMap String Order` match Topos `map of String to Order`.
+    pub fn add_generic_mapping(&mut self, name: impl Into<String>, canonical: impl Into<String>) {
+        self.generic_mappings.insert(name.into(), canonical.into());
+    }
+
     /// Detect drift between Topos concepts and Lean structures.
     pub fn detect_concept_drift(
         &self,
@@ -671,37 +757,72 @@ impl DriftDetector {
 
     /// Normalize a Topos type for comparison.
     fn normalize_topos_type(&self, ty: &str) -> String {
-        let ty = ty.trim().trim_matches('`');
+        let ty = ty.replace('`', "");
+        self.parse_topos_type(&ty)
+            .map_heads(&self.generic_mappings)
+            .render()
+    }
+
+    /// Parse Topos type syntax, e.g. `list of optional X`, `map of K to V`,
+    /// or `(A, B)`.
+    fn parse_topos_type(&self, ty: &str) -> TypeExpr {
+        let ty = ty.trim();
 
-        // Handle "list of X" -> "List X"
-        if let Some(inner) = ty.strip_prefix("list of ") {
-            let inner_normalized = self.normalize_topos_type(inner);
-            return format!("List {}", inner_normalized);
+        // Handle "map of K to V" -> "Map K V"
+        if let Some((key, value)) = ty
+            .strip_prefix("map of ")
+            .and_then(|rest| rest.split_once(" to "))
+        {
+            return TypeExpr::App(
+                self.map_topos_name("map"),
+                vec![self.parse_topos_type(key), self.parse_topos_type(value)],
+            );
+        }
+
+        // Handle "list of X" -> "List X" (and "set of", "array of")
+        if let Some((wrapper, inner)) = ty.split_once(" of ") {
+            if self.type_mappings.contains_key(wrapper) {
+                return TypeExpr::App(
+                    self.map_topos_name(wrapper),
+                    vec![self.parse_topos_type(inner)],
+                );
+            }
         }
 
         // Handle "optional X" -> "Option X"
-        if let Some(inner) = ty.strip_prefix("optional ") {
-            let inner_normalized = self.normalize_topos_type(inner);
-            return format!("Option {}", inner_normalized);
+        for wrapper in ["optional", "maybe"] {
+            if let Some(inner) = ty.strip_prefix(wrapper).and_then(|r| r.strip_prefix(' ')) {
+                return TypeExpr::App(
+                    self.map_topos_name(wrapper),
+                    vec![self.parse_topos_type(inner)],
+                );
+            }
         }
 
-        // Check direct mappings
-        if let Some(lean_type) = self.type_mappings.get(ty) {
-            return lean_type.clone();
+        // Handle "(A, B)" -> "A × B"
+        if let Some(inner) = ty.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+            let items = split_top_level(inner, ',');
+            if items.len() > 1 {
+                return TypeExpr::Tuple(items.iter().map(|i| self.parse_topos_type(i)).collect());
+            }
         }
 
-        // PascalCase assumption for custom types
-        ty.to_string()
+        // Anything else is Lean-style syntax; map primitive names
+        parse_lean_type_expr(ty).map_heads(&self.type_mappings)
+    }
+
+    fn map_topos_name(&self, name: &str) -> String {
+        self.type_mappings
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
     }
 
     /// Normalize a Lean type for comparison.
     fn normalize_lean_type(&self, ty: &str) -> String {
-        let ty = ty.trim();
-
-        // Handle List X -> List X (already normalized)
-        // Handle Option X -> Option X (already normalized)
-
-        ty.to_string()
+        parse_lean_type_expr(ty.trim())
+            .map_heads(&self.generic_mappings)
+            .render()
     }
 
     /// Detect all drifts from index and parsed elements.
@@ -742,18 +863,201 @@ impl DriftDetector {
     }
 }
 
+/// Split `s` on `sep` outside parentheses.
+fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c == sep && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Parse Lean type syntax: applications, parentheses and `×` products.
+///
+/// Unparseable input falls back to a single name so comparison degrades to
+/// the raw string.
+fn parse_lean_type_expr(ty: &str) -> TypeExpr {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in ty.chars() {
+        if c.is_whitespace() || matches!(c, '(' | ')' | '×') {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    let mut pos = 0;
+    match parse_product(&tokens, &mut pos) {
+        Some(expr) if pos == tokens.len() => expr,
+        _ => TypeExpr::Name(tokens.join(" ")),
+    }
+}
+
+fn parse_product(tokens: &[String], pos: &mut usize) -> Option<TypeExpr> {
+    let mut items = vec![parse_application(tokens, pos)?];
+    while tokens.get(*pos).map(String::as_str) == Some("×") {
+        *pos += 1;
+        items.push(parse_application(tokens, pos)?);
+    }
+
+    if items.len() == 1 {
+        return items.pop();
+    }
+    // `A × (B × C)` is the same type as `A × B × C`
+    let flattened = items
+        .into_iter()
+        .flat_map(|item| match item {
+            TypeExpr::Tuple(inner) => inner,
+            other => vec![other],
+        })
+        .collect();
+    Some(TypeExpr::Tuple(flattened))
+}
+
+fn parse_application(tokens: &[String], pos: &mut usize) -> Option<TypeExpr> {
+    let head = parse_atom(tokens, pos)?;
+    let mut args = Vec::new();
+    while let Some(token) = tokens.get(*pos) {
+        if token == ")" || token == "×" {
+            break;
+        }
+        args.push(parse_atom(tokens, pos)?);
+    }
+
+    match (head, args.is_empty()) {
+        (head, true) => Some(head),
+        (TypeExpr::Name(name), false) => Some(TypeExpr::App(name, args)),
+        _ => None,
+    }
+}
+
+fn parse_atom(tokens: &[String], pos: &mut usize) -> Option<TypeExpr> {
+    let token = tokens.get(*pos)?;
+    *pos += 1;
+    match token.as_str() {
+        "(" => {
+            let inner = parse_product(tokens, pos)?;
+            if tokens.get(*pos).map(String::as_str) != Some(")") {
+                return None;
+            }
+            *pos += 1;
+            Some(inner)
+        }
+        ")" | "×" => None,
+        name => Some(TypeExpr::Name(name.to_string())),
+    }
+}
+
 impl Default for DriftDetector {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Parse the YAML front-matter block at the top of a Topos file, if any.
+pub fn parse_topos_metadata(content: &str) -> Option<ToposMetadata> {
+    let lines: Vec<&str> = content.lines().collect();
+    split_front_matter(&lines).0
+}
+
+/// Split a leading `---`-delimited front-matter block off `lines`.
+///
+/// Returns the parsed metadata and the index of the first body line. Leading
+/// blank lines may precede the opening `---`. A block without a closing
+/// `---` is not front-matter, and the whole file is treated as body.
+fn split_front_matter(lines: &[&str]) -> (Option<ToposMetadata>, usize) {
+    let Some(open) = lines.iter().position(|l| !l.trim().is_empty()) else {
+        return (None, 0);
+    };
+    if lines[open].trim_end() != "---" {
+        return (None, 0);
+    }
+    let Some(close) = lines[open + 1..]
+        .iter()
+        .position(|l| l.trim_end() == "---")
+        .map(|offset| open + 1 + offset)
+    else {
+        return (None, 0);
+    };
+
+    let mut metadata = ToposMetadata::default();
+    let mut last_key: Option<String> = None;
+    for line in &lines[open + 1..close] {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let continuation = line.starts_with([' ', '\t']) || trimmed.starts_with("- ");
+        if continuation {
+            if let Some(value) = last_key
+                .as_ref()
+                .and_then(|key| metadata.entries.get_mut(key))
+            {
+                let item = trimmed.trim_start_matches("- ").trim();
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(unquote(item));
+            }
+            continue;
+        }
+
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let key = key.trim().to_string();
+            metadata
+                .entries
+                .insert(key.clone(), unquote(value.trim()).to_string());
+            last_key = Some(key);
+        }
+    }
+
+    (Some(metadata), close + 1)
+}
+
+/// Strip one pair of matching YAML quotes.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
 /// Parse Topos concepts from content (simplified parser for sync purposes).
+///
+/// A leading YAML front-matter block is skipped and attached to each
+/// concept as [`ToposConcept::metadata`].
 pub fn parse_topos_concepts(content: &str, file: &Path) -> Vec<ToposConcept> {
     let mut concepts = Vec::new();
     let lines: Vec<&str> = content.lines().collect();
+    let (metadata, body_start) = split_front_matter(&lines);
 
-    let mut i = 0;
+    let mut i = body_start;
     while i < lines.len() {
         let line = lines[i].trim();
 
@@ -827,6 +1131,7 @@ pub fn parse_topos_concepts(content: &str, file: &Path) -> Vec<ToposConcept> {
                     doc,
                     source_file: file.to_path_buf(),
                     line: start_line,
+                    metadata: metadata.clone(),
                 });
 
                 continue; // Don't increment i again
@@ -840,11 +1145,14 @@ pub fn parse_topos_concepts(content: &str, file: &Path) -> Vec<ToposConcept> {
 }
 
 /// Parse Topos behaviors from content.
+///
+/// Front-matter is handled as in [`parse_topos_concepts`].
 pub fn parse_topos_behaviors(content: &str, file: &Path) -> Vec<ToposBehavior> {
     let mut behaviors = Vec::new();
     let lines: Vec<&str> = content.lines().collect();
+    let (metadata, body_start) = split_front_matter(&lines);
 
-    let mut i = 0;
+    let mut i = body_start;
     while i < lines.len() {
         let line = lines[i].trim();
 
@@ -938,6 +1246,7 @@ pub fn parse_topos_behaviors(content: &str, file: &Path) -> Vec<ToposBehavior> {
                     doc,
                     source_file: file.to_path_buf(),
                     line: start_line,
+                    metadata: metadata.clone(),
                 });
 
                 continue;
@@ -1258,6 +1567,58 @@ Concept Order:
         assert_eq!(concepts[0].fields[0].field_type, "OrderId");
     }
 
+    #[test]
+    fn test_parse_topos_front_matter() {
+        let content = r#"---
+owner: payments-team
+version: "1.2"
+Concept: not-a-concept
+tags:
+  - billing
+  - core
+---
+Concept Order:
+  id: `OrderId`
+  status: `OrderStatus`
+
+Behavior cancel_order:
+  given: order (`Order`)
+"#;
+
+        let concepts = parse_topos_concepts(content, Path::new("order.tps"));
+        assert_eq!(concepts.len(), 1);
+        assert_eq!(concepts[0].name, "Order");
+        assert_eq!(concepts[0].line, 9);
+        assert_eq!(concepts[0].fields.len(), 2);
+
+        let metadata = concepts[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata.owner(), Some("payments-team"));
+        assert_eq!(metadata.version(), Some("1.2"));
+        assert_eq!(metadata.get("Concept"), Some("not-a-concept"));
+        assert_eq!(metadata.get("tags"), Some("billing\ncore"));
+
+        let behaviors = parse_topos_behaviors(content, Path::new("order.tps"));
+        assert_eq!(behaviors.len(), 1);
+        assert_eq!(behaviors[0].metadata.as_ref(), Some(metadata));
+        assert_eq!(parse_topos_metadata(content).as_ref(), Some(metadata));
+    }
+
+    #[test]
+    fn test_parse_topos_without_or_unclosed_front_matter() {
+        let plain = "Concept Order:\n  id: `OrderId`\n";
+        let concepts = parse_topos_concepts(plain, Path::new("order.tps"));
+        assert_eq!(concepts.len(), 1);
+        assert!(concepts[0].metadata.is_none());
+        assert!(parse_topos_metadata(plain).is_none());
+
+        let unclosed = "---\nowner: payments-team\nConcept Order:\n  id: `OrderId`\n";
+        let concepts = parse_topos_concepts(unclosed, Path::new("order.tps"));
+        assert_eq!(concepts.len(), 1);
+        assert_eq!(concepts[0].line, 3);
+        assert_eq!(concepts[0].fields.len(), 1);
+        assert!(concepts[0].metadata.is_none());
+    }
+
     #[test]
     fn test_parse_topos_behaviors() {
         let content = r#"
@@ -1317,6 +1678,32 @@ theorem create_order_spec (req : OrderRequest) : Order :=
         assert!(!detector.types_compatible("string", "Int"));
     }
 
+    #[test]
+    fn test_drift_detector_nested_generics() {
+        let detector = DriftDetector::new();
+
+        assert!(detector.types_compatible("list of optional Int", "List (Option Int)"));
+        assert!(detector.types_compatible("optional list of `nat`", "Option (List Nat)"));
+        assert!(!detector.types_compatible("list of optional Int", "Option (List Int)"));
+        assert!(detector.types_compatible("map of String to Order", "HashMap String Order"));
+        assert!(detector.types_compatible(
+            "map of string to list of `Order`",
+            "Std.HashMap String (List Order)"
+        ));
+        assert!(detector.types_compatible("(int, string, bool)", "Int × String × Bool"));
+        assert!(detector.types_compatible("(Int, String)", "(Int × String)"));
+        assert!(!detector.types_compatible("map of String to Order", "List Order"));
+    }
+
+    #[test]
+    fn test_drift_detector_custom_generic_mapping() {
+        let mut detector = DriftDetector::new();
+        assert!(!detector.types_compatible("map of Nat to Order", "AssocList Nat Order"));
+
+        detector.add_generic_mapping("AssocList", "Map");
+        assert!(detector.types_compatible("map of Nat to Order", "AssocList Nat Order"));
+    }
+
     #[test]
     fn test_detect_concept_drift_missing_structure() {
         let detector = DriftDetector::new();
@@ -1329,6 +1716,7 @@ theorem create_order_spec (req : OrderRequest) : Order :=
             doc: None,
             source_file: PathBuf::from("order.tps"),
             line: 1,
+            metadata: None,
         }];
 
         let structures: Vec<LeanStructure> = vec![];
@@ -1376,6 +1764,7 @@ theorem create_order_spec (req : OrderRequest) : Order :=
             doc: None,
             source_file: PathBuf::from("order.tps"),
             line: 1,
+            metadata: None,
         }];
 
         let structures = vec![LeanStructure {
@@ -1453,6 +1842,7 @@ theorem create_order_spec (req : OrderRequest) : Order :=
                     doc: None,
                     source_file: PathBuf::from("specs/app.tps"),
                     line: i as u32 + 1,
+                    metadata: None,
                 })
                 .collect();
 
@@ -207,7 +207,7 @@ impl DualTrackSync {
             for entry in entries.flatten() {
                 if let Ok(content) = fs::read_to_string(&entry) {
                     let rel_path = entry.strip_prefix(&self.topos_root).unwrap_or(&entry);
-                    let _ = self.link_index.index_topos_file(rel_path, &content);
+                    let _ = self.link_index.update_file(rel_path, &content);
                 }
             }
         }
@@ -220,7 +220,7 @@ impl DualTrackSync {
             for entry in entries.flatten() {
                 if let Ok(content) = fs::read_to_string(&entry) {
                     let rel_path = entry.strip_prefix(&self.lean_root).unwrap_or(&entry);
-                    let _ = self.link_index.index_lean_file(rel_path, &content);
+                    let _ = self.link_index.update_file(rel_path, &content);
                 }
             }
         }
@@ -229,6 +229,82 @@ impl DualTrackSync {
         Ok(())
     }
 
+    /// Re-parse only the given files, updating cached elements and links.
+    ///
+    /// Paths may be absolute or relative to the Topos/Lean root; files that
+    /// no longer exist have their elements and links dropped, so deleted
+    /// definitions surface as `Missing`/`Extra` drift on the next
+    /// [`detect_drift`](Self::detect_drift). Any other read or link-index
+    /// error is returned, leaving files before it in `paths` refreshed.
+    pub fn refresh_files(&mut self, paths: &[PathBuf]) -> Result<()> {
+        for path in paths {
+            let is_lean = path.extension().is_some_and(|ext| ext == "lean");
+            let root = if is_lean {
+                &self.lean_root
+            } else {
+                &self.topos_root
+            };
+            let abs_path = if path.is_absolute() {
+                path.clone()
+            } else {
+                root.join(path)
+            };
+            let rel_path = abs_path
+                .strip_prefix(root)
+                .unwrap_or(&abs_path)
+                .to_path_buf();
+            let content = match fs::read_to_string(&abs_path) {
+                Ok(content) => Some(content),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => {
+                    return Err(Error::Internal(format!(
+                        "Failed to read {}: {}",
+                        abs_path.display(),
+                        e
+                    )))
+                }
+            };
+
+            if is_lean {
+                self.lean_structures.retain(|s| s.source_file != rel_path);
+                self.lean_theorems.retain(|t| t.source_file != rel_path);
+                if let Some(content) = &content {
+                    self.lean_structures
+                        .extend(parse_lean_structures(content, &rel_path));
+                    self.lean_theorems
+                        .extend(parse_lean_theorems(content, &rel_path));
+                }
+            } else {
+                self.topos_concepts.retain(|c| c.source_file != rel_path);
+                self.topos_behaviors.retain(|b| b.source_file != rel_path);
+                if let Some(content) = &content {
+                    self.topos_concepts
+                        .extend(parse_topos_concepts(content, &rel_path));
+                    self.topos_behaviors
+                        .extend(parse_topos_behaviors(content, &rel_path));
+                }
+            }
+
+            self.reindex_file(&rel_path, content.as_deref())?;
+        }
+
+        self.link_index.touch();
+        Ok(())
+    }
+
+    /// Replace the links declared in one file.
+    fn reindex_file(&mut self, rel_path: &Path, content: Option<&str>) -> Result<()> {
+        match content {
+            Some(content) => {
+                self.link_index.update_file(rel_path, content)?;
+            }
+            None => {
+                self.link_index.remove_file(rel_path);
+            }
+        }
+        Ok(())
+    }
+
     /// Detect drift between Topos and Lean specifications.
     pub async fn detect_drift(&self) -> Result<DriftReport> {
         let report = self.drift_detector.detect_all(
@@ -5,8 +5,10 @@
 
 use std::collections::HashMap;
 
+use super::drift::{parse_lean_structures, parse_topos_concepts};
 use super::types::{
-    FormalizationLevel, LeanStructure, LeanTheorem, ToposBehavior, ToposConcept, ToposInvariant,
+    FieldDiff, FieldDiffKind, FormalizationLevel, LeanStructure, LeanTheorem, ToposBehavior,
+    ToposConcept, ToposInvariant,
 };
 
 /// Generator for Lean code from Topos specifications.
@@ -518,6 +520,67 @@ pub fn lean_to_topos_behavior(theorem: &LeanTheorem) -> String {
     ToposGenerator::new().generate_behavior(theorem)
 }
 
+/// Generate Lean for a concept, convert it back to Topos, and diff the
+/// recovered fields against the original.
+///
+/// Types are compared by the Lean type they map to, so spelling changes
+/// such as `nat` becoming `natural` are not reported. In each diff the
+/// `topos_*` side describes the original field and the `lean_*` side the
+/// field recovered after the round trip; [`FieldDiffKind::OnlyInTopos`]
+/// means the field was lost and [`FieldDiffKind::OnlyInLean`] that one
+/// appeared. An empty result means generation was lossless.
+pub fn roundtrip_concept(concept: &ToposConcept, level: FormalizationLevel) -> Vec<FieldDiff> {
+    let lean_gen = LeanGenerator::new(level);
+    let lean = lean_gen.generate_structure(concept);
+    let recovered = parse_lean_structures(&lean, &concept.source_file)
+        .into_iter()
+        .find(|s| s.name == concept.name)
+        .map(|structure| lean_to_topos_concept(&structure))
+        .and_then(|topos| {
+            parse_topos_concepts(&topos, &concept.source_file)
+                .into_iter()
+                .next()
+        });
+    let recovered_fields = recovered.map(|c| c.fields).unwrap_or_default();
+
+    let mut diffs = Vec::new();
+    for original in &concept.fields {
+        match recovered_fields.iter().find(|f| f.name == original.name) {
+            Some(field) => {
+                let expected = lean_gen.map_topos_type(&original.field_type);
+                if lean_gen.map_topos_type(&field.field_type) != expected {
+                    diffs.push(FieldDiff {
+                        topos_name: Some(original.name.clone()),
+                        lean_name: Some(field.name.clone()),
+                        topos_type: Some(original.field_type.clone()),
+                        lean_type: Some(field.field_type.clone()),
+                        kind: FieldDiffKind::TypeMismatch,
+                    });
+                }
+            }
+            None => diffs.push(FieldDiff {
+                topos_name: Some(original.name.clone()),
+                lean_name: None,
+                topos_type: Some(original.field_type.clone()),
+                lean_type: None,
+                kind: FieldDiffKind::OnlyInTopos,
+            }),
+        }
+    }
+    for field in &recovered_fields {
+        if !concept.fields.iter().any(|f| f.name == field.name) {
+            diffs.push(FieldDiff {
+                topos_name: None,
+                lean_name: Some(field.name.clone()),
+                topos_type: None,
+                lean_type: Some(field.field_type.clone()),
+                kind: FieldDiffKind::OnlyInLean,
+            });
+        }
+    }
+    diffs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -555,6 +618,7 @@ mod tests {
             doc: Some("Represents a customer order.".to_string()),
             source_file: PathBuf::from("order.tps"),
             line: 1,
+            metadata: None,
         }
     }
 
@@ -573,6 +637,7 @@ mod tests {
             doc: Some("Creates a new order from a request.".to_string()),
             source_file: PathBuf::from("order.tps"),
             line: 10,
+            metadata: None,
         }
     }
 
@@ -699,4 +764,52 @@ mod tests {
         assert!(output.contains("structure Order where"));
         assert!(output.contains("def create_order_spec"));
     }
+
+    fn field(name: &str, field_type: &str) -> ToposField {
+        ToposField {
+            name: name.to_string(),
+            field_type: field_type.to_string(),
+            description: None,
+            constraints: vec![],
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_preserves_list_optional_and_custom_fields() {
+        let mut concept = sample_concept();
+        concept
+            .fields
+            .push(field("coupon", "optional `CouponCode`"));
+        concept.fields.push(field("notes", "optional string"));
+        concept.fields.push(field("tags", "list of optional `Tag`"));
+
+        for level in [
+            FormalizationLevel::Types,
+            FormalizationLevel::Invariants,
+            FormalizationLevel::Contracts,
+        ] {
+            assert_eq!(roundtrip_concept(&concept, level), Vec::new());
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_reports_lost_fields() {
+        let mut concept = sample_concept();
+        concept.name = "Shift".to_string();
+        concept.invariants.clear();
+        concept.fields = vec![
+            field("start", "nat"),
+            field("end", "nat"),
+            field("crew", "list of `Worker`"),
+        ];
+
+        // The Lean parser reads `end : Nat` as closing the structure, so the
+        // remaining fields are dropped on the way back.
+        let diffs = roundtrip_concept(&concept, FormalizationLevel::Types);
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs.iter().all(|d| d.kind == FieldDiffKind::OnlyInTopos));
+        assert_eq!(diffs[0].topos_name.as_deref(), Some("end"));
+        assert_eq!(diffs[1].topos_name.as_deref(), Some("crew"));
+        assert_eq!(diffs[1].topos_type.as_deref(), Some("list of `Worker`"));
+    }
 }
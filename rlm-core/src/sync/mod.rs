@@ -15,6 +15,7 @@
 //! - [`drift`]: Drift detection between Topos and Lean
 //! - [`generators`]: Code generation for both directions
 //! - [`engine`]: The main `DualTrackSync` orchestrator
+//! - [`watch`]: Watch mode that re-checks drift as files change
 //!
 //! ## Formalization Levels
 //!
@@ -87,20 +88,22 @@ pub mod drift;
 pub mod engine;
 pub mod generators;
 pub mod types;
+pub mod watch;
 
 // Re-exports for convenience
 pub use drift::{
     parse_lean_structures, parse_lean_theorems, parse_topos_behaviors, parse_topos_concepts,
-    DriftDetector,
+    parse_topos_metadata, DriftDetector,
 };
 pub use engine::DualTrackSync;
 pub use generators::{
-    lean_to_topos_behavior, lean_to_topos_concept, topos_to_lean_structure, topos_to_lean_theorem,
-    LeanGenerator, ToposGenerator,
+    lean_to_topos_behavior, lean_to_topos_concept, roundtrip_concept, topos_to_lean_structure,
+    topos_to_lean_theorem, LeanGenerator, ToposGenerator,
 };
 pub use types::{
     Drift, DriftDetails, DriftReport, DriftSummary, DriftType, FieldDiff, FieldDiffKind,
     FormalizationLevel, LeanField, LeanStructure, LeanTheorem, SuggestedAction, SyncConfig,
     SyncDirection, SyncResult, SyncSuggestion, ToposBehavior, ToposConcept, ToposField,
-    ToposInvariant, TypeMismatch,
+    ToposInvariant, ToposMetadata, TypeMismatch,
 };
+pub use watch::{DriftStream, WatchConfig};
@@ -4,6 +4,7 @@
 //! between Topos specifications and Lean formalizations.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use crate::topos::{LeanRef, ToposRef};
@@ -616,6 +617,34 @@ impl Default for SyncConfig {
     }
 }
 
+/// Metadata from a `---`-delimited YAML front-matter block at the top of a
+/// Topos file.
+///
+/// Only flat `key: value` pairs are interpreted; indented or list lines are
+/// appended to the preceding key's value, one per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToposMetadata {
+    /// Raw entries, keyed as written.
+    pub entries: BTreeMap<String, String>,
+}
+
+impl ToposMetadata {
+    /// Value of a key, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// The `owner` entry.
+    pub fn owner(&self) -> Option<&str> {
+        self.get("owner")
+    }
+
+    /// The `version` entry.
+    pub fn version(&self) -> Option<&str> {
+        self.get("version")
+    }
+}
+
 /// A parsed Topos concept for sync purposes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToposConcept {
@@ -631,6 +660,9 @@ pub struct ToposConcept {
     pub source_file: PathBuf,
     /// Line number.
     pub line: u32,
+    /// Front-matter metadata of the source file.
+    #[serde(default)]
+    pub metadata: Option<ToposMetadata>,
 }
 
 /// A field in a Topos concept.
@@ -676,6 +708,9 @@ pub struct ToposBehavior {
     pub source_file: PathBuf,
     /// Line number.
     pub line: u32,
+    /// Front-matter metadata of the source file.
+    #[serde(default)]
+    pub metadata: Option<ToposMetadata>,
 }
 
 /// A parsed Lean structure for sync purposes.
@@ -0,0 +1,317 @@
+//! Watch mode for dual-track sync.
+//!
+//! [`DualTrackSync::watch`] polls the Topos and Lean roots for changed
+//! `.tps`/`.topos`/`.lean` files and yields a fresh [`DriftReport`] after
+//! each settled batch of edits. Only the files that changed are re-parsed.
+//! A batch that fails to scan or parse yields the error instead, and
+//! watching continues with the next batch.
+//!
+//! Polling rather than OS file notifications is deliberate: the watched
+//! trees are small spec directories, so a scan of file metadata every
+//! [`WatchConfig::poll_interval`] is cheap. It also behaves the same on
+//! every platform and on filesystems where notifications are unreliable
+//! (network mounts, bind mounts into containers). It adds no dependency.
+//! The cost is up to one poll interval of extra latency, which the
+//! debounce window dwarfs anyway.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::{Duration, Instant, SystemTime};
+
+use futures::stream::{self, Stream};
+
+use super::engine::DualTrackSync;
+use super::types::DriftReport;
+use crate::error::Result;
+
+/// Stream of drift reports produced by [`DualTrackSync::watch`].
+pub type DriftStream = Pin<Box<dyn Stream<Item = Result<DriftReport>> + Send>>;
+
+/// Configuration for [`DualTrackSync::watch`].
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    /// How often the file trees are polled for changes.
+    pub poll_interval: Duration,
+    /// Quiet period required after the last change before a report is
+    /// emitted, so rapid successive saves produce a single report.
+    pub debounce: Duration,
+    /// Emit a report for the initial scan before any change is seen.
+    pub emit_initial: bool,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(250),
+            debounce: Duration::from_millis(500),
+            emit_initial: true,
+        }
+    }
+}
+
+impl WatchConfig {
+    /// Set the poll interval.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Set the debounce window.
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set whether the initial scan produces a report.
+    pub fn with_emit_initial(mut self, emit: bool) -> Self {
+        self.emit_initial = emit;
+        self
+    }
+}
+
+/// Modification time and size of each watched file.
+type Snapshot = HashMap<PathBuf, (Option<SystemTime>, u64)>;
+
+struct WatchState {
+    sync: DualTrackSync,
+    config: WatchConfig,
+    snapshot: Snapshot,
+    initial_pending: bool,
+}
+
+impl DualTrackSync {
+    /// Watch the Topos and Lean roots and yield a drift report whenever
+    /// spec files change.
+    ///
+    /// The engine is scanned once up front; afterwards each batch of changes
+    /// (debounced by [`WatchConfig::debounce`]) re-parses just the affected
+    /// files. Deleted files drop their definitions, which surfaces as
+    /// `Missing`/`Extra` drift. If the initial scan, a refresh, or drift
+    /// detection fails, that tick yields the error and the next batch of
+    /// changes is tried afresh. The stream never ends on its own; drop it to
+    /// stop watching.
+    pub fn watch(self, config: WatchConfig) -> DriftStream {
+        let state = WatchState {
+            snapshot: snapshot(self.topos_root(), self.lean_root()),
+            initial_pending: true,
+            sync: self,
+            config,
+        };
+
+        Box::pin(stream::unfold(state, |mut state| async move {
+            if state.initial_pending {
+                state.initial_pending = false;
+                let scanned = state.sync.scan().await;
+                if let Err(e) = scanned {
+                    return Some((Err(e), state));
+                }
+                if state.config.emit_initial {
+                    let report = state.sync.detect_drift().await;
+                    return Some((report, state));
+                }
+            }
+
+            let changed = state.next_changes().await;
+            if let Err(e) = state.sync.refresh_files(&changed) {
+                return Some((Err(e), state));
+            }
+            let report = state.sync.detect_drift().await;
+            Some((report, state))
+        }))
+    }
+}
+
+impl WatchState {
+    /// Wait for file changes, then for `debounce` of quiet, and return every
+    /// path touched in between.
+    async fn next_changes(&mut self) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = Vec::new();
+        let mut last_change: Option<Instant> = None;
+
+        loop {
+            tokio::time::sleep(self.config.poll_interval).await;
+
+            let current = snapshot(self.sync.topos_root(), self.sync.lean_root());
+            let diff = diff_snapshots(&self.snapshot, &current);
+            self.snapshot = current;
+
+            if !diff.is_empty() {
+                for path in diff {
+                    if !changed.contains(&path) {
+                        changed.push(path);
+                    }
+                }
+                last_change = Some(Instant::now());
+            } else if let Some(at) = last_change {
+                if at.elapsed() >= self.config.debounce {
+                    return changed;
+                }
+            }
+        }
+    }
+}
+
+/// Collect the watched files under both roots.
+fn snapshot(topos_root: &Path, lean_root: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    for (root, ext) in [
+        (topos_root, "tps"),
+        (topos_root, "topos"),
+        (lean_root, "lean"),
+    ] {
+        let pattern = root.join(format!("**/*.{}", ext));
+        let Ok(entries) = glob::glob(pattern.to_str().unwrap_or("")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if let Ok(meta) = std::fs::metadata(&entry) {
+                files.insert(entry, (meta.modified().ok(), meta.len()));
+            }
+        }
+    }
+    files
+}
+
+/// Paths added, removed, or modified between two snapshots.
+fn diff_snapshots(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(*stamp))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(before.keys().filter(|p| !after.contains_key(*p)).cloned());
+    changed.sort();
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::types::DriftType;
+    use futures::StreamExt;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_test_dirs() -> (TempDir, PathBuf, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let topos_dir = temp.path().join("topos");
+        let lean_dir = temp.path().join("lean");
+        fs::create_dir_all(&topos_dir).unwrap();
+        fs::create_dir_all(&lean_dir).unwrap();
+        (temp, topos_dir, lean_dir)
+    }
+
+    fn fast_config() -> WatchConfig {
+        WatchConfig::default()
+            .with_poll_interval(Duration::from_millis(10))
+            .with_debounce(Duration::from_millis(60))
+    }
+
+    async fn next_report(stream: &mut DriftStream) -> DriftReport {
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("watch did not emit a report")
+            .expect("watch stream ended")
+            .expect("watch tick failed")
+    }
+
+    #[tokio::test]
+    async fn test_watch_debounces_rapid_saves() {
+        let (_temp, topos_dir, lean_dir) = setup_test_dirs();
+        let mut stream = DualTrackSync::new(topos_dir.clone(), lean_dir).watch(fast_config());
+
+        let initial = next_report(&mut stream).await;
+        assert!(!initial.has_drifts());
+
+        let spec = topos_dir.join("order.tps");
+        fs::write(&spec, "Concept Order:\n  id: `nat`\n").unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        fs::write(
+            &spec,
+            "Concept Order:\n  id: `nat`\n\nConcept Customer:\n  name: `string`\n",
+        )
+        .unwrap();
+
+        fs::write(
+            &spec,
+            "Concept Order:\n  id: `nat`\n\nConcept Customer:\n  name: `string`\n\n",
+        )
+        .unwrap();
+
+        // The burst lands in one report reflecting the final contents...
+        let report = next_report(&mut stream).await;
+        assert_eq!(report.summary.missing, 2);
+
+        // ...and no further report follows without another change.
+        let extra = tokio::time::timeout(Duration::from_millis(300), stream.next()).await;
+        assert!(
+            extra.is_err(),
+            "burst of saves produced more than one report"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_deleted_file_as_missing() {
+        let (_temp, topos_dir, lean_dir) = setup_test_dirs();
+        fs::write(topos_dir.join("order.tps"), "Concept Order:\n  id: `nat`\n").unwrap();
+        let lean_file = lean_dir.join("Order.lean");
+        fs::write(&lean_file, "structure Order where\n  id : Nat\n").unwrap();
+
+        let mut stream = DualTrackSync::new(topos_dir, lean_dir).watch(fast_config());
+        let initial = next_report(&mut stream).await;
+        assert!(initial
+            .drifts
+            .iter()
+            .all(|d| !d.description.contains("no corresponding Lean structure")));
+
+        fs::remove_file(&lean_file).unwrap();
+
+        let report = next_report(&mut stream).await;
+        assert!(report
+            .drifts
+            .iter()
+            .any(|d| d.drift_type == DriftType::Missing
+                && d.description.contains("no corresponding Lean structure")));
+    }
+
+    #[tokio::test]
+    async fn test_watch_yields_refresh_errors_and_keeps_going() {
+        let (_temp, topos_dir, lean_dir) = setup_test_dirs();
+        let mut stream =
+            DualTrackSync::new(topos_dir.clone(), lean_dir.clone()).watch(fast_config());
+        next_report(&mut stream).await;
+
+        // A directory named like a spec file matches the watch but can't be read.
+        fs::create_dir(lean_dir.join("Broken.lean")).unwrap();
+        let failed = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("watch did not emit")
+            .expect("watch stream ended");
+        assert!(failed.is_err());
+
+        fs::write(topos_dir.join("order.tps"), "Concept Order:\n  id: `nat`\n").unwrap();
+        let report = next_report(&mut stream).await;
+        assert_eq!(report.summary.missing, 1);
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let mut before = Snapshot::new();
+        before.insert(PathBuf::from("a.tps"), (None, 1));
+        before.insert(PathBuf::from("b.lean"), (None, 2));
+        let mut after = before.clone();
+        after.remove(Path::new("a.tps"));
+        after.insert(PathBuf::from("b.lean"), (None, 3));
+        after.insert(PathBuf::from("c.lean"), (None, 1));
+
+        assert_eq!(
+            diff_snapshots(&before, &after),
+            vec![
+                PathBuf::from("a.tps"),
+                PathBuf::from("b.lean"),
+                PathBuf::from("c.lean")
+            ]
+        );
+    }
+}
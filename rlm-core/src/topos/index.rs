@@ -8,9 +8,11 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use super::parser::{AnnotationParser, AnnotationTarget};
 use super::types::{LeanRef, Link, LinkSource, LinkType, ToposRef};
+use super::walk::{matches_any, walk_files, IgnoreRule};
 use crate::error::{Error, Result};
 
 /// Bidirectional index of Topos-Lean links.
@@ -37,6 +39,25 @@ pub struct IndexMetadata {
     pub lean_file_count: usize,
     /// Project root path (for resolving relative paths).
     pub project_root: Option<PathBuf>,
+    /// SHA-256 of each indexed file's content, keyed by relative path.
+    #[serde(default)]
+    pub file_hashes: HashMap<PathBuf, String>,
+}
+
+impl IndexMetadata {
+    /// Hash file content the way `file_hashes` records it.
+    pub fn content_hash(content: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Check whether `path` was indexed with exactly this content.
+    pub fn is_unchanged(&self, path: &Path, content: &str) -> bool {
+        self.file_hashes
+            .get(path)
+            .is_some_and(|hash| *hash == Self::content_hash(content))
+    }
 }
 
 impl LinkIndex {
@@ -137,11 +158,14 @@ impl LinkIndex {
             .collect()
     }
 
-    /// Clear all links.
+    /// Clear all links and per-file bookkeeping.
     pub fn clear(&mut self) {
         self.topos_to_lean.clear();
         self.lean_to_topos.clear();
         self.links.clear();
+        self.metadata.file_hashes.clear();
+        self.metadata.topos_file_count = 0;
+        self.metadata.lean_file_count = 0;
     }
 
     /// Get unique Topos elements.
@@ -154,6 +178,87 @@ impl LinkIndex {
         self.lean_to_topos.keys().map(|s| s.as_str()).collect()
     }
 
+    /// Re-index a single file in place, replacing the links it declared.
+    ///
+    /// Files ending in `.lean` are parsed for `@topos` annotations; anything
+    /// else for `@lean` annotations. Links the file no longer declares are
+    /// removed. The new links are parsed before the index is touched, so a
+    /// failed update leaves the index unchanged. Returns the number of links
+    /// the file now declares.
+    pub fn update_file(&mut self, path: &Path, content: &str) -> Result<usize> {
+        let is_lean = path.extension().is_some_and(|ext| ext == "lean");
+
+        let mut staged = LinkIndex::new();
+        let count = if is_lean {
+            staged.index_lean_file(path, content)?
+        } else {
+            staged.index_topos_file(path, content)?
+        };
+
+        let is_new = !self.metadata.file_hashes.contains_key(path);
+        self.replace_file_links(path, is_lean, staged.links);
+
+        if is_new {
+            if is_lean {
+                self.metadata.lean_file_count += 1;
+            } else {
+                self.metadata.topos_file_count += 1;
+            }
+        }
+        self.metadata
+            .file_hashes
+            .insert(path.to_path_buf(), IndexMetadata::content_hash(content));
+        self.touch();
+        Ok(count)
+    }
+
+    /// Drop every link declared by `path`, e.g. after the file was deleted.
+    ///
+    /// Returns `true` if the file was indexed.
+    pub fn remove_file(&mut self, path: &Path) -> bool {
+        let is_lean = path.extension().is_some_and(|ext| ext == "lean");
+        self.replace_file_links(path, is_lean, Vec::new());
+
+        let was_indexed = self.metadata.file_hashes.remove(path).is_some();
+        if was_indexed {
+            let count = if is_lean {
+                &mut self.metadata.lean_file_count
+            } else {
+                &mut self.metadata.topos_file_count
+            };
+            *count = count.saturating_sub(1);
+        }
+        self.touch();
+        was_indexed
+    }
+
+    /// Swap the links declared by one file for `new_links`.
+    ///
+    /// Builds the replacement link list and lookup maps first and assigns
+    /// them together, so the index never holds a partial update.
+    fn replace_file_links(&mut self, path: &Path, is_lean: bool, new_links: Vec<Link>) {
+        let declared_here = |link: &Link| match link.source {
+            LinkSource::Lean => is_lean && link.lean.file == path,
+            LinkSource::Topos => !is_lean && link.topos.file == path,
+            LinkSource::Synthesized => false,
+        };
+
+        let mut rebuilt = LinkIndex::new();
+        for link in self
+            .links
+            .iter()
+            .filter(|link| !declared_here(link))
+            .cloned()
+            .chain(new_links)
+        {
+            rebuilt.add_link(link);
+        }
+
+        self.topos_to_lean = rebuilt.topos_to_lean;
+        self.lean_to_topos = rebuilt.lean_to_topos;
+        self.links = rebuilt.links;
+    }
+
     /// Index a Topos file by parsing its @lean annotations.
     pub fn index_topos_file(&mut self, path: &Path, content: &str) -> Result<usize> {
         let annotations = AnnotationParser::parse_lean_annotations(content);
@@ -356,6 +461,9 @@ pub struct IndexBuilder {
     project_root: PathBuf,
     topos_patterns: Vec<String>,
     lean_patterns: Vec<String>,
+    include_globs: Vec<String>,
+    ignore_globs: Vec<String>,
+    respect_gitignore: bool,
 }
 
 impl IndexBuilder {
@@ -365,6 +473,9 @@ impl IndexBuilder {
             project_root: project_root.into(),
             topos_patterns: vec!["**/*.tps".to_string(), "**/*.topos".to_string()],
             lean_patterns: vec!["**/*.lean".to_string()],
+            include_globs: Vec::new(),
+            ignore_globs: Vec::new(),
+            respect_gitignore: true,
         }
     }
 
@@ -380,41 +491,123 @@ impl IndexBuilder {
         self
     }
 
+    /// Only index files matching at least one of these globs (relative to
+    /// the project root, e.g. `specs/**`).
+    pub fn with_include_globs(mut self, globs: Vec<String>) -> Self {
+        self.include_globs = globs;
+        self
+    }
+
+    /// Skip paths matching these gitignore-style patterns (e.g. `build/`,
+    /// `/vendor`, `*.gen.lean`, `!keep.lean`).
+    pub fn with_ignore_globs(mut self, globs: Vec<String>) -> Self {
+        self.ignore_globs = globs;
+        self
+    }
+
+    /// Whether `.gitignore` files in the project are honored (default: true).
+    pub fn respect_gitignore(mut self, respect: bool) -> Self {
+        self.respect_gitignore = respect;
+        self
+    }
+
     /// Build the index by scanning all files.
     pub fn build(self) -> Result<LinkIndex> {
         let mut index = LinkIndex::with_project_root(&self.project_root);
+        let walked = self.walk();
 
         // Find and index Topos files
-        for pattern in &self.topos_patterns {
-            let full_pattern = self.project_root.join(pattern);
-            if let Ok(entries) = glob::glob(full_pattern.to_str().unwrap_or("")) {
-                for entry in entries.flatten() {
-                    if let Ok(content) = fs::read_to_string(&entry) {
-                        // Use relative path
-                        let rel_path = entry.strip_prefix(&self.project_root).unwrap_or(&entry);
-                        let _ = index.index_topos_file(rel_path, &content);
-                    }
-                }
-            }
+        for (rel_path, content) in self.files(&walked, &self.topos_patterns) {
+            let _ = index.index_topos_file(&rel_path, &content);
+            index
+                .metadata
+                .file_hashes
+                .insert(rel_path, IndexMetadata::content_hash(&content));
         }
 
         // Find and index Lean files
-        for pattern in &self.lean_patterns {
-            let full_pattern = self.project_root.join(pattern);
-            if let Ok(entries) = glob::glob(full_pattern.to_str().unwrap_or("")) {
-                for entry in entries.flatten() {
-                    if let Ok(content) = fs::read_to_string(&entry) {
-                        // Use relative path
-                        let rel_path = entry.strip_prefix(&self.project_root).unwrap_or(&entry);
-                        let _ = index.index_lean_file(rel_path, &content);
-                    }
-                }
-            }
+        for (rel_path, content) in self.files(&walked, &self.lean_patterns) {
+            let _ = index.index_lean_file(&rel_path, &content);
+            index
+                .metadata
+                .file_hashes
+                .insert(rel_path, IndexMetadata::content_hash(&content));
         }
 
         index.touch();
         Ok(index)
     }
+
+    /// Rebuild `previous`, re-parsing only files whose content hash changed
+    /// and dropping links from files that no longer exist.
+    pub fn build_incremental(self, mut previous: LinkIndex) -> Result<LinkIndex> {
+        let mut seen = std::collections::HashSet::new();
+        let walked = self.walk();
+
+        for (rel_path, content) in self
+            .files(&walked, &self.topos_patterns)
+            .into_iter()
+            .chain(self.files(&walked, &self.lean_patterns))
+        {
+            if !previous.metadata.is_unchanged(&rel_path, &content) {
+                let _ = previous.update_file(&rel_path, &content);
+            }
+            seen.insert(rel_path);
+        }
+
+        let removed: Vec<PathBuf> = previous
+            .metadata
+            .file_hashes
+            .keys()
+            .filter(|path| !seen.contains(*path))
+            .cloned()
+            .collect();
+        for path in removed {
+            previous.remove_file(&path);
+        }
+
+        previous.metadata.project_root = Some(self.project_root.clone());
+        previous.touch();
+        Ok(previous)
+    }
+
+    /// Walk the project, applying include and ignore rules.
+    ///
+    /// Returns paths relative to the project root.
+    fn walk(&self) -> Vec<PathBuf> {
+        let root = Path::new("");
+        let rules: Vec<IgnoreRule> = self
+            .ignore_globs
+            .iter()
+            .filter_map(|glob| IgnoreRule::parse(glob, root))
+            .collect();
+        let includes = compile_globs(&self.include_globs);
+
+        walk_files(&self.project_root, &rules, self.respect_gitignore)
+            .into_iter()
+            .filter(|path| includes.is_empty() || matches_any(&includes, path))
+            .collect()
+    }
+
+    /// Read each walked file matching `patterns`.
+    fn files(&self, walked: &[PathBuf], patterns: &[String]) -> Vec<(PathBuf, String)> {
+        let patterns = compile_globs(patterns);
+        walked
+            .iter()
+            .filter(|path| matches_any(&patterns, path))
+            .filter_map(|path| {
+                let content = fs::read_to_string(self.project_root.join(path)).ok()?;
+                Some((path.clone(), content))
+            })
+            .collect()
+    }
+}
+
+fn compile_globs(globs: &[String]) -> Vec<glob::Pattern> {
+    globs
+        .iter()
+        .filter_map(|glob| glob::Pattern::new(glob).ok())
+        .collect()
 }
 
 #[cfg(test)]
@@ -464,6 +657,167 @@ Behavior create_order:
         assert_eq!(index.len(), 2);
     }
 
+    #[test]
+    fn test_update_file_tracks_annotation_changes() {
+        let mut index = LinkIndex::new();
+        let path = Path::new("orders.tps");
+        let order = ToposRef::new(path, "Order");
+        let customer = ToposRef::new(path, "Customer");
+
+        // Add an annotation
+        let v1 = "Concept Order:\n  id: `OrderId`\n  @lean: Order.lean#Order\n";
+        assert_eq!(index.update_file(path, v1).unwrap(), 1);
+        assert_eq!(index.get_lean_refs(&order)[0].lean.artifact, "Order");
+        assert!(index.metadata().is_unchanged(path, v1));
+
+        // Modify it and add a second one
+        let v2 = "Concept Order:\n  id: `OrderId`\n  @lean: Order.lean#OrderV2\n\n\
+                  Concept Customer:\n  name: `string`\n  @lean: Customer.lean#Customer\n";
+        assert_eq!(index.update_file(path, v2).unwrap(), 2);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get_lean_refs(&order)[0].lean.artifact, "OrderV2");
+        assert!(index
+            .get_topos_refs(&LeanRef::new("Order.lean", "Order"))
+            .is_empty());
+
+        // Drop the Order annotation
+        let v3 = "Concept Order:\n  id: `OrderId`\n\n\
+                  Concept Customer:\n  name: `string`\n  @lean: Customer.lean#Customer\n";
+        assert_eq!(index.update_file(path, v3).unwrap(), 1);
+        assert_eq!(index.len(), 1);
+        assert!(index.get_lean_refs(&order).is_empty());
+        assert_eq!(index.get_lean_refs(&customer).len(), 1);
+        assert_eq!(index.metadata().topos_file_count, 1);
+
+        assert!(index.remove_file(path));
+        assert!(index.is_empty());
+        assert_eq!(index.metadata().topos_file_count, 0);
+    }
+
+    #[test]
+    fn test_update_file_keeps_links_from_other_files() {
+        let mut index = LinkIndex::new();
+        index
+            .update_file(
+                Path::new("a.tps"),
+                "Concept A:\n  id: `nat`\n  @lean: A.lean#A\n",
+            )
+            .unwrap();
+        index
+            .update_file(
+                Path::new("A.lean"),
+                "/-- @topos: a.tps#A -/\nstructure A where\n  id : Nat\n",
+            )
+            .unwrap();
+        assert_eq!(index.len(), 2);
+
+        index
+            .update_file(Path::new("a.tps"), "Concept A:\n  id: `nat`\n")
+            .unwrap();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.all_links()[0].source, LinkSource::Lean);
+    }
+
+    #[test]
+    fn test_build_incremental() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("a.tps"),
+            "Concept A:\n  id: `nat`\n  @lean: A.lean#A\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("b.tps"),
+            "Concept B:\n  id: `nat`\n  @lean: B.lean#B\n",
+        )
+        .unwrap();
+
+        let index = IndexBuilder::new(root).build().unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.metadata().file_hashes.len(), 2);
+
+        fs::remove_file(root.join("b.tps")).unwrap();
+        fs::write(
+            root.join("a.tps"),
+            "Concept A:\n  id: `nat`\n  @lean: A.lean#A2\n",
+        )
+        .unwrap();
+
+        let index = IndexBuilder::new(root).build_incremental(index).unwrap();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.all_links()[0].lean.artifact, "A2");
+        assert_eq!(index.metadata().file_hashes.len(), 1);
+        assert_eq!(index.metadata().topos_file_count, 1);
+    }
+
+    fn write_spec(root: &Path, rel: &str, artifact: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            path,
+            format!(
+                "Concept {0}:\n  id: `nat`\n  @lean: {0}.lean#{0}\n",
+                artifact
+            ),
+        )
+        .unwrap();
+    }
+
+    fn indexed_files(index: &LinkIndex) -> Vec<String> {
+        let mut files: Vec<String> = index
+            .all_links()
+            .iter()
+            .map(|link| link.topos.file.display().to_string())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_builder_respects_ignore_rules() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write_spec(root, "specs/Order.tps", "Order");
+        write_spec(root, "build/Generated.tps", "Generated");
+        write_spec(root, "vendor/Lib.tps", "Lib");
+        fs::write(root.join(".gitignore"), "build/\n").unwrap();
+
+        let index = IndexBuilder::new(root)
+            .with_ignore_globs(vec!["/vendor".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(indexed_files(&index), vec!["specs/Order.tps"]);
+
+        let index = IndexBuilder::new(root)
+            .respect_gitignore(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            indexed_files(&index),
+            vec!["build/Generated.tps", "specs/Order.tps", "vendor/Lib.tps"]
+        );
+
+        let index = IndexBuilder::new(root)
+            .respect_gitignore(false)
+            .with_include_globs(vec!["vendor/**".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(indexed_files(&index), vec!["vendor/Lib.tps"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_builder_survives_symlink_loop() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        write_spec(root, "specs/Order.tps", "Order");
+        std::os::unix::fs::symlink(root.join("specs"), root.join("specs/loop")).unwrap();
+
+        let index = IndexBuilder::new(root).build().unwrap();
+        assert_eq!(indexed_files(&index), vec!["specs/Order.tps"]);
+    }
+
     #[test]
     fn test_index_lean_file() {
         let mut index = LinkIndex::new();
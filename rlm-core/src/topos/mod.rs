@@ -65,6 +65,7 @@ pub mod client;
 pub mod index;
 pub mod parser;
 pub mod types;
+mod walk;
 
 // Re-exports for convenience
 pub use client::{
@@ -0,0 +1,219 @@
+//! Directory walking for index builds.
+//!
+//! Walks a project tree applying include globs and gitignore-style ignore
+//! rules. Symlinked directories are followed, but each real directory is
+//! visited once so symlink loops terminate.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// One gitignore-style rule.
+#[derive(Debug, Clone)]
+pub(crate) struct IgnoreRule {
+    pattern: Pattern,
+    /// `!pattern`: re-include a previously ignored path.
+    negated: bool,
+    /// `pattern/`: only matches directories.
+    dir_only: bool,
+    /// Pattern contains a `/`, so it matches the path from `base` rather
+    /// than just the file name.
+    anchored: bool,
+    /// Directory (relative to the walk root) the rule was declared in.
+    base: PathBuf,
+}
+
+impl IgnoreRule {
+    /// Parse one line of gitignore syntax. Blank lines and comments yield `None`.
+    pub(crate) fn parse(line: &str, base: &Path) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+        let pattern = Pattern::new(line).ok()?;
+
+        Some(Self {
+            pattern,
+            negated,
+            dir_only,
+            anchored,
+            base: base.to_path_buf(),
+        })
+    }
+
+    /// Whether the rule applies to `rel_path` (relative to the walk root).
+    fn matches(&self, rel_path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Ok(local) = rel_path.strip_prefix(&self.base) else {
+            return false;
+        };
+        if self.anchored {
+            self.pattern.matches_path_with(local, MATCH_OPTIONS)
+        } else {
+            local.file_name().is_some_and(|name| {
+                self.pattern
+                    .matches_with(&name.to_string_lossy(), MATCH_OPTIONS)
+            })
+        }
+    }
+}
+
+/// Whether the last matching rule ignores `rel_path`.
+fn is_ignored(rules: &[IgnoreRule], rel_path: &Path, is_dir: bool) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(rel_path, is_dir))
+        .is_some_and(|rule| !rule.negated)
+}
+
+/// Read `.gitignore` rules declared in `dir`.
+fn gitignore_rules(dir: &Path, rel_dir: &Path) -> Vec<IgnoreRule> {
+    fs::read_to_string(dir.join(".gitignore"))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| IgnoreRule::parse(line, rel_dir))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Walk `root`, returning files (relative to `root`) that are not ignored.
+///
+/// `rules` apply from the root; when `use_gitignore` is set, `.gitignore`
+/// files found along the way add rules scoped to their directory. Ignored
+/// directories are not descended into.
+pub(crate) fn walk_files(root: &Path, rules: &[IgnoreRule], use_gitignore: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut visited = HashSet::new();
+    walk_dir(
+        root,
+        Path::new(""),
+        rules.to_vec(),
+        use_gitignore,
+        &mut visited,
+        &mut files,
+    );
+    files.sort();
+    files
+}
+
+fn walk_dir(
+    dir: &Path,
+    rel_dir: &Path,
+    mut rules: Vec<IgnoreRule>,
+    use_gitignore: bool,
+    visited: &mut HashSet<PathBuf>,
+    files: &mut Vec<PathBuf>,
+) {
+    // Canonical paths break symlink cycles.
+    let Ok(canonical) = fs::canonicalize(dir) else {
+        return;
+    };
+    if !visited.insert(canonical) {
+        return;
+    }
+
+    if use_gitignore {
+        rules.extend(gitignore_rules(dir, rel_dir));
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name();
+        if name == ".git" {
+            continue;
+        }
+        let path = entry.path();
+        let rel_path = rel_dir.join(&name);
+        // `metadata` follows symlinks, so linked directories are walked too.
+        let Ok(meta) = fs::metadata(&path) else {
+            continue;
+        };
+
+        if is_ignored(&rules, &rel_path, meta.is_dir()) {
+            continue;
+        }
+        if meta.is_dir() {
+            walk_dir(
+                &path,
+                &rel_path,
+                rules.clone(),
+                use_gitignore,
+                visited,
+                files,
+            );
+        } else if meta.is_file() {
+            files.push(rel_path);
+        }
+    }
+}
+
+/// Whether `rel_path` matches any of `patterns`.
+pub(crate) fn matches_any(patterns: &[Pattern], rel_path: &Path) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| pattern.matches_path_with(rel_path, MATCH_OPTIONS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(line: &str) -> IgnoreRule {
+        IgnoreRule::parse(line, Path::new("")).unwrap()
+    }
+
+    #[test]
+    fn test_ignore_rule_matching() {
+        let rules = vec![rule("build/"), rule("*.gen.lean"), rule("!keep.gen.lean")];
+
+        assert!(is_ignored(&rules, Path::new("build"), true));
+        assert!(is_ignored(&rules, Path::new("sub/build"), true));
+        assert!(!is_ignored(&rules, Path::new("build"), false));
+        assert!(is_ignored(&rules, Path::new("src/Foo.gen.lean"), false));
+        assert!(!is_ignored(&rules, Path::new("src/keep.gen.lean"), false));
+        assert!(!is_ignored(&rules, Path::new("src/Foo.lean"), false));
+    }
+
+    #[test]
+    fn test_anchored_rule() {
+        let rules = vec![rule("/vendor/specs")];
+
+        assert!(is_ignored(&rules, Path::new("vendor/specs"), true));
+        assert!(!is_ignored(&rules, Path::new("lib/vendor/specs"), true));
+    }
+
+    #[test]
+    fn test_comments_and_blanks_skipped() {
+        assert!(IgnoreRule::parse("# comment", Path::new("")).is_none());
+        assert!(IgnoreRule::parse("   ", Path::new("")).is_none());
+    }
+}
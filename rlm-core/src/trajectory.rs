@@ -15,7 +15,7 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use tokio::sync::broadcast;
 
@@ -74,6 +74,10 @@ pub enum TrajectoryEventType {
     IssueFound,
     /// Adversarial validation complete
     AdversarialComplete,
+    /// Orchestration stopped because projected spend exceeded the budget
+    BudgetExhausted,
+    /// A recursive sub-call was refused because it repeated an earlier state
+    CycleDetected,
 }
 
 impl std::fmt::Display for TrajectoryEventType {
@@ -104,6 +108,8 @@ impl std::fmt::Display for TrajectoryEventType {
             Self::CriticInvoked => "CRITIC_INVOKED",
             Self::IssueFound => "ISSUE_FOUND",
             Self::AdversarialComplete => "ADVERSARIAL_COMPLETE",
+            Self::BudgetExhausted => "BUDGET_EXHAUSTED",
+            Self::CycleDetected => "CYCLE_DETECTED",
         };
         write!(f, "{}", s)
     }
@@ -290,6 +296,39 @@ impl TrajectoryEvent {
         .with_metadata("cost_usd", cost_usd)
     }
 
+    /// Create a budget exhaustion event.
+    pub fn budget_exhausted(
+        depth: u32,
+        spent_usd: f64,
+        projected_usd: f64,
+        budget_usd: f64,
+    ) -> Self {
+        Self::new(
+            TrajectoryEventType::BudgetExhausted,
+            depth,
+            format!(
+                "Budget exhausted: ${:.4} spent, ${:.4} projected, ${:.4} budget",
+                spent_usd, projected_usd, budget_usd
+            ),
+        )
+        .with_metadata("spent_usd", spent_usd)
+        .with_metadata("projected_usd", projected_usd)
+        .with_metadata("budget_usd", budget_usd)
+    }
+
+    /// Create an event for a refused, repeated sub-call.
+    pub fn cycle_detected(depth: u32, query: &str) -> Self {
+        Self::new(
+            TrajectoryEventType::CycleDetected,
+            depth,
+            format!(
+                "Cycle detected: sub-query repeated at depth {}: {}",
+                depth, query
+            ),
+        )
+        .with_metadata("query", query)
+    }
+
     /// Check if this is an error event.
     pub fn is_error(&self) -> bool {
         self.event_type == TrajectoryEventType::Error
@@ -412,6 +451,8 @@ pub enum ExportFormat {
     JsonCompact,
     /// Markdown summary
     Markdown,
+    /// Mermaid sequence diagram
+    MermaidSequence,
 }
 
 /// Serialize a list of events to the specified format.
@@ -429,6 +470,7 @@ pub fn export_events(events: &[TrajectoryEvent], format: ExportFormat) -> String
             serde_json::to_string(events).unwrap_or_else(|_| "[]".to_string())
         }
         ExportFormat::Markdown => events_to_markdown(events),
+        ExportFormat::MermaidSequence => trajectory_to_mermaid_sequence(events),
     }
 }
 
@@ -450,6 +492,155 @@ fn events_to_markdown(events: &[TrajectoryEvent]) -> String {
     md
 }
 
+/// Longest message label in a sequence diagram, in characters.
+pub const MERMAID_LABEL_MAX_CHARS: usize = 60;
+
+/// Render events as a Mermaid sequence diagram, one message per event.
+///
+/// Participants are the orchestrator, each model (named by the event's
+/// `tier` or `model` metadata), the REPL, memory, and external tools, in
+/// order of first appearance. Labels carry the offset from the first event.
+/// Recursive sub-calls open an activation box that the matching
+/// `RecurseEnd` closes, so nested recursion shows as stacked boxes.
+pub fn trajectory_to_mermaid_sequence(events: &[TrajectoryEvent]) -> String {
+    let mut participants: Vec<(String, String)> = vec![("O".into(), "Orchestrator".into())];
+    let mut messages = Vec::with_capacity(events.len());
+    let mut active: Vec<String> = Vec::new();
+    let start = events.first().map(|e| e.timestamp);
+
+    for event in events {
+        let model = event
+            .get_metadata("tier")
+            .or_else(|| event.get_metadata("model"))
+            .and_then(Value::as_str)
+            .map(|name| {
+                (
+                    format!("M_{}", mermaid_id(name)),
+                    format!("Model ({})", name),
+                )
+            })
+            .unwrap_or_else(|| ("M".into(), "Model".into()));
+        let repl = ("R".to_string(), "REPL".to_string());
+        let memory = ("Mem".to_string(), "Memory".to_string());
+        let tools = ("T".to_string(), "Tools".to_string());
+        let orchestrator = ("O".to_string(), "Orchestrator".to_string());
+
+        use TrajectoryEventType as T;
+        let (from, to, arrow) = match event.event_type {
+            T::ReplExec | T::Externalize => (orchestrator, repl, "->>"),
+            T::ReplResult => (repl, orchestrator, "-->>"),
+            T::Memory => (orchestrator, memory, "->>"),
+            T::ToolUse => (orchestrator, tools, "->>"),
+            T::Analyze | T::Decompose | T::VerifyStart | T::AdversarialStart | T::CriticInvoked => {
+                (orchestrator, model, "->>")
+            }
+            T::Reason
+            | T::Synthesize
+            | T::Final
+            | T::ClaimExtracted
+            | T::EvidenceChecked
+            | T::HallucinationFlag
+            | T::IssueFound => (model, orchestrator, "-->>"),
+            T::RecurseStart => {
+                // Sub-calls without a model run on the orchestrator's own lifeline
+                let callee = if event.get_metadata("tier").is_some()
+                    || event.get_metadata("model").is_some()
+                {
+                    model
+                } else {
+                    orchestrator.clone()
+                };
+                active.push(callee.0.clone());
+                (orchestrator, callee, "->>+")
+            }
+            T::RecurseEnd => match active.pop() {
+                Some(id) => {
+                    let callee = participants
+                        .iter()
+                        .find(|(pid, _)| *pid == id)
+                        .cloned()
+                        .unwrap_or_else(|| orchestrator.clone());
+                    (callee, orchestrator, "-->>-")
+                }
+                None => (orchestrator.clone(), orchestrator, "-->>"),
+            },
+            T::RlmStart
+            | T::Error
+            | T::CostReport
+            | T::BudgetComputed
+            | T::VerifyComplete
+            | T::AdversarialComplete
+            | T::BudgetExhausted
+            | T::CycleDetected => (orchestrator.clone(), orchestrator, "->>"),
+        };
+
+        for participant in [&from, &to] {
+            if !participants.iter().any(|(id, _)| *id == participant.0) {
+                participants.push(participant.clone());
+            }
+        }
+
+        let offset = start
+            .map(|s| (event.timestamp - s).num_milliseconds() as f64 / 1000.0)
+            .unwrap_or(0.0);
+        let text = if event.content.is_empty() {
+            event.event_type.to_string()
+        } else {
+            format!("{}: {}", event.event_type, event.content)
+        };
+        messages.push(format!(
+            "    {}{}{}: +{:.2}s {}",
+            from.0,
+            arrow,
+            to.0,
+            offset,
+            mermaid_label(&text)
+        ));
+    }
+
+    let mut out = String::from("sequenceDiagram\n");
+    for (id, name) in &participants {
+        out.push_str(&format!("    participant {} as {}\n", id, name));
+    }
+    for message in messages {
+        out.push_str(&message);
+        out.push('\n');
+    }
+    out
+}
+
+/// Participant ID from a free-form name.
+fn mermaid_id(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Single-line message label, capped at [`MERMAID_LABEL_MAX_CHARS`] and with
+/// characters Mermaid treats as syntax replaced by entity codes.
+fn mermaid_label(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let capped = if flat.chars().count() > MERMAID_LABEL_MAX_CHARS {
+        let mut cut: String = flat.chars().take(MERMAID_LABEL_MAX_CHARS - 1).collect();
+        cut.push('…');
+        cut
+    } else {
+        flat
+    };
+
+    let mut label = String::with_capacity(capped.len());
+    for c in capped.chars() {
+        match c {
+            '#' => label.push_str("#35;"),
+            ';' => label.push_str("#59;"),
+            '<' => label.push_str("#lt;"),
+            '>' => label.push_str("#gt;"),
+            _ => label.push(c),
+        }
+    }
+    label
+}
+
 // =============================================================================
 // Model Pricing (Jan 2026)
 // =============================================================================
@@ -799,7 +990,11 @@ impl TrajectoryEventType {
     pub fn min_verbosity(&self) -> Verbosity {
         match self {
             // Always show
-            Self::Error | Self::Final | Self::CostReport => Verbosity::Minimal,
+            Self::Error
+            | Self::Final
+            | Self::CostReport
+            | Self::BudgetExhausted
+            | Self::CycleDetected => Verbosity::Minimal,
             // Normal operation
             Self::RlmStart
             | Self::Analyze
@@ -871,6 +1066,11 @@ impl BroadcastEmitter {
         self.sender.subscribe()
     }
 
+    /// Subscribe to the trajectory events that match `filter`.
+    pub fn subscribe_filtered(&self, filter: TrajectoryFilter) -> FilteredReceiver {
+        FilteredReceiver::new(self.sender.subscribe(), filter)
+    }
+
     /// Get number of active subscribers.
     pub fn subscriber_count(&self) -> usize {
         self.sender.receiver_count()
@@ -967,6 +1167,130 @@ impl TrajectoryEmitter for CollectingEmitter {
     }
 }
 
+/// A single condition on a trajectory event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum TrajectoryPredicate {
+    /// Event type is one of these
+    EventTypes(HashSet<TrajectoryEventType>),
+    /// Recursion depth is at least this
+    MinDepth(u32),
+    /// Event is (or is not) a final answer
+    IsFinal(bool),
+}
+
+impl TrajectoryPredicate {
+    /// Check an event against this condition.
+    pub fn matches(&self, event: &TrajectoryEvent) -> bool {
+        match self {
+            Self::EventTypes(types) => types.contains(&event.event_type),
+            Self::MinDepth(depth) => event.depth >= *depth,
+            Self::IsFinal(is_final) => event.is_final() == *is_final,
+        }
+    }
+}
+
+/// Selects the trajectory events a consumer cares about.
+///
+/// A filter is the AND of its predicates, so an empty filter passes every
+/// event and [`and`](Self::and) narrows one filter by another.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrajectoryFilter {
+    predicates: Vec<TrajectoryPredicate>,
+}
+
+impl TrajectoryFilter {
+    /// Create a filter that passes every event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filter passing only final answers.
+    pub fn final_only() -> Self {
+        Self::new().is_final(true)
+    }
+
+    /// Require one of these event types.
+    pub fn event_types(self, types: impl IntoIterator<Item = TrajectoryEventType>) -> Self {
+        self.with(TrajectoryPredicate::EventTypes(types.into_iter().collect()))
+    }
+
+    /// Require at least this recursion depth.
+    pub fn min_depth(self, depth: u32) -> Self {
+        self.with(TrajectoryPredicate::MinDepth(depth))
+    }
+
+    /// Require the event to be (or not be) a final answer.
+    pub fn is_final(self, is_final: bool) -> Self {
+        self.with(TrajectoryPredicate::IsFinal(is_final))
+    }
+
+    /// Add a predicate.
+    pub fn with(mut self, predicate: TrajectoryPredicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Require both this filter and `other` to match.
+    pub fn and(mut self, other: TrajectoryFilter) -> Self {
+        self.predicates.extend(other.predicates);
+        self
+    }
+
+    /// The predicates, all of which must hold.
+    pub fn predicates(&self) -> &[TrajectoryPredicate] {
+        &self.predicates
+    }
+
+    /// Check an event against every predicate.
+    pub fn matches(&self, event: &TrajectoryEvent) -> bool {
+        self.predicates.iter().all(|p| p.matches(event))
+    }
+
+    /// Keep only the matching events of an iterator.
+    pub fn apply<'a, I>(&'a self, events: I) -> impl Iterator<Item = TrajectoryEvent> + 'a
+    where
+        I: IntoIterator<Item = TrajectoryEvent>,
+        I::IntoIter: 'a,
+    {
+        events.into_iter().filter(move |e| self.matches(e))
+    }
+}
+
+/// Broadcast receiver that skips events a filter rejects.
+#[derive(Debug)]
+pub struct FilteredReceiver {
+    receiver: broadcast::Receiver<TrajectoryEvent>,
+    filter: TrajectoryFilter,
+}
+
+impl FilteredReceiver {
+    /// Wrap a receiver.
+    pub fn new(receiver: broadcast::Receiver<TrajectoryEvent>, filter: TrajectoryFilter) -> Self {
+        Self { receiver, filter }
+    }
+
+    /// Receive the next matching event.
+    pub async fn recv(&mut self) -> Result<TrajectoryEvent, broadcast::error::RecvError> {
+        loop {
+            let event = self.receiver.recv().await?;
+            if self.filter.matches(&event) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Receive a matching event if one is already queued.
+    pub fn try_recv(&mut self) -> Result<TrajectoryEvent, broadcast::error::TryRecvError> {
+        loop {
+            let event = self.receiver.try_recv()?;
+            if self.filter.matches(&event) {
+                return Ok(event);
+            }
+        }
+    }
+}
+
 /// Null emitter that discards all events.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct NullEmitter;
@@ -1033,6 +1357,50 @@ mod tests {
         assert_eq!(lines.len(), 2);
     }
 
+    #[test]
+    fn test_mermaid_sequence_export() {
+        let start = Utc::now();
+        let at = |ms| start + Duration::milliseconds(ms);
+        let mut events = vec![
+            TrajectoryEvent::rlm_start("Why is the build slow?"),
+            TrajectoryEvent::repl_exec(0, "timings = profile(build)"),
+            TrajectoryEvent::repl_result(0, "link step: 41s", true),
+            TrajectoryEvent::recurse_start(1, "Summarize the link step")
+                .with_metadata("tier", "fast"),
+            TrajectoryEvent::recurse_start(2, "Check LTO settings"),
+            TrajectoryEvent::recurse_end(2, "LTO is fat"),
+            TrajectoryEvent::recurse_end(
+                1,
+                "Linking dominates; thin LTO would help a lot more than anything",
+            ),
+            TrajectoryEvent::final_answer(0, "Switch to thin LTO; see #42"),
+        ];
+        for (i, event) in events.iter_mut().enumerate() {
+            event.timestamp = at(i as i64 * 500);
+        }
+
+        let diagram = trajectory_to_mermaid_sequence(&events);
+        let lines: Vec<_> = diagram.lines().collect();
+        assert_eq!(lines[0], "sequenceDiagram");
+        assert!(diagram.contains("participant R as REPL"));
+        assert!(diagram.contains("participant M_fast as Model (fast)"));
+
+        let messages: Vec<_> = lines.iter().filter(|l| l.contains(": +")).collect();
+        assert_eq!(messages.len(), events.len());
+        assert!(messages[1].starts_with("    O->>R: +0.50s REPL_EXEC"));
+        assert!(messages[3].starts_with("    O->>+M_fast: +1.50s"));
+        assert!(messages[4].starts_with("    O->>+O:"));
+        assert!(messages[5].starts_with("    O-->>-O:"));
+        assert!(messages[6].starts_with("    M_fast-->>-O:"));
+        assert!(messages[6].ends_with('…'));
+        assert!(messages[7].contains("thin LTO#59; see #35;42"));
+
+        assert_eq!(
+            export_events(&events, ExportFormat::MermaidSequence),
+            diagram
+        );
+    }
+
     // =========================================================================
     // Model Pricing Tests
     // =========================================================================
@@ -1201,6 +1569,45 @@ mod tests {
         assert_eq!(emitter.verbosity(), Verbosity::Minimal);
     }
 
+    #[test]
+    fn test_trajectory_filter_drops_other_types() {
+        let events = vec![
+            TrajectoryEvent::rlm_start("q"),
+            TrajectoryEvent::repl_exec(0, "x = 1"),
+            TrajectoryEvent::recurse_start(1, "sub"),
+            TrajectoryEvent::repl_exec(2, "y = 2"),
+            TrajectoryEvent::final_answer(0, "done"),
+        ];
+
+        let repl = TrajectoryFilter::new().event_types([TrajectoryEventType::ReplExec]);
+        let kept: Vec<_> = repl.apply(events.clone()).collect();
+        assert_eq!(kept.len(), 2);
+        assert!(kept
+            .iter()
+            .all(|e| e.event_type == TrajectoryEventType::ReplExec));
+
+        let deep_repl = repl.clone().and(TrajectoryFilter::new().min_depth(1));
+        let kept: Vec<_> = deep_repl.apply(events.clone()).collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].content, "y = 2");
+
+        assert_eq!(TrajectoryFilter::new().apply(events.clone()).count(), 5);
+        assert_eq!(TrajectoryFilter::final_only().apply(events).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_filtered_receiver() {
+        let emitter = BroadcastEmitter::new(16);
+        let mut rx = emitter.subscribe_filtered(TrajectoryFilter::final_only());
+
+        emitter.emit(TrajectoryEvent::rlm_start("q"));
+        emitter.emit(TrajectoryEvent::analyze(0, "simple"));
+        emitter.emit(TrajectoryEvent::final_answer(0, "done"));
+
+        assert_eq!(rx.recv().await.unwrap().content, "done");
+        assert!(rx.try_recv().is_err());
+    }
+
     #[test]
     fn test_broadcast_emitter_creation() {
         let emitter = BroadcastEmitter::new(100);
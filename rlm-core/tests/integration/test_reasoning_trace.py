@@ -0,0 +1,92 @@
+"""
+Integration tests for the reasoning trace Python bindings.
+
+Run with: pytest tests/integration/test_reasoning_trace.py -v
+"""
+
+import json
+
+import pytest
+
+
+@pytest.fixture(scope="module")
+def rlm_core():
+    """Import rlm_core, failing with build instructions if unavailable."""
+    try:
+        import rlm_core
+    except ImportError:
+        pytest.fail(
+            "rlm_core import failed. Build/install with: maturin develop --features full"
+        )
+    return rlm_core
+
+
+@pytest.fixture
+def trace(rlm_core):
+    trace = rlm_core.ReasoningTrace("Implement caching", "session-1")
+    chosen = trace.log_decision(
+        trace.root_id,
+        "Choose a cache backend",
+        ["In-memory", "Redis"],
+        0,
+        "No external service needed",
+    )
+    trace.log_action(chosen, "Add LRU cache", "Cache hit rate 80%")
+    return trace
+
+
+class TestReasoningTrace:
+    def test_node_ids_are_uuid_strings(self, rlm_core):
+        import uuid
+
+        trace = rlm_core.ReasoningTrace("Goal")
+        chosen = trace.log_decision(trace.root_id, "Pick", ["a", "b"], 1, "b is simpler")
+        action_id, outcome_id = trace.log_action(chosen, "Do b", "Done")
+
+        for node_id in (trace.root_id, chosen, action_id, outcome_id):
+            uuid.UUID(node_id)
+
+    def test_invalid_node_id_raises(self, rlm_core):
+        trace = rlm_core.ReasoningTrace("Goal")
+        with pytest.raises(ValueError):
+            trace.log_action("not-a-uuid", "Do it", "Done")
+
+    def test_chosen_index_out_of_range_raises(self, rlm_core):
+        trace = rlm_core.ReasoningTrace("Goal")
+        with pytest.raises(IndexError):
+            trace.log_decision(trace.root_id, "Pick", ["a"], 3, "")
+
+    def test_networkx_json_is_dict(self, trace):
+        graph = trace.to_networkx_json()
+
+        assert isinstance(graph, dict)
+        assert graph["directed"] is True
+        # Goal, decision, two options, action, outcome
+        assert len(graph["nodes"]) == 6
+        assert len(graph["links"]) == trace.edge_count
+
+    def test_networkx_node_link_graph(self, trace):
+        nx = pytest.importorskip("networkx")
+
+        graph = nx.node_link_graph(trace.to_networkx_json())
+        assert graph.number_of_nodes() == trace.node_count
+
+    def test_text_exports(self, trace):
+        assert trace.to_dot().startswith("digraph")
+        assert "Choose a cache backend" in trace.to_mermaid()
+
+    def test_json_round_trip(self, rlm_core, trace):
+        restored = rlm_core.ReasoningTrace.from_json(trace.to_json())
+
+        assert restored.id == trace.id
+        assert restored.root_id == trace.root_id
+        assert restored.node_count == trace.node_count
+        assert json.loads(restored.to_json()) == json.loads(trace.to_json())
+
+
+class TestTraceAnalyzer:
+    def test_analyzer(self, rlm_core, trace):
+        analyzer = rlm_core.TraceAnalyzer(trace)
+
+        assert 0.0 <= analyzer.overall_confidence() <= 1.0
+        assert "Implement caching" in analyzer.narrative()
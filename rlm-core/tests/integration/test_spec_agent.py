@@ -0,0 +1,144 @@
+"""
+Integration tests for the spec agent Python bindings.
+
+Run with: pytest tests/integration/test_spec_agent.py -v
+"""
+
+import asyncio
+
+import pytest
+
+
+NL_INPUT = (
+    "An Order has a list of items. "
+    "The total must be positive. "
+    "When an order is submitted, it should notify the customer."
+)
+
+
+@pytest.fixture(scope="module")
+def rlm_core():
+    """Import rlm_core, failing with build instructions if unavailable."""
+    try:
+        import rlm_core
+    except ImportError:
+        pytest.fail(
+            "rlm_core import failed. Build/install with: maturin develop --features full"
+        )
+    return rlm_core
+
+
+@pytest.fixture
+def agent(rlm_core):
+    return rlm_core.SpecAgent(validate_with_lean=False, validate_with_topos=False)
+
+
+def run(make_coroutine):
+    """Run a binding coroutine; the bindings need a running event loop."""
+
+    async def main():
+        return await make_coroutine()
+
+    return asyncio.run(main())
+
+
+def refined(rlm_core, agent):
+    """Intake NL_INPUT and answer every clarifying question once."""
+    ctx = run(lambda: agent.intake(NL_INPUT))
+    questions = run(lambda: agent.refine(ctx))
+    answers = [rlm_core.Answer.for_question(q, "Yes") for q in questions]
+    run(lambda: agent.refine(ctx, answers))
+    return ctx, questions
+
+
+CATEGORIES = (
+    "Scope",
+    "DataTypes",
+    "Invariants",
+    "Behavior",
+    "EdgeCases",
+    "Performance",
+    "Security",
+)
+
+
+class TestQuestionAnswer:
+    @pytest.mark.parametrize("name", CATEGORIES)
+    def test_question_category_round_trip(self, rlm_core, name):
+        category = getattr(rlm_core.QuestionCategory, name)
+        question = rlm_core.Question(
+            "Q-1",
+            "What happens?",
+            category,
+            rationale="Needed",
+            suggestions=["a", "b"],
+            required=False,
+        )
+
+        data = question.to_dict()
+        assert data["category"] == name
+
+        restored = rlm_core.Question.from_dict(data)
+        assert restored.category == category
+        assert restored.id == "Q-1"
+        assert restored.suggestions == ["a", "b"]
+        assert restored.required is False
+
+    def test_unknown_category_is_rejected(self, rlm_core):
+        data = rlm_core.Question("Q-1", "?", rlm_core.QuestionCategory.Scope).to_dict()
+        data["category"] = "Vibes"
+        with pytest.raises(ValueError):
+            rlm_core.Question.from_dict(data)
+
+    def test_answer_round_trip(self, rlm_core):
+        question = rlm_core.Question("Q-7", "Bounds?", rlm_core.QuestionCategory.Invariants)
+        answer = rlm_core.Answer.for_question(question, "Between 1 and 10", notes="inclusive")
+
+        restored = rlm_core.Answer.from_dict(answer.to_dict())
+        assert restored.question_id == "Q-7"
+        assert restored.text == "Between 1 and 10"
+        assert restored.notes == "inclusive"
+
+
+class TestSpecAgent:
+    def test_intake_extracts_requirements(self, agent):
+        ctx = run(lambda: agent.intake(NL_INPUT))
+
+        assert ctx.nl_input == NL_INPUT
+        assert len(ctx.requirements) >= 3
+        assert all(isinstance(r, dict) for r in ctx.requirements)
+
+    def test_refine_questions_carry_categories(self, rlm_core, agent):
+        ctx = run(lambda: agent.intake(NL_INPUT))
+        questions = run(lambda: agent.refine(ctx))
+
+        assert questions
+        assert all(isinstance(q.category, rlm_core.QuestionCategory) for q in questions)
+        assert {q.id for q in ctx.questions} == {q.id for q in questions}
+
+    def test_answers_update_context_in_place(self, rlm_core, agent):
+        before = len(run(lambda: agent.intake(NL_INPUT)).requirements)
+        ctx, questions = refined(rlm_core, agent)
+
+        assert {a.question_id for a in ctx.answers} == {q.id for q in questions}
+        assert len(ctx.requirements) > before
+        assert ctx.all_required_answered()
+        assert ctx.phase == "Formalize"
+
+    def test_context_dict_round_trip(self, rlm_core, agent):
+        ctx = run(lambda: agent.intake(NL_INPUT))
+        run(lambda: agent.refine(ctx))
+
+        restored = rlm_core.SpecContext.from_dict(ctx.to_dict())
+        assert [q.category for q in restored.questions] == [
+            q.category for q in ctx.questions
+        ]
+        assert restored.to_dict() == ctx.to_dict()
+
+    def test_formalize_produces_specs(self, rlm_core, agent):
+        ctx, _ = refined(rlm_core, agent)
+        result = run(lambda: agent.formalize(ctx))
+
+        assert result.topos_content
+        assert result.lean_content
+        assert result.lean_filename.endswith(".lean")